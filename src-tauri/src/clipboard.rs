@@ -4,18 +4,257 @@ use anyhow::Result;
 use arboard::{Clipboard, ImageData};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use image::ImageEncoder;
+use once_cell::sync::{Lazy, OnceCell};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 use tauri::Emitter;
 use uuid::Uuid;
 
+use crate::clipboard_ignore::{self, CATEGORY_TTLS, CONTENT_FILTER, IGNORE_LIST};
+use crate::clipboard_ocr;
+use crate::plugin::sandbox::SandboxManager;
+use crate::session_context::SESSION_CONTEXT;
 use crate::storage::clipboard_db::{ClipboardDatabase, ClipboardRecord};
 use crate::utils::paths;
 
+/// 暂停记录剪贴板变化（划词捕获临时借用剪贴板时使用）
+static CAPTURE_SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否暂停记录剪贴板变化
+pub fn set_capture_suppressed(suppressed: bool) {
+    CAPTURE_SUPPRESSED.store(suppressed, Ordering::SeqCst);
+}
+
+/// iLauncher 自身最近一次写入后的剪贴板序列号（监控线程据此跳过，避免重复记录）
+static OWN_WRITE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// 无序列号平台：自身最近一次写入文本的哈希
+static OWN_WRITE_HASH: AtomicU64 = AtomicU64::new(0);
+
+/// 正在进行的自身写入数量（写入完成前监控线程不处理变化）
+static OWN_WRITES_IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+
+/// 以 iLauncher 自身身份写入文本（不会被剪贴板历史重新记录）
+pub fn write_text(text: &str) -> Result<()> {
+    write_own(
+        || {
+            Clipboard::new()?.set_text(text)?;
+            Ok(())
+        },
+        Some(text),
+    )
+}
+
+/// 以 iLauncher 自身身份写入敏感内容（密码、TOTP 等）
+///
+/// 来源在设置中开启自动清除时，延迟到期后若剪贴板仍是该内容（期间没有复制其他内容）则清空剪贴板
+pub fn write_secret(text: &str, source: SecretSource) -> Result<()> {
+    write_text(text)?;
+
+    let Some(delay) = SECRET_CLEAR.read().delay_for(source) else {
+        return Ok(());
+    };
+    let generation = SECRET_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let sequence = platform::sequence_number();
+    let hash = text_hash(text);
+
+    thread::Builder::new()
+        .name("clipboard-secret-clear".to_string())
+        .spawn(move || {
+            thread::sleep(delay);
+            // 之后又复制了敏感内容，由新的计时负责清除
+            if SECRET_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if !holds_secret(sequence, hash) {
+                tracing::debug!("🔐 Clipboard changed since secret copy, skip auto-clear");
+                return;
+            }
+            let cleared = write_own(
+                || {
+                    Clipboard::new()?.clear()?;
+                    Ok(())
+                },
+                None,
+            );
+            match cleared {
+                Ok(()) => tracing::info!("🔐 Cleared copied secret ({:?}) after {}s", source, delay.as_secs()),
+                Err(e) => tracing::warn!("Failed to clear copied secret: {}", e),
+            }
+        })?;
+    Ok(())
+}
+
+/// 剪贴板是否仍是之前写入的敏感内容（有序列号的平台比较序列号，否则比较内容哈希）
+fn holds_secret(sequence: Option<u64>, hash: u64) -> bool {
+    match sequence {
+        Some(sequence) => platform::sequence_number() == Some(sequence),
+        None => Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map(|text| text_hash(&text) == hash)
+            .unwrap_or(false),
+    }
+}
+
+/// 执行自身写入并记录写入后的序列号
+fn write_own(write: impl FnOnce() -> Result<()>, text: Option<&str>) -> Result<()> {
+    OWN_WRITES_IN_PROGRESS.fetch_add(1, Ordering::SeqCst);
+    if let Some(text) = text {
+        OWN_WRITE_HASH.store(text_hash(text), Ordering::SeqCst);
+    }
+    let result = write();
+    if let Some(sequence) = platform::sequence_number() {
+        OWN_WRITE_SEQUENCE.store(sequence, Ordering::SeqCst);
+    }
+    OWN_WRITES_IN_PROGRESS.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+/// 剪贴板内容快照（划词捕获临时借用剪贴板后恢复原内容）
+pub enum ClipboardSnapshot {
+    Files(Vec<PathBuf>),
+    Image(ImageData<'static>),
+    Html { html: String, text: Option<String> },
+    Text(String),
+}
+
+impl ClipboardSnapshot {
+    /// 读取当前剪贴板（按文件列表、图片、富文本、纯文本的优先级），为空时返回 None
+    pub fn take(clipboard: &mut Clipboard) -> Option<Self> {
+        if let Ok(files) = clipboard.get().file_list() {
+            if !files.is_empty() {
+                return Some(Self::Files(files));
+            }
+        }
+        if let Ok(image) = clipboard.get_image() {
+            return Some(Self::Image(image));
+        }
+        let text = clipboard.get_text().ok();
+        if let Ok(html) = clipboard.get().html() {
+            return Some(Self::Html { html, text });
+        }
+        text.map(Self::Text)
+    }
+
+    /// 以 iLauncher 自身身份写回（不会被剪贴板历史重新记录）
+    pub fn restore(self) -> Result<()> {
+        let text = match &self {
+            Self::Text(text) => Some(text.clone()),
+            Self::Html { text, .. } => text.clone(),
+            _ => None,
+        };
+        write_own(
+            move || {
+                let mut clipboard = Clipboard::new()?;
+                match self {
+                    Self::Files(files) => clipboard.set().file_list(&files)?,
+                    Self::Image(image) => clipboard.set_image(image)?,
+                    Self::Html { html, text } => clipboard.set_html(html, text)?,
+                    Self::Text(text) => clipboard.set_text(text)?,
+                }
+                Ok(())
+            },
+            text.as_deref(),
+        )
+    }
+}
+
+fn text_hash(text: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 分类过期检查间隔
+const EXPIRE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// 插件配置中的字段名：敏感内容自动清除延迟（秒，0 表示不清除）
+pub const SECRET_CLEAR_SECONDS_KEY: &str = "secret_clear_seconds";
+
+/// 默认自动清除延迟（秒）
+pub const DEFAULT_SECRET_CLEAR_SECONDS: u64 = 30;
+
+/// 敏感内容来源（每个来源可在剪贴板插件设置中单独开启自动清除）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretSource {
+    /// 密码管理器插件
+    PasswordManager,
+    /// TOTP 验证码
+    Totp,
+    /// 标记为敏感的文本片段
+    Snippet,
+}
+
+impl SecretSource {
+    pub const ALL: [SecretSource; 3] = [Self::PasswordManager, Self::Totp, Self::Snippet];
+
+    /// 插件配置中的开关字段名
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::PasswordManager => "secret_clear_password_manager",
+            Self::Totp => "secret_clear_totp",
+            Self::Snippet => "secret_clear_snippets",
+        }
+    }
+}
+
+/// 敏感内容自动清除设置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretClearSettings {
+    pub delay_secs: u64,
+    pub sources: Vec<SecretSource>,
+}
+
+impl Default for SecretClearSettings {
+    fn default() -> Self {
+        Self {
+            delay_secs: DEFAULT_SECRET_CLEAR_SECONDS,
+            sources: SecretSource::ALL.to_vec(),
+        }
+    }
+}
+
+impl SecretClearSettings {
+    /// 从插件配置读取（缺失的开关默认开启）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        let delay_secs = config
+            .get(SECRET_CLEAR_SECONDS_KEY)
+            .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+            .unwrap_or(DEFAULT_SECRET_CLEAR_SECONDS);
+        let sources = SecretSource::ALL
+            .into_iter()
+            .filter(|source| config.get(source.config_key()).and_then(|v| v.as_bool()).unwrap_or(true))
+            .collect();
+        Self { delay_secs, sources }
+    }
+
+    /// 来源开启自动清除时返回延迟
+    fn delay_for(&self, source: SecretSource) -> Option<Duration> {
+        (self.delay_secs > 0 && self.sources.contains(&source)).then_some(Duration::from_secs(self.delay_secs))
+    }
+}
+
+/// 全局敏感内容清除设置（剪贴板插件设置保存时更新）
+static SECRET_CLEAR: Lazy<RwLock<SecretClearSettings>> = Lazy::new(|| RwLock::new(SecretClearSettings::default()));
+
+/// 敏感内容写入计数（只有最近一次写入的计时会清除剪贴板）
+static SECRET_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 更新敏感内容自动清除设置
+pub fn configure_secret_clear(config: &serde_json::Value) {
+    let settings = SecretClearSettings::from_plugin_config(config);
+    tracing::info!("🔐 Secret auto-clear: {}s for {:?}", settings.delay_secs, settings.sources);
+    *SECRET_CLEAR.write() = settings;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardItem {
     pub id: String,
@@ -33,6 +272,10 @@ pub struct ClipboardItem {
     pub category: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ocr_text: Option<String>, // 图片中识别出的文字
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned_slot: Option<u8>, // 快速粘贴槽位（1-9）
 }
 
 impl From<ClipboardRecord> for ClipboardItem {
@@ -47,6 +290,8 @@ impl From<ClipboardRecord> for ClipboardItem {
             file_path: record.file_path,
             category: record.category,
             tags: if record.tags.is_empty() { None } else { Some(record.tags) },
+            ocr_text: record.ocr_text.filter(|text| !text.is_empty()),
+            pinned_slot: record.pinned_slot,
         }
     }
 }
@@ -55,6 +300,7 @@ pub struct ClipboardManager {
     db: Arc<ClipboardDatabase>,
     image_dir: PathBuf, // 图片存储目录
     monitoring: Arc<RwLock<bool>>, // 监控状态
+    sandbox: Arc<OnceCell<Arc<SandboxManager>>>, // 审计（插件管理器创建后绑定）
 }
 
 impl ClipboardManager {
@@ -72,14 +318,21 @@ impl ClipboardManager {
             db: Arc::new(db),
             image_dir,
             monitoring: Arc::new(RwLock::new(false)),
+            sandbox: Arc::new(OnceCell::new()),
         })
     }
 
-    /// 启动剪贴板监控
+    /// 绑定沙盒管理器（忽略列表跳过记录时写入审计日志）
+    pub fn attach_sandbox(&self, sandbox: Arc<SandboxManager>) {
+        let _ = self.sandbox.set(sandbox);
+    }
+
+    /// 启动剪贴板监控（Windows 基于 WM_CLIPBOARDUPDATE 事件，其他平台自适应退避轮询）
     pub fn start_monitoring(&self, app_handle: tauri::AppHandle) {
         let db = self.db.clone();
         let image_dir = self.image_dir.clone();
         let monitoring = self.monitoring.clone();
+        let sandbox = self.sandbox.clone();
         
         // 设置监控状态
         *monitoring.write() = true;
@@ -93,13 +346,23 @@ impl ClipboardManager {
                 }
             };
             
+            let mut watcher = match platform::ChangeWatcher::new() {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    tracing::error!("Failed to create clipboard watcher: {}", e);
+                    return;
+                }
+            };
+            
+            let mut last_sequence = platform::sequence_number();
             let mut last_text = String::new();
             let mut last_image_hash: Option<u64> = None;
+            let mut last_expire_check: Option<std::time::Instant> = None;
             
-            tracing::info!("Clipboard monitoring started (enhanced mode)");
+            tracing::info!("Clipboard monitoring started (event-driven: {})", platform::EVENT_DRIVEN);
             
             loop {
-                thread::sleep(Duration::from_millis(500));
+                watcher.wait();
                 
                 // 检查是否应该停止监控
                 if !*monitoring.read() {
@@ -107,9 +370,95 @@ impl ClipboardManager {
                     break;
                 }
                 
+                // ⏳ 按分类清理过期记录
+                let expire_due = match last_expire_check {
+                    Some(checked_at) => checked_at.elapsed() >= EXPIRE_CHECK_INTERVAL,
+                    None => true,
+                };
+                if expire_due {
+                    last_expire_check = Some(std::time::Instant::now());
+                    if Self::expire_categories(&db) {
+                        let _ = app_handle.emit("clipboard:updated", ());
+                        if let Ok((total, _, _, _)) = db.get_stats() {
+                            SESSION_CONTEXT.set_clipboard_count(total);
+                        }
+                    }
+                }
+                
+                // 有序列号的平台：序列号未变说明内容没变，无需读取剪贴板
+                let sequence = platform::sequence_number();
+                if sequence.is_some() && sequence == last_sequence {
+                    watcher.adjust(false);
+                    continue;
+                }
+                
+                // 自身写入尚未完成，等下次唤醒再处理（不更新序列号）
+                if OWN_WRITES_IN_PROGRESS.load(Ordering::SeqCst) > 0 {
+                    continue;
+                }
+                last_sequence = sequence;
+                
+                // 划词捕获期间不记录
+                if CAPTURE_SUPPRESSED.load(Ordering::SeqCst) {
+                    continue;
+                }
+                
+                // iLauncher 自身写入的内容不重新记录
+                if sequence.is_some() && sequence == Some(OWN_WRITE_SEQUENCE.load(Ordering::SeqCst)) {
+                    if let Ok(text) = clipboard.get_text() {
+                        last_text = text;
+                    }
+                    continue;
+                }
+                
+                // 🔒 来自忽略列表中应用（密码管理器等）的内容不记录
+                if let Some(app) = clipboard_ignore::source_app() {
+                    if let Some(rule) = IGNORE_LIST.read().matching_rule(&app) {
+                        tracing::info!("📋 Clipboard capture skipped: {} matches ignore rule '{}'", app.name, rule);
+                        if let Some(sandbox) = sandbox.get() {
+                            sandbox.record_clipboard_skip(&app.name, rule);
+                        }
+                        // 记住当前内容，避免所有者变化后被补记
+                        if let Ok(text) = clipboard.get_text() {
+                            last_text = text;
+                        }
+                        if let Ok(image) = clipboard.get_image() {
+                            last_image_hash = Some(Self::hash_image(&image));
+                        }
+                        watcher.adjust(true);
+                        continue;
+                    }
+                }
+                
+                let mut changed = false;
+                
                 // 检查文本剪贴板
                 if let Ok(text) = clipboard.get_text() {
+                    if text != last_text && sequence.is_none() && text_hash(&text) == OWN_WRITE_HASH.load(Ordering::SeqCst) {
+                        // 无序列号平台按内容识别自身写入（只跳过一次）
+                        OWN_WRITE_HASH.store(0, Ordering::SeqCst);
+                        last_text = text;
+                        watcher.adjust(true);
+                        continue;
+                    }
+                    
+                    // 🔒 命中内容过滤规则（银行卡号、正则黑名单）的文本不记录
+                    if text != last_text && !text.is_empty() {
+                        if let Some(rule) = CONTENT_FILTER.read().matching_rule(&text) {
+                            tracing::info!("📋 Clipboard capture skipped: content matches {}", rule);
+                            if let Some(sandbox) = sandbox.get() {
+                                let source = clipboard_ignore::source_app().map(|app| app.name).unwrap_or_default();
+                                sandbox.record_clipboard_skip(&source, &rule);
+                            }
+                            last_text = text;
+                            watcher.adjust(true);
+                            continue;
+                        }
+                    }
+                    
                     if text != last_text && !text.is_empty() && text.len() < 100_000 {
+                        changed = true;
+                        
                         // 限制文本长度 100KB
                         let preview = if text.len() > 200 {
                             Some(format!("{}...", &text[..200]))
@@ -124,6 +473,9 @@ impl ClipboardManager {
                                 
                                 // 发送更新事件
                                 let _ = app_handle.emit("clipboard:updated", ());
+                                if let Ok((total, _, _, _)) = db.get_stats() {
+                                    SESSION_CONTEXT.set_clipboard_count(total);
+                                }
                             }
                             Err(e) => {
                                 if !e.to_string().contains("Duplicate") {
@@ -139,6 +491,8 @@ impl ClipboardManager {
                     let image_hash = Self::hash_image(&image);
                     
                     if Some(image_hash) != last_image_hash {
+                        changed = true;
+                        
                         match Self::save_image(&image, &image_dir) {
                             Ok((base64_data, file_path)) => {
                                 let preview = Some(format!(
@@ -159,8 +513,16 @@ impl ClipboardManager {
                                             id, image.width, image.height);
                                         last_image_hash = Some(image_hash);
                                         
+                                        // 🔎 后台识别图片文字
+                                        if clipboard_ocr::is_enabled() {
+                                            clipboard_ocr::enqueue(db.clone(), id, file_path.clone());
+                                        }
+                                        
                                         // 发送更新事件
                                         let _ = app_handle.emit("clipboard:updated", ());
+                                        if let Ok((total, _, _, _)) = db.get_stats() {
+                                            SESSION_CONTEXT.set_clipboard_count(total);
+                                        }
                                     }
                                     Err(e) => {
                                         if !e.to_string().contains("Duplicate") {
@@ -175,10 +537,50 @@ impl ClipboardManager {
                         }
                     }
                 }
+                
+                watcher.adjust(changed || sequence.is_some());
             }
         });
     }
 
+    /// 删除超过分类过期时间的记录（收藏除外），返回是否有记录被删除
+    fn expire_categories(db: &ClipboardDatabase) -> bool {
+        let ttls = CATEGORY_TTLS.read().clone();
+        let mut expired = false;
+        for (category, ttl) in ttls {
+            let cutoff = chrono::Local::now().timestamp() - ttl.as_secs() as i64;
+            let category = (category != clipboard_ignore::UNCATEGORIZED).then_some(category.as_str());
+            match db.expire_category(category, cutoff) {
+                Ok((deleted, file_paths)) => {
+                    for path in file_paths {
+                        let _ = std::fs::remove_file(path);
+                    }
+                    expired |= deleted > 0;
+                }
+                Err(e) => tracing::warn!("Failed to expire clipboard category {:?}: {}", category, e),
+            }
+        }
+        expired
+    }
+
+    /// 为尚未识别的历史图片补做 OCR（启用 OCR 时调用）
+    pub fn backfill_ocr(&self) {
+        if !clipboard_ocr::is_enabled() {
+            return;
+        }
+        match self.db.pending_ocr_images(clipboard_ocr::BACKFILL_LIMIT) {
+            Ok(pending) => {
+                if !pending.is_empty() {
+                    tracing::info!("🔎 Queued {} clipboard images for OCR", pending.len());
+                }
+                for (id, path) in pending {
+                    clipboard_ocr::enqueue(self.db.clone(), id, PathBuf::from(path));
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load images pending OCR: {}", e),
+        }
+    }
+
     /// 停止监控
     pub fn stop_monitoring(&self) {
         *self.monitoring.write() = false;
@@ -252,6 +654,10 @@ impl ClipboardManager {
 
     /// 复制到剪贴板
     pub fn copy_to_clipboard(&self, content: &str, content_type: &str) -> Result<()> {
+        write_own(|| self.set_clipboard_content(content, content_type), Some(content))
+    }
+
+    fn set_clipboard_content(&self, content: &str, content_type: &str) -> Result<()> {
         let mut clipboard = Clipboard::new()
             .map_err(|e| anyhow::anyhow!("Failed to access clipboard: {}", e))?;
         
@@ -301,6 +707,23 @@ impl ClipboardManager {
         self.db.add_tag(id_num, tag)
     }
 
+    /// 固定到快速粘贴槽位（1-9，None 取消固定）
+    pub fn set_pinned_slot(&self, id: &str, slot: Option<u8>) -> Result<()> {
+        let id_num: i64 = id.parse()?;
+        self.db.set_pinned_slot(id_num, slot)
+    }
+
+    /// 获取固定在槽位中的条目
+    pub fn get_pinned(&self) -> Result<Vec<ClipboardItem>> {
+        let records = self.db.get_pinned()?;
+        Ok(records.into_iter().map(ClipboardItem::from).collect())
+    }
+
+    /// 获取某个槽位的条目
+    pub fn get_pinned_slot(&self, slot: u8) -> Result<Option<ClipboardItem>> {
+        Ok(self.db.get_pinned_slot(slot)?.map(ClipboardItem::from))
+    }
+
     /// 删除记录
     pub fn delete_item(&self, id: &str) -> Result<()> {
         let id_num: i64 = id.parse()?;
@@ -315,7 +738,9 @@ impl ClipboardManager {
             }
         }
         
-        self.db.delete_record(id_num)
+        self.db.delete_record(id_num)?;
+        self.refresh_session_count();
+        Ok(())
     }
 
     /// 清空历史记录
@@ -328,11 +753,201 @@ impl ClipboardManager {
         
         // 清空数据库（保留收藏）
         self.db.cleanup_old_records(0)?;
+        self.refresh_session_count();
         Ok(())
     }
 
+    /// 重置剪贴板存储：删除全部记录（包括收藏和固定记录）和图片文件
+    pub fn reset(&self) -> Result<usize> {
+        if self.image_dir.exists() {
+            std::fs::remove_dir_all(&self.image_dir)?;
+            std::fs::create_dir_all(&self.image_dir)?;
+        }
+        let deleted = self.db.clear_all()?;
+        self.refresh_session_count();
+        Ok(deleted)
+    }
+
+    /// 同步剪贴板条目数量到会话上下文
+    fn refresh_session_count(&self) {
+        if let Ok((total, _, _, _)) = self.db.get_stats() {
+            SESSION_CONTEXT.set_clipboard_count(total);
+        }
+    }
+
     /// 获取统计信息
     pub fn get_stats(&self) -> Result<(usize, usize, usize, usize)> {
         self.db.get_stats()
     }
 }
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use anyhow::Result;
+    use std::time::Duration;
+    use windows::core::w;
+    use windows::Win32::Foundation::{HINSTANCE, HWND};
+    use windows::Win32::System::DataExchange::{
+        AddClipboardFormatListener, GetClipboardSequenceNumber, RemoveClipboardFormatListener,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DestroyWindow, DispatchMessageW, MsgWaitForMultipleObjects, PeekMessageW, TranslateMessage,
+        HMENU, HWND_MESSAGE, MSG, PM_REMOVE, QS_ALLINPUT, WINDOW_EX_STYLE, WINDOW_STYLE,
+    };
+
+    pub const EVENT_DRIVEN: bool = true;
+
+    /// 兜底唤醒间隔（自身写入进行中时需要再次检查序列号）
+    const WAKE_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn sequence_number() -> Option<u64> {
+        Some(unsafe { GetClipboardSequenceNumber() } as u64)
+    }
+
+    /// 消息窗口 + AddClipboardFormatListener，剪贴板变化时收到 WM_CLIPBOARDUPDATE
+    pub struct ChangeWatcher {
+        hwnd: HWND,
+    }
+
+    impl ChangeWatcher {
+        pub fn new() -> Result<Self> {
+            unsafe {
+                let hwnd = CreateWindowExW(
+                    WINDOW_EX_STYLE(0),
+                    w!("STATIC"),
+                    w!("iLauncherClipboardListener"),
+                    WINDOW_STYLE(0),
+                    0,
+                    0,
+                    0,
+                    0,
+                    HWND_MESSAGE,
+                    HMENU::default(),
+                    HINSTANCE::default(),
+                    None,
+                )?;
+                if let Err(e) = AddClipboardFormatListener(hwnd) {
+                    let _ = DestroyWindow(hwnd);
+                    return Err(e.into());
+                }
+                Ok(Self { hwnd })
+            }
+        }
+
+        /// 阻塞直到收到消息（剪贴板变化）或超时
+        pub fn wait(&mut self) {
+            unsafe {
+                MsgWaitForMultipleObjects(None, false, WAKE_INTERVAL.as_millis() as u32, QS_ALLINPUT);
+                let mut msg = MSG::default();
+                while PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_REMOVE).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+        }
+
+        /// 事件驱动无需退避
+        pub fn adjust(&mut self, _changed: bool) {}
+    }
+
+    impl Drop for ChangeWatcher {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = RemoveClipboardFormatListener(self.hwnd);
+                let _ = DestroyWindow(self.hwnd);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use anyhow::Result;
+    use std::time::Duration;
+
+    pub const EVENT_DRIVEN: bool = false;
+
+    /// 轮询间隔：有变化时回到最短间隔，空闲时逐步退避
+    const MIN_INTERVAL: Duration = Duration::from_millis(100);
+    const MAX_INTERVAL: Duration = Duration::from_millis(1000);
+
+    /// macOS：NSPasteboard changeCount（读取开销极低）
+    #[cfg(target_os = "macos")]
+    pub fn sequence_number() -> Option<u64> {
+        use objc2_app_kit::NSPasteboard;
+        let count = unsafe { NSPasteboard::generalPasteboard().changeCount() };
+        Some(count as u64)
+    }
+
+    /// 其他平台没有变化计数，由调用方比较内容
+    #[cfg(not(target_os = "macos"))]
+    pub fn sequence_number() -> Option<u64> {
+        None
+    }
+
+    pub struct ChangeWatcher {
+        interval: Duration,
+    }
+
+    impl ChangeWatcher {
+        pub fn new() -> Result<Self> {
+            Ok(Self {
+                interval: MIN_INTERVAL,
+            })
+        }
+
+        pub fn wait(&mut self) {
+            std::thread::sleep(self.interval);
+        }
+
+        pub fn adjust(&mut self, changed: bool) {
+            self.interval = next_interval(self.interval, changed);
+        }
+    }
+
+    fn next_interval(current: Duration, changed: bool) -> Duration {
+        if changed {
+            MIN_INTERVAL
+        } else {
+            (current * 2).min(MAX_INTERVAL)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_adaptive_backoff() {
+            let mut interval = MIN_INTERVAL;
+            for _ in 0..10 {
+                interval = next_interval(interval, false);
+            }
+            assert_eq!(interval, MAX_INTERVAL);
+            assert_eq!(next_interval(interval, true), MIN_INTERVAL);
+            assert_eq!(next_interval(MIN_INTERVAL, false), Duration::from_millis(200));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_clear_settings() {
+        let settings = SecretClearSettings::from_plugin_config(&serde_json::json!({}));
+        assert_eq!(settings, SecretClearSettings::default());
+        assert_eq!(settings.delay_for(SecretSource::Totp), Some(Duration::from_secs(DEFAULT_SECRET_CLEAR_SECONDS)));
+
+        let settings = SecretClearSettings::from_plugin_config(&serde_json::json!({
+            SECRET_CLEAR_SECONDS_KEY: "45",
+            "secret_clear_snippets": false,
+        }));
+        assert_eq!(settings.delay_for(SecretSource::PasswordManager), Some(Duration::from_secs(45)));
+        assert_eq!(settings.delay_for(SecretSource::Snippet), None);
+
+        let settings = SecretClearSettings::from_plugin_config(&serde_json::json!({ SECRET_CLEAR_SECONDS_KEY: 0 }));
+        assert_eq!(settings.delay_for(SecretSource::PasswordManager), None);
+    }
+}
@@ -0,0 +1,370 @@
+// 剪贴板忽略列表 - 来自指定应用（密码管理器、网银等）的复制内容不记录到历史
+// 复制发生时取剪贴板所有者（或前台窗口）所属进程，按规则匹配；规则在剪贴板插件设置中配置
+// 另外按内容过滤（银行卡号、正则黑名单），并支持按分类设置自动过期时间
+
+use crate::utils::wildcard::matches as wildcard_match;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 插件配置中的字段名
+pub const IGNORED_APPS_KEY: &str = "ignored_apps";
+/// 插件配置中的字段名：是否跳过银行卡号
+pub const SKIP_CARD_NUMBERS_KEY: &str = "skip_card_numbers";
+/// 插件配置中的字段名：内容正则黑名单（每行一条）
+pub const DENY_PATTERNS_KEY: &str = "deny_patterns";
+/// 插件配置中的字段名：分类自动过期时间（小时），如 "temp=1, work=72"
+pub const CATEGORY_TTL_KEY: &str = "category_ttl";
+
+/// 未分类记录在过期配置中的名称
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// 默认忽略的应用（常见密码管理器）
+pub const DEFAULT_IGNORED_APPS: &[&str] = &[
+    "KeePass",
+    "KeePassXC",
+    "1Password",
+    "Bitwarden",
+    "LastPass",
+    "Dashlane",
+    "Enpass",
+    "RoboForm",
+    "Keeper*",
+    "NordPass",
+];
+
+/// 全局忽略列表（监控线程读取，设置保存时更新）
+pub static IGNORE_LIST: Lazy<RwLock<IgnoreList>> = Lazy::new(|| RwLock::new(IgnoreList::default()));
+
+/// 复制来源应用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceApp {
+    /// 进程名（如 KeePassXC.exe）
+    pub name: String,
+    /// 可执行文件完整路径（可能为空）
+    pub path: String,
+}
+
+/// 应用忽略规则列表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreList {
+    rules: Vec<String>,
+}
+
+impl Default for IgnoreList {
+    fn default() -> Self {
+        Self::new(DEFAULT_IGNORED_APPS.iter().map(|s| s.to_string()))
+    }
+}
+
+impl IgnoreList {
+    pub fn new(rules: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|r| r.trim().to_lowercase())
+                .filter(|r| !r.is_empty())
+                .collect(),
+        }
+    }
+
+    /// 从插件配置读取（逗号/分号/换行分隔的字符串或字符串数组，缺失时使用默认规则）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        match config.get(IGNORED_APPS_KEY) {
+            Some(serde_json::Value::String(text)) => Self::new(text.split([',', ';', '\n']).map(str::to_string)),
+            Some(serde_json::Value::Array(items)) => {
+                Self::new(items.iter().filter_map(|v| v.as_str()).map(str::to_string))
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// 返回命中的规则
+    ///
+    /// 含路径分隔符的规则匹配可执行文件路径，其他规则匹配进程名（忽略 .exe 后缀）；支持 `*` 通配符
+    pub fn matching_rule(&self, app: &SourceApp) -> Option<&str> {
+        let name = app.name.to_lowercase();
+        let stem = name.strip_suffix(".exe").unwrap_or(&name);
+        let path = app.path.to_lowercase().replace('\\', "/");
+
+        self.rules
+            .iter()
+            .find(|rule| {
+                if rule.contains('/') || rule.contains('\\') {
+                    !path.is_empty() && wildcard_match(&rule.replace('\\', "/"), &path)
+                } else {
+                    wildcard_match(rule, &name) || wildcard_match(rule, stem)
+                }
+            })
+            .map(String::as_str)
+    }
+}
+
+/// 银行卡号（13-19 位，允许空格/短横线分隔），命中后再做 Luhn 校验
+static CARD_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap());
+
+/// Luhn 校验
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 1 { if d * 2 > 9 { d * 2 - 9 } else { d * 2 } } else { d })
+        .sum();
+    sum % 10 == 0
+}
+
+/// 文本中是否包含通过 Luhn 校验的卡号
+fn contains_card_number(text: &str) -> bool {
+    CARD_NUMBER_REGEX.find_iter(text).any(|m| {
+        let digits: Vec<u32> = m.as_str().chars().filter_map(|c| c.to_digit(10)).collect();
+        (13..=19).contains(&digits.len()) && luhn_valid(&digits)
+    })
+}
+
+/// 内容过滤规则（银行卡号 + 正则黑名单）
+#[derive(Debug, Clone)]
+pub struct ContentFilter {
+    skip_card_numbers: bool,
+    deny_patterns: Vec<Regex>,
+}
+
+impl Default for ContentFilter {
+    fn default() -> Self {
+        Self {
+            skip_card_numbers: true,
+            deny_patterns: Vec::new(),
+        }
+    }
+}
+
+impl ContentFilter {
+    /// 从插件配置读取（正则为每行一条的字符串或字符串数组，无效正则忽略并记录警告）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        let skip_card_numbers = config
+            .get(SKIP_CARD_NUMBERS_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let patterns: Vec<String> = match config.get(DENY_PATTERNS_KEY) {
+            Some(serde_json::Value::String(text)) => text.lines().map(str::to_string).collect(),
+            Some(serde_json::Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).map(str::to_string).collect(),
+            _ => Vec::new(),
+        };
+        let deny_patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| match Regex::new(p) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!("Invalid clipboard deny pattern '{}': {}", p, e);
+                    None
+                }
+            })
+            .collect();
+        Self { skip_card_numbers, deny_patterns }
+    }
+
+    /// 返回命中的规则描述
+    pub fn matching_rule(&self, text: &str) -> Option<String> {
+        if self.skip_card_numbers && contains_card_number(text) {
+            return Some("card number".to_string());
+        }
+        self.deny_patterns
+            .iter()
+            .find(|regex| regex.is_match(text))
+            .map(|regex| format!("/{}/", regex.as_str()))
+    }
+}
+
+/// 全局内容过滤规则
+pub static CONTENT_FILTER: Lazy<RwLock<ContentFilter>> = Lazy::new(|| RwLock::new(ContentFilter::default()));
+
+/// 分类自动过期时间（键为分类名，未分类记录使用 UNCATEGORIZED）
+pub fn category_ttls_from_config(config: &serde_json::Value) -> HashMap<String, Duration> {
+    let hours = |v: &serde_json::Value| v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()));
+    let entries: Vec<(String, f64)> = match config.get(CATEGORY_TTL_KEY) {
+        Some(serde_json::Value::Object(map)) => map
+            .iter()
+            .filter_map(|(category, value)| Some((category.clone(), hours(value)?)))
+            .collect(),
+        Some(serde_json::Value::String(text)) => text
+            .split([',', ';', '\n'])
+            .filter_map(|entry| {
+                let (category, value) = entry.split_once('=')?;
+                Some((category.to_string(), value.trim().parse().ok()?))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    entries
+        .into_iter()
+        .map(|(category, hours)| (category.trim().to_lowercase(), hours))
+        .filter(|(category, hours)| !category.is_empty() && *hours > 0.0)
+        .map(|(category, hours)| (category, Duration::from_secs_f64(hours * 3600.0)))
+        .collect()
+}
+
+/// 全局分类过期时间
+pub static CATEGORY_TTLS: Lazy<RwLock<HashMap<String, Duration>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 更新全局忽略列表、内容过滤规则与分类过期时间
+pub fn configure(config: &serde_json::Value) {
+    let list = IgnoreList::from_plugin_config(config);
+    let filter = ContentFilter::from_plugin_config(config);
+    let ttls = category_ttls_from_config(config);
+    tracing::info!(
+        "📋 Clipboard ignore list updated: {} rules, {} deny patterns, {} category TTLs",
+        list.rules.len(),
+        filter.deny_patterns.len(),
+        ttls.len()
+    );
+    *IGNORE_LIST.write() = list;
+    *CONTENT_FILTER.write() = filter;
+    *CATEGORY_TTLS.write() = ttls;
+}
+
+/// 获取复制内容的来源应用
+pub fn source_app() -> Option<SourceApp> {
+    platform::source_app()
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::SourceApp;
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{CloseHandle, HWND};
+    use windows::Win32::System::DataExchange::GetClipboardOwner;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    /// 优先取剪贴板所有者窗口，部分应用写入后释放所有权时退回前台窗口
+    pub fn source_app() -> Option<SourceApp> {
+        let hwnd = unsafe { GetClipboardOwner() }
+            .ok()
+            .filter(|hwnd| !hwnd.is_invalid())
+            .unwrap_or_else(|| unsafe { GetForegroundWindow() });
+        if hwnd.is_invalid() {
+            return None;
+        }
+        process_of_window(hwnd)
+    }
+
+    fn process_of_window(hwnd: HWND) -> Option<SourceApp> {
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+        if pid == 0 {
+            return None;
+        }
+
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+            let mut buffer = [0u16; 1024];
+            let mut len = buffer.len() as u32;
+            let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut len);
+            let _ = CloseHandle(process);
+            result.ok()?;
+
+            let path = String::from_utf16_lossy(&buffer[..len as usize]);
+            let name = path.rsplit(['\\', '/']).next().unwrap_or(&path).to_string();
+            Some(SourceApp { name, path })
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::SourceApp;
+    use objc2_app_kit::NSWorkspace;
+
+    /// macOS 剪贴板没有所有者概念，取前台应用
+    pub fn source_app() -> Option<SourceApp> {
+        unsafe {
+            let app = NSWorkspace::sharedWorkspace().frontmostApplication()?;
+            let name = app.localizedName()?.to_string();
+            let path = app
+                .executableURL()
+                .and_then(|url| url.path())
+                .map(|path| path.to_string())
+                .unwrap_or_default();
+            Some(SourceApp { name, path })
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use super::SourceApp;
+
+    pub fn source_app() -> Option<SourceApp> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str, path: &str) -> SourceApp {
+        SourceApp {
+            name: name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_matching_rule() {
+        let list = IgnoreList::new(["KeePassXC".to_string(), "bank*".to_string(), "C:\\Tools\\Vault\\*".to_string()]);
+
+        assert_eq!(list.matching_rule(&app("KeePassXC.exe", "")), Some("keepassxc"));
+        assert_eq!(list.matching_rule(&app("BankingClient.exe", "")), Some("bank*"));
+        assert_eq!(list.matching_rule(&app("vault.exe", "C:\\Tools\\Vault\\vault.exe")), Some("c:\\tools\\vault\\*"));
+        assert_eq!(list.matching_rule(&app("notepad.exe", "C:\\Windows\\notepad.exe")), None);
+        // 进程名需完整匹配
+        assert_eq!(list.matching_rule(&app("KeePassXC-Helper.exe", "")), None);
+    }
+
+    #[test]
+    fn test_from_plugin_config() {
+        let list = IgnoreList::from_plugin_config(&serde_json::json!({ "ignored_apps": "Foo, Bar;\n" }));
+        assert_eq!(list.rules, vec!["foo", "bar"]);
+
+        let list = IgnoreList::from_plugin_config(&serde_json::json!({ "ignored_apps": ["Baz"] }));
+        assert_eq!(list.rules, vec!["baz"]);
+
+        assert_eq!(IgnoreList::from_plugin_config(&serde_json::json!({})), IgnoreList::default());
+    }
+
+    #[test]
+    fn test_content_filter() {
+        let filter = ContentFilter::from_plugin_config(&serde_json::json!({
+            DENY_PATTERNS_KEY: "^sk-[A-Za-z0-9]{8,}$\n(invalid\n",
+        }));
+        assert_eq!(filter.deny_patterns.len(), 1);
+
+        // Visa 测试卡号（通过 Luhn 校验）
+        assert_eq!(filter.matching_rule("card: 4111 1111 1111 1111").as_deref(), Some("card number"));
+        assert_eq!(filter.matching_rule("order 4111-1111-1111-1112"), None);
+        assert_eq!(filter.matching_rule("sk-abcdef123456").as_deref(), Some("/^sk-[A-Za-z0-9]{8,}$/"));
+        assert_eq!(filter.matching_rule("hello world"), None);
+
+        let filter = ContentFilter::from_plugin_config(&serde_json::json!({ SKIP_CARD_NUMBERS_KEY: false }));
+        assert_eq!(filter.matching_rule("4111111111111111"), None);
+    }
+
+    #[test]
+    fn test_category_ttls() {
+        let ttls = category_ttls_from_config(&serde_json::json!({ CATEGORY_TTL_KEY: "Temp=1, work = 0.5; bad, zero=0" }));
+        assert_eq!(ttls.len(), 2);
+        assert_eq!(ttls["temp"], Duration::from_secs(3600));
+        assert_eq!(ttls["work"], Duration::from_secs(1800));
+
+        let ttls = category_ttls_from_config(&serde_json::json!({ CATEGORY_TTL_KEY: { "uncategorized": 24 } }));
+        assert_eq!(ttls[UNCATEGORIZED], Duration::from_secs(86400));
+    }
+}
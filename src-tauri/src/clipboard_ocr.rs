@@ -0,0 +1,255 @@
+// 剪贴板图片 OCR - 在后台提取截图中的文字并写入剪贴板数据库，供 search_clipboard 匹配
+// Windows 使用 Windows.Media.Ocr，失败或其他平台回退到 tesseract 命令行；默认关闭，在剪贴板插件设置中开启
+
+use crate::storage::clipboard_db::ClipboardDatabase;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+
+/// 插件配置中的字段名：是否启用 OCR
+pub const OCR_ENABLED_KEY: &str = "ocr_enabled";
+/// 插件配置中的字段名：tesseract 语言（如 eng、chi_sim+eng）
+pub const OCR_LANGUAGE_KEY: &str = "ocr_language";
+
+/// 默认 tesseract 语言
+pub const DEFAULT_OCR_LANGUAGE: &str = "eng";
+
+/// 启用后一次补做 OCR 的历史图片数量上限
+pub const BACKFILL_LIMIT: usize = 200;
+
+/// OCR 设置（剪贴板插件设置保存时更新）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrSettings {
+    pub enabled: bool,
+    pub language: String,
+}
+
+impl Default for OcrSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: DEFAULT_OCR_LANGUAGE.to_string(),
+        }
+    }
+}
+
+impl OcrSettings {
+    /// 从插件配置读取（缺失时关闭）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        let enabled = config.get(OCR_ENABLED_KEY).and_then(|v| v.as_bool()).unwrap_or(false);
+        let language = config
+            .get(OCR_LANGUAGE_KEY)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(DEFAULT_OCR_LANGUAGE)
+            .to_string();
+        Self { enabled, language }
+    }
+}
+
+/// 全局 OCR 设置
+static OCR_SETTINGS: Lazy<RwLock<OcrSettings>> = Lazy::new(|| RwLock::new(OcrSettings::default()));
+
+/// 更新 OCR 设置，返回是否从关闭切换为开启（调用方据此补做历史图片）
+pub fn configure(config: &serde_json::Value) -> bool {
+    let settings = OcrSettings::from_plugin_config(config);
+    let mut current = OCR_SETTINGS.write();
+    let turned_on = settings.enabled && !current.enabled;
+    if *current != settings {
+        tracing::info!("🔎 Clipboard OCR {} (language: {})", if settings.enabled { "enabled" } else { "disabled" }, settings.language);
+    }
+    *current = settings;
+    turned_on
+}
+
+/// 是否启用 OCR
+pub fn is_enabled() -> bool {
+    OCR_SETTINGS.read().enabled
+}
+
+/// OCR 任务
+struct OcrJob {
+    db: Arc<ClipboardDatabase>,
+    id: i64,
+    path: PathBuf,
+}
+
+/// 单线程 OCR 队列（识别耗 CPU，按顺序处理，不阻塞剪贴板监控线程）
+static OCR_QUEUE: Lazy<Mutex<Option<Sender<OcrJob>>>> = Lazy::new(|| Mutex::new(None));
+
+/// 把图片加入 OCR 队列（首次调用时启动工作线程）
+pub fn enqueue(db: Arc<ClipboardDatabase>, id: i64, path: PathBuf) {
+    let mut queue = OCR_QUEUE.lock();
+    let sender = queue.get_or_insert_with(|| {
+        let (sender, receiver) = mpsc::channel::<OcrJob>();
+        std::thread::Builder::new()
+            .name("clipboard-ocr".to_string())
+            .spawn(move || {
+                platform::init_thread();
+                for job in receiver {
+                    process(job);
+                }
+            })
+            .expect("Failed to spawn clipboard OCR thread");
+        sender
+    });
+
+    if sender.send(OcrJob { db, id, path }).is_err() {
+        tracing::warn!("Clipboard OCR worker is not running");
+        *queue = None;
+    }
+}
+
+/// 识别并写入数据库（识别不到文字时写入空字符串，避免重复处理）
+fn process(job: OcrJob) {
+    // 排队期间关闭了 OCR
+    if !is_enabled() {
+        return;
+    }
+
+    let language = OCR_SETTINGS.read().language.clone();
+    let start = std::time::Instant::now();
+    let text = match recognize(&job.path, &language) {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::warn!("Clipboard OCR failed for #{}: {}", job.id, e);
+            String::new()
+        }
+    };
+
+    match job.db.set_ocr_text(job.id, &text) {
+        Ok(()) => tracing::debug!(
+            "🔎 OCR #{}: {} chars in {:.0}ms",
+            job.id,
+            text.chars().count(),
+            start.elapsed().as_secs_f64() * 1000.0
+        ),
+        Err(e) => tracing::warn!("Failed to store OCR text for #{}: {}", job.id, e),
+    }
+}
+
+/// 识别图片中的文字（Windows 优先使用系统 OCR）
+pub fn recognize(path: &Path, language: &str) -> Result<String> {
+    if !path.exists() {
+        return Err(anyhow!("Image not found: {}", path.display()));
+    }
+
+    let raw = match platform::recognize(path) {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::debug!("System OCR unavailable ({}), falling back to tesseract", e);
+            recognize_tesseract(path, language)?
+        }
+    };
+    Ok(normalize_text(&raw))
+}
+
+/// 调用 tesseract 命令行（需要用户自行安装并加入 PATH）
+fn recognize_tesseract(path: &Path, language: &str) -> Result<String> {
+    let mut command = std::process::Command::new("tesseract");
+    command.arg(path).arg("stdout").arg("-l").arg(language);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| anyhow!("tesseract is not available: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("tesseract failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 合并空白、去掉空行，便于 LIKE 匹配
+fn normalize_text(raw: &str) -> String {
+    raw.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use anyhow::Result;
+    use std::path::Path;
+    use windows::core::HSTRING;
+    use windows::Graphics::Imaging::BitmapDecoder;
+    use windows::Media::Ocr::OcrEngine;
+    use windows::Storage::{FileAccessMode, StorageFile};
+    use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+
+    /// WinRT 调用前初始化 COM（工作线程常驻，不反初始化）
+    pub fn init_thread() {
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        }
+    }
+
+    /// Windows.Media.Ocr（使用用户配置的系统语言）
+    pub fn recognize(path: &Path) -> Result<String> {
+        let path = HSTRING::from(path.to_string_lossy().as_ref());
+        let file = StorageFile::GetFileFromPathAsync(&path)?.get()?;
+        let stream = file.OpenAsync(FileAccessMode::Read)?.get()?;
+        let decoder = BitmapDecoder::CreateAsync(&stream)?.get()?;
+        let bitmap = decoder.GetSoftwareBitmapAsync()?.get()?;
+
+        let engine = OcrEngine::TryCreateFromUserProfileLanguages()?;
+        let result = engine.RecognizeAsync(&bitmap)?.get()?;
+
+        // 按行拼接（Text 以空格连接所有行）
+        let lines = result.Lines()?;
+        let mut text = Vec::with_capacity(lines.Size()? as usize);
+        for line in lines {
+            text.push(line.Text()?.to_string());
+        }
+        Ok(text.join("\n"))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use anyhow::{anyhow, Result};
+    use std::path::Path;
+
+    pub fn init_thread() {}
+
+    pub fn recognize(_path: &Path) -> Result<String> {
+        Err(anyhow!("No system OCR on this platform"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_plugin_config() {
+        assert_eq!(OcrSettings::from_plugin_config(&serde_json::json!({})), OcrSettings::default());
+
+        let settings = OcrSettings::from_plugin_config(&serde_json::json!({
+            OCR_ENABLED_KEY: true,
+            OCR_LANGUAGE_KEY: " chi_sim+eng ",
+        }));
+        assert!(settings.enabled);
+        assert_eq!(settings.language, "chi_sim+eng");
+
+        let settings = OcrSettings::from_plugin_config(&serde_json::json!({ OCR_LANGUAGE_KEY: "" }));
+        assert_eq!(settings.language, DEFAULT_OCR_LANGUAGE);
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(normalize_text("  Invoice   #42 \n\n\tTotal:  19.99\n"), "Invoice #42\nTotal: 19.99");
+        assert_eq!(normalize_text("\n \n"), "");
+    }
+}
@@ -0,0 +1,140 @@
+// 剪贴板变换粘贴 - 把历史条目按纯文本 / 大写 / 小写 / JSON 转义写入剪贴板，
+// 再把焦点还给启动器显示前的窗口并模拟 Ctrl+V，直接粘贴而不只是复制
+
+use crate::core::types::{Action, WoxImage};
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// 等待启动器隐藏后再恢复前台窗口
+const HIDE_DELAY_MS: u64 = 150;
+
+/// 恢复前台窗口后等待焦点切换完成再粘贴
+const FOCUS_DELAY_MS: u64 = 50;
+
+/// 粘贴前对内容的变换
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteTransform {
+    /// 只写入纯文本（去掉富文本格式）
+    PlainText,
+    Upper,
+    Lower,
+    /// JSON 字符串转义（不含两侧引号，便于粘贴到已有字符串字面量中）
+    JsonEscaped,
+}
+
+impl PasteTransform {
+    pub const ALL: [PasteTransform; 4] = [Self::PlainText, Self::Upper, Self::Lower, Self::JsonEscaped];
+
+    /// 动作 ID（剪贴板插件结果动作、paste_clipboard_content 命令共用）
+    pub fn action_id(self) -> &'static str {
+        match self {
+            Self::PlainText => "paste_plain",
+            Self::Upper => "paste_upper",
+            Self::Lower => "paste_lower",
+            Self::JsonEscaped => "paste_json",
+        }
+    }
+
+    pub fn from_action_id(action_id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.action_id() == action_id)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::PlainText => "Paste as Plain Text",
+            Self::Upper => "Paste UPPERCASE",
+            Self::Lower => "Paste lowercase",
+            Self::JsonEscaped => "Paste JSON-escaped",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Self::PlainText => "📄",
+            Self::Upper => "🔠",
+            Self::Lower => "🔡",
+            Self::JsonEscaped => "🧾",
+        }
+    }
+
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::PlainText => text.to_string(),
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::JsonEscaped => {
+                let quoted = serde_json::Value::String(text.to_string()).to_string();
+                quoted[1..quoted.len() - 1].to_string()
+            }
+        }
+    }
+}
+
+/// 文本条目的变换粘贴动作
+pub fn transform_actions() -> Vec<Action> {
+    PasteTransform::ALL
+        .into_iter()
+        .map(|transform| Action {
+            id: transform.action_id().to_string(),
+            name: transform.label().to_string(),
+            icon: Some(WoxImage::emoji(transform.icon())),
+            is_default: false,
+            hotkey: None,
+            prevent_hide: false,
+        })
+        .collect()
+}
+
+/// 变换后写入剪贴板（以自身身份写入，不会重新进入历史），然后粘贴到之前的窗口
+pub fn paste_text(text: &str, transform: Option<PasteTransform>) -> Result<()> {
+    let text = match transform {
+        Some(transform) => transform.apply(text),
+        None => text.to_string(),
+    };
+    if text.is_empty() {
+        return Err(anyhow!("Nothing to paste"));
+    }
+    crate::clipboard::write_text(&text)?;
+    paste_into_previous_window(true);
+    Ok(())
+}
+
+/// 启动器隐藏后恢复前台窗口并模拟粘贴快捷键（后台线程执行，不阻塞调用方）
+///
+/// restore_focus 为 false 时直接粘贴到当前焦点窗口（启动器未显示时记录的窗口可能已过期）
+pub fn paste_into_previous_window(restore_focus: bool) {
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(HIDE_DELAY_MS));
+        if restore_focus && crate::hotkey::input::restore_foreground_window() {
+            std::thread::sleep(Duration::from_millis(FOCUS_DELAY_MS));
+        }
+        if let Err(e) = crate::hotkey::input::send_paste_shortcut() {
+            tracing::warn!("Failed to paste clipboard item: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transforms() {
+        assert_eq!(PasteTransform::Upper.apply("Straße"), "STRASSE");
+        assert_eq!(PasteTransform::Lower.apply("Hello World"), "hello world");
+        assert_eq!(PasteTransform::PlainText.apply("<b>x</b>"), "<b>x</b>");
+        assert_eq!(
+            PasteTransform::JsonEscaped.apply("say \"hi\"\n\tC:\\temp"),
+            "say \\\"hi\\\"\\n\\tC:\\\\temp"
+        );
+    }
+
+    #[test]
+    fn test_action_ids_round_trip() {
+        for transform in PasteTransform::ALL {
+            assert_eq!(PasteTransform::from_action_id(transform.action_id()), Some(transform));
+        }
+        assert_eq!(PasteTransform::from_action_id("paste"), None);
+        assert_eq!(transform_actions().len(), PasteTransform::ALL.len());
+    }
+}
@@ -0,0 +1,167 @@
+// MFT 索引磁盘占用、清理与压缩相关命令
+
+/// 获取 MFT 索引磁盘占用（按驱动器统计 FST/bitmap/paths/delta 文件）
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn get_index_storage_report() -> Result<crate::mft_scanner::storage_report::IndexStorageReport, String> {
+    let dir = crate::utils::paths::get_mft_database_dir().map_err(|e| e.to_string())?;
+    let indexed_drives = crate::mft_scanner::load_config().map(|c| c.drives).unwrap_or_default();
+    crate::mft_scanner::storage_report::storage_report(&dir, &indexed_drives).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn get_index_storage_report() -> Result<(), String> {
+    Err("MFT index is only available on Windows".to_string())
+}
+
+/// 删除驱动器的索引并从扫描配置中移除（之后不再自动索引该驱动器）
+/// MFT 服务占用的文件可能删除失败，重启服务后再次清理即可
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn drop_drive_index(drive: char) -> Result<crate::mft_scanner::storage_report::CleanupResult, String> {
+    let drive = drive.to_ascii_uppercase();
+    crate::mft_scanner::QUERY_CACHE.clear_drive(drive);
+    
+    let mut config = crate::mft_scanner::load_config().map_err(|e| e.to_string())?;
+    config.drives.retain(|d| !d.eq_ignore_ascii_case(&drive));
+    if !config.excluded_drives.contains(&drive) {
+        config.excluded_drives.push(drive);
+    }
+    crate::mft_scanner::config::save_config(&config).map_err(|e| e.to_string())?;
+    
+    let dir = crate::utils::paths::get_mft_database_dir().map_err(|e| e.to_string())?;
+    tracing::info!("🗑️ Dropping index for drive {}", drive);
+    crate::mft_scanner::storage_report::remove_files(&dir, Some(drive), &[]).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn drop_drive_index(_drive: char) -> Result<(), String> {
+    Err("MFT index is only available on Windows".to_string())
+}
+
+/// 合并增量索引（合并后删除 delta 文件），并清理构建中断遗留的临时文件
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn compact_index_deltas(drive: Option<char>) -> Result<crate::mft_scanner::storage_report::CleanupResult, String> {
+    use crate::mft_scanner::storage_report;
+    
+    let dir = crate::utils::paths::get_mft_database_dir().map_err(|e| e.to_string())?;
+    let drive = drive.map(|d| d.to_ascii_uppercase());
+    let before = storage_report::storage_report(&dir, &[]).map_err(|e| e.to_string())?;
+    
+    let output_dir = dir.to_string_lossy().to_string();
+    let drives_with_delta: Vec<char> = before
+        .drives
+        .iter()
+        .filter(|d| d.delta_bytes > 0 && drive.map_or(true, |target| target == d.drive))
+        .map(|d| d.drive)
+        .collect();
+    
+    for delta_drive in drives_with_delta {
+        let merger = crate::mft_scanner::DeltaMerger::new(delta_drive, output_dir.clone());
+        tokio::task::spawn_blocking(move || merger.merge())
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to compact drive {}: {}", delta_drive, e))?;
+        crate::mft_scanner::QUERY_CACHE.clear_drive(delta_drive);
+    }
+    
+    let mut result = storage_report::remove_stale_temp_files(&dir, drive, storage_report::STALE_TEMP_AGE)
+        .map_err(|e| e.to_string())?;
+    
+    // 合并会增大主索引，按前后总占用计算实际释放的空间
+    let after = storage_report::storage_report(&dir, &[]).map_err(|e| e.to_string())?;
+    result.freed_bytes = before.total_bytes.saturating_sub(after.total_bytes);
+    Ok(result)
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn compact_index_deltas(_drive: Option<char>) -> Result<(), String> {
+    Err("MFT index is only available on Windows".to_string())
+}
+
+/// 获取 MFT 索引统计：各驱动器索引/路径/位图文件大小、增量积压、墓碑数和占用上限
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn get_mft_index_stats() -> Result<crate::mft_scanner::storage_report::IndexStats, String> {
+    let dir = crate::utils::paths::get_mft_database_dir().map_err(|e| e.to_string())?;
+    let config = crate::mft_scanner::load_config().map_err(|e| e.to_string())?;
+    crate::mft_scanner::storage_report::index_stats(&dir, &config.drives, config.max_index_bytes())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn get_mft_index_stats() -> Result<serde_json::Value, String> {
+    Err("MFT index is only available on Windows".to_string())
+}
+
+/// 压缩 MFT 索引：合并增量、清理墓碑条目和遗留临时文件
+/// MFT 服务未运行时同时重写路径文件释放已删除路径的空间（服务运行中只清理位图）
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn compact_mft_index(drive: Option<char>) -> Result<crate::mft_scanner::compaction::CompactionReport, String> {
+    use crate::mft_scanner::{compaction::CompactionReport, storage_report};
+    
+    let dir = crate::utils::paths::get_mft_database_dir().map_err(|e| e.to_string())?;
+    let output_dir = dir.to_string_lossy().to_string();
+    let drive = drive.map(|d| d.to_ascii_uppercase());
+    let before = storage_report::storage_report(&dir, &[]).map_err(|e| e.to_string())?;
+    
+    let service_running = crate::mft_scanner::ipc::fetch_status(&output_dir).is_some();
+    let mut report = CompactionReport {
+        service_running,
+        ..Default::default()
+    };
+    
+    let drives: Vec<char> = before
+        .drives
+        .iter()
+        .map(|d| d.drive)
+        .filter(|d| drive.map_or(true, |target| target == *d))
+        .collect();
+    for target in drives {
+        let merger = crate::mft_scanner::DeltaMerger::new(target, output_dir.clone());
+        let compacted = tokio::task::spawn_blocking(move || merger.compact(!service_running))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to compact drive {}: {}", target, e))?;
+        if compacted.merged_delta || compacted.pruned_entries > 0 {
+            crate::mft_scanner::QUERY_CACHE.clear_drive(target);
+        }
+        report.drives.push(compacted);
+    }
+    
+    let removed = storage_report::remove_stale_temp_files(&dir, drive, storage_report::STALE_TEMP_AGE)
+        .map_err(|e| e.to_string())?;
+    report.removed_temp_files = removed.removed_files;
+    
+    let after = storage_report::storage_report(&dir, &[]).map_err(|e| e.to_string())?;
+    report.freed_bytes = before.total_bytes.saturating_sub(after.total_bytes);
+    Ok(report)
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn compact_mft_index(_drive: Option<char>) -> Result<serde_json::Value, String> {
+    Err("MFT index is only available on Windows".to_string())
+}
+
+/// 设置 MFT 索引磁盘占用上限（MB，None 表示不限制）
+/// MFT 服务在下次启动时读取，超出上限时后台自动压缩
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub async fn set_mft_index_limit(max_mb: Option<u64>) -> Result<(), String> {
+    let mut config = crate::mft_scanner::load_config().map_err(|e| e.to_string())?;
+    config.max_index_mb = max_mb.filter(|mb| *mb > 0);
+    crate::mft_scanner::config::save_config(&config).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub async fn set_mft_index_limit(_max_mb: Option<u64>) -> Result<(), String> {
+    Err("MFT index is only available on Windows".to_string())
+}
@@ -0,0 +1,72 @@
+// 本地 API 相关命令
+
+use crate::storage::{AppConfig, StorageManager};
+use tauri::State;
+
+/// 确保本地 API 令牌存在（首次使用时生成并保存）
+pub async fn ensure_local_api_token(storage: &StorageManager) -> anyhow::Result<AppConfig> {
+    let mut config = storage.load_config().await?;
+    if config.local_api.token.is_empty() {
+        config.local_api.token = crate::local_api::generate_token();
+        storage.save_config(&config).await?;
+    }
+    Ok(config)
+}
+
+fn local_api_status(config: &AppConfig) -> crate::local_api::LocalApiStatus {
+    crate::local_api::LocalApiStatus {
+        enabled: config.local_api.enabled,
+        running: crate::local_api::LOCAL_API.is_running(),
+        port: config.local_api.port,
+        token: config.local_api.token.clone(),
+        base_url: format!("http://127.0.0.1:{}/api/v1", config.local_api.port),
+    }
+}
+
+/// 获取本地 API 状态
+#[tauri::command]
+pub async fn get_local_api_status(
+    storage: State<'_, StorageManager>,
+) -> Result<crate::local_api::LocalApiStatus, String> {
+    let config = ensure_local_api_token(&storage).await.map_err(|e| e.to_string())?;
+    Ok(local_api_status(&config))
+}
+
+/// 启用/禁用本地 API（可同时修改端口）
+#[tauri::command]
+pub async fn set_local_api_enabled(
+    enabled: bool,
+    port: Option<u16>,
+    app: tauri::AppHandle,
+    storage: State<'_, StorageManager>,
+) -> Result<crate::local_api::LocalApiStatus, String> {
+    let mut config = ensure_local_api_token(&storage).await.map_err(|e| e.to_string())?;
+    config.local_api.enabled = enabled;
+    if let Some(port) = port {
+        config.local_api.port = port;
+    }
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    
+    if enabled {
+        crate::local_api::LOCAL_API
+            .start(app, config.local_api.port, config.local_api.token.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+    } else {
+        crate::local_api::LOCAL_API.stop().await;
+    }
+    
+    Ok(local_api_status(&config))
+}
+
+/// 重新生成本地 API 令牌（旧令牌立即失效）
+#[tauri::command]
+pub async fn regenerate_local_api_token(
+    storage: State<'_, StorageManager>,
+) -> Result<crate::local_api::LocalApiStatus, String> {
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.local_api.token = crate::local_api::generate_token();
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    crate::local_api::LOCAL_API.set_token(config.local_api.token.clone());
+    Ok(local_api_status(&config))
+}
@@ -2,76 +2,372 @@
 
 pub mod audit;
 pub mod ai;
+pub mod index_storage; // MFT 索引占用与压缩
+pub mod local_api;     // 本地 API
 pub mod plugin_market; // 插件市场
+pub mod profile;       // 数据目录与配置档案
+pub mod ranking;       // 自定义排序规则
+pub mod result_notes;  // 结果备注与别名
 pub mod suggestion;    // 智能推荐
 pub mod workflow;      // 工作流
 
 use crate::clipboard::ClipboardManager;
+use crate::core::confirmation::PendingAction;
 use crate::core::types::*;
-use crate::plugin::PluginManager;
+use crate::plugin::{PluginManager, QueryEvent};
 use crate::preview;
 use crate::ranking::IntelligentRanker;
+use crate::ranking::rules::CompiledRules;
 use crate::storage::{AppConfig, StorageManager};
 use crate::statistics::StatisticsManager;
 use tauri::{State, Emitter};
+use tracing::Instrument;
+
+/// 查询部分结果事件（stream 模式下每个插件完成时发送）
+const QUERY_RESULTS_PARTIAL_EVENT: &str = "query-results-partial";
+
+/// 即时答案事件（stream 模式下在其他插件之前发送）
+const QUERY_INSTANT_ANSWERS_EVENT: &str = "query-instant-answers";
+
+/// MFT 扫描进度事件（payload: ServiceStatus）
+#[cfg(target_os = "windows")]
+const MFT_PROGRESS_EVENT: &str = "mft-progress";
+
+/// 即时答案
+#[derive(Clone, serde::Serialize)]
+struct InstantAnswers<T> {
+    generation: u64,
+    results: T,
+}
+
+/// 单个插件的部分结果
+#[derive(Clone, serde::Serialize)]
+struct PartialQueryResults<'a, T> {
+    generation: u64,
+    plugin_id: &'a str,
+    results: T,
+}
 
 /// 查询命令
+/// generation 为前端递增的查询序号：新查询会取消同一窗口的旧查询，过期查询返回 QUERY_CANCELLED 错误；
+/// session 为前端搜索框实例的会话 ID（重新挂载后 generation 重新计数）
+/// 结果集按窗口保存，供 filter_results 以 generation 作为 query_id 二次过滤
+/// stream 为 true 时先发送 query-instant-answers 事件（计算器/单位换算/剪贴板/路径直达），
+/// 之后每个插件完成即发送 query-results-partial 事件（未排序），最终返回值仍为完整排序结果
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn query(
     input: String,
+    generation: Option<u64>,
+    session: Option<String>,
+    stream: Option<bool>,
+    window: tauri::Window,
     manager: State<'_, PluginManager>,
     stats: State<'_, StatisticsManager>,
     history: State<'_, crate::search_history::SearchHistoryManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<Vec<QueryResult>, String> {
+    let stream = stream.unwrap_or(false);
+    run_tracked_query(input, generation, session, stream, false, &window, &manager, &stats, &history, &notes, &storage).await
+}
+
+/// 轻量查询：参数同 query，返回不含预览/动作的列表项（部分结果事件同样为列表项）
+/// 选中结果时通过 get_result_detail 获取详情
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn query_items(
+    input: String,
+    generation: Option<u64>,
+    session: Option<String>,
+    stream: Option<bool>,
+    window: tauri::Window,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    history: State<'_, crate::search_history::SearchHistoryManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<Vec<ResultListItem>, String> {
+    let stream = stream.unwrap_or(false);
+    let results = run_tracked_query(input, generation, session, stream, true, &window, &manager, &stats, &history, &notes, &storage).await?;
+    Ok(results.iter().map(ResultListItem::from).collect())
+}
+
+/// 获取结果详情（预览/动作/上下文数据），从窗口最近一次查询的结果集中查找
+#[tauri::command]
+pub async fn get_result_detail(result_id: String, window: tauri::Window) -> Result<ResultDetail, String> {
+    crate::core::result_filter::RESULT_SESSIONS
+        .find(window.label(), &result_id)
+        .map(ResultDetail::from)
+        .ok_or_else(|| format!("Result '{}' is no longer available", result_id))
+}
+
+/// 获取被截断预览的完整内容（结果 context_data.preview_truncated 为 true 时可用）
+#[tauri::command]
+pub async fn load_full_preview(plugin_id: String, result_id: String) -> Result<Preview, String> {
+    crate::core::preview_cap::FULL_PREVIEWS
+        .get(&plugin_id, &result_id)
+        .ok_or_else(|| format!("Full preview for '{}' is no longer available", result_id))
+}
+
+/// 带取消与结果集缓存的查询（light 为 true 时部分结果以列表项形式发送）
+#[allow(clippy::too_many_arguments)]
+async fn run_tracked_query(
+    input: String,
+    generation: Option<u64>,
+    session: Option<String>,
+    stream: bool,
+    light: bool,
+    window: &tauri::Window,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    history: &crate::search_history::SearchHistoryManager,
+    notes: &crate::result_notes::ResultNotesManager,
+    storage: &StorageManager,
+) -> Result<Vec<QueryResult>, String> {
+    use crate::core::query_cancel::{QUERY_CANCELLED, QUERY_TRACKER};
+    
+    let Some(ticket) = QUERY_TRACKER.begin(window.label(), session.as_deref(), generation) else {
+        tracing::debug!("Dropping out-of-order query generation {:?}", generation);
+        return Err(QUERY_CANCELLED.to_string());
+    };
+    
+    let query_id = crate::profiling::next_query_id();
+    let on_event = |event: QueryEvent<'_>| {
+        if !stream || ticket.is_cancelled() {
+            return;
+        }
+        let light_items = |results: &[QueryResult]| -> Vec<ResultListItem> {
+            results.iter().map(ResultListItem::from).collect()
+        };
+        let generation = ticket.generation;
+        let emitted = match (event, light) {
+            (QueryEvent::Instant(results), true) => window.emit(
+                QUERY_INSTANT_ANSWERS_EVENT,
+                InstantAnswers { generation, results: light_items(results) },
+            ),
+            (QueryEvent::Instant(results), false) => window.emit(
+                QUERY_INSTANT_ANSWERS_EVENT,
+                InstantAnswers { generation, results },
+            ),
+            (QueryEvent::Partial { plugin_id, results }, true) => window.emit(
+                QUERY_RESULTS_PARTIAL_EVENT,
+                PartialQueryResults { generation, plugin_id, results: light_items(results) },
+            ),
+            (QueryEvent::Partial { plugin_id, results }, false) => window.emit(
+                QUERY_RESULTS_PARTIAL_EVENT,
+                PartialQueryResults { generation, plugin_id, results },
+            ),
+        };
+        if let Err(e) = emitted {
+            tracing::warn!("Failed to emit streaming results: {}", e);
+        }
+    };
+    let results = run_query(query_id, input, &ticket.token, &on_event, manager, stats, history, notes, storage).await?;
+    
+    // 排序完成后仍可能有更新的查询到达，此时丢弃结果
+    if !QUERY_TRACKER.is_current(window.label(), &ticket) {
+        tracing::debug!("🚫 Query #{} superseded, discarding results", query_id);
+        return Err(QUERY_CANCELLED.to_string());
+    }
+    
+    crate::core::result_filter::RESULT_SESSIONS.store(window.label(), ticket.generation, &results);
+    Ok(results)
+}
+
+/// 结果内搜索：按附加关键词过滤窗口最近一次查询的结果（不重新查询插件），返回高亮区间
+#[tauri::command]
+pub async fn filter_results(
+    query_id: u64,
+    term: String,
+    window: tauri::Window,
+) -> Result<Vec<QueryResult>, String> {
+    crate::core::result_filter::RESULT_SESSIONS
+        .filter(window.label(), query_id, &term)
+        .map_err(|e| e.to_string())
+}
+
+/// 窗口隐藏时保存查询文本、选中项和滚动位置（仅内存，供会话恢复）
+#[tauri::command]
+pub async fn save_session_state(state: crate::core::session_state::SessionState) -> Result<(), String> {
+    crate::core::session_state::SESSION_STATE.save(state);
+    Ok(())
+}
+
+/// 窗口显示时获取可恢复的会话状态（未开启或超过恢复时间返回 None）
+#[tauri::command]
+pub async fn get_session_state(
+    storage: State<'_, StorageManager>,
+) -> Result<Option<crate::core::session_state::SessionState>, String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    Ok(crate::core::session_state::SESSION_STATE.restore(config.general.restore_session_secs))
+}
+
+/// 不参与前端查询取消的查询（供本地 API 等外部调用）
+pub async fn query_untracked(
+    input: String,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    history: &crate::search_history::SearchHistoryManager,
+    notes: &crate::result_notes::ResultNotesManager,
+    storage: &StorageManager,
+) -> Result<Vec<QueryResult>, String> {
+    let query_id = crate::profiling::next_query_id();
+    let cancel = tokio_util::sync::CancellationToken::new();
+    run_query(query_id, input, &cancel, &|_| {}, manager, stats, history, notes, storage).await
+}
+
+/// 流式查询事件回调（即时答案 / 插件部分结果）
+type QueryEventCallback<'a> = dyn Fn(QueryEvent<'_>) + Send + Sync + 'a;
+
+/// 查询管线（每个阶段都有 tracing span，根 span 携带 query_id，供 capture_trace 导出）
+#[allow(clippy::too_many_arguments)]
+async fn run_query(
+    query_id: u64,
+    input: String,
+    cancel: &tokio_util::sync::CancellationToken,
+    on_event: &QueryEventCallback<'_>,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    history: &crate::search_history::SearchHistoryManager,
+    notes: &crate::result_notes::ResultNotesManager,
+    storage: &StorageManager,
+) -> Result<Vec<QueryResult>, String> {
+    let span = tracing::info_span!(crate::profiling::QUERY_SPAN, query_id, input = %input);
+    run_query_stages(query_id, input, cancel, on_event, manager, stats, history, notes, storage)
+        .instrument(span)
+        .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_query_stages(
+    query_id: u64,
+    input: String,
+    cancel: &tokio_util::sync::CancellationToken,
+    on_event: &QueryEventCallback<'_>,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    history: &crate::search_history::SearchHistoryManager,
+    notes: &crate::result_notes::ResultNotesManager,
+    storage: &StorageManager,
 ) -> Result<Vec<QueryResult>, String> {
     let query_start = std::time::Instant::now();
-    tracing::debug!("🔍 Query started: '{}'", input);
+    tracing::debug!("🔍 Query #{} started: '{}'", query_id, input);
     
     // 记录查询
     if !input.is_empty() {
         let _ = stats.record_query(&input).await;
     }
     
+    // 🏷️ 用户别名在插件之前查找，命中时立即作为即时答案发送
+    let alias_hit = crate::result_aliases::RESULT_ALIASES.lookup(&input);
+    if let Some(alias) = &alias_hit {
+        on_event(QueryEvent::Instant(std::slice::from_ref(alias)));
+    }
+    
     // 🔥 步骤 1: 执行插件查询
     let plugin_query_start = std::time::Instant::now();
-    let mut plugin_results = manager.query(&input).await.map_err(|e| e.to_string())?;
+    let mut plugin_results = manager
+        .query_streaming(&input, cancel.clone(), on_event)
+        .instrument(tracing::info_span!("plugin_query"))
+        .await
+        .map_err(|e| e.to_string())?;
     let plugin_elapsed = plugin_query_start.elapsed();
     
+    // 插件查询期间有新输入，跳过后续排序
+    if cancel.is_cancelled() {
+        tracing::debug!("🚫 Query #{} cancelled after plugin stage", query_id);
+        return Err(crate::core::query_cancel::QUERY_CANCELLED.to_string());
+    }
+    
+    // 🔥 附加用户备注（副标题/预览 + 备注操作），并补充备注命中的结果
+    notes
+        .apply_to_results(&mut plugin_results, &input)
+        .instrument(tracing::info_span!("result_notes"))
+        .await;
+    
+    // 🏷️ 注入指定/删除别名操作
+    crate::result_aliases::RESULT_ALIASES.attach_actions(&mut plugin_results);
+    
+    // 🔊 为 AI 回答、释义和文本预览注入朗读操作
+    crate::tts::TTS_SERVICE.attach_actions(&mut plugin_results);
+    
+    // 📁 为声明了本地路径的结果注入"在文件夹中显示"操作
+    crate::core::reveal::attach_actions(&mut plugin_results);
+    
+    // 🛡️ 带下载来源标记的文件结果：安全徽章与解除锁定/查看来源操作
+    tracing::info_span!("file_badges")
+        .in_scope(|| crate::core::file_badges::annotate(&mut plugin_results));
+    
+    // ✂️ 备注等附加的预览同样受大小上限约束
+    crate::core::preview_cap::cap_previews(&mut plugin_results);
+    
+    // 📌 当前查询的置顶结果，并注入置顶/取消置顶操作
+    let pins = stats.get_pins(&input).await.unwrap_or_default();
+    crate::ranking::pins::attach_actions(&mut plugin_results, &input, &pins);
+    
+    let config = storage
+        .load_config()
+        .instrument(tracing::info_span!("load_config"))
+        .await
+        .ok();
+    let fallback_config = config.as_ref().map(|c| c.fallback.clone()).unwrap_or_default();
+    
+    // ♿ 补充屏幕阅读器元数据，并按图标集切换高对比度图标
+    let icon_set = config.as_ref()
+        .map(|c| c.appearance.icon_set.as_str())
+        .unwrap_or(crate::core::accessibility::ICON_SET_DEFAULT);
+    tracing::info_span!("accessibility")
+        .in_scope(|| crate::core::accessibility::annotate(&mut plugin_results, icon_set));
+    
     // 🔥 步骤 2: 使用智能排序算法
     let ranking_start = std::time::Instant::now();
     
-    // 创建排序器
-    let ranker = IntelligentRanker::new();
-    
-    // 获取 MRU 结果列表
-    let mru_results = stats.get_top_results(50).await.unwrap_or_default();
-    let mru_ids: Vec<String> = mru_results.iter().map(|r| r.result_id.clone()).collect();
+    let (usage_stats, mru_ids) = collect_usage_stats(stats, &plugin_results)
+        .instrument(tracing::info_span!("usage_stats"))
+        .await;
+    let frecency = collect_frecency(stats, &plugin_results)
+        .instrument(tracing::info_span!("frecency"))
+        .await;
+    // 相似查询下用户曾经选择过的结果
+    let query_affinity = stats
+        .get_query_affinity(&input)
+        .instrument(tracing::info_span!("query_affinity"))
+        .await
+        .unwrap_or_default();
     
-    // 构建使用统计数据 (id, count, last_used)
-    let mut usage_stats = Vec::new();
-    for result in &plugin_results {
-        if let Ok(count) = stats.get_result_score(&result.id, &result.plugin_id).await {
-            // 查找最后使用时间
-            let last_used = mru_results.iter()
-                .find(|mru| mru.result_id == result.id)
-                .map(|mru| mru.last_used);
-            
-            usage_stats.push((result.id.clone(), count as u32, last_used));
-        }
-    }
+    // 创建排序器（使用配置的权重和插件倍率，附加用户自定义规则）
+    let ranking = config
+        .map(|c| c.ranking)
+        .unwrap_or_default();
+    let ranker = IntelligentRanker::with_weights(ranking.weights)
+        .with_plugin_boosts(ranking.plugin_boosts)
+        .with_query_affinity(query_affinity)
+        .with_frecency(frecency)
+        .with_pins(pins)
+        .with_rules(CompiledRules::compile_lenient(&ranking.rules));
     
     // 执行智能排序
-    ranker.rank_results(
-        &mut plugin_results,
-        &input,
-        &usage_stats,
-        &mru_ids,
-    );
+    tracing::info_span!("ranking").in_scope(|| {
+        ranker.rank_results(
+            &mut plugin_results,
+            &input,
+            &usage_stats,
+            &mru_ids,
+        )
+    });
+    
+    // 别名命中的结果始终置顶
+    if let Some(alias) = alias_hit {
+        crate::result_aliases::ResultAliasesManager::pin_to_top(&mut plugin_results, alias);
+    }
     
     let ranking_elapsed = ranking_start.elapsed();
     
     let total_elapsed = query_start.elapsed();
     tracing::info!(
-        "✅ Query completed: '{}' → {} results in {:.2}ms (plugin: {:.2}ms, ranking: {:.2}ms)",
+        "✅ Query #{} completed: '{}' → {} results in {:.2}ms (plugin: {:.2}ms, ranking: {:.2}ms)",
+        query_id,
         input,
         plugin_results.len(),
         total_elapsed.as_secs_f64() * 1000.0,
@@ -81,13 +377,73 @@ pub async fn query(
     
     // 记录搜索历史
     if !input.trim().is_empty() && plugin_results.len() > 0 {
-        let _ = history.add(input.clone(), plugin_results.len()).await;
+        let _ = history
+            .add(input.clone(), plugin_results.len())
+            .instrument(tracing::info_span!("search_history"))
+            .await;
+    }
+    
+    // 💡 记录无结果查询（用于推荐启用/安装相关插件），并按备选链提供合成结果
+    if plugin_results.is_empty() {
+        let _ = stats.record_zero_result(&input).await;
+        plugin_results = crate::core::fallback::results(&input, &fallback_config);
     }
     
     Ok(plugin_results)
 }
 
-/// 执行操作
+/// 各结果的 frecency 值（工作区上下文使用独立统计，此时按原始计数排序）
+async fn collect_frecency(stats: &StatisticsManager, results: &[QueryResult]) -> std::collections::HashMap<String, f64> {
+    let mut frecency = std::collections::HashMap::new();
+    if crate::workspace_context::WORKSPACE_CONTEXTS.active_id().is_some() {
+        return frecency;
+    }
+    for result in results {
+        if let Ok(value) = stats.get_frecency(&result.id, &result.plugin_id).await {
+            if value > 0.0 {
+                frecency.insert(result.id.clone(), value);
+            }
+        }
+    }
+    frecency
+}
+
+/// 构建排序所需的使用统计 (id, count, last_used) 与 MRU 列表
+/// 激活工作区上下文时使用该上下文独立的统计
+async fn collect_usage_stats(
+    stats: &StatisticsManager,
+    results: &[QueryResult],
+) -> (Vec<(String, u32, Option<chrono::DateTime<chrono::Utc>>)>, Vec<String>) {
+    let context_id = crate::workspace_context::WORKSPACE_CONTEXTS.active_id();
+    
+    // 获取 MRU 结果列表
+    let mru_results = match &context_id {
+        Some(context_id) => stats.get_context_top_results(context_id, 50).await,
+        None => stats.get_top_results(50).await,
+    }
+    .unwrap_or_default();
+    let mru_ids: Vec<String> = mru_results.iter().map(|r| r.result_id.clone()).collect();
+    
+    let mut usage_stats = Vec::new();
+    for result in results {
+        let score = match &context_id {
+            Some(context_id) => stats.get_context_result_score(context_id, &result.id, &result.plugin_id).await,
+            None => stats.get_result_score(&result.id, &result.plugin_id).await,
+        };
+        if let Ok(count) = score {
+            // 查找最后使用时间
+            let last_used = mru_results.iter()
+                .find(|mru| mru.result_id == result.id)
+                .map(|mru| mru.last_used);
+            
+            usage_stats.push((result.id.clone(), count as u32, last_used));
+        }
+    }
+    
+    (usage_stats, mru_ids)
+}
+
+/// 执行操作，返回插件的执行反馈（提示消息、替换查询、新结果等）
 #[tauri::command]
 pub async fn execute_action(
     result_id: String,
@@ -98,13 +454,215 @@ pub async fn execute_action(
     icon: WoxImage,
     manager: State<'_, PluginManager>,
     stats: State<'_, StatisticsManager>,
-) -> Result<(), String> {
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+    app: tauri::AppHandle,
+) -> Result<ExecuteOutcome, String> {
+    let action = PendingAction { result_id, action_id, plugin_id, title, subtitle, icon };
+    run_action(action, false, &manager, &stats, &notes, &storage, &app).await
+}
+
+/// 确认破坏性动作：凭 execute_action 返回的令牌继续执行（令牌一次有效）
+#[tauri::command]
+pub async fn confirm_action(
+    token: String,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+    app: tauri::AppHandle,
+) -> Result<ExecuteOutcome, String> {
+    let action = crate::core::confirmation::CONFIRMATIONS
+        .take(&token)
+        .ok_or_else(|| "Confirmation expired, please run the action again".to_string())?;
+    run_action(action, true, &manager, &stats, &notes, &storage, &app).await
+}
+
+/// 取消破坏性动作确认
+#[tauri::command]
+pub async fn cancel_confirmation(token: String) -> Result<(), String> {
+    crate::core::confirmation::CONFIRMATIONS.cancel(&token);
+    Ok(())
+}
+
+/// 快速选择（Alt+1..9）：执行窗口最近一次查询结果中第 position 个结果的默认动作
+/// result_id 为前端看到的结果 ID，与后端结果集不一致时（列表已刷新）返回错误
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn execute_result_index(
+    position: usize,
+    result_id: Option<String>,
+    window: tauri::Window,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+    app: tauri::AppHandle,
+) -> Result<ExecuteOutcome, String> {
+    let result = crate::core::result_filter::RESULT_SESSIONS
+        .nth(window.label(), position)
+        .ok_or_else(|| format!("No result at position {}", position))?;
+    if result_id.is_some_and(|id| id != result.id) {
+        return Err("Result list has changed, please try again".to_string());
+    }
+    let action_id = result
+        .default_action()
+        .map(|action| action.id.clone())
+        .ok_or_else(|| format!("Result '{}' has no action", result.title))?;
+
+    // 执行历史条目转到原始结果执行（与前端回车行为一致）
+    let original = |key: &str| result.context_data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let (target_id, target_action, target_plugin) = match (
+        result.plugin_id.as_str(),
+        original("original_id"),
+        original("action_id"),
+        original("plugin_id"),
+    ) {
+        ("execution-history", Some(id), Some(action), Some(plugin)) if action_id == "execute" => (id, action, plugin),
+        _ => (result.id.clone(), action_id, result.plugin_id.clone()),
+    };
+
+    tracing::debug!("⚡ Quick select #{}: {}", position, result.title);
+    let action = PendingAction {
+        result_id: target_id,
+        action_id: target_action,
+        plugin_id: target_plugin,
+        title: result.title,
+        subtitle: result.subtitle,
+        icon: result.icon,
+    };
+    run_action(action, false, &manager, &stats, &notes, &storage, &app).await
+}
+
+/// 执行结果动作（execute_action、快速选择与确认共用，confirmed 为 true 时跳过预演）
+async fn run_action(
+    action: PendingAction,
+    confirmed: bool,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    notes: &crate::result_notes::ResultNotesManager,
+    storage: &StorageManager,
+    app: &tauri::AppHandle,
+) -> Result<ExecuteOutcome, String> {
+    let PendingAction { result_id, action_id, plugin_id, title, subtitle, icon } = action;
+    
+    // 🔥 命令面板条目直接在后端执行
+    if plugin_id == crate::plugin::settings::SETTINGS_PLUGIN_ID {
+        if let Some(entry) = crate::plugin::settings::palette_entry(&result_id) {
+            return run_palette_entry(entry, app, manager, stats, storage).await;
+        }
+    }
+    
+    // 🔥 无结果备选直接在后端执行
+    if plugin_id == crate::core::fallback::FALLBACK_PLUGIN_ID {
+        return run_fallback(&result_id, app, manager, storage).await;
+    }
+    
+    // 🔥 备注、朗读操作由后端统一处理，不转发给插件
+    match action_id.as_str() {
+        crate::result_notes::ACTION_REMOVE_NOTE => {
+            notes.remove_note(&plugin_id, &result_id).await.map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        // 编辑备注由前端弹出输入框后调用 set_result_note
+        crate::result_notes::ACTION_EDIT_NOTE => return Ok(ExecuteOutcome::Hide),
+        // 指定别名由前端弹出输入框后调用 set_result_alias
+        crate::result_aliases::ACTION_ASSIGN_ALIAS => return Ok(ExecuteOutcome::Hide),
+        crate::result_aliases::ACTION_REMOVE_ALIAS => {
+            crate::result_aliases::RESULT_ALIASES
+                .remove_for_result(&result_id)
+                .map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        crate::tts::ACTION_READ_ALOUD => {
+            let config = storage.load_config().await.map_err(|e| e.to_string())?;
+            crate::tts::TTS_SERVICE
+                .speak_result(&result_id, &config.tts)
+                .map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        crate::core::reveal::ACTION_REVEAL => {
+            let path = crate::core::reveal::target_for(&result_id)
+                .ok_or_else(|| "This result has no file path".to_string())?;
+            crate::core::reveal::reveal(std::path::Path::new(&path)).map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        crate::core::file_badges::ACTION_UNBLOCK => {
+            let path = crate::core::reveal::target_for(&result_id)
+                .ok_or_else(|| "This result has no file path".to_string())?;
+            crate::utils::file_metadata::remove_zone_identifier(std::path::Path::new(&path))
+                .map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::message("File unblocked"));
+        }
+        crate::core::file_badges::ACTION_SHOW_ORIGIN => {
+            let path = crate::core::reveal::target_for(&result_id)
+                .ok_or_else(|| "This result has no file path".to_string())?;
+            let origin = crate::core::file_badges::origin(std::path::Path::new(&path))
+                .ok_or_else(|| "This file has no recorded download origin".to_string())?;
+            crate::clipboard::write_text(&origin).map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::message(format!("Downloaded from {} (copied)", origin)));
+        }
+        // 置顶/取消置顶针对最近一次查询
+        crate::ranking::pins::ACTION_PIN | crate::ranking::pins::ACTION_UNPIN => {
+            let query = crate::ranking::pins::last_query()
+                .ok_or_else(|| "No query to pin this result for".to_string())?;
+            return if action_id == crate::ranking::pins::ACTION_PIN {
+                stats.pin_result(&query, &result_id, &plugin_id).await.map_err(|e| e.to_string())?;
+                Ok(ExecuteOutcome::message(format!("Pinned for \"{}\"", query)))
+            } else {
+                stats.unpin_result(&query, &result_id, &plugin_id).await.map_err(|e| e.to_string())?;
+                Ok(ExecuteOutcome::message(format!("Unpinned for \"{}\"", query)))
+            };
+        }
+        // 系统右键菜单项（文件结果的 ID 即路径）
+        id if id.starts_with(crate::shell_menu::SHELL_ACTION_PREFIX) => {
+            let path = std::path::PathBuf::from(&result_id);
+            let shell_action = id.to_string();
+            tokio::task::spawn_blocking(move || crate::shell_menu::invoke(&path, &shell_action))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        _ => {}
+    }
+    
+    // 🔥 破坏性动作先返回预演信息（文件大小、子进程等），由前端确认后凭令牌继续
+    if !confirmed {
+        let dry_run = manager
+            .dry_run(&result_id, &action_id, &plugin_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(dry_run) = dry_run {
+            tracing::info!("⚠️ Action '{}' on '{}' needs confirmation", action_id, title);
+            let pending = PendingAction { result_id, action_id, plugin_id, title, subtitle, icon };
+            return Ok(ExecuteOutcome::Confirm(
+                crate::core::confirmation::CONFIRMATIONS.issue(pending, dry_run),
+            ));
+        }
+    }
+    
     // 记录统计
     let _ = stats.record_result_click(&result_id, &plugin_id, &title).await;
+    if let Some(context_id) = crate::workspace_context::WORKSPACE_CONTEXTS.active_id() {
+        let _ = stats.record_context_click(&context_id, &result_id, &plugin_id, &title).await;
+    }
     let _ = stats.record_plugin_usage(&plugin_id).await;
     
     // 执行操作
-    let result = manager.execute(&result_id, &action_id, &plugin_id).await.map_err(|e| e.to_string());
+    let result = manager
+        .execute(&result_id, &action_id, &plugin_id)
+        .await
+        .map(|outcome| crate::core::follow_up::FOLLOW_UPS.apply(&plugin_id, outcome))
+        .map_err(|e| e.to_string());
+    
+    // 🔥 目标文件已不存在：同时清除点击/MRU 记录，不记入运行历史
+    if let Ok(ExecuteOutcome::FileMissing { .. }) = &result {
+        if let Err(e) = stats.forget_result(&result_id, &plugin_id).await {
+            tracing::warn!("Failed to forget missing result {}: {}", result_id, e);
+        }
+        return result;
+    }
     
     // 如果执行成功，记录到运行历史（排除一些特殊插件）
     if result.is_ok() && !matches!(plugin_id.as_str(), 
@@ -126,10 +684,212 @@ pub async fn execute_action(
     result
 }
 
+/// 在当前二级选择的候选中过滤（未处于二级选择时返回 None）
+#[tauri::command]
+pub async fn filter_follow_up(search: String) -> Result<Option<FollowUp>, String> {
+    Ok(crate::core::follow_up::FOLLOW_UPS.filter(&search))
+}
+
+/// 返回上一级选择，回到根查询时返回 None
+#[tauri::command]
+pub async fn follow_up_back() -> Result<Option<FollowUp>, String> {
+    Ok(crate::core::follow_up::FOLLOW_UPS.back())
+}
+
+/// 退出二级选择
+#[tauri::command]
+pub async fn cancel_follow_up() -> Result<(), String> {
+    crate::core::follow_up::FOLLOW_UPS.clear();
+    Ok(())
+}
+
+/// 打开前端视图事件
+const OPEN_VIEW_EVENT: &str = "open-view";
+
+/// 打开 AI 对话并预填问题事件
+const AI_ASK_EVENT: &str = "ai-ask";
+
+/// 执行无结果备选
+async fn run_fallback(
+    result_id: &str,
+    app: &tauri::AppHandle,
+    manager: &PluginManager,
+    storage: &StorageManager,
+) -> Result<ExecuteOutcome, String> {
+    use crate::core::fallback;
+    use crate::storage::FallbackKind;
+    
+    let (kind, query) = fallback::parse_result_id(result_id)
+        .ok_or_else(|| format!("Unknown fallback '{}'", result_id))?;
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    
+    tracing::info!("🪂 Running fallback {:?} for '{}'", kind, query);
+    match kind {
+        FallbackKind::WebSearch => {
+            let url = fallback::search_url(&config.fallback.web_search_url, query);
+            crate::plugin::web_search::WebSearchPlugin::open_url(&url).await.map_err(|e| e.to_string())?;
+            Ok(ExecuteOutcome::Hide)
+        }
+        FallbackKind::AiAsk => {
+            app.emit(AI_ASK_EVENT, query).map_err(|e| e.to_string())?;
+            Ok(ExecuteOutcome::Hide)
+        }
+        FallbackKind::CreateTodo => {
+            let path = crate::quick_capture::inbox_path(&config.capture).map_err(|e| e.to_string())?;
+            let (inbox, text, now) = (path.clone(), query.to_string(), chrono::Local::now());
+            tokio::task::spawn_blocking(move || crate::quick_capture::append_todo(&inbox, &text, now))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?;
+            Ok(ExecuteOutcome::message(format!("To-do added to {}", path.display())))
+        }
+        FallbackKind::CreateSnippet => {
+            let snippets = manager
+                .get_plugin::<crate::plugin::snippets::SnippetsPlugin>()
+                .ok_or("Snippets plugin not found")?;
+            let snippet = snippets
+                .save_snippet(None, crate::quick_capture::snippet_name(query), String::new(), query.to_string(), false)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(ExecuteOutcome::message(format!("Snippet \"{}\" saved", snippet.name)))
+        }
+    }
+}
+
+/// 执行命令面板条目
+async fn run_palette_entry(
+    entry: &crate::plugin::settings::PaletteEntry,
+    app: &tauri::AppHandle,
+    manager: &PluginManager,
+    stats: &StatisticsManager,
+    storage: &StorageManager,
+) -> Result<ExecuteOutcome, String> {
+    use crate::plugin::settings::{MaintenanceTask, PaletteAction};
+    
+    tracing::info!("🎛️ Running palette command: {}", entry.id);
+    match entry.action {
+        PaletteAction::OpenView(view) => {
+            app.emit(OPEN_VIEW_EVENT, view).map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::Hide);
+        }
+        PaletteAction::Toggle { get, set } => {
+            let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+            let enabled = !get(&config);
+            set(&mut config, enabled);
+            storage.save_config(&config).await.map_err(|e| e.to_string())?;
+            return Ok(ExecuteOutcome::message(format!(
+                "{}: {}",
+                entry.title,
+                if enabled { "On" } else { "Off" }
+            )));
+        }
+        PaletteAction::Maintenance(MaintenanceTask::ClearCache) => {
+            storage.clear_cache().await.map_err(|e| e.to_string())?;
+        }
+        PaletteAction::Maintenance(MaintenanceTask::ClearStatistics) => {
+            stats.cleanup_old_data().await.map_err(|e| e.to_string())?;
+        }
+        PaletteAction::Maintenance(MaintenanceTask::ResetQueryMetrics) => {
+            manager.reset_query_metrics();
+        }
+        PaletteAction::Maintenance(MaintenanceTask::ReloadPlugin(plugin_id)) => {
+            manager.reload_plugin(plugin_id).await.map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(ExecuteOutcome::message(format!("{}: done", entry.title)))
+}
+
 /// 获取插件列表
 #[tauri::command]
 pub async fn get_plugins(manager: State<'_, PluginManager>) -> Result<Vec<PluginMetadata>, String> {
-    Ok(manager.get_plugins())
+    let mut plugins = manager.get_plugins();
+    // 图片图标登记到 preview:// 协议，前端直接按 URL 加载
+    for plugin in plugins.iter_mut() {
+        if let WoxImage::File(path) = &plugin.icon {
+            plugin.branding.icon_asset = crate::preview::assets::register_file(std::path::Path::new(path));
+        }
+    }
+    Ok(plugins)
+}
+
+/// 动态注册插件（无需重启即可启用）
+#[tauri::command]
+pub async fn register_plugin(
+    plugin_id: String,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    manager.load_plugin(&plugin_id).await.map_err(|e| e.to_string())
+}
+
+/// 注销插件，返回插件是否存在
+#[tauri::command]
+pub async fn unregister_plugin(
+    plugin_id: String,
+    manager: State<'_, PluginManager>,
+) -> Result<bool, String> {
+    Ok(manager.unregister_plugin(&plugin_id).is_some())
+}
+
+/// 重新加载插件（更新后生效）
+#[tauri::command]
+pub async fn reload_plugin(
+    plugin_id: String,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    manager.reload_plugin(&plugin_id).await.map_err(|e| e.to_string())
+}
+
+/// 获取插件查询耗时统计
+#[tauri::command]
+pub async fn get_plugin_query_metrics(
+    manager: State<'_, PluginManager>,
+) -> Result<Vec<crate::plugin::query_metrics::PluginQueryStats>, String> {
+    Ok(manager.query_metrics())
+}
+
+/// 清空插件查询耗时统计
+#[tauri::command]
+pub async fn reset_plugin_query_metrics(manager: State<'_, PluginManager>) -> Result<(), String> {
+    manager.reset_query_metrics();
+    Ok(())
+}
+
+/// 启用插件（持久化到配置并立即生效）
+#[tauri::command]
+pub async fn enable_plugin(
+    plugin_id: String,
+    manager: State<'_, PluginManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    set_plugin_enabled(&plugin_id, true, &manager, &storage).await
+}
+
+/// 禁用插件（持久化到配置并立即生效）
+#[tauri::command]
+pub async fn disable_plugin(
+    plugin_id: String,
+    manager: State<'_, PluginManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    set_plugin_enabled(&plugin_id, false, &manager, &storage).await
+}
+
+async fn set_plugin_enabled(
+    plugin_id: &str,
+    enabled: bool,
+    manager: &PluginManager,
+    storage: &StorageManager,
+) -> Result<(), String> {
+    if !manager.is_registered(plugin_id) {
+        return Err(format!("Plugin '{}' is not registered", plugin_id));
+    }
+    
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.plugins.set_enabled(plugin_id, enabled);
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    
+    manager.set_plugin_enabled(plugin_id, enabled);
+    Ok(())
 }
 
 /// 获取插件配置
@@ -147,14 +907,52 @@ pub async fn save_plugin_config(
     plugin_id: String,
     config: serde_json::Value,
     storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+    clipboard: State<'_, ClipboardManager>,
 ) -> Result<(), String> {
+    // 🔒 剪贴板忽略列表、OCR 与敏感内容自动清除设置即时生效（刚开启 OCR 时补做历史图片）
+    if plugin_id == "clipboard" {
+        crate::clipboard_ignore::configure(&config);
+        crate::clipboard::configure_secret_clear(&config);
+        if crate::clipboard_ocr::configure(&config) {
+            clipboard.backfill_ocr();
+        }
+    }
+    // 🧮 计算器数字格式区域即时生效
+    if plugin_id == "calculator" {
+        if let Some(calculator) = manager.get_plugin::<crate::plugin::calculator::CalculatorPlugin>() {
+            calculator.configure(&config);
+        }
+    }
+    // 🌐 自定义搜索引擎即时生效
+    if plugin_id == "web_search" {
+        if let Some(web_search) = manager.get_plugin::<crate::plugin::web_search::WebSearchPlugin>() {
+            web_search.configure(&config);
+        }
+    }
+    // 🗂️ 标签页搜索的调试端口即时生效
+    if plugin_id == "browser" {
+        if let Some(browser) = manager.get_plugin::<crate::plugin::browser::BrowserPlugin>() {
+            browser.configure(&config);
+        }
+    }
+    // 💱 汇率接口与缓存有效期即时生效
+    if plugin_id == "unit_converter" {
+        if let Some(unit_converter) = manager.get_plugin::<crate::plugin::unit_converter::UnitConverterPlugin>() {
+            unit_converter.configure(&config);
+        }
+    }
+    // ⏳ 最短查询长度 / 防抖即时生效
+    manager.set_query_overrides(&plugin_id, &config);
     storage.save_plugin_config(&plugin_id, config).await.map_err(|e| e.to_string())
 }
 
 /// 显示应用
 #[tauri::command]
 pub async fn show_app(window: tauri::Window) -> Result<(), String> {
-    // 🔥 显示前先居中窗口
+    crate::hotkey::input::remember_foreground_window();
+    // 🔥 显示前按目标显示器 DPI 调整尺寸并居中窗口
+    fit_window_to_monitor(&window);
     window.center().map_err(|e| e.to_string())?;
     window.show().map_err(|e| e.to_string())?;
     window.set_focus().map_err(|e| e.to_string())?;
@@ -166,6 +964,7 @@ pub async fn show_app(window: tauri::Window) -> Result<(), String> {
 pub async fn hide_app(window: tauri::Window) -> Result<(), String> {
     // 发送隐藏事件到前端，让前端根据配置清空搜索结果
     let _ = window.emit("app-hiding", ());
+    crate::core::follow_up::FOLLOW_UPS.clear();
     window.hide().map_err(|e| e.to_string())?;
     Ok(())
 }
@@ -176,7 +975,9 @@ pub async fn toggle_app(window: tauri::Window) -> Result<(), String> {
     if window.is_visible().map_err(|e| e.to_string())? {
         window.hide().map_err(|e| e.to_string())?;
     } else {
-        // 🔥 显示前先居中窗口
+        crate::hotkey::input::remember_foreground_window();
+        // 🔥 显示前按目标显示器 DPI 调整尺寸并居中窗口
+        fit_window_to_monitor(&window);
         window.center().map_err(|e| e.to_string())?;
         window.show().map_err(|e| e.to_string())?;
         window.set_focus().map_err(|e| e.to_string())?;
@@ -184,6 +985,73 @@ pub async fn toggle_app(window: tauri::Window) -> Result<(), String> {
     Ok(())
 }
 
+/// 前端输入框已获得焦点，完成本次窗口显示的延迟计时
+#[tauri::command]
+pub async fn report_show_input_ready(
+    app: tauri::AppHandle,
+) -> Result<Option<crate::hotkey::show_latency::ShowLatency>, String> {
+    Ok(crate::hotkey::show_latency::input_ready(&app))
+}
+
+/// 获取最近的窗口显示延迟统计
+#[tauri::command]
+pub async fn get_show_latency_stats() -> Result<crate::hotkey::show_latency::ShowLatencySummary, String> {
+    Ok(crate::hotkey::show_latency::summary())
+}
+
+/// 按窗口所在显示器的缩放比例设置窗口尺寸
+fn fit_window_to_monitor(window: &tauri::Window) {
+    let monitor = window.current_monitor().ok().flatten();
+    if let Some(size) = crate::window_geometry::WINDOW_GEOMETRY.size_for_monitor(monitor.as_ref()) {
+        if let Err(e) = window.set_size(size) {
+            tracing::warn!("Failed to resize window: {}", e);
+        }
+    }
+}
+
+/// 为窗口当前所在的显示器单独设置窗口尺寸（逻辑像素）
+#[tauri::command]
+pub async fn set_monitor_window_size(
+    width: u32,
+    height: u32,
+    window: tauri::Window,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    let monitor = window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .ok_or("No monitor found for window")?;
+    let name = monitor.name().cloned().ok_or("Monitor has no name")?;
+    
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.appearance.monitor_sizes.insert(name, crate::storage::WindowSize { width, height });
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    crate::window_geometry::WINDOW_GEOMETRY.configure(&config.appearance);
+    
+    fit_window_to_monitor(&window);
+    window.center().map_err(|e| e.to_string())
+}
+
+/// 重置窗口尺寸与位置（恢复默认尺寸，清除显示器覆盖和视图位置记忆）
+#[tauri::command]
+pub async fn reset_window_geometry(
+    window: tauri::Window,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    use crate::window_geometry::{DEFAULT_WINDOW_SIZE, WINDOW_GEOMETRY};
+    
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.appearance.window_width = DEFAULT_WINDOW_SIZE.width;
+    config.appearance.window_height = DEFAULT_WINDOW_SIZE.height;
+    config.appearance.monitor_sizes.clear();
+    config.appearance.window_positions = Default::default();
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    WINDOW_GEOMETRY.configure(&config.appearance);
+    
+    fit_window_to_monitor(&window);
+    window.center().map_err(|e| e.to_string())
+}
+
 /// 加载配置
 #[tauri::command]
 pub async fn load_config(storage: State<'_, StorageManager>) -> Result<AppConfig, String> {
@@ -199,16 +1067,67 @@ pub async fn get_config(storage: State<'_, StorageManager>) -> Result<AppConfig,
 /// 保存配置
 #[tauri::command]
 pub async fn save_config(
-    config: AppConfig,
+    mut config: AppConfig,
     storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    // 导入的配置不含本地 API 令牌：重新生成，旧令牌随之失效
+    if config.local_api.token.is_empty() {
+        config.local_api.token = crate::local_api::generate_token();
+        crate::local_api::LOCAL_API.set_token(config.local_api.token.clone());
+    }
+    storage.save_config(&config).await.map_err(|e| e.to_string())?;
+    manager.set_disabled_plugins(&config.plugins.disabled_plugins);
+    crate::window_geometry::WINDOW_GEOMETRY.configure(&config.appearance);
+    crate::http::HTTP_CLIENTS.configure(config.http);
+    if let Some(selection) = manager.get_plugin::<crate::plugin::selection::SelectionPlugin>() {
+        selection.set_language(&config.appearance.language);
+    }
+    Ok(())
+}
+
+/// 运行时更新主热键（无需重启；校验失败或冲突时原热键保持不变）
+///
+/// 同步命令在主线程执行（macOS 要求在主线程注册全局热键）
+#[tauri::command]
+pub fn update_hotkey(
+    hotkey: String,
+    hotkeys: State<'_, parking_lot::Mutex<crate::hotkey::HotkeyManager>>,
+) -> Result<(), String> {
+    hotkeys.lock().update_hotkey(&hotkey).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// 运行时重新注册命名热键绑定，返回注册失败的绑定（ID, 原因）；配置无效时不做任何改动
+#[tauri::command]
+pub fn update_hotkey_bindings(
+    bindings: Vec<crate::storage::HotkeyBinding>,
+    hotkeys: State<'_, parking_lot::Mutex<crate::hotkey::HotkeyManager>>,
+) -> Result<Vec<(String, String)>, String> {
+    hotkeys.lock().register_bindings(&bindings).map_err(|e| e.to_string())
+}
+
+/// 获取各提权功能的同意记录
+#[tauri::command]
+pub fn get_elevation_consents() -> Vec<crate::elevation::FeatureConsent> {
+    crate::elevation::ELEVATION.consents()
+}
+
+/// 修改提权功能的同意记录（设为 ask 可重新允许曾被拒绝的功能）
+#[tauri::command]
+pub fn set_elevation_consent(
+    feature: crate::elevation::ElevatedFeature,
+    decision: crate::elevation::ConsentDecision,
 ) -> Result<(), String> {
-    storage.save_config(&config).await.map_err(|e| e.to_string())
+    crate::elevation::ELEVATION
+        .set_consent(feature, decision)
+        .map_err(|e| e.to_string())
 }
 
 /// 切换 MFT 开关（Windows only）
 #[cfg(target_os = "windows")]
 #[tauri::command]
 pub async fn toggle_mft(
+    app: tauri::AppHandle,
     enabled: bool,
     storage: State<'_, StorageManager>,
 ) -> Result<(), String> {
@@ -234,36 +1153,44 @@ pub async fn toggle_mft(
     tracing::info!("✓ File Search plugin config updated: use_mft = {}", enabled);
     
     if enabled {
-        // 启动 MFT service 子进程（使用管理员权限）
+        // 启动 MFT service 子进程（通过提权助手；用户主动开启时清除之前拒绝的记录）
         tracing::info!("MFT enabled, starting MFT service subprocess with admin rights...");
+        use crate::elevation::{ConsentDecision, ElevatedFeature, HelperRequest, ELEVATION};
         
-        let exe_path = std::env::current_exe()
-            .map_err(|e| format!("Failed to get exe path: {}", e))?;
-        
-        // 使用 PowerShell Start-Process -Verb RunAs 请求管理员权限
-        let ps_command = format!(
-            "Start-Process -FilePath '{}' -ArgumentList '--mft-service' -Verb RunAs -WindowStyle Hidden",
-            exe_path.display()
-        );
+        if ELEVATION.consent(ElevatedFeature::Mft) == ConsentDecision::Denied {
+            ELEVATION.set_consent(ElevatedFeature::Mft, ConsentDecision::Ask).map_err(|e| e.to_string())?;
+        }
         
-        // 🔥 使用 CREATE_NO_WINDOW 标志隐藏控制台窗口
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        // 数据目录随参数传入（自定义数据目录时服务进程也能找到配置和索引）
+        let data_dir = crate::utils::paths::get_app_data_dir()
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .to_string();
+        let request = HelperRequest::StartMftService {
+            ui_pid: std::process::id(),
+            data_dir,
+            skip_scan: false,
+        };
         
-        Command::new("powershell.exe")
-            .args(["-WindowStyle", "Hidden", "-Command", &ps_command])
-            .creation_flags(CREATE_NO_WINDOW)
-            .spawn()
-            .map_err(|e| format!("Failed to start MFT service: {}", e))?;
+        // 等待 UAC 确认期间不阻塞异步运行时
+        tokio::task::spawn_blocking(move || ELEVATION.run(ElevatedFeature::Mft, request))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to start MFT service: {:#}", e))?;
         
-        tracing::info!("✓ MFT service launch requested (UAC prompt will appear)");
+        tracing::info!("✓ MFT service launch requested via elevated helper");
+        spawn_mft_progress_events(app);
     } else {
-        // 停止 MFT service（发送信号或杀掉进程）
+        // 停止 MFT service（优先通过 IPC 请求优雅退出，失败时强制终止进程）
         tracing::info!("MFT disabled, stopping MFT service...");
         
-        // 强制终止所有 MFT Service 进程
-        #[cfg(target_os = "windows")]
-        {
+        let stopped = crate::utils::paths::get_mft_database_dir()
+            .and_then(|dir| crate::mft_scanner::ipc::IpcClient::connect(&dir.to_string_lossy()))
+            .and_then(|mut client| client.shutdown());
+        
+        if let Err(e) = stopped {
+            tracing::warn!("IPC shutdown failed ({:#}), terminating MFT service process", e);
+            
             // 🔥 使用 CREATE_NO_WINDOW 标志隐藏控制台窗口
             use std::os::windows::process::CommandExt;
             const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -344,6 +1271,8 @@ pub async fn get_mft_status() -> Result<MftStatus, String> {
             drives: vec![],
             total_files: 0,
             message: "MFT database not found. Scanner may not be running.".to_string(),
+            service: None,
+            fallback_drives: crate::plugin::drive_index::mft_fallbacks(),
         });
     }
     
@@ -373,11 +1302,27 @@ pub async fn get_mft_status() -> Result<MftStatus, String> {
         }
     }
     
-    let is_ready = !drives.is_empty();
-    let message = if is_ready {
-        format!("MFT ready: {} drives, ~{} files indexed", drives.len(), total_files)
+    // 🔥 服务运行中时以 IPC 上报的驱动器状态为准
+    let service_status = crate::mft_scanner::ipc::fetch_status(&output_dir.to_string_lossy());
+    let is_ready = match &service_status {
+        Some(status) => !status.ready_drives().is_empty(),
+        None => !drives.is_empty(),
+    };
+    let progress = service_status.as_ref().and_then(|status| status.progress_message());
+    let message = match &service_status {
+        Some(_) if progress.is_some() => format!("MFT scanning: {}", progress.unwrap_or_default()),
+        Some(status) if is_ready => format!("MFT ready: drives {:?} ({:?})", status.ready_drives(), status.phase),
+        Some(status) => format!("MFT service is running ({:?}), waiting for initial scan...", status.phase),
+        None if is_ready => format!("MFT ready: {} drives, ~{} files indexed", drives.len(), total_files),
+        None => "MFT scanner is running initial scan...".to_string(),
+    };
+    
+    let fallback_drives = crate::plugin::drive_index::mft_fallbacks();
+    let message = if fallback_drives.is_empty() {
+        message
     } else {
-        "MFT scanner is running initial scan...".to_string()
+        let letters: Vec<String> = fallback_drives.iter().map(|d| format!("{}:", d.letter)).collect();
+        format!("{} (BFS fallback: {})", message, letters.join(", "))
     };
     
     Ok(MftStatus {
@@ -387,9 +1332,52 @@ pub async fn get_mft_status() -> Result<MftStatus, String> {
         drives,
         total_files,
         message,
+        service: service_status,
+        fallback_drives,
     })
 }
 
+/// 后台轮询 MFT Service 状态并向前端推送扫描进度（初始扫描结束或服务长时间未就绪时停止）
+#[cfg(target_os = "windows")]
+pub fn spawn_mft_progress_events(app: tauri::AppHandle) {
+    use crate::mft_scanner::ipc::{fetch_status, ServicePhase};
+    use std::time::{Duration, Instant};
+    
+    // 等待 UAC 确认和服务启动的最长时间
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(600);
+    
+    let output_dir = match crate::utils::paths::get_mft_database_dir() {
+        Ok(dir) => dir.to_string_lossy().to_string(),
+        Err(e) => {
+            tracing::warn!("Failed to get MFT database directory: {}", e);
+            return;
+        }
+    };
+    
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut connected = false;
+        
+        loop {
+            match fetch_status(&output_dir) {
+                Some(status) => {
+                    connected = true;
+                    let _ = app.emit(MFT_PROGRESS_EVENT, &status);
+                    if !matches!(status.phase, ServicePhase::Starting | ServicePhase::Scanning) {
+                        break;
+                    }
+                }
+                // 服务已退出，或始终未能启动
+                None if connected || started.elapsed() > CONNECT_TIMEOUT => break,
+                None => {}
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        
+        tracing::debug!("MFT progress events stopped");
+    });
+}
+
 #[cfg(not(target_os = "windows"))]
 #[tauri::command]
 pub async fn get_mft_status() -> Result<MftStatus, String> {
@@ -400,6 +1388,8 @@ pub async fn get_mft_status() -> Result<MftStatus, String> {
         drives: vec![],
         total_files: 0,
         message: "MFT is only available on Windows".to_string(),
+        service: None,
+        fallback_drives: Vec::new(),
     })
 }
 
@@ -411,6 +1401,11 @@ pub struct MftStatus {
     pub drives: Vec<MftDriveInfo>,
     pub total_files: u64,
     pub message: String,
+    /// MFT Service 上报的状态（含各驱动器扫描进度，服务未运行时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<crate::mft_scanner::ipc::ServiceStatus>,
+    /// MFT 索引不可用、已退回 BFS 索引的驱动器及原因
+    pub fallback_drives: Vec<crate::plugin::drive_index::DriveFallback>,
 }
 
 #[derive(serde::Serialize)]
@@ -452,6 +1447,19 @@ pub async fn read_file_preview(path: String) -> Result<preview::FilePreview, Str
     preview::read_file_preview(&path).await.map_err(|e| e.to_string())
 }
 
+/// 文件属性（预览面板“详细信息”页），hash 为 true 时额外计算 SHA-256
+#[tauri::command]
+pub async fn get_file_properties(
+    path: String,
+    hash: Option<bool>,
+) -> Result<preview::properties::FileProperties, String> {
+    let with_hash = hash.unwrap_or(false);
+    tokio::task::spawn_blocking(move || preview::properties::file_properties(&path, with_hash))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 /// 获取剪贴板历史
 #[tauri::command]
 pub async fn get_clipboard_history(
@@ -463,35 +1471,113 @@ pub async fn get_clipboard_history(
         .map_err(|e| e.to_string())
 }
 
-/// 搜索剪贴板
+/// 搜索剪贴板
+#[tauri::command]
+pub async fn search_clipboard(
+    query: String,
+    limit: Option<usize>,
+    clipboard: State<'_, ClipboardManager>,
+) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    clipboard.search(&query, limit.unwrap_or(50))
+        .map_err(|e| e.to_string())
+}
+
+/// 获取收藏的剪贴板项
+#[tauri::command]
+pub async fn get_clipboard_favorites(
+    clipboard: State<'_, ClipboardManager>,
+) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    clipboard.get_favorites()
+        .map_err(|e| e.to_string())
+}
+
+/// 复制到剪贴板
+#[tauri::command]
+pub async fn copy_to_clipboard(
+    content: String,
+    content_type: Option<String>,
+    clipboard: State<'_, ClipboardManager>,
+) -> Result<(), String> {
+    clipboard.copy_to_clipboard(&content, &content_type.unwrap_or("text".to_string()))
+        .map_err(|e| e.to_string())
+}
+
+/// 粘贴剪贴板条目到启动器显示前的窗口（可选变换：paste_plain / paste_upper / paste_lower / paste_json）
+#[tauri::command]
+pub async fn paste_clipboard_content(
+    content: String,
+    content_type: Option<String>,
+    transform: Option<String>,
+    window: tauri::Window,
+    clipboard: State<'_, ClipboardManager>,
+) -> Result<(), String> {
+    use crate::clipboard_paste::{self, PasteTransform};
+    
+    let content_type = content_type.unwrap_or("text".to_string());
+    let transform = match transform.as_deref() {
+        Some(id) => Some(PasteTransform::from_action_id(id).ok_or(format!("Unknown paste transform: {}", id))?),
+        None => None,
+    };
+    if transform.is_some() && content_type == "image" {
+        return Err("Images cannot be transformed".to_string());
+    }
+    
+    let _ = window.emit("app-hiding", ());
+    window.hide().map_err(|e| e.to_string())?;
+    
+    match transform {
+        Some(transform) => clipboard_paste::paste_text(&content, Some(transform)).map_err(|e| e.to_string()),
+        None => {
+            clipboard.copy_to_clipboard(&content, &content_type).map_err(|e| e.to_string())?;
+            clipboard_paste::paste_into_previous_window(true);
+            Ok(())
+        }
+    }
+}
+
+/// 把剪贴板条目固定到快速粘贴槽位（1-9，slot 为空时取消固定）
 #[tauri::command]
-pub async fn search_clipboard(
-    query: String,
-    limit: Option<usize>,
+pub async fn pin_clipboard_item(
+    id: String,
+    slot: Option<u8>,
     clipboard: State<'_, ClipboardManager>,
-) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
-    clipboard.search(&query, limit.unwrap_or(50))
+) -> Result<(), String> {
+    clipboard.set_pinned_slot(&id, slot)
         .map_err(|e| e.to_string())
 }
 
-/// 获取收藏的剪贴板项
+/// 获取固定在快速粘贴槽位中的条目
 #[tauri::command]
-pub async fn get_clipboard_favorites(
+pub async fn get_pinned_clipboard_items(
     clipboard: State<'_, ClipboardManager>,
 ) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
-    clipboard.get_favorites()
+    clipboard.get_pinned()
         .map_err(|e| e.to_string())
 }
 
-/// 复制到剪贴板
+/// 粘贴固定在槽位 n 的条目（前端绑定 Ctrl+Shift+1..9）
 #[tauri::command]
-pub async fn copy_to_clipboard(
-    content: String,
-    content_type: Option<String>,
+pub async fn paste_pinned_slot(
+    slot: u8,
+    window: tauri::Window,
     clipboard: State<'_, ClipboardManager>,
 ) -> Result<(), String> {
-    clipboard.copy_to_clipboard(&content, &content_type.unwrap_or("text".to_string()))
-        .map_err(|e| e.to_string())
+    let item = clipboard
+        .get_pinned_slot(slot)
+        .map_err(|e| e.to_string())?
+        .ok_or(format!("No clipboard item pinned to slot {}", slot))?;
+    clipboard.copy_to_clipboard(&item.content, &item.item_type)
+        .map_err(|e| e.to_string())?;
+    
+    // 启动器可见时先隐藏并把焦点还给之前的窗口
+    let visible = window.is_visible().unwrap_or(false);
+    if visible {
+        let _ = window.emit("app-hiding", ());
+        window.hide().map_err(|e| e.to_string())?;
+    }
+    crate::clipboard_paste::paste_into_previous_window(visible);
+    tracing::info!("📌 Pasted pinned clipboard slot {} (id={})", slot, item.id);
+    Ok(())
 }
 
 /// 删除剪贴板项
@@ -612,6 +1698,581 @@ pub async fn remove_search_history(
     history.remove(&query).await.map_err(|e| e.to_string())
 }
 
+// ==================== 工作区上下文 ====================
+
+/// 获取所有工作区上下文
+#[tauri::command]
+pub async fn list_workspace_contexts() -> Result<Vec<crate::workspace_context::WorkspaceContext>, String> {
+    Ok(crate::workspace_context::WORKSPACE_CONTEXTS.list())
+}
+
+/// 获取当前工作区上下文（None 表示默认）
+#[tauri::command]
+pub async fn get_active_workspace_context() -> Result<Option<crate::workspace_context::WorkspaceContext>, String> {
+    Ok(crate::workspace_context::WORKSPACE_CONTEXTS.active())
+}
+
+/// 切换工作区上下文（context_id 为空切回默认）
+#[tauri::command]
+pub async fn switch_workspace_context(context_id: Option<String>) -> Result<(), String> {
+    crate::workspace_context::WORKSPACE_CONTEXTS
+        .switch(context_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 添加或更新工作区上下文
+#[tauri::command]
+pub async fn save_workspace_context(
+    context: crate::workspace_context::WorkspaceContext,
+) -> Result<(), String> {
+    crate::workspace_context::WORKSPACE_CONTEXTS
+        .upsert(context)
+        .map_err(|e| e.to_string())
+}
+
+/// 删除工作区上下文
+#[tauri::command]
+pub async fn delete_workspace_context(context_id: String) -> Result<(), String> {
+    crate::workspace_context::WORKSPACE_CONTEXTS
+        .remove(&context_id)
+        .map_err(|e| e.to_string())
+}
+
+// ==================== 文本片段 ====================
+
+/// 获取全部文本片段
+#[tauri::command]
+pub async fn list_snippets(
+    manager: State<'_, PluginManager>,
+) -> Result<Vec<crate::plugin::snippets::Snippet>, String> {
+    let snippets = manager
+        .get_plugin::<crate::plugin::snippets::SnippetsPlugin>()
+        .ok_or("Snippets plugin not found")?;
+    Ok(snippets.list().await)
+}
+
+/// 新增或更新文本片段（id 为空时新增）
+#[tauri::command]
+pub async fn save_snippet(
+    id: Option<String>,
+    name: String,
+    keyword: String,
+    content: String,
+    secret: Option<bool>,
+    manager: State<'_, PluginManager>,
+) -> Result<crate::plugin::snippets::Snippet, String> {
+    let snippets = manager
+        .get_plugin::<crate::plugin::snippets::SnippetsPlugin>()
+        .ok_or("Snippets plugin not found")?;
+    snippets
+        .save_snippet(id, name, keyword, content, secret.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除文本片段
+#[tauri::command]
+pub async fn delete_snippet(
+    id: String,
+    manager: State<'_, PluginManager>,
+) -> Result<bool, String> {
+    let snippets = manager
+        .get_plugin::<crate::plugin::snippets::SnippetsPlugin>()
+        .ok_or("Snippets plugin not found")?;
+    snippets.delete_snippet(&id).await.map_err(|e| e.to_string())
+}
+
+// ==================== 快速记录 ====================
+
+/// 保存快速记录（按配置追加到收件箱或保存为文本片段）并隐藏窗口，返回保存位置说明
+#[tauri::command]
+pub async fn save_quick_capture(
+    text: String,
+    app: tauri::AppHandle,
+    manager: State<'_, PluginManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<String, String> {
+    use crate::quick_capture;
+    
+    if text.trim().is_empty() {
+        return Err("Nothing to save".to_string());
+    }
+    let config = storage.load_config().await.map_err(|e| e.to_string())?.capture;
+    
+    let saved_to = if config.target == quick_capture::TARGET_SNIPPET {
+        let snippets = manager
+            .get_plugin::<crate::plugin::snippets::SnippetsPlugin>()
+            .ok_or("Snippets plugin not found")?;
+        let snippet = snippets
+            .save_snippet(None, quick_capture::snippet_name(&text), String::new(), text.trim().to_string(), false)
+            .await
+            .map_err(|e| e.to_string())?;
+        format!("Snippet \"{}\"", snippet.name)
+    } else {
+        let path = quick_capture::inbox_path(&config).map_err(|e| e.to_string())?;
+        let now = chrono::Local::now();
+        let inbox = path.clone();
+        tokio::task::spawn_blocking(move || quick_capture::append_to_inbox(&inbox, &text, now))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+        path.display().to_string()
+    };
+    
+    quick_capture::hide(&app);
+    tracing::info!("📝 Quick capture saved to {}", saved_to);
+    Ok(saved_to)
+}
+
+/// 隐藏快速记录窗口（取消或失去焦点）
+#[tauri::command]
+pub async fn hide_quick_capture(app: tauri::AppHandle) -> Result<(), String> {
+    crate::quick_capture::hide(&app);
+    Ok(())
+}
+
+// ==================== 应用别名 ====================
+
+/// 获取全部应用及其别名（本地化名称、原始名称和用户自定义别名）
+#[tauri::command]
+pub async fn list_app_aliases(
+    manager: State<'_, PluginManager>,
+) -> Result<Vec<crate::plugin::app_search::AppAliases>, String> {
+    let apps = manager
+        .get_plugin::<crate::plugin::app_search::AppSearchPlugin>()
+        .ok_or("App search plugin not found")?;
+    Ok(apps.list_aliases().await)
+}
+
+/// 设置应用的用户别名（空列表表示清除），保存到 app_search 插件配置
+#[tauri::command]
+pub async fn set_app_aliases(
+    path: String,
+    aliases: Vec<String>,
+    storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    let apps = manager
+        .get_plugin::<crate::plugin::app_search::AppSearchPlugin>()
+        .ok_or("App search plugin not found")?;
+    let all_aliases = apps.set_user_aliases(&path, aliases).await;
+
+    let mut config = storage.get_plugin_config("app_search").await.map_err(|e| e.to_string())?;
+    if !config.is_object() {
+        config = serde_json::json!({});
+    }
+    config[crate::plugin::app_search::ALIASES_KEY] = all_aliases;
+    storage.save_plugin_config("app_search", config).await.map_err(|e| e.to_string())
+}
+
+// ==================== 自定义搜索引擎 ====================
+
+/// 获取全部搜索引擎（内置 + 自定义）
+#[tauri::command]
+pub async fn list_search_engines(
+    manager: State<'_, PluginManager>,
+) -> Result<Vec<crate::plugin::web_search::SearchEngine>, String> {
+    let web_search = manager
+        .get_plugin::<crate::plugin::web_search::WebSearchPlugin>()
+        .ok_or("Web search plugin not found")?;
+    Ok(web_search.engines())
+}
+
+/// 新增或修改自定义搜索引擎（original_keyword 为修改前的关键词），保存到 web_search 插件配置
+#[tauri::command]
+pub async fn save_search_engine(
+    engine: crate::plugin::web_search::SearchEngine,
+    original_keyword: Option<String>,
+    storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    let web_search = manager
+        .get_plugin::<crate::plugin::web_search::WebSearchPlugin>()
+        .ok_or("Web search plugin not found")?;
+    let custom = web_search
+        .save_engine(engine, original_keyword.as_deref())
+        .map_err(|e| e.to_string())?;
+    save_custom_search_engines(&storage, custom).await
+}
+
+/// 删除自定义搜索引擎
+#[tauri::command]
+pub async fn delete_search_engine(
+    keyword: String,
+    storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+) -> Result<(), String> {
+    let web_search = manager
+        .get_plugin::<crate::plugin::web_search::WebSearchPlugin>()
+        .ok_or("Web search plugin not found")?;
+    let custom = web_search.delete_engine(&keyword).map_err(|e| e.to_string())?;
+    save_custom_search_engines(&storage, custom).await
+}
+
+/// 从 OpenSearch 描述文件（或声明了描述文件的网页）导入搜索引擎，返回导入的引擎
+#[tauri::command]
+pub async fn import_opensearch_engine(
+    url: String,
+    keyword: Option<String>,
+    storage: State<'_, StorageManager>,
+    manager: State<'_, PluginManager>,
+) -> Result<crate::plugin::web_search::SearchEngine, String> {
+    let web_search = manager
+        .get_plugin::<crate::plugin::web_search::WebSearchPlugin>()
+        .ok_or("Web search plugin not found")?;
+    let engine = web_search.fetch_opensearch(&url, keyword).await.map_err(|e| e.to_string())?;
+    let custom = web_search.save_engine(engine.clone(), None).map_err(|e| e.to_string())?;
+    save_custom_search_engines(&storage, custom).await?;
+    tracing::info!("🌐 Imported search engine {} ({}) from {}", engine.name, engine.keyword, url);
+    Ok(engine)
+}
+
+async fn save_custom_search_engines(storage: &StorageManager, custom: serde_json::Value) -> Result<(), String> {
+    let mut config = storage.get_plugin_config("web_search").await.map_err(|e| e.to_string())?;
+    if !config.is_object() {
+        config = serde_json::json!({});
+    }
+    config[crate::plugin::web_search::CUSTOM_ENGINES_KEY] = custom;
+    storage.save_plugin_config("web_search", config).await.map_err(|e| e.to_string())
+}
+
+// ==================== 文字转语音 ====================
+
+/// 朗读文本（AI 回答、预览等）
+#[tauri::command]
+pub async fn speak_text(
+    text: String,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    crate::tts::TTS_SERVICE.speak(&text, &config.tts).map_err(|e| e.to_string())
+}
+
+/// 停止朗读
+#[tauri::command]
+pub async fn stop_speaking() -> Result<(), String> {
+    crate::tts::TTS_SERVICE.stop();
+    Ok(())
+}
+
+/// 是否正在朗读
+#[tauri::command]
+pub async fn is_speaking() -> Result<bool, String> {
+    Ok(crate::tts::TTS_SERVICE.is_speaking())
+}
+
+// ==================== 语音输入 ====================
+
+/// 语音输入状态
+#[derive(serde::Serialize)]
+pub struct VoiceStatus {
+    pub enabled: bool,
+    pub recording: bool,
+    pub configured: bool,
+    pub language: String,
+}
+
+/// 获取语音输入状态
+#[tauri::command]
+pub async fn get_voice_status(storage: State<'_, StorageManager>) -> Result<VoiceStatus, String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    Ok(VoiceStatus {
+        enabled: config.voice.enabled,
+        recording: crate::voice::VOICE_INPUT.is_recording(),
+        configured: !config.voice.whisper_path.is_empty()
+            && crate::voice::resolve_model_path(&config.voice).is_ok(),
+        language: config.voice.language,
+    })
+}
+
+/// 开始录音（界面上的麦克风按钮）
+#[tauri::command]
+pub async fn start_voice_input(
+    app: tauri::AppHandle,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    if !config.voice.enabled {
+        return Err("Voice input is disabled".to_string());
+    }
+    crate::voice::VOICE_INPUT.start_recording(&app).map_err(|e| e.to_string())
+}
+
+/// 停止录音并返回转写结果
+#[tauri::command]
+pub async fn stop_voice_input(
+    app: tauri::AppHandle,
+    storage: State<'_, StorageManager>,
+) -> Result<Option<crate::voice::VoiceTranscript>, String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    tokio::task::spawn_blocking(move || {
+        crate::voice::VOICE_INPUT.stop_and_transcribe(&app, &config.voice)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+// ==================== 文件拖拽 ====================
+
+/// 拖拽预览图标
+const DRAG_ICON: &[u8] = include_bytes!("../../icons/32x32.png");
+
+/// 获取文件的系统右键菜单项（作为结果的动态操作）
+#[tauri::command]
+pub async fn get_shell_context_actions(path: String) -> Result<Vec<Action>, String> {
+    tokio::task::spawn_blocking(move || crate::shell_menu::list_actions(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 从结果列表拖出文件：为给定路径创建系统原生拖拽源，
+/// 可直接拖放到资源管理器、邮件、聊天等应用
+#[tauri::command]
+pub async fn start_file_drag(
+    paths: Vec<String>,
+    window: tauri::WebviewWindow,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let files: Vec<std::path::PathBuf> = paths
+        .iter()
+        .map(std::path::PathBuf::from)
+        .filter(|p| p.exists())
+        .collect();
+    
+    if files.is_empty() {
+        return Err("No existing files to drag".to_string());
+    }
+    
+    // 拖拽必须在主线程发起
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.run_on_main_thread(move || {
+        #[cfg(target_os = "linux")]
+        let raw_window = window.gtk_window();
+        #[cfg(not(target_os = "linux"))]
+        let raw_window = tauri::Result::Ok(window.clone());
+        
+        let result = match raw_window {
+            Ok(raw_window) => {
+                let emitter = window.clone();
+                drag::start_drag(
+                    &raw_window,
+                    drag::DragItem::Files(files),
+                    drag::Image::Raw(DRAG_ICON.to_vec()),
+                    move |result, _cursor| {
+                        let dropped = matches!(result, drag::DragResult::Dropped);
+                        let _ = emitter.emit("file-drag:finished", dropped);
+                    },
+                    drag::Options::default(),
+                )
+                .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        let _ = tx.send(result);
+    })
+    .map_err(|e| e.to_string())?;
+    
+    rx.await.map_err(|e| e.to_string())?
+}
+
+// ==================== 划词搜索 ====================
+
+/// 获取最近一次划词捕获的文本
+#[tauri::command]
+pub async fn get_last_selection() -> Result<Option<String>, String> {
+    Ok(crate::hotkey::selection::LAST_SELECTION.read().clone())
+}
+
+/// 获取可选图标集（设置界面的主题选项）
+#[tauri::command]
+pub async fn get_icon_sets() -> Result<Vec<String>, String> {
+    Ok(crate::core::accessibility::ICON_SETS.iter().map(|s| s.to_string()).collect())
+}
+
+/// 获取插件数据存储用量（不传 plugin_id 时返回全部）
+#[tauri::command]
+pub async fn get_plugin_storage_usage(
+    plugin_id: Option<String>,
+    plugin_storage: State<'_, crate::plugin::plugin_storage::PluginStorageManager>,
+) -> Result<Vec<crate::plugin::plugin_storage::PluginStorageUsage>, String> {
+    let plugin_ids = match plugin_id {
+        Some(id) => vec![id],
+        None => plugin_storage.list_plugins(),
+    };
+
+    plugin_ids
+        .iter()
+        .map(|id| plugin_storage.for_plugin(id).and_then(|s| s.usage()))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())
+}
+
+/// 清空插件数据存储
+#[tauri::command]
+pub async fn clear_plugin_storage(
+    plugin_id: String,
+    plugin_storage: State<'_, crate::plugin::plugin_storage::PluginStorageManager>,
+) -> Result<(), String> {
+    plugin_storage
+        .for_plugin(&plugin_id)
+        .and_then(|s| s.clear())
+        .map_err(|e| e.to_string())
+}
+
+/// 后台下载文件到缓存目录（主题导入等），返回任务 ID，进度通过 download:progress 事件推送
+#[tauri::command]
+pub async fn start_download(
+    url: String,
+    file_name: String,
+    sha256: Option<String>,
+    storage: State<'_, StorageManager>,
+) -> Result<String, String> {
+    let file_name = std::path::Path::new(&file_name)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid file name")?;
+
+    Ok(crate::download::DOWNLOADS.start(crate::download::DownloadRequest {
+        url,
+        dest: storage.get_cache_dir().join("downloads").join(file_name),
+        sha256,
+        owner: "download".to_string(),
+    }))
+}
+
+/// 取消下载（保留已下载部分，重新下载时续传）
+#[tauri::command]
+pub async fn cancel_download(id: String) -> Result<bool, String> {
+    Ok(crate::download::DOWNLOADS.cancel(&id))
+}
+
+/// 列出下载任务
+#[tauri::command]
+pub async fn list_downloads() -> Result<Vec<crate::download::DownloadTask>, String> {
+    Ok(crate::download::DOWNLOADS.list())
+}
+
+/// 列出可下载的本地模型及安装状态
+#[tauri::command]
+pub async fn list_models() -> Result<Vec<crate::models::ModelInfo>, String> {
+    Ok(crate::models::MODELS.list())
+}
+
+/// 下载模型，返回下载任务 ID（进度通过 download:progress 事件推送）
+#[tauri::command]
+pub async fn download_model(model_id: String) -> Result<String, String> {
+    crate::models::MODELS.download(&model_id).map_err(|e| e.to_string())
+}
+
+/// 删除模型
+#[tauri::command]
+pub async fn delete_model(model_id: String) -> Result<(), String> {
+    crate::models::MODELS.delete(&model_id).map_err(|e| e.to_string())
+}
+
+/// 获取各功能的模型需求
+#[tauri::command]
+pub async fn get_model_requirements() -> Result<Vec<crate::models::ModelRequirement>, String> {
+    Ok(crate::models::MODELS.requirements())
+}
+
+/// 捕获一次查询的 tracing span 并导出为 Chrome Trace / 火焰图 JSON（用于在用户机器上排查性能问题）
+#[tauri::command]
+pub async fn capture_trace(
+    input: String,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    history: State<'_, crate::search_history::SearchHistoryManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<crate::profiling::CapturedTrace, String> {
+    use crate::profiling::{QUERY_SPAN, TRACE_CAPTURE};
+    
+    let query_id = crate::profiling::next_query_id();
+    TRACE_CAPTURE.arm(query_id);
+    
+    // 序列化阶段（正常查询由 Tauri 在返回后完成）放在同一个根 span 下
+    let root = tracing::info_span!(QUERY_SPAN, query_id, input = %input);
+    let outcome = async {
+        // 诊断查询不参与取消
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let results = run_query_stages(query_id, input, &cancel, &|_| {}, &manager, &stats, &history, &notes, &storage).await?;
+        tracing::info_span!("serialize", results = results.len())
+            .in_scope(|| serde_json::to_vec(&results))
+            .map_err(|e| e.to_string())
+    }
+    .instrument(root)
+    .await;
+    
+    let events = TRACE_CAPTURE
+        .finish(query_id)
+        .ok_or_else(|| "Trace capture was interrupted by another capture".to_string())?;
+    outcome?;
+    
+    crate::profiling::write_chrome_trace(query_id, &events).map_err(|e| e.to_string())
+}
+
+/// 获取安全模式状态
+#[tauri::command]
+pub async fn get_safe_mode_status() -> Result<crate::safe_mode::SafeModeStatus, String> {
+    Ok(crate::safe_mode::status())
+}
+
+/// 单独重置某个存储（config / stats / clipboard / indexes），返回是否需要重启生效
+#[tauri::command]
+pub async fn reset_store(
+    store: crate::safe_mode::RecoveryStore,
+    storage: State<'_, StorageManager>,
+    stats: State<'_, StatisticsManager>,
+    clipboard: State<'_, ClipboardManager>,
+) -> Result<bool, String> {
+    use crate::safe_mode::RecoveryStore;
+    
+    tracing::warn!("🛟 Resetting store: {:?}", store);
+    match store {
+        RecoveryStore::Config => {
+            storage.save_config(&AppConfig::default()).await.map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        RecoveryStore::Stats => {
+            stats.reset_all().await.map_err(|e| e.to_string())?;
+            Ok(false)
+        }
+        RecoveryStore::Clipboard => {
+            clipboard.reset().map_err(|e| e.to_string())?;
+            Ok(false)
+        }
+        RecoveryStore::Indexes => {
+            tokio::task::spawn_blocking(crate::safe_mode::reset_indexes)
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+    }
+}
+
+/// 退出安全模式：以正常模式重新启动
+#[tauri::command]
+pub async fn exit_safe_mode(app: tauri::AppHandle) -> Result<(), String> {
+    crate::safe_mode::mark_startup_succeeded();
+    
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != crate::safe_mode::SAFE_MODE_FLAG)
+        .collect();
+    std::process::Command::new(exe)
+        .args(args)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    
+    app.exit(0);
+    Ok(())
+}
+
 /// 获取搜索建议（根据前缀匹配）
 #[tauri::command]
 pub async fn get_search_suggestions(
@@ -631,6 +2292,16 @@ pub async fn record_search_execution(
     history.record_execution(&query).await.map_err(|e| e.to_string())
 }
 
+// ==================== 会话上下文 ====================
+
+/// 获取会话上下文（底部状态栏：ans、计时器、剪贴板数量、索引状态、搜索模式）
+///
+/// 后续变更通过 `session-context:updated` 事件推送，前端无需轮询
+#[tauri::command]
+pub async fn get_session_context() -> Result<crate::session_context::SessionContext, String> {
+    Ok(crate::session_context::SESSION_CONTEXT.snapshot())
+}
+
 // ==================== 插件沙盒管理 ====================
 
 /// 获取插件沙盒配置
@@ -1,5 +1,6 @@
 // 插件市场相关命令
 use crate::plugin::plugin_installer::{InstalledPlugin, PluginInstaller, PluginRegistry};
+use crate::plugin::PluginManager;
 use crate::plugin::plugin_store::{PluginDetails, PluginListItem, PluginStore, SearchParams};
 use anyhow::Result;
 use std::path::PathBuf;
@@ -74,6 +75,7 @@ pub async fn get_plugin_details(
 #[tauri::command]
 pub async fn install_plugin(
     state: State<'_, Arc<RwLock<PluginMarketState>>>,
+    manager: State<'_, PluginManager>,
     plugin_id: String,
     version: Option<String>,
 ) -> Result<InstalledPlugin, String> {
@@ -96,6 +98,9 @@ pub async fn install_plugin(
     // 3. 清理下载文件（可选）
     // tokio::fs::remove_file(&ilp_path).await.ok();
 
+    // 4. 启动插件进程并注册（无需重启）
+    register_installed(&manager, &installed).await;
+
     Ok(installed)
 }
 
@@ -103,6 +108,7 @@ pub async fn install_plugin(
 #[tauri::command]
 pub async fn uninstall_plugin(
     state: State<'_, Arc<RwLock<PluginMarketState>>>,
+    manager: State<'_, PluginManager>,
     plugin_id: String,
 ) -> Result<(), String> {
     let state = state.read().await;
@@ -111,13 +117,18 @@ pub async fn uninstall_plugin(
         .installer
         .uninstall(&plugin_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    // 从运行中的插件注册表移除（无需重启）
+    manager.unregister_plugin(&plugin_id);
+    Ok(())
 }
 
 /// 更新插件
 #[tauri::command]
 pub async fn update_plugin(
     state: State<'_, Arc<RwLock<PluginMarketState>>>,
+    manager: State<'_, PluginManager>,
     plugin_id: String,
 ) -> Result<InstalledPlugin, String> {
     let state = state.read().await;
@@ -129,13 +140,17 @@ pub async fn update_plugin(
         .await
         .map_err(|e| format!("Download failed: {}", e))?;
 
-    // 2. 更新插件
+    // 2. 停止旧版本进程（Windows 下运行中的可执行文件无法删除），再更新插件
+    manager.unregister_plugin(&plugin_id);
     let installed = state
         .installer
         .update(&plugin_id, &ilp_path)
         .await
         .map_err(|e| format!("Update failed: {}", e))?;
 
+    // 3. 启动新版本插件进程
+    register_installed(&manager, &installed).await;
+
     Ok(installed)
 }
 
@@ -153,6 +168,7 @@ pub async fn list_installed_plugins(
 #[tauri::command]
 pub async fn toggle_plugin(
     state: State<'_, Arc<RwLock<PluginMarketState>>>,
+    manager: State<'_, PluginManager>,
     plugin_id: String,
     enabled: bool,
 ) -> Result<(), String> {
@@ -162,7 +178,17 @@ pub async fn toggle_plugin(
         .registry
         .set_enabled(&plugin_id, enabled)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    // 同步运行中的插件进程
+    if enabled {
+        if let Some(installed) = state.registry.get_plugin(&plugin_id).await {
+            register_installed(&manager, &installed).await;
+        }
+    } else {
+        manager.unregister_plugin(&plugin_id);
+    }
+    Ok(())
 }
 
 /// 更新插件设置
@@ -264,14 +290,23 @@ pub async fn clear_plugin_cache(
 #[tauri::command]
 pub async fn install_plugin_from_file(
     state: State<'_, Arc<RwLock<PluginMarketState>>>,
+    manager: State<'_, PluginManager>,
     file_path: String,
 ) -> Result<InstalledPlugin, String> {
     let state = state.read().await;
 
     let path = PathBuf::from(file_path);
-    state
+    let installed = state
         .installer
         .install(&path)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    register_installed(&manager, &installed).await;
+    Ok(installed)
+}
+
+/// 启动已安装插件的进程并注册（失败只记录日志，安装结果不受影响）
+async fn register_installed(manager: &PluginManager, installed: &InstalledPlugin) {
+    manager.register_external_plugins(std::slice::from_ref(installed)).await;
 }
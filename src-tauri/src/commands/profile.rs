@@ -0,0 +1,96 @@
+// 数据目录迁移、配置档案和设置导出相关命令
+
+use crate::statistics::StatisticsManager;
+use crate::storage::StorageManager;
+use tauri::State;
+
+/// 获取数据目录信息
+#[tauri::command]
+pub async fn get_data_location() -> Result<crate::utils::data_location::DataLocationInfo, String> {
+    crate::utils::data_location::info().map_err(|e| e.to_string())
+}
+
+/// 迁移数据目录（path 为空时恢复默认目录），完成后重启应用生效
+#[tauri::command]
+pub async fn set_data_location(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<crate::utils::data_location::MigrationReport, String> {
+    let target = path
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| std::path::PathBuf::from(p.trim()));
+    
+    // 统计待迁移的文件，放到阻塞线程执行
+    let report = tokio::task::spawn_blocking(move || crate::utils::data_location::migrate(target))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    
+    // 留出时间把结果返回给前端，再重启
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(800)).await;
+        app.restart();
+    });
+    
+    Ok(report)
+}
+
+/// 导出配置档案（设置、插件配置、搜索历史、统计、剪贴板收藏、工作流）到 zip 文件
+#[tauri::command]
+pub async fn export_profile(
+    path: String,
+    stats: State<'_, StatisticsManager>,
+) -> Result<crate::utils::profile_backup::ProfileReport, String> {
+    // 先写入缓冲中的查询统计
+    if let Err(e) = stats.flush_queries().await {
+        tracing::warn!("Failed to flush query statistics: {}", e);
+    }
+    
+    let dest = std::path::PathBuf::from(path.trim());
+    tokio::task::spawn_blocking(move || crate::utils::profile_backup::export(&dest))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 导入配置档案，完成后重启应用生效
+#[tauri::command]
+pub async fn import_profile(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<crate::utils::profile_backup::ProfileReport, String> {
+    let archive = std::path::PathBuf::from(path.trim());
+    let report = tokio::task::spawn_blocking(move || crate::utils::profile_backup::import(&archive))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    
+    // 留出时间把结果返回给前端，再重启
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(800)).await;
+        app.restart();
+    });
+    
+    Ok(report)
+}
+
+/// 导出设置（应用配置 + 结果备注 + 结果别名）为 JSON
+#[tauri::command]
+pub async fn export_settings(
+    storage: State<'_, StorageManager>,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+    plugin_storage: State<'_, crate::plugin::plugin_storage::PluginStorageManager>,
+) -> Result<String, String> {
+    // 🔒 导出文件可能被分享，不包含本地 API 令牌
+    let config = storage.load_config().await.map_err(|e| e.to_string())?.redacted();
+    let plugin_data = plugin_storage.export_all().map_err(|e| e.to_string())?;
+    let export = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "config": config,
+        "result_notes": notes.list_notes().await,
+        "result_aliases": crate::result_aliases::RESULT_ALIASES.list(),
+        "plugin_data": plugin_data,
+    });
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
@@ -0,0 +1,160 @@
+// 自定义排序规则相关命令
+
+use super::{collect_frecency, collect_usage_stats};
+use crate::plugin::PluginManager;
+use crate::ranking::rules::{CompiledRules, ScoringRule};
+use crate::ranking::IntelligentRanker;
+use crate::statistics::StatisticsManager;
+use crate::storage::StorageManager;
+use tauri::State;
+
+/// 排序规则试运行结果（单条）
+#[derive(serde::Serialize)]
+pub struct RuleDryRunEntry {
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    pub plugin_id: String,
+    pub base_rank: usize,
+    pub base_score: i32,
+    pub final_rank: usize,
+    pub final_score: i32,
+    pub adjustment: i32,
+    pub matched_rules: Vec<String>,
+}
+
+/// 获取自定义排序规则
+#[tauri::command]
+pub async fn get_ranking_rules(
+    storage: State<'_, StorageManager>,
+) -> Result<Vec<ScoringRule>, String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    Ok(config.ranking.rules)
+}
+
+/// 保存自定义排序规则（保存前校验）
+#[tauri::command]
+pub async fn save_ranking_rules(
+    rules: Vec<ScoringRule>,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    CompiledRules::compile(&rules).map_err(|e| e.to_string())?;
+    
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.ranking.rules = rules;
+    storage.save_config(&config).await.map_err(|e| e.to_string())
+}
+
+/// 获取排序配置（因素权重、插件分数倍率、自定义规则）
+#[tauri::command]
+pub async fn get_ranking_config(
+    storage: State<'_, StorageManager>,
+) -> Result<crate::storage::RankingConfig, String> {
+    let config = storage.load_config().await.map_err(|e| e.to_string())?;
+    Ok(config.ranking)
+}
+
+/// 保存排序权重和插件分数倍率（自定义规则通过 save_ranking_rules 保存）
+#[tauri::command]
+pub async fn set_ranking_config(
+    weights: crate::ranking::RankingWeights,
+    plugin_boosts: std::collections::HashMap<String, f64>,
+    storage: State<'_, StorageManager>,
+) -> Result<(), String> {
+    weights.validate().map_err(|e| e.to_string())?;
+    crate::ranking::validate_plugin_boosts(&plugin_boosts).map_err(|e| e.to_string())?;
+    
+    let mut config = storage.load_config().await.map_err(|e| e.to_string())?;
+    config.ranking.weights = weights;
+    config.ranking.plugin_boosts = plugin_boosts;
+    storage.save_config(&config).await.map_err(|e| e.to_string())
+}
+
+/// 把结果置顶到某个查询的最前面
+#[tauri::command]
+pub async fn pin_result(
+    query: String,
+    result_id: String,
+    plugin_id: String,
+    stats: State<'_, StatisticsManager>,
+) -> Result<(), String> {
+    stats.pin_result(&query, &result_id, &plugin_id).await.map_err(|e| e.to_string())
+}
+
+/// 取消结果在某个查询下的置顶，返回是否存在该置顶
+#[tauri::command]
+pub async fn unpin_result(
+    query: String,
+    result_id: String,
+    plugin_id: String,
+    stats: State<'_, StatisticsManager>,
+) -> Result<bool, String> {
+    stats.unpin_result(&query, &result_id, &plugin_id).await.map_err(|e| e.to_string())
+}
+
+/// 试运行排序规则：对示例查询分别按无规则/有规则排序，展示规则带来的变化
+/// rules 为空时使用已保存的规则
+#[tauri::command]
+pub async fn dry_run_ranking_rules(
+    query: String,
+    rules: Option<Vec<ScoringRule>>,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    storage: State<'_, StorageManager>,
+) -> Result<Vec<RuleDryRunEntry>, String> {
+    let ranking = storage.load_config().await.map_err(|e| e.to_string())?.ranking;
+    let rules = rules.unwrap_or(ranking.rules);
+    let compiled = CompiledRules::compile(&rules).map_err(|e| e.to_string())?;
+    
+    let results = manager.query(&query).await.map_err(|e| e.to_string())?;
+    let (usage_stats, mru_ids) = collect_usage_stats(&stats, &results).await;
+    let query_affinity = stats.get_query_affinity(&query).await.unwrap_or_default();
+    let pins = stats.get_pins(&query).await.unwrap_or_default();
+    let frecency = collect_frecency(&stats, &results).await;
+    
+    let mut base_results = results.clone();
+    IntelligentRanker::with_weights(ranking.weights.clone())
+        .with_plugin_boosts(ranking.plugin_boosts.clone())
+        .with_query_affinity(query_affinity.clone())
+        .with_frecency(frecency.clone())
+        .with_pins(pins.clone())
+        .rank_results(&mut base_results, &query, &usage_stats, &mru_ids);
+    
+    let ranker = IntelligentRanker::with_weights(ranking.weights)
+        .with_plugin_boosts(ranking.plugin_boosts)
+        .with_query_affinity(query_affinity)
+        .with_frecency(frecency)
+        .with_pins(pins)
+        .with_rules(compiled);
+    let mut final_results = results;
+    ranker.rank_results(&mut final_results, &query, &usage_stats, &mru_ids);
+    
+    let entries = final_results
+        .iter()
+        .enumerate()
+        .map(|(final_rank, result)| {
+            let (base_rank, base_score) = base_results
+                .iter()
+                .enumerate()
+                .find(|(_, r)| r.id == result.id && r.plugin_id == result.plugin_id)
+                .map(|(rank, r)| (rank + 1, r.score))
+                .unwrap_or((0, 0));
+            let (adjustment, matched_rules) = ranker.rule_adjustment(result, &query);
+            
+            RuleDryRunEntry {
+                id: result.id.clone(),
+                title: result.title.clone(),
+                subtitle: result.subtitle.clone(),
+                plugin_id: result.plugin_id.clone(),
+                base_rank,
+                base_score,
+                final_rank: final_rank + 1,
+                final_score: result.score,
+                adjustment,
+                matched_rules,
+            }
+        })
+        .collect();
+    
+    Ok(entries)
+}
@@ -0,0 +1,86 @@
+// 结果备注与别名相关命令
+
+use crate::core::types::{QueryResult, WoxImage};
+use tauri::State;
+
+/// 添加或更新结果备注（空备注会删除）
+#[tauri::command]
+pub async fn set_result_note(
+    result_id: String,
+    plugin_id: String,
+    title: String,
+    subtitle: Option<String>,
+    note: String,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+) -> Result<(), String> {
+    notes
+        .set_note(result_id, plugin_id, title, subtitle.unwrap_or_default(), note)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取结果备注
+#[tauri::command]
+pub async fn get_result_note(
+    result_id: String,
+    plugin_id: String,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+) -> Result<Option<crate::result_notes::ResultNote>, String> {
+    Ok(notes.get_note(&plugin_id, &result_id).await)
+}
+
+/// 删除结果备注
+#[tauri::command]
+pub async fn remove_result_note(
+    result_id: String,
+    plugin_id: String,
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+) -> Result<(), String> {
+    notes.remove_note(&plugin_id, &result_id).await.map_err(|e| e.to_string())
+}
+
+/// 获取全部结果备注
+#[tauri::command]
+pub async fn list_result_notes(
+    notes: State<'_, crate::result_notes::ResultNotesManager>,
+) -> Result<Vec<crate::result_notes::ResultNote>, String> {
+    Ok(notes.list_notes().await)
+}
+
+/// 为结果指定别名关键词（同一关键词会覆盖之前的结果）
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn set_result_alias(
+    keyword: String,
+    result_id: String,
+    plugin_id: String,
+    title: String,
+    subtitle: Option<String>,
+    icon: WoxImage,
+    context_data: Option<serde_json::Value>,
+) -> Result<crate::result_aliases::ResultAlias, String> {
+    let mut result = QueryResult::new(title)
+        .with_subtitle(subtitle.unwrap_or_default())
+        .with_icon(icon);
+    result.id = result_id;
+    result.plugin_id = plugin_id;
+    result.context_data = context_data.unwrap_or_default();
+    
+    crate::result_aliases::RESULT_ALIASES
+        .set(&keyword, &result)
+        .map_err(|e| e.to_string())
+}
+
+/// 删除结果别名
+#[tauri::command]
+pub async fn remove_result_alias(keyword: String) -> Result<(), String> {
+    crate::result_aliases::RESULT_ALIASES
+        .remove(&keyword)
+        .map_err(|e| e.to_string())
+}
+
+/// 获取全部结果别名
+#[tauri::command]
+pub async fn list_result_aliases() -> Result<Vec<crate::result_aliases::ResultAlias>, String> {
+    Ok(crate::result_aliases::RESULT_ALIASES.list())
+}
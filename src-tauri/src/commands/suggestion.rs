@@ -1,4 +1,7 @@
+use crate::commands::plugin_market::PluginMarketState;
+use crate::plugin::plugin_suggestions::{self, PluginCandidate, PluginSuggestion, DISMISSED_SUGGESTIONS};
 use crate::plugin::smart_suggestion::{SmartSuggestionEngine, Suggestion, SuggestionContext};
+use crate::plugin::PluginManager;
 use crate::statistics::StatisticsManager;
 use std::sync::Arc;
 use tauri::State;
@@ -71,3 +74,57 @@ pub async fn get_recent_suggestions(
     
     Ok(suggestions)
 }
+
+/// 参与插件推荐的无结果查询数量
+const ZERO_RESULT_SAMPLE: usize = 200;
+
+/// 参与插件推荐的插件市场热门插件数量
+const STORE_CANDIDATES: u32 = 50;
+
+/// 根据无结果查询推荐启用/安装插件（“你可能会喜欢”）
+#[tauri::command]
+pub async fn get_plugin_suggestions(
+    limit: Option<usize>,
+    manager: State<'_, PluginManager>,
+    stats: State<'_, StatisticsManager>,
+    market: State<'_, Arc<RwLock<PluginMarketState>>>,
+) -> Result<Vec<PluginSuggestion>, String> {
+    let zero_results = stats
+        .get_zero_result_queries(ZERO_RESULT_SAMPLE)
+        .await
+        .map_err(|e| e.to_string())?;
+    if zero_results.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates: Vec<PluginCandidate> = manager
+        .get_plugins()
+        .iter()
+        .filter(|metadata| !manager.is_plugin_enabled(&metadata.id))
+        .map(PluginCandidate::disabled)
+        .collect();
+
+    // 插件市场不可用（离线）时只推荐启用已安装的插件
+    match market.read().await.store.get_popular_plugins(STORE_CANDIDATES).await {
+        Ok(items) => candidates.extend(
+            items
+                .iter()
+                .filter(|item| !manager.is_registered(&item.id))
+                .map(PluginCandidate::store),
+        ),
+        Err(e) => tracing::debug!("Plugin store unavailable for suggestions: {}", e),
+    }
+
+    Ok(plugin_suggestions::suggest(
+        &zero_results,
+        &candidates,
+        &DISMISSED_SUGGESTIONS.ids(),
+        limit.unwrap_or(3),
+    ))
+}
+
+/// 忽略插件推荐（之后不再提示）
+#[tauri::command]
+pub async fn dismiss_plugin_suggestion(plugin_id: String) -> Result<(), String> {
+    DISMISSED_SUGGESTIONS.dismiss(&plugin_id).map_err(|e| e.to_string())
+}
@@ -0,0 +1,212 @@
+// 无障碍支持 - 为查询结果补充屏幕阅读器元数据（图标文字描述、操作角色），
+// 并在启用高对比度图标集时将 emoji 图标替换为高对比度 SVG
+
+use crate::core::types::{Action, QueryResult, WoxImage};
+use serde::{Deserialize, Serialize};
+
+/// 默认图标集
+pub const ICON_SET_DEFAULT: &str = "default";
+/// 高对比度图标集
+pub const ICON_SET_HIGH_CONTRAST: &str = "high-contrast";
+
+/// 可选图标集
+pub const ICON_SETS: &[&str] = &[ICON_SET_DEFAULT, ICON_SET_HIGH_CONTRAST];
+
+/// 结果的无障碍元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Accessibility {
+    /// 完整朗读文本（标题 + 副标题 + 图标描述）
+    pub label: String,
+    /// 图标的纯文本描述（emoji 无法被屏幕阅读器正确朗读）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_description: Option<String>,
+    pub actions: Vec<ActionAccessibility>,
+}
+
+/// 操作的无障碍元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionAccessibility {
+    pub id: String,
+    /// 角色提示：link / button / copy / destructive
+    pub role: String,
+    pub label: String,
+}
+
+/// emoji → (文字描述, 高对比度 SVG)
+const ICON_TABLE: &[(&str, &str, Option<&str>)] = &[
+    ("📁", "Folder", Some(SVG_FOLDER)),
+    ("📂", "Open folder", Some(SVG_FOLDER)),
+    ("📄", "File", Some(SVG_FILE)),
+    ("📝", "Note", Some(SVG_FILE)),
+    ("📋", "Clipboard", Some(SVG_CLIPBOARD)),
+    ("🗑️", "Delete", Some(SVG_TRASH)),
+    ("🔍", "Search", Some(SVG_SEARCH)),
+    ("🔗", "Link", Some(SVG_LINK)),
+    ("🌐", "Web", Some(SVG_GLOBE)),
+    ("🌍", "Translate", Some(SVG_GLOBE)),
+    ("⚙️", "Settings", Some(SVG_GEAR)),
+    ("🚀", "Application", Some(SVG_APP)),
+    ("📦", "Package", Some(SVG_APP)),
+    ("🧮", "Calculator", None),
+    ("🔢", "Number", None),
+    ("🤖", "AI assistant", None),
+    ("💬", "Message", None),
+    ("💭", "Thinking", None),
+    ("📏", "Unit converter", None),
+    ("⚖️", "Weight", None),
+    ("🌡️", "Temperature", None),
+    ("⏳", "Loading", None),
+    ("🕐", "History", None),
+    ("🔄", "Refresh", None),
+    ("🔌", "Integration", None),
+    ("🔊", "Read aloud", None),
+    ("📭", "Empty", None),
+    ("💾", "Save", None),
+    ("⭐", "Favorite", None),
+    ("✂️", "Selection", None),
+    ("⚡", "Quick action", None),
+    ("▶️", "Run", None),
+    ("ℹ️", "Information", None),
+    ("🗂️", "Context", None),
+    ("📖", "Dictionary", None),
+];
+
+const SVG_FOLDER: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><path d="M3 6h6l2 2h10v11H3z"/></svg>"#;
+const SVG_FILE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><path d="M6 2h8l4 4v16H6z"/><path d="M14 2v4h4"/></svg>"#;
+const SVG_CLIPBOARD: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><path d="M8 4H5v18h14V4h-3"/><path d="M8 2h8v4H8z"/></svg>"#;
+const SVG_TRASH: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><path d="M3 6h18M8 6V3h8v3M5 6l1 16h12l1-16"/></svg>"#;
+const SVG_SEARCH: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><circle cx="10" cy="10" r="7"/><path d="M15 15l7 7"/></svg>"#;
+const SVG_LINK: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><path d="M10 14a5 5 0 0 0 7 0l3-3a5 5 0 0 0-7-7l-1 1"/><path d="M14 10a5 5 0 0 0-7 0l-3 3a5 5 0 0 0 7 7l1-1"/></svg>"#;
+const SVG_GLOBE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><circle cx="12" cy="12" r="10"/><path d="M2 12h20M12 2a15 15 0 0 1 0 20M12 2a15 15 0 0 0 0 20"/></svg>"#;
+const SVG_GEAR: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><circle cx="12" cy="12" r="3"/><path d="M12 1v4M12 19v4M1 12h4M19 12h4M4.2 4.2l2.8 2.8M17 17l2.8 2.8M4.2 19.8L7 17M17 7l2.8-2.8"/></svg>"#;
+const SVG_APP: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2.5"><rect x="3" y="3" width="18" height="18" rx="3"/><path d="M3 8h18"/></svg>"#;
+
+/// emoji 的文字描述
+pub fn describe_emoji(emoji: &str) -> Option<&'static str> {
+    ICON_TABLE
+        .iter()
+        .find(|(e, _, _)| *e == emoji)
+        .map(|(_, description, _)| *description)
+}
+
+/// emoji 对应的高对比度 SVG
+fn high_contrast_svg(emoji: &str) -> Option<&'static str> {
+    ICON_TABLE
+        .iter()
+        .find(|(e, _, _)| *e == emoji)
+        .and_then(|(_, _, svg)| *svg)
+}
+
+/// 根据操作 ID 推断角色提示
+pub fn action_role(action_id: &str) -> &'static str {
+    match action_id {
+        "delete" | "remove" | "kill" | crate::result_notes::ACTION_REMOVE_NOTE => "destructive",
+        id if id.starts_with("copy") => "copy",
+        id if id.starts_with("open") || id == crate::core::reveal::ACTION_REVEAL => "link",
+        _ => "button",
+    }
+}
+
+/// 为结果生成无障碍元数据
+fn build(result: &QueryResult) -> Accessibility {
+    let icon_description = match &result.icon {
+        WoxImage::Emoji(emoji) => describe_emoji(emoji).map(|d| d.to_string()),
+        _ => None,
+    };
+
+    let mut label = result.title.clone();
+    if !result.subtitle.is_empty() {
+        label.push_str(", ");
+        label.push_str(&result.subtitle);
+    }
+    for badge in &result.badges {
+        label.push_str(", ");
+        label.push_str(&badge.label);
+    }
+    if let Some(description) = &icon_description {
+        label = format!("{}: {}", description, label);
+    }
+
+    Accessibility {
+        label,
+        icon_description,
+        actions: result.actions.iter().map(action_accessibility).collect(),
+    }
+}
+
+fn action_accessibility(action: &Action) -> ActionAccessibility {
+    let role = action_role(&action.id);
+    let label = if action.is_default {
+        format!("{} (default)", action.name)
+    } else {
+        action.name.clone()
+    };
+
+    ActionAccessibility {
+        id: action.id.clone(),
+        role: role.to_string(),
+        label,
+    }
+}
+
+/// 为查询结果补充无障碍元数据；高对比度图标集下替换 emoji 图标
+pub fn annotate(results: &mut [QueryResult], icon_set: &str) {
+    let high_contrast = icon_set == ICON_SET_HIGH_CONTRAST;
+
+    for result in results.iter_mut() {
+        result.accessibility = Some(build(result));
+
+        if high_contrast {
+            if let WoxImage::Emoji(emoji) = &result.icon {
+                if let Some(svg) = high_contrast_svg(emoji) {
+                    result.icon = WoxImage::Svg(svg.to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_describes_emoji_and_actions() {
+        let mut results = vec![QueryResult::new("Documents")
+            .with_subtitle("C:\\Users\\me\\Documents")
+            .with_icon(WoxImage::emoji("📁"))
+            .with_action(Action {
+                id: "delete".to_string(),
+                ..Action::new("Delete")
+            })];
+
+        annotate(&mut results, ICON_SET_DEFAULT);
+
+        let a11y = results[0].accessibility.as_ref().unwrap();
+        assert_eq!(a11y.icon_description.as_deref(), Some("Folder"));
+        assert_eq!(a11y.label, "Folder: Documents, C:\\Users\\me\\Documents");
+        assert_eq!(a11y.actions[0].role, "destructive");
+        assert!(matches!(results[0].icon, WoxImage::Emoji(_)));
+    }
+
+    #[test]
+    fn test_high_contrast_replaces_known_icons() {
+        let mut results = vec![
+            QueryResult::new("a").with_icon(WoxImage::emoji("📁")),
+            QueryResult::new("b").with_icon(WoxImage::emoji("🧮")),
+        ];
+
+        annotate(&mut results, ICON_SET_HIGH_CONTRAST);
+
+        assert!(matches!(results[0].icon, WoxImage::Svg(_)));
+        assert!(matches!(results[1].icon, WoxImage::Emoji(_)));
+    }
+
+    #[test]
+    fn test_action_role() {
+        assert_eq!(action_role("open_folder"), "link");
+        assert_eq!(action_role("copy_path"), "copy");
+        assert_eq!(action_role("kill"), "destructive");
+        assert_eq!(action_role("send"), "button");
+    }
+}
@@ -0,0 +1,80 @@
+// 时钟抽象 - 依赖当前时间的逻辑（时间衰减、过期清理）通过 Clock 取时间，测试时可替换为可控时钟
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// 当前时间来源
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// 共享时钟
+pub type SharedClock = Arc<dyn Clock>;
+
+/// 系统时钟
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 默认使用的系统时钟
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// 测试用时钟（时间只在手动调整时变化）
+#[cfg(test)]
+pub struct FakeClock {
+    now: parking_lot::Mutex<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> Arc<Self> {
+        Arc::new(Self {
+            now: parking_lot::Mutex::new(now),
+        })
+    }
+
+    /// 固定在 2024-01-01 00:00:00 UTC
+    pub fn fixed() -> Arc<Self> {
+        Self::new(DateTime::from_timestamp(1_704_067_200, 0).unwrap())
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock() = now;
+    }
+
+    pub fn advance(&self, duration: chrono::Duration) {
+        *self.now.lock() += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_clock() {
+        let clock = FakeClock::fixed();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(chrono::Duration::hours(2));
+        assert_eq!(clock.now() - start, chrono::Duration::hours(2));
+
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
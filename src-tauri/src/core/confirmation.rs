@@ -0,0 +1,137 @@
+// 破坏性动作确认 - 插件预演（dry_run）返回确认信息时，待执行的动作按令牌暂存，
+// 前端确认后凭令牌继续执行；令牌一次有效，超时作废
+
+use super::clock::{self, SharedClock};
+use super::types::{ConfirmationRequest, DryRun, WoxImage};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// 确认令牌有效期
+const CONFIRM_TTL_SECS: u64 = 120;
+
+/// 全局待确认动作
+pub static CONFIRMATIONS: Lazy<PendingConfirmations> = Lazy::new(|| PendingConfirmations::new(clock::system()));
+
+/// 待确认的动作（确认后按原参数执行）
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    pub result_id: String,
+    pub action_id: String,
+    pub plugin_id: String,
+    pub title: String,
+    pub subtitle: String,
+    pub icon: WoxImage,
+}
+
+pub struct PendingConfirmations {
+    clock: SharedClock,
+    pending: Mutex<HashMap<String, (PendingAction, DateTime<Utc>)>>,
+}
+
+impl PendingConfirmations {
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 暂存动作并生成确认请求（顺带清理过期令牌）
+    pub fn issue(&self, action: PendingAction, dry_run: DryRun) -> ConfirmationRequest {
+        let now = self.clock.now();
+        let expires_at = now + chrono::Duration::seconds(CONFIRM_TTL_SECS as i64);
+        let token = uuid::Uuid::new_v4().to_string();
+        let title = action.title.clone();
+
+        let mut pending = self.pending.lock();
+        pending.retain(|_, (_, expiry)| *expiry > now);
+        pending.insert(token.clone(), (action, expires_at));
+
+        ConfirmationRequest {
+            token,
+            title,
+            dry_run,
+            expires_in_secs: CONFIRM_TTL_SECS,
+        }
+    }
+
+    /// 取出令牌对应的动作（令牌只能使用一次，过期返回 None）
+    pub fn take(&self, token: &str) -> Option<PendingAction> {
+        let (action, expires_at) = self.pending.lock().remove(token)?;
+        (self.clock.now() <= expires_at).then_some(action)
+    }
+
+    /// 用户取消确认
+    pub fn cancel(&self, token: &str) -> bool {
+        self.pending.lock().remove(token).is_some()
+    }
+}
+
+/// 字节数的可读形式（1024 进制，保留一位小数）
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::FakeClock;
+
+    fn action() -> PendingAction {
+        PendingAction {
+            result_id: "C:\\temp\\build".to_string(),
+            action_id: "delete".to_string(),
+            plugin_id: "file_search".to_string(),
+            title: "build".to_string(),
+            subtitle: String::new(),
+            icon: WoxImage::emoji("📁"),
+        }
+    }
+
+    #[test]
+    fn test_token_is_single_use() {
+        let confirmations = PendingConfirmations::new(FakeClock::fixed());
+        let request = confirmations.issue(action(), DryRun::new("Delete build"));
+        assert_eq!(request.title, "build");
+        assert_eq!(request.dry_run.summary, "Delete build");
+
+        let taken = confirmations.take(&request.token).unwrap();
+        assert_eq!(taken.action_id, "delete");
+        assert!(confirmations.take(&request.token).is_none());
+        assert!(confirmations.take("unknown").is_none());
+    }
+
+    #[test]
+    fn test_expired_and_cancelled_tokens() {
+        let clock = FakeClock::fixed();
+        let confirmations = PendingConfirmations::new(clock.clone());
+
+        let expired = confirmations.issue(action(), DryRun::default());
+        clock.advance(chrono::Duration::seconds(CONFIRM_TTL_SECS as i64 + 1));
+        assert!(confirmations.take(&expired.token).is_none());
+
+        let cancelled = confirmations.issue(action(), DryRun::default());
+        assert!(confirmations.cancel(&cancelled.token));
+        assert!(confirmations.take(&cancelled.token).is_none());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
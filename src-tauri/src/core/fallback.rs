@@ -0,0 +1,153 @@
+// 无结果备选 - 没有任何插件返回结果时，按配置的顺序提供网页搜索、询问 AI、创建待办、保存片段等合成结果
+// 结果 ID 为 "<类型>:<查询>"，执行时由后端直接处理（询问 AI 由前端打开对话界面）
+
+use super::types::*;
+use crate::storage::{FallbackConfig, FallbackKind};
+use std::collections::HashSet;
+
+/// 备选结果的插件 ID
+pub const FALLBACK_PLUGIN_ID: &str = "fallback";
+
+/// 备选结果分组
+const FALLBACK_GROUP: &str = "No results";
+
+impl FallbackKind {
+    pub fn id(self) -> &'static str {
+        match self {
+            FallbackKind::WebSearch => "web_search",
+            FallbackKind::AiAsk => "ai_ask",
+            FallbackKind::CreateTodo => "create_todo",
+            FallbackKind::CreateSnippet => "create_snippet",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        [FallbackKind::WebSearch, FallbackKind::AiAsk, FallbackKind::CreateTodo, FallbackKind::CreateSnippet]
+            .into_iter()
+            .find(|kind| kind.id() == id)
+    }
+}
+
+/// 按备选链生成合成结果（已禁用和重复的条目跳过）
+pub fn results(query: &str, config: &FallbackConfig) -> Vec<QueryResult> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::new();
+    config
+        .chain
+        .iter()
+        .filter(|entry| entry.enabled && seen.insert(entry.kind))
+        .enumerate()
+        .map(|(index, entry)| {
+            // 询问 AI 会切换到对话界面，执行后不隐藏窗口
+            let prevent_hide = entry.kind == FallbackKind::AiAsk;
+            let (title, subtitle, icon, action) = match entry.kind {
+                FallbackKind::WebSearch => (
+                    format!("Search the web for \"{}\"", query),
+                    search_url(&config.web_search_url, query),
+                    "🌐",
+                    "Search",
+                ),
+                FallbackKind::AiAsk => (
+                    format!("Ask AI: {}", query),
+                    "Open AI chat with this question".to_string(),
+                    "💬",
+                    "Ask AI",
+                ),
+                FallbackKind::CreateTodo => (
+                    format!("Create to-do: {}", query),
+                    "Append to the quick capture inbox".to_string(),
+                    "☑️",
+                    "Create To-do",
+                ),
+                FallbackKind::CreateSnippet => (
+                    format!("Save as snippet: {}", query),
+                    "Create a text snippet from this text".to_string(),
+                    "📝",
+                    "Save Snippet",
+                ),
+            };
+
+            QueryResult {
+                id: format!("{}:{}", entry.kind.id(), query),
+                title,
+                subtitle,
+                icon: WoxImage::emoji(icon),
+                preview: None,
+                score: 100 - index as i32,
+                context_data: serde_json::Value::Null,
+                group: Some(FALLBACK_GROUP.to_string()),
+                plugin_id: FALLBACK_PLUGIN_ID.to_string(),
+                refreshable: false,
+                actions: vec![Action {
+                    id: "run".to_string(),
+                    name: action.to_string(),
+                    icon: None,
+                    is_default: true,
+                    prevent_hide,
+                    hotkey: None,
+                }],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// 解析备选结果 ID
+pub fn parse_result_id(result_id: &str) -> Option<(FallbackKind, &str)> {
+    let (kind, query) = result_id.split_once(':')?;
+    Some((FallbackKind::from_id(kind)?, query))
+}
+
+/// 用搜索 URL 模板生成搜索地址（模板没有 {query} 时追加到末尾）
+pub fn search_url(template: &str, query: &str) -> String {
+    let encoded = urlencoding::encode(query);
+    if template.contains("{query}") {
+        template.replace("{query}", &encoded)
+    } else {
+        format!("{}{}", template, encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FallbackEntry;
+
+    #[test]
+    fn test_results_follow_chain_order() {
+        let mut config = FallbackConfig::default();
+        config.chain = vec![
+            FallbackEntry { kind: FallbackKind::CreateSnippet, enabled: true },
+            FallbackEntry { kind: FallbackKind::AiAsk, enabled: false },
+            FallbackEntry { kind: FallbackKind::WebSearch, enabled: true },
+            FallbackEntry { kind: FallbackKind::CreateSnippet, enabled: true },
+        ];
+
+        let results = results("  rust lifetimes ", &config);
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["create_snippet:rust lifetimes", "web_search:rust lifetimes"]);
+        assert!(results[0].score > results[1].score);
+        assert_eq!(results[1].subtitle, "https://www.google.com/search?q=rust%20lifetimes");
+
+        assert!(super::results("   ", &config).is_empty());
+    }
+
+    #[test]
+    fn test_parse_result_id() {
+        assert_eq!(parse_result_id("create_todo:call bob: 3pm"), Some((FallbackKind::CreateTodo, "call bob: 3pm")));
+        assert_eq!(parse_result_id("unknown:x"), None);
+        assert_eq!(parse_result_id("web_search"), None);
+    }
+
+    #[test]
+    fn test_search_url() {
+        assert_eq!(search_url("https://duckduckgo.com/?q={query}", "a&b"), "https://duckduckgo.com/?q=a%26b");
+        assert_eq!(search_url("https://www.bing.com/search?q=", "x y"), "https://www.bing.com/search?q=x%20y");
+    }
+}
@@ -0,0 +1,110 @@
+// 文件安全徽章 - 声明了本地路径的结果如果带有下载来源标记（Mark-of-the-Web），
+// 显示“已下载/已阻止”徽章，并注入“解除锁定”“查看来源”操作，由 execute_action 集中处理
+
+use crate::core::types::{Action, BadgeTone, QueryResult, ResultBadge, WoxImage};
+use crate::utils::file_metadata::{self, SecurityZone, ZoneIdentifier};
+use std::path::Path;
+
+/// 解除锁定（移除 Zone.Identifier 数据流）
+pub const ACTION_UNBLOCK: &str = "unblock_file";
+
+/// 查看（并复制）来源 URL
+pub const ACTION_SHOW_ORIGIN: &str = "show_file_origin";
+
+/// 每次查询最多检查的文件结果数（插件已按分数排序，避免对大量文件逐个读取数据流）
+const MAX_CHECKED: usize = 30;
+
+/// 为带下载来源标记的文件结果补充徽章和操作
+pub fn annotate(results: &mut [QueryResult]) {
+    annotate_with(results, file_metadata::read_zone_identifier);
+}
+
+fn annotate_with<F>(results: &mut [QueryResult], read: F)
+where
+    F: Fn(&Path) -> Option<ZoneIdentifier>,
+{
+    for result in results.iter_mut().filter(|r| r.path().is_some()).take(MAX_CHECKED) {
+        let Some(path) = result.path() else {
+            continue;
+        };
+        let Some(zone) = read(Path::new(path)).filter(ZoneIdentifier::is_untrusted) else {
+            continue;
+        };
+
+        let (label, tone) = match zone.zone {
+            SecurityZone::Restricted => ("Blocked", BadgeTone::Danger),
+            _ => ("Downloaded", BadgeTone::Warning),
+        };
+        result.badges.push(ResultBadge {
+            id: "mark_of_the_web".to_string(),
+            label: label.to_string(),
+            tone,
+            tooltip: Some(match zone.origin() {
+                Some(origin) => format!("Downloaded from {}", origin),
+                None => "Downloaded from the internet".to_string(),
+            }),
+        });
+
+        result.actions.push(action(ACTION_UNBLOCK, "Unblock File", "🔓"));
+        if zone.origin().is_some() {
+            result.actions.push(action(ACTION_SHOW_ORIGIN, "Show Download Origin", "🌐"));
+        }
+    }
+}
+
+fn action(id: &str, name: &str, emoji: &str) -> Action {
+    Action {
+        id: id.to_string(),
+        name: name.to_string(),
+        icon: Some(WoxImage::emoji(emoji)),
+        is_default: false,
+        hotkey: None,
+        prevent_hide: true,
+    }
+}
+
+/// 文件的来源 URL
+pub fn origin(path: &Path) -> Option<String> {
+    file_metadata::read_zone_identifier(path).and_then(|zone| zone.origin().map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(zone: SecurityZone, host_url: Option<&str>) -> ZoneIdentifier {
+        ZoneIdentifier {
+            zone,
+            referrer_url: None,
+            host_url: host_url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_annotate_marks_downloaded_files() {
+        let mut results = vec![
+            QueryResult::new("setup.exe").with_path(r"C:\Downloads\setup.exe"),
+            QueryResult::new("blocked.docm").with_path(r"C:\Downloads\blocked.docm"),
+            QueryResult::new("notes.txt").with_path(r"C:\docs\notes.txt"),
+            QueryResult::new("calc"),
+        ];
+
+        annotate_with(&mut results, |path| match path.to_str()? {
+            r"C:\Downloads\setup.exe" => Some(zone(SecurityZone::Internet, Some("https://example.com/setup.exe"))),
+            r"C:\Downloads\blocked.docm" => Some(zone(SecurityZone::Restricted, None)),
+            r"C:\docs\notes.txt" => Some(zone(SecurityZone::LocalMachine, None)),
+            _ => None,
+        });
+
+        assert_eq!(results[0].badges[0].label, "Downloaded");
+        assert_eq!(results[0].badges[0].tooltip.as_deref(), Some("Downloaded from https://example.com/setup.exe"));
+        let actions: Vec<&str> = results[0].actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(actions, vec![ACTION_UNBLOCK, ACTION_SHOW_ORIGIN]);
+
+        assert_eq!(results[1].badges[0].tone, BadgeTone::Danger);
+        assert_eq!(results[1].actions.len(), 1);
+
+        assert!(results[2].badges.is_empty() && results[2].actions.is_empty());
+        assert!(results[3].badges.is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+// 二级选择（follow-up）- 动作返回候选列表替换当前结果，服务端维护面包屑栈
+// 搜索框输入在当前层的候选中过滤，Esc 返回上一层，链条结束（执行完成/窗口隐藏）时清空
+
+use super::types::{ExecuteOutcome, FollowUp, QueryResult};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// 最大层数（防止插件无限嵌套）
+const MAX_DEPTH: usize = 8;
+
+/// 全局二级选择栈
+pub static FOLLOW_UPS: Lazy<FollowUpStack> = Lazy::new(FollowUpStack::new);
+
+struct Frame {
+    plugin_id: String,
+    follow_up: FollowUp,
+}
+
+pub struct FollowUpStack {
+    frames: Mutex<Vec<Frame>>,
+}
+
+impl FollowUpStack {
+    fn new() -> Self {
+        Self {
+            frames: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 处理插件执行结果：FollowUp 入栈并填充面包屑，其他结果结束选择链（提示消息、文件丢失提示除外）
+    pub fn apply(&self, plugin_id: &str, outcome: ExecuteOutcome) -> ExecuteOutcome {
+        match outcome {
+            ExecuteOutcome::FollowUp(mut follow_up) => {
+                let mut frames = self.frames.lock();
+                if frames.len() >= MAX_DEPTH {
+                    tracing::warn!("Follow-up depth limit reached for plugin {}", plugin_id);
+                    return ExecuteOutcome::message("Too many nested selections");
+                }
+
+                for result in &mut follow_up.results {
+                    if result.plugin_id.is_empty() {
+                        result.plugin_id = plugin_id.to_string();
+                    }
+                }
+                frames.push(Frame {
+                    plugin_id: plugin_id.to_string(),
+                    follow_up: follow_up.clone(),
+                });
+                follow_up.breadcrumbs = Self::breadcrumbs(&frames);
+                ExecuteOutcome::FollowUp(follow_up)
+            }
+            ExecuteOutcome::ShowMessage { .. } | ExecuteOutcome::FileMissing { .. } => outcome,
+            _ => {
+                self.clear();
+                outcome
+            }
+        }
+    }
+
+    /// 在当前层的候选中过滤（未处于二级选择时返回 None）
+    pub fn filter(&self, search: &str) -> Option<FollowUp> {
+        let frames = self.frames.lock();
+        let top = frames.last()?;
+        let mut view = top.follow_up.clone();
+        view.breadcrumbs = Self::breadcrumbs(&frames);
+        view.results = filter_results(&top.follow_up.results, search.trim());
+        Some(view)
+    }
+
+    /// 返回上一层，已回到根查询时返回 None
+    pub fn back(&self) -> Option<FollowUp> {
+        self.frames.lock().pop();
+        self.filter("")
+    }
+
+    /// 清空选择链
+    pub fn clear(&self) {
+        self.frames.lock().clear();
+    }
+
+    /// 当前层由指定插件创建时返回其上下文
+    pub fn context(&self, plugin_id: &str) -> Option<serde_json::Value> {
+        self.frames
+            .lock()
+            .last()
+            .filter(|frame| frame.plugin_id == plugin_id)
+            .map(|frame| frame.follow_up.context.clone())
+    }
+
+    fn breadcrumbs(frames: &[Frame]) -> Vec<String> {
+        frames.iter().map(|frame| frame.follow_up.title.clone()).collect()
+    }
+}
+
+/// 按标题和副标题模糊匹配，空搜索保持插件给出的顺序
+fn filter_results(results: &[QueryResult], search: &str) -> Vec<QueryResult> {
+    if search.is_empty() {
+        return results.to_vec();
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, &QueryResult)> = results
+        .iter()
+        .filter_map(|result| {
+            let title = matcher.fuzzy_match(&result.title, search);
+            let subtitle = matcher.fuzzy_match(&result.subtitle, search).map(|s| s / 2);
+            title.max(subtitle).map(|score| (score, result))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, result)| result.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(title: &str) -> QueryResult {
+        QueryResult::new(title.to_string()).with_subtitle(format!("{} terminal", title))
+    }
+
+    #[test]
+    fn test_follow_up_stack() {
+        let stack = FollowUpStack::new();
+        let first = ExecuteOutcome::follow_up(
+            "Open in terminal",
+            serde_json::json!({ "path": "/tmp" }),
+            vec![candidate("PowerShell"), candidate("Git Bash")],
+        );
+
+        let ExecuteOutcome::FollowUp(view) = stack.apply("git", first) else {
+            panic!("expected follow-up");
+        };
+        assert_eq!(view.breadcrumbs, vec!["Open in terminal"]);
+        assert_eq!(view.results[0].plugin_id, "git");
+        assert_eq!(stack.context("git").unwrap()["path"], "/tmp");
+        assert!(stack.context("process").is_none());
+
+        let filtered = stack.filter("bash").unwrap();
+        assert_eq!(filtered.results.len(), 1);
+        assert_eq!(filtered.results[0].title, "Git Bash");
+
+        let second = ExecuteOutcome::follow_up("Profile", serde_json::Value::Null, vec![candidate("Admin")]);
+        let ExecuteOutcome::FollowUp(view) = stack.apply("git", second) else {
+            panic!("expected follow-up");
+        };
+        assert_eq!(view.breadcrumbs, vec!["Open in terminal", "Profile"]);
+
+        // 提示消息保留当前层，Esc 返回上一层
+        stack.apply("git", ExecuteOutcome::message("Not found"));
+        assert_eq!(stack.back().unwrap().title, "Open in terminal");
+        assert!(stack.back().is_none());
+    }
+
+    #[test]
+    fn test_completed_action_clears_stack() {
+        let stack = FollowUpStack::new();
+        stack.apply("git", ExecuteOutcome::follow_up("Pick", serde_json::Value::Null, vec![candidate("A")]));
+        assert!(stack.filter("").is_some());
+
+        assert!(matches!(stack.apply("git", ExecuteOutcome::Hide), ExecuteOutcome::Hide));
+        assert!(stack.filter("").is_none());
+    }
+}
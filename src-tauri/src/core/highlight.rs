@@ -0,0 +1,174 @@
+// 匹配高亮 - 计算标题/副标题中命中查询的字符区间，前端据此加粗显示
+// 区间使用字符下标（非字节），忽略大小写
+
+use serde::{Deserialize, Serialize};
+
+/// 高亮区间（字符下标，左闭右开）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 结果的匹配高亮
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchHighlights {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub title: Vec<HighlightSpan>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtitle: Vec<HighlightSpan>,
+}
+
+impl MatchHighlights {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_empty() && self.subtitle.is_empty()
+    }
+
+    /// 空高亮返回 None，便于直接赋值给 QueryResult.highlights
+    pub fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+}
+
+/// 按字符小写化（保持与原文本一一对应的下标）
+fn fold(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// 将匹配字符下标（如 fuzzy_indices 的返回值）合并为连续区间
+pub fn spans_from_indices(indices: &[usize]) -> Vec<HighlightSpan> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    for index in sorted {
+        match spans.last_mut() {
+            Some(last) if last.end == index => last.end += 1,
+            _ => spans.push(HighlightSpan { start: index, end: index + 1 }),
+        }
+    }
+    spans
+}
+
+/// needle 在 text 中的全部出现位置
+pub fn substring_spans(text: &str, needle: &str) -> Vec<HighlightSpan> {
+    find_all(&fold(text), &fold(needle))
+}
+
+/// 空格分隔的每个词在 text 中的出现位置（合并后）
+pub fn term_spans(text: &str, query: &str) -> Vec<HighlightSpan> {
+    let text = fold(text);
+    merge_spans(
+        query
+            .split_whitespace()
+            .flat_map(|term| find_all(&text, &fold(term)))
+            .collect(),
+    )
+}
+
+/// n-gram 命中位置（MFT 索引按 3-gram 匹配，查询的每个 gram 都会命中）
+pub fn gram_spans(text: &str, query: &str, n: usize) -> Vec<HighlightSpan> {
+    let text = fold(text);
+    let query = fold(query);
+    if query.len() <= n {
+        return find_all(&text, &query);
+    }
+    merge_spans(query.windows(n).flat_map(|gram| find_all(&text, gram)).collect())
+}
+
+/// 首字母缩写命中位置（例如 "gc" 命中 "Git Client" 的 G 和 C）
+pub fn initials_spans(text: &str, query: &str) -> Vec<HighlightSpan> {
+    let query = fold(query);
+    let mut spans = Vec::new();
+    let mut query_idx = 0;
+    let mut at_word_start = true;
+
+    for (index, c) in fold(text).into_iter().enumerate() {
+        if c.is_whitespace() {
+            at_word_start = true;
+            continue;
+        }
+        if at_word_start && query_idx < query.len() {
+            if c != query[query_idx] {
+                return Vec::new();
+            }
+            spans.push(HighlightSpan { start: index, end: index + 1 });
+            query_idx += 1;
+        }
+        at_word_start = false;
+    }
+
+    if query_idx == query.len() {
+        spans
+    } else {
+        Vec::new()
+    }
+}
+
+/// 排序并合并重叠/相邻的区间
+pub fn merge_spans(mut spans: Vec<HighlightSpan>) -> Vec<HighlightSpan> {
+    spans.sort_by_key(|s| s.start);
+    let mut merged: Vec<HighlightSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+fn find_all(text: &[char], needle: &[char]) -> Vec<HighlightSpan> {
+    if needle.is_empty() || needle.len() > text.len() {
+        return Vec::new();
+    }
+    text.windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| *window == needle)
+        .map(|(start, _)| HighlightSpan {
+            start,
+            end: start + needle.len(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize) -> HighlightSpan {
+        HighlightSpan { start, end }
+    }
+
+    #[test]
+    fn test_substring_and_terms() {
+        assert_eq!(substring_spans("Visual Studio Code", "CODE"), vec![span(14, 18)]);
+        assert_eq!(term_spans("Visual Studio Code", "vis code"), vec![span(0, 3), span(14, 18)]);
+        assert_eq!(substring_spans("开发工具", "工具"), vec![span(2, 4)]);
+        assert!(substring_spans("abc", "").is_empty());
+    }
+
+    #[test]
+    fn test_indices_and_grams() {
+        assert_eq!(spans_from_indices(&[4, 0, 1, 2, 5]), vec![span(0, 3), span(4, 6)]);
+        assert_eq!(gram_spans("my_report_2024.xlsx", "report", 3), vec![span(3, 9)]);
+        assert_eq!(gram_spans("ab.txt", "ab", 3), vec![span(0, 2)]);
+    }
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(initials_spans("Git Client", "gc"), vec![span(0, 1), span(4, 5)]);
+        assert!(initials_spans("Git Client", "gx").is_empty());
+    }
+
+    #[test]
+    fn test_merge_overlapping_spans() {
+        assert_eq!(
+            merge_spans(vec![span(3, 6), span(0, 4), span(8, 9)]),
+            vec![span(0, 6), span(8, 9)]
+        );
+    }
+}
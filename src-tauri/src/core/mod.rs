@@ -1,3 +1,15 @@
 // 核心模块导出
 
+pub mod accessibility;
+pub mod clock;
+pub mod confirmation;
+pub mod fallback;
+pub mod file_badges;
+pub mod follow_up;
+pub mod highlight;
+pub mod preview_cap;
+pub mod query_cancel;
+pub mod result_filter;
+pub mod reveal;
+pub mod session_state;
 pub mod types;
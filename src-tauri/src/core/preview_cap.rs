@@ -0,0 +1,203 @@
+// 预览大小上限 - 文本/Markdown 预览超过上限时只保留开头和结尾（中间插入截断标记），
+// 完整内容暂存在内存中，前端需要时通过 load_full_preview 按需获取
+
+use super::types::{Preview, QueryResult};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// 预览文本大小上限（字节）
+pub const MAX_PREVIEW_BYTES: usize = 64 * 1024;
+
+/// 截断后开头部分占上限的比例（其余保留结尾）
+const HEAD_RATIO: f64 = 0.75;
+
+/// 在截断点附近寻找换行的范围（占保留部分的比例），找到时按整行截断
+const LINE_SNAP_RATIO: f64 = 0.2;
+
+/// 暂存的完整预览数量
+const FULL_PREVIEW_CAPACITY: usize = 64;
+
+/// 预览已截断时写入 context_data 的字段
+pub const TRUNCATED_KEY: &str = "preview_truncated";
+
+/// 全局完整预览暂存
+pub static FULL_PREVIEWS: Lazy<FullPreviews> = Lazy::new(|| FullPreviews::new(FULL_PREVIEW_CAPACITY));
+
+/// 截断超限的预览（结果集中统一调用）
+pub fn cap_previews(results: &mut [QueryResult]) {
+    FULL_PREVIEWS.cap(results, MAX_PREVIEW_BYTES);
+}
+
+/// 被截断预览的完整内容（按插件 ID + 结果 ID，超出容量时淘汰最早的）
+pub struct FullPreviews {
+    capacity: usize,
+    entries: Mutex<VecDeque<(String, String, Preview)>>,
+}
+
+impl FullPreviews {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 截断超过 max_bytes 的文本/Markdown 预览并暂存完整内容
+    pub fn cap(&self, results: &mut [QueryResult], max_bytes: usize) {
+        for result in results.iter_mut() {
+            let truncated = match &result.preview {
+                Some(Preview::Text(text)) => truncate(text, max_bytes).map(Preview::Text),
+                Some(Preview::Markdown(text)) => truncate(text, max_bytes).map(Preview::Markdown),
+                _ => None,
+            };
+            let Some(truncated) = truncated else {
+                continue;
+            };
+
+            if let Some(full) = result.preview.replace(truncated) {
+                self.store(&result.plugin_id, &result.id, full);
+            }
+            if !result.context_data.is_object() {
+                result.context_data = serde_json::json!({});
+            }
+            result.context_data[TRUNCATED_KEY] = serde_json::Value::Bool(true);
+        }
+    }
+
+    fn store(&self, plugin_id: &str, result_id: &str, preview: Preview) {
+        let mut entries = self.entries.lock();
+        entries.retain(|(p, r, _)| p != plugin_id || r != result_id);
+        entries.push_back((plugin_id.to_string(), result_id.to_string(), preview));
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+    }
+
+    /// 获取完整预览（未被截断或已被淘汰时返回 None）
+    pub fn get(&self, plugin_id: &str, result_id: &str) -> Option<Preview> {
+        self.entries
+            .lock()
+            .iter()
+            .find(|(p, r, _)| p == plugin_id && r == result_id)
+            .map(|(_, _, preview)| preview.clone())
+    }
+}
+
+/// 保留开头和结尾，中间替换为截断标记；未超限返回 None
+pub fn truncate(text: &str, max_bytes: usize) -> Option<String> {
+    if text.len() <= max_bytes {
+        return None;
+    }
+
+    let head_budget = (max_bytes as f64 * HEAD_RATIO) as usize;
+    let tail_budget = max_bytes - head_budget;
+
+    let mut head_end = floor_char_boundary(text, head_budget);
+    let snap = (head_end as f64 * LINE_SNAP_RATIO) as usize;
+    if let Some(newline) = text[..head_end].rfind('\n').filter(|i| head_end - i <= snap) {
+        head_end = newline + 1;
+    }
+
+    let mut tail_start = ceil_char_boundary(text, text.len() - tail_budget);
+    let snap = ((text.len() - tail_start) as f64 * LINE_SNAP_RATIO) as usize;
+    if let Some(offset) = text[tail_start..].find('\n').filter(|i| *i < snap) {
+        tail_start += offset + 1;
+    }
+
+    let omitted = tail_start - head_end;
+    Some(format!(
+        "{}\n\n… {} omitted ({} total) — load the full preview to see everything …\n\n{}",
+        &text[..head_end],
+        super::confirmation::format_bytes(omitted as u64),
+        super::confirmation::format_bytes(text.len() as u64),
+        &text[tail_start..]
+    ))
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result_with_preview(id: &str, preview: Preview) -> QueryResult {
+        let mut result = QueryResult::new(id);
+        result.id = id.to_string();
+        result.plugin_id = "ai_assistant".to_string();
+        result.preview = Some(preview);
+        result
+    }
+
+    #[test]
+    fn test_truncate_keeps_head_and_tail() {
+        assert_eq!(truncate("short", 100), None);
+
+        let text = format!("HEAD{}TAIL", "x".repeat(1000));
+        let truncated = truncate(&text, 100).unwrap();
+        assert!(truncated.starts_with("HEADxxx"));
+        assert!(truncated.ends_with("xxxTAIL"));
+        assert!(truncated.contains("omitted (1008 B total)"));
+    }
+
+    #[test]
+    fn test_truncate_snaps_to_lines_and_char_boundaries() {
+        let lines: String = (0..200).map(|i| format!("line {:03}\n", i)).collect();
+        let truncated = truncate(&lines, 200).unwrap();
+        let (head, rest) = truncated.split_once("\n\n…").unwrap();
+        assert_eq!(head.lines().count(), 16);
+        assert!(head.lines().all(|line| line.len() == 8));
+        let tail = rest.rsplit_once("…\n\n").unwrap().1;
+        assert!(tail.starts_with("line "));
+
+        // 多字节字符不会被切开
+        let chinese = "中文预览".repeat(100);
+        let truncated = truncate(&chinese, 101).unwrap();
+        assert!(truncated.starts_with("中文"));
+        assert!(truncated.ends_with("预览"));
+    }
+
+    #[test]
+    fn test_cap_stores_full_preview() {
+        let previews = FullPreviews::new(2);
+        let long = "a".repeat(500);
+        let mut results = vec![
+            result_with_preview("long", Preview::Text(long.clone())),
+            result_with_preview("small", Preview::Text("ok".to_string())),
+            result_with_preview("html", Preview::Html(long.clone())),
+        ];
+        previews.cap(&mut results, 100);
+
+        assert!(matches!(&results[0].preview, Some(Preview::Text(text)) if text.len() < 200));
+        assert_eq!(results[0].context_data[TRUNCATED_KEY], true);
+        assert!(matches!(previews.get("ai_assistant", "long"), Some(Preview::Text(text)) if text == long));
+
+        assert!(results[1].context_data.get(TRUNCATED_KEY).is_none());
+        assert!(previews.get("ai_assistant", "small").is_none());
+        assert!(previews.get("ai_assistant", "html").is_none());
+    }
+
+    #[test]
+    fn test_oldest_full_previews_are_evicted() {
+        let previews = FullPreviews::new(2);
+        for id in ["a", "b", "c"] {
+            let mut results = vec![result_with_preview(id, Preview::Markdown("#".repeat(50)))];
+            previews.cap(&mut results, 10);
+        }
+        assert!(previews.get("ai_assistant", "a").is_none());
+        assert!(previews.get("ai_assistant", "b").is_some());
+        assert!(previews.get("ai_assistant", "c").is_some());
+    }
+}
@@ -0,0 +1,139 @@
+// 查询取消 - 前端每次输入携带递增的 generation，新查询开始时取消同一窗口的上一个查询
+// 过期查询在服务端中止，结果不会返回给前端；generation 只在同一前端会话内比较
+// （搜索框重新挂载后计数从 1 开始，会话 ID 随之变化）
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use tokio_util::sync::CancellationToken;
+
+/// 被取消查询返回的错误信息（前端据此忽略，不视为失败）
+pub const QUERY_CANCELLED: &str = "Query cancelled";
+
+/// 全局查询跟踪器
+pub static QUERY_TRACKER: Lazy<QueryTracker> = Lazy::new(QueryTracker::new);
+
+/// 单次查询的取消凭据
+#[derive(Debug, Clone)]
+pub struct QueryTicket {
+    pub generation: u64,
+    pub token: CancellationToken,
+    session: Option<String>,
+}
+
+impl QueryTicket {
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+struct Latest {
+    session: Option<String>,
+    generation: u64,
+    token: CancellationToken,
+}
+
+/// 跟踪每个窗口的最新查询，同一窗口同一时间只有最新的查询有效
+pub struct QueryTracker {
+    latest: Mutex<HashMap<String, Latest>>,
+}
+
+impl QueryTracker {
+    fn new() -> Self {
+        Self {
+            latest: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 开始新查询并取消该窗口的上一个查询
+    /// generation 为 None 时自动分配；同一会话中比最新查询更旧（乱序到达）时返回 None，
+    /// 会话变化（前端重新挂载）时计数重新开始，总是接受
+    pub fn begin(&self, window: &str, session: Option<&str>, generation: Option<u64>) -> Option<QueryTicket> {
+        let mut all = self.latest.lock();
+        let latest = all.entry(window.to_string()).or_insert_with(|| Latest {
+            session: None,
+            generation: 0,
+            token: CancellationToken::new(),
+        });
+        let same_session = latest.session.as_deref() == session;
+        let generation = match generation {
+            Some(generation) => generation,
+            None if same_session => latest.generation + 1,
+            None => 1,
+        };
+        if same_session && generation < latest.generation {
+            return None;
+        }
+
+        latest.token.cancel();
+        latest.session = session.map(str::to_string);
+        latest.generation = generation;
+        latest.token = CancellationToken::new();
+        Some(QueryTicket {
+            generation,
+            token: latest.token.clone(),
+            session: latest.session.clone(),
+        })
+    }
+
+    /// 是否仍是所在窗口的最新查询
+    pub fn is_current(&self, window: &str, ticket: &QueryTicket) -> bool {
+        self.latest
+            .lock()
+            .get(window)
+            .is_some_and(|latest| latest.session == ticket.session && latest.generation == ticket.generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newer_query_cancels_previous() {
+        let tracker = QueryTracker::new();
+        let first = tracker.begin("main", Some("a"), Some(1)).unwrap();
+        let second = tracker.begin("main", Some("a"), Some(2)).unwrap();
+
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert!(tracker.is_current("main", &second));
+        assert!(!tracker.is_current("main", &first));
+    }
+
+    #[test]
+    fn test_stale_generation_rejected() {
+        let tracker = QueryTracker::new();
+        let latest = tracker.begin("main", Some("a"), Some(5)).unwrap();
+        assert!(tracker.begin("main", Some("a"), Some(4)).is_none());
+        assert!(!latest.is_cancelled());
+
+        let auto = tracker.begin("main", Some("a"), None).unwrap();
+        assert_eq!(auto.generation, 6);
+        assert!(latest.is_cancelled());
+    }
+
+    #[test]
+    fn test_generation_restarts_after_remount() {
+        let tracker = QueryTracker::new();
+        let old = tracker.begin("main", Some("before"), Some(42)).unwrap();
+
+        // 搜索框重新挂载：新会话从 1 开始计数，不能被旧会话的序号拒绝
+        let fresh = tracker.begin("main", Some("after"), Some(1)).unwrap();
+        assert!(old.is_cancelled());
+        assert!(tracker.is_current("main", &fresh));
+        assert!(!tracker.is_current("main", &old));
+        assert!(tracker.begin("main", Some("after"), Some(2)).is_some());
+    }
+
+    #[test]
+    fn test_windows_tracked_independently() {
+        let tracker = QueryTracker::new();
+        let main = tracker.begin("main", Some("a"), Some(10)).unwrap();
+        let other = tracker.begin("selection", Some("b"), Some(1)).unwrap();
+
+        assert!(!main.is_cancelled());
+        assert!(tracker.is_current("main", &main));
+        assert!(tracker.is_current("selection", &other));
+    }
+}
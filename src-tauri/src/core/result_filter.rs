@@ -0,0 +1,170 @@
+// 结果内搜索（二次过滤）- 保留每个窗口最近一次查询的结果集，按附加关键词在内存中过滤，不重新查询插件
+
+use super::highlight::{merge_spans, substring_spans, MatchHighlights};
+use super::types::QueryResult;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 全局结果集缓存（按窗口 label 区分会话）
+pub static RESULT_SESSIONS: Lazy<ResultSessions> = Lazy::new(ResultSessions::new);
+
+struct ResultSet {
+    query_id: u64,
+    results: Arc<Vec<QueryResult>>,
+}
+
+/// 每个窗口会话最近一次的查询结果
+pub struct ResultSessions {
+    sessions: RwLock<HashMap<String, ResultSet>>,
+}
+
+impl ResultSessions {
+    fn new() -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 保存窗口最近一次查询的结果（覆盖旧结果集）
+    pub fn store(&self, session: &str, query_id: u64, results: &[QueryResult]) {
+        self.sessions.write().insert(
+            session.to_string(),
+            ResultSet {
+                query_id,
+                results: Arc::new(results.to_vec()),
+            },
+        );
+    }
+
+    /// 在窗口最近一次的结果集中查找结果
+    pub fn find(&self, session: &str, result_id: &str) -> Option<QueryResult> {
+        let sessions = self.sessions.read();
+        sessions
+            .get(session)?
+            .results
+            .iter()
+            .find(|result| result.id == result_id)
+            .cloned()
+    }
+
+    /// 窗口最近一次结果集中的第 position 个结果（从 1 开始，供 Alt+1..9 快速选择）
+    pub fn nth(&self, session: &str, position: usize) -> Option<QueryResult> {
+        let index = position.checked_sub(1)?;
+        self.sessions.read().get(session)?.results.get(index).cloned()
+    }
+
+    /// 在结果集中按关键词过滤（空格分隔的多个词须全部命中标题或副标题），保持原有排序
+    /// 返回结果的 highlights 替换为过滤词的命中位置
+    pub fn filter(&self, session: &str, query_id: u64, term: &str) -> Result<Vec<QueryResult>> {
+        let results = {
+            let sessions = self.sessions.read();
+            let set = sessions
+                .get(session)
+                .filter(|set| set.query_id == query_id)
+                .ok_or_else(|| anyhow!("Result set for query {} is no longer available", query_id))?;
+            set.results.clone()
+        };
+
+        let terms: Vec<&str> = term.split_whitespace().collect();
+        Ok(results
+            .iter()
+            .filter_map(|result| filter_result(result, &terms))
+            .collect())
+    }
+}
+
+/// 单个结果的匹配与高亮，未命中返回 None
+fn filter_result(result: &QueryResult, terms: &[&str]) -> Option<QueryResult> {
+    let mut highlights = MatchHighlights::default();
+
+    for term in terms {
+        let title_spans = substring_spans(&result.title, term);
+        let subtitle_spans = substring_spans(&result.subtitle, term);
+        if title_spans.is_empty() && subtitle_spans.is_empty() {
+            return None;
+        }
+        highlights.title.extend(title_spans);
+        highlights.subtitle.extend(subtitle_spans);
+    }
+
+    let mut filtered = result.clone();
+    if !terms.is_empty() {
+        filtered.highlights = MatchHighlights {
+            title: merge_spans(highlights.title),
+            subtitle: merge_spans(highlights.subtitle),
+        }
+        .non_empty();
+    }
+    Some(filtered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::highlight::HighlightSpan;
+
+    fn results() -> Vec<QueryResult> {
+        vec![
+            QueryResult::new("Visual Studio Code").with_subtitle("C:\\Program Files\\Microsoft VS Code"),
+            QueryResult::new("Visual Studio 2022").with_subtitle("开发工具"),
+            QueryResult::new("记事本").with_subtitle("notepad.exe"),
+        ]
+    }
+
+    #[test]
+    fn test_filter_with_highlights() {
+        let sessions = ResultSessions::new();
+        sessions.store("main", 7, &results());
+
+        let filtered = sessions.filter("main", 7, "vis code").unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(
+            filtered[0].highlights.as_ref().unwrap().title,
+            vec![HighlightSpan { start: 0, end: 3 }, HighlightSpan { start: 14, end: 18 }]
+        );
+
+        let filtered = sessions.filter("main", 7, "工具").unwrap();
+        assert_eq!(
+            filtered[0].highlights.as_ref().unwrap().subtitle,
+            vec![HighlightSpan { start: 2, end: 4 }]
+        );
+        assert_eq!(sessions.filter("main", 7, "").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_stale_query_id_rejected() {
+        let sessions = ResultSessions::new();
+        sessions.store("main", 1, &results());
+        sessions.store("main", 2, &results()[..1]);
+        assert!(sessions.filter("main", 1, "code").is_err());
+        assert!(sessions.filter("other", 2, "code").is_err());
+    }
+
+    #[test]
+    fn test_find_in_latest_set() {
+        let sessions = ResultSessions::new();
+        let results = results();
+        sessions.store("main", 1, &results);
+        assert_eq!(sessions.find("main", &results[2].id).unwrap().title, "记事本");
+        assert!(sessions.find("main", "missing").is_none());
+        assert!(sessions.find("other", &results[2].id).is_none());
+    }
+
+    #[test]
+    fn test_nth_is_one_based() {
+        let sessions = ResultSessions::new();
+        sessions.store("main", 1, &results());
+        assert_eq!(sessions.nth("main", 1).unwrap().title, "Visual Studio Code");
+        assert_eq!(sessions.nth("main", 3).unwrap().title, "记事本");
+        assert!(sessions.nth("main", 0).is_none());
+        assert!(sessions.nth("main", 4).is_none());
+
+        // 新的查询覆盖旧结果集
+        sessions.store("main", 2, &results()[2..]);
+        assert_eq!(sessions.nth("main", 1).unwrap().title, "记事本");
+        assert!(sessions.nth("other", 1).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+// 在文件管理器中显示 - 任何在 context_data.path 中声明了本地路径的结果（不限插件）
+// 都会注入统一的"在文件夹中显示"操作，由 execute_action 集中处理
+
+use crate::core::types::{Action, QueryResult, WoxImage};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 结果声明本地路径使用的 context_data 字段
+pub const PATH_KEY: &str = "path";
+
+/// 在文件管理器中显示（后端直接处理）
+pub const ACTION_REVEAL: &str = "reveal_in_folder";
+
+/// 插件自带的等价操作（已有时不重复注入）
+const EQUIVALENT_ACTIONS: &[&str] = &["open_folder", "open_explorer", "open_location"];
+
+/// 最近一次查询中声明了路径的结果（result_id → path）
+static REVEAL_TARGETS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 为声明了路径的结果注入"在文件夹中显示"操作，并记录目标路径
+pub fn attach_actions(results: &mut [QueryResult]) {
+    let mut targets = REVEAL_TARGETS.lock();
+    targets.clear();
+
+    for result in results.iter_mut() {
+        let Some(path) = result.path().map(str::to_string) else {
+            continue;
+        };
+        targets.insert(result.id.clone(), path);
+
+        if result.actions.iter().any(|a| EQUIVALENT_ACTIONS.contains(&a.id.as_str())) {
+            continue;
+        }
+        result.actions.push(Action {
+            id: ACTION_REVEAL.to_string(),
+            name: "Show in Folder".to_string(),
+            icon: Some(WoxImage::emoji("📁")),
+            is_default: false,
+            hotkey: None,
+            prevent_hide: false,
+        });
+    }
+}
+
+/// 查找结果对应的路径（由 execute_action 调用）
+pub fn target_for(result_id: &str) -> Option<String> {
+    REVEAL_TARGETS.lock().get(result_id).cloned()
+}
+
+/// 在系统文件管理器中显示并选中路径
+pub fn reveal(path: &Path) -> Result<()> {
+    if !path.exists() {
+        anyhow::bail!("Path no longer exists: {}", path.display());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        std::process::Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", path.display()))
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open").arg("-R").arg(path).spawn()?;
+    }
+
+    // xdg-open 不支持选中文件，打开所在目录
+    #[cfg(target_os = "linux")]
+    {
+        let folder = path.parent().filter(|_| !path.is_dir()).unwrap_or(path);
+        std::process::Command::new("xdg-open").arg(folder).spawn()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result_with_actions(id: &str, actions: &[&str]) -> QueryResult {
+        let mut result = QueryResult::new(id);
+        result.id = id.to_string();
+        for action in actions {
+            result = result.with_action(Action {
+                id: action.to_string(),
+                name: action.to_string(),
+                icon: None,
+                is_default: false,
+                hotkey: None,
+                prevent_hide: false,
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_attach_actions() {
+        let mut results = vec![
+            result_with_actions("app", &["open"]).with_path(r"C:\Apps\tool.exe"),
+            result_with_actions("file", &["open", "open_folder"]).with_path(r"C:\docs\a.txt"),
+            result_with_actions("calc", &[]),
+        ];
+        results[2].context_data = serde_json::json!({ "path": "" });
+
+        attach_actions(&mut results);
+
+        assert!(results[0].actions.iter().any(|a| a.id == ACTION_REVEAL));
+        assert!(!results[1].actions.iter().any(|a| a.id == ACTION_REVEAL));
+        assert!(results[2].actions.is_empty());
+        assert_eq!(target_for("app").as_deref(), Some(r"C:\Apps\tool.exe"));
+        assert_eq!(target_for("file").as_deref(), Some(r"C:\docs\a.txt"));
+        assert_eq!(target_for("calc"), None);
+    }
+
+    #[test]
+    fn test_with_path_keeps_context() {
+        let mut result = QueryResult::new("project");
+        result.context_data = serde_json::json!({ "name": "api" });
+        let result = result.with_path("/home/me/api");
+        assert_eq!(result.path(), Some("/home/me/api"));
+        assert_eq!(result.context_data["name"], "api");
+        assert_eq!(QueryResult::new("x").path(), None);
+    }
+}
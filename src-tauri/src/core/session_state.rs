@@ -0,0 +1,100 @@
+// 会话恢复 - 窗口隐藏时记下查询文本、选中项和滚动位置（仅保存在内存中），
+// 在配置的时间窗口内重新呼出启动器时返回给前端恢复
+
+use super::clock::{self, SharedClock};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// 全局会话状态（主窗口）
+pub static SESSION_STATE: Lazy<SessionStore> = Lazy::new(|| SessionStore::new(clock::system()));
+
+/// 前端隐藏时上报的状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub query: String,
+    pub selected_index: usize,
+    pub scroll_top: f64,
+}
+
+pub struct SessionStore {
+    clock: SharedClock,
+    saved: Mutex<Option<(SessionState, DateTime<Utc>)>>,
+}
+
+impl SessionStore {
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            saved: Mutex::new(None),
+        }
+    }
+
+    /// 保存状态（空查询视为没有可恢复的内容）
+    pub fn save(&self, state: SessionState) {
+        let mut saved = self.saved.lock();
+        *saved = if state.query.trim().is_empty() {
+            None
+        } else {
+            Some((state, self.clock.now()))
+        };
+    }
+
+    /// 隐藏后 window_secs 秒内返回保存的状态（0 表示禁用会话恢复）
+    pub fn restore(&self, window_secs: u64) -> Option<SessionState> {
+        if window_secs == 0 {
+            return None;
+        }
+        let saved = self.saved.lock();
+        let (state, saved_at) = saved.as_ref()?;
+        let elapsed = self.clock.now() - *saved_at;
+        (elapsed <= chrono::Duration::seconds(window_secs as i64)).then(|| state.clone())
+    }
+
+    pub fn clear(&self) {
+        *self.saved.lock() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::FakeClock;
+
+    fn state(query: &str) -> SessionState {
+        SessionState {
+            query: query.to_string(),
+            selected_index: 3,
+            scroll_top: 120.0,
+        }
+    }
+
+    #[test]
+    fn test_restore_within_window() {
+        let clock = FakeClock::fixed();
+        let store = SessionStore::new(clock.clone());
+        store.save(state("code"));
+
+        clock.advance(chrono::Duration::seconds(59));
+        assert_eq!(store.restore(60), Some(state("code")));
+        // 未超时前可以多次恢复
+        assert_eq!(store.restore(60), Some(state("code")));
+
+        clock.advance(chrono::Duration::seconds(2));
+        assert_eq!(store.restore(60), None);
+        assert_eq!(store.restore(0), None);
+    }
+
+    #[test]
+    fn test_empty_query_clears_state() {
+        let store = SessionStore::new(FakeClock::fixed());
+        store.save(state("code"));
+        store.save(state("  "));
+        assert_eq!(store.restore(60), None);
+
+        store.save(state("code"));
+        store.clear();
+        assert_eq!(store.restore(60), None);
+    }
+}
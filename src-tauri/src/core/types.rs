@@ -10,6 +10,9 @@ pub struct QueryContext {
     pub command: Option<String>,
     pub search: String,
     pub raw_query: String,
+    /// 查询取消令牌（有更新的输入时被取消，耗时插件应检查并提前返回）
+    #[serde(skip)]
+    pub cancel: tokio_util::sync::CancellationToken,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +37,15 @@ pub struct QueryResult {
     pub refreshable: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group: Option<String>,
+    /// 无障碍元数据（由查询管线统一填充）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessibility: Option<crate::core::accessibility::Accessibility>,
+    /// 标题/副标题的匹配高亮区间（插件匹配时给出，否则由排序器补充）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<crate::core::highlight::MatchHighlights>,
+    /// 结果徽章（如文件的下载来源标记，由查询管线统一填充）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub badges: Vec<ResultBadge>,
 }
 
 impl QueryResult {
@@ -50,6 +62,9 @@ impl QueryResult {
             preview: None,
             refreshable: false,
             group: None,
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         }
     }
 
@@ -72,6 +87,31 @@ impl QueryResult {
         self.actions.push(action);
         self
     }
+
+    /// 声明结果对应的本地路径（写入 context_data.path，统一提供"在文件管理器中显示"）
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        if !self.context_data.is_object() {
+            self.context_data = serde_json::json!({});
+        }
+        self.context_data[crate::core::reveal::PATH_KEY] = serde_json::Value::String(path.into());
+        self
+    }
+
+    /// 结果声明的本地路径（context_data.path，空字符串视为未声明）
+    pub fn path(&self) -> Option<&str> {
+        self.context_data
+            .get(crate::core::reveal::PATH_KEY)
+            .and_then(|p| p.as_str())
+            .filter(|p| !p.is_empty())
+    }
+
+    /// 默认动作（未标记默认时取第一个，与回车行为一致）
+    pub fn default_action(&self) -> Option<&Action> {
+        self.actions
+            .iter()
+            .find(|action| action.is_default)
+            .or_else(|| self.actions.first())
+    }
 }
 
 /// 操作
@@ -110,6 +150,25 @@ impl Action {
     }
 }
 
+/// 结果徽章（显示在标题旁）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultBadge {
+    pub id: String,
+    pub label: String,
+    pub tone: BadgeTone,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+}
+
+/// 徽章样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeTone {
+    Info,
+    Warning,
+    Danger,
+}
+
 /// 图标类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
@@ -136,6 +195,181 @@ impl WoxImage {
     }
 }
 
+/// 轻量结果列表项（不含预览/动作/上下文数据，选中时通过 get_result_detail 获取，减少每次按键的序列化开销）
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultListItem {
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    pub icon: WoxImage,
+    pub score: i32,
+    pub plugin_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub has_preview: bool,
+    pub refreshable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessibility: Option<crate::core::accessibility::Accessibility>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<crate::core::highlight::MatchHighlights>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub badges: Vec<ResultBadge>,
+}
+
+impl From<&QueryResult> for ResultListItem {
+    fn from(result: &QueryResult) -> Self {
+        Self {
+            id: result.id.clone(),
+            title: result.title.clone(),
+            subtitle: result.subtitle.clone(),
+            icon: result.icon.clone(),
+            score: result.score,
+            plugin_id: result.plugin_id.clone(),
+            group: result.group.clone(),
+            has_preview: result.preview.is_some(),
+            refreshable: result.refreshable,
+            accessibility: result.accessibility.clone(),
+            highlights: result.highlights.clone(),
+            badges: result.badges.clone(),
+        }
+    }
+}
+
+/// 结果详情（选中结果时获取）
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultDetail {
+    pub id: String,
+    pub plugin_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<Preview>,
+    pub actions: Vec<Action>,
+    pub context_data: serde_json::Value,
+}
+
+impl From<QueryResult> for ResultDetail {
+    fn from(result: QueryResult) -> Self {
+        Self {
+            id: result.id,
+            plugin_id: result.plugin_id,
+            preview: result.preview,
+            actions: result.actions,
+            context_data: result.context_data,
+        }
+    }
+}
+
+/// 插件执行动作后的反馈（由 execute_action 返回给前端）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecuteOutcome {
+    /// 按动作的默认行为处理（prevent_hide 为 false 时隐藏窗口）
+    #[default]
+    Hide,
+    /// 显示提示消息并保持窗口（如“已复制”）
+    ShowMessage { message: String },
+    /// 替换搜索框内容并重新查询
+    ReplaceQuery { query: String },
+    /// 用新的结果替换当前结果列表
+    PushResults { results: Vec<QueryResult> },
+    /// 打开预览面板
+    OpenPreview { preview: Preview },
+    /// 进入二级选择（候选列表替换当前结果，Esc 返回上一级）
+    FollowUp(FollowUp),
+    /// 目标文件已不存在，已从索引和使用记录中移除（前端提示并移除该结果）
+    FileMissing { path: String, message: String },
+    /// 破坏性动作需要确认（前端展示预演信息，确认后以 token 调用 confirm_action）
+    Confirm(ConfirmationRequest),
+}
+
+impl ExecuteOutcome {
+    pub fn message(message: impl Into<String>) -> Self {
+        Self::ShowMessage { message: message.into() }
+    }
+
+    pub fn file_missing(path: impl Into<String>) -> Self {
+        Self::FileMissing {
+            path: path.into(),
+            message: "File no longer exists — removed from index".to_string(),
+        }
+    }
+
+    pub fn follow_up(title: impl Into<String>, context: serde_json::Value, results: Vec<QueryResult>) -> Self {
+        Self::FollowUp(FollowUp {
+            title: title.into(),
+            placeholder: None,
+            context,
+            results,
+            breadcrumbs: Vec::new(),
+        })
+    }
+}
+
+/// 破坏性动作的预演：执行前描述将要发生的事（文件大小、子进程等）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRun {
+    /// 一句话说明（如"永久删除文件夹 build"）
+    pub summary: String,
+    /// 明细（标签 + 值）
+    pub details: Vec<DryRunDetail>,
+    /// 是否可撤销（如移到回收站）
+    pub reversible: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRunDetail {
+    pub label: String,
+    pub value: String,
+}
+
+impl DryRun {
+    pub fn new(summary: impl Into<String>) -> Self {
+        Self {
+            summary: summary.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_detail(mut self, label: impl Into<String>, value: impl Into<String>) -> Self {
+        self.details.push(DryRunDetail {
+            label: label.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    pub fn reversible(mut self) -> Self {
+        self.reversible = true;
+        self
+    }
+}
+
+/// 确认请求（token 在有效期内可用于继续执行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmationRequest {
+    pub token: String,
+    /// 结果标题
+    pub title: String,
+    pub dry_run: DryRun,
+    pub expires_in_secs: u64,
+}
+
+/// 二级选择（如选择终端、选择目标文件夹）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FollowUp {
+    /// 当前层级标题（显示在面包屑中）
+    pub title: String,
+    /// 搜索框占位提示
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// 插件自定义上下文，执行候选项时通过 FOLLOW_UPS.context 取回
+    #[serde(default)]
+    pub context: serde_json::Value,
+    pub results: Vec<QueryResult>,
+    /// 从第一层到当前层的标题（由后端填充）
+    #[serde(default)]
+    pub breadcrumbs: Vec<String>,
+}
+
 /// 预览
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -145,6 +379,15 @@ pub enum Preview {
     Image(String),
     Html(String),
     File(String),
+    /// 二进制预览资源（图片、PDF 等），通过 preview:// 协议按 URL 加载
+    Asset(PreviewAsset),
+}
+
+/// 预览资源引用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewAsset {
+    pub url: String,
+    pub mime: String,
 }
 
 /// 插件元数据
@@ -161,6 +404,118 @@ pub struct PluginMetadata {
     pub settings: Vec<SettingDefinition>,
     pub supported_os: Vec<String>,
     pub plugin_type: PluginType,
+    /// 默认查询节流策略（可被插件配置覆盖）
+    #[serde(default)]
+    pub query_policy: QueryPolicy,
+    /// 分类、标签、主页与图标资源（序列化时展开到元数据顶层）
+    #[serde(flatten)]
+    pub branding: PluginBranding,
+}
+
+/// 插件分类（设置页插件列表按分类分组）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginCategory {
+    Search,
+    Productivity,
+    Developer,
+    System,
+    Utilities,
+    #[default]
+    Other,
+}
+
+impl PluginCategory {
+    /// 解析 manifest / 商店中的分类名（不区分大小写，未知分类归入 Other）
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "search" => Self::Search,
+            "productivity" => Self::Productivity,
+            "developer" | "development" | "dev" => Self::Developer,
+            "system" => Self::System,
+            "utilities" | "utility" | "tools" => Self::Utilities,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Search => "search",
+            Self::Productivity => "productivity",
+            Self::Developer => "developer",
+            Self::System => "system",
+            Self::Utilities => "utilities",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// 插件品牌信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginBranding {
+    #[serde(default)]
+    pub category: PluginCategory,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// 图片图标经 preview:// 协议提供的 URL（get_plugins 时登记，emoji 图标为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_asset: Option<PreviewAsset>,
+}
+
+impl PluginBranding {
+    pub fn new(category: PluginCategory) -> Self {
+        Self {
+            category,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+}
+
+/// 插件查询节流策略：输入不足最短长度时跳过插件，防抖期间输入变化则放弃本次查询
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryPolicy {
+    /// 最短查询长度（字符数，0 表示不限制；通过触发词路由时不生效）
+    #[serde(default)]
+    pub min_query_length: usize,
+    /// 防抖时间（毫秒）
+    #[serde(default)]
+    pub debounce_ms: u64,
+}
+
+impl QueryPolicy {
+    /// 防抖时间上限，避免配置错误导致插件看起来“无响应”
+    pub const MAX_DEBOUNCE_MS: u64 = 2000;
+
+    pub const fn new(min_query_length: usize, debounce_ms: u64) -> Self {
+        Self { min_query_length, debounce_ms }
+    }
+
+    /// 用插件配置中的 min_query_length / debounce_ms 覆盖默认值
+    pub fn with_config(mut self, config: &serde_json::Value) -> Self {
+        if let Some(min) = config.get("min_query_length").and_then(|v| v.as_u64()) {
+            self.min_query_length = min as usize;
+        }
+        if let Some(debounce) = config.get("debounce_ms").and_then(|v| v.as_u64()) {
+            self.debounce_ms = debounce.min(Self::MAX_DEBOUNCE_MS);
+        }
+        self
+    }
+
+    /// 查询是否达到最短长度
+    pub fn accepts(&self, search: &str) -> bool {
+        search.trim().chars().count() >= self.min_query_length
+    }
+
+    pub fn debounce(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.debounce_ms)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -186,4 +541,5 @@ pub enum PluginType {
     Python,
     NodeJS,
     Script,
+    Wasm,
 }
@@ -0,0 +1,498 @@
+// 下载管理器 - 插件包、主题、更新以及大文件（本地 AI 模型、离线词典）的统一下载服务
+// 支持断点续传（.part 临时文件 + Range/If-Range 请求）、SHA-256 校验、限速和进度事件
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 全局下载管理器
+pub static DOWNLOADS: Lazy<DownloadManager> = Lazy::new(DownloadManager::new);
+
+/// 下载进度事件（payload: DownloadTask）
+pub const DOWNLOAD_PROGRESS_EVENT: &str = "download:progress";
+
+/// 进度事件最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+/// 下载请求超时（大文件需要较长时间）
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// 下载请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadRequest {
+    pub url: String,
+    /// 目标文件路径
+    pub dest: PathBuf,
+    /// 期望的 SHA-256（十六进制），为空则不校验
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// 发起下载的插件（用于 User-Agent 标记）
+    #[serde(default = "default_owner")]
+    pub owner: String,
+}
+
+fn default_owner() -> String {
+    "download".to_string()
+}
+
+/// 下载状态
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadStatus {
+    Pending,
+    Downloading,
+    Verifying,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// 下载任务（同时作为进度事件 payload）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadTask {
+    pub id: String,
+    pub url: String,
+    pub dest: PathBuf,
+    pub owner: String,
+    pub status: DownloadStatus,
+    pub downloaded_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<u64>,
+    /// 当前速度（字节/秒）
+    pub speed_bps: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 服务器返回的 Content-Disposition（调用方可据此重命名）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_disposition: Option<String>,
+}
+
+/// 下载管理器
+pub struct DownloadManager {
+    tasks: RwLock<HashMap<String, DownloadTask>>,
+    cancel_flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    app_handle: OnceCell<AppHandle>,
+}
+
+impl DownloadManager {
+    fn new() -> Self {
+        Self {
+            tasks: RwLock::new(HashMap::new()),
+            cancel_flags: Mutex::new(HashMap::new()),
+            app_handle: OnceCell::new(),
+        }
+    }
+
+    /// 绑定 AppHandle（setup 阶段调用一次），之后的进度会推送到前端
+    pub fn attach(&self, app_handle: AppHandle) {
+        if self.app_handle.set(app_handle).is_err() {
+            tracing::warn!("Download manager already attached to app handle");
+        }
+    }
+
+    /// 后台下载，立即返回任务 ID（进度通过事件推送）
+    pub fn start(&'static self, request: DownloadRequest) -> String {
+        let id = self.register(&request);
+        let task_id = id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = self.run(&task_id, request).await {
+                tracing::warn!("Download {} failed: {}", task_id, e);
+            }
+        });
+        id
+    }
+
+    /// 下载并等待完成
+    pub async fn download(&self, request: DownloadRequest) -> Result<DownloadTask> {
+        let id = self.register(&request);
+        self.run(&id, request).await
+    }
+
+    /// 取消下载（保留 .part 文件以便续传）
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.cancel_flags.lock().get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 列出所有任务
+    pub fn list(&self) -> Vec<DownloadTask> {
+        self.tasks.read().values().cloned().collect()
+    }
+
+    /// 获取任务
+    pub fn get(&self, id: &str) -> Option<DownloadTask> {
+        self.tasks.read().get(id).cloned()
+    }
+
+    /// 清理已结束的任务记录
+    pub fn clear_finished(&self) {
+        self.tasks.write().retain(|_, task| {
+            matches!(task.status, DownloadStatus::Pending | DownloadStatus::Downloading | DownloadStatus::Verifying)
+        });
+    }
+
+    fn register(&self, request: &DownloadRequest) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.tasks.write().insert(id.clone(), DownloadTask {
+            id: id.clone(),
+            url: request.url.clone(),
+            dest: request.dest.clone(),
+            owner: request.owner.clone(),
+            status: DownloadStatus::Pending,
+            downloaded_bytes: 0,
+            total_bytes: None,
+            speed_bps: 0,
+            error: None,
+            content_disposition: None,
+        });
+        self.cancel_flags.lock().insert(id.clone(), Arc::new(AtomicBool::new(false)));
+        id
+    }
+
+    async fn run(&self, id: &str, request: DownloadRequest) -> Result<DownloadTask> {
+        let cancel = self
+            .cancel_flags
+            .lock()
+            .get(id)
+            .cloned()
+            .unwrap_or_default();
+
+        let result = self.transfer(id, &request, &cancel).await;
+        self.cancel_flags.lock().remove(id);
+
+        match result {
+            Ok(()) => {
+                self.update(id, |task| task.status = DownloadStatus::Completed, true);
+                tracing::info!("⬇️ Downloaded {} -> {}", request.url, request.dest.display());
+            }
+            Err(e) => {
+                let cancelled = cancel.load(Ordering::SeqCst);
+                self.update(
+                    id,
+                    |task| {
+                        if cancelled {
+                            task.status = DownloadStatus::Cancelled;
+                        } else {
+                            task.status = DownloadStatus::Failed;
+                            task.error = Some(e.to_string());
+                        }
+                    },
+                    true,
+                );
+                return Err(e);
+            }
+        }
+
+        self.get(id).ok_or_else(|| anyhow!("Download task disappeared"))
+    }
+
+    async fn transfer(&self, id: &str, request: &DownloadRequest, cancel: &AtomicBool) -> Result<()> {
+        let part_path = part_path(&request.dest);
+        let meta_path = meta_path(&request.dest);
+        if let Some(parent) = request.dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // 断点续传：已有 .part 文件且记录了 ETag/Last-Modified 时请求剩余部分
+        let mut offset = tokio::fs::metadata(&part_path).await.map(|m| m.len()).unwrap_or(0);
+        let validator = PartValidator::load(&meta_path).await;
+        if offset > 0 && validator.is_none() {
+            tracing::info!("No validator for {}, restarting download", part_path.display());
+            discard_part(&part_path, &meta_path).await;
+            offset = 0;
+        }
+
+        let client = crate::http::client_with_timeout(&request.owner, DOWNLOAD_TIMEOUT)?;
+        let mut response = loop {
+            let mut builder = client.get(&request.url);
+            if let Some(if_range) = validator.as_ref().and_then(|v| v.if_range()).filter(|_| offset > 0) {
+                // 文件已变化时服务器返回完整内容（200）而不是拼接到旧数据上
+                builder = builder
+                    .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+                    .header(reqwest::header::IF_RANGE, if_range);
+            }
+            let response = builder.send().await?;
+
+            let content_range = response
+                .headers()
+                .get(reqwest::header::CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .map(parse_content_range);
+            let resumable = match response.status() {
+                // 416 只有在服务器报告的总大小与已下载大小一致时才视为下载完整
+                reqwest::StatusCode::RANGE_NOT_SATISFIABLE => content_range.is_some_and(|(_, total)| total == Some(offset)),
+                reqwest::StatusCode::PARTIAL_CONTENT => content_range.is_some_and(|(start, _)| start == Some(offset)),
+                _ => true,
+            };
+            if resumable {
+                break response;
+            }
+            if offset == 0 {
+                return Err(anyhow!("Download failed: HTTP {}", response.status()));
+            }
+            tracing::warn!("Cannot resume {} at {} bytes, restarting from zero", request.url, offset);
+            discard_part(&part_path, &meta_path).await;
+            offset = 0;
+        };
+
+        let status = response.status();
+        if status == reqwest::StatusCode::OK {
+            // 完整内容（首次下载、服务器不支持 Range 或文件已变化），记录校验信息供之后续传
+            offset = 0;
+            match PartValidator::from_headers(response.headers()) {
+                Some(validator) => validator.save(&meta_path).await?,
+                None => {
+                    let _ = tokio::fs::remove_file(&meta_path).await;
+                }
+            }
+        } else if !status.is_success() && status != reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            return Err(anyhow!("Download failed: HTTP {}", status));
+        }
+
+        let total = if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            Some(offset)
+        } else {
+            response.content_length().map(|len| len + offset)
+        };
+        let content_disposition = response
+            .headers()
+            .get(reqwest::header::CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        self.update(
+            id,
+            |task| {
+                task.status = DownloadStatus::Downloading;
+                task.downloaded_bytes = offset;
+                task.total_bytes = total;
+                task.content_disposition = content_disposition;
+            },
+            true,
+        );
+
+        if status != reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(offset > 0)
+                .truncate(offset == 0)
+                .open(&part_path)
+                .await?;
+
+            let limit_bps = crate::http::HTTP_CLIENTS.download_limit_kbps() * 1024;
+            let started = Instant::now();
+            let mut received: u64 = 0;
+            let mut last_emit = Instant::now();
+
+            while let Some(chunk) = response.chunk().await? {
+                if cancel.load(Ordering::SeqCst) {
+                    file.flush().await?;
+                    return Err(anyhow!("Download cancelled"));
+                }
+
+                file.write_all(&chunk).await?;
+                received += chunk.len() as u64;
+
+                // 限速：实际耗时短于按限速计算的耗时则等待
+                if limit_bps > 0 {
+                    let expected = Duration::from_secs_f64(received as f64 / limit_bps as f64);
+                    let elapsed = started.elapsed();
+                    if expected > elapsed {
+                        tokio::time::sleep(expected - elapsed).await;
+                    }
+                }
+
+                if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                    last_emit = Instant::now();
+                    let speed = (received as f64 / started.elapsed().as_secs_f64().max(0.001)) as u64;
+                    self.update(
+                        id,
+                        |task| {
+                            task.downloaded_bytes = offset + received;
+                            task.speed_bps = speed;
+                        },
+                        true,
+                    );
+                }
+            }
+            file.flush().await?;
+            self.update(id, |task| task.downloaded_bytes = offset + received, false);
+        }
+
+        // 校验
+        if let Some(expected) = request.sha256.as_deref().filter(|s| !s.is_empty()) {
+            self.update(id, |task| task.status = DownloadStatus::Verifying, true);
+            let actual = sha256_file(&part_path).await?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                // 损坏的文件无法续传，删除后重新下载
+                discard_part(&part_path, &meta_path).await;
+                return Err(anyhow!("Checksum mismatch: expected {}, got {}", expected, actual));
+            }
+        }
+
+        tokio::fs::rename(&part_path, &request.dest).await?;
+        let _ = tokio::fs::remove_file(&meta_path).await;
+        Ok(())
+    }
+
+    fn update(&self, id: &str, f: impl FnOnce(&mut DownloadTask), emit: bool) {
+        let snapshot = {
+            let mut tasks = self.tasks.write();
+            let Some(task) = tasks.get_mut(id) else {
+                return;
+            };
+            f(task);
+            task.clone()
+        };
+
+        if emit {
+            if let Some(app) = self.app_handle.get() {
+                let _ = app.emit(DOWNLOAD_PROGRESS_EVENT, &snapshot);
+            }
+        }
+    }
+}
+
+/// 临时文件路径（<dest>.part）
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// 续传校验信息路径（<dest>.part.meta）
+fn meta_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".part.meta");
+    dest.with_file_name(name)
+}
+
+/// 删除无法续传的临时文件及其校验信息
+async fn discard_part(part_path: &Path, meta_path: &Path) {
+    let _ = tokio::fs::remove_file(part_path).await;
+    let _ = tokio::fs::remove_file(meta_path).await;
+}
+
+/// .part 文件对应的服务器版本（续传时作为 If-Range 发送）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PartValidator {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+}
+
+impl PartValidator {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let header = |name: reqwest::header::HeaderName| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let validator = Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        };
+        validator.if_range().is_some().then_some(validator)
+    }
+
+    /// If-Range 值：弱 ETag 不能用于 If-Range，退回 Last-Modified
+    fn if_range(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+
+    async fn load(path: &Path) -> Option<Self> {
+        let bytes = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice::<Self>(&bytes).ok().filter(|v| v.if_range().is_some())
+    }
+
+    async fn save(&self, path: &Path) -> Result<()> {
+        tokio::fs::write(path, serde_json::to_vec(self)?).await?;
+        Ok(())
+    }
+}
+
+/// 解析 Content-Range（"bytes 100-199/200" 或 "bytes */200"），返回起始位置和总大小
+fn parse_content_range(value: &str) -> (Option<u64>, Option<u64>) {
+    let Some((range, total)) = value.trim().strip_prefix("bytes ").and_then(|rest| rest.split_once('/')) else {
+        return (None, None);
+    };
+    let start = range.split_once('-').and_then(|(start, _)| start.trim().parse().ok());
+    (start, total.trim().parse().ok())
+}
+
+/// 计算文件 SHA-256（十六进制小写）
+pub async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/tmp/models/base.bin")),
+            PathBuf::from("/tmp/models/base.bin.part")
+        );
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(parse_content_range("bytes 100-199/200"), (Some(100), Some(200)));
+        assert_eq!(parse_content_range("bytes */200"), (None, Some(200)));
+        assert_eq!(parse_content_range("bytes 0-9/*"), (Some(0), None));
+        assert_eq!(parse_content_range("items 0-9/10"), (None, None));
+    }
+
+    #[test]
+    fn test_part_validator_if_range() {
+        let validator = PartValidator {
+            etag: Some("W/\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        assert_eq!(validator.if_range(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        let validator = PartValidator { etag: Some("W/\"abc\"".to_string()), last_modified: None };
+        assert_eq!(validator.if_range(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sha256_file() {
+        let path = std::env::temp_dir().join(format!("ilauncher_sha_{}", uuid::Uuid::new_v4().simple()));
+        tokio::fs::write(&path, b"abc").await.unwrap();
+        assert_eq!(
+            sha256_file(&path).await.unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
@@ -0,0 +1,145 @@
+// 提权同意记录 - 按功能记住用户对 UAC 提示的选择，拒绝过的功能不再在每次启动时弹出提示
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 需要管理员权限的功能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElevatedFeature {
+    /// MFT 文件索引服务
+    Mft,
+    /// 结束无权限的进程
+    ProcessKill,
+}
+
+impl ElevatedFeature {
+    pub const ALL: [ElevatedFeature; 2] = [ElevatedFeature::Mft, ElevatedFeature::ProcessKill];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ElevatedFeature::Mft => "MFT file indexing",
+            ElevatedFeature::ProcessKill => "killing protected processes",
+        }
+    }
+}
+
+/// 用户的选择
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentDecision {
+    /// 尚未决定：需要时弹出 UAC，根据结果记录
+    #[default]
+    Ask,
+    /// 用户曾同意提权
+    Allowed,
+    /// 用户曾取消 UAC：不再提示，功能降级
+    Denied,
+}
+
+/// 功能 + 选择（返回给设置界面）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureConsent {
+    pub feature: ElevatedFeature,
+    pub label: &'static str,
+    pub decision: ConsentDecision,
+}
+
+/// 同意记录（JSON 文件）
+#[derive(Debug, Default)]
+pub struct ConsentStore {
+    path: Option<PathBuf>,
+    decisions: BTreeMap<ElevatedFeature, ConsentDecision>,
+}
+
+impl ConsentStore {
+    /// 读取记录，文件不存在或损坏时视为全部未决定
+    pub fn load(path: PathBuf) -> Self {
+        let decisions = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            decisions,
+        }
+    }
+
+    pub fn get(&self, feature: ElevatedFeature) -> ConsentDecision {
+        self.decisions.get(&feature).copied().unwrap_or_default()
+    }
+
+    /// 更新选择并写回文件（Ask 等同于删除记录）
+    pub fn set(&mut self, feature: ElevatedFeature, decision: ConsentDecision) -> Result<()> {
+        if decision == ConsentDecision::Ask {
+            self.decisions.remove(&feature);
+        } else {
+            self.decisions.insert(feature, decision);
+        }
+        match &self.path {
+            Some(path) => save(path, &self.decisions),
+            None => Ok(()),
+        }
+    }
+
+    pub fn all(&self) -> Vec<FeatureConsent> {
+        ElevatedFeature::ALL
+            .iter()
+            .map(|&feature| FeatureConsent {
+                feature,
+                label: feature.label(),
+                decision: self.get(feature),
+            })
+            .collect()
+    }
+}
+
+fn save(path: &Path, decisions: &BTreeMap<ElevatedFeature, ConsentDecision>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(decisions)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consent_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_consent_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("elevation_consent.json");
+
+        let mut store = ConsentStore::load(path.clone());
+        assert_eq!(store.get(ElevatedFeature::Mft), ConsentDecision::Ask);
+        store.set(ElevatedFeature::Mft, ConsentDecision::Denied).unwrap();
+        store.set(ElevatedFeature::ProcessKill, ConsentDecision::Allowed).unwrap();
+
+        let reloaded = ConsentStore::load(path.clone());
+        assert_eq!(reloaded.get(ElevatedFeature::Mft), ConsentDecision::Denied);
+        assert_eq!(reloaded.get(ElevatedFeature::ProcessKill), ConsentDecision::Allowed);
+
+        // 重置为 Ask 时删除记录
+        store.set(ElevatedFeature::Mft, ConsentDecision::Ask).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("mft"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_file_is_ignored() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_consent_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("elevation_consent.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        let store = ConsentStore::load(path);
+        assert!(store.all().iter().all(|c| c.decision == ConsentDecision::Ask));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,101 @@
+// 提权助手进程（--elevated-helper）- 以管理员权限运行，主动连接 UI 监听的本机端口，
+// 只执行协议中定义的请求；UI 断开连接即退出（助手自身不监听端口，其他进程无法向它发送请求）
+
+use super::protocol::{self, Hello, HelperRequest, HelperResponse};
+use crate::mft_scanner::ipc::{read_frame, write_frame};
+use anyhow::{bail, Context, Result};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::time::Duration;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 连接 UI 的超时
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 助手进程入口：--elevated-helper --port <端口> --nonce <令牌>
+pub fn run(args: &[String]) {
+    let arg = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|pos| args.get(pos + 1))
+            .cloned()
+    };
+    let (Some(port), Some(nonce)) = (arg("--port").and_then(|p| p.parse::<u16>().ok()), arg("--nonce")) else {
+        eprintln!("Usage: --elevated-helper --port <port> --nonce <nonce>");
+        std::process::exit(2);
+    };
+
+    if let Err(e) = serve(port, &nonce) {
+        eprintln!("Elevated helper stopped: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+fn serve(port: u16, nonce: &str) -> Result<()> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).context("Failed to connect to iLauncher")?;
+    let _ = stream.set_nodelay(true);
+    write_frame(
+        &mut stream,
+        &Hello {
+            nonce: nonce.to_string(),
+            pid: std::process::id(),
+        },
+    )?;
+
+    // UI 退出或关闭连接时结束
+    while let Some(request) = read_frame::<_, HelperRequest>(&mut stream)? {
+        let response = match execute(&request) {
+            Ok(response) => response,
+            Err(e) => HelperResponse::Error {
+                message: format!("{:#}", e),
+            },
+        };
+        write_frame(&mut stream, &response)?;
+    }
+    Ok(())
+}
+
+/// 执行请求（UI 本身已是管理员时直接调用，不经过助手进程）
+pub fn execute(request: &HelperRequest) -> Result<HelperResponse> {
+    protocol::validate(request)?;
+
+    match request {
+        HelperRequest::Ping => Ok(HelperResponse::Pong),
+        HelperRequest::KillProcess { pid, tree } => {
+            let mut command = Command::new("taskkill.exe");
+            command.arg("/F");
+            if *tree {
+                command.arg("/T");
+            }
+            let output = command
+                .args(["/PID", &pid.to_string()])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .context("Failed to run taskkill")?;
+            if !output.status.success() {
+                bail!(
+                    "taskkill failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Ok(HelperResponse::Done)
+        }
+        HelperRequest::StartMftService { ui_pid, data_dir, skip_scan } => {
+            let exe = std::env::current_exe().context("Failed to get current exe path")?;
+            let mut command = Command::new(exe);
+            command.arg("--mft-service");
+            if *skip_scan {
+                command.arg("--skip-scan");
+            }
+            command
+                .args(["--ui-pid", &ui_pid.to_string(), "--data-dir", data_dir])
+                .creation_flags(CREATE_NO_WINDOW)
+                .spawn()
+                .context("Failed to start MFT service")?;
+            Ok(HelperResponse::Done)
+        }
+    }
+}
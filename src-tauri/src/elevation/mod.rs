@@ -0,0 +1,267 @@
+// 按需提权 - 需要管理员权限的功能（MFT 索引、结束受保护进程）共用一个提权助手进程：
+// 首次需要时弹出一次 UAC，之后本次运行内已同意的功能都交给助手执行；
+// 助手由其他功能启动时，尚未决定的功能仍会弹出自己的 UAC，不沿用别的功能的同意；
+// 每个功能记住用户的选择，取消过 UAC 的功能不再在每次启动时提示
+
+pub mod consent;
+#[cfg(target_os = "windows")]
+pub mod helper;
+pub mod protocol;
+
+pub use consent::{ConsentDecision, ElevatedFeature, FeatureConsent};
+pub use protocol::{HelperRequest, HelperResponse};
+
+use anyhow::Result;
+use consent::ConsentStore;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// 同意记录文件名（位于数据目录）
+const CONSENT_FILE: &str = "elevation_consent.json";
+
+/// 全局提权代理
+pub static ELEVATION: Lazy<ElevationBroker> = Lazy::new(ElevationBroker::load);
+
+/// 提权错误
+#[derive(Debug, thiserror::Error)]
+pub enum ElevationError {
+    #[error("Administrator rights for {0} were declined earlier; allow them again in settings")]
+    Declined(&'static str),
+    #[error("Elevation was cancelled")]
+    Cancelled,
+    #[error("Elevation is only supported on Windows")]
+    Unsupported,
+}
+
+pub struct ElevationBroker {
+    consents: Mutex<ConsentStore>,
+    #[cfg(target_os = "windows")]
+    helper: Mutex<Option<windows_impl::HelperConnection>>,
+}
+
+impl ElevationBroker {
+    fn load() -> Self {
+        let consents = match crate::utils::paths::get_data_dir() {
+            Ok(dir) => ConsentStore::load(dir.join(CONSENT_FILE)),
+            Err(e) => {
+                tracing::warn!("Failed to locate elevation consent file: {}", e);
+                ConsentStore::default()
+            }
+        };
+        Self {
+            consents: Mutex::new(consents),
+            #[cfg(target_os = "windows")]
+            helper: Mutex::new(None),
+        }
+    }
+
+    pub fn consent(&self, feature: ElevatedFeature) -> ConsentDecision {
+        self.consents.lock().get(feature)
+    }
+
+    pub fn consents(&self) -> Vec<FeatureConsent> {
+        self.consents.lock().all()
+    }
+
+    pub fn set_consent(&self, feature: ElevatedFeature, decision: ConsentDecision) -> Result<()> {
+        self.consents.lock().set(feature, decision)
+    }
+
+    fn record(&self, feature: ElevatedFeature, decision: ConsentDecision) {
+        if self.consent(feature) == decision {
+            return;
+        }
+        tracing::info!("🛡️ Elevation consent for {:?}: {:?}", feature, decision);
+        if let Err(e) = self.set_consent(feature, decision) {
+            tracing::warn!("Failed to save elevation consent: {}", e);
+        }
+    }
+
+    /// 以管理员权限执行请求（阻塞调用：助手未运行时会等待 UAC 确认）
+    ///
+    /// 用户拒绝过的功能直接返回 Declined，不再弹出 UAC；只为触发 UAC 的功能记录同意
+    #[cfg(target_os = "windows")]
+    pub fn run(&self, feature: ElevatedFeature, request: HelperRequest) -> Result<HelperResponse> {
+        if self.consent(feature) == ConsentDecision::Denied {
+            return Err(ElevationError::Declined(feature.label()).into());
+        }
+        protocol::validate(&request)?;
+
+        // 自身已是管理员时直接执行
+        if is_elevated() {
+            return helper::execute(&request);
+        }
+
+        let mut helper = self.helper.lock();
+        if requires_prompt(helper.is_some(), self.consent(feature)) {
+            // 替换已有连接时旧助手随连接断开退出
+            match windows_impl::HelperConnection::spawn() {
+                Ok(connection) => {
+                    *helper = Some(connection);
+                    self.record(feature, ConsentDecision::Allowed);
+                }
+                Err(e) => {
+                    if matches!(e.downcast_ref::<ElevationError>(), Some(ElevationError::Cancelled)) {
+                        self.record(feature, ConsentDecision::Denied);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let connection = helper.as_mut().expect("helper connected above");
+        match connection.send(&request) {
+            Ok(HelperResponse::Error { message }) => Err(anyhow::anyhow!(message)),
+            Ok(response) => Ok(response),
+            Err(e) => {
+                // 助手已退出，下次请求时重新启动
+                *helper = None;
+                Err(e)
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn run(&self, _feature: ElevatedFeature, _request: HelperRequest) -> Result<HelperResponse> {
+        Err(ElevationError::Unsupported.into())
+    }
+}
+
+/// 是否需要弹出 UAC：助手未运行，或助手已运行但该功能尚未得到用户同意
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn requires_prompt(helper_running: bool, decision: ConsentDecision) -> bool {
+    !helper_running || decision == ConsentDecision::Ask
+}
+
+/// 当前进程是否已有管理员权限
+#[cfg(target_os = "windows")]
+pub fn is_elevated() -> bool {
+    unsafe { windows::Win32::UI::Shell::IsUserAnAdmin().as_bool() }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+/// 以管理员权限启动程序（弹出 UAC 确认，用户取消时返回 ElevationError::Cancelled）
+#[cfg(target_os = "windows")]
+pub fn shell_execute_runas(file: &str, params: &str, cwd: Option<&std::path::Path>) -> Result<()> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+
+    /// 用户在 UAC 提示中选择"否"
+    const SE_ERR_ACCESSDENIED: isize = 5;
+
+    let operation = HSTRING::from("runas");
+    let file = HSTRING::from(file);
+    let params = HSTRING::from(params);
+    let directory = cwd.map(|dir| HSTRING::from(dir.as_os_str()));
+
+    let result = unsafe {
+        ShellExecuteW(
+            None,
+            &operation,
+            &file,
+            &params,
+            directory.as_ref().map(|d| PCWSTR(d.as_ptr())).unwrap_or(PCWSTR::null()),
+            SW_HIDE,
+        )
+    };
+
+    // ShellExecuteW 返回值 > 32 表示成功
+    match result.0 as isize {
+        code if code > 32 => Ok(()),
+        SE_ERR_ACCESSDENIED => Err(ElevationError::Cancelled.into()),
+        code => Err(anyhow::anyhow!("Elevation failed (code {})", code)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn shell_execute_runas(file: &str, _params: &str, _cwd: Option<&std::path::Path>) -> Result<()> {
+    Err(anyhow::anyhow!("Elevated launch not supported on this OS: {}", file))
+}
+
+#[cfg(target_os = "windows")]
+mod windows_impl {
+    use super::protocol::{Hello, HelperRequest, HelperResponse};
+    use crate::mft_scanner::ipc::{read_frame, write_frame};
+    use anyhow::{bail, Context, Result};
+    use std::io::ErrorKind;
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
+    use std::time::{Duration, Instant};
+
+    /// UAC 确认后等待助手连接的最长时间
+    const ACCEPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// 单个请求的超时（结束进程树可能较慢）
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// 与提权助手的连接（由 UI 监听、助手连入，连接断开助手即退出）
+    pub struct HelperConnection {
+        stream: TcpStream,
+    }
+
+    impl HelperConnection {
+        /// 启动助手进程（弹出 UAC）并等待它连入
+        pub fn spawn() -> Result<Self> {
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).context("Failed to bind helper listener")?;
+            let port = listener.local_addr()?.port();
+            let nonce = uuid::Uuid::new_v4().simple().to_string();
+
+            let exe = std::env::current_exe().context("Failed to get current exe path")?;
+            let params = format!("--elevated-helper --port {} --nonce {}", port, nonce);
+            tracing::info!("🛡️ Requesting administrator rights for the elevated helper");
+            super::shell_execute_runas(&exe.to_string_lossy(), &params, None)?;
+
+            listener.set_nonblocking(true)?;
+            let deadline = Instant::now() + ACCEPT_TIMEOUT;
+            loop {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        stream.set_nonblocking(false)?;
+                        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+                        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+                        let _ = stream.set_nodelay(true);
+
+                        // 只接受携带本次 nonce 的连接
+                        match read_frame::<_, Hello>(&mut stream) {
+                            Ok(Some(hello)) if hello.nonce == nonce => {
+                                tracing::info!("✓ Elevated helper connected (PID {})", hello.pid);
+                                return Ok(Self { stream });
+                            }
+                            _ => tracing::warn!("⚠️  Rejected unexpected connection to elevated helper port"),
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        if Instant::now() > deadline {
+                            bail!("Elevated helper did not connect in time");
+                        }
+                        std::thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        pub fn send(&mut self, request: &HelperRequest) -> Result<HelperResponse> {
+            write_frame(&mut self.stream, request)?;
+            read_frame(&mut self.stream)?.context("Elevated helper closed the connection")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_helper_not_shared_without_consent() {
+        assert!(requires_prompt(false, ConsentDecision::Allowed));
+        assert!(requires_prompt(false, ConsentDecision::Ask));
+        assert!(!requires_prompt(true, ConsentDecision::Allowed));
+        // 其他功能启动的助手不能替未决定的功能执行
+        assert!(requires_prompt(true, ConsentDecision::Ask));
+    }
+}
@@ -0,0 +1,95 @@
+// 提权助手协议 - UI 与管理员权限助手进程之间只允许以下几种请求，
+// 双方都会在执行前校验参数，助手不接受任意命令行
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 不允许结束的系统进程（Idle / System）
+const PROTECTED_PIDS: &[u32] = &[0, 4];
+
+/// UI -> 助手请求
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HelperRequest {
+    /// 检查助手是否存活
+    Ping,
+    /// 以管理员权限结束进程（tree 为 true 时连同子进程）
+    KillProcess { pid: u32, tree: bool },
+    /// 启动 MFT Service（随 UI 进程退出）
+    StartMftService {
+        ui_pid: u32,
+        data_dir: String,
+        skip_scan: bool,
+    },
+}
+
+/// 助手 -> UI 响应
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HelperResponse {
+    Pong,
+    Done,
+    Error { message: String },
+}
+
+/// 助手连上 UI 后发送的第一帧（nonce 由 UI 随启动参数传入）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hello {
+    pub nonce: String,
+    pub pid: u32,
+}
+
+/// 校验请求参数（UI 发送前和助手执行前各校验一次）
+pub fn validate(request: &HelperRequest) -> Result<()> {
+    match request {
+        HelperRequest::Ping => Ok(()),
+        HelperRequest::KillProcess { pid, .. } => {
+            if PROTECTED_PIDS.contains(pid) {
+                bail!("Refusing to kill system process {}", pid);
+            }
+            Ok(())
+        }
+        HelperRequest::StartMftService { ui_pid, data_dir, .. } => {
+            if *ui_pid == 0 {
+                bail!("Invalid UI process id");
+            }
+            if !Path::new(data_dir).is_absolute() {
+                bail!("Data directory must be an absolute path: {}", data_dir);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&HelperRequest::Ping).is_ok());
+        assert!(validate(&HelperRequest::KillProcess { pid: 1234, tree: true }).is_ok());
+        assert!(validate(&HelperRequest::KillProcess { pid: 4, tree: false }).is_err());
+
+        let data_dir = std::env::temp_dir().to_string_lossy().to_string();
+        let start = HelperRequest::StartMftService { ui_pid: 42, data_dir, skip_scan: false };
+        assert!(validate(&start).is_ok());
+        let relative = HelperRequest::StartMftService {
+            ui_pid: 42,
+            data_dir: "data".to_string(),
+            skip_scan: false,
+        };
+        assert!(validate(&relative).is_err());
+    }
+
+    #[test]
+    fn test_request_wire_format() {
+        let json = serde_json::to_value(HelperRequest::KillProcess { pid: 7, tree: false }).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "kill_process", "pid": 7, "tree": false}));
+
+        // 协议外的请求无法反序列化
+        let unknown = serde_json::from_str::<HelperRequest>(r#"{"type":"run_command","cmd":"calc.exe"}"#);
+        assert!(unknown.is_err());
+    }
+}
@@ -0,0 +1,129 @@
+// 命名热键绑定 - 主热键以外的全局热键，按 ID 绑定到动作（如 Ctrl+Alt+V 直接打开剪贴板历史）
+// 后端只负责显示窗口并推送事件，由前端根据 payload 中的绑定 ID 和动作执行
+
+use crate::storage::HotkeyBinding;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 推送给前端的事件名（payload: BindingTriggered）
+pub const BINDING_EVENT: &str = "hotkey:binding";
+
+/// 显示启动器并预填查询（argument 为查询文本，如 "cb "）
+pub const ACTION_QUERY: &str = "query";
+
+/// 运行工作流（argument 为工作流 ID，不显示启动器）
+pub const ACTION_WORKFLOW: &str = "workflow";
+
+/// 热键触发事件
+#[derive(Debug, Clone, Serialize)]
+pub struct BindingTriggered {
+    pub id: String,
+    pub action: String,
+    pub argument: String,
+}
+
+/// 校验绑定列表：ID 唯一、动作已知、启用的热键可解析且互不重复
+pub fn validate(bindings: &[HotkeyBinding]) -> Result<()> {
+    let mut ids = HashSet::new();
+    let mut hotkeys = HashSet::new();
+
+    for binding in bindings {
+        if binding.id.trim().is_empty() {
+            bail!("Hotkey binding id must not be empty");
+        }
+        if !ids.insert(binding.id.as_str()) {
+            bail!("Duplicate hotkey binding id: {}", binding.id);
+        }
+        match binding.action.as_str() {
+            ACTION_QUERY => {}
+            ACTION_WORKFLOW if binding.argument.trim().is_empty() => {
+                bail!("Hotkey binding '{}' needs a workflow id", binding.id)
+            }
+            ACTION_WORKFLOW => {}
+            other => bail!("Unknown action '{}' for hotkey binding '{}'", other, binding.id),
+        }
+
+        if !binding.enabled {
+            continue;
+        }
+        let hotkey = super::HotkeyManager::parse_hotkey(&binding.hotkey)
+            .map_err(|e| anyhow::anyhow!("Invalid hotkey for binding '{}': {}", binding.id, e))?;
+        if !hotkeys.insert(hotkey.id()) {
+            bail!("Hotkey '{}' is bound more than once", binding.hotkey);
+        }
+    }
+    Ok(())
+}
+
+/// 处理绑定热键（阻塞调用，需在独立线程中执行）
+pub fn trigger(app_handle: &AppHandle, binding: &HotkeyBinding) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        tracing::warn!("Window 'main' not found!");
+        return;
+    };
+
+    if binding.action == ACTION_QUERY && !window.is_visible().unwrap_or(false) {
+        super::HotkeyManager::show_window(&window, super::show_latency::ShowTrace::start("binding"));
+    }
+
+    tracing::info!("⌨️ Hotkey binding '{}' triggered ({})", binding.id, binding.action);
+    let _ = window.emit(
+        BINDING_EVENT,
+        BindingTriggered {
+            id: binding.id.clone(),
+            action: binding.action.clone(),
+            argument: binding.argument.clone(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(id: &str, hotkey: &str, action: &str, argument: &str) -> HotkeyBinding {
+        HotkeyBinding {
+            id: id.to_string(),
+            hotkey: hotkey.to_string(),
+            action: action.to_string(),
+            argument: argument.to_string(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(validate(&crate::storage::AppConfig::default().hotkey_bindings).is_ok());
+        assert!(validate(&[
+            binding("clipboard", "Ctrl+Alt+V", ACTION_QUERY, "cb "),
+            binding("standup", "Ctrl+Alt+S", ACTION_WORKFLOW, "wf-standup"),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_bindings() {
+        let duplicate_id = [
+            binding("clip", "Ctrl+Alt+V", ACTION_QUERY, "cb "),
+            binding("clip", "Ctrl+Alt+B", ACTION_QUERY, "cb "),
+        ];
+        assert!(validate(&duplicate_id).is_err());
+
+        let same_hotkey = [
+            binding("a", "Ctrl+Alt+V", ACTION_QUERY, ""),
+            binding("b", "alt+ctrl+v", ACTION_QUERY, ""),
+        ];
+        assert!(validate(&same_hotkey).is_err());
+
+        assert!(validate(&[binding("x", "Ctrl+Alt+X", "launch_rocket", "")]).is_err());
+        assert!(validate(&[binding("wf", "Ctrl+Alt+W", ACTION_WORKFLOW, " ")]).is_err());
+        assert!(validate(&[binding("bad", "Ctrl+Alt+", ACTION_QUERY, "")]).is_err());
+
+        // 禁用的绑定不检查热键
+        let mut disabled = binding("off", "not a hotkey", ACTION_QUERY, "");
+        disabled.enabled = false;
+        assert!(validate(&[disabled]).is_ok());
+    }
+}
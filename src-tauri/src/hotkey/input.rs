@@ -0,0 +1,92 @@
+// 输入注入 - 模拟系统快捷键、恢复前台窗口（片段粘贴、快速粘贴、剪贴板变换粘贴共用）
+
+use anyhow::Result;
+
+/// 模拟系统粘贴快捷键
+///
+/// 先释放 Alt/Shift/Win，避免与触发热键时仍按住的修饰键组合成其他快捷键
+#[cfg(target_os = "windows")]
+pub fn send_paste_shortcut() -> Result<()> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        keybd_event, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VK_CONTROL, VK_LWIN, VK_MENU, VK_SHIFT,
+    };
+    const VK_V: u8 = 0x56;
+
+    unsafe {
+        for vk in [VK_MENU, VK_SHIFT, VK_LWIN] {
+            keybd_event(vk.0 as u8, 0, KEYEVENTF_KEYUP, 0);
+        }
+
+        keybd_event(VK_CONTROL.0 as u8, 0, KEYBD_EVENT_FLAGS(0), 0);
+        keybd_event(VK_V, 0, KEYBD_EVENT_FLAGS(0), 0);
+        keybd_event(VK_V, 0, KEYEVENTF_KEYUP, 0);
+        keybd_event(VK_CONTROL.0 as u8, 0, KEYEVENTF_KEYUP, 0);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn send_paste_shortcut() -> Result<()> {
+    std::process::Command::new("osascript")
+        .args(["-e", "tell application \"System Events\" to keystroke \"v\" using command down"])
+        .status()?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub fn send_paste_shortcut() -> Result<()> {
+    std::process::Command::new("xdotool")
+        .args(["key", "--clearmodifiers", "ctrl+v"])
+        .status()?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod foreground {
+    use std::sync::atomic::{AtomicIsize, Ordering};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId, IsWindow, SetForegroundWindow,
+    };
+
+    /// 启动器显示前的前台窗口
+    static PREVIOUS_FOREGROUND: AtomicIsize = AtomicIsize::new(0);
+
+    pub fn remember() {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            let mut process_id = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+            // 前台已经是启动器自身（重复按热键）时保留原来的目标
+            if hwnd.is_invalid() || process_id == std::process::id() {
+                return;
+            }
+            PREVIOUS_FOREGROUND.store(hwnd.0 as isize, Ordering::SeqCst);
+        }
+    }
+
+    pub fn restore() -> bool {
+        let hwnd = HWND(PREVIOUS_FOREGROUND.load(Ordering::SeqCst) as _);
+        unsafe { !hwnd.is_invalid() && IsWindow(hwnd).as_bool() && SetForegroundWindow(hwnd).as_bool() }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod foreground {
+    pub fn remember() {}
+
+    /// macOS / Linux：启动器隐藏后系统自动把焦点还给之前的窗口
+    pub fn restore() -> bool {
+        false
+    }
+}
+
+/// 记录启动器显示前的前台窗口（显示主窗口前调用）
+pub fn remember_foreground_window() {
+    foreground::remember();
+}
+
+/// 把焦点还给启动器显示前的窗口，返回是否成功切换
+pub fn restore_foreground_window() -> bool {
+    foreground::restore()
+}
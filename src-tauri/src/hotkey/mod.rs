@@ -1,11 +1,23 @@
 // 全局热键管理
 
+pub mod bindings;
+pub mod input;
+pub mod quick_paste;
+pub mod selection;
+pub mod show_latency;
+
+use crate::storage::HotkeyBinding;
 use anyhow::Result;
 use global_hotkey::{
     hotkey::{Code, HotKey, Modifiers},
     GlobalHotKeyEvent, GlobalHotKeyManager,
 };
-use tauri::{AppHandle, Manager, Emitter};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use show_latency::ShowTrace;
+use tauri::{AppHandle, Manager, Emitter, WebviewWindow};
 
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::HWND;
@@ -17,9 +29,71 @@ use windows::Win32::UI::WindowsAndMessaging::{
 #[cfg(target_os = "windows")]
 use windows::Win32::UI::Input::KeyboardAndMouse::{keybd_event, KEYEVENTF_KEYUP, VK_MENU};
 
+/// 划词搜索热键 ID（0 表示未注册），供监听线程区分热键
+static SELECTION_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
+/// 语音输入（按住说话）热键 ID
+static VOICE_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
+/// 快速记录热键 ID
+static CAPTURE_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
+/// 快速粘贴热键 ID（下标 + 1 为粘贴位置，0 表示该位置未注册）
+static QUICK_PASTE_HOTKEY_IDS: Lazy<RwLock<Vec<u32>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// 命名热键绑定（热键 ID → 绑定），供监听线程分发
+static BINDING_HOTKEYS: Lazy<RwLock<HashMap<u32, HotkeyBinding>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 热键对应的快速粘贴位置（从 1 开始）
+fn quick_paste_position(id: u32) -> Option<usize> {
+    QUICK_PASTE_HOTKEY_IDS
+        .read()
+        .iter()
+        .position(|&hotkey_id| hotkey_id != 0 && hotkey_id == id)
+        .map(|index| index + 1)
+}
+
+/// 热键字符串解析错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HotkeyParseError {
+    #[error("Empty hotkey string")]
+    Empty,
+    #[error("Empty key segment in hotkey: {0:?}")]
+    EmptySegment(String),
+    #[error("Unknown key: {0}")]
+    UnknownKey(String),
+    #[error("No key code found in hotkey string")]
+    MissingKey,
+}
+
+/// 运行时更新热键的错误（原热键保持不变）
+#[derive(Debug, thiserror::Error)]
+pub enum HotkeyUpdateError {
+    #[error("Invalid hotkey: {0}")]
+    Invalid(#[from] HotkeyParseError),
+    #[error("Hotkey is already used by the {0} hotkey")]
+    InUse(&'static str),
+    #[error("Hotkey is already registered by another application: {0}")]
+    Unavailable(String),
+}
+
+/// 与其他功能热键冲突时返回该功能名称
+fn conflicting_role(hotkey: HotKey, registered: &[(&'static str, HotKey)]) -> Option<&'static str> {
+    registered
+        .iter()
+        .find(|(_, other)| other.id() == hotkey.id())
+        .map(|(role, _)| *role)
+}
+
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
     main_hotkey: Option<HotKey>,
+    selection_hotkey: Option<HotKey>,
+    voice_hotkey: Option<HotKey>,
+    capture_hotkey: Option<HotKey>,
+    quick_paste_hotkeys: Vec<HotKey>,
+    /// 命名热键绑定（绑定 ID → 已注册的热键）
+    binding_hotkeys: HashMap<String, HotKey>,
 }
 
 impl HotkeyManager {
@@ -28,6 +102,11 @@ impl HotkeyManager {
         Ok(Self {
             manager,
             main_hotkey: None,
+            selection_hotkey: None,
+            voice_hotkey: None,
+            capture_hotkey: None,
+            quick_paste_hotkeys: Vec::new(),
+            binding_hotkeys: HashMap::new(),
         })
     }
 
@@ -83,6 +162,138 @@ impl HotkeyManager {
         }
     }
 
+    /// 注册划词搜索热键（空字符串表示禁用）
+    pub fn register_selection_hotkey(&mut self, hotkey_str: &str) -> Result<()> {
+        if let Some(hotkey) = self.selection_hotkey.take() {
+            self.manager.unregister(hotkey)?;
+            SELECTION_HOTKEY_ID.store(0, Ordering::Relaxed);
+        }
+        
+        if hotkey_str.trim().is_empty() {
+            return Ok(());
+        }
+        
+        let hotkey = Self::parse_hotkey(hotkey_str)?;
+        self.manager.register(hotkey)?;
+        self.selection_hotkey = Some(hotkey);
+        SELECTION_HOTKEY_ID.store(hotkey.id(), Ordering::Relaxed);
+        
+        tracing::info!("Registered selection hotkey '{}': {:?}", hotkey_str, hotkey);
+        Ok(())
+    }
+
+    /// 注册语音输入热键（空字符串表示禁用）
+    pub fn register_voice_hotkey(&mut self, hotkey_str: &str) -> Result<()> {
+        if let Some(hotkey) = self.voice_hotkey.take() {
+            self.manager.unregister(hotkey)?;
+            VOICE_HOTKEY_ID.store(0, Ordering::Relaxed);
+        }
+        
+        if hotkey_str.trim().is_empty() {
+            return Ok(());
+        }
+        
+        let hotkey = Self::parse_hotkey(hotkey_str)?;
+        self.manager.register(hotkey)?;
+        self.voice_hotkey = Some(hotkey);
+        VOICE_HOTKEY_ID.store(hotkey.id(), Ordering::Relaxed);
+        
+        tracing::info!("Registered voice hotkey '{}': {:?}", hotkey_str, hotkey);
+        Ok(())
+    }
+
+    /// 注册快速记录热键（空字符串表示禁用）
+    pub fn register_capture_hotkey(&mut self, hotkey_str: &str) -> Result<()> {
+        if let Some(hotkey) = self.capture_hotkey.take() {
+            self.manager.unregister(hotkey)?;
+            CAPTURE_HOTKEY_ID.store(0, Ordering::Relaxed);
+        }
+        
+        if hotkey_str.trim().is_empty() {
+            return Ok(());
+        }
+        
+        let hotkey = Self::parse_hotkey(hotkey_str)?;
+        self.manager.register(hotkey)?;
+        self.capture_hotkey = Some(hotkey);
+        CAPTURE_HOTKEY_ID.store(hotkey.id(), Ordering::Relaxed);
+        
+        tracing::info!("Registered quick capture hotkey '{}': {:?}", hotkey_str, hotkey);
+        Ok(())
+    }
+
+    /// 注册快速粘贴热键（第 N 个热键粘贴第 N 条剪贴板历史，空字符串跳过该位置）
+    pub fn register_quick_paste_hotkeys(&mut self, hotkeys: &[String]) -> Result<()> {
+        for hotkey in std::mem::take(&mut self.quick_paste_hotkeys) {
+            self.manager.unregister(hotkey)?;
+        }
+        
+        let mut ids = Vec::with_capacity(hotkeys.len());
+        for hotkey_str in hotkeys {
+            if hotkey_str.trim().is_empty() {
+                ids.push(0);
+                continue;
+            }
+            
+            // 单个热键冲突不影响其他位置
+            let registered = Self::parse_hotkey(hotkey_str).map_err(anyhow::Error::from).and_then(|hotkey| {
+                self.manager.register(hotkey)?;
+                Ok(hotkey)
+            });
+            match registered {
+                Ok(hotkey) => {
+                    self.quick_paste_hotkeys.push(hotkey);
+                    ids.push(hotkey.id());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to register quick paste hotkey '{}': {}", hotkey_str, e);
+                    ids.push(0);
+                }
+            }
+        }
+        
+        tracing::info!("Registered {} quick paste hotkeys", self.quick_paste_hotkeys.len());
+        *QUICK_PASTE_HOTKEY_IDS.write() = ids;
+        Ok(())
+    }
+
+    /// 注册命名热键绑定（替换之前的全部绑定；单个热键冲突不影响其他绑定，返回注册失败的绑定 ID 和原因）
+    pub fn register_bindings(&mut self, bindings: &[HotkeyBinding]) -> Result<Vec<(String, String)>> {
+        bindings::validate(bindings)?;
+        
+        for (_, hotkey) in self.binding_hotkeys.drain() {
+            self.manager.unregister(hotkey)?;
+        }
+        BINDING_HOTKEYS.write().clear();
+        
+        let mut failed = Vec::new();
+        for binding in bindings.iter().filter(|b| b.enabled) {
+            let registered = Self::parse_hotkey(&binding.hotkey).map_err(anyhow::Error::from).and_then(|hotkey| {
+                if let Some(role) = conflicting_role(hotkey, &self.secondary_hotkeys()) {
+                    anyhow::bail!("already used by the {} hotkey", role);
+                }
+                if self.main_hotkey.is_some_and(|main| main.id() == hotkey.id()) {
+                    anyhow::bail!("already used by the main hotkey");
+                }
+                self.manager.register(hotkey)?;
+                Ok(hotkey)
+            });
+            match registered {
+                Ok(hotkey) => {
+                    self.binding_hotkeys.insert(binding.id.clone(), hotkey);
+                    BINDING_HOTKEYS.write().insert(hotkey.id(), binding.clone());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to register hotkey binding '{}' ({}): {}", binding.id, binding.hotkey, e);
+                    failed.push((binding.id.clone(), e.to_string()));
+                }
+            }
+        }
+        
+        tracing::info!("Registered {} hotkey bindings", self.binding_hotkeys.len());
+        Ok(failed)
+    }
+
     /// 取消注册热键
     pub fn unregister(&mut self) -> Result<()> {
         if let Some(hotkey) = self.main_hotkey {
@@ -93,17 +304,20 @@ impl HotkeyManager {
     }
 
     /// 解析热键字符串 (例如: "Alt+Space", "Ctrl+Shift+A")
-    pub fn parse_hotkey(hotkey_str: &str) -> Result<HotKey> {
-        let parts: Vec<&str> = hotkey_str.split('+').map(|s| s.trim()).collect();
-        
-        if parts.is_empty() {
-            anyhow::bail!("Empty hotkey string");
+    pub fn parse_hotkey(hotkey_str: &str) -> std::result::Result<HotKey, HotkeyParseError> {
+        if hotkey_str.trim().is_empty() {
+            return Err(HotkeyParseError::Empty);
         }
         
         let mut modifiers = Modifiers::empty();
         let mut key_code: Option<Code> = None;
         
-        for part in parts {
+        for part in hotkey_str.split('+').map(|s| s.trim()) {
+            // "Ctrl+"、"A++B" 等缺少按键的片段
+            if part.is_empty() {
+                return Err(HotkeyParseError::EmptySegment(hotkey_str.to_string()));
+            }
+            
             match part.to_lowercase().as_str() {
                 "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
                 "alt" => modifiers |= Modifiers::ALT,
@@ -166,11 +380,11 @@ impl HotkeyManager {
                 "f10" => key_code = Some(Code::F10),
                 "f11" => key_code = Some(Code::F11),
                 "f12" => key_code = Some(Code::F12),
-                _ => anyhow::bail!("Unknown key: {}", part),
+                _ => return Err(HotkeyParseError::UnknownKey(part.to_string())),
             }
         }
         
-        let code = key_code.ok_or_else(|| anyhow::anyhow!("No key code found in hotkey string"))?;
+        let code = key_code.ok_or(HotkeyParseError::MissingKey)?;
         
         let modifier_opt = if modifiers.is_empty() {
             None
@@ -181,20 +395,44 @@ impl HotkeyManager {
         Ok(HotKey::new(modifier_opt, code))
     }
 
-    /// 更新热键
-    pub fn update_hotkey(&mut self, hotkey_str: &str) -> Result<()> {
-        // 先取消注册旧热键
-        self.unregister()?;
-        
-        // 解析新热键
+    /// 更新主热键（先注册新热键，成功后再注销旧热键，失败时原热键继续可用）
+    pub fn update_hotkey(&mut self, hotkey_str: &str) -> std::result::Result<HotKey, HotkeyUpdateError> {
         let hotkey = Self::parse_hotkey(hotkey_str)?;
+        if self.main_hotkey.is_some_and(|current| current.id() == hotkey.id()) {
+            return Ok(hotkey);
+        }
         
-        // 注册新热键
-        self.manager.register(hotkey)?;
-        self.main_hotkey = Some(hotkey);
+        if let Some(role) = conflicting_role(hotkey, &self.secondary_hotkeys()) {
+            return Err(HotkeyUpdateError::InUse(role));
+        }
         
-        tracing::info!("Updated hotkey to: {:?}", hotkey);
-        Ok(())
+        self.manager
+            .register(hotkey)
+            .map_err(|e| HotkeyUpdateError::Unavailable(e.to_string()))?;
+        
+        if let Some(old) = self.main_hotkey.replace(hotkey) {
+            if let Err(e) = self.manager.unregister(old) {
+                tracing::warn!("Failed to unregister old hotkey {:?}: {}", old, e);
+            }
+        }
+        
+        tracing::info!("Updated hotkey to '{}': {:?}", hotkey_str, hotkey);
+        Ok(hotkey)
+    }
+    
+    /// 主热键以外已注册的热键（功能名称, 热键）
+    fn secondary_hotkeys(&self) -> Vec<(&'static str, HotKey)> {
+        let mut hotkeys: Vec<(&'static str, HotKey)> = [
+            ("selection search", self.selection_hotkey),
+            ("voice input", self.voice_hotkey),
+            ("quick capture", self.capture_hotkey),
+        ]
+        .into_iter()
+        .filter_map(|(role, hotkey)| hotkey.map(|hotkey| (role, hotkey)))
+        .collect();
+        hotkeys.extend(self.quick_paste_hotkeys.iter().map(|&hotkey| ("quick paste", hotkey)));
+        hotkeys.extend(self.binding_hotkeys.values().map(|&hotkey| ("custom binding", hotkey)));
+        hotkeys
     }
 
     /// 监听热键事件
@@ -205,10 +443,46 @@ impl HotkeyManager {
             
             loop {
                 if let Ok(event) = receiver.recv() {
+                    // 🎙️ 语音输入：按下开始录音，松开停止并转写
+                    if event.id == VOICE_HOTKEY_ID.load(Ordering::Relaxed) {
+                        Self::handle_voice_hotkey(&app_handle, event.state);
+                        continue;
+                    }
+                    
                     // 只处理按键按下事件，忽略释放事件
                     if event.state == global_hotkey::HotKeyState::Pressed {
+                        // ⏱️ 从收到热键事件开始计时
+                        let trace = ShowTrace::start("hotkey");
                         tracing::info!("Hotkey pressed! Event: {:?}", event);
                         
+                        // 📋 快速粘贴热键
+                        if let Some(position) = quick_paste_position(event.id) {
+                            let app_handle = app_handle.clone();
+                            std::thread::spawn(move || quick_paste::paste_recent(&app_handle, position));
+                            continue;
+                        }
+                        
+                        // ⌨️ 命名热键绑定
+                        let binding = BINDING_HOTKEYS.read().get(&event.id).cloned();
+                        if let Some(binding) = binding {
+                            let app_handle = app_handle.clone();
+                            std::thread::spawn(move || bindings::trigger(&app_handle, &binding));
+                            continue;
+                        }
+                        
+                        // 📝 快速记录热键（独立窗口，不打开搜索界面）
+                        if event.id == CAPTURE_HOTKEY_ID.load(Ordering::Relaxed) {
+                            crate::quick_capture::toggle(&app_handle);
+                            continue;
+                        }
+                        
+                        // 🔥 划词搜索热键
+                        if event.id == SELECTION_HOTKEY_ID.load(Ordering::Relaxed) {
+                            let app_handle = app_handle.clone();
+                            std::thread::spawn(move || selection::search_selection(&app_handle));
+                            continue;
+                        }
+                        
                         // 切换窗口显示状态
                         if let Some(window) = app_handle.get_webview_window("main") {
                             match window.is_visible() {
@@ -219,93 +493,7 @@ impl HotkeyManager {
                                     } else {
                                         // 在新线程中处理窗口显示，避免阻塞热键监听
                                         let window_clone = window.clone();
-                                        std::thread::spawn(move || {
-                                            // 设置置顶
-                                            let _ = window_clone.set_always_on_top(true);
-                                            
-                                            // 居中窗口
-                                            let _ = window_clone.center();
-                                            
-                                            // Windows: 请求用户注意（强制激活窗口）
-                                            #[cfg(target_os = "windows")]
-                                            {
-                                                use tauri::UserAttentionType;
-                                                let _ = window_clone.request_user_attention(Some(UserAttentionType::Informational));
-                                            }
-                                            
-                                            // 显示窗口
-                                            let _ = window_clone.show();
-                                            
-                                            // Windows API 激活
-                                            #[cfg(target_os = "windows")]
-                                            {
-                                                if let Ok(hwnd) = window_clone.hwnd() {
-                                                    unsafe {
-                                                        let hwnd = HWND(hwnd.0 as _);
-                                                        
-                                                        // 释放 Alt 键
-                                                        keybd_event(VK_MENU.0 as u8, 0, KEYEVENTF_KEYUP, 0);
-                                                        
-                                                        // 激活窗口
-                                                        ShowWindow(hwnd, SW_SHOW);
-                                                        BringWindowToTop(hwnd);
-                                                        SetForegroundWindow(hwnd);
-                                                        
-                                                        std::thread::sleep(std::time::Duration::from_millis(10));
-                                                    }
-                                                }
-                                            }
-                                            
-                                            // 设置焦点
-                                            std::thread::sleep(std::time::Duration::from_millis(50));
-                                            let _ = window_clone.set_focus();
-                                            
-                                            // 等待窗口完全激活
-                                            std::thread::sleep(std::time::Duration::from_millis(150));
-                                            
-                                            // Windows: 发送点击消息到 WebView 子窗口激活输入
-                                            #[cfg(target_os = "windows")]
-                                            {
-                                                if let Ok(hwnd) = window_clone.hwnd() {
-                                                    use windows::Win32::Foundation::{LPARAM, WPARAM};
-                                                    
-                                                    unsafe {
-                                                        let hwnd = HWND(hwnd.0 as _);
-                                                        
-                                                        // 查找 WebView 子窗口 (3层嵌套)
-                                                        let target_hwnd = match FindWindowExW(hwnd, None, None, None) {
-                                                            Ok(child1) if !child1.is_invalid() => {
-                                                                match FindWindowExW(child1, None, None, None) {
-                                                                    Ok(child2) if !child2.is_invalid() => {
-                                                                        match FindWindowExW(child2, None, None, None) {
-                                                                            Ok(child3) if !child3.is_invalid() => child3,
-                                                                            _ => child2
-                                                                        }
-                                                                    }
-                                                                    _ => child1
-                                                                }
-                                                            }
-                                                            _ => hwnd
-                                                        };
-                                                        
-                                                        // 输入框坐标
-                                                        let x = 350i32;
-                                                        let y = 50i32;
-                                                        let lparam = LPARAM(((y as u32) << 16 | (x as u32 & 0xFFFF)) as isize);
-                                                        let wparam = WPARAM(0);
-                                                        
-                                                        // 发送点击消息
-                                                        SendMessageW(target_hwnd, WM_LBUTTONDOWN, wparam, lparam);
-                                                        std::thread::sleep(std::time::Duration::from_millis(10));
-                                                        SendMessageW(target_hwnd, WM_LBUTTONUP, wparam, lparam);
-                                                    }
-                                                }
-                                            }
-                                            
-                                            // 发送事件到前端
-                                            std::thread::sleep(std::time::Duration::from_millis(50));
-                                            let _ = window_clone.emit("focus-input", ());
-                                        });
+                                        std::thread::spawn(move || Self::show_window(&window_clone, trace));
                                     }
                                 }
                                 Err(e) => {
@@ -320,4 +508,261 @@ impl HotkeyManager {
             }
         });
     }
+    
+    /// 处理语音输入热键
+    fn handle_voice_hotkey(app_handle: &AppHandle, state: global_hotkey::HotKeyState) {
+        use crate::voice::VOICE_INPUT;
+        
+        match state {
+            global_hotkey::HotKeyState::Pressed => {
+                if let Err(e) = VOICE_INPUT.start_recording(app_handle) {
+                    tracing::error!("Failed to start voice recording: {}", e);
+                }
+            }
+            global_hotkey::HotKeyState::Released => {
+                let app_handle = app_handle.clone();
+                std::thread::spawn(move || {
+                    let storage = app_handle.state::<crate::storage::StorageManager>();
+                    let config = tauri::async_runtime::block_on(storage.load_config())
+                        .unwrap_or_default();
+                    
+                    match VOICE_INPUT.stop_and_transcribe(&app_handle, &config.voice) {
+                        Ok(Some(_)) => {
+                            // 转写完成后显示启动器，由前端根据 target 填入搜索框或发送给 AI
+                            if let Some(window) = app_handle.get_webview_window("main") {
+                                if !window.is_visible().unwrap_or(false) {
+                                    Self::show_window(&window, ShowTrace::start("voice"));
+                                }
+                            }
+                        }
+                        Ok(None) => tracing::info!("🎙️ Empty voice transcript"),
+                        Err(e) => tracing::error!("Voice transcription failed: {}", e),
+                    }
+                });
+            }
+        }
+    }
+    
+    /// 预热主窗口：启动时在屏幕外显示一次再隐藏，提前完成 WebView 合成初始化，缩短首次显示延迟
+    /// （阻塞调用，需在独立线程中执行）
+    pub fn prewarm_window(window: &WebviewWindow) {
+        let _ = window.set_position(tauri::PhysicalPosition::new(-32000, -32000));
+        if window.show().is_err() {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        let _ = window.hide();
+        let _ = window.center();
+        show_latency::mark_prewarmed();
+        tracing::info!("🔥 Main window pre-warmed");
+    }
+    
+    /// 显示并激活主窗口（阻塞调用，需在独立线程中执行），各阶段耗时记入 trace
+    pub fn show_window(window: &WebviewWindow, mut trace: ShowTrace) {
+        // 记录之前的前台窗口（变换粘贴时把焦点还给它）
+        input::remember_foreground_window();
+        
+        // 设置置顶
+        let _ = window.set_always_on_top(true);
+        
+        // 按目标显示器 DPI 调整尺寸并居中窗口
+        let monitor = window.current_monitor().ok().flatten();
+        if let Some(size) = crate::window_geometry::WINDOW_GEOMETRY.size_for_monitor(monitor.as_ref()) {
+            let _ = window.set_size(size);
+        }
+        let _ = window.center();
+        
+        // Windows: 请求用户注意（强制激活窗口）
+        #[cfg(target_os = "windows")]
+        {
+            use tauri::UserAttentionType;
+            let _ = window.request_user_attention(Some(UserAttentionType::Informational));
+        }
+        
+        // 显示窗口
+        let _ = window.show();
+        trace.mark_shown();
+        
+        // Windows API 激活
+        #[cfg(target_os = "windows")]
+        {
+            if let Ok(hwnd) = window.hwnd() {
+                unsafe {
+                    let hwnd = HWND(hwnd.0 as _);
+                    
+                    // 释放 Alt 键
+                    keybd_event(VK_MENU.0 as u8, 0, KEYEVENTF_KEYUP, 0);
+                    
+                    // 激活窗口
+                    ShowWindow(hwnd, SW_SHOW);
+                    BringWindowToTop(hwnd);
+                    SetForegroundWindow(hwnd);
+                    
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        
+        // 设置焦点
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let _ = window.set_focus();
+        trace.mark_focused();
+        
+        // 等待窗口完全激活
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        
+        // Windows: 发送点击消息到 WebView 子窗口激活输入
+        #[cfg(target_os = "windows")]
+        {
+            if let Ok(hwnd) = window.hwnd() {
+                use windows::Win32::Foundation::{LPARAM, WPARAM};
+                
+                unsafe {
+                    let hwnd = HWND(hwnd.0 as _);
+                    
+                    // 查找 WebView 子窗口 (3层嵌套)
+                    let target_hwnd = match FindWindowExW(hwnd, None, None, None) {
+                        Ok(child1) if !child1.is_invalid() => {
+                            match FindWindowExW(child1, None, None, None) {
+                                Ok(child2) if !child2.is_invalid() => {
+                                    match FindWindowExW(child2, None, None, None) {
+                                        Ok(child3) if !child3.is_invalid() => child3,
+                                        _ => child2
+                                    }
+                                }
+                                _ => child1
+                            }
+                        }
+                        _ => hwnd
+                    };
+                    
+                    // 输入框坐标
+                    let x = 350i32;
+                    let y = 50i32;
+                    let lparam = LPARAM(((y as u32) << 16 | (x as u32 & 0xFFFF)) as isize);
+                    let wparam = WPARAM(0);
+                    
+                    // 发送点击消息
+                    SendMessageW(target_hwnd, WM_LBUTTONDOWN, wparam, lparam);
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                    SendMessageW(target_hwnd, WM_LBUTTONUP, wparam, lparam);
+                }
+            }
+        }
+        
+        // 发送事件到前端
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let _ = window.emit("focus-input", ());
+        trace.mark_emitted();
+        
+        // 前端输入框获得焦点后调用 report_input_ready 完成计时
+        show_latency::await_input_ready(window.app_handle(), trace);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// 解析语料：每行 "ok <热键>" 或 "err <热键>"
+    const CORPUS: &str = include_str!("testdata/hotkey_corpus.txt");
+
+    const KEYS: &[(&str, Code)] = &[
+        ("A", Code::KeyA),
+        ("z", Code::KeyZ),
+        ("Space", Code::Space),
+        ("Enter", Code::Enter),
+        ("Esc", Code::Escape),
+        ("5", Code::Digit5),
+        ("F12", Code::F12),
+    ];
+
+    const MODIFIERS: &[(&str, Modifiers)] = &[
+        ("Ctrl", Modifiers::CONTROL),
+        ("Alt", Modifiers::ALT),
+        ("Shift", Modifiers::SHIFT),
+        ("Win", Modifiers::SUPER),
+    ];
+
+    #[test]
+    fn test_parse_hotkey() {
+        let hotkey = HotkeyManager::parse_hotkey("Alt + Shift + Space").unwrap();
+        assert_eq!(hotkey.mods, Modifiers::ALT | Modifiers::SHIFT);
+        assert_eq!(hotkey.key, Code::Space);
+
+        assert_eq!(HotkeyManager::parse_hotkey("  "), Err(HotkeyParseError::Empty));
+        assert_eq!(HotkeyManager::parse_hotkey("++"), Err(HotkeyParseError::EmptySegment("++".to_string())));
+        assert_eq!(HotkeyManager::parse_hotkey("ctrl+"), Err(HotkeyParseError::EmptySegment("ctrl+".to_string())));
+        assert_eq!(HotkeyManager::parse_hotkey("Ctrl+Shift"), Err(HotkeyParseError::MissingKey));
+        assert_eq!(HotkeyManager::parse_hotkey("Ctrl+É"), Err(HotkeyParseError::UnknownKey("É".to_string())));
+    }
+
+    #[test]
+    fn test_conflicting_role() {
+        let registered = [
+            ("selection search", HotkeyManager::parse_hotkey("Ctrl+Shift+S").unwrap()),
+            ("quick paste", HotkeyManager::parse_hotkey("Ctrl+Alt+1").unwrap()),
+        ];
+        let same = HotkeyManager::parse_hotkey("shift + ctrl + s").unwrap();
+        assert_eq!(conflicting_role(same, &registered), Some("selection search"));
+
+        let free = HotkeyManager::parse_hotkey("Alt+Space").unwrap();
+        assert_eq!(conflicting_role(free, &registered), None);
+    }
+
+    #[test]
+    fn test_hotkey_corpus() {
+        for line in CORPUS.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+            let (expected, input) = line.split_once(' ').unwrap_or((line, ""));
+            let result = HotkeyManager::parse_hotkey(input);
+            match expected {
+                "ok" => assert!(result.is_ok(), "expected {:?} to parse: {:?}", input, result),
+                "err" => assert!(result.is_err(), "expected {:?} to be rejected", input),
+                _ => panic!("invalid corpus line: {}", line),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_hotkey_never_panics(input in "\\PC{0,32}") {
+            if let Err(HotkeyParseError::UnknownKey(key)) = HotkeyManager::parse_hotkey(&input) {
+                // 未知按键一定来自输入中的某个片段
+                prop_assert!(input.split('+').any(|part| part.trim() == key));
+            }
+        }
+
+        #[test]
+        fn parse_hotkey_handles_separator_noise(input in "[+ a-zA-Z0-9é中]{0,16}") {
+            let result = HotkeyManager::parse_hotkey(&input);
+            if input.trim().is_empty() {
+                prop_assert_eq!(result, Err(HotkeyParseError::Empty));
+            } else if input.split('+').any(|part| part.trim().is_empty()) {
+                prop_assert!(result.is_err());
+            }
+            if let Err(HotkeyParseError::EmptySegment(text)) = &result {
+                prop_assert_eq!(text, &input);
+            }
+        }
+
+        #[test]
+        fn parse_hotkey_roundtrip(
+            mods in proptest::sample::subsequence(MODIFIERS, 0..=MODIFIERS.len()),
+            key in proptest::sample::select(KEYS),
+            lowercase in any::<bool>(),
+            spaced in any::<bool>(),
+        ) {
+            let separator = if spaced { " + " } else { "+" };
+            let mut text = mods.iter().map(|(name, _)| *name).chain([key.0]).collect::<Vec<_>>().join(separator);
+            if lowercase {
+                text = text.to_lowercase();
+            }
+
+            let hotkey = HotkeyManager::parse_hotkey(&text).unwrap();
+            let expected = mods.iter().fold(Modifiers::empty(), |acc, (_, m)| acc | *m);
+            prop_assert_eq!(hotkey.mods, expected);
+            prop_assert_eq!(hotkey.key, key.1);
+        }
+    }
 }
@@ -0,0 +1,93 @@
+// 快速粘贴 - 按位置把最近的剪贴板条目直接粘贴到当前应用（默认 Ctrl+Alt+1..9），不打开启动器
+// 原理：以自身身份写入第 N 条历史（不会被重新记录，位置保持稳定）→ 模拟粘贴快捷键 → 系统通知确认
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::clipboard::{ClipboardItem, ClipboardManager};
+
+/// 写入剪贴板后等待用户松开热键再粘贴
+const PASTE_DELAY_MS: u64 = 120;
+
+/// 通知中预览的最大字符数
+const MAX_TOAST_CHARS: usize = 60;
+
+/// 粘贴第 position 条（从 1 开始）最近的剪贴板条目（阻塞调用，需在独立线程中执行）
+pub fn paste_recent(app_handle: &AppHandle, position: usize) {
+    match paste_item(app_handle, position) {
+        Ok(item) => toast(app_handle, &format!("Pasted clipboard item #{}", position), &summary(&item)),
+        Err(e) => {
+            tracing::warn!("Quick paste #{} failed: {:#}", position, e);
+            toast(app_handle, "Quick paste failed", &format!("{:#}", e));
+        }
+    }
+}
+
+fn paste_item(app_handle: &AppHandle, position: usize) -> Result<ClipboardItem> {
+    let clipboard = app_handle.state::<ClipboardManager>();
+    let item = clipboard
+        .get_history(1, position.saturating_sub(1))?
+        .into_iter()
+        .next()
+        .with_context(|| format!("No clipboard item at position {}", position))?;
+
+    clipboard.copy_to_clipboard(&item.content, &item.item_type)?;
+    std::thread::sleep(Duration::from_millis(PASTE_DELAY_MS));
+    super::input::send_paste_shortcut()?;
+
+    tracing::info!("📋 Quick pasted clipboard item #{} (id={})", position, item.id);
+    Ok(item)
+}
+
+/// 通知正文（文本压缩空白后截断，图片显示尺寸）
+fn summary(item: &ClipboardItem) -> String {
+    if item.item_type == "image" {
+        return item.preview.clone().unwrap_or_else(|| "Image".to_string());
+    }
+
+    let text = item.content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_TOAST_CHARS {
+        format!("{}…", text.chars().take(MAX_TOAST_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn toast(app_handle: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app_handle.notification().builder().title(title).body(body).show() {
+        tracing::debug!("Failed to show quick paste notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(item_type: &str, content: &str) -> ClipboardItem {
+        ClipboardItem {
+            id: "1".to_string(),
+            item_type: item_type.to_string(),
+            content: content.to_string(),
+            preview: Some("Image 10x20".to_string()),
+            timestamp: 0,
+            favorite: None,
+            file_path: None,
+            category: None,
+            tags: None,
+            ocr_text: None,
+            pinned_slot: None,
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(&item("text", "  hello\n\tworld ")), "hello world");
+        assert_eq!(summary(&item("image", "base64")), "Image 10x20");
+
+        let long = summary(&item("text", &"字".repeat(100)));
+        assert_eq!(long.chars().count(), MAX_TOAST_CHARS + 1);
+        assert!(long.ends_with('…'));
+    }
+}
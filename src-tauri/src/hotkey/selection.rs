@@ -0,0 +1,136 @@
+// 划词搜索 - 捕获前台应用中选中的文本，预填到启动器
+// 原理：暂存剪贴板（文本、富文本、图片、文件列表）→ 模拟复制快捷键 → 读取选中文本 → 恢复剪贴板
+
+use anyhow::Result;
+use crate::clipboard::ClipboardSnapshot;
+use arboard::Clipboard;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 最近一次捕获的选中文本（供划词插件提供上下文结果）
+pub static LAST_SELECTION: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+/// 推送给前端的事件名（payload: 选中文本）
+pub const SEARCH_SELECTION_EVENT: &str = "search-selection";
+
+/// 选中文本最大长度
+const MAX_SELECTION_LENGTH: usize = 2000;
+
+/// 捕获期间暂停剪贴板历史记录，离开作用域自动恢复
+struct SuppressGuard;
+
+impl SuppressGuard {
+    fn new() -> Self {
+        crate::clipboard::set_capture_suppressed(true);
+        Self
+    }
+}
+
+impl Drop for SuppressGuard {
+    fn drop(&mut self) {
+        crate::clipboard::set_capture_suppressed(false);
+    }
+}
+
+/// 划词搜索：捕获选中文本并显示启动器（阻塞调用，需在独立线程中执行）
+pub fn search_selection(app_handle: &AppHandle) {
+    let selection = match capture_selected_text() {
+        Ok(selection) => selection,
+        Err(e) => {
+            tracing::warn!("Failed to capture selected text: {}", e);
+            None
+        }
+    };
+    *LAST_SELECTION.write() = selection.clone();
+
+    let Some(window) = app_handle.get_webview_window("main") else {
+        tracing::warn!("Window 'main' not found!");
+        return;
+    };
+
+    if !window.is_visible().unwrap_or(false) {
+        super::HotkeyManager::show_window(&window, super::show_latency::ShowTrace::start("selection"));
+    }
+
+    if let Some(text) = selection {
+        tracing::info!("🔍 Search selection: {} chars", text.chars().count());
+        let _ = window.emit(SEARCH_SELECTION_EVENT, text);
+    }
+}
+
+/// 捕获前台应用中选中的文本
+pub fn capture_selected_text() -> Result<Option<String>> {
+    let mut clipboard = Clipboard::new()?;
+    let _guard = SuppressGuard::new();
+
+    // 暂存原剪贴板内容
+    let original = ClipboardSnapshot::take(&mut clipboard);
+    let _ = clipboard.clear();
+
+    send_copy_shortcut()?;
+
+    // 等待目标应用写入剪贴板（最多 ~300ms）
+    let mut selected = None;
+    for _ in 0..15 {
+        std::thread::sleep(Duration::from_millis(20));
+        if let Ok(text) = clipboard.get_text() {
+            if !text.is_empty() {
+                selected = Some(text);
+                break;
+            }
+        }
+    }
+
+    // 恢复原剪贴板（标记为自身写入，不会被历史记录重新捕获）
+    if let Some(original) = original {
+        if let Err(e) = original.restore() {
+            tracing::warn!("Failed to restore clipboard after capturing selection: {}", e);
+        }
+    }
+    // 留出时间让监控线程看到恢复后的内容
+    std::thread::sleep(Duration::from_millis(100));
+
+    Ok(selected
+        .map(|text| text.trim().chars().take(MAX_SELECTION_LENGTH).collect::<String>())
+        .filter(|text| !text.is_empty()))
+}
+
+/// 模拟系统复制快捷键
+#[cfg(target_os = "windows")]
+fn send_copy_shortcut() -> Result<()> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        keybd_event, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VK_CONTROL, VK_LWIN, VK_MENU, VK_SHIFT,
+    };
+    const VK_C: u8 = 0x43;
+
+    unsafe {
+        // 先释放触发热键时按住的修饰键，避免组合成其他快捷键
+        for vk in [VK_MENU, VK_SHIFT, VK_LWIN] {
+            keybd_event(vk.0 as u8, 0, KEYEVENTF_KEYUP, 0);
+        }
+
+        keybd_event(VK_CONTROL.0 as u8, 0, KEYBD_EVENT_FLAGS(0), 0);
+        keybd_event(VK_C, 0, KEYBD_EVENT_FLAGS(0), 0);
+        keybd_event(VK_C, 0, KEYEVENTF_KEYUP, 0);
+        keybd_event(VK_CONTROL.0 as u8, 0, KEYEVENTF_KEYUP, 0);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn send_copy_shortcut() -> Result<()> {
+    std::process::Command::new("osascript")
+        .args(["-e", "tell application \"System Events\" to keystroke \"c\" using command down"])
+        .status()?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn send_copy_shortcut() -> Result<()> {
+    std::process::Command::new("xdotool")
+        .args(["key", "--clearmodifiers", "ctrl+c"])
+        .status()?;
+    Ok(())
+}
@@ -0,0 +1,213 @@
+// 窗口显示延迟统计 - 记录从收到热键到窗口显示、获得焦点、输入框就绪的各阶段耗时
+// 每次显示完成后推送 show-latency 事件并写入结构化日志，保留最近的样本供诊断
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 推送给前端的事件名
+pub const SHOW_LATENCY_EVENT: &str = "show-latency";
+
+/// 保留的样本数
+const MAX_SAMPLES: usize = 100;
+
+/// 启动时是否已预热窗口
+static PREWARMED: AtomicBool = AtomicBool::new(false);
+
+/// 等待前端回报输入框就绪的显示过程
+static PENDING: Lazy<Mutex<Option<ShowTrace>>> = Lazy::new(|| Mutex::new(None));
+
+/// 最近的显示延迟样本
+static SAMPLES: Lazy<Mutex<VecDeque<ShowLatency>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// 标记窗口已预热
+pub fn mark_prewarmed() {
+    PREWARMED.store(true, Ordering::Relaxed);
+}
+
+/// 一次窗口显示过程的计时（收到触发事件时创建）
+#[derive(Debug)]
+pub struct ShowTrace {
+    trigger: &'static str,
+    started: Instant,
+    shown: Option<Duration>,
+    focused: Option<Duration>,
+    emitted: Option<Duration>,
+}
+
+impl ShowTrace {
+    /// 开始计时（trigger：hotkey / selection / voice）
+    pub fn start(trigger: &'static str) -> Self {
+        Self {
+            trigger,
+            started: Instant::now(),
+            shown: None,
+            focused: None,
+            emitted: None,
+        }
+    }
+
+    /// window.show() 返回
+    pub fn mark_shown(&mut self) {
+        self.shown = Some(self.started.elapsed());
+    }
+
+    /// 窗口获得焦点
+    pub fn mark_focused(&mut self) {
+        self.focused = Some(self.started.elapsed());
+    }
+
+    /// focus-input 事件已发出
+    pub fn mark_emitted(&mut self) {
+        self.emitted = Some(self.started.elapsed());
+    }
+
+    fn into_latency(self, input_ready: Option<Duration>) -> ShowLatency {
+        let ms = |d: Option<Duration>| d.map(|d| d.as_secs_f64() * 1000.0);
+        ShowLatency {
+            trigger: self.trigger.to_string(),
+            prewarmed: PREWARMED.load(Ordering::Relaxed),
+            show_ms: ms(self.shown),
+            focus_ms: ms(self.focused),
+            emit_ms: ms(self.emitted),
+            input_ready_ms: ms(input_ready),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// 一次显示的各阶段耗时（均从收到触发事件起计算，毫秒）
+#[derive(Debug, Clone, Serialize)]
+pub struct ShowLatency {
+    pub trigger: String,
+    pub prewarmed: bool,
+    pub show_ms: Option<f64>,
+    pub focus_ms: Option<f64>,
+    pub emit_ms: Option<f64>,
+    /// 前端输入框获得焦点（前端未回报时为 None）
+    pub input_ready_ms: Option<f64>,
+    pub timestamp: i64,
+}
+
+impl ShowLatency {
+    /// 端到端耗时：优先使用输入框就绪时间
+    pub fn total_ms(&self) -> Option<f64> {
+        self.input_ready_ms.or(self.emit_ms)
+    }
+}
+
+/// 显示延迟汇总
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShowLatencySummary {
+    pub samples: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    pub recent: Vec<ShowLatency>,
+}
+
+/// 后端阶段完成，等待前端回报输入框就绪（上一次未回报的显示直接记录）
+pub fn await_input_ready(app_handle: &AppHandle, trace: ShowTrace) {
+    if let Some(previous) = PENDING.lock().replace(trace) {
+        record(app_handle, previous.into_latency(None));
+    }
+}
+
+/// 前端输入框已获得焦点，完成本次显示的计时
+pub fn input_ready(app_handle: &AppHandle) -> Option<ShowLatency> {
+    let trace = PENDING.lock().take()?;
+    let elapsed = trace.started.elapsed();
+    let latency = trace.into_latency(Some(elapsed));
+    record(app_handle, latency.clone());
+    Some(latency)
+}
+
+fn record(app_handle: &AppHandle, latency: ShowLatency) {
+    tracing::info!(
+        target: "show_latency",
+        trigger = %latency.trigger,
+        prewarmed = latency.prewarmed,
+        show_ms = latency.show_ms.unwrap_or_default(),
+        focus_ms = latency.focus_ms.unwrap_or_default(),
+        emit_ms = latency.emit_ms.unwrap_or_default(),
+        input_ready_ms = latency.input_ready_ms.unwrap_or_default(),
+        "⏱️ Window show latency"
+    );
+    let _ = app_handle.emit(SHOW_LATENCY_EVENT, &latency);
+
+    let mut samples = SAMPLES.lock();
+    samples.push_back(latency);
+    while samples.len() > MAX_SAMPLES {
+        samples.pop_front();
+    }
+}
+
+/// 最近样本的汇总
+pub fn summary() -> ShowLatencySummary {
+    let samples = SAMPLES.lock();
+    summarize(samples.iter().cloned().collect())
+}
+
+fn summarize(recent: Vec<ShowLatency>) -> ShowLatencySummary {
+    let mut totals: Vec<f64> = recent.iter().filter_map(ShowLatency::total_ms).collect();
+    if totals.is_empty() {
+        return ShowLatencySummary { recent, ..Default::default() };
+    }
+    totals.sort_by(|a, b| a.total_cmp(b));
+
+    let percentile = |p: f64| totals[((totals.len() - 1) as f64 * p).round() as usize];
+    ShowLatencySummary {
+        samples: totals.len(),
+        p50_ms: percentile(0.5),
+        p95_ms: percentile(0.95),
+        max_ms: totals[totals.len() - 1],
+        recent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(emit_ms: f64, input_ready_ms: Option<f64>) -> ShowLatency {
+        ShowLatency {
+            trigger: "hotkey".to_string(),
+            prewarmed: false,
+            show_ms: Some(emit_ms / 4.0),
+            focus_ms: Some(emit_ms / 2.0),
+            emit_ms: Some(emit_ms),
+            input_ready_ms,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_trace_stages() {
+        let mut trace = ShowTrace::start("hotkey");
+        trace.mark_shown();
+        trace.mark_focused();
+        trace.mark_emitted();
+        let latency = trace.into_latency(None);
+        assert!(latency.show_ms.unwrap() <= latency.focus_ms.unwrap());
+        assert!(latency.focus_ms.unwrap() <= latency.emit_ms.unwrap());
+        assert_eq!(latency.total_ms(), latency.emit_ms);
+    }
+
+    #[test]
+    fn test_summary_percentiles() {
+        assert_eq!(summarize(Vec::new()).samples, 0);
+
+        let mut samples: Vec<ShowLatency> = (1..=19).map(|i| sample(i as f64 * 10.0, None)).collect();
+        samples.push(sample(100.0, Some(900.0)));
+        let summary = summarize(samples);
+        assert_eq!(summary.samples, 20);
+        assert_eq!(summary.p50_ms, 110.0);
+        assert_eq!(summary.p95_ms, 190.0);
+        assert_eq!(summary.max_ms, 900.0);
+        assert_eq!(summary.recent.len(), 20);
+    }
+}
@@ -0,0 +1,216 @@
+// 统一 HTTP 客户端 - 所有插件共用的代理、TLS 与重试策略
+// 每个插件使用独立的 User-Agent（iLauncher/<版本> (plugin:<id>)），便于在代理/网关审计日志中区分来源
+
+use crate::storage::HttpConfig;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 全局 HTTP 客户端工厂
+pub static HTTP_CLIENTS: Lazy<HttpClientFactory> = Lazy::new(HttpClientFactory::new);
+
+/// 重试初始等待时间
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// 重试最大等待时间
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// 获取插件专用客户端（使用配置中的默认超时）
+pub fn client(plugin_id: &str) -> Result<Client> {
+    HTTP_CLIENTS.client(plugin_id, None)
+}
+
+/// 获取插件专用客户端（自定义超时，如 AI 长响应）
+pub fn client_with_timeout(plugin_id: &str, timeout: Duration) -> Result<Client> {
+    HTTP_CLIENTS.client(plugin_id, Some(timeout))
+}
+
+/// HTTP 客户端工厂（按插件 + 超时缓存，配置变更后重建）
+pub struct HttpClientFactory {
+    config: RwLock<HttpConfig>,
+    clients: RwLock<HashMap<(String, Option<Duration>), Client>>,
+}
+
+impl HttpClientFactory {
+    fn new() -> Self {
+        Self {
+            config: RwLock::new(HttpConfig::default()),
+            clients: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 应用网络配置（启动时及保存设置后调用）
+    pub fn configure(&self, config: HttpConfig) {
+        *self.config.write() = config;
+        self.clients.write().clear();
+        tracing::info!("🌐 HTTP client configuration updated");
+    }
+
+    /// 当前最大重试次数
+    pub fn max_retries(&self) -> u32 {
+        self.config.read().max_retries
+    }
+
+    /// 下载限速（KB/s，0 为不限速）
+    pub fn download_limit_kbps(&self) -> u64 {
+        self.config.read().download_limit_kbps
+    }
+
+    /// 获取（或创建）插件专用客户端
+    pub fn client(&self, plugin_id: &str, timeout: Option<Duration>) -> Result<Client> {
+        let key = (plugin_id.to_string(), timeout);
+        if let Some(client) = self.clients.read().get(&key) {
+            return Ok(client.clone());
+        }
+
+        let client = build_client(&self.config.read(), plugin_id, timeout)?;
+        self.clients.write().insert(key, client.clone());
+        Ok(client)
+    }
+}
+
+/// 按配置构建客户端
+fn build_client(config: &HttpConfig, plugin_id: &str, timeout: Option<Duration>) -> Result<Client> {
+    let mut builder = Client::builder()
+        .user_agent(user_agent(plugin_id))
+        .timeout(timeout.unwrap_or(Duration::from_secs(config.timeout_secs)))
+        .connect_timeout(Duration::from_secs(10));
+
+    // 代理：system 使用系统/环境变量代理（reqwest 默认行为）
+    match config.proxy_mode.as_str() {
+        "none" => builder = builder.no_proxy(),
+        "custom" if !config.proxy_url.is_empty() => {
+            let proxy = reqwest::Proxy::all(&config.proxy_url)
+                .with_context(|| format!("Invalid proxy URL: {}", config.proxy_url))?
+                .no_proxy(reqwest::NoProxy::from_string(&config.no_proxy));
+            builder = builder.proxy(proxy);
+        }
+        _ => {}
+    }
+
+    // 企业自签 CA
+    if !config.ca_bundle_path.is_empty() {
+        let pem = std::fs::read(&config.ca_bundle_path)
+            .with_context(|| format!("Failed to read CA bundle: {}", config.ca_bundle_path))?;
+        for cert in parse_pem_bundle(&pem)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// 插件专用 User-Agent
+fn user_agent(plugin_id: &str) -> String {
+    format!("iLauncher/{} (plugin:{})", env!("CARGO_PKG_VERSION"), plugin_id)
+}
+
+/// 解析 PEM 证书包（可包含多个证书）
+fn parse_pem_bundle(pem: &[u8]) -> Result<Vec<reqwest::Certificate>> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    let text = String::from_utf8_lossy(pem);
+    let mut certs = Vec::new();
+    let mut rest = text.as_ref();
+
+    while let Some(start) = rest.find(BEGIN) {
+        let Some(end) = rest[start..].find(END) else {
+            break;
+        };
+        let block = &rest[start..start + end + END.len()];
+        certs.push(reqwest::Certificate::from_pem(block.as_bytes())?);
+        rest = &rest[start + end + END.len()..];
+    }
+
+    if certs.is_empty() {
+        return Err(anyhow!("No certificates found in CA bundle"));
+    }
+    Ok(certs)
+}
+
+/// 是否值得重试（限流或服务端错误）
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// 第 attempt 次重试前的等待时间（指数退避）
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// 带重试的请求发送
+#[async_trait]
+pub trait RequestBuilderExt {
+    /// 发送请求；连接失败、超时、429 和 5xx 时按指数退避重试
+    async fn send_with_retry(self) -> Result<Response>;
+}
+
+#[async_trait]
+impl RequestBuilderExt for RequestBuilder {
+    async fn send_with_retry(self) -> Result<Response> {
+        let max_retries = HTTP_CLIENTS.max_retries();
+        let mut attempt = 0;
+
+        loop {
+            // 流式请求体无法克隆，只能发送一次
+            let Some(request) = self.try_clone() else {
+                return Ok(self.send().await?);
+            };
+
+            let retry_reason = match request.send().await {
+                Ok(response) if attempt < max_retries && is_retryable_status(response.status()) => {
+                    response.status().to_string()
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if attempt < max_retries && (e.is_connect() || e.is_timeout()) => e.to_string(),
+                Err(e) => return Err(e.into()),
+            };
+
+            let delay = retry_delay(attempt);
+            attempt += 1;
+            tracing::debug!(
+                "HTTP request failed ({}), retry {}/{} in {:?}",
+                retry_reason,
+                attempt,
+                max_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        assert_eq!(retry_delay(0), Duration::from_millis(500));
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(10), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_user_agent_tags_plugin() {
+        assert!(user_agent("translator").ends_with("(plugin:translator)"));
+    }
+
+    #[test]
+    fn test_parse_pem_bundle_rejects_empty() {
+        assert!(parse_pem_bundle(b"not a certificate").is_err());
+    }
+}
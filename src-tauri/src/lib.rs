@@ -1,15 +1,35 @@
 // iLauncher - 核心模块
 mod clipboard;
+mod clipboard_ignore;
+mod clipboard_ocr;
+mod clipboard_paste;
 mod commands;
 mod core;
+mod download;
+mod elevation;
 mod hotkey;
+mod http;
+mod local_api;
+mod models;
 mod plugin;
 mod preview;
+mod profiling;
+mod quick_capture;
 mod ranking;
+mod safe_mode;
+mod result_aliases;
+mod result_notes;
 mod search_history;
+mod session_context;
+mod shell_menu;
 mod storage;
+mod tts;
 mod statistics;
 mod utils;
+mod voice;
+mod webview_runtime;
+mod window_geometry;
+mod workspace_context;
 
 // MFT 扫描器模块
 #[cfg(target_os = "windows")]
@@ -24,6 +44,9 @@ pub fn run() {
     use tracing_appender::rolling;
     use crate::utils::paths;
     
+    // 🔥 执行上次登记的数据目录迁移（须在解析数据目录、打开任何数据文件之前）
+    let migration = utils::data_location::apply_pending_migration();
+
     // 🔥 创建日志目录和文件写入器
     let log_dir = paths::get_log_dir()
         .expect("Failed to create log directory");
@@ -43,39 +66,99 @@ pub fn run() {
             .with_writer(file_appender)
             .with_ansi(false)
             .with_timer(local_timer))
+        .with(profiling::TraceCaptureLayer) // 单次查询性能追踪（capture_trace）
         .init();
 
     tracing::info!("========== iLauncher Started at {} ==========", 
                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
     tracing::info!("📝 Log file: {:?}", log_dir.join("ilauncher.log"));
     tracing::info!("Starting iLauncher...");
+    
+    match migration {
+        Some(Ok(report)) => tracing::info!(
+            "📦 Data directory migrated: {:?} → {:?} ({} files, {} bytes)",
+            report.from, report.to, report.files, report.bytes
+        ),
+        Some(Err(e)) => tracing::error!("Data directory migration failed, keeping current location: {:#}", e),
+        None => {}
+    }
+    // 📦 应用上次导入的配置档案（须在各管理器打开数据文件之前）
+    utils::profile_backup::apply_pending_import();
+
+    // 🛟 记录启动尝试，--safe-mode 或连续启动失败时进入安全模式
+    let args: Vec<String> = std::env::args().collect();
+    safe_mode::begin_startup(&args);
+
+    // 🌐 WebView 运行时缺失时提示安装或显示错误窗口，而不是启动后崩溃
+    if !webview_runtime::ensure_available() {
+        tracing::error!("WebView runtime unavailable, exiting");
+        return;
+    }
 
-    tauri::Builder::default()
+    let result = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
+        // 🔥 二进制预览资源（图片/PDF），读取文件放到阻塞线程池
+        .register_asynchronous_uri_scheme_protocol(preview::assets::PREVIEW_SCHEME, |_ctx, request, responder| {
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(preview::assets::handle(&request));
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             commands::query,
+            commands::filter_results,
+            commands::query_items,
+            commands::get_result_detail,
+            commands::load_full_preview,
             commands::execute_action,
+            commands::execute_result_index,
+            commands::confirm_action,
+            commands::cancel_confirmation,
+            commands::save_session_state,
+            commands::get_session_state,
+            commands::filter_follow_up,
+            commands::follow_up_back,
+            commands::cancel_follow_up,
             commands::get_plugins,
             commands::get_plugin_config,
             commands::save_plugin_config,
             commands::show_app,
             commands::hide_app,
             commands::toggle_app,
+            commands::set_monitor_window_size,
+            commands::reset_window_geometry,
             commands::load_config,
             commands::get_config,
             commands::save_config,
+            commands::update_hotkey,
+            commands::update_hotkey_bindings,
+            commands::get_elevation_consents,
+            commands::set_elevation_consent,
             commands::toggle_mft,
             commands::get_mft_status,
+            commands::index_storage::get_index_storage_report,
+            commands::index_storage::drop_drive_index,
+            commands::index_storage::compact_index_deltas,
+            commands::index_storage::get_mft_index_stats,
+            commands::index_storage::compact_mft_index,
+            commands::index_storage::set_mft_index_limit,
             commands::clear_cache,
             commands::get_storage_paths,
             commands::get_statistics,
             commands::clear_statistics,
             commands::read_file_preview,
+            commands::get_file_properties,
             commands::get_clipboard_history,
             commands::search_clipboard,
             commands::get_clipboard_favorites,
             commands::copy_to_clipboard,
+            commands::report_show_input_ready,
+            commands::get_show_latency_stats,
+            commands::paste_clipboard_content,
+            commands::pin_clipboard_item,
+            commands::get_pinned_clipboard_items,
+            commands::paste_pinned_slot,
             commands::delete_clipboard_item,
             commands::toggle_clipboard_favorite,
             commands::set_clipboard_category,
@@ -89,8 +172,77 @@ pub fn run() {
             commands::get_search_history,
             commands::clear_search_history,
             commands::remove_search_history,
+            commands::ranking::get_ranking_rules,
+            commands::ranking::save_ranking_rules,
+            commands::ranking::dry_run_ranking_rules,
+            commands::ranking::get_ranking_config,
+            commands::ranking::set_ranking_config,
+            commands::ranking::pin_result,
+            commands::ranking::unpin_result,
+            commands::list_workspace_contexts,
+            commands::get_active_workspace_context,
+            commands::switch_workspace_context,
+            commands::save_workspace_context,
+            commands::delete_workspace_context,
+            commands::list_snippets,
+            commands::save_snippet,
+            commands::delete_snippet,
+            commands::save_quick_capture,
+            commands::hide_quick_capture,
+            commands::list_app_aliases,
+            commands::set_app_aliases,
+            commands::list_search_engines,
+            commands::save_search_engine,
+            commands::delete_search_engine,
+            commands::import_opensearch_engine,
+            commands::local_api::get_local_api_status,
+            commands::local_api::set_local_api_enabled,
+            commands::local_api::regenerate_local_api_token,
+            commands::speak_text,
+            commands::stop_speaking,
+            commands::is_speaking,
+            commands::get_voice_status,
+            commands::start_voice_input,
+            commands::stop_voice_input,
+            commands::start_file_drag,
+            commands::get_shell_context_actions,
+            commands::get_last_selection,
+            commands::get_icon_sets,
+            commands::get_plugin_storage_usage,
+            commands::clear_plugin_storage,
+            commands::start_download,
+            commands::cancel_download,
+            commands::list_downloads,
+            commands::list_models,
+            commands::download_model,
+            commands::delete_model,
+            commands::get_model_requirements,
+            commands::register_plugin,
+            commands::unregister_plugin,
+            commands::reload_plugin,
+            commands::enable_plugin,
+            commands::get_plugin_query_metrics,
+            commands::reset_plugin_query_metrics,
+            commands::disable_plugin,
+            commands::capture_trace,
+            commands::profile::get_data_location,
+            commands::profile::set_data_location,
+            commands::profile::export_profile,
+            commands::profile::import_profile,
+            commands::get_safe_mode_status,
+            commands::reset_store,
+            commands::exit_safe_mode,
+            commands::result_notes::set_result_note,
+            commands::result_notes::get_result_note,
+            commands::result_notes::remove_result_note,
+            commands::result_notes::list_result_notes,
+            commands::result_notes::set_result_alias,
+            commands::result_notes::remove_result_alias,
+            commands::result_notes::list_result_aliases,
+            commands::profile::export_settings,
             commands::get_search_suggestions,
             commands::record_search_execution,
+            commands::get_session_context,
             commands::get_sandbox_config,
             commands::update_sandbox_config,
             commands::get_plugin_permissions,
@@ -132,6 +284,8 @@ pub fn run() {
             commands::suggestion::get_frequent_suggestions,
             commands::suggestion::get_time_based_suggestions,
             commands::suggestion::get_recent_suggestions,
+            commands::suggestion::get_plugin_suggestions,
+            commands::suggestion::dismiss_plugin_suggestion,
         ])
         .setup(|app| {
             // 初始化存储管理器
@@ -143,9 +297,24 @@ pub fn run() {
                 storage_manager.load_config().await.unwrap_or_default()
             });
             
+            // 🛟 安全模式：本次运行使用默认配置（不覆盖配置文件）
+            let config = if safe_mode::is_active() {
+                let defaults = storage::AppConfig::default();
+                tauri::async_runtime::block_on(storage_manager.set_session_config(defaults.clone()));
+                defaults
+            } else {
+                config
+            };
+            
             // 将存储管理器添加到应用状态
             app.manage(storage_manager);
             
+            // 🌐 应用网络配置（代理、CA、重试）
+            http::HTTP_CLIENTS.configure(config.http.clone());
+            
+            // 🖥️ 应用窗口尺寸配置（显示时按显示器 DPI 缩放）
+            window_geometry::WINDOW_GEOMETRY.configure(&config.appearance);
+            
             // 🔥 同步开机自启状态
             if let Err(e) = utils::autostart::sync_with_config(config.advanced.start_on_boot) {
                 tracing::warn!("Failed to sync autostart with config: {}", e);
@@ -172,7 +341,9 @@ pub fn run() {
                 
                 let mut mft_launch_success = false;
                 
-                if use_mft {
+                if use_mft && safe_mode::is_active() {
+                    tracing::info!("🛟 Safe mode: file indexing paused, MFT service not started");
+                } else if use_mft {
                     tracing::info!("🚀 MFT is enabled in file_search plugin, starting MFT service with admin rights...");
                     
                     let exe_path = std::env::current_exe()
@@ -189,45 +360,26 @@ pub fn run() {
                         tracing::error!("❌ Executable not found: {:?}", exe_path);
                         tracing::warn!("  Falling back to BFS mode");
                     } else {
-                        // 🔥 使用 Windows ShellExecuteW API 直接请求管理员权限
-                        // 这比通过 PowerShell 更可靠
-                        use windows::core::HSTRING;
-                        use windows::Win32::UI::Shell::ShellExecuteW;
-                        use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
-                        
-                        let exe_path_str = exe_path.to_string_lossy().to_string();
+                        // 🛡️ 通过提权助手启动（同一次运行内只弹出一次 UAC，取消过则不再提示）
+                        let data_dir = utils::paths::get_app_data_dir()
+                            .map(|d| d.to_string_lossy().to_string())
+                            .unwrap_or_default();
                         
                         // 🆕 Debug 模式下添加 --skip-scan 参数
-                        let parameters = if cfg!(debug_assertions) {
-                            format!("--mft-service --skip-scan --ui-pid {}", ui_pid)
-                        } else {
-                            format!("--mft-service --ui-pid {}", ui_pid)
+                        let request = elevation::HelperRequest::StartMftService {
+                            ui_pid,
+                            data_dir,
+                            skip_scan: cfg!(debug_assertions),
                         };
                         
-                        tracing::debug!("ShellExecuteW: exe={}, params={}", exe_path_str, parameters);
-                        
-                        unsafe {
-                            let operation = HSTRING::from("runas");  // 请求管理员权限
-                            let file = HSTRING::from(exe_path_str.as_str());
-                            let params = HSTRING::from(parameters.as_str());
-                            
-                            let result = ShellExecuteW(
-                                None,                // hwnd
-                                &operation,          // "runas" = 请求管理员权限
-                                &file,               // 可执行文件路径
-                                &params,             // 参数
-                                None,                // 工作目录
-                                SW_HIDE,             // 隐藏窗口
-                            );
-                            
-                            // ShellExecuteW 返回值 > 32 表示成功
-                            if result.0 as isize > 32 {
-                                tracing::info!("✓ MFT service launch requested with admin elevation via ShellExecuteW");
+                        match elevation::ELEVATION.run(elevation::ElevatedFeature::Mft, request) {
+                            Ok(_) => {
+                                tracing::info!("✓ MFT service launch requested via elevated helper");
                                 tracing::info!("  UI PID: {}, Service will auto-exit when UI closes", ui_pid);
-                                tracing::info!("  User will see UAC prompt if not running as admin");
                                 mft_launch_success = true;
-                            } else {
-                                tracing::error!("❌ ShellExecuteW failed with code: {:?}", result.0 as isize);
+                            }
+                            Err(e) => {
+                                tracing::error!("❌ Failed to start MFT service: {:#}", e);
                                 tracing::warn!("  Falling back to BFS mode");
                             }
                         }
@@ -236,6 +388,10 @@ pub fn run() {
                     tracing::info!("⚡ MFT is disabled in file_search plugin, will use BFS scanning mode");
                 }
                 
+                if mft_launch_success {
+                    commands::spawn_mft_progress_events(app.handle().clone());
+                }
+                
                 // 🔥 返回实际是否使用 MFT (只有配置启用且启动成功才返回 true)
                 use_mft && mft_launch_success
             };
@@ -248,6 +404,32 @@ pub fn run() {
                 .expect("Failed to create statistics manager");
             app.manage(statistics_manager);
             
+            // 每 10 秒写入缓存的最终查询（输入停顿后的查询不必等到攒满一批）
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+                loop {
+                    interval.tick().await;
+                    let stats = app_handle.state::<statistics::StatisticsManager>();
+                    if let Err(e) = stats.flush_queries().await {
+                        tracing::warn!("Failed to flush query statistics: {}", e);
+                    }
+                }
+            });
+            
+            // 定期衰减长期未用的点击计数（启动后先执行一次）
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(statistics::frecency::DECAY_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let stats = app_handle.state::<statistics::StatisticsManager>();
+                    if let Err(e) = stats.decay_counts().await {
+                        tracing::warn!("Failed to decay click counts: {}", e);
+                    }
+                }
+            });
+            
             // 初始化搜索历史管理器
             let data_dir = utils::paths::get_data_dir()
                 .expect("Failed to get data directory");
@@ -257,10 +439,34 @@ pub fn run() {
             );
             app.manage(search_history);
             
+            // 初始化结果备注管理器
+            let notes_path = data_dir.join("result_notes.json");
+            let result_notes = result_notes::ResultNotesManager::new(
+                notes_path.to_string_lossy().to_string()
+            );
+            app.manage(result_notes);
+            
             // 初始化剪贴板管理器
             let clipboard_manager = clipboard::ClipboardManager::new()
                 .expect("Failed to create clipboard manager");
             
+            // 🔥 绑定会话上下文（底部状态栏推送）
+            session_context::SESSION_CONTEXT.attach(app.handle().clone());
+            download::DOWNLOADS.attach(app.handle().clone());
+            if let Ok((total, _, _, _)) = clipboard_manager.get_stats() {
+                session_context::SESSION_CONTEXT.set_clipboard_count(total);
+            }
+            
+            // 🔒 加载剪贴板忽略列表、OCR 与敏感内容自动清除设置（剪贴板插件设置）
+            if let Ok(config) = tauri::async_runtime::block_on(
+                app.state::<storage::StorageManager>().get_plugin_config("clipboard")
+            ) {
+                clipboard_ignore::configure(&config);
+                clipboard_ocr::configure(&config);
+                clipboard::configure_secret_clear(&config);
+            }
+            clipboard_manager.backfill_ocr();
+            
             // 启动剪贴板监听
             let app_handle_for_clipboard = app.handle().clone();
             clipboard_manager.start_monitoring(app_handle_for_clipboard);
@@ -272,6 +478,14 @@ pub fn run() {
             let plugin_manager = tauri::async_runtime::block_on(async {
                 plugin::PluginManager::new_with_mft_override(Some(actual_use_mft)).await
             });
+            
+            // 初始化插件数据存储（配额由沙盒检查）
+            let plugin_storage = plugin::plugin_storage::PluginStorageManager::new(
+                data_dir.join("plugins"),
+                plugin_manager.sandbox_manager().clone(),
+            );
+            app.manage(plugin_storage);
+            app.state::<clipboard::ClipboardManager>().attach_sandbox(plugin_manager.sandbox_manager().clone());
             app.manage(plugin_manager);
             
             // 🔥 Phase 3: 初始化插件市场状态
@@ -285,9 +499,16 @@ pub fn run() {
             ));
             // 🔥 加载已安装插件
             tauri::async_runtime::block_on(async {
-                if let Err(e) = plugin_market_state.read().await.registry.load_installed_plugins().await {
+                let market = plugin_market_state.read().await;
+                if let Err(e) = market.registry.load_installed_plugins().await {
                     tracing::warn!("Failed to load installed plugins: {}", e);
                 }
+                // 🔥 启动外部插件进程并注册到插件管理器（安全模式下不启动）
+                if safe_mode::is_active() {
+                    return;
+                }
+                let installed = market.registry.list_plugins().await;
+                app.state::<plugin::PluginManager>().register_external_plugins(&installed).await;
             });
             app.manage(plugin_market_state);
             
@@ -306,6 +527,23 @@ pub fn run() {
             });
             app.manage(workflow_engine);
             
+            // 🔥 启动本地 API（外部集成，默认关闭）
+            if config.local_api.enabled {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let storage = app_handle.state::<storage::StorageManager>();
+                    let result = match commands::local_api::ensure_local_api_token(&storage).await {
+                        Ok(config) => local_api::LOCAL_API
+                            .start(app_handle.clone(), config.local_api.port, config.local_api.token)
+                            .await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::error!("Failed to start local API: {}", e);
+                    }
+                });
+            }
+            
             // 初始化热键管理器
             let mut hotkey_manager = hotkey::HotkeyManager::new()
                 .expect("Failed to create hotkey manager");
@@ -318,8 +556,37 @@ pub fn run() {
                     .expect("Failed to register main hotkey");
             }
             
-            // 使用 Box::leak 让热键管理器永久存活
-            Box::leak(Box::new(hotkey_manager));
+            // 注册划词搜索热键
+            if let Err(e) = hotkey_manager.register_selection_hotkey(&config.general.selection_hotkey) {
+                tracing::warn!("Failed to register selection hotkey: {}", e);
+            }
+            
+            // 注册剪贴板快速粘贴热键
+            if let Err(e) = hotkey_manager.register_quick_paste_hotkeys(&config.general.quick_paste_hotkeys) {
+                tracing::warn!("Failed to register quick paste hotkeys: {}", e);
+            }
+            
+            // 注册语音输入热键（默认关闭）
+            if config.voice.enabled {
+                if let Err(e) = hotkey_manager.register_voice_hotkey(&config.voice.hotkey) {
+                    tracing::warn!("Failed to register voice hotkey: {}", e);
+                }
+            }
+            
+            // 注册快速记录热键（默认关闭）
+            if config.capture.enabled {
+                if let Err(e) = hotkey_manager.register_capture_hotkey(&config.capture.hotkey) {
+                    tracing::warn!("Failed to register quick capture hotkey: {}", e);
+                }
+            }
+            
+            // 注册命名热键绑定（剪贴板历史、计算器、工作流等）
+            if let Err(e) = hotkey_manager.register_bindings(&config.hotkey_bindings) {
+                tracing::warn!("Failed to register hotkey bindings: {}", e);
+            }
+            
+            // 热键管理器作为托管状态，供 update_hotkey 在运行时重新注册
+            app.manage(parking_lot::Mutex::new(hotkey_manager));
             
             // 启动热键监听器
             let app_handle = app.handle().clone();
@@ -331,12 +598,27 @@ pub fn run() {
             // 🔥 移除预渲染逻辑，避免启动时窗口闪现
             // WebView 会在首次调用 show_app 时自动加载
             // 配置中的 "visible": false 确保窗口启动时完全隐藏
+            // 开启 prewarm_window 时在屏幕外预热一次，缩短首次呼出延迟
+            if config.general.prewarm_window {
+                if let Some(window) = app.get_webview_window("main") {
+                    std::thread::spawn(move || hotkey::HotkeyManager::prewarm_window(&window));
+                }
+            }
+            
+            // 🛟 稳定运行一段时间后视为启动成功，清零连续失败计数
+            std::thread::spawn(|| {
+                std::thread::sleep(safe_mode::STABLE_AFTER);
+                safe_mode::mark_startup_succeeded();
+            });
             
             tracing::info!("iLauncher setup completed");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .run(tauri::generate_context!());
+
+    if let Err(e) = result {
+        webview_runtime::report_startup_failure(&e.to_string());
+    }
 }
 
 /// 🔹 运行 MFT Service（全量扫描 + 实时监控）
@@ -350,7 +632,16 @@ pub fn run_mft_service(args: &[String]) {
     use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
     use tracing_appender::rolling;
     
-    // 🔥 初始化文件日志（写入 AppData\Local\iLauncher\logs\mft_service.log）
+    // 🔥 UI 传入的数据目录（自定义数据目录时，提权进程不依赖重定向文件解析路径）
+    if let Some(pos) = args.iter().position(|a| a == "--data-dir") {
+        if let Some(dir) = args.get(pos + 1) {
+            if let Err(e) = crate::utils::paths::set_app_data_dir_override(std::path::Path::new(dir)) {
+                eprintln!("Failed to use data directory {}: {}", dir, e);
+            }
+        }
+    }
+    
+    // 🔥 初始化文件日志（写入 <数据目录>\logs\mft_service.log）
     let log_dir = match crate::utils::paths::get_log_dir() {
         Ok(dir) => dir,
         Err(e) => {
@@ -460,20 +751,30 @@ pub fn run_mft_service(args: &[String]) {
     let process_id = std::process::id();
     info!("✓ MFT Service PID: {}", process_id);
     
-    // 🔥 清理旧的 .ready 标记文件
+    // 🔥 清理旧版本遗留的 .ready 标记文件（就绪状态改由 IPC 查询）
     for drive in &drives {
         let ready_file = format!("{}\\{}.ready", output_dir, drive);
-        if std::path::Path::new(&ready_file).exists() {
-            if let Err(e) = std::fs::remove_file(&ready_file) {
-                warn!("Failed to remove old ready file {}: {}", ready_file, e);
-            } else {
-                info!("✓ Cleaned up old ready file: {}.ready", drive);
-            }
+        if std::path::Path::new(&ready_file).exists() && std::fs::remove_file(&ready_file).is_ok() {
+            info!("✓ Removed legacy ready file: {}.ready", drive);
         }
     }
     
+    // 🔥 用户配置的索引排除规则（全量扫描和增量更新共用）
+    let exclusions = Arc::new(crate::utils::index_exclusions::IndexExclusions::load());
+    
     // 启动 UI 进程监控线程
     let running = Arc::new(AtomicBool::new(true));
+    
+    // 🔥 启动 IPC 服务（UI 通过它查询状态、执行查询、请求关闭）
+    let status = mft_scanner::ipc::StatusHandle::new(&drives);
+    if let Err(e) = mft_scanner::ipc::IpcServer::start(
+        &output_dir,
+        status.clone(),
+        mft_scanner::ipc::IndexQueryBackend::new(&output_dir),
+        running.clone(),
+    ) {
+        error!("❌ Failed to start IPC server: {:#}", e);
+    }
     if let Some(pid) = ui_pid {
         info!("🔍 UI process PID: {}, will auto-exit when UI closes", pid);
         
@@ -498,30 +799,23 @@ pub fn run_mft_service(args: &[String]) {
         // 检查哪些驱动器有有效的索引文件
         let mut existing_drives = Vec::new();
         for drive in &drives {
-            let fst_file = format!("{}\\{}_index.fst", output_dir, drive);
-            let dat_file = format!("{}\\{}_bitmaps.dat", output_dir, drive);
-            let paths_file = format!("{}\\{}_paths.dat", output_dir, drive);
+            // 🔥 只使用已发布且校验通过的索引（崩溃遗留的半写入文件不会被当作有效索引）
+            let verified = mft_scanner::IndexManifest::load(*drive, &output_dir)
+                .and_then(|manifest| manifest.verify_all(&output_dir));
             
-            if std::path::Path::new(&fst_file).exists() 
-                && std::path::Path::new(&dat_file).exists()
-                && std::path::Path::new(&paths_file).exists() {
+            if let Err(e) = verified {
+                warn!("⚠️  Drive {}: Invalid or unpublished index ({:#}), skipping", drive, e);
+                status.set_drive(*drive, mft_scanner::ipc::DriveState::Failed { message: format!("{:#}", e) });
+            } else {
                 info!("✓ Drive {}: Found existing index files", drive);
                 existing_drives.push(*drive);
-                
-                // 创建 .ready 标记文件
-                let ready_file = format!("{}\\{}.ready", output_dir, drive);
-                if let Err(e) = std::fs::write(&ready_file, format!("{}", process_id)) {
-                    warn!("Failed to create ready file {}: {}", ready_file, e);
-                } else {
-                    info!("✓ Created ready marker: {}.ready", drive);
-                }
-            } else {
-                warn!("⚠️  Drive {}: Missing index files, skipping", drive);
+                status.set_drive(*drive, mft_scanner::ipc::DriveState::Ready);
             }
         }
         
         if existing_drives.is_empty() {
             error!("❌ No valid index files found! Please run without --skip-scan first.");
+            mft_scanner::ipc::remove_endpoint(&output_dir);
             std::process::exit(1);
         }
         
@@ -542,7 +836,10 @@ pub fn run_mft_service(args: &[String]) {
         scan_config.drives = drives.clone();
         scan_config.output_dir = output_dir.clone();
         
-        let scanner = mft_scanner::MultiDriveScanner::new(&scan_config);
+        status.set_phase(mft_scanner::ipc::ServicePhase::Scanning);
+        let scanner = mft_scanner::MultiDriveScanner::new(&scan_config)
+            .with_status(status.clone())
+            .with_exclusions(exclusions.clone());
         
         match scanner.scan_all() {
             Ok(_) => {
@@ -550,11 +847,13 @@ pub fn run_mft_service(args: &[String]) {
             }
             Err(e) => {
                 error!("❌ Scan failed: {:#}", e);
+                mft_scanner::ipc::remove_endpoint(&output_dir);
                 std::process::exit(1);
             }
         }
         
-        drives.clone()
+        // 只监控扫描成功的驱动器
+        status.snapshot().ready_drives()
     };
     
     info!("");
@@ -564,24 +863,15 @@ pub fn run_mft_service(args: &[String]) {
     info!("✓ Successfully scanned drives: {:?}", scanned_drives);
     info!("");
     
-    // 🔥 为每个成功扫描的驱动器创建 .ready 标记文件（如果还没创建的话）
-    if !skip_scan {
-        for drive in &scanned_drives {
-            let ready_file = format!("{}\\{}.ready", output_dir, drive);
-            if let Err(e) = std::fs::write(&ready_file, format!("{}", process_id)) {
-                error!("❌ Failed to create ready file {}: {}", ready_file, e);
-            } else {
-                info!("✓ Created ready file: {}.ready (PID: {})", drive, process_id);
-            }
-        }
-    }
-    
     // 如果只需要扫描，则退出
     if scan_only {
         info!("🏁 Scan-only mode, exiting...");
+        mft_scanner::ipc::remove_endpoint(&output_dir);
         std::process::exit(0);
     }
     
+    status.set_phase(mft_scanner::ipc::ServicePhase::Monitoring);
+    
     // ============ 阶段 2: 实时监控 (使用 USN Incremental Updater) ============
     info!("╔═══════════════════════════════════════════╗");
     info!("║    Phase 2: Real-time Monitoring          ║");
@@ -601,20 +891,23 @@ pub fn run_mft_service(args: &[String]) {
         error!("Failed to set Ctrl+C handler: {:#}", e);
     }
     
+    let max_index_bytes = config.max_index_bytes();
     let monitor_handles: Vec<_> = scanned_drives
         .iter()
         .map(|&drive| {
             let output_dir_clone = output_dir.clone();
             let running_clone = running.clone();
+            let exclusions = exclusions.clone();
             
             // 🔥 启动后台合并任务（每个驱动器独立）
-            mft_scanner::DeltaMerger::start_background_merge(drive, output_dir_clone.clone());
+            mft_scanner::DeltaMerger::start_background_merge(drive, output_dir_clone.clone(), max_index_bytes);
             
             std::thread::spawn(move || {
                 info!("👀 Starting USN incremental updater for drive {}:", drive);
                 
                 // 🔥 使用新的 UsnIncrementalUpdater（基于 prompt.txt）
-                let mut updater = mft_scanner::UsnIncrementalUpdater::new(drive, output_dir_clone.clone());
+                let mut updater = mft_scanner::UsnIncrementalUpdater::new(drive, output_dir_clone.clone())
+                    .with_exclusions(exclusions);
                 
                 // 初始化 USN 位置
                 if let Err(e) = updater.initialize() {
@@ -649,6 +942,7 @@ pub fn run_mft_service(args: &[String]) {
     for handle in monitor_handles {
         handle.join().unwrap();
     }
+    mft_scanner::ipc::remove_endpoint(&output_dir);
     
     info!("");
     info!("🎉 MFT Service stopped successfully");
@@ -662,6 +956,12 @@ pub fn run_mft_service(_args: &[String]) {
     std::process::exit(1);
 }
 
+/// 提权助手进程入口（--elevated-helper，由 UI 通过 UAC 启动）
+#[cfg(target_os = "windows")]
+pub fn run_elevated_helper(args: &[String]) {
+    elevation::helper::run(args);
+}
+
 /// 监控 UI 进程，当 UI 退出时自动退出 Service
 #[cfg(target_os = "windows")]
 fn monitor_ui_process(ui_pid: u32, running: std::sync::Arc<std::sync::atomic::AtomicBool>) {
@@ -819,6 +1119,13 @@ fn setup_tray_icon(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error
                     if let Some(webview_window) = app.get_webview_window("main") {
                         let _ = webview_window.hide();
                     }
+                    // 退出前写入缓存的查询统计
+                    let stats = app.state::<statistics::StatisticsManager>();
+                    stats.finalize_pending_queries();
+                    if let Err(e) = tauri::async_runtime::block_on(stats.flush_queries()) {
+                        tracing::warn!("Failed to flush query statistics: {}", e);
+                    }
+                    safe_mode::mark_startup_succeeded();
                     std::thread::sleep(std::time::Duration::from_millis(100));
                     std::process::exit(0);
                 }
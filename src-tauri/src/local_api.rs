@@ -0,0 +1,445 @@
+// 本地 HTTP + WebSocket API - 供 Stream Deck、AutoHotkey、浏览器扩展等外部集成调用
+// 仅监听 127.0.0.1，所有接口（除 health）需要携带令牌：
+//   Authorization: Bearer <token>，WebSocket 升级请求也可使用 ?token=<token>
+//   （查询参数会出现在日志和浏览器历史中，普通 HTTP 接口不接受）
+
+use crate::clipboard::ClipboardManager;
+use crate::plugin::workflow_engine::WorkflowEngine;
+use crate::plugin::PluginManager;
+use crate::statistics::StatisticsManager;
+use crate::storage::StorageManager;
+use anyhow::Result;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+
+/// 全局本地 API 服务
+pub static LOCAL_API: Lazy<LocalApiServer> = Lazy::new(LocalApiServer::new);
+
+/// 生成新的访问令牌
+pub fn generate_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// 本地 API 状态（返回给设置界面）
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    pub token: String,
+    pub base_url: String,
+}
+
+/// 本地 API 服务
+pub struct LocalApiServer {
+    token: Arc<RwLock<String>>,
+    /// 当前令牌下建立的 WebSocket 会话，令牌轮换或重启时取消
+    sessions: Arc<RwLock<CancellationToken>>,
+    server: Mutex<Option<RunningServer>>,
+    port: RwLock<Option<u16>>,
+}
+
+/// 运行中的服务任务
+struct RunningServer {
+    shutdown: oneshot::Sender<()>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    app: AppHandle,
+    token: Arc<RwLock<String>>,
+    sessions: Arc<RwLock<CancellationToken>>,
+}
+
+impl LocalApiServer {
+    fn new() -> Self {
+        Self {
+            token: Arc::new(RwLock::new(String::new())),
+            sessions: Arc::new(RwLock::new(CancellationToken::new())),
+            server: Mutex::new(None),
+            port: RwLock::new(None),
+        }
+    }
+
+    /// 是否正在运行
+    pub fn is_running(&self) -> bool {
+        self.server.lock().is_some()
+    }
+
+    /// 更新令牌（立即生效，无需重启），旧令牌下建立的 WebSocket 连接随之断开
+    pub fn set_token(&self, token: String) {
+        *self.token.write() = token;
+        self.close_sessions();
+    }
+
+    /// 断开所有已建立的 WebSocket 连接
+    fn close_sessions(&self) {
+        let old = std::mem::replace(&mut *self.sessions.write(), CancellationToken::new());
+        old.cancel();
+    }
+
+    /// 启动服务（已在运行时先停止）
+    pub async fn start(&self, app: AppHandle, port: u16, token: String) -> Result<()> {
+        self.stop().await;
+        self.set_token(token);
+
+        let state = ApiState {
+            app,
+            token: self.token.clone(),
+            sessions: self.sessions.clone(),
+        };
+
+        let protected = Router::new()
+            .route("/query", post(query_handler))
+            .route("/execute", post(execute_handler))
+            .route("/confirm", post(confirm_handler))
+            .route("/clipboard", get(clipboard_list_handler).post(clipboard_copy_handler))
+            .route("/workflows", get(workflow_list_handler))
+            .route("/workflows/:id/run", post(workflow_run_handler))
+            .route("/ws", get(ws_handler))
+            .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+        let router = Router::new()
+            .route("/api/v1/health", get(health_handler))
+            .nest("/api/v1", protected)
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let task = tauri::async_runtime::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = rx.await;
+                })
+                .await;
+            if let Err(e) = result {
+                tracing::error!("Local API server error: {}", e);
+            }
+        });
+
+        *self.server.lock() = Some(RunningServer { shutdown: tx, task });
+        *self.port.write() = Some(port);
+        tracing::info!("🔌 Local API listening on http://127.0.0.1:{}/api/v1", port);
+        Ok(())
+    }
+
+    /// 停止服务，等待监听端口释放后返回（重启时才能立即重新绑定）
+    pub async fn stop(&self) {
+        let Some(server) = self.server.lock().take() else {
+            return;
+        };
+        *self.port.write() = None;
+        self.close_sessions();
+        let _ = server.shutdown.send(());
+        if let Err(e) = server.task.await {
+            tracing::warn!("Local API server task failed: {}", e);
+        }
+        tracing::info!("🔌 Local API stopped");
+    }
+}
+
+// ==================== 鉴权 ====================
+
+async fn auth_middleware(State(state): State<ApiState>, req: Request, next: Next) -> Response {
+    let expected = state.token.read().clone();
+
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|s| s.to_string());
+    let query_token = ws_query_token(req.uri());
+
+    let authorized = !expected.is_empty()
+        && bearer.or(query_token).map_or(false, |token| constant_time_eq(&token, &expected));
+
+    if !authorized {
+        return api_error(StatusCode::UNAUTHORIZED, "Invalid or missing token");
+    }
+
+    next.run(req).await
+}
+
+/// WebSocket 升级请求的 ?token= 参数（浏览器 WebSocket 无法设置请求头）
+fn ws_query_token(uri: &axum::http::Uri) -> Option<String> {
+    if !uri.path().ends_with("/ws") {
+        return None;
+    }
+    uri.query()?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == "token")
+        .map(|(_, v)| v.to_string())
+}
+
+/// 常量时间比较，避免计时攻击
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn api_error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+fn into_response(result: Result<Value, String>) -> Response {
+    match result {
+        Ok(value) => Json(value).into_response(),
+        Err(e) => api_error(StatusCode::BAD_REQUEST, &e),
+    }
+}
+
+// ==================== 分发 ====================
+
+#[derive(Debug, Deserialize)]
+struct ExecuteRequest {
+    result_id: String,
+    action_id: String,
+    plugin_id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    subtitle: String,
+}
+
+/// 统一方法分发（REST 与 WebSocket 共用）
+async fn dispatch(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    match method {
+        "query" => {
+            let query = params.get("query").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let results = crate::commands::query_untracked(
+                query,
+                &app.state::<PluginManager>(),
+                &app.state::<StatisticsManager>(),
+                &app.state::<crate::search_history::SearchHistoryManager>(),
+                &app.state::<crate::result_notes::ResultNotesManager>(),
+                &app.state::<StorageManager>(),
+            )
+            .await?;
+            serde_json::to_value(results).map_err(|e| e.to_string())
+        }
+        "execute" => {
+            let req: ExecuteRequest = serde_json::from_value(params).map_err(|e| e.to_string())?;
+            let outcome = crate::commands::execute_action(
+                req.result_id,
+                req.action_id,
+                req.plugin_id,
+                req.title,
+                req.subtitle,
+                crate::core::types::WoxImage::emoji("🔌"),
+                app.state::<PluginManager>(),
+                app.state::<StatisticsManager>(),
+                app.state::<crate::result_notes::ResultNotesManager>(),
+                app.state::<StorageManager>(),
+                app.clone(),
+            )
+            .await?;
+            Ok(json!({ "success": true, "outcome": outcome }))
+        }
+        // 破坏性动作返回 confirm 结果后，凭其中的 token 确认执行
+        "confirm" => {
+            let token = params
+                .get("token")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'token'")?
+                .to_string();
+            let outcome = crate::commands::confirm_action(
+                token,
+                app.state::<PluginManager>(),
+                app.state::<StatisticsManager>(),
+                app.state::<crate::result_notes::ResultNotesManager>(),
+                app.state::<StorageManager>(),
+                app.clone(),
+            )
+            .await?;
+            Ok(json!({ "success": true, "outcome": outcome }))
+        }
+        "clipboard.list" => {
+            let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+            let clipboard = app.state::<ClipboardManager>();
+            let items = match params.get("query").and_then(|v| v.as_str()) {
+                Some(query) if !query.is_empty() => clipboard.search(query, limit),
+                _ => clipboard.get_history(limit, 0),
+            }
+            .map_err(|e| e.to_string())?;
+            serde_json::to_value(items).map_err(|e| e.to_string())
+        }
+        "clipboard.copy" => {
+            let content = params
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+            app.state::<ClipboardManager>()
+                .copy_to_clipboard(content, "text")
+                .map_err(|e| e.to_string())?;
+            Ok(json!({ "success": true }))
+        }
+        "workflow.list" => {
+            let engine = app.state::<Arc<tokio::sync::RwLock<WorkflowEngine>>>();
+            let workflows = engine.read().await.list_workflows().await;
+            serde_json::to_value(workflows).map_err(|e| e.to_string())
+        }
+        "workflow.run" => {
+            let id = params.get("id").and_then(|v| v.as_str()).ok_or("Missing 'id'")?.to_string();
+            let variables: HashMap<String, Value> = params
+                .get("variables")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default();
+            let engine = app.state::<Arc<tokio::sync::RwLock<WorkflowEngine>>>();
+            let context = engine
+                .read()
+                .await
+                .execute_workflow(&id, variables)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(json!({ "variables": context.variables }))
+        }
+        _ => Err(format!("Unknown method: {}", method)),
+    }
+}
+
+// ==================== REST 接口 ====================
+
+async fn health_handler() -> Json<Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+async fn query_handler(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    into_response(dispatch(&state.app, "query", body).await)
+}
+
+async fn execute_handler(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    into_response(dispatch(&state.app, "execute", body).await)
+}
+
+async fn confirm_handler(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    into_response(dispatch(&state.app, "confirm", body).await)
+}
+
+async fn clipboard_list_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let params = json!({
+        "limit": params.get("limit").and_then(|l| l.parse::<u64>().ok()).unwrap_or(20),
+        "query": params.get("query").cloned().unwrap_or_default(),
+    });
+    into_response(dispatch(&state.app, "clipboard.list", params).await)
+}
+
+async fn clipboard_copy_handler(State(state): State<ApiState>, Json(body): Json<Value>) -> Response {
+    into_response(dispatch(&state.app, "clipboard.copy", body).await)
+}
+
+async fn workflow_list_handler(State(state): State<ApiState>) -> Response {
+    into_response(dispatch(&state.app, "workflow.list", Value::Null).await)
+}
+
+async fn workflow_run_handler(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    body: Option<Json<Value>>,
+) -> Response {
+    let variables = body.map(|Json(b)| b).unwrap_or(Value::Null);
+    let variables = variables.get("variables").cloned().unwrap_or(json!({}));
+    into_response(dispatch(&state.app, "workflow.run", json!({ "id": id, "variables": variables })).await)
+}
+
+// ==================== WebSocket ====================
+
+/// WebSocket 请求: {"id": 1, "method": "query", "params": {"query": "chrome"}}
+/// WebSocket 响应: {"id": 1, "result": ...} 或 {"id": 1, "error": "..."}
+#[derive(Debug, Deserialize)]
+struct WsRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+async fn ws_handler(State(state): State<ApiState>, ws: WebSocketUpgrade) -> Response {
+    let closed = state.sessions.read().clone();
+    ws.on_upgrade(move |socket| handle_socket(socket, state.app, closed))
+}
+
+async fn handle_socket(mut socket: WebSocket, app: AppHandle, closed: CancellationToken) {
+    loop {
+        let message = tokio::select! {
+            _ = closed.cancelled() => {
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+            message = socket.recv() => match message {
+                Some(Ok(message)) => message,
+                _ => break,
+            },
+        };
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let response = match serde_json::from_str::<WsRequest>(&text) {
+            Ok(req) => match dispatch(&app, &req.method, req.params).await {
+                Ok(result) => json!({ "id": req.id, "result": result }),
+                Err(e) => json!({ "id": req.id, "error": e }),
+            },
+            Err(e) => json!({ "id": Value::Null, "error": format!("Invalid request: {}", e) }),
+        };
+
+        if socket.send(Message::Text(response.to_string())).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("abc", "abc"));
+        assert!(!constant_time_eq("abc", "abd"));
+        assert!(!constant_time_eq("abc", "abcd"));
+    }
+
+    #[test]
+    fn test_query_token_only_for_websocket() {
+        let ws: axum::http::Uri = "/api/v1/ws?token=abc".parse().unwrap();
+        assert_eq!(ws_query_token(&ws).as_deref(), Some("abc"));
+        let nested: axum::http::Uri = "/ws?x=1&token=abc".parse().unwrap();
+        assert_eq!(ws_query_token(&nested).as_deref(), Some("abc"));
+
+        let http: axum::http::Uri = "/api/v1/clipboard?token=abc".parse().unwrap();
+        assert_eq!(ws_query_token(&http), None);
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_token());
+    }
+}
@@ -19,6 +19,13 @@ fn main() {
         return;
     }
     
+    // 🔹 提权助手模式（执行需要管理员权限的受限请求）
+    #[cfg(target_os = "windows")]
+    if args.contains(&"--elevated-helper".to_string()) {
+        ilauncher_lib::run_elevated_helper(&args);
+        return;
+    }
+    
     // 🔹 正常 GUI 模式
     ilauncher_lib::run()
 }
@@ -0,0 +1,202 @@
+// 索引压缩 - 墓碑记录与已删除条目清理
+// USN 更新器把删除/重命名前的 file_id 记入 {盘符}_tombstones.dat（RoaringBitmap），查询时排除；
+// 压缩时从所有 gram 位图中移除这些 ID，服务未运行时还会重写 _paths.dat / _offsets.dat 并重新编号
+
+use super::manifest;
+use anyhow::Result;
+use roaring::RoaringBitmap;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// 重新编号表中表示已删除的 ID
+const REMOVED: u32 = u32::MAX;
+
+/// 单个驱动器的压缩结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DriveCompaction {
+    pub drive: char,
+    /// 是否合并了增量索引
+    pub merged_delta: bool,
+    /// 从位图中移除的墓碑条目数
+    pub pruned_entries: u64,
+    /// 是否重写了路径文件（释放已删除路径占用的空间）
+    pub paths_rewritten: bool,
+}
+
+/// 压缩结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompactionReport {
+    pub drives: Vec<DriveCompaction>,
+    pub removed_temp_files: usize,
+    /// 压缩前后总占用之差（合并会增大主索引，可能为 0）
+    pub freed_bytes: u64,
+    /// 服务运行中时只清理位图，路径文件留待服务停止后重写
+    pub service_running: bool,
+}
+
+/// 读取墓碑位图（文件不存在时为空）
+pub fn load_tombstones(drive: char, output_dir: &str) -> Result<RoaringBitmap> {
+    let path = manifest::file_path(drive, output_dir, manifest::TOMBSTONES_FILE);
+    match std::fs::read(&path) {
+        Ok(bytes) => Ok(RoaringBitmap::deserialize_from(&bytes[..])?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RoaringBitmap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 墓碑条目数（读取失败视为 0）
+pub fn tombstone_count(drive: char, output_dir: &str) -> u64 {
+    load_tombstones(drive, output_dir).map(|t| t.len()).unwrap_or(0)
+}
+
+/// 追加墓碑（与已有记录取并集后原子写入）
+pub fn append_tombstones(drive: char, output_dir: &str, ids: &RoaringBitmap) -> Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let mut tombstones = load_tombstones(drive, output_dir)?;
+    tombstones |= ids;
+
+    let mut bytes = Vec::with_capacity(tombstones.serialized_size());
+    tombstones.serialize_into(&mut bytes)?;
+    manifest::write_atomic(&manifest::file_path(drive, output_dir, manifest::TOMBSTONES_FILE), &bytes)
+}
+
+/// 删除墓碑文件（路径文件重写后旧 ID 不再存在）
+pub fn clear_tombstones(drive: char, output_dir: &str) -> Result<()> {
+    match std::fs::remove_file(manifest::file_path(drive, output_dir, manifest::TOMBSTONES_FILE)) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 重写路径文件，跳过墓碑条目；写入 paths_out / offsets_out，返回旧 ID → 新 ID 的映射
+pub fn rewrite_paths(
+    paths_in: &Path,
+    paths_out: &Path,
+    offsets_out: &Path,
+    dead: &RoaringBitmap,
+) -> Result<Vec<u32>> {
+    let mut reader = BufReader::with_capacity(8 * 1024 * 1024, File::open(paths_in)?);
+    let mut paths_writer = BufWriter::new(File::create(paths_out)?);
+
+    let mut remap = Vec::new();
+    let mut offsets: Vec<u64> = Vec::new();
+    let mut offset = 0u64;
+    let mut len_buf = [0u8; 4];
+    let mut path = Vec::new();
+
+    while reader.read_exact(&mut len_buf).is_ok() {
+        let len = u32::from_le_bytes(len_buf) as usize;
+        path.resize(len, 0);
+        reader.read_exact(&mut path)?;
+
+        let old_id = remap.len() as u32;
+        if dead.contains(old_id) {
+            remap.push(REMOVED);
+            continue;
+        }
+
+        remap.push(offsets.len() as u32);
+        offsets.push(offset);
+        paths_writer.write_all(&len_buf)?;
+        paths_writer.write_all(&path)?;
+        offset += 4 + len as u64;
+    }
+    paths_writer.flush()?;
+
+    // 偏移量文件格式与 IndexBuilder 一致：条目数 + 每条 8 字节偏移
+    let mut offsets_writer = BufWriter::new(File::create(offsets_out)?);
+    offsets_writer.write_all(&(offsets.len() as u32).to_le_bytes())?;
+    for offset in offsets {
+        offsets_writer.write_all(&offset.to_le_bytes())?;
+    }
+    offsets_writer.flush()?;
+
+    Ok(remap)
+}
+
+/// 从位图中移除墓碑 ID；有重新编号表时同时转换为新 ID，返回移除的条目数
+pub fn prune_bitmap(bitmap: &mut RoaringBitmap, dead: &RoaringBitmap, remap: Option<&[u32]>) -> u64 {
+    let before = bitmap.len();
+    *bitmap -= dead;
+    let removed = before - bitmap.len();
+
+    if let Some(remap) = remap {
+        // 超出映射表范围的 ID（路径文件中不存在）一并丢弃
+        *bitmap = bitmap
+            .iter()
+            .filter_map(|id| remap.get(id as usize).copied())
+            .filter(|&id| id != REMOVED)
+            .collect();
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_paths(path: &Path, paths: &[&str]) {
+        let mut data = Vec::new();
+        for p in paths {
+            data.extend_from_slice(&(p.len() as u32).to_le_bytes());
+            data.extend_from_slice(p.as_bytes());
+        }
+        std::fs::write(path, data).unwrap();
+    }
+
+    #[test]
+    fn test_tombstones_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_tombstones_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output_dir = dir.to_string_lossy().to_string();
+
+        assert_eq!(tombstone_count('C', &output_dir), 0);
+        append_tombstones('C', &output_dir, &[1u32, 5].into_iter().collect()).unwrap();
+        append_tombstones('C', &output_dir, &[5u32, 9].into_iter().collect()).unwrap();
+        assert_eq!(load_tombstones('C', &output_dir).unwrap().iter().collect::<Vec<_>>(), vec![1, 5, 9]);
+        assert_eq!(tombstone_count('D', &output_dir), 0);
+
+        clear_tombstones('C', &output_dir).unwrap();
+        clear_tombstones('C', &output_dir).unwrap();
+        assert_eq!(tombstone_count('C', &output_dir), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rewrite_paths_and_prune() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_compaction_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths_in = dir.join("C_paths.dat");
+        write_paths(&paths_in, &["C:\\a.txt", "C:\\old.txt", "C:\\b.txt", "C:\\gone.txt"]);
+
+        let dead: RoaringBitmap = [1u32, 3].into_iter().collect();
+        let remap = rewrite_paths(&paths_in, &dir.join("paths.new"), &dir.join("offsets.new"), &dead).unwrap();
+        assert_eq!(remap, vec![0, REMOVED, 1, REMOVED]);
+
+        write_paths(&dir.join("expected.dat"), &["C:\\a.txt", "C:\\b.txt"]);
+        assert_eq!(std::fs::read(dir.join("paths.new")).unwrap(), std::fs::read(dir.join("expected.dat")).unwrap());
+
+        let offsets = std::fs::read(dir.join("offsets.new")).unwrap();
+        assert_eq!(&offsets[..4], &2u32.to_le_bytes());
+        assert_eq!(&offsets[4..12], &0u64.to_le_bytes());
+        assert_eq!(&offsets[12..20], &(4u64 + 8).to_le_bytes());
+
+        // 只移除墓碑 ID
+        let mut bitmap: RoaringBitmap = [0u32, 1, 2].into_iter().collect();
+        assert_eq!(prune_bitmap(&mut bitmap, &dead, None), 1);
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 2]);
+
+        // 移除并重新编号，超出路径文件范围的 ID 丢弃
+        let mut bitmap: RoaringBitmap = [1u32, 2, 3, 7].into_iter().collect();
+        assert_eq!(prune_bitmap(&mut bitmap, &dead, Some(&remap)), 2);
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![1]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
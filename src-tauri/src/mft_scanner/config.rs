@@ -20,7 +20,10 @@ pub fn load_config() -> Result<ScanConfig> {
     // 自动检测并更新驱动器列表（如果检测到新的 NTFS 驱动器）
     #[cfg(target_os = "windows")]
     {
-        let detected_drives = ScanConfig::detect_ntfs_drives();
+        let detected_drives: Vec<char> = ScanConfig::detect_ntfs_drives()
+            .into_iter()
+            .filter(|d| !config.excluded_drives.contains(d))
+            .collect();
         
         // 如果检测到的驱动器比配置中的多，或者配置为空，则更新
         if config.drives.is_empty() || detected_drives.len() > config.drives.len() {
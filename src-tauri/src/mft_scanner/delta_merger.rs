@@ -1,5 +1,8 @@
 // Delta 索引合并器 - 后台任务定期合并增量索引到主索引
 
+use super::compaction::{self, DriveCompaction};
+use super::manifest::{self, IndexManifest};
+use super::storage_report;
 use anyhow::Result;
 use roaring::RoaringBitmap;
 use fst::{Map, MapBuilder, Streamer};
@@ -39,37 +42,109 @@ impl DeltaMerger {
         false
     }
     
+    /// 索引目录总占用是否超出上限（且本驱动器有可压缩的增量或墓碑）
+    pub fn over_budget(&self, max_bytes: Option<u64>) -> bool {
+        let Some(max_bytes) = max_bytes else {
+            return false;
+        };
+        let total = storage_report::storage_report(Path::new(&self.output_dir), &[])
+            .map(|report| report.total_bytes)
+            .unwrap_or(0);
+        total > max_bytes
+            && (self.has_delta() || compaction::tombstone_count(self.drive_letter, &self.output_dir) > 0)
+    }
+    
     /// 执行合并（重建 FST + RoaringBitmap）
     pub fn merge(&self) -> Result<()> {
-        info!("🔄 Starting delta index merge for drive {}...", self.drive_letter);
+        self.compact(false).map(|_| ())
+    }
+    
+    /// 压缩索引：合并 delta、从位图中移除墓碑条目
+    /// rewrite_paths 时同时重写路径文件并重新编号（会改变 file_id，只能在 MFT 服务未运行时执行）
+    pub fn compact(&self, rewrite_paths: bool) -> Result<DriveCompaction> {
+        let dead = compaction::load_tombstones(self.drive_letter, &self.output_dir)?;
+        let mut result = DriveCompaction {
+            drive: self.drive_letter,
+            merged_delta: self.has_delta(),
+            ..Default::default()
+        };
+        if !result.merged_delta && dead.is_empty() {
+            return Ok(result);
+        }
+        
+        info!("🔄 Starting index compaction for drive {}...", self.drive_letter);
         let start = std::time::Instant::now();
         
         // 1. 加载现有主索引
         let mut main_index = self.load_main_index()?;
         
-        // 2. 加载 delta 索引
-        let delta_index = self.load_delta_index()?;
+        // 2. 合并 delta 索引
+        if result.merged_delta {
+            for (gram, delta_bitmap) in self.load_delta_index()? {
+                main_index.entry(gram)
+                    .and_modify(|existing| *existing |= delta_bitmap.clone())
+                    .or_insert(delta_bitmap);
+            }
+        }
+        
+        // 3. 重写路径文件到临时文件（索引重建成功后再一起替换）
+        let rewritten = if rewrite_paths && !dead.is_empty() {
+            let paths_file = manifest::file_path(self.drive_letter, &self.output_dir, manifest::PATHS_FILE);
+            let offsets_file = manifest::file_path(self.drive_letter, &self.output_dir, manifest::OFFSETS_FILE);
+            let paths_temp = manifest::temp_path(&paths_file);
+            let offsets_temp = manifest::temp_path(&offsets_file);
+            let remap = compaction::rewrite_paths(&paths_file, &paths_temp, &offsets_temp, &dead)?;
+            Some((remap, [(paths_temp, paths_file), (offsets_temp, offsets_file)]))
+        } else {
+            None
+        };
         
-        // 3. 合并 bitmap
-        for (gram, delta_bitmap) in delta_index {
-            main_index.entry(gram)
-                .and_modify(|existing| *existing |= delta_bitmap.clone())
-                .or_insert(delta_bitmap);
+        // 4. 移除墓碑条目（重写路径时同时换成新编号）
+        let remap = rewritten.as_ref().map(|(remap, _)| remap.as_slice());
+        for bitmap in main_index.values_mut() {
+            compaction::prune_bitmap(bitmap, &dead, remap);
         }
+        main_index.retain(|_, bitmap| !bitmap.is_empty());
+        result.pruned_entries = dead.len();
         
-        // 4. 重建 FST + Bitmap 文件（使用临时文件避免文件锁）
+        // 5. 重建 FST + Bitmap 文件（使用临时文件避免文件锁）
         self.rebuild_index(&main_index)?;
+        let mut replaced = vec![manifest::FST_FILE, manifest::BITMAPS_FILE];
+        
+        if let Some((_, files)) = rewritten {
+            for (temp, dest) in files {
+                manifest::publish_file(&temp, &dest)?;
+            }
+            replaced.extend([manifest::PATHS_FILE, manifest::OFFSETS_FILE]);
+            // 旧编号已不存在，墓碑随之失效
+            compaction::clear_tombstones(self.drive_letter, &self.output_dir)?;
+            result.paths_rewritten = true;
+        }
+        
+        // 6. 更新清单中被替换文件的校验和（之后才递增版本号通知 UI 重新加载）
+        let mut index_manifest = IndexManifest::load(self.drive_letter, &self.output_dir)?;
+        index_manifest.refresh(&self.output_dir, &replaced)?;
+        index_manifest.publish(&self.output_dir)?;
         
-        // 5. 删除 delta 文件
+        // 7. 删除 delta 文件
         self.cleanup_delta()?;
         
-        // 6. 更新版本号（通知 UI 重新加载）
+        // 8. 更新版本号（通知 UI 重新加载）
         self.increment_version()?;
         
-        let elapsed = start.elapsed();
-        info!("✓ Delta merge completed in {:.2}s", elapsed.as_secs_f64());
+        info!(
+            "✓ Index compaction completed in {:.2}s (delta merged: {}, tombstones pruned: {}, paths rewritten: {})",
+            start.elapsed().as_secs_f64(),
+            result.merged_delta,
+            result.pruned_entries,
+            result.paths_rewritten
+        );
         
-        Ok(())
+        Ok(result)
+    }
+    
+    fn has_delta(&self) -> bool {
+        Path::new(&format!("{}\\{}_index_delta.dat", self.output_dir, self.drive_letter)).exists()
     }
     
     /// 加载主索引
@@ -219,8 +294,8 @@ impl DeltaMerger {
         
         // 🔥 Windows 特性：rename 可以覆盖被 mmap 的文件
         // UI 进程的 mmap 不会失效，但下次 reload 会看到新内容
-        std::fs::rename(&fst_file_new, &fst_file)?;
-        std::fs::rename(&bitmap_file_new, &bitmap_file)?;
+        manifest::publish_file(Path::new(&fst_file_new), Path::new(&fst_file))?;
+        manifest::publish_file(Path::new(&bitmap_file_new), Path::new(&bitmap_file))?;
         
         info!("✓ Index files replaced via rename");
         
@@ -262,8 +337,8 @@ impl DeltaMerger {
         Ok(())
     }
     
-    /// 后台定期检查并合并
-    pub fn start_background_merge(drive_letter: char, output_dir: String) {
+    /// 后台定期检查并合并（max_index_bytes 为索引目录占用上限，超出时自动压缩）
+    pub fn start_background_merge(drive_letter: char, output_dir: String, max_index_bytes: Option<u64>) {
         std::thread::spawn(move || {
             let merger = DeltaMerger::new(drive_letter, output_dir);
             
@@ -271,12 +346,23 @@ impl DeltaMerger {
                 // 每 5 分钟检查一次
                 std::thread::sleep(Duration::from_secs(300));
                 
-                if merger.should_merge() {
+                let over_budget = merger.over_budget(max_index_bytes);
+                if over_budget {
+                    info!("🔔 Index size limit exceeded, compacting drive {}...", drive_letter);
+                    let _ = storage_report::remove_stale_temp_files(
+                        Path::new(&merger.output_dir),
+                        Some(drive_letter),
+                        storage_report::STALE_TEMP_AGE,
+                    );
+                } else if merger.should_merge() {
                     info!("🔔 Delta index threshold reached, starting merge...");
-                    
-                    if let Err(e) = merger.merge() {
-                        error!("❌ Delta merge failed: {:#}", e);
-                    }
+                } else {
+                    continue;
+                }
+                
+                // 服务运行中 USN 更新器仍在追加路径，只清理位图
+                if let Err(e) = merger.compact(false) {
+                    error!("❌ Delta merge failed: {:#}", e);
                 }
             }
         });
@@ -1,6 +1,9 @@
 // 3-Gram 倒排索引构建器 - 基于 prompt.txt 方案
 // 使用 FST + RoaringBitmap 实现极致压缩
 
+use super::compaction;
+use super::manifest::{self, IndexManifest};
+use super::path_match::{self, PathMatch};
 use anyhow::Result;
 use fst::{Map, MapBuilder};
 use roaring::RoaringBitmap;
@@ -73,9 +76,10 @@ impl IndexBuilder {
         self.total_grams = self.gram_index.len();
         info!("✓ Index built: {} files, {} unique 3-grams", path_id, self.total_grams);
         
-        // 🔥 保存 offset index 到文件
-        let offset_file = format!("{}\\{}_offsets.dat", output_dir, self.drive_letter);
-        let mut offset_writer = BufWriter::new(File::create(offset_file)?);
+        // 🔥 保存 offset index 到文件（写临时文件后原子替换）
+        let offset_file = manifest::file_path(self.drive_letter, output_dir, manifest::OFFSETS_FILE);
+        let offset_temp = manifest::temp_path(&offset_file);
+        let mut offset_writer = BufWriter::new(File::create(&offset_temp)?);
         
         // 写入文件数量
         offset_writer.write_all(&(offset_index.len() as u32).to_le_bytes())?;
@@ -85,6 +89,8 @@ impl IndexBuilder {
             offset_writer.write_all(&(*offset as u64).to_le_bytes())?;
         }
         offset_writer.flush()?;
+        drop(offset_writer);
+        manifest::publish_file(&offset_temp, &offset_file)?;
         
         Ok(())
     }
@@ -118,16 +124,19 @@ impl IndexBuilder {
         info!("💾 Saving compressed index...");
         
         // 🔥 步骤 1: 构建 FST（3-gram -> offset 映射）
-        let fst_file = format!("{}\\{}_index.fst", output_dir, self.drive_letter);
-        let bitmap_file = format!("{}\\{}_bitmaps.dat", output_dir, self.drive_letter);
+        // 先写入 .new 临时文件，全部完成后再原子替换，崩溃时不会留下半写入的索引
+        let fst_file = manifest::file_path(self.drive_letter, output_dir, manifest::FST_FILE);
+        let bitmap_file = manifest::file_path(self.drive_letter, output_dir, manifest::BITMAPS_FILE);
+        let fst_temp = manifest::temp_path(&fst_file);
+        let bitmap_temp = manifest::temp_path(&bitmap_file);
         
         // 排序所有 3-gram（FST 需要有序）
         let mut sorted_grams: Vec<_> = self.gram_index.iter().collect();
         sorted_grams.sort_by(|a, b| a.0.cmp(b.0));
         
         // 构建 FST
-        let mut fst_builder = MapBuilder::new(BufWriter::new(File::create(&fst_file)?))?;
-        let mut bitmap_writer = BufWriter::new(File::create(&bitmap_file)?);
+        let mut fst_builder = MapBuilder::new(BufWriter::new(File::create(&fst_temp)?))?;
+        let mut bitmap_writer = BufWriter::new(File::create(&bitmap_temp)?);
         
         let mut current_offset: u64 = 0;
         
@@ -148,6 +157,10 @@ impl IndexBuilder {
         
         fst_builder.finish()?;
         bitmap_writer.flush()?;
+        drop(bitmap_writer);
+        
+        manifest::publish_file(&fst_temp, &fst_file)?;
+        manifest::publish_file(&bitmap_temp, &bitmap_file)?;
         
         // 计算压缩率
         let fst_size = std::fs::metadata(&fst_file)?.len();
@@ -170,6 +183,13 @@ impl IndexBuilder {
     }
 }
 
+/// 路径匹配查询的命中结果
+#[derive(Debug, Clone)]
+pub struct PathHit {
+    pub path: String,
+    pub matched: PathMatch,
+}
+
 /// 索引查询器（零拷贝，内存映射）
 pub struct IndexQuery {
     drive_letter: char,
@@ -177,6 +197,7 @@ pub struct IndexQuery {
     fst_map: Map<memmap2::Mmap>,
     bitmap_mmap: memmap2::Mmap,
     delta_index: Option<DeltaIndex>,  // 增量索引
+    tombstones: RoaringBitmap,  // 已删除/重命名条目（压缩前从结果中排除）
     loaded_version: u64,  // 已加载的索引版本号
 }
 
@@ -190,25 +211,13 @@ impl IndexQuery {
     pub fn open(drive_letter: char, output_dir: &str) -> Result<Self> {
         let open_start = std::time::Instant::now();
         
-        let fst_file = format!("{}\\{}_index.fst", output_dir, drive_letter);
-        let bitmap_file = format!("{}\\{}_bitmaps.dat", output_dir, drive_letter);
-        
-        // 内存映射 FST
-        let fst_mmap = unsafe {
-            memmap2::MmapOptions::new()
-                .map(&File::open(fst_file)?)?
-        };
-        let fst_map = Map::new(fst_mmap)?;
-        
-        // 内存映射 Bitmap 文件
-        let bitmap_mmap = unsafe {
-            memmap2::MmapOptions::new()
-                .map(&File::open(bitmap_file)?)?
-        };
+        let (fst_map, bitmap_mmap) = Self::map_verified(drive_letter, output_dir)?;
         
         // 加载 delta 索引（如果存在）
         let delta_index = Self::load_delta_index(drive_letter, output_dir).ok();
         
+        let tombstones = compaction::load_tombstones(drive_letter, output_dir).unwrap_or_default();
+        
         // 读取当前版本号
         let loaded_version = Self::read_version(drive_letter, output_dir);
         
@@ -218,6 +227,7 @@ impl IndexQuery {
             fst_map,
             bitmap_mmap,
             delta_index,
+            tombstones,
             loaded_version,
         };
         
@@ -229,6 +239,37 @@ impl IndexQuery {
         Ok(query)
     }
     
+    /// 映射 FST 和 Bitmap 文件，并按发布清单校验（未发布或校验失败的索引拒绝加载）
+    fn map_verified(drive_letter: char, output_dir: &str) -> Result<(Map<memmap2::Mmap>, memmap2::Mmap)> {
+        let manifest = IndexManifest::load(drive_letter, output_dir)?;
+        
+        let fst_file = manifest::file_path(drive_letter, output_dir, manifest::FST_FILE);
+        let bitmap_file = manifest::file_path(drive_letter, output_dir, manifest::BITMAPS_FILE);
+        
+        // 内存映射 FST
+        let fst_mmap = unsafe {
+            memmap2::MmapOptions::new()
+                .map(&File::open(fst_file)?)?
+        };
+        
+        // 内存映射 Bitmap 文件
+        let bitmap_mmap = unsafe {
+            memmap2::MmapOptions::new()
+                .map(&File::open(bitmap_file)?)?
+        };
+        
+        let verify_start = std::time::Instant::now();
+        manifest.verify_bytes(manifest::FST_FILE, &fst_mmap)?;
+        manifest.verify_bytes(manifest::BITMAPS_FILE, &bitmap_mmap)?;
+        tracing::debug!(
+            "✓ Index manifest verified for drive {} in {:.2}ms",
+            drive_letter,
+            verify_start.elapsed().as_secs_f64() * 1000.0
+        );
+        
+        Ok((Map::new(fst_mmap)?, bitmap_mmap))
+    }
+    
     /// 预热 mmap 映射的数据（强制 OS 加载到物理内存）
     fn warmup_mmap(&self) -> Result<()> {
         let warmup_start = std::time::Instant::now();
@@ -300,24 +341,14 @@ impl IndexQuery {
     pub fn reload(&mut self) -> Result<()> {
         tracing::info!("🔄 Reloading index for drive {} (version changed)...", self.drive_letter);
         
-        let fst_file = format!("{}\\{}_index.fst", self.output_dir, self.drive_letter);
-        let bitmap_file = format!("{}\\{}_bitmaps.dat", self.output_dir, self.drive_letter);
-        
-        // 重新映射 FST
-        let fst_mmap = unsafe {
-            memmap2::MmapOptions::new()
-                .map(&File::open(fst_file)?)?
-        };
-        self.fst_map = Map::new(fst_mmap)?;
-        
-        // 重新映射 Bitmap
-        self.bitmap_mmap = unsafe {
-            memmap2::MmapOptions::new()
-                .map(&File::open(bitmap_file)?)?
-        };
+        // 校验失败时保留旧映射继续服务
+        let (fst_map, bitmap_mmap) = Self::map_verified(self.drive_letter, &self.output_dir)?;
+        self.fst_map = fst_map;
+        self.bitmap_mmap = bitmap_mmap;
         
         // 重新加载 delta 索引
         self.delta_index = Self::load_delta_index(self.drive_letter, &self.output_dir).ok();
+        self.tombstones = compaction::load_tombstones(self.drive_letter, &self.output_dir).unwrap_or_default();
         
         // 更新版本号
         self.loaded_version = Self::read_version(self.drive_letter, &self.output_dir);
@@ -419,13 +450,16 @@ impl IndexQuery {
         }
         
         // 🔥 步骤 3: 快速交集运算（约 1-5ms）
-        let result_bitmap = if bitmaps.len() == 1 {
+        let mut result_bitmap = if bitmaps.len() == 1 {
             bitmaps.into_iter().next().unwrap()
         } else {
             // 多个 bitmap 交集
             bitmaps.into_iter().reduce(|a, b| a & b).unwrap()
         };
         
+        // 排除已删除的条目（压缩后才从位图中真正移除）
+        result_bitmap -= &self.tombstones;
+        
         // 🔥 步骤 4: 转换为 Vec（约 1-2ms）
         let results: Vec<u32> = result_bitmap.iter().take(limit).collect();
         
@@ -441,6 +475,57 @@ impl IndexQuery {
         Ok(results)
     }
     
+    /// 将已不存在的路径记为墓碑（执行结果时发现文件丢失，USN 事件可能尚未处理），返回是否找到该路径
+    pub fn forget_path(&mut self, reader: &PathReader, path: &str) -> Result<bool> {
+        // 同名文件可能很多，按文件名检索后用完整路径确认
+        const MAX_CANDIDATES: usize = 10_000;
+        let Some(name) = std::path::Path::new(path).file_name().map(|n| n.to_string_lossy().into_owned()) else {
+            return Ok(false);
+        };
+        
+        let dead: RoaringBitmap = self
+            .search(&name, MAX_CANDIDATES)?
+            .into_iter()
+            .filter(|&file_id| reader.get_path(file_id).is_ok_and(|p| p.eq_ignore_ascii_case(path)))
+            .collect();
+        if dead.is_empty() {
+            return Ok(false);
+        }
+        
+        compaction::append_tombstones(self.drive_letter, &self.output_dir, &dead)?;
+        self.tombstones |= &dead;
+        info!("🪦 Tombstoned missing path on drive {}: {}", self.drive_letter, path);
+        Ok(true)
+    }
+    
+    /// 路径匹配查询：按锚点词检索候选，在完整路径上校验全部词并按匹配质量排序
+    /// 锚点只命中目录（候选全部校验失败）时换下一个锚点重试
+    pub fn search_paths(&self, reader: &PathReader, keyword: &str, limit: usize) -> Result<Vec<PathHit>> {
+        // 候选需要二次校验和排序，多取一些
+        const MAX_CANDIDATES: usize = 20_000;
+        let candidates = limit.saturating_mul(20).clamp(1_000, MAX_CANDIDATES);
+        
+        for anchor in path_match::anchor_terms(keyword) {
+            let mut hits: Vec<PathHit> = self
+                .search(&anchor, candidates)?
+                .into_iter()
+                .filter_map(|file_id| {
+                    let path = reader.get_path(file_id).ok()?;
+                    let matched = path_match::match_path(&path, keyword)?;
+                    Some(PathHit { path, matched })
+                })
+                .collect();
+            
+            if !hits.is_empty() {
+                hits.sort_by(|a, b| b.matched.score().cmp(&a.matched.score()).then_with(|| a.path.len().cmp(&b.path.len())));
+                hits.truncate(limit);
+                return Ok(hits);
+            }
+        }
+        
+        Ok(Vec::new())
+    }
+    
     /// 拆分为 3-gram
     fn split_to_3grams(&self, text: &str) -> Vec<String> {
         if text.len() < 3 {
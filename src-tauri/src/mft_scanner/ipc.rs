@@ -0,0 +1,558 @@
+// MFT Service 进程间通信
+// UI 进程与（管理员权限运行的）MFT Service 之间通过本地 TCP 交换带长度前缀的 JSON 帧
+// 服务启动时监听 127.0.0.1 随机端口，把端口和本次运行的令牌写入 mft_service.endpoint；
+// 连接失败即视为服务未运行，不再依赖 .ready 标记文件和 PID 校验
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::manifest;
+use super::progress::{format_eta, DriveProgress};
+
+/// 端点文件名（位于索引目录）
+pub const ENDPOINT_FILE: &str = "mft_service.endpoint";
+
+/// 单次查询最多返回的路径数
+pub const MAX_QUERY_RESULTS: usize = 5000;
+
+/// 单帧大小上限
+const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+/// 连接超时（本机连接，服务未运行时应立即失败）
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// 读写超时
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 服务端点（端口 + 令牌）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub port: u16,
+    /// 每次启动随机生成，防止其他本地进程冒充 UI 发送请求
+    pub token: String,
+}
+
+impl Endpoint {
+    pub fn path(output_dir: &str) -> PathBuf {
+        Path::new(output_dir).join(ENDPOINT_FILE)
+    }
+
+    pub fn load(output_dir: &str) -> Result<Self> {
+        let path = Self::path(output_dir);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("MFT Service endpoint not found: {}", path.display()))?;
+        serde_json::from_str(&content).context("Invalid MFT Service endpoint file")
+    }
+
+    /// 原子写入端点文件
+    fn publish(&self, output_dir: &str) -> Result<()> {
+        let dest = Self::path(output_dir);
+        let temp = manifest::temp_path(&dest);
+        std::fs::write(&temp, serde_json::to_vec(self)?)?;
+        manifest::publish_file(&temp, &dest)
+    }
+}
+
+/// 服务退出时移除端点文件
+pub fn remove_endpoint(output_dir: &str) {
+    let _ = std::fs::remove_file(Endpoint::path(output_dir));
+}
+
+/// UI -> Service 请求
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// 查询服务状态和各驱动器进度
+    Status,
+    /// 在服务端索引中查询
+    Query { drive: char, keyword: String, limit: usize },
+    /// 请求服务优雅退出
+    Shutdown,
+}
+
+/// Service -> UI 响应
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Status { status: ServiceStatus },
+    Paths { paths: Vec<String> },
+    ShuttingDown,
+    Error { message: String },
+}
+
+/// 请求帧（附带令牌）
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    token: String,
+    request: Request,
+}
+
+/// 服务运行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServicePhase {
+    Starting,
+    Scanning,
+    Monitoring,
+    ShuttingDown,
+}
+
+/// 驱动器索引状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DriveState {
+    Pending,
+    /// 扫描中（首次汇报进度前为 None）
+    Scanning { progress: Option<DriveProgress> },
+    Ready,
+    Failed { message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriveStatus {
+    pub drive: char,
+    #[serde(flatten)]
+    pub state: DriveState,
+}
+
+/// 服务状态快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub pid: u32,
+    pub phase: ServicePhase,
+    pub drives: Vec<DriveStatus>,
+}
+
+impl ServiceStatus {
+    pub fn new(drives: &[char]) -> Self {
+        Self {
+            pid: std::process::id(),
+            phase: ServicePhase::Starting,
+            drives: drives
+                .iter()
+                .map(|&drive| DriveStatus {
+                    drive,
+                    state: DriveState::Pending,
+                })
+                .collect(),
+        }
+    }
+
+    /// 驱动器索引是否可查询
+    pub fn is_ready(&self, drive: char) -> bool {
+        self.drives
+            .iter()
+            .any(|d| d.drive.eq_ignore_ascii_case(&drive) && d.state == DriveState::Ready)
+    }
+
+    pub fn ready_drives(&self) -> Vec<char> {
+        self.drives
+            .iter()
+            .filter(|d| d.state == DriveState::Ready)
+            .map(|d| d.drive)
+            .collect()
+    }
+
+    /// 扫描中驱动器的进度摘要，如 "C: 42% (~1m 20s left), D: scanning"
+    pub fn progress_message(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .drives
+            .iter()
+            .filter_map(|d| match &d.state {
+                DriveState::Scanning { progress: Some(p) } => Some(match p.eta_secs {
+                    Some(eta) => format!("{}: {:.0}% (~{} left)", d.drive, p.percent, format_eta(eta)),
+                    None => format!("{}: {:.0}%", d.drive, p.percent),
+                }),
+                DriveState::Scanning { progress: None } => Some(format!("{}: scanning", d.drive)),
+                _ => None,
+            })
+            .collect();
+
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// 服务端共享状态（扫描线程更新，IPC 线程读取）
+#[derive(Clone)]
+pub struct StatusHandle(Arc<RwLock<ServiceStatus>>);
+
+impl StatusHandle {
+    pub fn new(drives: &[char]) -> Self {
+        Self(Arc::new(RwLock::new(ServiceStatus::new(drives))))
+    }
+
+    pub fn snapshot(&self) -> ServiceStatus {
+        self.0.read().clone()
+    }
+
+    pub fn set_phase(&self, phase: ServicePhase) {
+        self.0.write().phase = phase;
+    }
+
+    pub fn set_drive(&self, drive: char, state: DriveState) {
+        let mut status = self.0.write();
+        match status.drives.iter_mut().find(|d| d.drive == drive) {
+            Some(entry) => entry.state = state,
+            None => status.drives.push(DriveStatus { drive, state }),
+        }
+    }
+}
+
+/// 写入一帧：4 字节小端长度 + JSON
+pub fn write_frame<W: Write, T: Serialize>(writer: &mut W, message: &T) -> Result<()> {
+    let body = serde_json::to_vec(message)?;
+    if body.len() > MAX_FRAME_BYTES {
+        bail!("Frame too large: {} bytes", body.len());
+    }
+    writer.write_all(&(body.len() as u32).to_le_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// 读取一帧（对端在帧边界关闭连接时返回 None）
+pub fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> Result<Option<T>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_BYTES {
+        bail!("Frame too large: {} bytes", len);
+    }
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// 服务端查询实现
+pub trait QueryBackend: Send + Sync + 'static {
+    fn query(&self, drive: char, keyword: &str, limit: usize) -> Result<Vec<String>>;
+}
+
+/// IPC 服务端
+pub struct IpcServer {
+    token: String,
+    status: StatusHandle,
+    backend: Box<dyn QueryBackend>,
+    running: Arc<AtomicBool>,
+}
+
+impl IpcServer {
+    /// 监听本机随机端口、发布端点文件并启动接收线程
+    pub fn start(
+        output_dir: &str,
+        status: StatusHandle,
+        backend: impl QueryBackend,
+        running: Arc<AtomicBool>,
+    ) -> Result<Endpoint> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).context("Failed to bind IPC listener")?;
+        let endpoint = Endpoint {
+            port: listener.local_addr()?.port(),
+            token: uuid::Uuid::new_v4().simple().to_string(),
+        };
+        endpoint.publish(output_dir)?;
+
+        let server = Arc::new(Self {
+            token: endpoint.token.clone(),
+            status,
+            backend: Box::new(backend),
+            running,
+        });
+        std::thread::Builder::new()
+            .name("mft-ipc".to_string())
+            .spawn(move || server.accept_loop(listener))?;
+
+        info!("✓ MFT IPC listening on 127.0.0.1:{}", endpoint.port);
+        Ok(endpoint)
+    }
+
+    fn accept_loop(self: Arc<Self>, listener: TcpListener) {
+        for stream in listener.incoming() {
+            if !self.running.load(Ordering::SeqCst) {
+                break;
+            }
+            match stream {
+                Ok(stream) => {
+                    let server = self.clone();
+                    std::thread::spawn(move || server.serve(stream));
+                }
+                Err(e) => warn!("⚠️  IPC accept failed: {}", e),
+            }
+        }
+    }
+
+    /// 处理一个连接上的所有请求
+    fn serve(&self, mut stream: TcpStream) {
+        let _ = stream.set_nodelay(true);
+
+        loop {
+            let envelope: Envelope = match read_frame(&mut stream) {
+                Ok(Some(envelope)) => envelope,
+                Ok(None) => break,
+                Err(e) => {
+                    debug!("IPC connection closed: {:#}", e);
+                    break;
+                }
+            };
+
+            // 令牌不符直接断开
+            if envelope.token != self.token {
+                warn!("⚠️  Rejected IPC request with invalid token");
+                break;
+            }
+
+            let response = self.handle(envelope.request);
+            if write_frame(&mut stream, &response).is_err() {
+                break;
+            }
+        }
+    }
+
+    fn handle(&self, request: Request) -> Response {
+        match request {
+            Request::Status => Response::Status {
+                status: self.status.snapshot(),
+            },
+            Request::Query { drive, keyword, limit } => {
+                if !self.status.snapshot().is_ready(drive) {
+                    return Response::Error {
+                        message: format!("Drive {} is not ready", drive),
+                    };
+                }
+                match self.backend.query(drive, &keyword, limit.min(MAX_QUERY_RESULTS)) {
+                    Ok(paths) => Response::Paths { paths },
+                    Err(e) => Response::Error {
+                        message: format!("{:#}", e),
+                    },
+                }
+            }
+            Request::Shutdown => {
+                info!("🛑 Shutdown requested over IPC");
+                self.status.set_phase(ServicePhase::ShuttingDown);
+                self.running.store(false, Ordering::SeqCst);
+                Response::ShuttingDown
+            }
+        }
+    }
+}
+
+/// IPC 客户端（UI 进程使用）
+pub struct IpcClient {
+    stream: TcpStream,
+    token: String,
+}
+
+impl IpcClient {
+    /// 连接索引目录中登记的服务
+    pub fn connect(output_dir: &str) -> Result<Self> {
+        let endpoint = Endpoint::load(output_dir)?;
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, endpoint.port));
+        let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).context("MFT Service is not running")?;
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        let _ = stream.set_nodelay(true);
+
+        Ok(Self {
+            stream,
+            token: endpoint.token,
+        })
+    }
+
+    /// 发送请求并等待响应（Error 响应转换为错误）
+    pub fn request(&mut self, request: Request) -> Result<Response> {
+        let envelope = Envelope {
+            token: self.token.clone(),
+            request,
+        };
+        write_frame(&mut self.stream, &envelope)?;
+
+        match read_frame(&mut self.stream)? {
+            Some(Response::Error { message }) => bail!(message),
+            Some(response) => Ok(response),
+            None => bail!("MFT Service closed the connection"),
+        }
+    }
+
+    pub fn status(&mut self) -> Result<ServiceStatus> {
+        match self.request(Request::Status)? {
+            Response::Status { status } => Ok(status),
+            other => bail!("Unexpected response: {:?}", other),
+        }
+    }
+
+    pub fn query(&mut self, drive: char, keyword: &str, limit: usize) -> Result<Vec<String>> {
+        let request = Request::Query {
+            drive,
+            keyword: keyword.to_string(),
+            limit,
+        };
+        match self.request(request)? {
+            Response::Paths { paths } => Ok(paths),
+            other => bail!("Unexpected response: {:?}", other),
+        }
+    }
+
+    pub fn shutdown(&mut self) -> Result<()> {
+        match self.request(Request::Shutdown)? {
+            Response::ShuttingDown => Ok(()),
+            other => bail!("Unexpected response: {:?}", other),
+        }
+    }
+}
+
+/// 查询服务状态（服务未运行时返回 None）
+pub fn fetch_status(output_dir: &str) -> Option<ServiceStatus> {
+    IpcClient::connect(output_dir).and_then(|mut client| client.status()).ok()
+}
+
+/// 基于 FST 索引的服务端查询（按驱动器缓存已打开的索引）
+#[cfg(target_os = "windows")]
+pub struct IndexQueryBackend {
+    output_dir: String,
+    indexes: parking_lot::Mutex<std::collections::HashMap<char, (super::IndexQuery, super::PathReader)>>,
+}
+
+#[cfg(target_os = "windows")]
+impl IndexQueryBackend {
+    pub fn new(output_dir: &str) -> Self {
+        Self {
+            output_dir: output_dir.to_string(),
+            indexes: parking_lot::Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl QueryBackend for IndexQueryBackend {
+    fn query(&self, drive: char, keyword: &str, limit: usize) -> Result<Vec<String>> {
+        use super::{IndexQuery, PathReader};
+
+        let mut indexes = self.indexes.lock();
+
+        // 首次查询或索引版本变化（合并后）时重新打开
+        let stale = indexes.get(&drive).map_or(true, |(query, _)| query.needs_reload());
+        if stale {
+            let query = IndexQuery::open(drive, &self.output_dir)?;
+            let reader = PathReader::open(drive, &self.output_dir)?;
+            indexes.insert(drive, (query, reader));
+        }
+
+        let (query, reader) = &indexes[&drive];
+        let hits = query.search_paths(reader, keyword, limit)?;
+        Ok(hits.into_iter().map(|hit| hit.path).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    struct FakeBackend;
+
+    impl QueryBackend for FakeBackend {
+        fn query(&self, drive: char, keyword: &str, limit: usize) -> Result<Vec<String>> {
+            Ok((0..limit.min(3)).map(|i| format!("{}:\\{}{}.txt", drive, keyword, i)).collect())
+        }
+    }
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, &Request::Status).unwrap();
+        write_frame(&mut buffer, &Request::Shutdown).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(read_frame::<_, Request>(&mut reader).unwrap(), Some(Request::Status));
+        assert_eq!(read_frame::<_, Request>(&mut reader).unwrap(), Some(Request::Shutdown));
+        assert_eq!(read_frame::<_, Request>(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_oversized_frame_rejected() {
+        let mut buffer = ((MAX_FRAME_BYTES + 1) as u32).to_le_bytes().to_vec();
+        buffer.extend_from_slice(b"{}");
+        assert!(read_frame::<_, Request>(&mut Cursor::new(buffer)).is_err());
+    }
+
+    #[test]
+    fn test_status_handle() {
+        let status = StatusHandle::new(&['C', 'D']);
+        status.set_drive('C', DriveState::Ready);
+        status.set_drive('E', DriveState::Scanning { progress: None });
+
+        let snapshot = status.snapshot();
+        assert!(snapshot.is_ready('C'));
+        assert!(snapshot.is_ready('c'));
+        assert!(!snapshot.is_ready('D'));
+        assert_eq!(snapshot.ready_drives(), vec!['C']);
+        assert_eq!(snapshot.drives.len(), 3);
+    }
+
+    #[test]
+    fn test_progress_message() {
+        use super::super::progress::ScanStage;
+
+        let status = StatusHandle::new(&['C', 'D', 'E']);
+        assert_eq!(status.snapshot().progress_message(), None);
+
+        let progress = DriveProgress::new(ScanStage::Enumerating, 60, 100, Duration::from_secs(42));
+        status.set_drive('C', DriveState::Scanning { progress: Some(progress) });
+        status.set_drive('D', DriveState::Scanning { progress: None });
+        status.set_drive('E', DriveState::Ready);
+        assert_eq!(
+            status.snapshot().progress_message().as_deref(),
+            Some("C: 42% (~58s left), D: scanning")
+        );
+    }
+
+    #[test]
+    fn test_client_server() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_ipc_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let output_dir = dir.to_string_lossy().to_string();
+
+        assert!(fetch_status(&output_dir).is_none());
+
+        let status = StatusHandle::new(&['C', 'D']);
+        status.set_drive('C', DriveState::Ready);
+        let running = Arc::new(AtomicBool::new(true));
+        IpcServer::start(&output_dir, status.clone(), FakeBackend, running.clone()).unwrap();
+
+        let mut client = IpcClient::connect(&output_dir).unwrap();
+        assert!(client.status().unwrap().is_ready('C'));
+        assert_eq!(client.query('C', "a", 2).unwrap(), vec!["C:\\a0.txt", "C:\\a1.txt"]);
+        // 未就绪的驱动器返回错误，连接仍可继续使用
+        assert!(client.query('D', "a", 2).is_err());
+        client.shutdown().unwrap();
+        assert!(!running.load(Ordering::SeqCst));
+        assert_eq!(status.snapshot().phase, ServicePhase::ShuttingDown);
+
+        // 令牌错误的请求被断开
+        let endpoint = Endpoint::load(&output_dir).unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", endpoint.port)).unwrap();
+        let envelope = Envelope {
+            token: "wrong".to_string(),
+            request: Request::Status,
+        };
+        write_frame(&mut stream, &envelope).unwrap();
+        assert!(read_frame::<_, Response>(&mut stream).map_or(true, |r| r.is_none()));
+
+        remove_endpoint(&output_dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
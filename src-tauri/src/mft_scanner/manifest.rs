@@ -0,0 +1,297 @@
+// 索引发布清单 - 保证读取方看不到写了一半的索引
+// 数据文件先写入 .new 临时文件并 fsync，再原子 rename；全部就位后写入 {盘符}_index.manifest（同样先写临时文件再 rename）
+// 清单记录每个文件的大小和 SHA-256，是索引“已发布”的唯一标志，IndexQuery 打开前校验
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// 清单格式版本
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// 清单覆盖的索引文件（后缀）
+pub const FST_FILE: &str = "_index.fst";
+pub const BITMAPS_FILE: &str = "_bitmaps.dat";
+pub const PATHS_FILE: &str = "_paths.dat";
+pub const OFFSETS_FILE: &str = "_offsets.dat";
+
+/// 墓碑记录（已删除条目的 file_id，不在清单中，随增量更新改写）
+pub const TOMBSTONES_FILE: &str = "_tombstones.dat";
+
+const PUBLISHED_FILES: [&str; 4] = [PATHS_FILE, OFFSETS_FILE, FST_FILE, BITMAPS_FILE];
+
+/// 只追加的文件（USN 增量更新会在末尾追加路径），只校验发布时的前缀
+const APPEND_ONLY_FILES: [&str; 1] = [PATHS_FILE];
+
+/// 清单中的单个文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 文件后缀（如 _index.fst）
+    pub name: String,
+    /// 发布时的文件大小
+    pub size: u64,
+    /// 前 size 字节的 SHA-256（十六进制小写）
+    pub sha256: String,
+    /// 允许在发布后追加内容
+    #[serde(default)]
+    pub append_only: bool,
+}
+
+/// 索引发布清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexManifest {
+    pub version: u32,
+    pub drive: char,
+    /// 发布时间（Unix 时间戳，秒）
+    pub published_at: i64,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl IndexManifest {
+    /// 清单文件路径
+    pub fn path(drive: char, output_dir: &str) -> PathBuf {
+        Path::new(output_dir).join(format!("{}_index.manifest", drive))
+    }
+
+    /// 为已就位的索引文件计算校验和
+    pub fn build(drive: char, output_dir: &str) -> Result<Self> {
+        let mut entries = Vec::with_capacity(PUBLISHED_FILES.len());
+        for name in PUBLISHED_FILES {
+            entries.push(hash_entry(drive, output_dir, name)?);
+        }
+
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            drive,
+            published_at: chrono::Utc::now().timestamp(),
+            entries,
+        })
+    }
+
+    /// 加载清单（不存在表示索引尚未发布）
+    pub fn load(drive: char, output_dir: &str) -> Result<Self> {
+        let path = Self::path(drive, output_dir);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Index manifest not found: {}", path.display()))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid index manifest: {}", path.display()))?;
+
+        if manifest.version != MANIFEST_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported index manifest version {} (expected {})",
+                manifest.version,
+                MANIFEST_VERSION
+            ));
+        }
+        if !manifest.drive.eq_ignore_ascii_case(&drive) {
+            return Err(anyhow::anyhow!("Index manifest belongs to drive {}, not {}", manifest.drive, drive));
+        }
+        Ok(manifest)
+    }
+
+    /// 原子发布清单（发布后索引才对读取方可见）
+    pub fn publish(&self, output_dir: &str) -> Result<()> {
+        let content = serde_json::to_vec_pretty(self)?;
+        write_atomic(&Self::path(self.drive, output_dir), &content)?;
+        tracing::info!("✓ Published index manifest for drive {}", self.drive);
+        Ok(())
+    }
+
+    /// 撤回清单（重新构建前调用，构建完成前读取方拒绝加载）
+    pub fn retract(drive: char, output_dir: &str) -> Result<()> {
+        match std::fs::remove_file(Self::path(drive, output_dir)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 重新计算指定文件的校验和（合并等只替换部分文件的场景）
+    pub fn refresh(&mut self, output_dir: &str, names: &[&str]) -> Result<()> {
+        for name in names {
+            let entry = hash_entry(self.drive, output_dir, name)?;
+            match self.entries.iter_mut().find(|e| e.name == *name) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+        self.published_at = chrono::Utc::now().timestamp();
+        Ok(())
+    }
+
+    /// 校验已读入（或已映射）的文件内容
+    pub fn verify_bytes(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| anyhow::anyhow!("{}{} is not listed in the index manifest", self.drive, name))?;
+
+        let len = bytes.len() as u64;
+        let size_ok = if entry.append_only { len >= entry.size } else { len == entry.size };
+        if !size_ok {
+            return Err(anyhow::anyhow!(
+                "{}{} size mismatch: expected {} bytes, found {}",
+                self.drive,
+                name,
+                entry.size,
+                len
+            ));
+        }
+
+        let actual = sha256_hex(&bytes[..entry.size as usize]);
+        if actual != entry.sha256 {
+            return Err(anyhow::anyhow!("{}{} checksum mismatch", self.drive, name));
+        }
+        Ok(())
+    }
+
+    /// 校验清单中的所有文件
+    pub fn verify_all(&self, output_dir: &str) -> Result<()> {
+        for entry in &self.entries {
+            let (size, sha256) = hash_prefix(&file_path(self.drive, output_dir, &entry.name), entry.size)?;
+            if size < entry.size || (!entry.append_only && size != entry.size) {
+                return Err(anyhow::anyhow!("{}{} size mismatch", self.drive, entry.name));
+            }
+            if sha256 != entry.sha256 {
+                return Err(anyhow::anyhow!("{}{} checksum mismatch", self.drive, entry.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 索引数据文件路径
+pub fn file_path(drive: char, output_dir: &str, name: &str) -> PathBuf {
+    Path::new(output_dir).join(format!("{}{}", drive, name))
+}
+
+/// 写入中的临时文件路径（<dest>.new）
+pub fn temp_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".new");
+    dest.with_file_name(name)
+}
+
+/// 将写完的临时文件落盘并原子替换目标文件
+pub fn publish_file(temp: &Path, dest: &Path) -> Result<()> {
+    File::open(temp)
+        .and_then(|file| file.sync_all())
+        .with_context(|| format!("Failed to sync {}", temp.display()))?;
+    std::fs::rename(temp, dest)
+        .with_context(|| format!("Failed to publish {} -> {}", temp.display(), dest.display()))?;
+    Ok(())
+}
+
+/// 原子写入小文件
+pub fn write_atomic(dest: &Path, content: &[u8]) -> Result<()> {
+    let temp = temp_path(dest);
+    {
+        let mut file = File::create(&temp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&temp, dest)?;
+    Ok(())
+}
+
+fn hash_entry(drive: char, output_dir: &str, name: &str) -> Result<ManifestEntry> {
+    let path = file_path(drive, output_dir, name);
+    let (size, sha256) = hash_prefix(&path, u64::MAX)?;
+    Ok(ManifestEntry {
+        name: name.to_string(),
+        size,
+        sha256,
+        append_only: APPEND_ONLY_FILES.contains(&name),
+    })
+}
+
+/// 计算文件前 limit 字节的 SHA-256，返回 (文件总大小, 校验和)
+fn hash_prefix(path: &Path, limit: u64) -> Result<(u64, String)> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let total = file.metadata()?.len();
+
+    let mut reader = file.take(limit);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok((total, hex(&hasher.finalize())))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_index() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ilauncher_manifest_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in [
+            (PATHS_FILE, b"paths".as_slice()),
+            (OFFSETS_FILE, b"offsets".as_slice()),
+            (FST_FILE, b"fst".as_slice()),
+            (BITMAPS_FILE, b"bitmaps".as_slice()),
+        ] {
+            std::fs::write(dir.join(format!("C{}", name)), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_publish_and_verify() {
+        let dir = write_index();
+        let output_dir = dir.to_str().unwrap();
+
+        assert!(IndexManifest::load('C', output_dir).is_err());
+        IndexManifest::build('C', output_dir).unwrap().publish(output_dir).unwrap();
+        assert!(!temp_path(&IndexManifest::path('C', output_dir)).exists());
+
+        let manifest = IndexManifest::load('C', output_dir).unwrap();
+        manifest.verify_all(output_dir).unwrap();
+        manifest.verify_bytes(FST_FILE, b"fst").unwrap();
+
+        // 半写入/损坏的文件
+        assert!(manifest.verify_bytes(FST_FILE, b"fs").is_err());
+        assert!(manifest.verify_bytes(BITMAPS_FILE, b"bitmapz").is_err());
+
+        // 路径文件允许追加
+        manifest.verify_bytes(PATHS_FILE, b"paths+new").unwrap();
+        assert!(manifest.verify_bytes(FST_FILE, b"fst+").is_err());
+
+        IndexManifest::retract('C', output_dir).unwrap();
+        assert!(IndexManifest::load('C', output_dir).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_refresh_replaced_files() {
+        let dir = write_index();
+        let output_dir = dir.to_str().unwrap();
+
+        let mut manifest = IndexManifest::build('C', output_dir).unwrap();
+        std::fs::write(dir.join("C_index.fst"), b"merged fst").unwrap();
+        assert!(manifest.verify_all(output_dir).is_err());
+
+        manifest.refresh(output_dir, &[FST_FILE]).unwrap();
+        manifest.verify_all(output_dir).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
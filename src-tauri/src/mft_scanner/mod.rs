@@ -26,6 +26,28 @@ pub mod delta_merger;
 #[cfg(target_os = "windows")]
 pub mod query_cache;
 
+// 扫描限流（优先级、后台 I/O、暂停条件）
+#[cfg(target_os = "windows")]
+pub mod throttle;
+
+// 索引磁盘占用统计与清理
+pub mod storage_report;
+
+// 索引发布清单（临时文件 + 原子 rename + 校验和）
+pub mod manifest;
+
+// UI 与 MFT Service 之间的 IPC（状态、查询、关闭）
+pub mod ipc;
+
+// 扫描进度（百分比、剩余时间）
+pub mod progress;
+
+// 完整路径匹配与匹配分类（MFT 查询结果打分）
+pub mod path_match;
+
+// 墓碑记录与索引压缩（清理已删除条目）
+pub mod compaction;
+
 // 重新导出核心类型
 #[cfg(target_os = "windows")]
 pub use types::{MftFileEntry, ScanConfig, FrnMap, ParentInfo};
@@ -40,6 +62,8 @@ pub use streaming_builder::StreamingBuilder;
 #[cfg(target_os = "windows")]
 pub use index_builder::{IndexBuilder, IndexQuery, PathReader};
 
+pub use manifest::IndexManifest;
+
 #[cfg(target_os = "windows")]
 pub use multi_drive_scanner::{MultiDriveScanner, DiskType};
 
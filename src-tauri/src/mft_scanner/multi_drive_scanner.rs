@@ -10,10 +10,15 @@ use tracing::{info, warn};
 
 use super::streaming_builder::StreamingBuilder;
 use super::index_builder::IndexBuilder;
+use super::ipc::{DriveState, StatusHandle};
+use super::manifest::IndexManifest;
+use super::progress::{ProgressReporter, ScanStage};
+use super::throttle::ScanThrottle;
 use super::types::ScanConfig;
+use crate::utils::index_exclusions::IndexExclusions;
 
 // 🔥 当前数据格式版本（变更后需要重建）
-const DATA_FORMAT_VERSION: u32 = 2;  // v1: SQLite, v2: FST+RoaringBitmap
+const DATA_FORMAT_VERSION: u32 = 3;  // v1: SQLite, v2: FST+RoaringBitmap, v3: 带校验清单的原子发布
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DiskType {
@@ -27,6 +32,9 @@ pub struct MultiDriveScanner {
     drives: Vec<char>,
     output_dir: String,
     disk_types: HashMap<char, DiskType>,
+    throttle: Arc<ScanThrottle>,
+    status: Option<StatusHandle>, // IPC 状态（各驱动器扫描进度）
+    exclusions: Arc<IndexExclusions>, // 用户排除规则
 }
 
 impl MultiDriveScanner {
@@ -35,6 +43,9 @@ impl MultiDriveScanner {
             drives: config.drives.clone(),
             output_dir: config.output_dir.clone(),
             disk_types: HashMap::new(),
+            throttle: Arc::new(ScanThrottle::new(config.throttle.clone())),
+            status: None,
+            exclusions: Arc::new(IndexExclusions::new(Vec::new())),
         };
         
         // 检测每个盘符的磁盘类型
@@ -47,6 +58,18 @@ impl MultiDriveScanner {
         scanner
     }
     
+    /// 扫描过程中向 IPC 状态汇报每个驱动器的进度
+    pub fn with_status(mut self, status: StatusHandle) -> Self {
+        self.status = Some(status);
+        self
+    }
+    
+    /// 扫描时应用的排除规则
+    pub fn with_exclusions(mut self, exclusions: Arc<IndexExclusions>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+    
     /// 检测磁盘类型（SSD/HDD）
     #[cfg(target_os = "windows")]
     fn detect_disk_type(drive: char) -> DiskType {
@@ -162,6 +185,9 @@ impl MultiDriveScanner {
         // 🔥 检查数据格式版本，如有变更则清理旧数据
         self.check_and_cleanup_old_data()?;
         
+        // 🔥 降低进程优先级，避免全量扫描占满 CPU
+        self.throttle.apply_process_settings();
+        
         let total_start = Instant::now();
         
         // 分组：SSD 并行，HDD 串行
@@ -183,7 +209,7 @@ impl MultiDriveScanner {
             let ssd_results: Vec<_> = ssd_drives
                 .par_iter()
                 .map(|&&drive| {
-                    self.scan_single_drive(drive)
+                    self.scan_and_report(drive)
                 })
                 .collect();
             
@@ -199,7 +225,7 @@ impl MultiDriveScanner {
             info!("💿 Phase 2: Scanning HDD drives serially...");
             
             for &&drive in &hdd_drives {
-                if let Ok(_) = self.scan_single_drive(drive) {
+                if let Ok(_) = self.scan_and_report(drive) {
                     scanned_drives.lock().unwrap().push(drive);
                 }
             }
@@ -260,8 +286,8 @@ impl MultiDriveScanner {
                     }
                     
                     if let Some(ext) = path.extension() {
-                        // 删除 .dat, .fst, .db, .tmp 文件
-                        if ext == "dat" || ext == "fst" || ext == "db" || ext == "tmp" {
+                        // 删除 .dat, .fst, .db, .tmp, .new, .manifest 文件
+                        if ext == "dat" || ext == "fst" || ext == "db" || ext == "tmp" || ext == "new" || ext == "manifest" {
                             if let Some(name) = path.file_name() {
                                 info!("   ❌ Removing old file: {:?}", name);
                                 fs::remove_file(&path).ok();
@@ -280,14 +306,45 @@ impl MultiDriveScanner {
         Ok(())
     }
     
+    /// 扫描单个驱动器并更新 IPC 状态（完成即可查询，不必等待其他驱动器）
+    fn scan_and_report(&self, drive: char) -> Result<()> {
+        if let Some(status) = &self.status {
+            status.set_drive(drive, DriveState::Scanning { progress: None });
+        }
+        
+        let result = self.scan_single_drive(drive);
+        
+        if let Some(status) = &self.status {
+            let state = match &result {
+                Ok(()) => DriveState::Ready,
+                Err(e) => DriveState::Failed { message: format!("{:#}", e) },
+            };
+            status.set_drive(drive, state);
+        }
+        result
+    }
+    
     /// 扫描单个驱动器
     fn scan_single_drive(&self, drive: char) -> Result<()> {
         let drive_start = Instant::now();
         
         info!("🚀 Scanning drive {}:", drive);
         
+        // 扫描线程使用后台 I/O 优先级（离开作用域时恢复）
+        let _background_io = self.throttle.background_io_guard();
+        
+        // 撤回旧清单：重建完成前读取方不会加载本驱动器的索引
+        IndexManifest::retract(drive, &self.output_dir)?;
+        
+        let mut progress = self.status.clone().map(|status| ProgressReporter::new(status, drive));
+        
         // 🔥 步骤 1: 流式构建（MFT -> 路径文件）
-        let mut builder = StreamingBuilder::new(drive, &self.output_dir)?;
+        let mut builder = StreamingBuilder::new(drive, &self.output_dir)?
+            .with_throttle(self.throttle.clone())
+            .with_exclusions(self.exclusions.clone());
+        if let Some(progress) = &progress {
+            builder = builder.with_progress(progress.clone());
+        }
         builder.scan_mft_streaming()?;
         builder.finalize(&self.output_dir)?;
         
@@ -296,10 +353,16 @@ impl MultiDriveScanner {
         
         // 🔥 步骤 2: 构建 3-gram 索引
         let index_start = Instant::now();
+        if let Some(progress) = &mut progress {
+            progress.begin_stage(ScanStage::BuildingIndex, 0);
+        }
         let mut index_builder = IndexBuilder::new(drive);
         index_builder.build_from_paths(&self.output_dir)?;
         index_builder.save_index(&self.output_dir)?;
         
+        // 🔥 步骤 3: 所有文件就位后发布清单（索引从此刻起对读取方可见）
+        IndexManifest::build(drive, &self.output_dir)?.publish(&self.output_dir)?;
+        
         let index_elapsed = index_start.elapsed();
         info!("   ✓ Index build: {:.2}s", index_elapsed.as_secs_f32());
         
@@ -0,0 +1,229 @@
+// 路径匹配 - 对 MFT 索引候选结果做完整路径匹配和分类
+// 3-gram 索引只覆盖文件名：查询中最长的词作为锚点在索引中检索候选，
+// 其余词（包括 "src\main" 这类带分隔符的词）在完整路径上校验，
+// 并返回匹配类型与命中位置，供排序层按匹配质量打分
+
+use crate::core::highlight::{self, HighlightSpan};
+use serde::{Deserialize, Serialize};
+
+/// 匹配类型（由好到差）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// 文件名（或去掉扩展名后）与查询完全相同
+    Exact,
+    /// 文件名以查询开头
+    Prefix,
+    /// 所有词都以子串形式出现在路径中
+    Substring,
+    /// 部分词只能按字符顺序（子序列）匹配文件名
+    Fuzzy,
+}
+
+/// 单条路径的匹配结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathMatch {
+    pub kind: MatchKind,
+    /// 是否所有词都命中文件名（否则至少一个词只命中目录部分）
+    pub name_only: bool,
+    /// 路径深度（目录层数）
+    pub depth: usize,
+    /// 文件名中的命中区间（字符下标）
+    pub name_spans: Vec<HighlightSpan>,
+    /// 完整路径中的命中区间（字符下标）
+    pub path_spans: Vec<HighlightSpan>,
+}
+
+impl PathMatch {
+    /// 结果分数（1-100）：按匹配类型给基础分，命中目录、路径较深时递减
+    pub fn score(&self) -> i32 {
+        let base = match self.kind {
+            MatchKind::Exact => 100,
+            MatchKind::Prefix => 90,
+            MatchKind::Substring => 75,
+            MatchKind::Fuzzy => 55,
+        };
+        let dir_penalty = if self.name_only { 0 } else { 10 };
+        (base - dir_penalty - self.depth.min(10) as i32).max(1)
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == '\\' || c == '/'
+}
+
+/// 统一分隔符为 '\' 并按字符小写化（保持字符下标与原路径一致）
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c == '/' { '\\' } else { c.to_lowercase().next().unwrap_or(c) })
+        .collect()
+}
+
+/// 索引检索锚点：查询中的词（按分隔符和空格拆分）按长度降序，
+/// 锚点越长 3-gram 越多、候选越少
+pub fn anchor_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| c.is_whitespace() || is_separator(c))
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()));
+    terms
+}
+
+/// 子序列匹配，返回命中字符下标
+fn subsequence_indices(text: &[char], term: &[char]) -> Option<Vec<usize>> {
+    let mut indices = Vec::with_capacity(term.len());
+    let mut chars = text.iter().enumerate();
+    for &wanted in term {
+        let (index, _) = chars.find(|(_, &c)| c == wanted)?;
+        indices.push(index);
+    }
+    Some(indices)
+}
+
+/// 在完整路径上匹配查询：空格分隔的每个词都必须命中
+/// 不含分隔符的词优先匹配文件名，其次目录，最后按子序列匹配文件名；
+/// 含分隔符的词（如 "src\main"）按子串匹配完整路径
+pub fn match_path(path: &str, query: &str) -> Option<PathMatch> {
+    let terms: Vec<String> = query.split_whitespace().map(normalize).collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let path_norm = normalize(path);
+    let trimmed = path_norm.trim_end_matches('\\');
+    let name = trimmed.rsplit('\\').next().unwrap_or(trimmed);
+    let name_start = trimmed.chars().count() - name.chars().count();
+    let name_chars: Vec<char> = name.chars().collect();
+    let depth = trimmed.matches('\\').count();
+
+    let mut spans = Vec::new();
+    let mut name_only = true;
+    let mut fuzzy = false;
+
+    for term in &terms {
+        let term = term.as_str();
+        if !term.contains('\\') && name.contains(term) {
+            spans.extend(
+                highlight::substring_spans(name, term)
+                    .into_iter()
+                    .map(|s| HighlightSpan { start: s.start + name_start, end: s.end + name_start }),
+            );
+        } else if path_norm.contains(term) {
+            spans.extend(highlight::substring_spans(&path_norm, term));
+            name_only = false;
+        } else {
+            let term_chars: Vec<char> = term.chars().collect();
+            let indices = subsequence_indices(&name_chars, &term_chars)?;
+            spans.extend(highlight::spans_from_indices(
+                &indices.iter().map(|i| i + name_start).collect::<Vec<_>>(),
+            ));
+            fuzzy = true;
+        }
+    }
+
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let kind = if fuzzy {
+        MatchKind::Fuzzy
+    } else if terms.len() == 1 && (name == terms[0] || stem == terms[0]) {
+        MatchKind::Exact
+    } else if name_only && name.starts_with(terms[0].as_str()) {
+        MatchKind::Prefix
+    } else {
+        MatchKind::Substring
+    };
+
+    let path_spans = highlight::merge_spans(spans);
+    let name_spans = path_spans
+        .iter()
+        .filter(|s| s.end > name_start && s.start < name_start + name_chars.len())
+        .map(|s| HighlightSpan {
+            start: s.start.max(name_start) - name_start,
+            end: s.end.min(name_start + name_chars.len()) - name_start,
+        })
+        .collect();
+
+    Some(PathMatch {
+        kind,
+        name_only,
+        depth,
+        name_spans,
+        path_spans,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize) -> HighlightSpan {
+        HighlightSpan { start, end }
+    }
+
+    #[test]
+    fn test_anchor_terms() {
+        assert_eq!(anchor_terms("src\\main.rs"), vec!["main.rs", "src"]);
+        assert_eq!(anchor_terms("  Report  Q3 "), vec!["report", "q3"]);
+        assert!(anchor_terms(" \\ / ").is_empty());
+    }
+
+    #[test]
+    fn test_match_kinds() {
+        let path = "C:\\Projects\\iLauncher\\Cargo.toml";
+        assert_eq!(match_path(path, "cargo.toml").unwrap().kind, MatchKind::Exact);
+        assert_eq!(match_path(path, "Cargo").unwrap().kind, MatchKind::Exact);
+        assert_eq!(match_path(path, "car").unwrap().kind, MatchKind::Prefix);
+        assert_eq!(match_path(path, "toml").unwrap().kind, MatchKind::Substring);
+        assert_eq!(match_path(path, "ctml").unwrap().kind, MatchKind::Fuzzy);
+        assert!(match_path(path, "readme").is_none());
+        assert!(match_path(path, "   ").is_none());
+    }
+
+    #[test]
+    fn test_directory_components() {
+        let path = "C:\\Projects\\iLauncher\\src\\main.rs";
+
+        // 目录词 + 文件名词
+        let m = match_path(path, "ilauncher main").unwrap();
+        assert_eq!(m.kind, MatchKind::Substring);
+        assert!(!m.name_only);
+        assert_eq!(m.name_spans, vec![span(0, 4)]);
+        assert_eq!(m.path_spans, vec![span(12, 21), span(26, 30)]);
+
+        // 带分隔符的词匹配连续的目录层级，'/' 与 '\' 等价
+        let m = match_path(path, "launcher/src\\main").unwrap();
+        assert_eq!(m.path_spans, vec![span(13, 30)]);
+        assert_eq!(m.name_spans, vec![span(0, 4)]);
+        assert!(match_path(path, "src\\ilauncher").is_none());
+
+        // 命中文件名优先于只命中目录
+        let in_name = match_path(path, "main").unwrap();
+        let in_dir = match_path("C:\\main\\src\\lib.rs", "main").unwrap();
+        assert!(in_name.score() > in_dir.score());
+    }
+
+    #[test]
+    fn test_score_order() {
+        let exact = match_path("C:\\a\\notes.txt", "notes").unwrap();
+        let prefix = match_path("C:\\a\\notes-old.txt", "notes").unwrap();
+        let substring = match_path("C:\\a\\my-notes.txt", "notes").unwrap();
+        let fuzzy = match_path("C:\\a\\n_o_t_e_s.txt", "notes").unwrap();
+        assert!(exact.score() > prefix.score());
+        assert!(prefix.score() > substring.score());
+        assert!(substring.score() > fuzzy.score());
+
+        // 同类型时浅路径优先
+        let deep = match_path("C:\\a\\b\\c\\d\\notes.txt", "notes").unwrap();
+        assert!(exact.score() > deep.score());
+    }
+
+    #[test]
+    fn test_unicode_positions() {
+        let m = match_path("D:\\文档\\项目计划.docx", "计划").unwrap();
+        assert_eq!(m.name_spans, vec![span(2, 4)]);
+        assert_eq!(m.path_spans, vec![span(8, 10)]);
+    }
+}
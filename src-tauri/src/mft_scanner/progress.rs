@@ -0,0 +1,233 @@
+// MFT 扫描进度 - 扫描线程按阶段汇报已处理记录数，换算为整体百分比和剩余时间
+// 进度写入 IPC 状态，UI 通过 Status 请求读取（get_mft_status / mft-progress 事件）
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use super::ipc::{DriveState, StatusHandle};
+
+/// 进度更新的最小间隔
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 扫描阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanStage {
+    /// 枚举 MFT 记录（构建 FRN 映射）
+    Enumerating,
+    /// 重建路径并写入磁盘
+    WritingPaths,
+    /// 构建 3-gram 索引
+    BuildingIndex,
+}
+
+impl ScanStage {
+    /// 阶段在整体进度中占据的区间（百分比）
+    fn range(self) -> (f32, f32) {
+        match self {
+            ScanStage::Enumerating => (0.0, 70.0),
+            ScanStage::WritingPaths => (70.0, 90.0),
+            ScanStage::BuildingIndex => (90.0, 100.0),
+        }
+    }
+}
+
+impl ScanStage {
+    /// 阶段名称（显示在搜索结果中）
+    pub fn label(self) -> &'static str {
+        match self {
+            ScanStage::Enumerating => "Reading MFT records",
+            ScanStage::WritingPaths => "Writing paths",
+            ScanStage::BuildingIndex => "Building index",
+        }
+    }
+}
+
+/// 单个驱动器的扫描进度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriveProgress {
+    pub stage: ScanStage,
+    /// 当前阶段已处理的记录数
+    pub records_scanned: u64,
+    /// 当前阶段的记录总数（未知时为 0）
+    pub total_records: u64,
+    /// 整体进度（0-100）
+    pub percent: f32,
+    /// 预计剩余秒数（进度太少时无法估算）
+    pub eta_secs: Option<u64>,
+}
+
+impl DriveProgress {
+    /// 由阶段内进度和已用时间计算整体百分比与剩余时间
+    pub fn new(stage: ScanStage, records_scanned: u64, total_records: u64, elapsed: Duration) -> Self {
+        let fraction = if total_records > 0 {
+            (records_scanned as f32 / total_records as f32).min(1.0)
+        } else {
+            0.0
+        };
+        let (start, end) = stage.range();
+        let percent = start + (end - start) * fraction;
+
+        // 按目前的平均速度线性外推
+        let eta_secs = (percent >= 1.0)
+            .then(|| (elapsed.as_secs_f32() * (100.0 - percent) / percent).round() as u64);
+
+        Self {
+            stage,
+            records_scanned,
+            total_records,
+            percent,
+            eta_secs,
+        }
+    }
+}
+
+impl DriveProgress {
+    /// 进度详情，如 "Reading MFT records · 120,000 / 300,000 · ~1m 20s left"
+    pub fn detail(&self) -> String {
+        let mut parts = vec![self.stage.label().to_string()];
+        if self.total_records > 0 {
+            parts.push(format!(
+                "{} / {}",
+                format_count(self.records_scanned),
+                format_count(self.total_records)
+            ));
+        } else if self.records_scanned > 0 {
+            parts.push(format_count(self.records_scanned));
+        }
+        parts.push(match self.eta_secs {
+            Some(eta) => format!("~{} left", format_eta(eta)),
+            None => "estimating time left".to_string(),
+        });
+        parts.join(" · ")
+    }
+}
+
+/// 扫描线程使用的进度汇报器（限制更新频率）
+#[derive(Clone)]
+pub struct ProgressReporter {
+    status: StatusHandle,
+    drive: char,
+    started: Instant,
+    last_report: Option<Instant>,
+}
+
+impl ProgressReporter {
+    pub fn new(status: StatusHandle, drive: char) -> Self {
+        Self {
+            status,
+            drive,
+            started: Instant::now(),
+            last_report: None,
+        }
+    }
+
+    /// 汇报阶段内进度（距上次汇报不足间隔时忽略）
+    pub fn report(&mut self, stage: ScanStage, records_scanned: u64, total_records: u64) {
+        let now = Instant::now();
+        if self.last_report.is_some_and(|last| now.duration_since(last) < REPORT_INTERVAL) {
+            return;
+        }
+        self.last_report = Some(now);
+
+        let progress = DriveProgress::new(stage, records_scanned, total_records, self.started.elapsed());
+        self.status.set_drive(
+            self.drive,
+            DriveState::Scanning {
+                progress: Some(progress),
+            },
+        );
+    }
+
+    /// 进入新阶段（立即汇报）
+    pub fn begin_stage(&mut self, stage: ScanStage, total_records: u64) {
+        self.last_report = None;
+        self.report(stage, 0, total_records);
+    }
+}
+
+/// 剩余时间的简短描述（1m 20s）
+pub fn format_eta(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// 千分位分隔的计数（1234567 → 1,234,567）
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(ch);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drive_progress() {
+        let progress = DriveProgress::new(ScanStage::Enumerating, 50, 100, Duration::from_secs(35));
+        assert_eq!(progress.percent, 35.0);
+        assert_eq!(progress.eta_secs, Some(65));
+
+        let writing = DriveProgress::new(ScanStage::WritingPaths, 200, 100, Duration::from_secs(90));
+        assert_eq!(writing.percent, 90.0);
+        assert_eq!(writing.eta_secs, Some(10));
+
+        // 总数未知时停留在阶段起点
+        let unknown = DriveProgress::new(ScanStage::Enumerating, 1000, 0, Duration::from_secs(5));
+        assert_eq!(unknown.percent, 0.0);
+        assert_eq!(unknown.eta_secs, None);
+    }
+
+    #[test]
+    fn test_reporter_updates_status() {
+        let status = StatusHandle::new(&['C']);
+        let mut reporter = ProgressReporter::new(status.clone(), 'C');
+        reporter.begin_stage(ScanStage::WritingPaths, 10);
+        // 间隔内的更新被忽略
+        reporter.report(ScanStage::WritingPaths, 5, 10);
+
+        let snapshot = status.snapshot();
+        let DriveState::Scanning { progress: Some(progress) } = &snapshot.drives[0].state else {
+            panic!("drive should be scanning");
+        };
+        assert_eq!(progress.stage, ScanStage::WritingPaths);
+        assert_eq!(progress.records_scanned, 0);
+    }
+
+    #[test]
+    fn test_progress_detail() {
+        let progress = DriveProgress::new(ScanStage::Enumerating, 120_000, 300_000, Duration::from_secs(28));
+        assert_eq!(progress.detail(), "Reading MFT records · 120,000 / 300,000 · ~1m 12s left");
+
+        let unknown = DriveProgress::new(ScanStage::BuildingIndex, 0, 0, Duration::from_secs(1));
+        assert_eq!(unknown.detail(), "Building index · ~0s left");
+
+        let starting = DriveProgress::new(ScanStage::Enumerating, 512, 0, Duration::ZERO);
+        assert_eq!(starting.detail(), "Reading MFT records · 512 · estimating time left");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1000), "1,000");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(42), "42s");
+        assert_eq!(format_eta(80), "1m 20s");
+        assert_eq!(format_eta(7260), "2h 1m");
+    }
+}
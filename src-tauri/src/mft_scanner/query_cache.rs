@@ -24,11 +24,12 @@ pub struct QueryCacheManager {
 
 impl QueryCacheManager {
     pub fn new() -> Self {
-        // 从环境或默认路径获取输出目录
+        // 从环境或统一的数据目录获取输出目录（支持自定义数据目录）
         let output_dir = std::env::var("MFT_INDEX_DIR")
             .unwrap_or_else(|_| {
-                let local_data = std::env::var("LOCALAPPDATA").unwrap_or_default();
-                format!("{}\\iLauncher\\mft_databases", local_data)
+                crate::utils::paths::get_mft_database_dir()
+                    .map(|dir| dir.to_string_lossy().to_string())
+                    .unwrap_or_default()
             });
         
         Self {
@@ -0,0 +1,296 @@
+// MFT 索引磁盘占用统计与清理
+// 索引文件命名：{盘符}_index.fst / _bitmaps.dat / _paths.dat / _offsets.dat / _index_delta.dat / _tombstones.dat / _index.version / _index.manifest
+// 构建/合并过程中的临时文件：*.tmp / *.new
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 遗留临时文件的判定时间（超过此时间未修改视为构建中断的残留）
+pub const STALE_TEMP_AGE: Duration = Duration::from_secs(3600);
+
+/// 索引文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFileKind {
+    Fst,
+    Bitmap,
+    Paths,
+    Offsets,
+    Delta,
+    Tombstones,
+    Temp,
+    Other,
+}
+
+/// 单个驱动器的索引占用（字节）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DriveIndexUsage {
+    pub drive: char,
+    pub fst_bytes: u64,
+    pub bitmap_bytes: u64,
+    pub paths_bytes: u64,
+    pub offsets_bytes: u64,
+    pub delta_bytes: u64,
+    pub tombstone_bytes: u64,
+    /// 未完成构建/合并遗留的临时文件
+    pub temp_bytes: u64,
+    pub other_bytes: u64,
+    pub total_bytes: u64,
+    /// 是否仍在扫描配置中（不在则可安全删除）
+    pub indexed: bool,
+}
+
+/// 索引目录占用报告
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStorageReport {
+    pub dir: PathBuf,
+    pub total_bytes: u64,
+    pub drives: Vec<DriveIndexUsage>,
+}
+
+/// 清理结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CleanupResult {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+    /// 删除失败的文件（通常被 MFT 服务占用）
+    pub failed: Vec<String>,
+}
+
+/// 解析索引文件名 → (盘符, 类型)
+pub fn classify(file_name: &str) -> Option<(char, IndexFileKind)> {
+    let mut chars = file_name.chars();
+    let drive = chars.next()?.to_ascii_uppercase();
+    if !drive.is_ascii_alphabetic() {
+        return None;
+    }
+    let rest = chars.as_str();
+
+    let kind = if rest.ends_with(".tmp") || rest.ends_with(".new") {
+        IndexFileKind::Temp
+    } else {
+        match rest {
+            "_index.fst" => IndexFileKind::Fst,
+            "_bitmaps.dat" => IndexFileKind::Bitmap,
+            "_paths.dat" => IndexFileKind::Paths,
+            "_offsets.dat" => IndexFileKind::Offsets,
+            "_index_delta.dat" => IndexFileKind::Delta,
+            "_tombstones.dat" => IndexFileKind::Tombstones,
+            // 版本号文件、发布清单和旧版 {盘符}.db 数据库
+            "_index.version" | "_index.manifest" | ".db" => IndexFileKind::Other,
+            _ => return None,
+        }
+    };
+    Some((drive, kind))
+}
+
+/// 统计索引目录占用；indexed_drives 为扫描配置中的驱动器
+pub fn storage_report(dir: &Path, indexed_drives: &[char]) -> Result<IndexStorageReport> {
+    let mut drives: BTreeMap<char, DriveIndexUsage> = BTreeMap::new();
+
+    if dir.exists() {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let Some((drive, kind)) = classify(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+            let usage = drives.entry(drive).or_insert_with(|| DriveIndexUsage {
+                drive,
+                ..Default::default()
+            });
+            match kind {
+                IndexFileKind::Fst => usage.fst_bytes += size,
+                IndexFileKind::Bitmap => usage.bitmap_bytes += size,
+                IndexFileKind::Paths => usage.paths_bytes += size,
+                IndexFileKind::Offsets => usage.offsets_bytes += size,
+                IndexFileKind::Delta => usage.delta_bytes += size,
+                IndexFileKind::Tombstones => usage.tombstone_bytes += size,
+                IndexFileKind::Temp => usage.temp_bytes += size,
+                IndexFileKind::Other => usage.other_bytes += size,
+            }
+            usage.total_bytes += size;
+        }
+    }
+
+    let drives: Vec<DriveIndexUsage> = drives
+        .into_values()
+        .map(|mut usage| {
+            usage.indexed = indexed_drives.iter().any(|d| d.eq_ignore_ascii_case(&usage.drive));
+            usage
+        })
+        .collect();
+
+    Ok(IndexStorageReport {
+        dir: dir.to_path_buf(),
+        total_bytes: drives.iter().map(|d| d.total_bytes).sum(),
+        drives,
+    })
+}
+
+/// 驱动器索引统计（占用 + 待压缩的积压）
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveIndexStats {
+    #[serde(flatten)]
+    pub usage: DriveIndexUsage,
+    /// 主索引（FST + 位图）占用
+    pub index_bytes: u64,
+    /// 待清理的墓碑条目数（已删除/重命名的文件）
+    pub tombstones: u64,
+}
+
+/// 索引目录统计
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    pub dir: PathBuf,
+    pub total_bytes: u64,
+    /// 磁盘占用上限（None 表示不限制）
+    pub max_bytes: Option<u64>,
+    pub over_budget: bool,
+    pub drives: Vec<DriveIndexStats>,
+}
+
+/// 统计索引占用、增量积压和墓碑数量
+pub fn index_stats(dir: &Path, indexed_drives: &[char], max_bytes: Option<u64>) -> Result<IndexStats> {
+    let report = storage_report(dir, indexed_drives)?;
+    let output_dir = dir.to_string_lossy();
+    let drives = report
+        .drives
+        .into_iter()
+        .map(|usage| DriveIndexStats {
+            index_bytes: usage.fst_bytes + usage.bitmap_bytes,
+            tombstones: super::compaction::tombstone_count(usage.drive, &output_dir),
+            usage,
+        })
+        .collect();
+
+    Ok(IndexStats {
+        dir: report.dir,
+        total_bytes: report.total_bytes,
+        max_bytes,
+        over_budget: max_bytes.map_or(false, |max| report.total_bytes > max),
+        drives,
+    })
+}
+
+/// 删除匹配的索引文件（drive 为 None 表示所有驱动器；kinds 为空表示所有类型）
+pub fn remove_files(dir: &Path, drive: Option<char>, kinds: &[IndexFileKind]) -> Result<CleanupResult> {
+    remove_matching(dir, drive, kinds, None)
+}
+
+/// 删除遗留的临时文件（超过 older_than 未修改，避免误删正在构建的索引）
+pub fn remove_stale_temp_files(dir: &Path, drive: Option<char>, older_than: Duration) -> Result<CleanupResult> {
+    remove_matching(dir, drive, &[IndexFileKind::Temp], Some(older_than))
+}
+
+fn remove_matching(
+    dir: &Path,
+    drive: Option<char>,
+    kinds: &[IndexFileKind],
+    older_than: Option<Duration>,
+) -> Result<CleanupResult> {
+    let mut result = CleanupResult::default();
+    if !dir.exists() {
+        return Ok(result);
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some((file_drive, kind)) = classify(&name) else {
+            continue;
+        };
+        if drive.map_or(false, |d| !d.eq_ignore_ascii_case(&file_drive)) {
+            continue;
+        }
+        if !kinds.is_empty() && !kinds.contains(&kind) {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        if let Some(older_than) = older_than {
+            let age = metadata.modified().ok().and_then(|t| t.elapsed().ok()).unwrap_or_default();
+            if age < older_than {
+                continue;
+            }
+        }
+
+        let size = metadata.len();
+        match std::fs::remove_file(entry.path()) {
+            Ok(()) => {
+                result.removed_files += 1;
+                result.freed_bytes += size;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to remove index file {}: {}", name, e);
+                result.failed.push(name);
+            }
+        }
+    }
+
+    tracing::info!(
+        "🧹 Index cleanup: {} files removed, {} bytes freed",
+        result.removed_files,
+        result.freed_bytes
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_index_files() {
+        assert_eq!(classify("C_index.fst"), Some(('C', IndexFileKind::Fst)));
+        assert_eq!(classify("d_bitmaps.dat"), Some(('D', IndexFileKind::Bitmap)));
+        assert_eq!(classify("E_index_delta.dat"), Some(('E', IndexFileKind::Delta)));
+        assert_eq!(classify("E_tombstones.dat"), Some(('E', IndexFileKind::Tombstones)));
+        assert_eq!(classify("C_index.fst.new"), Some(('C', IndexFileKind::Temp)));
+        assert_eq!(classify("C_paths.tmp"), Some(('C', IndexFileKind::Temp)));
+        assert_eq!(classify("C.db"), Some(('C', IndexFileKind::Other)));
+        assert_eq!(classify("C_index.manifest"), Some(('C', IndexFileKind::Other)));
+        assert_eq!(classify("scan_config.json"), None);
+        assert_eq!(classify("1_index.fst"), None);
+    }
+
+    #[test]
+    fn test_report_and_remove_drive() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_index_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("C_index.fst"), vec![0u8; 10]).unwrap();
+        std::fs::write(dir.join("C_index_delta.dat"), vec![0u8; 5]).unwrap();
+        std::fs::write(dir.join("D_paths.dat"), vec![0u8; 7]).unwrap();
+        std::fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+
+        let report = storage_report(&dir, &['C']).unwrap();
+        assert_eq!(report.total_bytes, 22);
+        assert_eq!(report.drives.len(), 2);
+        assert!(report.drives[0].indexed);
+        assert_eq!(report.drives[0].delta_bytes, 5);
+        assert!(!report.drives[1].indexed);
+
+        let stats = index_stats(&dir, &['C'], Some(20)).unwrap();
+        assert!(stats.over_budget);
+        assert_eq!(stats.drives[0].index_bytes, 10);
+        assert_eq!(stats.drives[0].tombstones, 0);
+        assert!(!index_stats(&dir, &['C'], None).unwrap().over_budget);
+
+        let removed = remove_files(&dir, Some('C'), &[IndexFileKind::Delta]).unwrap();
+        assert_eq!(removed.freed_bytes, 5);
+        let removed = remove_files(&dir, Some('d'), &[]).unwrap();
+        assert_eq!(removed.removed_files, 1);
+        assert!(dir.join("notes.txt").exists());
+
+        std::fs::write(dir.join("C_paths.tmp"), b"building").unwrap();
+        let removed = remove_stale_temp_files(&dir, None, Duration::from_secs(3600)).unwrap();
+        assert_eq!(removed.removed_files, 0);
+        let removed = remove_stale_temp_files(&dir, None, Duration::ZERO).unwrap();
+        assert_eq!(removed.removed_files, 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
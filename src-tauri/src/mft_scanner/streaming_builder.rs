@@ -6,12 +6,18 @@ use bumpalo::Bump;
 use rustc_hash::FxHashMap;
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
 use tracing::{info, debug};
 use windows::Win32::Foundation::*;
 use windows::Win32::Storage::FileSystem::*;
 use windows::Win32::System::IO::DeviceIoControl;
 
+use super::manifest;
+use super::progress::{ProgressReporter, ScanStage};
+use super::throttle::ScanThrottle;
 use super::types::*;
+use crate::utils::index_exclusions::IndexExclusions;
 
 /// FileRecord - 不存储完整路径，只存储文件名引用和父目录ID
 struct FileRecord {
@@ -31,6 +37,9 @@ pub struct StreamingBuilder {
     index_writer: BufWriter<File>,              // 流式写入索引
     current_path_id: u32,
     total_files: u64,
+    throttle: Option<Arc<ScanThrottle>>,            // 扫描限流（可选）
+    progress: Option<ProgressReporter>,             // 进度汇报（可选）
+    exclusions: Arc<IndexExclusions>,               // 用户排除规则
 }
 
 impl StreamingBuilder {
@@ -58,9 +67,44 @@ impl StreamingBuilder {
             ),
             current_path_id: 0,
             total_files: 0,
+            throttle: None,
+            progress: None,
+            exclusions: Arc::new(IndexExclusions::new(Vec::new())),
         })
     }
     
+    /// 设置扫描限流器（限速、全屏/高负载时暂停）
+    pub fn with_throttle(mut self, throttle: Arc<ScanThrottle>) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+    
+    /// 设置进度汇报器（通过 IPC 状态展示到 UI）
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+    
+    /// 设置用户排除规则（node_modules、构建目录等不写入索引）
+    pub fn with_exclusions(mut self, exclusions: Arc<IndexExclusions>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+    
+    /// 汇报阶段内进度
+    fn report_progress(&mut self, stage: ScanStage, records_scanned: u64, total_records: u64) {
+        if let Some(progress) = &mut self.progress {
+            progress.report(stage, records_scanned, total_records);
+        }
+    }
+    
+    /// 通知限流器已处理的记录数
+    fn throttle_tick(&self, records: u64) {
+        if let Some(throttle) = &self.throttle {
+            throttle.tick(records);
+        }
+    }
+    
     /// 从 MFT 流式读取（内存占用稳定）
     pub fn scan_mft_streaming(&mut self) -> Result<()> {
         info!("🚀 Starting streaming scan for drive {}:", self.drive_letter);
@@ -114,6 +158,30 @@ impl StreamingBuilder {
         }
     }
     
+    /// 查询 MFT 记录总数（用于估算枚举进度，失败时返回 0）
+    fn query_mft_record_count(&self, volume_handle: HANDLE) -> u64 {
+        let mut volume_data: NtfsVolumeData = Default::default();
+        let mut bytes_returned: u32 = 0;
+        
+        let result = unsafe {
+            DeviceIoControl(
+                volume_handle,
+                FSCTL_GET_NTFS_VOLUME_DATA,
+                None,
+                0,
+                Some(&mut volume_data as *mut _ as *mut std::ffi::c_void),
+                std::mem::size_of::<NtfsVolumeData>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )
+        };
+        
+        if result.is_err() || volume_data.bytes_per_file_record_segment == 0 {
+            return 0;
+        }
+        volume_data.mft_valid_data_length as u64 / volume_data.bytes_per_file_record_segment as u64
+    }
+    
     /// 查询 USN Journal
     fn query_usn_journal(&self, volume_handle: HANDLE) -> Result<UsnJournalData> {
         let mut journal_data: UsnJournalData = Default::default();
@@ -145,6 +213,12 @@ impl StreamingBuilder {
         // 预分配 HashMap 容量（减少 rehashing）
         let mut frn_map = FxHashMap::with_capacity_and_hasher(2_500_000, Default::default());
         
+        // MFT 按记录号顺序枚举，下一个起始 FRN 的低 48 位即已扫描的记录数
+        let total_records = self.query_mft_record_count(volume_handle);
+        if let Some(progress) = &mut self.progress {
+            progress.begin_stage(ScanStage::Enumerating, total_records);
+        }
+        
         let mut enum_data = MftEnumData {
             start_file_reference_number: 0,
             low_usn: 0,
@@ -155,6 +229,7 @@ impl StreamingBuilder {
         const BUFFER_SIZE: usize = 4 * 1024 * 1024;
         let mut buffer = vec![0u8; BUFFER_SIZE];
         let mut bytes_returned: u32 = 0;
+        let mut last_len = 0usize;
         
         loop {
             unsafe {
@@ -209,6 +284,13 @@ impl StreamingBuilder {
                     }
                 }
             }
+            
+            // 每批 MFT 记录后检查限速/暂停
+            self.throttle_tick((frn_map.len() - last_len) as u64);
+            last_len = frn_map.len();
+            
+            let records_scanned = enum_data.start_file_reference_number & 0x0000_FFFF_FFFF_FFFF;
+            self.report_progress(ScanStage::Enumerating, records_scanned, total_records);
         }
         
         debug!("   Total entries: {}", frn_map.len());
@@ -233,7 +315,16 @@ impl StreamingBuilder {
         // 重用 buffer
         let mut path_buffer = String::with_capacity(512);
         
-        for (frn, parent_info) in frn_map.iter() {
+        let total_records = frn_map.len() as u64;
+        if let Some(progress) = &mut self.progress {
+            progress.begin_stage(ScanStage::WritingPaths, total_records);
+        }
+        
+        for (processed, (frn, parent_info)) in frn_map.iter().enumerate() {
+            if processed % BATCH_SIZE == 0 {
+                self.report_progress(ScanStage::WritingPaths, processed as u64, total_records);
+            }
+            
             path_buffer.clear();
             
             // 🔹 延迟构建完整路径
@@ -254,6 +345,7 @@ impl StreamingBuilder {
                 // 批量刷新
                 if self.total_files % BATCH_SIZE as u64 == 0 {
                     self.flush_buffers()?;
+                    self.throttle_tick(BATCH_SIZE as u64);
                     
                     // 🔥 减少日志频率（从 50K 提升到 200K）
                     if self.total_files % 200_000 == 0 {
@@ -338,7 +430,8 @@ impl StreamingBuilder {
         path_lower.contains("$recycle.bin") ||
         path_lower.contains("system volume information") ||
         path_lower.contains("\\winsxs\\") ||
-        path_lower.contains("\\temp\\")
+        path_lower.contains("\\temp\\") ||
+        self.exclusions.is_excluded(path)
     }
     
     /// 写入路径条目
@@ -392,7 +485,8 @@ impl StreamingBuilder {
         let final_paths = format!("{}\\{}_paths.dat", output_dir, self.drive_letter);
         let final_index = format!("{}\\{}_index.dat", output_dir, self.drive_letter);
         
-        std::fs::rename(temp_paths, final_paths)?;
+        // 路径文件落盘后再替换（索引构建和清单都依赖它）
+        manifest::publish_file(Path::new(&temp_paths), Path::new(&final_paths))?;
         std::fs::rename(temp_index, final_index)?;
         
         info!("✅ Database finalized: {} files", self.total_files);
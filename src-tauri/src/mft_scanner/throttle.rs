@@ -0,0 +1,284 @@
+// 扫描限流 - 降低 MFT 服务对前台的影响
+// 进程优先级/CPU 亲和性、后台 I/O 优先级、吞吐量限制，以及全屏应用或高负载时暂停扫描
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tracing::{info, warn};
+
+/// 暂停条件检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+/// 暂停期间的轮询间隔
+const PAUSE_POLL: Duration = Duration::from_secs(1);
+/// 单次暂停最长时间（避免扫描永远无法完成）
+const MAX_PAUSE: Duration = Duration::from_secs(10 * 60);
+
+/// 进程优先级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessPriority {
+    Idle,
+    BelowNormal,
+    Normal,
+}
+
+/// 扫描限流配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanThrottleConfig {
+    /// 扫描期间的进程优先级
+    pub process_priority: ProcessPriority,
+    /// 扫描线程使用后台 I/O 优先级（HDD 上显著减少卡顿）
+    pub background_io: bool,
+    /// 每秒最多处理的记录数（None 表示不限制）
+    pub max_records_per_sec: Option<u64>,
+    /// CPU 亲和性掩码（None 表示不限制）
+    pub cpu_affinity_mask: Option<u64>,
+    /// 前台有全屏应用（游戏、演示）时暂停扫描
+    pub pause_when_fullscreen: bool,
+    /// 其他进程 CPU 占用超过该百分比时暂停扫描（None 表示不检测）
+    pub pause_cpu_threshold: Option<f32>,
+}
+
+impl Default for ScanThrottleConfig {
+    fn default() -> Self {
+        Self {
+            process_priority: ProcessPriority::BelowNormal,
+            background_io: true,
+            max_records_per_sec: None,
+            cpu_affinity_mask: None,
+            pause_when_fullscreen: true,
+            pause_cpu_threshold: Some(85.0),
+        }
+    }
+}
+
+struct RateWindow {
+    started: Instant,
+    records: u64,
+}
+
+struct LoadProbe {
+    system: System,
+    last_check: Instant,
+}
+
+/// 扫描限流器（多个驱动器的扫描线程共享，吞吐量限制按总量计算）
+pub struct ScanThrottle {
+    config: ScanThrottleConfig,
+    window: Mutex<RateWindow>,
+    probe: Mutex<LoadProbe>,
+}
+
+impl ScanThrottle {
+    pub fn new(config: ScanThrottleConfig) -> Self {
+        Self {
+            config,
+            window: Mutex::new(RateWindow {
+                started: Instant::now(),
+                records: 0,
+            }),
+            probe: Mutex::new(LoadProbe {
+                system: System::new(),
+                last_check: Instant::now(),
+            }),
+        }
+    }
+
+    /// 应用进程优先级和 CPU 亲和性
+    pub fn apply_process_settings(&self) {
+        if let Err(e) = platform::set_process_priority(self.config.process_priority) {
+            warn!("Failed to set scanner priority: {}", e);
+        } else {
+            info!("⚙️ Scanner priority: {:?}", self.config.process_priority);
+        }
+
+        if let Some(mask) = self.config.cpu_affinity_mask.filter(|mask| *mask != 0) {
+            match platform::set_affinity(mask) {
+                Ok(()) => info!("⚙️ Scanner CPU affinity: {:#x}", mask),
+                Err(e) => warn!("Failed to set scanner CPU affinity: {}", e),
+            }
+        }
+    }
+
+    /// 当前线程进入后台 I/O 模式（guard 释放时恢复）
+    pub fn background_io_guard(&self) -> Option<BackgroundIoGuard> {
+        if !self.config.background_io {
+            return None;
+        }
+        match platform::begin_background_mode() {
+            Ok(()) => Some(BackgroundIoGuard),
+            Err(e) => {
+                warn!("Failed to enter background I/O mode: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 记录已处理的记录数，按需限速或暂停（由扫描循环定期调用）
+    pub fn tick(&self, records: u64) {
+        if let Some(max_per_sec) = self.config.max_records_per_sec {
+            let delay = {
+                let mut window = self.window.lock();
+                window.records += records;
+                let delay = limiter_delay(window.records, window.started.elapsed(), max_per_sec);
+                // 每秒重置窗口，避免长时间扫描后累计误差
+                if window.started.elapsed() >= Duration::from_secs(1) && delay.is_zero() {
+                    window.started = Instant::now();
+                    window.records = 0;
+                }
+                delay
+            };
+            if !delay.is_zero() {
+                std::thread::sleep(delay);
+            }
+        }
+
+        self.wait_while_busy();
+    }
+
+    /// 前台全屏或系统高负载时阻塞等待
+    fn wait_while_busy(&self) {
+        let paused_at = Instant::now();
+        let mut logged = false;
+
+        loop {
+            let Some(reason) = self.pause_reason() else {
+                break;
+            };
+            if paused_at.elapsed() >= MAX_PAUSE {
+                warn!("⏯️ Scan paused for {:?} ({}), resuming anyway", MAX_PAUSE, reason);
+                break;
+            }
+            if !logged {
+                info!("⏸️ Scan paused: {}", reason);
+                logged = true;
+            }
+            std::thread::sleep(PAUSE_POLL);
+        }
+
+        if logged {
+            info!("▶️ Scan resumed after {:.1}s", paused_at.elapsed().as_secs_f32());
+        }
+    }
+
+    /// 返回需要暂停的原因（检查间隔内只检查一次）
+    fn pause_reason(&self) -> Option<String> {
+        let mut probe = self.probe.lock();
+        if probe.last_check.elapsed() < CHECK_INTERVAL {
+            return None;
+        }
+        probe.last_check = Instant::now();
+
+        if self.config.pause_when_fullscreen && platform::is_fullscreen_app_active() {
+            return Some("fullscreen application in foreground".to_string());
+        }
+
+        let threshold = self.config.pause_cpu_threshold?;
+        let pid = Pid::from_u32(std::process::id());
+        probe.system.refresh_cpu_usage();
+        probe.system.refresh_processes(ProcessesToUpdate::Some(&[pid]));
+        let own = probe.system.process(pid).map(|p| p.cpu_usage()).unwrap_or(0.0);
+        let load = others_cpu_load(probe.system.global_cpu_usage(), own, probe.system.cpus().len());
+        (load > threshold).then(|| format!("CPU load {:.0}% > {:.0}%", load, threshold))
+    }
+}
+
+/// 后台 I/O 模式守卫
+pub struct BackgroundIoGuard;
+
+impl Drop for BackgroundIoGuard {
+    fn drop(&mut self) {
+        let _ = platform::end_background_mode();
+    }
+}
+
+/// 按目标吞吐量计算需要等待的时间
+fn limiter_delay(records: u64, elapsed: Duration, max_per_sec: u64) -> Duration {
+    if max_per_sec == 0 {
+        return Duration::ZERO;
+    }
+    let expected = Duration::from_secs_f64(records as f64 / max_per_sec as f64);
+    expected.saturating_sub(elapsed)
+}
+
+/// 扣除自身占用后的系统 CPU 负载（进程占用按单核百分比计算）
+fn others_cpu_load(global: f32, own: f32, cpu_count: usize) -> f32 {
+    let own_share = own / cpu_count.max(1) as f32;
+    (global - own_share).max(0.0)
+}
+
+mod platform {
+    use super::ProcessPriority;
+    use anyhow::Result;
+    use windows::Win32::System::Threading::{
+        GetCurrentProcess, GetCurrentThread, SetPriorityClass, SetProcessAffinityMask, SetThreadPriority,
+        BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, THREAD_MODE_BACKGROUND_BEGIN,
+        THREAD_MODE_BACKGROUND_END,
+    };
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN,
+    };
+
+    pub fn set_process_priority(priority: ProcessPriority) -> Result<()> {
+        let class = match priority {
+            ProcessPriority::Idle => IDLE_PRIORITY_CLASS,
+            ProcessPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+        };
+        unsafe { SetPriorityClass(GetCurrentProcess(), class)? };
+        Ok(())
+    }
+
+    pub fn set_affinity(mask: u64) -> Result<()> {
+        unsafe { SetProcessAffinityMask(GetCurrentProcess(), mask as usize)? };
+        Ok(())
+    }
+
+    /// 后台模式同时降低线程的 CPU、I/O 和内存优先级
+    pub fn begin_background_mode() -> Result<()> {
+        unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN)? };
+        Ok(())
+    }
+
+    pub fn end_background_mode() -> Result<()> {
+        unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END)? };
+        Ok(())
+    }
+
+    pub fn is_fullscreen_app_active() -> bool {
+        match unsafe { SHQueryUserNotificationState() } {
+            Ok(state) => matches!(state, QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limiter_delay() {
+        // 1 秒内处理了 2000 条，限制 1000 条/秒 → 需要再等 1 秒
+        assert_eq!(limiter_delay(2000, Duration::from_secs(1), 1000), Duration::from_secs(1));
+        assert_eq!(limiter_delay(500, Duration::from_secs(1), 1000), Duration::ZERO);
+        assert_eq!(limiter_delay(500, Duration::ZERO, 0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_others_cpu_load() {
+        // 8 核，自身占满 2 核（200%），全局 50% → 其他进程 25%
+        assert_eq!(others_cpu_load(50.0, 200.0, 8), 25.0);
+        assert_eq!(others_cpu_load(10.0, 400.0, 4), 0.0);
+    }
+
+    #[test]
+    fn test_config_defaults_when_missing() {
+        let config: ScanThrottleConfig = serde_json::from_str(r#"{ "max_records_per_sec": 50000 }"#).unwrap();
+        assert_eq!(config.process_priority, ProcessPriority::BelowNormal);
+        assert!(config.background_io);
+        assert_eq!(config.max_records_per_sec, Some(50000));
+    }
+}
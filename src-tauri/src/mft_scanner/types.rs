@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use rustc_hash::FxHashMap;  // 🔥 使用高性能哈希
 
+use super::throttle::ScanThrottleConfig;
+
 /// 父目录信息
 #[derive(Debug, Clone)]
 pub struct ParentInfo {
@@ -63,6 +65,15 @@ pub struct ScanConfig {
     pub output_dir: String,
     /// 忽略路径列表（小写）
     pub ignore_paths: Vec<String>,
+    /// 用户移除索引的驱动器（自动检测时跳过）
+    #[serde(default)]
+    pub excluded_drives: Vec<char>,
+    /// 扫描限流（优先级、I/O、吞吐量、暂停条件）
+    #[serde(default)]
+    pub throttle: ScanThrottleConfig,
+    /// 索引目录磁盘占用上限（MB，None 表示不限制），超出时后台自动压缩
+    #[serde(default)]
+    pub max_index_mb: Option<u64>,
 }
 
 impl Default for ScanConfig {
@@ -78,66 +89,27 @@ impl Default for ScanConfig {
                 "c:\\$recycle.bin".to_string(),
                 "appdata\\local\\temp".to_string(),
             ],
+            excluded_drives: vec![],
+            throttle: ScanThrottleConfig::default(),
+            max_index_mb: None,
         }
     }
 }
 
 impl ScanConfig {
-    /// 检测所有 NTFS 驱动器
+    /// 磁盘占用上限（字节）
+    pub fn max_index_bytes(&self) -> Option<u64> {
+        self.max_index_mb.map(|mb| mb * 1024 * 1024)
+    }
+    
+    /// 检测所有 NTFS 驱动器（固定磁盘，不包括光驱、U盘等）
     #[cfg(target_os = "windows")]
     pub fn detect_ntfs_drives() -> Vec<char> {
-        use std::ffi::OsString;
-        use std::os::windows::ffi::OsStringExt;
-        
-        let mut drives = Vec::new();
-        
-        unsafe {
-            use windows::Win32::Storage::FileSystem::{GetLogicalDrives, GetDriveTypeW, GetVolumeInformationW};
-            use windows::core::PCWSTR;
-            
-            // 获取所有逻辑驱动器的位掩码
-            let drive_mask = GetLogicalDrives();
-            
-            for i in 0..26 {
-                // 检查该位是否被设置（表示驱动器存在）
-                if (drive_mask & (1 << i)) != 0 {
-                    let drive_letter = (b'A' + i) as char;
-                    let root_path: Vec<u16> = format!("{}:\\", drive_letter)
-                        .encode_utf16()
-                        .chain(std::iter::once(0))
-                        .collect();
-                    
-                    // 检查是否是固定磁盘（不包括光驱、U盘等）
-                    // 3 = DRIVE_FIXED
-                    let drive_type = GetDriveTypeW(PCWSTR(root_path.as_ptr()));
-                    
-                    if drive_type == 3 {
-                        // 检查文件系统类型
-                        let mut fs_name = vec![0u16; 32];
-                        let result = GetVolumeInformationW(
-                            PCWSTR(root_path.as_ptr()),
-                            None,
-                            None,
-                            None,
-                            None,
-                            Some(&mut fs_name),
-                        );
-                        
-                        if result.is_ok() {
-                            let fs_type = OsString::from_wide(&fs_name)
-                                .to_string_lossy()
-                                .trim_end_matches('\0')
-                                .to_string();
-                            
-                            // 只添加 NTFS 驱动器
-                            if fs_type == "NTFS" {
-                                drives.push(drive_letter);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let mut drives: Vec<char> = crate::utils::drives::list_drives()
+            .into_iter()
+            .filter(|drive| drive.supports_mft())
+            .map(|drive| drive.letter)
+            .collect();
         
         // 如果没有检测到驱动器，至少包含 C 盘
         if drives.is_empty() {
@@ -248,12 +220,33 @@ pub struct NtfsFileRecordInputBuffer {
     pub file_reference_number: u64,
 }
 
+/// NTFS 卷信息（用于估算 MFT 记录总数）
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NtfsVolumeData {
+    pub volume_serial_number: i64,
+    pub number_sectors: i64,
+    pub total_clusters: i64,
+    pub free_clusters: i64,
+    pub total_reserved: i64,
+    pub bytes_per_sector: u32,
+    pub bytes_per_cluster: u32,
+    pub bytes_per_file_record_segment: u32,
+    pub clusters_per_file_record_segment: u32,
+    pub mft_valid_data_length: i64,
+    pub mft_start_lcn: i64,
+    pub mft2_start_lcn: i64,
+    pub mft_zone_start: i64,
+    pub mft_zone_end: i64,
+}
+
 // IOCTL 代码
 pub const FSCTL_QUERY_USN_JOURNAL: u32 = 0x000900f4;
 pub const FSCTL_CREATE_USN_JOURNAL: u32 = 0x000900e7;
 pub const FSCTL_ENUM_USN_DATA: u32 = 0x000900b3;
 pub const FSCTL_READ_USN_JOURNAL: u32 = 0x000900bb;
 pub const FSCTL_GET_NTFS_FILE_RECORD: u32 = 0x00090068;
+pub const FSCTL_GET_NTFS_VOLUME_DATA: u32 = 0x00090064;
 
 // 文件属性常量
 pub const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x00000010;
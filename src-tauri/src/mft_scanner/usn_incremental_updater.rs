@@ -21,6 +21,7 @@ use windows::Win32::Storage::FileSystem::*;
 use windows::Win32::System::IO::DeviceIoControl;
 
 use super::types::*;
+use crate::utils::index_exclusions::IndexExclusions;
 
 /// 父目录信息（优化内存占用）
 #[derive(Clone, Debug)]
@@ -39,11 +40,14 @@ pub struct UsnIncrementalUpdater {
     frn_map: FxHashMap<u64, ParentInfo>,         // FRN -> (parent_frn, filename)
     file_id_counter: u32,                         // 当前最大 file_id
     index_cache: HashMap<String, RoaringBitmap>,  // gram -> bitmap 缓存
-    deleted_files: FxHashMap<u64, u32>,          // deleted_frn -> old_file_id
+    file_ids: FxHashMap<u64, u32>,               // FRN -> 本次运行中追加的 file_id
+    tombstones: RoaringBitmap,                    // 待写入的已删除 file_id
     
     // 文件句柄
     paths_writer: Option<BufWriter<File>>,
     paths_offset: u64,  // 当前写入偏移量
+    
+    exclusions: Arc<IndexExclusions>,  // 用户排除规则
 }
 
 impl UsnIncrementalUpdater {
@@ -55,12 +59,20 @@ impl UsnIncrementalUpdater {
             frn_map: FxHashMap::default(),
             file_id_counter: 0,
             index_cache: HashMap::new(),
-            deleted_files: FxHashMap::default(),
+            file_ids: FxHashMap::default(),
+            tombstones: RoaringBitmap::new(),
             paths_writer: None,
             paths_offset: 0,
+            exclusions: Arc::new(IndexExclusions::new(Vec::new())),
         }
     }
     
+    /// 设置排除规则（命中的新文件不追加到索引）
+    pub fn with_exclusions(mut self, exclusions: Arc<IndexExclusions>) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+    
     /// 初始化 USN（读取当前位置 + 加载现有 FRN Map）
     pub fn initialize(&mut self) -> Result<()> {
         info!("🔧 Initializing USN updater for drive {}:", self.drive_letter);
@@ -359,7 +371,7 @@ impl UsnIncrementalUpdater {
                     debug!("Processed {} USN changes", changes);
                     
                     // 每 1000 条刷新缓存
-                    if self.index_cache.len() > 1000 {
+                    if self.index_cache.len() > 1000 || self.tombstones.len() > 1000 {
                         self.flush_index_cache()?;
                     }
                 }
@@ -396,15 +408,9 @@ impl UsnIncrementalUpdater {
         if reason & 0x00000200 != 0 {  // USN_REASON_FILE_DELETE
             debug!("🗑️  File deleted: {}", filename);
             
-            // 从 FRN Map 移除
-            if let Some(_info) = self.frn_map.remove(&frn) {
-                // TODO: 标记文件已删除，但保留 file_id 用于去重
-                // 实际应该在 bitmap 中移除对应的 bit
-                // 这里简化处理：记录到 deleted_files
-                if let Some(&file_id) = self.deleted_files.get(&frn) {
-                    self.deleted_files.insert(frn, file_id);
-                }
-            }
+            // 从 FRN Map 移除，已知 file_id 的条目记为墓碑
+            self.frn_map.remove(&frn);
+            self.remove_file(frn)?;
         }
         
         // 文件重命名
@@ -437,9 +443,18 @@ impl UsnIncrementalUpdater {
         // 1. 构建完整路径
         let full_path = self.build_path_from_frn(frn)?;
         
-        // 2. 分配新的 file_id
+        // 命中排除规则的路径不进入索引（FRN Map 仍保留，用于构建子项路径）
+        if self.exclusions.is_excluded(&full_path) {
+            debug!("   ⏭️  Excluded from index: {}", full_path);
+            return Ok(());
+        }
+        
+        // 2. 分配新的 file_id（同一 FRN 的旧条目记为墓碑）
         let file_id = self.file_id_counter;
         self.file_id_counter += 1;
+        if let Some(old_id) = self.file_ids.insert(frn, file_id) {
+            self.tombstones.insert(old_id);
+        }
         
         // 3. 追加到 _paths.dat
         self.append_path_to_file(&full_path)?;
@@ -573,37 +588,19 @@ impl UsnIncrementalUpdater {
     }
     
     /// 删除文件（更新索引）
-    fn remove_file(&mut self, _frn: u64) -> Result<()> {
-        // TODO: 从 bitmap 中移除对应的 bit
-        // 由于 RoaringBitmap 不支持直接删除，实际需要重建或标记删除
-        // 这里简化：仅记录到 deleted_files
+    /// 位图和路径文件不能原地删除，记为墓碑：查询时排除，压缩时清理
+    fn remove_file(&mut self, frn: u64) -> Result<()> {
+        if let Some(file_id) = self.file_ids.remove(&frn) {
+            self.tombstones.insert(file_id);
+        }
         Ok(())
     }
     
     /// 更新文件名（更新索引）
-    fn update_file_name_in_index(&mut self, _old_name: &str, new_name: &str, _frn: u64) -> Result<()> {
-        // TODO: 找到对应的 file_id，然后：
-        // 1. 从旧 3-gram 的 bitmap 中移除 file_id
-        // 2. 添加到新 3-gram 的 bitmap
-        //
-        // 由于没有维护 file_id -> frn 的反向映射，这里简化：
-        // 直接生成新 3-gram（旧 3-gram 保留，下次重建时清理）
-        
-        let new_name_lower = new_name.to_lowercase();
-        let grams = self.split_to_3grams(&new_name_lower);
-        
-        // 分配新 file_id（视为新文件）
-        let file_id = self.file_id_counter;
-        self.file_id_counter += 1;
-        
-        for gram in grams {
-            self.index_cache
-                .entry(gram)
-                .or_insert_with(RoaringBitmap::new)
-                .insert(file_id);
-        }
-        
-        Ok(())
+    /// 作为新条目追加（新路径 + 新 3-gram），旧条目记为墓碑，压缩时清理
+    fn update_file_name_in_index(&mut self, _old_name: &str, new_name: &str, frn: u64) -> Result<()> {
+        self.remove_file(frn)?;
+        self.add_file_to_index(new_name, frn)
     }
     
     /// 提取文件名
@@ -619,6 +616,12 @@ impl UsnIncrementalUpdater {
     
     /// 刷新索引缓存到磁盘
     fn flush_index_cache(&mut self) -> Result<()> {
+        if !self.tombstones.is_empty() {
+            super::compaction::append_tombstones(self.drive_letter, &self.output_dir, &self.tombstones)?;
+            debug!("🪦 Flushed {} tombstones", self.tombstones.len());
+            self.tombstones.clear();
+        }
+        
         if self.index_cache.is_empty() {
             return Ok(());
         }
@@ -0,0 +1,314 @@
+// 离线模型管理 - 本地 AI/OCR 功能所需模型（向量、语音识别、文字识别）的下载、存储与占用统计
+// 模型存放在 data/models/<kind>/ 下；依赖模型的功能通过 require() 在运行时检查
+
+use crate::download::{DownloadRequest, DownloadStatus, DOWNLOADS};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// 全局模型管理器
+pub static MODELS: Lazy<ModelManager> = Lazy::new(ModelManager::new);
+
+/// 模型类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelKind {
+    Embedding,
+    Whisper,
+    Ocr,
+}
+
+impl ModelKind {
+    fn dir_name(&self) -> &'static str {
+        match self {
+            ModelKind::Embedding => "embedding",
+            ModelKind::Whisper => "whisper",
+            ModelKind::Ocr => "ocr",
+        }
+    }
+}
+
+/// 可下载模型定义
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: ModelKind,
+    pub file_name: &'static str,
+    pub url: &'static str,
+    /// 大致大小（用于下载前提示）
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<&'static str>,
+}
+
+/// 内置模型目录
+const CATALOG: &[ModelDefinition] = &[
+    ModelDefinition {
+        id: "whisper-tiny",
+        name: "Whisper Tiny (multilingual)",
+        kind: ModelKind::Whisper,
+        file_name: "ggml-tiny.bin",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
+        size_bytes: 78_000_000,
+        sha256: None,
+    },
+    ModelDefinition {
+        id: "whisper-base",
+        name: "Whisper Base (multilingual)",
+        kind: ModelKind::Whisper,
+        file_name: "ggml-base.bin",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
+        size_bytes: 148_000_000,
+        sha256: None,
+    },
+    ModelDefinition {
+        id: "whisper-small",
+        name: "Whisper Small (multilingual)",
+        kind: ModelKind::Whisper,
+        file_name: "ggml-small.bin",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
+        size_bytes: 488_000_000,
+        sha256: None,
+    },
+    ModelDefinition {
+        id: "minilm-l6-v2",
+        name: "all-MiniLM-L6-v2 (ONNX)",
+        kind: ModelKind::Embedding,
+        file_name: "all-MiniLM-L6-v2.onnx",
+        url: "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/onnx/model.onnx",
+        size_bytes: 91_000_000,
+        sha256: None,
+    },
+    ModelDefinition {
+        id: "tesseract-eng",
+        name: "Tesseract English",
+        kind: ModelKind::Ocr,
+        file_name: "eng.traineddata",
+        url: "https://github.com/tesseract-ocr/tessdata_fast/raw/main/eng.traineddata",
+        size_bytes: 4_100_000,
+        sha256: None,
+    },
+    ModelDefinition {
+        id: "tesseract-chi-sim",
+        name: "Tesseract Chinese (Simplified)",
+        kind: ModelKind::Ocr,
+        file_name: "chi_sim.traineddata",
+        url: "https://github.com/tesseract-ocr/tessdata_fast/raw/main/chi_sim.traineddata",
+        size_bytes: 2_400_000,
+        sha256: None,
+    },
+];
+
+/// 模型状态（返回给设置界面）
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    #[serde(flatten)]
+    pub definition: ModelDefinition,
+    pub installed: bool,
+    /// 已安装文件实际大小
+    pub installed_bytes: u64,
+    pub path: PathBuf,
+    /// 正在进行的下载任务
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_id: Option<String>,
+}
+
+/// 功能的模型需求（满足其一即可）
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelRequirement {
+    pub feature: String,
+    pub kind: ModelKind,
+    /// 可选模型，按优先级排列
+    pub models: Vec<String>,
+}
+
+/// 模型管理器
+pub struct ModelManager {
+    root: PathBuf,
+    /// 进行中的下载（model_id → download_id）
+    downloads: RwLock<HashMap<String, String>>,
+    requirements: RwLock<Vec<ModelRequirement>>,
+}
+
+impl ModelManager {
+    fn new() -> Self {
+        let root = crate::utils::paths::get_data_dir()
+            .unwrap_or_else(|_| std::env::temp_dir())
+            .join("models");
+
+        let manager = Self {
+            root,
+            downloads: RwLock::new(HashMap::new()),
+            requirements: RwLock::new(Vec::new()),
+        };
+
+        // 内置功能的模型需求
+        manager.declare_requirement("voice_input", ModelKind::Whisper, &["whisper-base", "whisper-tiny", "whisper-small"]);
+        manager
+    }
+
+    /// 模型目录
+    pub fn catalog(&self) -> &'static [ModelDefinition] {
+        CATALOG
+    }
+
+    fn definition(&self, model_id: &str) -> Result<&'static ModelDefinition> {
+        CATALOG
+            .iter()
+            .find(|m| m.id == model_id)
+            .ok_or_else(|| anyhow!("Unknown model: {}", model_id))
+    }
+
+    /// 模型文件路径
+    pub fn model_path(&self, model_id: &str) -> Result<PathBuf> {
+        let definition = self.definition(model_id)?;
+        Ok(self.root.join(definition.kind.dir_name()).join(definition.file_name))
+    }
+
+    /// 模型是否已安装
+    pub fn is_installed(&self, model_id: &str) -> bool {
+        self.model_path(model_id).map_or(false, |p| p.is_file())
+    }
+
+    /// 列出所有模型及安装状态
+    pub fn list(&self) -> Vec<ModelInfo> {
+        self.cleanup_finished_downloads();
+        let downloads = self.downloads.read();
+
+        CATALOG
+            .iter()
+            .map(|definition| {
+                let path = self.root.join(definition.kind.dir_name()).join(definition.file_name);
+                let installed_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                ModelInfo {
+                    definition: definition.clone(),
+                    installed: path.is_file(),
+                    installed_bytes,
+                    path,
+                    download_id: downloads.get(definition.id).cloned(),
+                }
+            })
+            .collect()
+    }
+
+    /// 已安装模型总占用（字节）
+    pub fn total_installed_bytes(&self) -> u64 {
+        self.list().iter().map(|m| m.installed_bytes).sum()
+    }
+
+    /// 开始下载模型，返回下载任务 ID（进度通过 download:progress 事件推送）
+    pub fn download(&self, model_id: &str) -> Result<String> {
+        self.cleanup_finished_downloads();
+        if let Some(id) = self.downloads.read().get(model_id) {
+            return Ok(id.clone());
+        }
+
+        let definition = self.definition(model_id)?;
+        let download_id = DOWNLOADS.start(DownloadRequest {
+            url: definition.url.to_string(),
+            dest: self.model_path(model_id)?,
+            sha256: definition.sha256.map(|s| s.to_string()),
+            owner: "models".to_string(),
+        });
+
+        self.downloads.write().insert(model_id.to_string(), download_id.clone());
+        tracing::info!("📦 Downloading model {} ({})", model_id, download_id);
+        Ok(download_id)
+    }
+
+    /// 删除模型（包括未完成的下载）
+    pub fn delete(&self, model_id: &str) -> Result<()> {
+        if let Some(download_id) = self.downloads.write().remove(model_id) {
+            DOWNLOADS.cancel(&download_id);
+        }
+
+        let path = self.model_path(model_id)?;
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        let mut part = path.into_os_string();
+        part.push(".part");
+        let _ = std::fs::remove_file(PathBuf::from(part));
+
+        tracing::info!("🗑️ Deleted model: {}", model_id);
+        Ok(())
+    }
+
+    /// 声明功能的模型需求
+    pub fn declare_requirement(&self, feature: &str, kind: ModelKind, models: &[&str]) {
+        let mut requirements = self.requirements.write();
+        requirements.retain(|r| r.feature != feature);
+        requirements.push(ModelRequirement {
+            feature: feature.to_string(),
+            kind,
+            models: models.iter().map(|m| m.to_string()).collect(),
+        });
+    }
+
+    /// 列出所有功能的模型需求
+    pub fn requirements(&self) -> Vec<ModelRequirement> {
+        self.requirements.read().clone()
+    }
+
+    /// 检查功能的模型需求，返回第一个已安装模型的路径
+    pub fn require(&self, feature: &str) -> Result<PathBuf> {
+        let requirement = self
+            .requirements
+            .read()
+            .iter()
+            .find(|r| r.feature == feature)
+            .cloned()
+            .ok_or_else(|| anyhow!("No model requirement declared for '{}'", feature))?;
+
+        requirement
+            .models
+            .iter()
+            .find(|id| self.is_installed(id))
+            .map(|id| self.model_path(id))
+            .transpose()?
+            .ok_or_else(|| {
+                anyhow!(
+                    "'{}' requires one of these models: {}",
+                    feature,
+                    requirement.models.join(", ")
+                )
+            })
+    }
+
+    /// 移除已结束的下载记录
+    fn cleanup_finished_downloads(&self) {
+        self.downloads.write().retain(|_, download_id| {
+            DOWNLOADS.get(download_id).map_or(false, |task| {
+                matches!(
+                    task.status,
+                    DownloadStatus::Pending | DownloadStatus::Downloading | DownloadStatus::Verifying
+                )
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_ids_unique() {
+        let mut ids: Vec<&str> = CATALOG.iter().map(|m| m.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), CATALOG.len());
+    }
+
+    #[test]
+    fn test_require_unknown_feature() {
+        let manager = ModelManager::new();
+        assert!(manager.require("nonexistent").is_err());
+        assert!(manager.model_path("nonexistent").is_err());
+        assert!(manager.model_path("whisper-base").unwrap().ends_with("whisper/ggml-base.bin"));
+    }
+}
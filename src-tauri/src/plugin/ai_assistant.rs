@@ -6,7 +6,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use reqwest::Client;
+use crate::http::RequestBuilderExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -53,7 +53,6 @@ pub struct AIAssistantPlugin {
     config: Arc<RwLock<AIConfig>>,
     conversations: Arc<RwLock<Vec<Conversation>>>,
     current_conversation: Arc<RwLock<Option<String>>>, // current conversation ID
-    client: Client,
     matcher: SkimMatcherV2,
 }
 
@@ -100,18 +99,21 @@ impl AIAssistantPlugin {
                     "linux".to_string(),
                 ],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Productivity).with_tags(&["ai", "chat"]),
             },
             config: Arc::new(RwLock::new(AIConfig::default())),
             conversations: Arc::new(RwLock::new(Vec::new())),
             current_conversation: Arc::new(RwLock::new(None)),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(60))
-                .build()
-                .expect("Failed to build HTTP client for AI Assistant"),
             matcher: SkimMatcherV2::default(),
         }
     }
 
+    /// 共享 HTTP 客户端（AI 响应较慢，使用更长超时）
+    fn client() -> Result<reqwest::Client> {
+        crate::http::client_with_timeout("ai_assistant", std::time::Duration::from_secs(60))
+    }
+
     /// 加载配置
     pub async fn load_config(&self, config: AIConfig) {
         let mut cfg = self.config.write().await;
@@ -246,13 +248,12 @@ impl AIAssistantPlugin {
             max_tokens: config.max_tokens,
         };
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(format!("{}/chat/completions", base_url))
             .header("Authorization", format!("Bearer {}", config.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
@@ -308,14 +309,13 @@ impl AIAssistantPlugin {
             max_tokens: config.max_tokens,
         };
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(format!("{}/messages", base_url))
             .header("x-api-key", &config.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
@@ -386,6 +386,9 @@ impl Plugin for AIAssistantPlugin {
                     prevent_hide: false,
                     hotkey: None,
                 }],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             }]);
         }
 
@@ -412,6 +415,9 @@ impl Plugin for AIAssistantPlugin {
                     prevent_hide: true, // 不隐藏窗口，等待响应
                     hotkey: None,
                 }],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
 
@@ -471,6 +477,9 @@ impl Plugin for AIAssistantPlugin {
                             hotkey: None,
                         },
                     ],
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
                 });
             }
         }
@@ -478,7 +487,7 @@ impl Plugin for AIAssistantPlugin {
         Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         match action_id {
             "send" => {
                 // 发送消息（实际处理由前端完成，这里只是占位）
@@ -498,7 +507,7 @@ impl Plugin for AIAssistantPlugin {
             _ => {}
         }
 
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
     }
 }
 
@@ -544,15 +553,14 @@ impl AIAssistantPlugin {
             max_tokens: config.max_tokens,
         };
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(format!("{}/chat/completions", base_url))
             .header("Authorization", format!("Bearer {}", config.api_key))
             .header("Content-Type", "application/json")
             .header("Editor-Version", "vscode/1.85.0")
             .header("Editor-Plugin-Version", "copilot/1.145.0")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
@@ -609,13 +617,12 @@ impl AIAssistantPlugin {
             max_tokens: config.max_tokens,
         };
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(format!("{}/chat/completions", base_url))
             .header("Authorization", format!("Bearer {}", config.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
@@ -689,12 +696,11 @@ impl AIAssistantPlugin {
             base_url, config.model, config.api_key
         );
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(&url)
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
@@ -745,12 +751,11 @@ impl AIAssistantPlugin {
             stream: false,
         };
 
-        let response = self
-            .client
+        let response = Self::client()?
             .post(format!("{}/api/chat", base_url))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_with_retry()
             .await?;
 
         if !response.status().is_success() {
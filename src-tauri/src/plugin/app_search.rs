@@ -1,29 +1,271 @@
 // 应用搜索插件
 
 use crate::core::types::*;
+use crate::plugin::jump_lists;
 use crate::plugin::Plugin;
 use anyhow::Result;
 use async_trait::async_trait;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// 插件配置中用户别名的字段名（应用路径 → 别名列表）
+pub const ALIASES_KEY: &str = "aliases";
+
+/// 展开应用的最近文档（跳转列表）
+const ACTION_RECENT_DOCUMENTS: &str = "recent_documents";
+
+/// 打开最近文档
+const ACTION_OPEN_DOCUMENT: &str = "open_document";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct App {
+    /// 显示名称（优先使用本地化名称）
     pub name: String,
     pub path: PathBuf,
     pub icon_path: Option<PathBuf>,
+    /// 自动识别的别名：英文/原始文件名、快捷方式目标程序名、其他语言的名称
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// 应用别名（供设置界面编辑）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppAliases {
+    pub name: String,
+    pub path: String,
+    pub aliases: Vec<String>,
+    pub user_aliases: Vec<String>,
 }
 
 pub struct AppSearchPlugin {
     metadata: PluginMetadata,
     apps: Arc<RwLock<Vec<App>>>,
+    /// 用户自定义别名（应用路径 → 别名）
+    user_aliases: Arc<RwLock<HashMap<String, Vec<String>>>>,
     matcher: SkimMatcherV2,
 }
 
+/// 追加别名（忽略空值及与名称/已有别名重复的项，不区分大小写）
+fn push_alias(aliases: &mut Vec<String>, name: &str, alias: &str) {
+    let alias = alias.trim();
+    if alias.is_empty()
+        || alias.eq_ignore_ascii_case(name)
+        || aliases.iter().any(|a| a.eq_ignore_ascii_case(alias))
+    {
+        return;
+    }
+    aliases.push(alias.to_string());
+}
+
+/// 解码 desktop.ini（开始菜单中通常为带 BOM 的 UTF-16LE）
+fn decode_ini(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// 解析 desktop.ini 的 [LocalizedFileNames] 段：文件名（小写）→ 本地化名称或资源引用
+fn parse_localized_file_names(content: &str) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let mut in_section = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line.eq_ignore_ascii_case("[LocalizedFileNames]");
+        } else if in_section {
+            if let Some((file, name)) = line.split_once('=') {
+                names.insert(file.trim().to_lowercase(), name.trim().to_string());
+            }
+        }
+    }
+    names
+}
+
+/// 解析本地化名称：@dll,-id 形式的资源引用需要系统加载，其余为字面名称
+fn resolve_localized_name(value: &str) -> Option<String> {
+    if !value.starts_with('@') {
+        return Some(value.to_string()).filter(|v| !v.is_empty());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::core::HSTRING;
+        use windows::Win32::UI::Shell::SHLoadIndirectString;
+
+        let mut buffer = [0u16; 512];
+        unsafe { SHLoadIndirectString(&HSTRING::from(value), &mut buffer, None).ok()? };
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len])).filter(|v| !v.is_empty())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    None
+}
+
+/// 读取目录下 desktop.ini 中的本地化文件名
+fn localized_names(dir: &Path) -> HashMap<String, String> {
+    let Ok(bytes) = std::fs::read(dir.join("desktop.ini")) else {
+        return HashMap::new();
+    };
+    parse_localized_file_names(&decode_ini(&bytes))
+        .into_iter()
+        .filter_map(|(file, value)| resolve_localized_name(&value).map(|name| (file, name)))
+        .collect()
+}
+
+/// 从 .lnk 文件中读取目标路径（MS-SHLLINK LinkInfo.LocalBasePath），
+/// 商店应用等“广告快捷方式”没有 LinkInfo，返回 None
+pub(crate) fn lnk_target(data: &[u8]) -> Option<String> {
+    const HEADER_SIZE: usize = 0x4C;
+    const HAS_TARGET_ID_LIST: u32 = 0x1;
+    const HAS_LINK_INFO: u32 = 0x2;
+    const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+
+    let u16_at = |offset: usize| data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let u32_at = |offset: usize| data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+    if u32_at(0)? != HEADER_SIZE as u32 {
+        return None;
+    }
+    let flags = u32_at(0x14)?;
+    if flags & HAS_LINK_INFO == 0 {
+        return None;
+    }
+
+    let mut offset = HEADER_SIZE;
+    if flags & HAS_TARGET_ID_LIST != 0 {
+        offset += 2 + u16_at(offset)?;
+    }
+
+    let link_info = offset;
+    let header_size = u32_at(link_info + 4)? as usize;
+    if u32_at(link_info + 8)? & VOLUME_ID_AND_LOCAL_BASE_PATH == 0 {
+        return None;
+    }
+
+    // 头部 >= 0x24 时包含 Unicode 路径
+    if header_size >= 0x24 {
+        let start = link_info + u32_at(link_info + 0x1C)? as usize;
+        let units: Vec<u16> = data
+            .get(start..)?
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|&c| c != 0)
+            .collect();
+        return Some(String::from_utf16_lossy(&units)).filter(|p| !p.is_empty());
+    }
+
+    let start = link_info + u32_at(link_info + 0x10)? as usize;
+    let bytes: Vec<u8> = data.get(start..)?.iter().copied().take_while(|&b| b != 0).collect();
+    Some(String::from_utf8_lossy(&bytes).into_owned()).filter(|p| !p.is_empty())
+}
+
+/// 应用实际指向的程序（快捷方式读取目标，其余为自身路径）
+fn app_target(path: &Path) -> PathBuf {
+    let is_lnk = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+    is_lnk
+        .then(|| std::fs::read(path).ok().and_then(|data| lnk_target(&data)))
+        .flatten()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// 最近文档二级列表中的结果（按列表顺序递减评分）
+fn recent_document_result(doc: jump_lists::RecentDocument, score: i32) -> QueryResult {
+    // 跳转列表记录的是 Windows 路径，按两种分隔符取文件名
+    let name = doc.path.rsplit(['\\', '/']).find(|part| !part.is_empty()).unwrap_or(&doc.path).to_string();
+    let subtitle = match chrono::DateTime::from_timestamp_millis(doc.last_access) {
+        Some(time) if !doc.pinned => format!(
+            "{} · {}",
+            time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            doc.path
+        ),
+        _ => doc.path.clone(),
+    };
+    let mut result = QueryResult::new(name)
+        .with_subtitle(subtitle)
+        .with_icon(WoxImage::emoji(if doc.pinned { "📌" } else { "📄" }))
+        .with_score(score)
+        .with_path(doc.path.clone())
+        .with_action(Action {
+            id: ACTION_OPEN_DOCUMENT.to_string(),
+            name: "Open".to_string(),
+            icon: None,
+            is_default: true,
+            prevent_hide: false,
+            hotkey: None,
+        });
+    result.id = doc.path;
+    result
+}
+
+/// 当前界面语言（如 zh_CN），来自 LC_ALL / LC_MESSAGES / LANG
+#[cfg(target_os = "linux")]
+fn current_locale() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| value.split(['.', '@']).next().unwrap_or_default().to_string())
+        .unwrap_or_default()
+}
+
+/// 解析 .desktop 文件：返回 (显示名称, 别名)
+/// 显示名称优先 Name[zh_CN] → Name[zh] → Name，其余语言的名称、GenericName 和 Exec 程序名作为别名
+fn parse_desktop_entry(content: &str, locale: &str) -> Option<(String, Vec<String>)> {
+    let mut in_entry = false;
+    let mut names: Vec<(String, String)> = Vec::new();
+    let mut extra = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=').filter(|_| in_entry) else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match key {
+            "NoDisplay" | "Hidden" if value.eq_ignore_ascii_case("true") => return None,
+            "Name" => names.push((String::new(), value.to_string())),
+            "GenericName" => extra.push(value.to_string()),
+            "Exec" => {
+                let program = value.split_whitespace().next().unwrap_or_default();
+                if let Some(stem) = Path::new(program).file_stem() {
+                    extra.push(stem.to_string_lossy().into_owned());
+                }
+            }
+            _ => {
+                if let Some(lang) = key.strip_prefix("Name[").and_then(|k| k.strip_suffix(']')) {
+                    names.push((lang.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+
+    let language = locale.split('_').next().unwrap_or_default();
+    let display = [locale, language, ""]
+        .iter()
+        .find_map(|lang| names.iter().find(|(l, _)| l == lang))
+        .map(|(_, name)| name.clone())?;
+
+    let mut aliases = Vec::new();
+    for alias in names.iter().map(|(_, name)| name).chain(extra.iter()) {
+        push_alias(&mut aliases, &display, alias);
+    }
+    Some((display, aliases))
+}
+
 impl AppSearchPlugin {
     pub fn new() -> Self {
         Self {
@@ -39,14 +281,23 @@ impl AppSearchPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Search).with_tags(&["apps", "launcher"]),
             },
             apps: Arc::new(RwLock::new(Vec::new())),
+            user_aliases: Arc::new(RwLock::new(HashMap::new())),
             matcher: SkimMatcherV2::default(),
         }
     }
     
     /// 初始化并加载应用
     pub async fn init(&self) {
+        if let Ok(storage) = crate::storage::StorageManager::new() {
+            if let Ok(config) = storage.get_plugin_config("app_search").await {
+                *self.user_aliases.write().await = Self::aliases_from_config(&config);
+            }
+        }
+        
         if let Ok(apps) = Self::scan_applications().await {
             let mut apps_guard = self.apps.write().await;
             *apps_guard = apps;
@@ -54,6 +305,52 @@ impl AppSearchPlugin {
         }
     }
     
+    /// 从插件配置读取用户别名
+    fn aliases_from_config(config: &serde_json::Value) -> HashMap<String, Vec<String>> {
+        config
+            .get(ALIASES_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+    
+    /// 全部应用及其别名
+    pub async fn list_aliases(&self) -> Vec<AppAliases> {
+        let user_aliases = self.user_aliases.read().await;
+        let mut apps: Vec<AppAliases> = self
+            .apps
+            .read()
+            .await
+            .iter()
+            .map(|app| {
+                let path = app.path.to_string_lossy().to_string();
+                AppAliases {
+                    name: app.name.clone(),
+                    aliases: app.aliases.clone(),
+                    user_aliases: user_aliases.get(&path).cloned().unwrap_or_default(),
+                    path,
+                }
+            })
+            .collect();
+        apps.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        apps
+    }
+    
+    /// 设置应用的用户别名，返回需要写回插件配置的完整别名表
+    pub async fn set_user_aliases(&self, path: &str, aliases: Vec<String>) -> serde_json::Value {
+        let mut cleaned = Vec::new();
+        for alias in &aliases {
+            push_alias(&mut cleaned, "", alias);
+        }
+        
+        let mut user_aliases = self.user_aliases.write().await;
+        if cleaned.is_empty() {
+            user_aliases.remove(path);
+        } else {
+            user_aliases.insert(path.to_string(), cleaned);
+        }
+        serde_json::to_value(&*user_aliases).unwrap_or_default()
+    }
+    
     /// 扫描系统应用
     async fn scan_applications() -> Result<Vec<App>> {
         #[cfg(target_os = "windows")]
@@ -148,6 +445,9 @@ impl AppSearchPlugin {
         }
         
         fn scan_recursive(dir: &PathBuf, extension: &str, apps: &mut Vec<App>) -> Result<()> {
+            // 🔥 开始菜单目录的 desktop.ini 记录了快捷方式的本地化名称（如 Calculator.lnk → 计算器）
+            let localized = localized_names(dir);
+            
             if let Ok(entries) = fs::read_dir(dir) {
                 for entry in entries.flatten() {
                     let path = entry.path();
@@ -156,12 +456,8 @@ impl AppSearchPlugin {
                         let _ = scan_recursive(&path, extension, apps);
                     } else if let Some(ext) = path.extension() {
                         if ext.to_string_lossy().to_lowercase() == extension.trim_start_matches('.') {
-                            if let Some(name) = path.file_stem() {
-                                apps.push(App {
-                                    name: name.to_string_lossy().to_string(),
-                                    path: path.clone(),
-                                    icon_path: None,
-                                });
+                            if let Some(app) = AppSearchPlugin::load_app(&path, &localized) {
+                                apps.push(app);
                             }
                         }
                     }
@@ -173,6 +469,39 @@ impl AppSearchPlugin {
         scan_recursive(dir, extension, &mut apps)?;
         Ok(apps)
     }
+    
+    /// 读取应用名称和别名：本地化名称作为显示名称，原始文件名、目标程序名等作为别名
+    fn load_app(path: &Path, localized: &HashMap<String, String>) -> Option<App> {
+        let stem = path.file_stem()?.to_string_lossy().to_string();
+        let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        
+        let (name, mut aliases) = if extension == "desktop" {
+            let content = std::fs::read_to_string(path).ok()?;
+            #[cfg(target_os = "linux")]
+            let locale = current_locale();
+            #[cfg(not(target_os = "linux"))]
+            let locale = String::new();
+            parse_desktop_entry(&content, &locale)?
+        } else {
+            (localized.get(&file_name).cloned().unwrap_or_else(|| stem.clone()), Vec::new())
+        };
+        
+        push_alias(&mut aliases, &name, &stem);
+        if extension == "lnk" {
+            let target = std::fs::read(path).ok().and_then(|data| lnk_target(&data));
+            if let Some(target_stem) = target.as_deref().and_then(|t| Path::new(t).file_stem()) {
+                push_alias(&mut aliases, &name, &target_stem.to_string_lossy());
+            }
+        }
+        
+        Some(App {
+            name,
+            path: path.to_path_buf(),
+            icon_path: None,
+            aliases,
+        })
+    }
 }
 
 #[async_trait]
@@ -189,18 +518,26 @@ impl Plugin for AppSearchPlugin {
         }
         
         let apps = self.apps.read().await;
+        let user_aliases = self.user_aliases.read().await;
         let mut results = Vec::new();
         
         for app in apps.iter() {
-            if let Some(score) = self.matcher.fuzzy_match(&app.name, query) {
+            let path = app.path.to_string_lossy().to_string();
+            // 名称和任一别名（自动识别 + 用户自定义）匹配即可，取最高分
+            let score = std::iter::once(&app.name)
+                .chain(app.aliases.iter())
+                .chain(user_aliases.get(&path).into_iter().flatten())
+                .filter_map(|candidate| self.matcher.fuzzy_match(candidate, query))
+                .max();
+            if let Some(score) = score {
                 results.push(QueryResult {
-                    id: app.path.to_string_lossy().to_string(),
+                    id: path,
                     title: app.name.clone(),
                     subtitle: app.path.to_string_lossy().to_string(),
                     icon: WoxImage::emoji("📦"),
                     preview: None,
                     score: score as i32,
-                    context_data: serde_json::Value::Null,
+                    context_data: serde_json::json!({ "path": app.path.to_string_lossy() }),
                     group: None,
                     plugin_id: self.metadata.id.clone(),
                     refreshable: false,
@@ -214,6 +551,9 @@ impl Plugin for AppSearchPlugin {
                             hotkey: None,
                         }
                     ],
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
                 });
             }
         }
@@ -222,11 +562,52 @@ impl Plugin for AppSearchPlugin {
         results.sort_by(|a, b| b.score.cmp(&a.score));
         results.truncate(10); // 只返回前 10 个结果
         
+        // 有跳转列表的应用可按 Tab 展开最近文档
+        for result in &mut results {
+            if jump_lists::has_recent_documents(&app_target(Path::new(&result.id))) {
+                result.actions.push(Action {
+                    id: ACTION_RECENT_DOCUMENTS.to_string(),
+                    name: "Recent Documents".to_string(),
+                    icon: Some(WoxImage::emoji("🕘")),
+                    is_default: false,
+                    prevent_hide: true,
+                    hotkey: Some("Tab".to_string()),
+                });
+            }
+        }
+        
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
-        if action_id == "open" {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        if action_id == ACTION_RECENT_DOCUMENTS {
+            let name = self
+                .apps
+                .read()
+                .await
+                .iter()
+                .find(|app| app.path.to_string_lossy() == result_id)
+                .map(|app| app.name.clone())
+                .unwrap_or_else(|| result_id.to_string());
+            let target = app_target(Path::new(result_id));
+            let documents = tokio::task::spawn_blocking(move || {
+                jump_lists::recent_documents(&target, jump_lists::MAX_DOCUMENTS)
+            })
+            .await??;
+            if documents.is_empty() {
+                return Ok(ExecuteOutcome::message(format!("No recent documents for {}", name)));
+            }
+            
+            let count = documents.len() as i32;
+            let results = documents
+                .into_iter()
+                .enumerate()
+                .map(|(i, doc)| recent_document_result(doc, count - i as i32))
+                .collect();
+            return Ok(ExecuteOutcome::follow_up(name, serde_json::json!({ "app": result_id }), results));
+        }
+        
+        if action_id == "open" || action_id == ACTION_OPEN_DOCUMENT {
             #[cfg(target_os = "windows")]
             {
                 // 🔥 使用 CREATE_NO_WINDOW 标志隐藏控制台窗口
@@ -253,10 +634,128 @@ impl Plugin for AppSearchPlugin {
                     .spawn()?;
             }
             
-            tracing::info!("Opened application: {}", result_id);
-            Ok(())
+            tracing::info!("Opened {}: {}", if action_id == "open" { "application" } else { "document" }, result_id);
+            Ok(ExecuteOutcome::Hide)
         } else {
             Err(anyhow::anyhow!("Unknown action"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localized_file_names_utf16() {
+        let text = "\r\n[.ShellClassInfo]\r\nLocalizedResourceName=@%SystemRoot%\\system32\\shell32.dll,-21787\r\n\
+                    [LocalizedFileNames]\r\nCalculator.lnk=计算器\r\nNotepad.lnk=@%SystemRoot%\\system32\\shell32.dll,-22563\r\n";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(|c| c.to_le_bytes()));
+
+        let names = parse_localized_file_names(&decode_ini(&bytes));
+        assert_eq!(names.len(), 2);
+        assert_eq!(names["calculator.lnk"], "计算器");
+        assert!(names["notepad.lnk"].starts_with('@'));
+        assert_eq!(resolve_localized_name("计算器").as_deref(), Some("计算器"));
+    }
+
+    #[test]
+    fn test_lnk_target() {
+        let path = "C:\\Windows\\System32\\calc.exe";
+        let mut data = vec![0u8; 0x4C];
+        data[0] = 0x4C;
+        data[0x14] = 0x3; // HasLinkTargetIDList | HasLinkInfo
+        // IDList：长度 4 + 4 字节内容
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        // LinkInfo：头部 0x1C，LocalBasePath 紧随其后
+        let mut link_info = vec![0u8; 0x1C];
+        link_info[4..8].copy_from_slice(&0x1Cu32.to_le_bytes());
+        link_info[8..12].copy_from_slice(&1u32.to_le_bytes());
+        link_info[0x10..0x14].copy_from_slice(&0x1Cu32.to_le_bytes());
+        link_info.extend_from_slice(path.as_bytes());
+        link_info.push(0);
+        data.extend_from_slice(&link_info);
+
+        assert_eq!(lnk_target(&data).as_deref(), Some(path));
+
+        // 广告快捷方式（无 LinkInfo）和截断数据
+        data[0x14] = 0x1;
+        assert_eq!(lnk_target(&data), None);
+        assert_eq!(lnk_target(&data[..0x20]), None);
+        assert_eq!(lnk_target(b"not a link"), None);
+    }
+
+    #[test]
+    fn test_desktop_entry() {
+        let content = "[Desktop Entry]\nName=Files\nName[zh_CN]=文件\nName[de]=Dateien\n\
+                       GenericName=File Manager\nExec=/usr/bin/nautilus --new-window %U\n\
+                       [Desktop Action new-window]\nName=New Window\n";
+
+        let (name, aliases) = parse_desktop_entry(content, "zh_CN").unwrap();
+        assert_eq!(name, "文件");
+        assert_eq!(aliases, vec!["Files", "Dateien", "File Manager", "nautilus"]);
+
+        let (name, _) = parse_desktop_entry(content, "de_DE").unwrap();
+        assert_eq!(name, "Dateien");
+        let (name, _) = parse_desktop_entry(content, "").unwrap();
+        assert_eq!(name, "Files");
+
+        assert!(parse_desktop_entry("[Desktop Entry]\nName=Hidden\nNoDisplay=true\n", "").is_none());
+    }
+
+    fn ctx(search: &str) -> QueryContext {
+        QueryContext {
+            query_type: QueryType::Input,
+            trigger_keyword: String::new(),
+            command: None,
+            search: search.to_string(),
+            raw_query: search.to_string(),
+            cancel: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_matches_aliases() {
+        let plugin = AppSearchPlugin::new();
+        *plugin.apps.write().await = vec![App {
+            name: "计算器".to_string(),
+            path: PathBuf::from("C:\\Start Menu\\Calculator.lnk"),
+            icon_path: None,
+            aliases: vec!["Calculator".to_string(), "calc".to_string()],
+        }];
+
+        assert_eq!(plugin.query(&ctx("计算")).await.unwrap().len(), 1);
+        assert_eq!(plugin.query(&ctx("calc")).await.unwrap().len(), 1);
+        assert!(plugin.query(&ctx("jsq")).await.unwrap().is_empty());
+
+        let config = plugin
+            .set_user_aliases("C:\\Start Menu\\Calculator.lnk", vec!["jsq".into(), " JSQ ".into(), "".into()])
+            .await;
+        assert_eq!(config["C:\\Start Menu\\Calculator.lnk"], serde_json::json!(["jsq"]));
+        assert_eq!(plugin.query(&ctx("jsq")).await.unwrap().len(), 1);
+        assert_eq!(plugin.list_aliases().await[0].user_aliases, vec!["jsq"]);
+    }
+
+    #[test]
+    fn test_recent_document_result() {
+        let doc = jump_lists::RecentDocument {
+            path: r"C:\docs\report.docx".to_string(),
+            last_access: 1_700_000_000_000,
+            pinned: true,
+        };
+        let result = recent_document_result(doc, 5);
+        assert_eq!(result.id, r"C:\docs\report.docx");
+        assert_eq!(result.title, "report.docx");
+        assert_eq!(result.subtitle, r"C:\docs\report.docx");
+        assert_eq!(result.path(), Some(r"C:\docs\report.docx"));
+        assert_eq!(result.default_action().map(|a| a.id.as_str()), Some(ACTION_OPEN_DOCUMENT));
+    }
+
+    #[test]
+    fn test_app_target_without_shortcut() {
+        let path = Path::new("/usr/share/applications/code.desktop");
+        assert_eq!(app_target(path), path);
+    }
+}
@@ -44,6 +44,26 @@ pub enum AuditEventType {
         old_level: String,
         new_level: String,
     },
+    /// 查询超时（超过沙盒配置的 timeout_ms 被中止）
+    QueryTimeout {
+        plugin_id: String,
+        timeout_ms: u64,
+    },
+    /// 结束进程（elevated 表示通过管理员权限重试）
+    ProcessTermination {
+        plugin_id: String,
+        pid: u32,
+        process_name: String,
+        mode: String,
+        elevated: bool,
+        success: bool,
+    },
+    /// 剪贴板内容来自忽略列表中的应用，未记录到历史
+    ClipboardCaptureSkipped {
+        plugin_id: String,
+        process_name: String,
+        rule: String,
+    },
 }
 
 /// 审计日志条目
@@ -123,7 +143,10 @@ impl AuditLogger {
                 | AuditEventType::NetworkAccess { plugin_id: id, .. }
                 | AuditEventType::ProgramExecution { plugin_id: id, .. }
                 | AuditEventType::ViolationAttempt { plugin_id: id, .. }
-                | AuditEventType::ConfigChange { plugin_id: id, .. } => id == plugin_id,
+                | AuditEventType::ConfigChange { plugin_id: id, .. }
+                | AuditEventType::QueryTimeout { plugin_id: id, .. }
+                | AuditEventType::ProcessTermination { plugin_id: id, .. }
+                | AuditEventType::ClipboardCaptureSkipped { plugin_id: id, .. } => id == plugin_id,
             })
             .cloned()
             .collect()
@@ -181,6 +204,9 @@ impl AuditLogger {
                 AuditEventType::ViolationAttempt { .. } => {
                     stats.violations += 1;
                 }
+                AuditEventType::QueryTimeout { .. } => {
+                    stats.timeouts += 1;
+                }
                 _ => {}
             }
         }
@@ -205,6 +231,8 @@ pub struct AuditStatistics {
     pub network_accesses: usize,
     pub denied_network_accesses: usize,
     pub violations: usize,
+    #[serde(default)]
+    pub timeouts: usize,
 }
 
 #[cfg(test)]
@@ -1,231 +1,298 @@
 // 浏览器书签和历史记录搜索插件
+// 合并所有已安装浏览器（Chromium 系各配置文件 + Firefox）的书签和历史记录，
+// 监听数据文件变化，下次查询时在后台重新加载（频繁写入的历史数据库按最小间隔节流）；
+// tab 模式通过 DevTools 协议列出开启远程调试的浏览器中已打开的标签页，执行时切换过去
 
+use crate::core::clock::{self, SharedClock};
 use crate::core::types::*;
+use crate::plugin::browser_profiles::{self, Bookmark, BrowserProfile, HistoryEntry};
+use crate::plugin::browser_tabs::{self, BrowserTab};
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use rusqlite::Connection;
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::sync::Arc;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Bookmark {
-    title: String,
-    url: String,
-    folder: String,
-}
+/// 合并后保留的历史记录数
+const MAX_HISTORY: usize = 1000;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct HistoryEntry {
-    title: String,
-    url: String,
-    visit_count: i32,
-    last_visit_time: i64,
-}
+/// 数据文件变化后两次重新加载的最小间隔（秒）
+const MIN_RELOAD_INTERVAL_SECS: i64 = 30;
+
+/// 插件配置中远程调试端口的字段名（逗号分隔）
+pub const DEVTOOLS_PORTS_KEY: &str = "devtools_ports";
 
 pub struct BrowserPlugin {
     metadata: PluginMetadata,
-    bookmarks: Arc<RwLock<Vec<Bookmark>>>,
-    history: Arc<RwLock<Vec<HistoryEntry>>>,
+    index: Arc<BrowserIndex>,
+    /// 查询标签页的调试端口
+    devtools_ports: parking_lot::RwLock<Vec<u16>>,
+    /// 最近一次列出的标签页（切换失败时按 URL 重新打开、复制链接）
+    tabs: parking_lot::RwLock<Vec<BrowserTab>>,
 }
 
-impl BrowserPlugin {
-    pub fn new() -> Self {
+/// 书签/历史索引（监听回调持有弱引用）
+struct BrowserIndex {
+    bookmarks: RwLock<Vec<Bookmark>>,
+    history: RwLock<Vec<HistoryEntry>>,
+    gate: ReloadGate,
+    watcher: parking_lot::Mutex<Option<RecommendedWatcher>>,
+}
+
+/// 重新加载节流：数据文件变化后标记为待更新，距上次加载超过最小间隔才允许再次加载
+struct ReloadGate {
+    dirty: AtomicBool,
+    last_reload: parking_lot::Mutex<Option<DateTime<Utc>>>,
+    clock: SharedClock,
+}
+
+impl ReloadGate {
+    fn new(clock: SharedClock) -> Self {
         Self {
-            metadata: PluginMetadata {
-                id: "browser".to_string(),
-                name: "浏览器".to_string(),
-                description: "搜索浏览器书签和历史记录".to_string(),
-                icon: WoxImage::Emoji("🌐".to_string()),
-                version: "1.0.0".to_string(),
-                author: "iLauncher".to_string(),
-                trigger_keywords: vec!["bm".to_string(), "his".to_string()],
-                commands: vec![],
-                settings: vec![],
-                supported_os: vec!["windows".to_string()],
-                plugin_type: PluginType::Native,
-            },
-            bookmarks: Arc::new(RwLock::new(Vec::new())),
-            history: Arc::new(RwLock::new(Vec::new())),
+            dirty: AtomicBool::new(false),
+            last_reload: parking_lot::Mutex::new(None),
+            clock,
         }
     }
 
-    pub async fn init(&self) {
-        tracing::info!("Initializing browser plugin...");
-        
-        // 加载书签
-        if let Err(e) = self.load_bookmarks().await {
-            tracing::warn!("Failed to load bookmarks: {}", e);
-        }
-        
-        // 加载历史记录
-        if let Err(e) = self.load_history().await {
-            tracing::warn!("Failed to load history: {}", e);
-        }
-        
-        let bookmark_count = self.bookmarks.read().await.len();
-        let history_count = self.history.read().await.len();
-        tracing::info!("Browser plugin initialized: {} bookmarks, {} history entries", bookmark_count, history_count);
+    fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
     }
 
-    async fn load_bookmarks(&self) -> Result<()> {
-        let mut all_bookmarks = Vec::new();
-
-        // Chrome 书签
-        if let Ok(bookmarks) = self.load_chrome_bookmarks().await {
-            all_bookmarks.extend(bookmarks);
-        }
-
-        // Edge 书签
-        if let Ok(bookmarks) = self.load_edge_bookmarks().await {
-            all_bookmarks.extend(bookmarks);
-        }
-
-        *self.bookmarks.write().await = all_bookmarks;
-        Ok(())
+    /// 记录一次加载
+    fn record_reload(&self) {
+        *self.last_reload.lock() = Some(self.clock.now());
     }
 
-    async fn load_chrome_bookmarks(&self) -> Result<Vec<Bookmark>> {
-        let local_data = dirs::data_local_dir().ok_or_else(|| anyhow::anyhow!("No local data dir"))?;
-        let bookmark_path = local_data.join("Google\\Chrome\\User Data\\Default\\Bookmarks");
-        
-        if !bookmark_path.exists() {
-            return Ok(Vec::new());
+    /// 是否需要重新加载（返回 true 时同时记录本次加载，避免并发查询重复触发）
+    fn try_begin(&self) -> bool {
+        if !self.dirty.load(Ordering::Relaxed) {
+            return false;
         }
-
-        let content = std::fs::read_to_string(&bookmark_path)?;
-        let json: serde_json::Value = serde_json::from_str(&content)?;
-        
-        let mut bookmarks = Vec::new();
-        self.parse_bookmark_folder(&json["roots"]["bookmark_bar"], "书签栏", &mut bookmarks);
-        self.parse_bookmark_folder(&json["roots"]["other"], "其他书签", &mut bookmarks);
-        
-        tracing::info!("Loaded {} Chrome bookmarks", bookmarks.len());
-        Ok(bookmarks)
-    }
-
-    async fn load_edge_bookmarks(&self) -> Result<Vec<Bookmark>> {
-        let local_data = dirs::data_local_dir().ok_or_else(|| anyhow::anyhow!("No local data dir"))?;
-        let bookmark_path = local_data.join("Microsoft\\Edge\\User Data\\Default\\Bookmarks");
-        
-        if !bookmark_path.exists() {
-            return Ok(Vec::new());
+        let now = self.clock.now();
+        let mut last_reload = self.last_reload.lock();
+        if last_reload.is_some_and(|last| now - last < chrono::Duration::seconds(MIN_RELOAD_INTERVAL_SECS)) {
+            return false;
         }
-
-        let content = std::fs::read_to_string(&bookmark_path)?;
-        let json: serde_json::Value = serde_json::from_str(&content)?;
-        
-        let mut bookmarks = Vec::new();
-        self.parse_bookmark_folder(&json["roots"]["bookmark_bar"], "书签栏", &mut bookmarks);
-        self.parse_bookmark_folder(&json["roots"]["other"], "其他书签", &mut bookmarks);
-        
-        tracing::info!("Loaded {} Edge bookmarks", bookmarks.len());
-        Ok(bookmarks)
+        *last_reload = Some(now);
+        self.dirty.store(false, Ordering::Relaxed);
+        true
     }
+}
 
-    fn parse_bookmark_folder(&self, node: &serde_json::Value, folder: &str, bookmarks: &mut Vec<Bookmark>) {
-        if let Some(node_type) = node["type"].as_str() {
-            if node_type == "url" {
-                if let (Some(title), Some(url)) = (node["name"].as_str(), node["url"].as_str()) {
-                    bookmarks.push(Bookmark {
-                        title: title.to_string(),
-                        url: url.to_string(),
-                        folder: folder.to_string(),
-                    });
+impl BrowserIndex {
+    /// 重新枚举配置文件并加载全部书签和历史记录，然后更新文件监听
+    async fn reload(self: Arc<Self>) {
+        self.gate.record_reload();
+        let roots = browser_profiles::browser_roots();
+        let scan_roots = roots.clone();
+        let loaded = tokio::task::spawn_blocking(move || {
+            let profiles = browser_profiles::discover(&scan_roots);
+            let mut bookmarks = Vec::new();
+            let mut history = Vec::new();
+            for profile in &profiles {
+                match browser_profiles::read_bookmarks(profile) {
+                    Ok(items) => bookmarks.extend(items),
+                    Err(e) => tracing::warn!("Failed to load {} bookmarks: {}", profile.display_name(), e),
                 }
-            } else if node_type == "folder" {
-                if let Some(children) = node["children"].as_array() {
-                    let folder_name = node["name"].as_str().unwrap_or(folder);
-                    for child in children {
-                        self.parse_bookmark_folder(child, folder_name, bookmarks);
-                    }
+                match browser_profiles::read_history(profile) {
+                    Ok(items) => history.extend(items),
+                    Err(e) => tracing::warn!("Failed to load {} history: {}", profile.display_name(), e),
                 }
             }
-        }
+            (profiles, browser_profiles::merge_bookmarks(bookmarks), browser_profiles::merge_history(history, MAX_HISTORY))
+        })
+        .await;
+
+        let Ok((profiles, bookmarks, history)) = loaded else {
+            tracing::warn!("Browser data reload task failed");
+            return;
+        };
+        tracing::info!(
+            "Browser data loaded: {} profiles, {} bookmarks, {} history entries",
+            profiles.len(),
+            bookmarks.len(),
+            history.len()
+        );
+
+        self.watch(&roots, &profiles);
+        *self.bookmarks.write().await = bookmarks;
+        *self.history.write().await = history;
     }
 
-    async fn load_history(&self) -> Result<()> {
-        let mut all_history = Vec::new();
+    /// 监听数据根目录（新配置文件）和各配置文件目录（书签/历史变化）
+    fn watch(self: &Arc<Self>, roots: &[(browser_profiles::BrowserKind, std::path::PathBuf)], profiles: &[BrowserProfile]) {
+        let index: Weak<Self> = Arc::downgrade(self);
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let relevant = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+                && event.paths.iter().any(|path| browser_profiles::is_source_file(path));
+            if relevant {
+                if let Some(index) = index.upgrade() {
+                    index.gate.mark_dirty();
+                }
+            }
+        });
 
-        // Chrome 历史
-        if let Ok(history) = self.load_chrome_history().await {
-            all_history.extend(history);
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!("Failed to create browser data watcher: {}", e);
+                return;
+            }
+        };
+        for dir in browser_profiles::watch_dirs(roots, profiles) {
+            if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                tracing::debug!("Failed to watch {}: {}", dir.display(), e);
+            }
         }
+        *self.watcher.lock() = Some(watcher);
+    }
+}
 
-        // Edge 历史
-        if let Ok(history) = self.load_edge_history().await {
-            all_history.extend(history);
+impl BrowserPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata {
+                id: "browser".to_string(),
+                name: "浏览器".to_string(),
+                description: "搜索浏览器书签、历史记录和已打开的标签页".to_string(),
+                icon: WoxImage::Emoji("🌐".to_string()),
+                version: "1.0.0".to_string(),
+                author: "iLauncher".to_string(),
+                trigger_keywords: vec!["bm".to_string(), "his".to_string(), "tab".to_string()],
+                commands: vec![],
+                settings: vec![SettingDefinition {
+                    r#type: "text".to_string(),
+                    key: Some(DEVTOOLS_PORTS_KEY.to_string()),
+                    label: Some("标签页搜索的远程调试端口（逗号分隔；浏览器需以 --remote-debugging-port=端口 启动）".to_string()),
+                    value: Some(serde_json::json!(browser_tabs::DEFAULT_PORT.to_string())),
+                }],
+                supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
+                plugin_type: PluginType::Native,
+                // tab 模式空搜索列出全部标签页，书签/历史的空搜索在 query 中直接返回
+                query_policy: QueryPolicy::new(0, 100),
+                branding: PluginBranding::new(PluginCategory::Search).with_tags(&["bookmarks", "history", "tabs"]),
+            },
+            index: Arc::new(BrowserIndex {
+                bookmarks: RwLock::new(Vec::new()),
+                history: RwLock::new(Vec::new()),
+                gate: ReloadGate::new(clock::system()),
+                watcher: parking_lot::Mutex::new(None),
+            }),
+            devtools_ports: parking_lot::RwLock::new(vec![browser_tabs::DEFAULT_PORT]),
+            tabs: parking_lot::RwLock::new(Vec::new()),
         }
-
-        // 按访问次数和时间排序
-        all_history.sort_by(|a, b| {
-            b.visit_count.cmp(&a.visit_count)
-                .then(b.last_visit_time.cmp(&a.last_visit_time))
-        });
-
-        // 只保留前 1000 条
-        all_history.truncate(1000);
-
-        *self.history.write().await = all_history;
-        Ok(())
     }
 
-    async fn load_chrome_history(&self) -> Result<Vec<HistoryEntry>> {
-        let local_data = dirs::data_local_dir().ok_or_else(|| anyhow::anyhow!("No local data dir"))?;
-        let history_path = local_data.join("Google\\Chrome\\User Data\\Default\\History");
-        
-        self.load_history_from_db(&history_path).await
+    pub async fn init(&self) {
+        tracing::info!("Initializing browser plugin...");
+        if let Ok(storage) = crate::storage::StorageManager::new() {
+            if let Ok(config) = storage.get_plugin_config(&self.metadata.id).await {
+                self.configure(&config);
+            }
+        }
+        self.index.clone().reload().await;
     }
 
-    async fn load_edge_history(&self) -> Result<Vec<HistoryEntry>> {
-        let local_data = dirs::data_local_dir().ok_or_else(|| anyhow::anyhow!("No local data dir"))?;
-        let history_path = local_data.join("Microsoft\\Edge\\User Data\\Default\\History");
-        
-        self.load_history_from_db(&history_path).await
+    /// 应用插件配置（保存配置后即时生效）
+    pub fn configure(&self, config: &serde_json::Value) {
+        let ports = config
+            .get(DEVTOOLS_PORTS_KEY)
+            .map(|v| match v {
+                serde_json::Value::Number(n) => n.to_string(),
+                other => other.as_str().unwrap_or_default().to_string(),
+            })
+            .map(|v| browser_tabs::parse_ports(&v))
+            .filter(|ports| !ports.is_empty())
+            .unwrap_or_else(|| vec![browser_tabs::DEFAULT_PORT]);
+        *self.devtools_ports.write() = ports;
     }
 
-    async fn load_history_from_db(&self, db_path: &PathBuf) -> Result<Vec<HistoryEntry>> {
-        if !db_path.exists() {
-            return Ok(Vec::new());
+    /// 列出已打开的标签页（空搜索按浏览器中的顺序全部列出）
+    async fn query_tabs(&self, search_term: &str) -> Vec<QueryResult> {
+        let ports = self.devtools_ports.read().clone();
+        let tabs = browser_tabs::list_tabs(&ports).await;
+        *self.tabs.write() = tabs.clone();
+
+        if tabs.is_empty() {
+            let flag = format!("--remote-debugging-port={}", ports[0]);
+            let mut hint = QueryResult::new("未找到开启远程调试的浏览器")
+                .with_subtitle(format!("以 {} 启动 Chrome / Edge 后可搜索已打开的标签页", flag))
+                .with_icon(WoxImage::emoji("🗂️"))
+                .with_action(Action {
+                    id: "copy_flag".to_string(),
+                    name: "复制启动参数".to_string(),
+                    icon: None,
+                    is_default: true,
+                    hotkey: None,
+                    prevent_hide: false,
+                });
+            hint.id = flag;
+            hint.plugin_id = self.metadata.id.clone();
+            return vec![hint];
         }
 
-        // 复制数据库到临时文件（避免锁定）
-        let temp_path = std::env::temp_dir().join(format!("ilauncher_history_{}.db", uuid::Uuid::new_v4()));
-        std::fs::copy(db_path, &temp_path)?;
-
-        let conn = Connection::open(&temp_path)?;
-        let mut stmt = conn.prepare(
-            "SELECT title, url, visit_count, last_visit_time 
-             FROM urls 
-             WHERE visit_count > 0 
-             ORDER BY visit_count DESC, last_visit_time DESC 
-             LIMIT 500"
-        )?;
-
-        let history_iter = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                title: row.get(0).unwrap_or_default(),
-                url: row.get(1)?,
-                visit_count: row.get(2)?,
-                last_visit_time: row.get(3)?,
+        let matcher = SkimMatcherV2::default();
+        let total = tabs.len() as i64;
+        let mut results: Vec<QueryResult> = tabs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, tab)| {
+                let score = if search_term.is_empty() {
+                    total - i as i64
+                } else {
+                    let title_score = matcher.fuzzy_match(&tab.title, search_term).unwrap_or(0);
+                    let url_score = matcher.fuzzy_match(&tab.url, search_term).unwrap_or(0);
+                    title_score.max(url_score)
+                };
+                if !search_term.is_empty() && score <= 30 {
+                    return None;
+                }
+                let subtitle = if ports.len() > 1 {
+                    format!("🔌 {} | {}", tab.port, tab.url)
+                } else {
+                    tab.url.clone()
+                };
+                let mut result = QueryResult::new(tab.title.clone())
+                    .with_subtitle(subtitle)
+                    .with_icon(WoxImage::emoji("🗂️"))
+                    .with_score(score as i32)
+                    .with_action(Action {
+                        id: "activate_tab".to_string(),
+                        name: "切换到标签页".to_string(),
+                        icon: None,
+                        is_default: true,
+                        hotkey: None,
+                        prevent_hide: false,
+                    })
+                    .with_action(Action {
+                        id: "copy_tab_url".to_string(),
+                        name: "复制链接".to_string(),
+                        icon: None,
+                        is_default: false,
+                        hotkey: None,
+                        prevent_hide: false,
+                    });
+                result.id = tab.result_id();
+                result.plugin_id = self.metadata.id.clone();
+                Some(result)
             })
-        })?;
-
-        let mut history = Vec::new();
-        for entry in history_iter {
-            if let Ok(entry) = entry {
-                history.push(entry);
-            }
-        }
-
-        // 清理临时文件
-        let _ = std::fs::remove_file(&temp_path);
+            .collect();
+        results.sort_by(|a, b| b.score.cmp(&a.score));
+        results.truncate(20);
+        results
+    }
 
-        tracing::info!("Loaded {} history entries from {:?}", history.len(), db_path);
-        Ok(history)
+    /// 最近一次列出的标签页中的链接
+    fn tab_url(&self, result_id: &str) -> Option<String> {
+        self.tabs.read().iter().find(|tab| tab.result_id() == result_id).map(|tab| tab.url.clone())
     }
 }
 
@@ -236,29 +303,31 @@ impl crate::plugin::Plugin for BrowserPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.trim();
-        
-        if query.is_empty() {
+        // 检查是否有触发词（必须经触发词路由才激活）
+        let (search_bookmarks, search_history) = match ctx.trigger_keyword.as_str() {
+            "bm" => (true, false),
+            "his" => (false, true),
+            "tab" => return Ok(self.query_tabs(ctx.search.trim()).await),
+            // 没有触发词，不激活插件
+            _ => return Ok(Vec::new()),
+        };
+        let search_term = ctx.search.trim();
+        if search_term.is_empty() {
             return Ok(Vec::new());
         }
 
+        // 🔄 数据文件有变化时后台重新加载（本次查询仍使用当前数据）
+        if self.index.gate.try_begin() {
+            tokio::spawn(self.index.clone().reload());
+        }
+
         let matcher = SkimMatcherV2::default();
         let mut results = Vec::new();
 
-        // 检查是否有触发词（必须有前缀才激活）
-        let (search_bookmarks, search_history, search_term) = if query.starts_with("bm ") {
-            (true, false, &query[3..])
-        } else if query.starts_with("his ") {
-            (false, true, &query[4..])
-        } else {
-            // 没有触发词，不激活插件
-            return Ok(Vec::new());
-        };
-
         // 搜索书签
         if search_bookmarks {
-            let bookmarks = self.bookmarks.read().await;
-            for bookmark in bookmarks.iter().take(100) {
+            let bookmarks = self.index.bookmarks.read().await;
+            for bookmark in bookmarks.iter() {
                 let title_score = matcher.fuzzy_match(&bookmark.title, search_term).unwrap_or(0);
                 let url_score = matcher.fuzzy_match(&bookmark.url, search_term).unwrap_or(0);
                 let score = title_score.max(url_score);
@@ -268,8 +337,8 @@ impl crate::plugin::Plugin for BrowserPlugin {
                         id: bookmark.url.clone(),
                         plugin_id: self.metadata.id.clone(),
                         title: bookmark.title.clone(),
-                        subtitle: format!("📁 {} | {}", bookmark.folder, bookmark.url),
-                        icon: WoxImage::emoji("🔖".to_string()),
+                        subtitle: format!("{} · 📁 {} | {}", bookmark.profile, bookmark.folder, bookmark.url),
+                        icon: WoxImage::emoji(bookmark.browser.emoji()),
                         score: score as i32,
                         context_data: serde_json::to_value(&bookmark)?,
                         actions: vec![
@@ -293,6 +362,9 @@ impl crate::plugin::Plugin for BrowserPlugin {
                         preview: None,
                         refreshable: false,
                         group: None,
+                        accessibility: None,
+                        highlights: None,
+                        badges: Vec::new(),
                     });
                 }
             }
@@ -300,8 +372,8 @@ impl crate::plugin::Plugin for BrowserPlugin {
 
         // 搜索历史记录
         if search_history {
-            let history = self.history.read().await;
-            for entry in history.iter().take(100) {
+            let history = self.index.history.read().await;
+            for entry in history.iter() {
                 let title_score = matcher.fuzzy_match(&entry.title, search_term).unwrap_or(0);
                 let url_score = matcher.fuzzy_match(&entry.url, search_term).unwrap_or(0);
                 let score = title_score.max(url_score);
@@ -311,8 +383,8 @@ impl crate::plugin::Plugin for BrowserPlugin {
                         id: entry.url.clone(),
                         plugin_id: self.metadata.id.clone(),
                         title: if entry.title.is_empty() { entry.url.clone() } else { entry.title.clone() },
-                        subtitle: format!("🕒 访问 {} 次 | {}", entry.visit_count, entry.url),
-                        icon: WoxImage::emoji("📜".to_string()),
+                        subtitle: format!("{} · 🕒 访问 {} 次 | {}", entry.profile, entry.visit_count, entry.url),
+                        icon: WoxImage::emoji(entry.browser.emoji()),
                         score: (score as i32) + (entry.visit_count / 10),
                         context_data: serde_json::to_value(&entry)?,
                         actions: vec![
@@ -336,6 +408,9 @@ impl crate::plugin::Plugin for BrowserPlugin {
                         preview: None,
                         refreshable: false,
                         group: None,
+                        accessibility: None,
+                        highlights: None,
+                        badges: Vec::new(),
                     });
                 }
             }
@@ -348,35 +423,83 @@ impl crate::plugin::Plugin for BrowserPlugin {
         Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         match action_id {
             "open" => {
                 // 在默认浏览器中打开
-                #[cfg(target_os = "windows")]
-                {
-                    std::process::Command::new("cmd")
-                        .args(&["/C", "start", result_id])
-                        .spawn()?;
-                }
-                
-                #[cfg(not(target_os = "windows"))]
-                {
-                    std::process::Command::new("xdg-open")
-                        .arg(result_id)
-                        .spawn()?;
-                }
-                
-                Ok(())
+                crate::plugin::web_search::WebSearchPlugin::open_url(result_id).await?;
+                Ok(ExecuteOutcome::Hide)
             }
             "copy" => {
                 // 复制到剪贴板
-                use arboard::Clipboard;
-                let mut clipboard = Clipboard::new()?;
-                clipboard.set_text(result_id)?;
+                crate::clipboard::write_text(result_id)?;
                 tracing::info!("Copied URL to clipboard: {}", result_id);
-                Ok(())
+                Ok(ExecuteOutcome::message("URL copied to clipboard"))
+            }
+            "activate_tab" => {
+                let (port, id) = browser_tabs::parse_result_id(result_id)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid tab: {}", result_id))?;
+                if let Err(e) = browser_tabs::activate(port, id).await {
+                    // 标签页已关闭或浏览器已退出：按原链接重新打开
+                    let url = self.tab_url(result_id).ok_or(e)?;
+                    tracing::info!("Tab {} is gone, reopening {}", result_id, url);
+                    crate::plugin::web_search::WebSearchPlugin::open_url(&url).await?;
+                }
+                Ok(ExecuteOutcome::Hide)
+            }
+            "copy_tab_url" => {
+                let url = self
+                    .tab_url(result_id)
+                    .ok_or_else(|| anyhow::anyhow!("Tab not found: {}", result_id))?;
+                crate::clipboard::write_text(&url)?;
+                Ok(ExecuteOutcome::message("URL copied to clipboard"))
+            }
+            "copy_flag" => {
+                crate::clipboard::write_text(result_id)?;
+                Ok(ExecuteOutcome::message("Launch flag copied to clipboard"))
             }
             _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::FakeClock;
+
+    #[test]
+    fn test_reload_gate_throttles_changes() {
+        let clock = FakeClock::fixed();
+        let gate = ReloadGate::new(clock.clone());
+        assert!(!gate.try_begin());
+
+        // 首次变化立即允许加载
+        gate.mark_dirty();
+        assert!(gate.try_begin());
+        assert!(!gate.try_begin());
+
+        // 间隔内的变化等到间隔结束
+        gate.mark_dirty();
+        clock.advance(chrono::Duration::seconds(MIN_RELOAD_INTERVAL_SECS - 1));
+        assert!(!gate.try_begin());
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(gate.try_begin());
+
+        // 初始加载后同样节流
+        gate.record_reload();
+        gate.mark_dirty();
+        assert!(!gate.try_begin());
+    }
+    #[test]
+    fn test_configure_devtools_ports() {
+        let plugin = BrowserPlugin::new();
+        plugin.configure(&serde_json::json!({ DEVTOOLS_PORTS_KEY: "9223, 9333" }));
+        assert_eq!(*plugin.devtools_ports.read(), vec![9223, 9333]);
+        plugin.configure(&serde_json::json!({ DEVTOOLS_PORTS_KEY: 9229 }));
+        assert_eq!(*plugin.devtools_ports.read(), vec![9229]);
+        // 无效配置回退默认端口
+        plugin.configure(&serde_json::json!({ DEVTOOLS_PORTS_KEY: "abc" }));
+        assert_eq!(*plugin.devtools_ports.read(), vec![browser_tabs::DEFAULT_PORT]);
+    }
+}
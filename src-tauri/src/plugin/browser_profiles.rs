@@ -0,0 +1,659 @@
+// 浏览器配置文件发现与读取 - Chromium 系（Chrome / Edge / Brave / Vivaldi / Chromium）按 Local State 枚举全部配置文件，
+// Firefox 按 profiles.ini 枚举；历史数据库被浏览器锁定，读取前连同 WAL 文件复制到临时目录
+
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 每个配置文件最多读取的历史记录数
+const HISTORY_PER_PROFILE: usize = 500;
+
+/// Chromium 时间戳（1601-01-01 起的微秒）与 Unix 毫秒的差值
+const CHROMIUM_EPOCH_OFFSET_MS: i64 = 11_644_473_600_000;
+
+/// 数据变化时需要重新加载的文件
+const SOURCE_FILES: &[&str] = &["Bookmarks", "History", "Local State", "places.sqlite", "places.sqlite-wal", "profiles.ini"];
+
+/// 浏览器
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserKind {
+    Chrome,
+    Edge,
+    Brave,
+    Vivaldi,
+    Chromium,
+    Firefox,
+}
+
+impl BrowserKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Chrome => "Chrome",
+            Self::Edge => "Edge",
+            Self::Brave => "Brave",
+            Self::Vivaldi => "Vivaldi",
+            Self::Chromium => "Chromium",
+            Self::Firefox => "Firefox",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Chrome => "🟡",
+            Self::Edge => "🌊",
+            Self::Brave => "🦁",
+            Self::Vivaldi => "🎻",
+            Self::Chromium => "🔵",
+            Self::Firefox => "🦊",
+        }
+    }
+}
+
+/// 浏览器配置文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserProfile {
+    pub browser: BrowserKind,
+    /// 配置文件显示名称
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl BrowserProfile {
+    /// 带配置文件名的浏览器名称（默认配置文件只显示浏览器名）
+    pub fn display_name(&self) -> String {
+        if matches!(self.name.as_str(), "" | "Default" | "default" | "default-release") {
+            self.browser.label().to_string()
+        } else {
+            format!("{} ({})", self.browser.label(), self.name)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    pub folder: String,
+    pub browser: BrowserKind,
+    pub profile: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub title: String,
+    pub url: String,
+    pub visit_count: i32,
+    /// 最近访问时间（Unix 毫秒）
+    pub last_visit_time: i64,
+    pub browser: BrowserKind,
+    pub profile: String,
+}
+
+/// 当前系统上各浏览器的数据根目录
+pub fn browser_roots() -> Vec<(BrowserKind, PathBuf)> {
+    let mut roots = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(local) = dirs::data_local_dir() {
+            roots.push((BrowserKind::Chrome, local.join("Google").join("Chrome").join("User Data")));
+            roots.push((BrowserKind::Edge, local.join("Microsoft").join("Edge").join("User Data")));
+            roots.push((BrowserKind::Brave, local.join("BraveSoftware").join("Brave-Browser").join("User Data")));
+            roots.push((BrowserKind::Vivaldi, local.join("Vivaldi").join("User Data")));
+            roots.push((BrowserKind::Chromium, local.join("Chromium").join("User Data")));
+        }
+        if let Some(roaming) = dirs::data_dir() {
+            roots.push((BrowserKind::Firefox, roaming.join("Mozilla").join("Firefox")));
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(support) = dirs::data_dir() {
+            roots.push((BrowserKind::Chrome, support.join("Google").join("Chrome")));
+            roots.push((BrowserKind::Edge, support.join("Microsoft Edge")));
+            roots.push((BrowserKind::Brave, support.join("BraveSoftware").join("Brave-Browser")));
+            roots.push((BrowserKind::Vivaldi, support.join("Vivaldi")));
+            roots.push((BrowserKind::Chromium, support.join("Chromium")));
+            roots.push((BrowserKind::Firefox, support.join("Firefox")));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(config) = dirs::config_dir() {
+            roots.push((BrowserKind::Chrome, config.join("google-chrome")));
+            roots.push((BrowserKind::Edge, config.join("microsoft-edge")));
+            roots.push((BrowserKind::Brave, config.join("BraveSoftware").join("Brave-Browser")));
+            roots.push((BrowserKind::Vivaldi, config.join("vivaldi")));
+            roots.push((BrowserKind::Chromium, config.join("chromium")));
+        }
+        if let Some(home) = dirs::home_dir() {
+            roots.push((BrowserKind::Firefox, home.join(".mozilla").join("firefox")));
+        }
+    }
+
+    roots
+}
+
+/// 枚举已安装浏览器的全部配置文件
+pub fn discover(roots: &[(BrowserKind, PathBuf)]) -> Vec<BrowserProfile> {
+    roots
+        .iter()
+        .filter(|(_, root)| root.is_dir())
+        .flat_map(|(browser, root)| match browser {
+            BrowserKind::Firefox => firefox_profiles(root),
+            _ => chromium_profiles(*browser, root),
+        })
+        .collect()
+}
+
+/// Chromium 配置文件：Local State 的 profile.info_cache（缺失时按 Default / Profile N 目录枚举）
+fn chromium_profiles(browser: BrowserKind, root: &Path) -> Vec<BrowserProfile> {
+    let has_data = |dir: &Path| dir.join("Bookmarks").is_file() || dir.join("History").is_file();
+
+    let local_state: Option<serde_json::Value> = std::fs::read_to_string(root.join("Local State"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    let info_cache = local_state
+        .as_ref()
+        .and_then(|state| state["profile"]["info_cache"].as_object());
+
+    let mut profiles: Vec<BrowserProfile> = match info_cache {
+        Some(cache) => cache
+            .iter()
+            .map(|(dir_name, info)| BrowserProfile {
+                browser,
+                name: info["name"].as_str().unwrap_or(dir_name).to_string(),
+                dir: root.join(dir_name),
+            })
+            .collect(),
+        None => std::fs::read_dir(root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .filter(|name| name == "Default" || name.starts_with("Profile "))
+                    .map(|name| BrowserProfile {
+                        browser,
+                        dir: root.join(&name),
+                        name,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    profiles.retain(|profile| has_data(&profile.dir));
+    profiles.sort_by(|a, b| a.dir.cmp(&b.dir));
+    profiles
+}
+
+/// Firefox 配置文件：profiles.ini 中的 [ProfileN] 段
+fn firefox_profiles(root: &Path) -> Vec<BrowserProfile> {
+    let Ok(ini) = std::fs::read_to_string(root.join("profiles.ini")) else {
+        return Vec::new();
+    };
+
+    parse_profiles_ini(&ini)
+        .into_iter()
+        .map(|(name, path, relative)| BrowserProfile {
+            browser: BrowserKind::Firefox,
+            name,
+            dir: if relative { root.join(path) } else { PathBuf::from(path) },
+        })
+        .filter(|profile| profile.dir.join("places.sqlite").is_file())
+        .collect()
+}
+
+/// 解析 profiles.ini，返回 (Name, Path, IsRelative)
+fn parse_profiles_ini(ini: &str) -> Vec<(String, String, bool)> {
+    let mut profiles = Vec::new();
+    let mut current: Option<HashMap<String, String>> = None;
+
+    let mut flush = |section: Option<HashMap<String, String>>| {
+        if let Some(mut section) = section {
+            if let Some(path) = section.remove("Path") {
+                let relative = section.get("IsRelative").map_or(true, |v| v == "1");
+                let name = section.remove("Name").unwrap_or_else(|| path.clone());
+                profiles.push((name, path, relative));
+            }
+        }
+    };
+
+    for line in ini.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            flush(current.take());
+            if line[1..line.len() - 1].starts_with("Profile") {
+                current = Some(HashMap::new());
+            }
+        } else if let (Some(section), Some((key, value))) = (current.as_mut(), line.split_once('=')) {
+            section.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    flush(current);
+    profiles
+}
+
+/// 配置文件发生变化时需要监听的目录（数据根目录 + 各配置文件目录）
+pub fn watch_dirs(roots: &[(BrowserKind, PathBuf)], profiles: &[BrowserProfile]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = roots
+        .iter()
+        .map(|(_, root)| root.clone())
+        .chain(profiles.iter().map(|p| p.dir.clone()))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// 变更的文件是否影响书签/历史/配置文件列表
+pub fn is_source_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| SOURCE_FILES.contains(&name))
+}
+
+/// 读取配置文件的书签
+pub fn read_bookmarks(profile: &BrowserProfile) -> Result<Vec<Bookmark>> {
+    match profile.browser {
+        BrowserKind::Firefox => firefox_bookmarks(profile),
+        _ => chromium_bookmarks(profile),
+    }
+}
+
+/// 读取配置文件的历史记录（按访问次数排序，最多 HISTORY_PER_PROFILE 条）
+pub fn read_history(profile: &BrowserProfile) -> Result<Vec<HistoryEntry>> {
+    match profile.browser {
+        BrowserKind::Firefox => firefox_history(profile),
+        _ => chromium_history(profile),
+    }
+}
+
+fn chromium_bookmarks(profile: &BrowserProfile) -> Result<Vec<Bookmark>> {
+    let path = profile.dir.join("Bookmarks");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+
+    let mut bookmarks = Vec::new();
+    for (root, folder) in [("bookmark_bar", "书签栏"), ("other", "其他书签"), ("synced", "移动设备书签")] {
+        parse_bookmark_folder(profile, &json["roots"][root], folder, &mut bookmarks);
+    }
+    Ok(bookmarks)
+}
+
+fn parse_bookmark_folder(profile: &BrowserProfile, node: &serde_json::Value, folder: &str, bookmarks: &mut Vec<Bookmark>) {
+    match node["type"].as_str() {
+        Some("url") => {
+            if let (Some(title), Some(url)) = (node["name"].as_str(), node["url"].as_str()) {
+                bookmarks.push(Bookmark {
+                    title: title.to_string(),
+                    url: url.to_string(),
+                    folder: folder.to_string(),
+                    browser: profile.browser,
+                    profile: profile.display_name(),
+                });
+            }
+        }
+        Some("folder") => {
+            if let Some(children) = node["children"].as_array() {
+                let folder_name = node["name"].as_str().unwrap_or(folder);
+                for child in children {
+                    parse_bookmark_folder(profile, child, folder_name, bookmarks);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn chromium_history(profile: &BrowserProfile) -> Result<Vec<HistoryEntry>> {
+    let path = profile.dir.join("History");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let snapshot = SqliteSnapshot::open(&path)?;
+    let mut stmt = snapshot.conn().prepare(
+        "SELECT title, url, visit_count, last_visit_time
+         FROM urls
+         WHERE visit_count > 0
+         ORDER BY visit_count DESC, last_visit_time DESC
+         LIMIT ?1",
+    )?;
+    let entries = stmt
+        .query_map([HISTORY_PER_PROFILE as i64], |row| {
+            Ok(HistoryEntry {
+                title: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                url: row.get(1)?,
+                visit_count: row.get(2)?,
+                last_visit_time: row.get::<_, i64>(3)? / 1000 - CHROMIUM_EPOCH_OFFSET_MS,
+                browser: profile.browser,
+                profile: profile.display_name(),
+            })
+        })?
+        .flatten()
+        .collect();
+    Ok(entries)
+}
+
+fn firefox_bookmarks(profile: &BrowserProfile) -> Result<Vec<Bookmark>> {
+    let snapshot = SqliteSnapshot::open(&profile.dir.join("places.sqlite"))?;
+    let mut stmt = snapshot.conn().prepare(
+        "SELECT b.title, p.url, parent.title
+         FROM moz_bookmarks b
+         JOIN moz_places p ON b.fk = p.id
+         LEFT JOIN moz_bookmarks parent ON b.parent = parent.id
+         WHERE b.type = 1 AND p.url NOT LIKE 'place:%'",
+    )?;
+    let bookmarks = stmt
+        .query_map([], |row| {
+            let url: String = row.get(1)?;
+            Ok(Bookmark {
+                title: row.get::<_, Option<String>>(0)?.filter(|t| !t.is_empty()).unwrap_or_else(|| url.clone()),
+                url,
+                folder: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                browser: profile.browser,
+                profile: profile.display_name(),
+            })
+        })?
+        .flatten()
+        .collect();
+    Ok(bookmarks)
+}
+
+fn firefox_history(profile: &BrowserProfile) -> Result<Vec<HistoryEntry>> {
+    let snapshot = SqliteSnapshot::open(&profile.dir.join("places.sqlite"))?;
+    let mut stmt = snapshot.conn().prepare(
+        "SELECT title, url, visit_count, last_visit_date
+         FROM moz_places
+         WHERE visit_count > 0 AND hidden = 0
+         ORDER BY visit_count DESC, last_visit_date DESC
+         LIMIT ?1",
+    )?;
+    let entries = stmt
+        .query_map([HISTORY_PER_PROFILE as i64], |row| {
+            Ok(HistoryEntry {
+                title: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                url: row.get(1)?,
+                visit_count: row.get(2)?,
+                last_visit_time: row.get::<_, Option<i64>>(3)?.unwrap_or_default() / 1000,
+                browser: profile.browser,
+                profile: profile.display_name(),
+            })
+        })?
+        .flatten()
+        .collect();
+    Ok(entries)
+}
+
+/// 合并各浏览器的历史记录：同一 URL 访问次数累加，保留最近一次访问的浏览器
+pub fn merge_history(entries: Vec<HistoryEntry>, limit: usize) -> Vec<HistoryEntry> {
+    let mut merged: HashMap<String, HistoryEntry> = HashMap::new();
+    for entry in entries {
+        match merged.get_mut(&entry.url) {
+            Some(existing) => {
+                existing.visit_count += entry.visit_count;
+                if existing.title.is_empty() {
+                    existing.title = entry.title.clone();
+                }
+                if entry.last_visit_time > existing.last_visit_time {
+                    existing.last_visit_time = entry.last_visit_time;
+                    existing.browser = entry.browser;
+                    existing.profile = entry.profile;
+                }
+            }
+            None => {
+                merged.insert(entry.url.clone(), entry);
+            }
+        }
+    }
+
+    let mut history: Vec<HistoryEntry> = merged.into_values().collect();
+    history.sort_by(|a, b| {
+        b.visit_count
+            .cmp(&a.visit_count)
+            .then(b.last_visit_time.cmp(&a.last_visit_time))
+    });
+    history.truncate(limit);
+    history
+}
+
+/// 合并各浏览器的书签（同一 URL 只保留第一次出现的）
+pub fn merge_bookmarks(bookmarks: Vec<Bookmark>) -> Vec<Bookmark> {
+    let mut seen = std::collections::HashSet::new();
+    bookmarks.into_iter().filter(|b| seen.insert(b.url.clone())).collect()
+}
+
+/// SQLite 数据库的临时副本（浏览器运行时数据库被锁定；连同 WAL 一起复制以包含最近写入），释放时删除
+struct SqliteSnapshot {
+    conn: Option<Connection>,
+    files: Vec<PathBuf>,
+}
+
+impl SqliteSnapshot {
+    fn open(db: &Path) -> Result<Self> {
+        let copy = std::env::temp_dir().join(format!("ilauncher_browser_{}.db", uuid::Uuid::new_v4()));
+        let mut snapshot = Self {
+            conn: None,
+            files: Vec::new(),
+        };
+
+        std::fs::copy(db, &copy)?;
+        snapshot.files.push(copy.clone());
+
+        let mut wal = db.as_os_str().to_owned();
+        wal.push("-wal");
+        let wal = PathBuf::from(wal);
+        if wal.is_file() {
+            let mut wal_copy = copy.as_os_str().to_owned();
+            wal_copy.push("-wal");
+            let wal_copy = PathBuf::from(wal_copy);
+            if std::fs::copy(&wal, &wal_copy).is_ok() {
+                snapshot.files.push(wal_copy);
+            }
+            let mut shm = copy.as_os_str().to_owned();
+            shm.push("-shm");
+            snapshot.files.push(PathBuf::from(shm));
+        }
+
+        snapshot.conn = Some(Connection::open(&copy)?);
+        Ok(snapshot)
+    }
+
+    fn conn(&self) -> &Connection {
+        self.conn.as_ref().expect("snapshot connection is open until drop")
+    }
+}
+
+impl Drop for SqliteSnapshot {
+    fn drop(&mut self) {
+        // 先关闭连接再删除文件（Windows 无法删除打开中的文件）
+        drop(self.conn.take());
+        for file in &self.files {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ilauncher_browser_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn history_entry(url: &str, browser: BrowserKind, visit_count: i32, last_visit_time: i64) -> HistoryEntry {
+        HistoryEntry {
+            title: String::new(),
+            url: url.to_string(),
+            visit_count,
+            last_visit_time,
+            browser,
+            profile: browser.label().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_discover_chromium_profiles() {
+        let root = temp_root();
+        write(
+            &root,
+            "Local State",
+            r#"{ "profile": { "info_cache": { "Default": { "name": "Personal" }, "Profile 2": { "name": "Work" }, "Profile 3": { "name": "Empty" } } } }"#,
+        );
+        write(&root, "Default/Bookmarks", r#"{ "roots": {} }"#);
+        write(&root, "Profile 2/History", "");
+        std::fs::create_dir_all(root.join("Profile 3")).unwrap();
+
+        let profiles = discover(&[(BrowserKind::Brave, root.clone())]);
+        let names: Vec<String> = profiles.iter().map(BrowserProfile::display_name).collect();
+        assert_eq!(names, vec!["Brave (Personal)", "Brave (Work)"]);
+
+        // 没有 Local State 时按目录名枚举
+        std::fs::remove_file(root.join("Local State")).unwrap();
+        let profiles = discover(&[(BrowserKind::Chrome, root.clone())]);
+        let names: Vec<String> = profiles.iter().map(BrowserProfile::display_name).collect();
+        assert_eq!(names, vec!["Chrome", "Chrome (Profile 2)"]);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_parse_profiles_ini() {
+        let ini = "[Install4F96D1932A9F858E]\nDefault=Profiles/abc.default-release\n\n\
+                   [Profile1]\nName=default\nIsRelative=1\nPath=Profiles/xyz.default\n\n\
+                   [Profile0]\nName=dev\nIsRelative=0\nPath=/opt/firefox-dev\n\n[General]\nVersion=2\n";
+        assert_eq!(
+            parse_profiles_ini(ini),
+            vec![
+                ("default".to_string(), "Profiles/xyz.default".to_string(), true),
+                ("dev".to_string(), "/opt/firefox-dev".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_chromium_bookmarks_and_history() {
+        let root = temp_root();
+        write(
+            &root,
+            "Default/Bookmarks",
+            r#"{ "roots": {
+                "bookmark_bar": { "type": "folder", "name": "Bookmarks bar", "children": [
+                    { "type": "url", "name": "Rust", "url": "https://www.rust-lang.org/" },
+                    { "type": "folder", "name": "Docs", "children": [
+                        { "type": "url", "name": "Tauri", "url": "https://tauri.app/" }
+                    ] }
+                ] },
+                "other": { "type": "folder", "name": "Other", "children": [] }
+            } }"#,
+        );
+        let conn = Connection::open(root.join("Default/History")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT, title TEXT, visit_count INTEGER, last_visit_time INTEGER);
+             INSERT INTO urls (url, title, visit_count, last_visit_time) VALUES
+                ('https://github.com/', 'GitHub', 12, 13350000000000000),
+                ('https://never.example/', 'Never', 0, 0);",
+        )
+        .unwrap();
+        drop(conn);
+
+        let profile = BrowserProfile {
+            browser: BrowserKind::Edge,
+            name: "Default".to_string(),
+            dir: root.join("Default"),
+        };
+        let bookmarks = read_bookmarks(&profile).unwrap();
+        let folders: Vec<(&str, &str)> = bookmarks.iter().map(|b| (b.title.as_str(), b.folder.as_str())).collect();
+        assert_eq!(folders, vec![("Rust", "Bookmarks bar"), ("Tauri", "Docs")]);
+
+        let history = read_history(&profile).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].profile, "Edge");
+        assert_eq!(history[0].last_visit_time, 13_350_000_000_000 - CHROMIUM_EPOCH_OFFSET_MS);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_read_firefox_places() {
+        let root = temp_root();
+        let conn = Connection::open(root.join("places.sqlite")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT, title TEXT, visit_count INTEGER, hidden INTEGER, last_visit_date INTEGER);
+             CREATE TABLE moz_bookmarks (id INTEGER PRIMARY KEY, type INTEGER, fk INTEGER, parent INTEGER, title TEXT);
+             INSERT INTO moz_places VALUES (1, 'https://developer.mozilla.org/', 'MDN', 7, 0, 1700000000000000);
+             INSERT INTO moz_places VALUES (2, 'place:sort=8', NULL, 0, 1, NULL);
+             INSERT INTO moz_places VALUES (3, 'https://hidden.example/', 'Hidden', 3, 1, 1700000000000000);
+             INSERT INTO moz_bookmarks VALUES (10, 2, NULL, 0, 'toolbar');
+             INSERT INTO moz_bookmarks VALUES (11, 1, 1, 10, 'MDN Web Docs');
+             INSERT INTO moz_bookmarks VALUES (12, 1, 2, 10, 'Recent');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let profile = BrowserProfile {
+            browser: BrowserKind::Firefox,
+            name: "default-release".to_string(),
+            dir: root.clone(),
+        };
+        let bookmarks = read_bookmarks(&profile).unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "MDN Web Docs");
+        assert_eq!(bookmarks[0].folder, "toolbar");
+
+        let history = read_history(&profile).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].last_visit_time, 1_700_000_000_000);
+
+        // 临时副本在释放后删除
+        std::fs::write(root.join("places.sqlite-wal"), "").unwrap();
+        let snapshot = SqliteSnapshot::open(&root.join("places.sqlite")).unwrap();
+        let files = snapshot.files.clone();
+        assert_eq!(files.len(), 3);
+        drop(snapshot);
+        assert!(files.iter().all(|file| !file.exists()));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_merge_history_across_browsers() {
+        let merged = merge_history(
+            vec![
+                history_entry("https://a.example/", BrowserKind::Chrome, 3, 100),
+                history_entry("https://b.example/", BrowserKind::Chrome, 4, 50),
+                history_entry("https://a.example/", BrowserKind::Firefox, 2, 200),
+            ],
+            10,
+        );
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].url, "https://a.example/");
+        assert_eq!(merged[0].visit_count, 5);
+        assert_eq!(merged[0].browser, BrowserKind::Firefox);
+        assert_eq!(merged[0].last_visit_time, 200);
+    }
+
+    #[test]
+    fn test_is_source_file() {
+        assert!(is_source_file(Path::new("/p/Default/History")));
+        assert!(is_source_file(Path::new("/p/abc.default/places.sqlite-wal")));
+        assert!(!is_source_file(Path::new("/p/Default/History-journal")));
+        assert!(!is_source_file(Path::new("/p/Default/Cookies")));
+    }
+}
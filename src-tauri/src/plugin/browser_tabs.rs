@@ -0,0 +1,152 @@
+// 浏览器标签页 - 通过 Chrome DevTools 协议的 HTTP 端点列出已打开的标签页（/json/list）并切换（/json/activate），
+// 浏览器需以 --remote-debugging-port=<端口> 启动（Chrome / Edge / Brave 等 Chromium 系浏览器均支持）
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 默认远程调试端口
+pub const DEFAULT_PORT: u16 = 9222;
+
+/// 本机端点请求超时（端口未开启时连接会立即失败，超时只针对无响应的进程）
+const REQUEST_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// 已打开的标签页
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrowserTab {
+    /// DevTools 目标 ID
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    /// 所属浏览器的调试端口
+    pub port: u16,
+}
+
+impl BrowserTab {
+    /// 结果 ID（端口/目标 ID）
+    pub fn result_id(&self) -> String {
+        format!("{}/{}", self.port, self.id)
+    }
+}
+
+/// /json/list 返回的调试目标
+#[derive(Debug, Deserialize)]
+struct Target {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+}
+
+/// 解析端口配置（逗号或空白分隔，忽略无效值和重复项）
+pub fn parse_ports(value: &str) -> Vec<u16> {
+    let mut ports = Vec::new();
+    for port in value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|p| p.trim().parse::<u16>().ok())
+        .filter(|&p| p != 0)
+    {
+        if !ports.contains(&port) {
+            ports.push(port);
+        }
+    }
+    ports
+}
+
+/// 解析结果 ID
+pub fn parse_result_id(result_id: &str) -> Option<(u16, &str)> {
+    let (port, id) = result_id.split_once('/')?;
+    let port = port.parse().ok()?;
+    (!id.is_empty()).then_some((port, id))
+}
+
+/// 列出各端口浏览器的标签页（未开启调试的端口跳过）
+pub async fn list_tabs(ports: &[u16]) -> Vec<BrowserTab> {
+    let Ok(client) = crate::http::client_with_timeout("browser", REQUEST_TIMEOUT) else {
+        return Vec::new();
+    };
+    let mut tabs = Vec::new();
+    for &port in ports {
+        let url = format!("http://127.0.0.1:{}/json/list", port);
+        let body = match client.get(&url).send().await {
+            Ok(response) => response.text().await,
+            Err(e) => {
+                tracing::debug!("No DevTools endpoint on port {}: {}", port, e);
+                continue;
+            }
+        };
+        match body.map_err(anyhow::Error::from).and_then(|body| parse_targets(&body, port)) {
+            Ok(items) => tabs.extend(items),
+            Err(e) => tracing::warn!("Failed to list browser tabs on port {}: {}", port, e),
+        }
+    }
+    tabs
+}
+
+/// 切换到指定标签页（浏览器会把所在窗口带到前台）
+pub async fn activate(port: u16, id: &str) -> Result<()> {
+    let client = crate::http::client_with_timeout("browser", REQUEST_TIMEOUT)?;
+    let url = format!("http://127.0.0.1:{}/json/activate/{}", port, urlencoding::encode(id));
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Tab is no longer open ({})", response.status()));
+    }
+    Ok(())
+}
+
+/// 解析 /json/list 响应，只保留网页标签页（排除扩展后台页、Service Worker 和 DevTools 自身）
+fn parse_targets(json: &str, port: u16) -> Result<Vec<BrowserTab>> {
+    let targets: Vec<Target> = serde_json::from_str(json)?;
+    Ok(targets
+        .into_iter()
+        .filter(|t| t.kind == "page" && !t.url.starts_with("devtools://"))
+        .map(|t| BrowserTab {
+            title: if t.title.is_empty() { t.url.clone() } else { t.title },
+            id: t.id,
+            url: t.url,
+            port,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = r#"[
+  {"description": "", "devtoolsFrontendUrl": "/devtools/inspector.html?ws=127.0.0.1:9222/devtools/page/A1",
+   "id": "A1", "title": "Rust Programming Language", "type": "page", "url": "https://www.rust-lang.org/",
+   "webSocketDebuggerUrl": "ws://127.0.0.1:9222/devtools/page/A1"},
+  {"id": "B2", "title": "", "type": "page", "url": "https://example.com/blank"},
+  {"id": "C3", "title": "uBlock Origin", "type": "background_page", "url": "chrome-extension://abc/background.html"},
+  {"id": "D4", "title": "Service Worker", "type": "service_worker", "url": "https://example.com/sw.js"},
+  {"id": "E5", "title": "DevTools", "type": "page", "url": "devtools://devtools/bundled/inspector.html"}
+]"#;
+
+    #[test]
+    fn test_parse_targets_keeps_pages() {
+        let tabs = parse_targets(LIST, 9222).unwrap();
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(tabs[0].title, "Rust Programming Language");
+        assert_eq!(tabs[0].result_id(), "9222/A1");
+        // 无标题时显示 URL
+        assert_eq!(tabs[1].title, "https://example.com/blank");
+        assert!(parse_targets("not json", 9222).is_err());
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        assert_eq!(parse_ports("9222, 9223 9222,abc,0,"), vec![9222, 9223]);
+        assert!(parse_ports("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_result_id() {
+        assert_eq!(parse_result_id("9222/A1"), Some((9222, "A1")));
+        assert_eq!(parse_result_id("9222/"), None);
+        assert_eq!(parse_result_id("https://example.com"), None);
+    }
+}
@@ -0,0 +1,474 @@
+// 计算器表达式求值 - 递归下降解析，支持:
+// 四则运算、乘方(**)、括号、百分比(200*15%、100+10%)、
+// 进制字面量(0xFF / 0b1010 / 0o17)、位运算(& | ^ ~ << >>)、科学计数法(1e6)、上一次结果 ans
+
+use anyhow::{anyhow, bail, Result};
+
+/// 求值结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub value: f64,
+    /// 表达式使用了进制字面量或位运算（结果额外显示十六/二/八进制）
+    pub uses_bases: bool,
+    /// 表达式引用了 ans
+    pub uses_ans: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(f64),
+    /// 十六/二/八进制字面量
+    BaseLiteral(f64),
+    Ans,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Power,
+    Percent,
+    And,
+    Or,
+    Xor,
+    Not,
+    Shl,
+    Shr,
+    LParen,
+    RParen,
+}
+
+/// 对表达式求值（ans 为上一次结果，未设置时引用 ans 报错）
+pub fn evaluate(input: &str, ans: Option<f64>) -> Result<Evaluation> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        bail!("Empty expression");
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        ans,
+        uses_bases: false,
+        uses_ans: false,
+    };
+    let value = parser.bit_or()?.value;
+    if parser.pos < parser.tokens.len() {
+        bail!("Unexpected token at position {}", parser.pos);
+    }
+    if !value.is_finite() {
+        bail!("Result is not a finite number");
+    }
+
+    Ok(Evaluation {
+        value,
+        uses_bases: parser.uses_bases,
+        uses_ans: parser.uses_ans,
+    })
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '0' if matches!(next, Some('x' | 'X' | 'b' | 'B' | 'o' | 'O')) => {
+                let radix = match next.unwrap().to_ascii_lowercase() {
+                    'x' => 16,
+                    'b' => 2,
+                    _ => 8,
+                };
+                let start = i + 2;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_digit(radix) || chars[end] == '_') {
+                    end += 1;
+                }
+                let digits: String = chars[start..end].iter().filter(|c| **c != '_').collect();
+                if digits.is_empty() {
+                    bail!("Missing digits after base prefix");
+                }
+                let value = u64::from_str_radix(&digits, radix).map_err(|e| anyhow!("Invalid literal: {}", e))?;
+                i = end;
+                tokens.push(Token::BaseLiteral(value as f64));
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = i;
+                while end < chars.len() && (chars[end].is_ascii_digit() || chars[end] == '.' || chars[end] == '_') {
+                    end += 1;
+                }
+                // 科学计数法：1e6 / 2.5E-3
+                if end < chars.len() && matches!(chars[end], 'e' | 'E') {
+                    let mut exp_end = end + 1;
+                    if exp_end < chars.len() && matches!(chars[exp_end], '+' | '-') {
+                        exp_end += 1;
+                    }
+                    if exp_end < chars.len() && chars[exp_end].is_ascii_digit() {
+                        while exp_end < chars.len() && chars[exp_end].is_ascii_digit() {
+                            exp_end += 1;
+                        }
+                        end = exp_end;
+                    }
+                }
+                let literal: String = chars[i..end].iter().filter(|c| **c != '_').collect();
+                let value = literal
+                    .parse::<f64>()
+                    .map_err(|e| anyhow!("Invalid number '{}': {}", literal, e))?;
+                i = end;
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut end = i;
+                while end < chars.len() && chars[end].is_ascii_alphanumeric() {
+                    end += 1;
+                }
+                let word: String = chars[i..end].iter().collect();
+                if !word.eq_ignore_ascii_case("ans") {
+                    bail!("Unknown identifier '{}'", word);
+                }
+                i = end;
+                tokens.push(Token::Ans);
+                continue;
+            }
+            '*' if next == Some('*') => {
+                i += 1;
+                Token::Power
+            }
+            '<' if next == Some('<') => {
+                i += 1;
+                Token::Shl
+            }
+            '>' if next == Some('>') => {
+                i += 1;
+                Token::Shr
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' | '×' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '%' => Token::Percent,
+            '&' => Token::And,
+            '|' => Token::Or,
+            '^' => Token::Xor,
+            '~' => Token::Not,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            other => bail!("Unexpected character '{}'", other),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// 操作数（percent 为 true 表示带 % 后缀，加减时按左操作数的百分比计算）
+#[derive(Debug, Clone, Copy)]
+struct Operand {
+    value: f64,
+    percent: bool,
+}
+
+impl Operand {
+    fn plain(value: f64) -> Self {
+        Self { value, percent: false }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    ans: Option<f64>,
+    uses_bases: bool,
+    uses_ans: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 位运算的操作数须为整数
+    fn integer(&mut self, value: f64) -> Result<i64> {
+        self.uses_bases = true;
+        if value.fract() != 0.0 || value.abs() > i64::MAX as f64 {
+            bail!("Bitwise operators need integer operands");
+        }
+        Ok(value as i64)
+    }
+
+    fn bit_or(&mut self) -> Result<Operand> {
+        let mut left = self.bit_xor()?;
+        while self.eat(Token::Or) {
+            let right = self.bit_xor()?;
+            let value = self.integer(left.value)? | self.integer(right.value)?;
+            left = Operand::plain(value as f64);
+        }
+        Ok(left)
+    }
+
+    fn bit_xor(&mut self) -> Result<Operand> {
+        let mut left = self.bit_and()?;
+        while self.eat(Token::Xor) {
+            let right = self.bit_and()?;
+            let value = self.integer(left.value)? ^ self.integer(right.value)?;
+            left = Operand::plain(value as f64);
+        }
+        Ok(left)
+    }
+
+    fn bit_and(&mut self) -> Result<Operand> {
+        let mut left = self.shift()?;
+        while self.eat(Token::And) {
+            let right = self.shift()?;
+            let value = self.integer(left.value)? & self.integer(right.value)?;
+            left = Operand::plain(value as f64);
+        }
+        Ok(left)
+    }
+
+    fn shift(&mut self) -> Result<Operand> {
+        let mut left = self.additive()?;
+        loop {
+            let shl = match self.peek() {
+                Some(Token::Shl) => true,
+                Some(Token::Shr) => false,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.additive()?;
+            let (value, amount) = (self.integer(left.value)?, self.integer(right.value)?);
+            if !(0..64).contains(&amount) {
+                bail!("Shift amount must be between 0 and 63");
+            }
+            let shifted = if shl { value << amount } else { value >> amount };
+            left = Operand::plain(shifted as f64);
+        }
+    }
+
+    /// 加减：a + b% 表示 a 增加 b%（与 "1200 + 19%" 语法一致）
+    fn additive(&mut self) -> Result<Operand> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let plus = match self.peek() {
+                Some(Token::Plus) => true,
+                Some(Token::Minus) => false,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.multiplicative()?;
+            let delta = if right.percent {
+                left.value * right.value
+            } else {
+                right.value
+            };
+            let value = if plus { left.value + delta } else { left.value - delta };
+            left = Operand::plain(value);
+        }
+    }
+
+    /// 乘除：百分比按小数参与运算（200*15% = 30）
+    fn multiplicative(&mut self) -> Result<Operand> {
+        let mut left = self.unary()?;
+        loop {
+            let multiply = match self.peek() {
+                Some(Token::Star) => true,
+                Some(Token::Slash) => false,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.unary()?;
+            let value = if multiply {
+                left.value * right.value
+            } else {
+                if right.value == 0.0 {
+                    bail!("Division by zero");
+                }
+                left.value / right.value
+            };
+            left = Operand::plain(value);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Operand> {
+        if self.eat(Token::Minus) {
+            let operand = self.unary()?;
+            return Ok(Operand {
+                value: -operand.value,
+                ..operand
+            });
+        }
+        if self.eat(Token::Plus) {
+            return self.unary();
+        }
+        if self.eat(Token::Not) {
+            let operand = self.unary()?;
+            let value = !self.integer(operand.value)?;
+            return Ok(Operand::plain(value as f64));
+        }
+        self.power()
+    }
+
+    /// 乘方（右结合，优先级高于一元负号的操作数：-2**2 = -4）
+    fn power(&mut self) -> Result<Operand> {
+        let base = self.postfix()?;
+        if self.eat(Token::Power) {
+            let exponent = self.unary()?;
+            return Ok(Operand::plain(base.value.powf(exponent.value)));
+        }
+        Ok(base)
+    }
+
+    fn postfix(&mut self) -> Result<Operand> {
+        let mut operand = Operand::plain(self.primary()?);
+        if self.eat(Token::Percent) {
+            operand = Operand {
+                value: operand.value / 100.0,
+                percent: true,
+            };
+        }
+        Ok(operand)
+    }
+
+    fn primary(&mut self) -> Result<f64> {
+        match self.peek() {
+            Some(Token::Number(value)) => {
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(Token::BaseLiteral(value)) => {
+                self.pos += 1;
+                self.uses_bases = true;
+                Ok(value)
+            }
+            Some(Token::Ans) => {
+                self.pos += 1;
+                self.uses_ans = true;
+                self.ans.ok_or_else(|| anyhow!("No previous result for 'ans'"))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let value = self.bit_or()?.value;
+                if !self.eat(Token::RParen) {
+                    bail!("Missing closing parenthesis");
+                }
+                Ok(value)
+            }
+            Some(_) => bail!("Unexpected token at position {}", self.pos),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+}
+
+/// 整数结果的十六/二/八进制表示（负数带符号前缀）
+pub fn base_representations(value: i64) -> [(&'static str, String); 3] {
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    [
+        ("十六进制", format!("{}0x{:X}", sign, magnitude)),
+        ("二进制", format!("{}0b{:b}", sign, magnitude)),
+        ("八进制", format!("{}0o{:o}", sign, magnitude)),
+    ]
+}
+
+/// 大数的简写（1.23 million / 4.5 billion），小于一百万或超出 quadrillion 时返回 None
+pub fn compact_number(value: f64) -> Option<String> {
+    const SCALES: [(f64, &str); 4] = [
+        (1e15, "quadrillion"),
+        (1e12, "trillion"),
+        (1e9, "billion"),
+        (1e6, "million"),
+    ];
+    let magnitude = value.abs();
+    if magnitude >= 1e18 {
+        return None;
+    }
+    SCALES.iter().find(|(scale, _)| magnitude >= *scale).map(|(scale, name)| {
+        let scaled = format!("{:.2}", value / scale);
+        format!("{} {}", scaled.trim_end_matches('0').trim_end_matches('.'), name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str) -> f64 {
+        evaluate(input, Some(21.0)).unwrap().value
+    }
+
+    #[test]
+    fn test_arithmetic_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("2 - -3"), 5.0);
+        assert_eq!(eval("2 ** 10"), 1024.0);
+        assert_eq!(eval("-2 ** 2"), -4.0);
+        assert_eq!(eval("1e3 + 1_000"), 2000.0);
+        assert_eq!(eval("10 / 4"), 2.5);
+        assert!(evaluate("5 / 0", None).is_err());
+        assert!(evaluate("(1 + 2", None).is_err());
+        assert!(evaluate("1 +", None).is_err());
+        assert!(evaluate("hello", None).is_err());
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(eval("200*15%"), 30.0);
+        assert_eq!(eval("200 + 10%"), 220.0);
+        assert_eq!(eval("(100 + 10%) * 2"), 220.0);
+        assert_eq!(eval("80 - 25%"), 60.0);
+        assert_eq!(eval("50%"), 0.5);
+    }
+
+    #[test]
+    fn test_bases_and_bitwise() {
+        let result = evaluate("0xFF + 0b1010", None).unwrap();
+        assert_eq!(result.value, 265.0);
+        assert!(result.uses_bases);
+        assert_eq!(eval("1 << 4 | 1"), 17.0);
+        assert_eq!(eval("0xF0 & 0x3C ^ 0o1"), 0x31 as f64);
+        assert_eq!(eval("~0"), -1.0);
+        assert!(evaluate("1.5 & 1", None).is_err());
+        assert!(evaluate("1 << 64", None).is_err());
+        assert!(!evaluate("1 + 2", None).unwrap().uses_bases);
+
+        assert_eq!(
+            base_representations(255).map(|(_, repr)| repr),
+            ["0xFF".to_string(), "0b11111111".to_string(), "0o377".to_string()]
+        );
+        assert_eq!(base_representations(-10)[0].1, "-0xA");
+    }
+
+    #[test]
+    fn test_ans() {
+        let result = evaluate("ans * 2", Some(21.0)).unwrap();
+        assert_eq!(result.value, 42.0);
+        assert!(result.uses_ans);
+        assert!(evaluate("ans + 1", None).is_err());
+    }
+
+    #[test]
+    fn test_compact_number() {
+        assert_eq!(compact_number(1_234_567_890.0).as_deref(), Some("1.23 billion"));
+        assert_eq!(compact_number(-2_500_000.0).as_deref(), Some("-2.5 million"));
+        assert_eq!(compact_number(999_999.0), None);
+        assert_eq!(compact_number(1e20), None);
+    }
+}
@@ -1,19 +1,148 @@
 // 计算器插件 - 增强版
-// 支持: 数学表达式、进制转换、单位转换
+// 支持: 数学表达式（百分比、位运算、进制字面量、ans）、进制转换、单位转换、百分比/税费/小费（按区域格式化数字）
 
 use crate::core::types::*;
+use crate::plugin::calc_expr;
 use crate::plugin::Plugin;
 use anyhow::Result;
 use async_trait::async_trait;
+use parking_lot::RwLock;
 use regex::Regex;
 
+/// 数字格式区域配置键（auto 跟随界面语言）
+const LOCALE_KEY: &str = "locale";
+
+/// 复制原始数值（结果 ID 即原始数值）
+const ACTION_COPY: &str = "copy";
+/// 复制按区域格式化后的数值
+const ACTION_COPY_FORMATTED: &str = "copy_formatted";
+/// 复制科学计数法表示（大数）
+const ACTION_COPY_SCIENTIFIC: &str = "copy_scientific";
+
+/// 超过该值的结果提供科学计数法复制
+const SCIENTIFIC_THRESHOLD: f64 = 1e9;
+
+/// 数字格式（千位分隔符 + 小数点）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NumberLocale {
+    group: char,
+    decimal: char,
+}
+
+impl NumberLocale {
+    const EN: Self = Self { group: ',', decimal: '.' };
+
+    /// 由区域标签（en-US / de-DE / fr-FR / de-CH ...）确定格式，未知区域按英文格式
+    fn from_tag(tag: &str) -> Self {
+        let tag = tag.trim().to_lowercase().replace('_', "-");
+        if tag == "de-ch" || tag == "it-ch" {
+            return Self { group: '\'', decimal: '.' };
+        }
+        match tag.split('-').next().unwrap_or_default() {
+            "de" | "es" | "it" | "nl" | "pt" | "tr" | "id" | "da" | "el" => Self { group: '.', decimal: ',' },
+            "fr" | "ru" | "pl" | "cs" | "sv" | "nb" | "fi" | "uk" | "hu" | "sk" => Self { group: '\u{a0}', decimal: ',' },
+            _ => Self::EN,
+        }
+    }
+
+    /// 把原始数值字符串（"-1234.5"）格式化为区域格式（"-1.234,5"）
+    fn format(&self, raw: &str) -> String {
+        // 科学计数法不分组
+        if raw.contains('e') {
+            return raw.replace('.', &self.decimal.to_string());
+        }
+        let (sign, digits) = match raw.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", raw),
+        };
+        let (int_part, frac_part) = match digits.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (digits, None),
+        };
+
+        let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push(self.group);
+            }
+            grouped.push(c);
+        }
+
+        match frac_part {
+            Some(frac) => format!("{}{}{}{}", sign, grouped, self.decimal, frac),
+            None => format!("{}{}", sign, grouped),
+        }
+    }
+
+    /// 解析输入中的数值（小数逗号区域也接受 "84,50"）
+    fn parse(&self, input: &str) -> Option<f64> {
+        let input = input.trim();
+        if self.decimal == ',' && input.contains(',') && !input.contains('.') {
+            return input.replace(',', ".").parse().ok();
+        }
+        input.replace(',', "").parse().ok()
+    }
+}
+
+/// 默认复制操作（复制内容即结果 ID，由 execute 按 ACTION_COPY 处理）
+fn copy_action(value: &str) -> Action {
+    Action {
+        id: ACTION_COPY.to_string(),
+        name: format!("Copy {}", value),
+        icon: None,
+        is_default: true,
+        hotkey: None,
+        prevent_hide: false,
+    }
+}
+
+/// 原始数值字符串（最多 6 位小数，去掉末尾 0；超过 f64 精确整数范围时用科学计数法）
+fn raw_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else if value.abs() >= 1e21 {
+        format!("{:e}", value)
+    } else {
+        format!("{:.6}", value).trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// 科学计数法（保留 6 位有效小数）
+fn scientific(value: f64) -> String {
+    let formatted = format!("{:.6e}", value);
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) => format!(
+            "{}e{}",
+            mantissa.trim_end_matches('0').trim_end_matches('.'),
+            exponent
+        ),
+        None => formatted,
+    }
+}
+
+/// 金额原始字符串（固定 2 位小数，四舍五入）
+fn raw_money(value: f64) -> String {
+    format!("{:.2}", (value * 100.0).round() / 100.0)
+}
+
+/// 百分比/税费/小费计算结果
+#[derive(Debug, Clone, PartialEq)]
+struct PercentAnswer {
+    /// 原始数值（复制与结果 ID）
+    raw: String,
+    /// 说明（如 "Tip 15%"）
+    label: String,
+}
+
 pub struct CalculatorPlugin {
     metadata: PluginMetadata,
-    expr_regex: Regex,
-    hex_regex: Regex,
-    bin_regex: Regex,
-    oct_regex: Regex,
     unit_regex: Regex,
+    percent_change_regex: Regex,
+    percent_of_regex: Regex,
+    tip_regex: Regex,
+    tax_regex: Regex,
+    /// (区域配置, 界面语言)
+    locale: RwLock<(String, String)>,
 }
 
 impl CalculatorPlugin {
@@ -22,128 +151,193 @@ impl CalculatorPlugin {
             metadata: PluginMetadata {
                 id: "calculator".to_string(),
                 name: "Calculator".to_string(),
-                description: "数学计算、进制转换、单位转换".to_string(),
+                description: "数学计算（位运算、进制字面量、ans）、单位转换、百分比/税费/小费".to_string(),
                 author: "iLauncher".to_string(),
                 version: "1.0.0".to_string(),
                 icon: WoxImage::emoji("🧮"),
                 trigger_keywords: vec!["=".to_string(), "calc".to_string()],
                 commands: vec![],
-                settings: vec![],
+                settings: vec![
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(LOCALE_KEY.to_string()),
+                        label: Some("数字格式区域（auto 跟随界面语言，如 en-US / de-DE / fr-FR）".to_string()),
+                        value: Some(serde_json::json!("auto")),
+                    },
+                ],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Utilities).with_tags(&["math", "currency"]),
             },
-            // 匹配数学表达式：数字、运算符、括号、小数点
-            expr_regex: Regex::new(r"^[\d+\-*/().\s]+$").unwrap(),
-            // 匹配十六进制: 0x 或 0X 开头
-            hex_regex: Regex::new(r"^0[xX][0-9a-fA-F]+$").unwrap(),
-            // 匹配二进制: 0b 或 0B 开头
-            bin_regex: Regex::new(r"^0[bB][01]+$").unwrap(),
-            // 匹配八进制: 0o 或 0O 开头
-            oct_regex: Regex::new(r"^0[oO][0-7]+$").unwrap(),
             // 匹配单位转换: 数字+单位
             unit_regex: Regex::new(r"^([\d.]+)\s*([a-zA-Z]+)$").unwrap(),
+            // 匹配百分比增减: 1200 + 19%
+            percent_change_regex: Regex::new(r"^([\d.,]+)\s*([+\-])\s*([\d.,]+)\s*%$").unwrap(),
+            // 匹配百分比取值: 15% of 84.50
+            percent_of_regex: Regex::new(r"(?i)^([\d.,]+)\s*%\s*of\s+([\d.,]+)$").unwrap(),
+            // 匹配小费: tip 15% on 84.50
+            tip_regex: Regex::new(r"(?i)^tip\s+([\d.,]+)\s*%\s*(?:on|of)\s+([\d.,]+)$").unwrap(),
+            // 匹配税费: vat 8.1% of 200 / tax 7% on 50
+            tax_regex: Regex::new(r"(?i)^(vat|tax|gst)\s+([\d.,]+)\s*%\s*(?:on|of)\s+([\d.,]+)$").unwrap(),
+            locale: RwLock::new(("auto".to_string(), "en".to_string())),
         }
     }
     
-    /// 计算表达式
-    fn calculate(&self, expr: &str) -> Result<f64> {
-        // 简单的表达式解析器（支持 +、-、*、/、括号）
-        let expr = expr.replace(" ", "");
-        self.eval_expr(&expr)
+    /// 初始化：读取数字格式区域与界面语言
+    pub async fn init(&self) {
+        let Ok(storage) = crate::storage::StorageManager::new() else {
+            return;
+        };
+        if let Ok(config) = storage.load_config().await {
+            self.locale.write().1 = config.general.language;
+        }
+        if let Ok(config) = storage.get_plugin_config(&self.metadata.id).await {
+            self.configure(&config);
+        }
     }
     
-    fn eval_expr(&self, expr: &str) -> Result<f64> {
-        // 处理加减
-        let parts: Vec<&str> = expr.split(&['+', '-'][..]).collect();
-        let ops: Vec<char> = expr.chars().filter(|c| *c == '+' || *c == '-').collect();
-        
-        if parts.len() > 1 {
-            let mut result = self.eval_term(parts[0])?;
-            for (i, part) in parts.iter().enumerate().skip(1) {
-                let val = self.eval_term(part)?;
-                match ops.get(i - 1) {
-                    Some('+') => result += val,
-                    Some('-') => result -= val,
-                    _ => {}
-                }
-            }
-            return Ok(result);
+    /// 应用插件配置（保存配置后即时生效）
+    pub fn configure(&self, config: &serde_json::Value) {
+        let preference = config
+            .get(LOCALE_KEY)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or("auto");
+        self.locale.write().0 = preference.to_string();
+    }
+    
+    /// 当前生效的数字格式
+    fn number_locale(&self) -> NumberLocale {
+        let (preference, language) = &*self.locale.read();
+        if preference.eq_ignore_ascii_case("auto") {
+            NumberLocale::from_tag(language)
+        } else {
+            NumberLocale::from_tag(preference)
         }
-        
-        self.eval_term(expr)
     }
     
-    fn eval_term(&self, term: &str) -> Result<f64> {
-        // 处理乘除
-        let parts: Vec<&str> = term.split(&['*', '/'][..]).collect();
-        let ops: Vec<char> = term.chars().filter(|c| *c == '*' || *c == '/').collect();
+    /// 百分比/税费/小费语法
+    fn calculate_percent(&self, input: &str, locale: NumberLocale) -> Option<Vec<PercentAnswer>> {
+        let answer = |value: f64, raw: fn(f64) -> String, label: String| PercentAnswer { raw: raw(value), label };
         
-        if parts.len() > 1 {
-            let mut result = self.eval_factor(parts[0])?;
-            for (i, part) in parts.iter().enumerate().skip(1) {
-                let val = self.eval_factor(part)?;
-                match ops.get(i - 1) {
-                    Some('*') => result *= val,
-                    Some('/') => {
-                        if val == 0.0 {
-                            return Err(anyhow::anyhow!("Division by zero"));
-                        }
-                        result /= val;
-                    }
-                    _ => {}
-                }
-            }
-            return Ok(result);
+        if let Some(caps) = self.percent_change_regex.captures(input) {
+            let base = locale.parse(&caps[1])?;
+            let percent = locale.parse(&caps[3])?;
+            let delta = base * percent / 100.0;
+            let (value, sign) = if &caps[2] == "+" { (base + delta, '+') } else { (base - delta, '-') };
+            return Some(vec![answer(value, raw_number, format!("{} {} {}%", caps[1].trim(), sign, caps[3].trim()))]);
         }
         
-        self.eval_factor(term)
+        if let Some(caps) = self.percent_of_regex.captures(input) {
+            let percent = locale.parse(&caps[1])?;
+            let base = locale.parse(&caps[2])?;
+            return Some(vec![answer(base * percent / 100.0, raw_number, format!("{}% of {}", caps[1].trim(), caps[2].trim()))]);
+        }
+        
+        if let Some(caps) = self.tip_regex.captures(input) {
+            let percent = locale.parse(&caps[1])?;
+            let bill = locale.parse(&caps[2])?;
+            let tip = bill * percent / 100.0;
+            return Some(vec![
+                answer(tip, raw_money, format!("Tip {}%", caps[1].trim())),
+                answer(bill + tip, raw_money, format!("Total with {}% tip", caps[1].trim())),
+            ]);
+        }
+        
+        if let Some(caps) = self.tax_regex.captures(input) {
+            let kind = caps[1].to_uppercase();
+            let percent = locale.parse(&caps[2])?;
+            let amount = locale.parse(&caps[3])?;
+            let tax = amount * percent / 100.0;
+            return Some(vec![
+                answer(tax, raw_money, format!("{} {}%", kind, caps[2].trim())),
+                answer(amount + tax, raw_money, format!("Gross incl. {} {}%", kind, caps[2].trim())),
+                answer(amount / (1.0 + percent / 100.0), raw_money, format!("Net if {} is gross", caps[3].trim())),
+            ]);
+        }
+        
+        None
     }
     
-    fn eval_factor(&self, factor: &str) -> Result<f64> {
-        // 处理括号
-        if factor.starts_with('(') && factor.ends_with(')') {
-            return self.eval_expr(&factor[1..factor.len()-1]);
+    /// 计算结果（标题为区域格式，结果 ID 为原始数值，提供两种复制操作）
+    fn answer_result(&self, raw: String, subtitle: String, score: i32, locale: NumberLocale) -> QueryResult {
+        let formatted = locale.format(&raw);
+        let mut result = QueryResult::new(formatted.clone())
+            .with_subtitle(subtitle)
+            .with_icon(WoxImage::emoji("🧮"))
+            .with_score(score);
+        result.id = raw.clone();
+        result.plugin_id = self.metadata.id.clone();
+        result.actions = vec![copy_action(&raw)];
+        if formatted != raw {
+            result.actions.push(Action {
+                id: ACTION_COPY_FORMATTED.to_string(),
+                name: format!("Copy {}", formatted),
+                icon: None,
+                is_default: false,
+                hotkey: None,
+                prevent_hide: false,
+            });
         }
-        
-        // 解析数字
-        factor.parse::<f64>()
-            .map_err(|e| anyhow::anyhow!("Invalid number: {}", e))
+        if let Some(value) = raw.parse::<f64>().ok().filter(|v| v.abs() >= SCIENTIFIC_THRESHOLD) {
+            result.actions.push(Action {
+                id: ACTION_COPY_SCIENTIFIC.to_string(),
+                name: format!("Copy {}", scientific(value)),
+                icon: None,
+                is_default: false,
+                hotkey: None,
+                prevent_hide: false,
+            });
+        }
+        result
     }
     
-    /// 进制转换
-    fn convert_base(&self, input: &str) -> Option<QueryResult> {
-        let input_lower = input.to_lowercase();
-        
-        // 检测进制并转换
-        let (num, base_name) = if self.hex_regex.is_match(input) {
-            let hex_str = &input_lower[2..];
-            let num = i64::from_str_radix(hex_str, 16).ok()?;
-            (num, "十六进制")
-        } else if self.bin_regex.is_match(input) {
-            let bin_str = &input_lower[2..];
-            let num = i64::from_str_radix(bin_str, 2).ok()?;
-            (num, "二进制")
-        } else if self.oct_regex.is_match(input) {
-            let oct_str = &input_lower[2..];
-            let num = i64::from_str_radix(oct_str, 8).ok()?;
-            (num, "八进制")
-        } else {
-            return None;
-        };
-        
-        // 生成所有进制的表示
-        let conversions = format!(
-            "十进制: {} | 十六进制: 0x{:X} | 二进制: 0b{:b} | 八进制: 0o{:o}",
-            num, num, num, num
-        );
+    /// 表达式结果：数值结果 + 使用进制/位运算时的各进制表示（每种表示单独一行，可分别复制）
+    fn expression_results(&self, query: &str, evaluation: &calc_expr::Evaluation, locale: NumberLocale) -> Vec<QueryResult> {
+        let raw = raw_number(evaluation.value);
+        let mut subtitle = format!("{} = {}", query, locale.format(&raw));
+        if let Some(compact) = calc_expr::compact_number(evaluation.value) {
+            subtitle.push_str(&format!(" (≈ {})", compact));
+        }
+        // ans 只在复制结果时更新，提示当前引用的值
+        if let (true, Some(answer)) = (evaluation.uses_ans, Self::previous_answer()) {
+            subtitle.push_str(&format!(" · ans = {}", locale.format(&raw_number(answer))));
+        }
+        let mut results = vec![self.answer_result(raw, subtitle, 1000, locale)];
         
-        Some(
-            QueryResult::new(conversions.clone())
-                .with_subtitle(format!("{} 进制转换", base_name))
-                .with_icon(WoxImage::emoji("🔢"))
-                .with_score(900)
-                .with_action(Action::new("copy").default())
-        )
+        let value = evaluation.value;
+        if evaluation.uses_bases && value.fract() == 0.0 && value.abs() <= i64::MAX as f64 {
+            for (i, (base_name, repr)) in calc_expr::base_representations(value as i64).into_iter().enumerate() {
+                let mut result = QueryResult::new(repr.clone())
+                    .with_subtitle(format!("{} · {}", base_name, query))
+                    .with_icon(WoxImage::emoji("🔢"))
+                    .with_score(999 - i as i32)
+                    .with_action(copy_action(&repr));
+                result.id = repr;
+                result.plugin_id = self.metadata.id.clone();
+                results.push(result);
+            }
+        }
+        results
+    }
+    
+    /// 上一次结果（ans），会话上下文中的字符串可能是进制表示
+    fn previous_answer() -> Option<f64> {
+        let answer = crate::session_context::SESSION_CONTEXT.last_answer()?;
+        calc_expr::evaluate(&answer, None).ok().map(|e| e.value)
+    }
+    
+    /// 单位转换结果（结果 ID 即转换文本，复制时原样写入剪贴板）
+    fn conversion_result(&self, conversions: String, subtitle: String, icon: &str) -> QueryResult {
+        let mut result = QueryResult::new(conversions.clone())
+            .with_subtitle(subtitle)
+            .with_icon(WoxImage::emoji(icon))
+            .with_score(850)
+            .with_action(copy_action(&conversions));
+        result.id = conversions;
+        result.plugin_id = self.metadata.id.clone();
+        result
     }
     
     /// 单位转换
@@ -168,13 +362,7 @@ impl CalculatorPlugin {
                 _ => return None,
             };
             
-            results.push(
-                QueryResult::new(conversions)
-                    .with_subtitle(format!("长度转换: {}{}", value, unit))
-                    .with_icon(WoxImage::emoji("📏"))
-                    .with_score(850)
-                    .with_action(Action::new("copy").default())
-            );
+            results.push(self.conversion_result(conversions, format!("长度转换: {}{}", value, unit), "📏"));
         }
         
         // 重量单位
@@ -191,13 +379,7 @@ impl CalculatorPlugin {
                 _ => return None,
             };
             
-            results.push(
-                QueryResult::new(conversions)
-                    .with_subtitle(format!("重量转换: {}{}", value, unit))
-                    .with_icon(WoxImage::emoji("⚖️"))
-                    .with_score(850)
-                    .with_action(Action::new("copy").default())
-            );
+            results.push(self.conversion_result(conversions, format!("重量转换: {}{}", value, unit), "⚖️"));
         }
         
         // 温度单位
@@ -212,13 +394,7 @@ impl CalculatorPlugin {
                 _ => return None,
             };
             
-            results.push(
-                QueryResult::new(conversions)
-                    .with_subtitle(format!("温度转换: {}{}", value, unit.to_uppercase()))
-                    .with_icon(WoxImage::emoji("🌡️"))
-                    .with_score(850)
-                    .with_action(Action::new("copy").default())
-            );
+            results.push(self.conversion_result(conversions, format!("温度转换: {}{}", value, unit.to_uppercase()), "🌡️"));
         }
         
         // 存储单位
@@ -237,13 +413,7 @@ impl CalculatorPlugin {
                 _ => return None,
             };
             
-            results.push(
-                QueryResult::new(conversions)
-                    .with_subtitle(format!("存储转换: {}{}", value, unit.to_uppercase()))
-                    .with_icon(WoxImage::emoji("💾"))
-                    .with_score(850)
-                    .with_action(Action::new("copy").default())
-            );
+            results.push(self.conversion_result(conversions, format!("存储转换: {}{}", value, unit.to_uppercase()), "💾"));
         }
         
         if results.is_empty() {
@@ -269,48 +439,130 @@ impl Plugin for CalculatorPlugin {
         
         let mut results = Vec::new();
         
-        // 1. 尝试进制转换
-        if let Some(result) = self.convert_base(query) {
-            results.push(result);
-        }
-        
-        // 2. 尝试单位转换
+        // 1. 尝试单位转换
         if let Some(mut unit_results) = self.convert_unit(query) {
             results.append(&mut unit_results);
         }
         
-        // 3. 尝试数学表达式计算
-        if self.expr_regex.is_match(query) {
-            match self.calculate(query) {
-                Ok(result) => {
-                    let result_str = if result.fract() == 0.0 {
-                        format!("{}", result as i64)
-                    } else {
-                        format!("{:.6}", result).trim_end_matches('0').trim_end_matches('.').to_string()
-                    };
-                    
-                    results.push(
-                        QueryResult::new(result_str.clone())
-                            .with_subtitle(format!("{} = {}", query, result_str))
-                            .with_icon(WoxImage::emoji("🧮"))
-                            .with_score(1000)
-                            .with_action(Action::new("copy").default())
-                    );
-                }
-                Err(_) => {}
+        let locale = self.number_locale();
+        
+        // 2. 百分比、税费、小费
+        if let Some(answers) = self.calculate_percent(query, locale) {
+            for (i, answer) in answers.into_iter().enumerate() {
+                results.push(self.answer_result(answer.raw, answer.label, 1000 - i as i32, locale));
             }
+            return Ok(results);
+        }
+        
+        // 3. 数学表达式（含进制字面量、位运算、百分比、ans）
+        // 输入过程中不更新 ans（否则每次按键都会覆盖），复制结果时才更新
+        if let Ok(evaluation) = calc_expr::evaluate(query, Self::previous_answer()) {
+            results.extend(self.expression_results(query, &evaluation, locale));
         }
         
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
-        if action_id == "copy" {
-            // 复制到剪贴板（后续实现）
-            tracing::info!("Copy result: {}", result_id);
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Unknown action"))
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        let text = match action_id {
+            ACTION_COPY => result_id.to_string(),
+            ACTION_COPY_FORMATTED => self.number_locale().format(result_id),
+            ACTION_COPY_SCIENTIFIC => scientific(result_id.parse::<f64>()?),
+            _ => return Err(anyhow::anyhow!("Unknown action")),
+        };
+        // 复制的数值结果成为下一次表达式中的 ans
+        if calc_expr::evaluate(result_id, None).is_ok() {
+            crate::session_context::SESSION_CONTEXT.set_last_answer(result_id);
         }
+        crate::clipboard::write_text(&text)?;
+        tracing::info!("Copy result: {}", text);
+        Ok(ExecuteOutcome::Hide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_formatting() {
+        assert_eq!(NumberLocale::EN.format("1234567.89"), "1,234,567.89");
+        assert_eq!(NumberLocale::EN.format("-999"), "-999");
+        assert_eq!(NumberLocale::from_tag("de-DE").format("1234.5"), "1.234,5");
+        assert_eq!(NumberLocale::from_tag("fr_FR").format("1234"), "1\u{a0}234");
+        assert_eq!(NumberLocale::from_tag("de-CH").format("1428"), "1'428");
+        assert_eq!(NumberLocale::from_tag("zh-CN"), NumberLocale::EN);
+        assert_eq!(NumberLocale::from_tag("de").parse("84,50"), Some(84.5));
+        assert_eq!(NumberLocale::EN.parse("1,200"), Some(1200.0));
+    }
+
+    #[test]
+    fn test_percent_helpers() {
+        let calc = CalculatorPlugin::new();
+        let raws = |input: &str| -> Vec<String> {
+            calc.calculate_percent(input, NumberLocale::EN)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| a.raw)
+                .collect()
+        };
+
+        assert_eq!(raws("1200 + 19%"), vec!["1428"]);
+        assert_eq!(raws("80 - 25%"), vec!["60"]);
+        assert_eq!(raws("15% of 84.50"), vec!["12.675"]);
+        assert_eq!(raws("tip 15% on 84.50"), vec!["12.68", "97.18"]);
+        assert_eq!(raws("VAT 8.1% of 200"), vec!["16.20", "216.20", "185.01"]);
+        assert!(raws("1200 + 19").is_empty());
+    }
+
+    #[test]
+    fn test_answer_result_copy_actions() {
+        let calc = CalculatorPlugin::new();
+        let result = calc.answer_result("1428".to_string(), String::new(), 1000, NumberLocale::EN);
+        assert_eq!(result.title, "1,428");
+        assert_eq!(result.id, "1428");
+        assert!(result.actions.iter().any(|a| a.id == ACTION_COPY && a.is_default));
+        assert!(result.actions.iter().any(|a| a.id == ACTION_COPY_FORMATTED));
+
+        let result = calc.answer_result("42".to_string(), String::new(), 1000, NumberLocale::EN);
+        assert_eq!(result.actions.len(), 1);
+    }
+
+    #[test]
+    fn test_expression_results_per_base() {
+        let calc = CalculatorPlugin::new();
+        let query = "0xFF + 0b1010";
+        let evaluation = calc_expr::evaluate(query, None).unwrap();
+        let results = calc.expression_results(query, &evaluation, NumberLocale::EN);
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["265", "0x109", "0b100001001", "0o411"]);
+        // 每种表示单独复制（结果 ID 即复制内容）
+        assert_eq!(results[1].id, "0x109");
+        assert!(results[1].actions.iter().any(|a| a.id == ACTION_COPY && a.is_default));
+
+        let evaluation = calc_expr::evaluate("1 + 2", None).unwrap();
+        assert_eq!(calc.expression_results("1 + 2", &evaluation, NumberLocale::EN).len(), 1);
+    }
+
+    #[test]
+    fn test_unit_conversion_copy_action() {
+        let calc = CalculatorPlugin::new();
+        let results = calc.convert_unit("5 km").unwrap();
+        assert_eq!(results[0].id, "5000m | 3.11mi");
+        assert!(results[0].actions.iter().any(|a| a.id == ACTION_COPY && a.is_default));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let calc = CalculatorPlugin::new();
+        let evaluation = calc_expr::evaluate("2 ** 40", None).unwrap();
+        let result = &calc.expression_results("2 ** 40", &evaluation, NumberLocale::EN)[0];
+        assert_eq!(result.title, "1,099,511,627,776");
+        assert!(result.subtitle.contains("≈ 1.1 trillion"));
+        assert!(result.actions.iter().any(|a| a.id == ACTION_COPY_SCIENTIFIC && a.name == "Copy 1.099512e12"));
+
+        assert_eq!(raw_number(1e22), "1e22");
+        assert_eq!(NumberLocale::EN.format("1e22"), "1e22");
+        assert_eq!(scientific(12_345_000_000.0), "1.2345e10");
     }
 }
@@ -1,5 +1,6 @@
 // 剪贴板历史插件
 
+use crate::clipboard_paste::PasteTransform;
 use crate::core::types::*;
 use crate::plugin::Plugin;
 use anyhow::Result;
@@ -44,9 +45,72 @@ impl ClipboardPlugin {
                 icon: WoxImage::emoji("📋"),
                 trigger_keywords: vec!["cb".to_string(), "clip".to_string()],
                 commands: vec![],
-                settings: vec![],
+                settings: vec![
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(crate::clipboard_ignore::IGNORED_APPS_KEY.to_string()),
+                        label: Some("忽略的应用（进程名或路径，逗号分隔，支持 * 通配符）".to_string()),
+                        value: Some(serde_json::json!(crate::clipboard_ignore::DEFAULT_IGNORED_APPS.join(", "))),
+                    },
+                    SettingDefinition {
+                        r#type: "checkbox".to_string(),
+                        key: Some(crate::clipboard_ignore::SKIP_CARD_NUMBERS_KEY.to_string()),
+                        label: Some("不记录包含银行卡号的内容".to_string()),
+                        value: Some(serde_json::json!(true)),
+                    },
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(crate::clipboard_ignore::DENY_PATTERNS_KEY.to_string()),
+                        label: Some("不记录匹配以下正则的内容（每行一条）".to_string()),
+                        value: Some(serde_json::json!("")),
+                    },
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(crate::clipboard_ignore::CATEGORY_TTL_KEY.to_string()),
+                        label: Some("按分类自动过期（小时，如 temp=1, uncategorized=168；收藏不过期）".to_string()),
+                        value: Some(serde_json::json!("")),
+                    },
+                    SettingDefinition {
+                        r#type: "checkbox".to_string(),
+                        key: Some(crate::clipboard_ocr::OCR_ENABLED_KEY.to_string()),
+                        label: Some("识别图片中的文字（OCR），可按截图内容搜索".to_string()),
+                        value: Some(serde_json::json!(false)),
+                    },
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(crate::clipboard_ocr::OCR_LANGUAGE_KEY.to_string()),
+                        label: Some("tesseract 识别语言（非 Windows 或系统 OCR 不可用时使用，如 eng、chi_sim+eng）".to_string()),
+                        value: Some(serde_json::json!(crate::clipboard_ocr::DEFAULT_OCR_LANGUAGE)),
+                    },
+                    SettingDefinition {
+                        r#type: "number".to_string(),
+                        key: Some(crate::clipboard::SECRET_CLEAR_SECONDS_KEY.to_string()),
+                        label: Some("复制密码/验证码后自动清空剪贴板的延迟（秒，0 表示不清空）".to_string()),
+                        value: Some(serde_json::json!(crate::clipboard::DEFAULT_SECRET_CLEAR_SECONDS)),
+                    },
+                    SettingDefinition {
+                        r#type: "checkbox".to_string(),
+                        key: Some(crate::clipboard::SecretSource::PasswordManager.config_key().to_string()),
+                        label: Some("自动清空：密码管理器插件复制的密码".to_string()),
+                        value: Some(serde_json::json!(true)),
+                    },
+                    SettingDefinition {
+                        r#type: "checkbox".to_string(),
+                        key: Some(crate::clipboard::SecretSource::Totp.config_key().to_string()),
+                        label: Some("自动清空：TOTP 验证码".to_string()),
+                        value: Some(serde_json::json!(true)),
+                    },
+                    SettingDefinition {
+                        r#type: "checkbox".to_string(),
+                        key: Some(crate::clipboard::SecretSource::Snippet.config_key().to_string()),
+                        label: Some("自动清空：标记为敏感的文本片段".to_string()),
+                        value: Some(serde_json::json!(true)),
+                    },
+                ],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Productivity).with_tags(&["clipboard", "history"]),
             },
             history: Arc::new(RwLock::new(Vec::new())),
             matcher: SkimMatcherV2::default(),
@@ -119,6 +183,32 @@ impl ClipboardPlugin {
         Ok(())
     }
     
+    /// 条目动作：粘贴（默认）、复制，文本条目另有变换粘贴
+    fn item_actions(item_type: &ClipboardType) -> Vec<Action> {
+        let mut actions = vec![
+            Action {
+                id: "paste".to_string(),
+                name: "Paste".to_string(),
+                icon: None,
+                is_default: true,
+                prevent_hide: false,
+                hotkey: None,
+            },
+            Action {
+                id: "copy".to_string(),
+                name: "Copy to Clipboard".to_string(),
+                icon: Some(WoxImage::emoji("📋")),
+                is_default: false,
+                prevent_hide: false,
+                hotkey: None,
+            },
+        ];
+        if *item_type == ClipboardType::Text {
+            actions.extend(crate::clipboard_paste::transform_actions());
+        }
+        actions
+    }
+    
     fn format_timestamp(dt: &DateTime<Local>) -> String {
         let now = Local::now();
         let diff = now.signed_duration_since(*dt);
@@ -169,6 +259,9 @@ impl Plugin for ClipboardPlugin {
                 plugin_id: self.metadata.id.clone(),
                 refreshable: false,
                 actions: vec![],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             }]);
         }
         
@@ -208,16 +301,10 @@ impl Plugin for ClipboardPlugin {
                 group: Some("Clipboard".to_string()),
                 plugin_id: self.metadata.id.clone(),
                 refreshable: false,
-                actions: vec![
-                    Action {
-                        id: "paste".to_string(),
-                        name: "Copy to Clipboard".to_string(),
-                        icon: None,
-                        is_default: true,
-                        prevent_hide: false,
-                        hotkey: None,
-                    },
-                ],
+                actions: Self::item_actions(&item.item_type),
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
             
             // 限制结果数量
@@ -229,13 +316,22 @@ impl Plugin for ClipboardPlugin {
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         let history = self.history.read().await;
+        let Some(item) = history.iter().find(|i| i.id == result_id) else {
+            return Ok(ExecuteOutcome::Hide);
+        };
         
-        if let Some(item) = history.iter().find(|i| i.id == result_id) {
-            Self::copy_to_clipboard(&item.content).await?;
+        match action_id {
+            "copy" => {
+                Self::copy_to_clipboard(&item.content).await?;
+                Ok(ExecuteOutcome::Hide)
+            }
+            // 粘贴到启动器显示前的窗口（可选变换）
+            _ => {
+                crate::clipboard_paste::paste_text(&item.content, PasteTransform::from_action_id(action_id))?;
+                Ok(ExecuteOutcome::Hide)
+            }
         }
-        
-        Ok(())
     }
 }
@@ -0,0 +1,212 @@
+// 颜色解析与转换 - 支持 #rgb / #rrggbb、rgb(r, g, b)、hsl(h, s%, l%) 输入，
+// 输出 HEX / RGB / HSL 表示、调亮/调暗变体，以及结果图标使用的色块
+
+use base64::{engine::general_purpose, Engine as _};
+
+/// 调亮/调暗的亮度步长（HSL 亮度百分点）
+pub const SHADE_STEPS: &[f64] = &[10.0, 20.0];
+
+/// RGB 颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// 解析颜色输入（不区分大小写，允许多余空白）
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().to_lowercase();
+        if let Some(hex) = input.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+        if let Some(args) = function_args(&input, "rgb") {
+            let [r, g, b] = args;
+            return Some(Self::new(channel(r)?, channel(g)?, channel(b)?));
+        }
+        if let Some(args) = function_args(&input, "hsl") {
+            let [h, s, l] = args;
+            let h = h.strip_suffix("deg").unwrap_or(h).parse::<f64>().ok()?;
+            return Some(Self::from_hsl(h, percent(s)?, percent(l)?));
+        }
+        None
+    }
+
+    /// 由 HSL 构造（h 为角度，s / l 为 0-100 的百分比）
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let h = h.rem_euclid(360.0) / 360.0;
+        let s = (s / 100.0).clamp(0.0, 1.0);
+        let l = (l / 100.0).clamp(0.0, 1.0);
+
+        if s == 0.0 {
+            let v = to_byte(l);
+            return Self::new(v, v, v);
+        }
+
+        let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+        let p = 2.0 * l - q;
+        Self::new(
+            to_byte(hue_to_rgb(p, q, h + 1.0 / 3.0)),
+            to_byte(hue_to_rgb(p, q, h)),
+            to_byte(hue_to_rgb(p, q, h - 1.0 / 3.0)),
+        )
+    }
+
+    /// 转为 HSL（h 为角度，s / l 为 0-100 的百分比）
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let r = self.r as f64 / 255.0;
+        let g = self.g as f64 / 255.0;
+        let b = self.b as f64 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+
+        if max == min {
+            return (0.0, 0.0, l * 100.0);
+        }
+
+        let d = max - min;
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s * 100.0, l * 100.0)
+    }
+
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    pub fn rgb(self) -> String {
+        format!("rgb({}, {}, {})", self.r, self.g, self.b)
+    }
+
+    pub fn hsl(self) -> String {
+        let (h, s, l) = self.to_hsl();
+        format!("hsl({}, {}%, {}%)", h.round() as i32 % 360, s.round(), l.round())
+    }
+
+    /// 调整 HSL 亮度（正数调亮，负数调暗）
+    pub fn shade(self, delta: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::from_hsl(h, s, (l + delta).clamp(0.0, 100.0))
+    }
+
+    /// 色块图标（SVG data URL，作为 WoxImage::Base64 使用）
+    pub fn swatch(self) -> String {
+        let svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' width='32' height='32' viewBox='0 0 32 32'>\
+             <rect x='2' y='2' width='28' height='28' rx='6' fill='{}' stroke='rgba(128,128,128,0.6)' stroke-width='1'/></svg>",
+            self.hex()
+        );
+        format!("data:image/svg+xml;base64,{}", general_purpose::STANDARD.encode(svg))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    let byte = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+    Some(Color::new(byte(0)?, byte(2)?, byte(4)?))
+}
+
+/// 取出 name(a, b, c) 的三个参数（逗号或空白分隔）
+fn function_args<'a>(input: &'a str, name: &str) -> Option<[&'a str; 3]> {
+    let inner = input.strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')?;
+    let mut parts = inner.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty());
+    let args = [parts.next()?, parts.next()?, parts.next()?];
+    parts.next().is_none().then_some(args)
+}
+
+fn channel(value: &str) -> Option<u8> {
+    value.parse::<u8>().ok()
+}
+
+fn percent(value: &str) -> Option<f64> {
+    let value = value.strip_suffix('%').unwrap_or(value).parse::<f64>().ok()?;
+    (0.0..=100.0).contains(&value).then_some(value)
+}
+
+fn hue_to_rgb(p: f64, q: f64, t: f64) -> f64 {
+    let t = t.rem_euclid(1.0);
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 0.5 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let orange = Color::new(255, 136, 0);
+        assert_eq!(Color::parse("#ff8800"), Some(orange));
+        assert_eq!(Color::parse(" #FF8800 "), Some(orange));
+        assert_eq!(Color::parse("#f80"), Some(orange));
+        assert_eq!(Color::parse("rgb(255,136,0)"), Some(orange));
+        assert_eq!(Color::parse("RGB(255 136 0)"), Some(orange));
+        assert_eq!(Color::parse("hsl(32, 100%, 50%)"), Some(orange));
+        assert_eq!(Color::parse("hsl(32deg 100% 50%)"), Some(orange));
+
+        assert_eq!(Color::parse("#ff88"), None);
+        assert_eq!(Color::parse("#gg0000"), None);
+        assert_eq!(Color::parse("rgb(256, 0, 0)"), None);
+        assert_eq!(Color::parse("rgb(1, 2)"), None);
+        assert_eq!(Color::parse("hsl(0, 120%, 50%)"), None);
+        assert_eq!(Color::parse("ff8800"), None);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let orange = Color::new(255, 136, 0);
+        assert_eq!(orange.hex(), "#ff8800");
+        assert_eq!(orange.rgb(), "rgb(255, 136, 0)");
+        assert_eq!(orange.hsl(), "hsl(32, 100%, 50%)");
+
+        assert_eq!(Color::new(128, 128, 128).hsl(), "hsl(0, 0%, 50%)");
+        for color in [Color::new(18, 52, 86), Color::new(0, 0, 0), Color::new(255, 255, 255), Color::new(200, 30, 120)] {
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Color::from_hsl(h, s, l), color);
+        }
+    }
+
+    #[test]
+    fn test_shade_and_swatch() {
+        let orange = Color::new(255, 136, 0);
+        assert_eq!(orange.shade(20.0).hsl(), "hsl(32, 100%, 70%)");
+        assert_eq!(orange.shade(-20.0).hsl(), "hsl(32, 100%, 30%)");
+        assert_eq!(Color::new(255, 255, 255).shade(10.0), Color::new(255, 255, 255));
+
+        let swatch = orange.swatch();
+        let svg = general_purpose::STANDARD
+            .decode(swatch.strip_prefix("data:image/svg+xml;base64,").unwrap())
+            .unwrap();
+        assert!(String::from_utf8(svg).unwrap().contains("fill='#ff8800'"));
+    }
+}
@@ -0,0 +1,305 @@
+// 文件内容搜索（全文检索）- `content:关键词` 或 `ff 关键词`
+// 候选文件来自 BFS 内存索引（若已加载）或直接遍历配置的根目录，按扩展名/大小过滤后并行逐行匹配
+// 匹配的行片段放在预览中；查询中的 ext:/path:/size:/modified: 过滤词同样作用于候选文件
+
+use crate::plugin::file_filter::FileFilters;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 内容搜索前缀
+pub const CONTENT_PREFIX: &str = "content:";
+
+/// 内容搜索触发词
+pub const CONTENT_KEYWORD: &str = "ff";
+
+/// 每个文件最多保留的匹配行
+const MAX_LINES_PER_FILE: usize = 5;
+
+/// 行片段最大字符数
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// 判断二进制文件时检查的字节数
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// 内容搜索配置（保存在 file_search 插件配置中）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentSearchConfig {
+    /// 搜索根目录
+    pub content_roots: Vec<PathBuf>,
+    /// 参与搜索的扩展名（小写，不带点）
+    pub content_extensions: Vec<String>,
+    /// 单个文件大小上限（KB）
+    pub content_max_file_kb: u64,
+    /// 单次最多检查的文件数
+    pub content_max_files: usize,
+}
+
+impl Default for ContentSearchConfig {
+    fn default() -> Self {
+        let content_roots = directories::UserDirs::new()
+            .map(|dirs| {
+                [dirs.document_dir(), dirs.desktop_dir()]
+                    .into_iter()
+                    .flatten()
+                    .map(Path::to_path_buf)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            content_roots,
+            content_extensions: [
+                "txt", "md", "markdown", "log", "csv", "json", "yaml", "yml", "toml", "ini", "cfg", "conf", "xml",
+                "html", "css", "scss", "js", "jsx", "ts", "tsx", "vue", "py", "rs", "go", "java", "kt", "c", "h",
+                "cpp", "hpp", "cs", "rb", "php", "swift", "sh", "ps1", "bat", "sql",
+            ]
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect(),
+            content_max_file_kb: 1024,
+            content_max_files: 20_000,
+        }
+    }
+}
+
+impl ContentSearchConfig {
+    /// 从 file_search 插件配置中读取（缺失字段使用默认值）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        serde_json::from_value(config.clone()).unwrap_or_default()
+    }
+
+    /// 文件是否在搜索范围内（根目录 + 扩展名）
+    pub fn accepts(&self, path: &Path) -> bool {
+        let ext_ok = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| self.content_extensions.iter().any(|allowed| allowed.eq_ignore_ascii_case(e)))
+            .unwrap_or(false);
+        ext_ok && self.content_roots.iter().any(|root| path.starts_with(root))
+    }
+}
+
+/// 从查询中取出内容搜索关键词（`content:xxx` 或触发词 `ff` 路由）
+pub fn parse_content_query<'a>(search: &'a str, trigger_keyword: &str) -> Option<&'a str> {
+    if trigger_keyword.eq_ignore_ascii_case(CONTENT_KEYWORD) {
+        return Some(search.trim());
+    }
+    let prefix = search.get(..CONTENT_PREFIX.len())?;
+    prefix
+        .eq_ignore_ascii_case(CONTENT_PREFIX)
+        .then(|| search[CONTENT_PREFIX.len()..].trim())
+}
+
+/// 单个文件的匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMatch {
+    pub path: PathBuf,
+    /// (行号, 行片段)，行号从 1 开始
+    pub lines: Vec<(usize, String)>,
+    /// 文件中匹配的总行数
+    pub total: usize,
+}
+
+impl ContentMatch {
+    /// 预览文本
+    pub fn preview(&self) -> String {
+        let mut text = format!("{}\n\n", self.path.display());
+        for (line_no, snippet) in &self.lines {
+            text.push_str(&format!("{:>5} │ {}\n", line_no, snippet));
+        }
+        if self.total > self.lines.len() {
+            text.push_str(&format!("\n… {} more matches", self.total - self.lines.len()));
+        }
+        text
+    }
+}
+
+/// 遍历根目录收集候选文件（BFS 索引不可用时）
+pub fn walk_candidates(config: &ContentSearchConfig) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    for root in &config.content_roots {
+        let walker = walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| !is_hidden_or_vendor(entry.file_name().to_str().unwrap_or_default()));
+        for entry in walker.flatten() {
+            if candidates.len() >= config.content_max_files {
+                return candidates;
+            }
+            if entry.file_type().is_file() && config.accepts(entry.path()) {
+                candidates.push(entry.into_path());
+            }
+        }
+    }
+    candidates
+}
+
+/// 跳过隐藏目录和依赖/构建目录
+fn is_hidden_or_vendor(name: &str) -> bool {
+    (name.starts_with('.') && name.len() > 1) || matches!(name, "node_modules" | "target" | "dist" | "build" | "__pycache__")
+}
+
+/// 并行搜索候选文件，最多返回 limit 个匹配文件（按匹配行数降序）
+pub fn search(
+    needle: &str,
+    candidates: &[PathBuf],
+    filters: &FileFilters,
+    config: &ContentSearchConfig,
+    limit: usize,
+) -> Vec<ContentMatch> {
+    let needle = needle.to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let max_bytes = config.content_max_file_kb * 1024;
+    let found = AtomicUsize::new(0);
+
+    let mut matches: Vec<ContentMatch> = candidates
+        .par_iter()
+        .take(config.content_max_files)
+        .filter_map(|path| {
+            // 已找到足够结果时跳过剩余文件
+            if found.load(Ordering::Relaxed) >= limit {
+                return None;
+            }
+
+            let metadata = std::fs::metadata(path).ok()?;
+            if !metadata.is_file() || metadata.len() > max_bytes {
+                return None;
+            }
+            let path_str = path.to_string_lossy();
+            let name = path.file_name()?.to_string_lossy();
+            let stat = || {
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+                Some((metadata.len(), modified))
+            };
+            if !filters.matches(&path_str, &name, false, stat) {
+                return None;
+            }
+
+            let content = read_text(path, max_bytes)?;
+            let result = match_lines(&content, &needle).map(|(lines, total)| ContentMatch {
+                path: path.clone(),
+                lines,
+                total,
+            })?;
+            found.fetch_add(1, Ordering::Relaxed);
+            Some(result)
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.path.cmp(&b.path)));
+    matches.truncate(limit);
+    matches
+}
+
+/// 读取文本文件（二进制文件返回 None）
+fn read_text(path: &Path, max_bytes: u64) -> Option<String> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path).ok()?.take(max_bytes).read_to_end(&mut bytes).ok()?;
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 逐行匹配（needle 已转小写），返回 (前几条行片段, 总匹配行数)
+fn match_lines(content: &str, needle: &str) -> Option<(Vec<(usize, String)>, usize)> {
+    let mut lines = Vec::new();
+    let mut total = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        let lower = line.to_lowercase();
+        let Some(pos) = lower.find(needle) else {
+            continue;
+        };
+        total += 1;
+        if lines.len() < MAX_LINES_PER_FILE {
+            // 小写转换可能改变字节长度，按字符位置截取片段
+            let char_pos = lower[..pos].chars().count();
+            lines.push((index + 1, snippet(line, char_pos)));
+        }
+    }
+
+    (total > 0).then_some((lines, total))
+}
+
+/// 截取匹配位置附近的片段
+fn snippet(line: &str, char_pos: usize) -> String {
+    let chars: Vec<char> = line.trim_end().chars().collect();
+    let start = char_pos.saturating_sub(MAX_SNIPPET_CHARS / 3).min(chars.len());
+    let end = (start + MAX_SNIPPET_CHARS).min(chars.len());
+
+    let mut text: String = chars[start..end].iter().collect();
+    if start > 0 {
+        text.insert(0, '…');
+    }
+    if end < chars.len() {
+        text.push('…');
+    }
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_content_query() {
+        assert_eq!(parse_content_query("content: TODO fix", ""), Some("TODO fix"));
+        assert_eq!(parse_content_query("Content:main", ""), Some("main"));
+        assert_eq!(parse_content_query("needle", "ff"), Some("needle"));
+        assert_eq!(parse_content_query("report.pdf", ""), None);
+        assert_eq!(parse_content_query("内容", ""), None);
+    }
+
+    #[test]
+    fn test_match_lines() {
+        let content = "fn main() {\n    // TODO: handle errors\n    println!(\"todo\");\n}\n";
+        let (lines, total) = match_lines(content, "todo").unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(lines[0], (2, "// TODO: handle errors".to_string()));
+        assert_eq!(lines[1].0, 3);
+        assert!(match_lines(content, "missing").is_none());
+    }
+
+    #[test]
+    fn test_snippet_truncates_long_lines() {
+        let line = format!("{}needle{}", "a".repeat(300), "b".repeat(300));
+        let text = snippet(&line, 300);
+        assert!(text.starts_with('…') && text.ends_with('…'));
+        assert!(text.contains("needle"));
+    }
+
+    #[test]
+    fn test_search_files() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_content_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.rs"), "let needle = 1;\nneedle again\n").unwrap();
+        std::fs::write(dir.join("b.md"), "no match here\n").unwrap();
+        std::fs::write(dir.join("c.txt"), b"needle\0binary").unwrap();
+
+        let config = ContentSearchConfig {
+            content_roots: vec![dir.clone()],
+            ..Default::default()
+        };
+        let candidates = walk_candidates(&config);
+        assert_eq!(candidates.len(), 3);
+
+        let matches = search("NEEDLE", &candidates, &FileFilters::default(), &config, 10);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, dir.join("a.rs"));
+        assert_eq!(matches[0].total, 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
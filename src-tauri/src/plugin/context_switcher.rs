@@ -0,0 +1,205 @@
+// 工作区上下文切换插件 - "ctx " 关键词切换上下文，并提供当前上下文的快捷链接
+
+use crate::core::types::*;
+use crate::plugin::Plugin;
+use crate::workspace_context::WORKSPACE_CONTEXTS;
+use anyhow::Result;
+use async_trait::async_trait;
+
+const KEYWORD: &str = "ctx";
+const DEFAULT_CONTEXT_ID: &str = "__default__";
+const QUICKLINK_PREFIX: &str = "quicklink:";
+
+pub struct ContextSwitcherPlugin {
+    metadata: PluginMetadata,
+}
+
+impl ContextSwitcherPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata {
+                id: "context_switcher".to_string(),
+                name: "Context Switcher".to_string(),
+                description: "Switch between work/personal/gaming contexts".to_string(),
+                author: "iLauncher".to_string(),
+                version: "1.0.0".to_string(),
+                icon: WoxImage::emoji("🗂️"),
+                trigger_keywords: vec![KEYWORD.to_string()],
+                commands: vec![],
+                settings: vec![],
+                supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
+                plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Productivity).with_tags(&["workspace"]),
+            },
+        }
+    }
+
+    /// 列出可切换的上下文
+    fn context_results(&self, filter: &str) -> Vec<QueryResult> {
+        let filter = filter.to_lowercase();
+        let active_id = WORKSPACE_CONTEXTS.active_id();
+
+        let mut entries = vec![(
+            DEFAULT_CONTEXT_ID.to_string(),
+            "Default".to_string(),
+            "🌐".to_string(),
+            "All plugins and search roots".to_string(),
+        )];
+        for context in WORKSPACE_CONTEXTS.list() {
+            let summary = format!(
+                "{} disabled plugins · {} quicklinks · {} search roots",
+                context.disabled_plugins.len(),
+                context.quicklinks.len(),
+                context.search_roots.len()
+            );
+            entries.push((context.id, context.name, context.icon, summary));
+        }
+
+        entries
+            .into_iter()
+            .filter(|(id, name, _, _)| {
+                filter.is_empty() || id.to_lowercase().contains(&filter) || name.to_lowercase().contains(&filter)
+            })
+            .map(|(id, name, icon, summary)| {
+                let is_active = match &active_id {
+                    Some(active) => active == &id,
+                    None => id == DEFAULT_CONTEXT_ID,
+                };
+
+                QueryResult {
+                    id: id.clone(),
+                    title: if is_active { format!("{} ✓", name) } else { format!("Switch to {}", name) },
+                    subtitle: summary,
+                    icon: WoxImage::emoji(&icon),
+                    preview: None,
+                    score: if is_active { 90 } else { 100 },
+                    context_data: serde_json::Value::Null,
+                    group: Some("Contexts".to_string()),
+                    plugin_id: self.metadata.id.clone(),
+                    refreshable: false,
+                    actions: vec![Action {
+                        id: "switch".to_string(),
+                        name: "Switch Context".to_string(),
+                        icon: None,
+                        is_default: true,
+                        hotkey: None,
+                        prevent_hide: true,
+                    }],
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// 当前上下文中匹配的快捷链接
+    fn quicklink_results(&self, search: &str) -> Vec<QueryResult> {
+        let Some(context) = WORKSPACE_CONTEXTS.active() else {
+            return Vec::new();
+        };
+        let search = search.to_lowercase();
+
+        context
+            .quicklinks
+            .iter()
+            .filter(|link| link.name.to_lowercase().contains(&search))
+            .map(|link| QueryResult {
+                id: format!("{}{}", QUICKLINK_PREFIX, link.target),
+                title: link.name.clone(),
+                subtitle: format!("{} · {}", context.name, link.target),
+                icon: WoxImage::emoji("🔗"),
+                preview: None,
+                score: 80,
+                context_data: serde_json::Value::Null,
+                group: Some("Quicklinks".to_string()),
+                plugin_id: self.metadata.id.clone(),
+                refreshable: false,
+                actions: vec![Action {
+                    id: "open".to_string(),
+                    name: "Open".to_string(),
+                    icon: None,
+                    is_default: true,
+                    hotkey: None,
+                    prevent_hide: false,
+                }],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// 打开快捷链接（URL 或本地路径）
+    async fn open_target(target: &str) -> Result<()> {
+        let target = target.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            #[cfg(target_os = "windows")]
+            {
+                use std::os::windows::process::CommandExt;
+                const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+                std::process::Command::new("cmd")
+                    .args(["/C", "start", "", &target])
+                    .creation_flags(CREATE_NO_WINDOW)
+                    .spawn()?;
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                std::process::Command::new("open").arg(&target).spawn()?;
+            }
+
+            #[cfg(target_os = "linux")]
+            {
+                std::process::Command::new("xdg-open").arg(&target).spawn()?;
+            }
+
+            tracing::info!("Opened quicklink: {}", target);
+            Ok(())
+        })
+        .await?
+    }
+}
+
+#[async_trait]
+impl Plugin for ContextSwitcherPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let search = ctx.search.trim();
+
+        if search == KEYWORD {
+            return Ok(self.context_results(""));
+        }
+        if let Some(filter) = search.strip_prefix("ctx ") {
+            return Ok(self.context_results(filter.trim()));
+        }
+
+        // 快捷链接至少输入2个字符
+        if search.chars().count() < 2 {
+            return Ok(Vec::new());
+        }
+        Ok(self.quicklink_results(search))
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        match action_id {
+            "switch" => {
+                let id = (result_id != DEFAULT_CONTEXT_ID).then_some(result_id);
+                WORKSPACE_CONTEXTS.switch(id)?;
+                Ok(ExecuteOutcome::Hide)
+            }
+            "open" => {
+                let target = result_id.strip_prefix(QUICKLINK_PREFIX).unwrap_or(result_id);
+                Self::open_target(target).await?;
+                Ok(ExecuteOutcome::Hide)
+            }
+            _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
+        }
+    }
+}
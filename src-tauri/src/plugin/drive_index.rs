@@ -0,0 +1,462 @@
+// 按驱动器选择索引策略：NTFS 固定磁盘走 MFT，exFAT/FAT32/可移动驱动器走 BFS 扫描 + 文件变更监听
+// 驱动器插拔由后台轮询发现：新插入的驱动器自动扫描并监听，拔出的驱动器从索引中移除
+// MFT 索引不可用（扫描失败、索引损坏、长时间未就绪）的驱动器同样退回 BFS 监听索引
+
+use crate::utils::drives::{self, DriveInfo, DriveKind};
+use crate::utils::index_exclusions::IndexExclusions;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, MAIN_SEPARATOR};
+use std::sync::{Arc, Weak};
+
+/// 驱动器插拔检测间隔
+const HOTPLUG_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// 遍历时跳过的系统目录
+const SKIP_NAMES: &[&str] = &[
+    "$Recycle.Bin",
+    "System Volume Information",
+    "Config.Msi",
+    "Recovery",
+    "$RECYCLE.BIN",
+];
+
+/// 驱动器索引策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveStrategy {
+    /// MFT 服务扫描 + USN 增量更新
+    Mft,
+    /// 启动时 BFS 全量扫描（结果缓存）
+    Bfs,
+    /// BFS 扫描 + 文件变更监听，随驱动器插拔加入/移出索引
+    Watched,
+    /// 不索引（网络驱动器、光驱、未插入介质的读卡器）
+    Skip,
+}
+
+/// 为驱动器选择索引策略
+pub fn select_strategy(drive: &DriveInfo, use_mft: bool) -> DriveStrategy {
+    if drive.file_system.is_empty() {
+        return DriveStrategy::Skip;
+    }
+    match drive.kind {
+        DriveKind::Fixed if drive.is_ntfs() && use_mft => DriveStrategy::Mft,
+        DriveKind::Fixed if drive.is_ntfs() => DriveStrategy::Bfs,
+        DriveKind::Fixed | DriveKind::Removable => DriveStrategy::Watched,
+        DriveKind::Network | DriveKind::CdRom | DriveKind::Other => DriveStrategy::Skip,
+    }
+}
+
+/// 退回 BFS 的 MFT 驱动器（诊断信息）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriveFallback {
+    pub letter: char,
+    pub reason: String,
+}
+
+/// MFT 索引不可用的驱动器 → 原因（由 file_search 的健康检查更新）
+static MFT_FALLBACKS: Lazy<RwLock<BTreeMap<char, String>>> = Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// 更新退回 BFS 的驱动器列表，返回是否有变化
+pub fn set_mft_fallbacks(fallbacks: BTreeMap<char, String>) -> bool {
+    let mut current = MFT_FALLBACKS.write();
+    if *current == fallbacks {
+        return false;
+    }
+    for (letter, reason) in &fallbacks {
+        if !current.contains_key(letter) {
+            tracing::warn!("💾 Drive {}: {}, falling back to BFS index", letter, reason);
+        }
+    }
+    for letter in current.keys().filter(|letter| !fallbacks.contains_key(letter)) {
+        tracing::info!("💾 Drive {}: MFT index healthy again, leaving BFS fallback", letter);
+    }
+    *current = fallbacks;
+    true
+}
+
+/// 驱动器是否已退回 BFS
+pub fn is_mft_fallback(letter: char) -> bool {
+    MFT_FALLBACKS.read().contains_key(&letter.to_ascii_uppercase())
+}
+
+/// 当前退回 BFS 的驱动器
+pub fn mft_fallbacks() -> Vec<DriveFallback> {
+    MFT_FALLBACKS
+        .read()
+        .iter()
+        .map(|(&letter, reason)| DriveFallback { letter, reason: reason.clone() })
+        .collect()
+}
+
+/// 考虑 MFT 可用性后的实际策略：MFT 索引不可用的驱动器改为 BFS 扫描 + 监听
+fn effective_strategy(drive: &DriveInfo, use_mft: bool, fallbacks: &BTreeMap<char, String>) -> DriveStrategy {
+    match select_strategy(drive, use_mft) {
+        DriveStrategy::Mft if fallbacks.contains_key(&drive.letter) => DriveStrategy::Watched,
+        strategy => strategy,
+    }
+}
+
+/// 路径中是否包含需要跳过的系统目录
+fn is_skipped(path: &Path) -> bool {
+    path.components()
+        .any(|c| SKIP_NAMES.iter().any(|skip| c.as_os_str() == *skip))
+}
+
+/// BFS 遍历目录树（不含 base 本身），对每一项回调 (完整路径, 名称, 是否目录)
+/// 跳过系统目录和用户排除规则，排除的目录不再深入
+pub fn walk(base: &Path, exclusions: &IndexExclusions, mut visit: impl FnMut(String, String, bool)) {
+    // 使用 VecDeque 作为 BFS 队列，比递归更快
+    let mut queue = VecDeque::with_capacity(1000);
+    queue.push_back(base.to_path_buf());
+
+    while let Some(current_dir) = queue.pop_front() {
+        // 快速读取目录，忽略错误
+        let Ok(entries) = std::fs::read_dir(&current_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if SKIP_NAMES.contains(&file_name.as_str()) {
+                continue;
+            }
+
+            let path = entry.path();
+            let path_str = path.to_string_lossy().into_owned();
+            if exclusions.is_excluded(&path_str) {
+                continue;
+            }
+
+            // 快速判断是否是目录（避免元数据查询）
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            visit(path_str, file_name, is_dir);
+
+            if is_dir {
+                queue.push_back(path);
+            }
+        }
+    }
+}
+
+/// 索引条目（完整路径作为键）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// 监听索引：覆盖不支持 MFT 的驱动器
+pub struct WatchedDriveIndex {
+    /// 驱动器 → (完整路径 → 条目)，BTreeMap 便于按目录前缀删除子项
+    entries: RwLock<HashMap<char, BTreeMap<String, IndexedEntry>>>,
+    /// 已接管的驱动器及其监听器（监听失败时为 None，丢弃即停止监听）
+    watchers: Mutex<HashMap<char, Option<RecommendedWatcher>>>,
+    exclusions: IndexExclusions,
+}
+
+impl WatchedDriveIndex {
+    pub fn new(exclusions: IndexExclusions) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            watchers: Mutex::new(HashMap::new()),
+            exclusions,
+        }
+    }
+
+    /// 遍历所有条目，visit 返回 false 时停止
+    pub fn for_each(&self, mut visit: impl FnMut(&str, &IndexedEntry) -> bool) {
+        let entries = self.entries.read();
+        for (path, entry) in entries.values().flat_map(|drive| drive.iter()) {
+            if !visit(path, entry) {
+                return;
+            }
+        }
+    }
+
+    /// 移除已不存在的路径（执行结果时发现文件丢失，监听事件可能已丢失）
+    pub fn forget(&self, path: &Path) {
+        if let Some(letter) = path.to_string_lossy().chars().next().map(|c| c.to_ascii_uppercase()) {
+            self.remove_path(letter, path);
+        }
+    }
+
+    /// 后台轮询驱动器列表，按策略接管或移除驱动器（仅 Windows 有盘符概念）
+    pub fn spawn_monitor(self: &Arc<Self>, use_mft: bool) {
+        if !cfg!(target_os = "windows") {
+            return;
+        }
+
+        let index = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                let Some(index) = index.upgrade() else {
+                    break;
+                };
+                // 读取卷信息可能因介质唤醒而阻塞
+                let drives = tokio::task::spawn_blocking(drives::list_drives).await.unwrap_or_default();
+                index.sync_drives(&drives, use_mft);
+                drop(index);
+
+                tokio::time::sleep(HOTPLUG_POLL_INTERVAL).await;
+            }
+        });
+    }
+
+    /// 与当前驱动器列表同步：新驱动器开始扫描和监听，消失的驱动器移出索引
+    fn sync_drives(self: &Arc<Self>, drives: &[DriveInfo], use_mft: bool) {
+        let fallbacks = MFT_FALLBACKS.read().clone();
+        let wanted: HashMap<char, &DriveInfo> = drives
+            .iter()
+            .filter(|drive| effective_strategy(drive, use_mft, &fallbacks) == DriveStrategy::Watched)
+            .map(|drive| (drive.letter, drive))
+            .collect();
+        let current: HashSet<char> = self.watchers.lock().keys().copied().collect();
+
+        for letter in current.iter().filter(|letter| !wanted.contains_key(letter)) {
+            tracing::info!("💾 Drive {}: removed, dropping from index", letter);
+            self.remove_drive(*letter);
+        }
+
+        for (letter, drive) in wanted {
+            if !current.contains(&letter) {
+                tracing::info!("💾 Drive {}: {} ({:?}), indexing with file watcher", letter, drive.file_system, drive.kind);
+                self.add_drive(drive.clone());
+            }
+        }
+    }
+
+    /// 接管驱动器：先监听再扫描，避免扫描期间的变更丢失
+    fn add_drive(self: &Arc<Self>, drive: DriveInfo) {
+        let letter = drive.letter;
+        let watcher = match self.create_watcher(&drive) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                tracing::warn!("Failed to watch drive {}: {} (index will not update until re-plugged)", letter, e);
+                None
+            }
+        };
+        self.watchers.lock().insert(letter, watcher);
+        self.entries.write().entry(letter).or_default();
+
+        let index = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let start = std::time::Instant::now();
+            let mut scanned = BTreeMap::new();
+            walk(&drive.root(), &index.exclusions, |path, name, is_dir| {
+                scanned.insert(path, IndexedEntry { name, is_dir });
+            });
+
+            // 扫描期间驱动器被拔出则丢弃结果
+            if !index.watchers.lock().contains_key(&letter) {
+                return;
+            }
+            let mut entries = index.entries.write();
+            let current = entries.entry(letter).or_default();
+            // 保留扫描期间监听到的新增项
+            scanned.append(current);
+            *current = scanned;
+
+            tracing::info!(
+                "✓ Drive {}: indexed {} files in {:.1}s",
+                letter,
+                current.len(),
+                start.elapsed().as_secs_f32()
+            );
+        });
+    }
+
+    fn remove_drive(&self, letter: char) {
+        self.watchers.lock().remove(&letter);
+        self.entries.write().remove(&letter);
+    }
+
+    fn create_watcher(self: &Arc<Self>, drive: &DriveInfo) -> notify::Result<RecommendedWatcher> {
+        // 监听回调持有弱引用，避免 索引 → 监听器 → 索引 的循环引用
+        let index: Weak<Self> = Arc::downgrade(self);
+        let letter = drive.letter;
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+            Ok(event) => {
+                if let Some(index) = index.upgrade() {
+                    index.apply_event(letter, &event);
+                }
+            }
+            Err(e) => tracing::debug!("Drive {} watcher error: {}", letter, e),
+        })?;
+        watcher.watch(&drive.root(), RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+
+    /// 应用文件变更事件
+    fn apply_event(&self, letter: char, event: &Event) {
+        match event.kind {
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                event.paths.iter().for_each(|path| self.insert_path(letter, path));
+            }
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                event.paths.iter().for_each(|path| self.remove_path(letter, path));
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let [from, to] = event.paths.as_slice() {
+                    self.remove_path(letter, from);
+                    self.insert_path(letter, to);
+                }
+            }
+            // 无法区分重命名方向时按当前是否存在处理
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &event.paths {
+                    if path.exists() {
+                        self.insert_path(letter, path);
+                    } else {
+                        self.remove_path(letter, path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 加入新建/移入的路径（目录连同子项一起加入）
+    fn insert_path(&self, letter: char, path: &Path) {
+        let path_str = path.to_string_lossy().into_owned();
+        if is_skipped(path) || self.exclusions.is_excluded(&path_str) {
+            return;
+        }
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+            return;
+        };
+
+        let is_dir = path.is_dir();
+        let mut added = vec![(path_str, IndexedEntry { name, is_dir })];
+        if is_dir {
+            walk(path, &self.exclusions, |path, name, is_dir| {
+                added.push((path, IndexedEntry { name, is_dir }));
+            });
+        }
+
+        if let Some(entries) = self.entries.write().get_mut(&letter) {
+            entries.extend(added);
+        }
+    }
+
+    /// 移除路径及其所有子项
+    fn remove_path(&self, letter: char, path: &Path) {
+        let path_str = path.to_string_lossy().into_owned();
+        let prefix = format!("{}{}", path_str.trim_end_matches(MAIN_SEPARATOR), MAIN_SEPARATOR);
+
+        let mut all = self.entries.write();
+        let Some(entries) = all.get_mut(&letter) else {
+            return;
+        };
+        entries.remove(&path_str);
+        let children: Vec<String> = entries
+            .range(prefix.clone()..)
+            .take_while(|(child, _)| child.starts_with(&prefix))
+            .map(|(child, _)| child.clone())
+            .collect();
+        for child in children {
+            entries.remove(&child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, RemoveKind};
+
+    fn drive(letter: char, kind: DriveKind, file_system: &str) -> DriveInfo {
+        DriveInfo { letter, kind, file_system: file_system.to_string() }
+    }
+
+    fn temp_index() -> (WatchedDriveIndex, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("ilauncher_drive_index_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index = WatchedDriveIndex::new(IndexExclusions::new(["node_modules".to_string()]));
+        index.entries.write().insert('T', BTreeMap::new());
+        (index, dir)
+    }
+
+    fn indexed_names(index: &WatchedDriveIndex) -> Vec<String> {
+        let mut names = Vec::new();
+        index.for_each(|_, entry| {
+            names.push(entry.name.clone());
+            true
+        });
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_select_strategy() {
+        assert_eq!(select_strategy(&drive('C', DriveKind::Fixed, "NTFS"), true), DriveStrategy::Mft);
+        assert_eq!(select_strategy(&drive('C', DriveKind::Fixed, "NTFS"), false), DriveStrategy::Bfs);
+        assert_eq!(select_strategy(&drive('D', DriveKind::Fixed, "exFAT"), true), DriveStrategy::Watched);
+        assert_eq!(select_strategy(&drive('E', DriveKind::Removable, "NTFS"), true), DriveStrategy::Watched);
+        assert_eq!(select_strategy(&drive('F', DriveKind::Removable, "FAT32"), false), DriveStrategy::Watched);
+        assert_eq!(select_strategy(&drive('G', DriveKind::Removable, ""), true), DriveStrategy::Skip);
+        assert_eq!(select_strategy(&drive('Z', DriveKind::Network, "NTFS"), true), DriveStrategy::Skip);
+        assert_eq!(select_strategy(&drive('R', DriveKind::CdRom, "CDFS"), true), DriveStrategy::Skip);
+    }
+
+    #[test]
+    fn test_effective_strategy_falls_back_to_bfs() {
+        let fallbacks = BTreeMap::from([('C', "MFT scan failed".to_string())]);
+        let c = drive('C', DriveKind::Fixed, "NTFS");
+        let d = drive('D', DriveKind::Fixed, "NTFS");
+        assert_eq!(effective_strategy(&c, true, &fallbacks), DriveStrategy::Watched);
+        assert_eq!(effective_strategy(&d, true, &fallbacks), DriveStrategy::Mft);
+        // 未启用 MFT 时保持原策略
+        assert_eq!(effective_strategy(&c, false, &fallbacks), DriveStrategy::Bfs);
+    }
+
+    #[test]
+    fn test_walk_skips_excluded_and_system_dirs() {
+        let (index, dir) = temp_index();
+        std::fs::create_dir_all(dir.join("docs").join("node_modules")).unwrap();
+        std::fs::create_dir_all(dir.join("System Volume Information")).unwrap();
+        std::fs::write(dir.join("docs").join("a.txt"), "a").unwrap();
+
+        let mut names = Vec::new();
+        walk(&dir, &index.exclusions, |_, name, is_dir| names.push((name, is_dir)));
+        names.sort();
+        assert_eq!(names, vec![("a.txt".to_string(), false), ("docs".to_string(), true)]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_events() {
+        let (index, dir) = temp_index();
+        let folder = dir.join("photos");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("1.jpg"), "x").unwrap();
+
+        // 新建（或拷入）目录时子项一并加入
+        index.apply_event('T', &Event::new(EventKind::Create(CreateKind::Folder)).add_path(folder.clone()));
+        assert_eq!(indexed_names(&index), vec!["1.jpg", "photos"]);
+
+        // 重命名
+        let renamed = dir.join("holiday");
+        std::fs::rename(&folder, &renamed).unwrap();
+        index.apply_event(
+            'T',
+            &Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(folder.clone())
+                .add_path(renamed.clone()),
+        );
+        assert_eq!(indexed_names(&index), vec!["1.jpg", "holiday"]);
+
+        // 删除目录时子项一并移除；未接管的驱动器忽略事件
+        index.apply_event('T', &Event::new(EventKind::Remove(RemoveKind::Folder)).add_path(renamed.clone()));
+        index.apply_event('U', &Event::new(EventKind::Create(CreateKind::Folder)).add_path(dir.clone()));
+        assert!(indexed_names(&index).is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
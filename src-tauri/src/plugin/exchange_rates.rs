@@ -0,0 +1,303 @@
+// 汇率缓存 - 从可配置的汇率接口获取汇率并缓存到缓存目录；
+// 缓存过期后先返回旧汇率、后台刷新，离线时继续使用缓存
+
+use super::sandbox::{NetworkScope, PluginPermission, SandboxManager};
+use crate::core::clock::SharedClock;
+use crate::http::RequestBuilderExt;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// 默认汇率接口（免密钥，以 USD 为基准）
+pub const DEFAULT_PROVIDER: &str = "https://open.er-api.com/v6/latest/USD";
+
+/// 默认缓存有效期（小时）
+pub const DEFAULT_TTL_HOURS: u64 = 12;
+
+/// 缓存文件名（位于缓存目录）
+pub const CACHE_FILE: &str = "exchange_rates.json";
+
+/// 沙盒中允许访问的汇率接口域名
+pub const PROVIDER_DOMAINS: &[&str] = &["open.er-api.com", "api.frankfurter.app", "api.exchangerate.host"];
+
+/// 常用货币代码（尚无汇率缓存时，只有这些代码会触发联网获取）
+const COMMON_CURRENCIES: &[&str] = &[
+    "usd", "eur", "cny", "rmb", "jpy", "gbp", "hkd", "twd", "krw", "sgd", "aud", "cad", "chf", "nzd", "sek",
+    "nok", "dkk", "pln", "czk", "huf", "rub", "inr", "idr", "thb", "myr", "php", "vnd", "brl", "mxn", "zar",
+    "try", "aed", "sar", "ils",
+];
+
+/// 汇率表（以 base 为基准：1 base = rates[code] code）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateTable {
+    pub base: String,
+    pub rates: HashMap<String, f64>,
+    pub fetched_at: DateTime<Utc>,
+    pub provider: String,
+}
+
+impl RateTable {
+    /// 解析接口响应，兼容 {"base_code"|"base": "USD", "rates": {...}} 格式
+    pub fn parse(body: &str, provider: &str, fetched_at: DateTime<Utc>) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(alias = "base_code")]
+            base: Option<String>,
+            rates: Option<HashMap<String, f64>>,
+        }
+
+        let response: Response = serde_json::from_str(body).context("Invalid exchange rate response")?;
+        let rates: HashMap<String, f64> = response
+            .rates
+            .ok_or_else(|| anyhow!("Exchange rate response has no rates"))?
+            .into_iter()
+            .filter(|(_, rate)| rate.is_finite() && *rate > 0.0)
+            .map(|(code, rate)| (code.to_uppercase(), rate))
+            .collect();
+        if rates.is_empty() {
+            return Err(anyhow!("Exchange rate response has no rates"));
+        }
+
+        Ok(Self {
+            base: response.base.unwrap_or_else(|| "USD".to_string()).to_uppercase(),
+            rates,
+            fetched_at,
+            provider: provider.to_string(),
+        })
+    }
+
+    fn rate(&self, code: &str) -> Option<f64> {
+        let code = normalize_code(code);
+        if code == self.base {
+            Some(1.0)
+        } else {
+            self.rates.get(&code).copied()
+        }
+    }
+
+    pub fn supports(&self, code: &str) -> bool {
+        self.rate(code).is_some()
+    }
+
+    /// 通过基准货币换算任意两种货币
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        Some(amount / self.rate(from)? * self.rate(to)?)
+    }
+
+    /// 是否仍在有效期内（且来自当前配置的接口）
+    pub fn is_fresh(&self, provider: &str, ttl: chrono::Duration, now: DateTime<Utc>) -> bool {
+        self.provider == provider && now - self.fetched_at < ttl
+    }
+}
+
+/// 货币代码规范化（rmb 视为 CNY）
+pub fn normalize_code(code: &str) -> String {
+    match code.to_uppercase().as_str() {
+        "RMB" => "CNY".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// 汇率来源配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateSettings {
+    pub provider: String,
+    pub ttl_hours: u64,
+}
+
+impl Default for RateSettings {
+    fn default() -> Self {
+        Self {
+            provider: DEFAULT_PROVIDER.to_string(),
+            ttl_hours: DEFAULT_TTL_HOURS,
+        }
+    }
+}
+
+impl RateSettings {
+    fn ttl(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.ttl_hours.max(1) as i64)
+    }
+}
+
+/// 查询时使用的汇率
+#[derive(Debug, Clone)]
+pub struct Rates {
+    pub table: RateTable,
+    /// 已过期（刷新失败或正在后台刷新）
+    pub stale: bool,
+}
+
+pub struct ExchangeRates {
+    plugin_id: String,
+    clock: SharedClock,
+    sandbox: Arc<SandboxManager>,
+    cache_path: Option<PathBuf>,
+    settings: RwLock<RateSettings>,
+    table: RwLock<Option<RateTable>>,
+    refreshing: AtomicBool,
+}
+
+impl ExchangeRates {
+    /// 创建并读取磁盘缓存（缓存不存在或损坏时视为无缓存）
+    pub fn new(plugin_id: &str, clock: SharedClock, sandbox: Arc<SandboxManager>, cache_path: Option<PathBuf>) -> Self {
+        let table = cache_path.as_deref().and_then(load_cache);
+        Self {
+            plugin_id: plugin_id.to_string(),
+            clock,
+            sandbox,
+            cache_path,
+            settings: RwLock::new(RateSettings::default()),
+            table: RwLock::new(table),
+            refreshing: AtomicBool::new(false),
+        }
+    }
+
+    pub fn configure(&self, settings: RateSettings) {
+        *self.settings.write() = settings;
+    }
+
+    /// 输入是否像货币代码（有缓存时按缓存判断，否则只认常用货币）
+    pub fn recognizes(&self, code: &str) -> bool {
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return false;
+        }
+        match &*self.table.read() {
+            Some(table) => table.supports(code),
+            None => COMMON_CURRENCIES.contains(&code.to_lowercase().as_str()),
+        }
+    }
+
+    /// 获取汇率：缓存有效时直接返回；过期时返回旧汇率并在后台刷新；无缓存时联网获取
+    pub async fn rates(self: &Arc<Self>) -> Result<Rates> {
+        let settings = self.settings.read().clone();
+        let cached = self.table.read().clone();
+
+        match cached {
+            Some(table) if table.is_fresh(&settings.provider, settings.ttl(), self.clock.now()) => {
+                Ok(Rates { table, stale: false })
+            }
+            Some(table) => {
+                self.refresh_in_background();
+                Ok(Rates { table, stale: true })
+            }
+            None => Ok(Rates {
+                table: self.refresh(&settings.provider).await?,
+                stale: false,
+            }),
+        }
+    }
+
+    fn refresh_in_background(self: &Arc<Self>) {
+        if self.refreshing.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let this = self.clone();
+        tokio::spawn(async move {
+            let provider = this.settings.read().provider.clone();
+            if let Err(e) = this.refresh(&provider).await {
+                tracing::warn!("Failed to refresh exchange rates, keeping cached rates: {}", e);
+            }
+            this.refreshing.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// 从接口获取汇率并写入缓存（须通过沙盒的网络域名检查）
+    async fn refresh(&self, provider: &str) -> Result<RateTable> {
+        let url = reqwest::Url::parse(provider).context("Invalid exchange rate provider URL")?;
+        let host = url.host_str().ok_or_else(|| anyhow!("Exchange rate provider URL has no host"))?;
+        self.sandbox
+            .check_permission(&self.plugin_id, &PluginPermission::NetworkAccess(NetworkScope::Domain(host.to_string())))?;
+
+        let client = crate::http::client(&self.plugin_id)?;
+        let body = client.get(url.clone()).send_with_retry().await?.error_for_status()?.text().await?;
+        let table = RateTable::parse(&body, provider, self.clock.now())?;
+
+        if let Some(path) = &self.cache_path {
+            if let Err(e) = save_cache(path, &table) {
+                tracing::warn!("Failed to save exchange rate cache: {}", e);
+            }
+        }
+        tracing::info!("💱 Exchange rates updated from {} ({} currencies)", host, table.rates.len());
+        *self.table.write() = Some(table.clone());
+        Ok(table)
+    }
+}
+
+fn load_cache(path: &Path) -> Option<RateTable> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_cache(path: &Path, table: &RateTable) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(table)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::FakeClock;
+
+    const ER_API_BODY: &str = r#"{"result":"success","base_code":"USD","rates":{"USD":1,"CNY":7.2,"EUR":0.9,"JPY":150.0}}"#;
+
+    fn table(fetched_at: DateTime<Utc>) -> RateTable {
+        RateTable::parse(ER_API_BODY, DEFAULT_PROVIDER, fetched_at).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_cross_convert() {
+        let table = table(Utc::now());
+        assert_eq!(table.base, "USD");
+        assert_eq!(table.convert(100.0, "usd", "cny"), Some(720.0));
+        assert_eq!(table.convert(100.0, "rmb", "usd"), Some(100.0 / 7.2));
+        assert!((table.convert(90.0, "eur", "jpy").unwrap() - 15000.0).abs() < 1e-9);
+        assert_eq!(table.convert(1.0, "usd", "xyz"), None);
+
+        // frankfurter 格式：base 字段且 rates 不含基准货币
+        let frankfurter = RateTable::parse(r#"{"amount":1.0,"base":"EUR","rates":{"USD":1.1}}"#, "x", Utc::now()).unwrap();
+        assert_eq!(frankfurter.convert(10.0, "eur", "usd"), Some(11.0));
+
+        assert!(RateTable::parse(r#"{"result":"error"}"#, "x", Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_freshness_depends_on_ttl_and_provider() {
+        let clock = FakeClock::fixed();
+        let table = table(clock.now());
+        let ttl = chrono::Duration::hours(DEFAULT_TTL_HOURS as i64);
+
+        assert!(table.is_fresh(DEFAULT_PROVIDER, ttl, clock.now()));
+        assert!(!table.is_fresh("https://api.frankfurter.app/latest", ttl, clock.now()));
+        clock.advance(ttl);
+        assert!(!table.is_fresh(DEFAULT_PROVIDER, ttl, clock.now()));
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_is_used_offline() {
+        let clock = FakeClock::fixed();
+        let dir = std::env::temp_dir().join(format!("ilauncher_rates_{}", uuid::Uuid::new_v4()));
+        let path = dir.join(CACHE_FILE);
+        save_cache(&path, &table(clock.now() - chrono::Duration::days(3))).unwrap();
+
+        // 未在沙盒中注册的插件无法联网，过期缓存仍可使用
+        let sandbox = Arc::new(SandboxManager::new());
+        let rates = Arc::new(ExchangeRates::new("rates_test", clock, sandbox, Some(path)));
+        assert!(rates.recognizes("jpy"));
+        assert!(!rates.recognizes("abc"));
+
+        let current = rates.rates().await.unwrap();
+        assert!(current.stale);
+        assert_eq!(current.table.convert(1.0, "usd", "cny"), Some(7.2));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
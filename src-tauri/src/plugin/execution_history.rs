@@ -44,6 +44,8 @@ impl ExecutionHistoryPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System).with_tags(&["history"]),
             },
             history: Arc::new(RwLock::new(Vec::new())),
             storage_path: storage_path.clone(),
@@ -243,6 +245,9 @@ impl Plugin for ExecutionHistoryPlugin {
                         prevent_hide: true,
                     },
                 ],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
         
@@ -260,13 +265,16 @@ impl Plugin for ExecutionHistoryPlugin {
                 plugin_id: self.metadata.id.clone(),
                 refreshable: false,
                 actions: vec![],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
         
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         if action_id == "remove" {
             // 解析 result_id
             let parts: Vec<&str> = result_id.split(':').collect();
@@ -274,7 +282,7 @@ impl Plugin for ExecutionHistoryPlugin {
                 self.remove(parts[0], parts[1]).await?;
                 tracing::info!("Removed from execution history: {}", result_id);
             }
-            return Ok(());
+            return Ok(ExecuteOutcome::Hide);
         }
         
         // execute 操作需要转发到原插件
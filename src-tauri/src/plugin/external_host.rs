@@ -0,0 +1,664 @@
+// 外部插件宿主 - 以子进程运行第三方插件（Python / Node / 可执行文件），通过 stdio 上的 JSON-RPC 2.0 通信
+//
+// 协议：每行一条 JSON 消息（换行分隔）
+//   宿主 → 插件：{"jsonrpc":"2.0","id":1,"method":"metadata"|"query"|"execute","params":{...}}
+//   插件 → 宿主：{"jsonrpc":"2.0","id":1,"result":...} 或 {"jsonrpc":"2.0","id":1,"error":{"code":-1,"message":"..."}}
+//   插件 → 宿主通知：{"jsonrpc":"2.0","method":"log","params":{"level":"info","message":"..."}}
+// stdout 中非 JSON 的行会被忽略（仅记录调试日志），stderr 转发到日志
+
+use super::plugin_installer::InstalledPlugin;
+use super::Plugin;
+use crate::core::types::*;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command as ProcessCommand};
+use tokio::sync::oneshot;
+
+/// 协议版本（握手时发送给插件）
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// 握手（metadata）超时，插件进程冷启动可能较慢
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 没有声明动作的结果使用的默认动作 ID
+const DEFAULT_ACTION_ID: &str = "execute";
+
+/// JSON-RPC 请求
+#[derive(Debug, Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+/// JSON-RPC 错误
+#[derive(Debug, Clone, Deserialize)]
+struct RpcError {
+    #[serde(default)]
+    code: i64,
+    message: String,
+}
+
+/// 插件发来的消息（响应或通知）
+#[derive(Debug, Deserialize)]
+struct RpcMessage {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    params: serde_json::Value,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+/// 插件 metadata 响应（均为可选，缺省使用 manifest 中的值）
+#[derive(Debug, Default, Deserialize)]
+struct ExternalMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    trigger_keywords: Option<Vec<String>>,
+    #[serde(default)]
+    commands: Vec<Command>,
+}
+
+/// 插件返回的查询结果（字段尽量宽松，便于移植 Wox / Flow Launcher 插件）
+#[derive(Debug, Deserialize)]
+struct ExternalResult {
+    #[serde(default)]
+    id: Option<String>,
+    title: String,
+    #[serde(default)]
+    subtitle: String,
+    #[serde(default)]
+    icon: Option<WoxImage>,
+    #[serde(default)]
+    score: i32,
+    #[serde(default)]
+    context_data: serde_json::Value,
+    #[serde(default)]
+    actions: Vec<ExternalAction>,
+    #[serde(default)]
+    preview: Option<Preview>,
+    #[serde(default)]
+    group: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalAction {
+    id: String,
+    name: String,
+    #[serde(default)]
+    is_default: bool,
+    #[serde(default)]
+    hotkey: Option<String>,
+    #[serde(default)]
+    prevent_hide: bool,
+}
+
+/// 子进程启动参数
+#[derive(Debug, Clone, PartialEq)]
+struct LaunchSpec {
+    program: PathBuf,
+    args: Vec<String>,
+    cwd: PathBuf,
+}
+
+impl LaunchSpec {
+    /// 根据 manifest 的 engine 字段确定启动方式
+    fn from_installed(installed: &InstalledPlugin) -> Result<Self> {
+        let engine = &installed.manifest.engine;
+        let entry = installed.install_path.join(&engine.entry);
+        let cwd = installed.install_path.clone();
+        let entry_arg = entry.to_string_lossy().to_string();
+
+        let spec = match engine.r#type.as_str() {
+            "python" => Self {
+                program: PathBuf::from(if cfg!(windows) { "python" } else { "python3" }),
+                args: vec![entry_arg],
+                cwd,
+            },
+            "node" | "nodejs" | "javascript" => Self {
+                program: PathBuf::from("node"),
+                args: vec![entry_arg],
+                cwd,
+            },
+            "executable" | "native" => Self {
+                program: entry,
+                args: Vec::new(),
+                cwd,
+            },
+            other => return Err(anyhow!("Unsupported plugin engine: {}", other)),
+        };
+        Ok(spec)
+    }
+}
+
+/// 运行中的插件进程
+struct HostProcess {
+    // 保留句柄以便 drop 时结束进程（kill_on_drop）
+    _child: Child,
+    stdin: ChildStdin,
+    pending: Arc<Mutex<HashMap<u64, oneshot::Sender<RpcMessage>>>>,
+    alive: Arc<AtomicBool>,
+}
+
+impl HostProcess {
+    fn spawn(plugin_id: &str, spec: &LaunchSpec) -> Result<Self> {
+        let mut child = ProcessCommand::new(&spec.program)
+            .args(&spec.args)
+            .current_dir(&spec.cwd)
+            .env("ILAUNCHER_PLUGIN_ID", plugin_id)
+            .env("ILAUNCHER_PROTOCOL_VERSION", PROTOCOL_VERSION.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start plugin process: {:?}", spec.program))?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("Plugin stdin unavailable"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("Plugin stdout unavailable"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("Plugin stderr unavailable"))?;
+
+        let pending: Arc<Mutex<HashMap<u64, oneshot::Sender<RpcMessage>>>> = Arc::new(Mutex::new(HashMap::new()));
+        let alive = Arc::new(AtomicBool::new(true));
+
+        // 读取响应并分发给等待中的请求
+        {
+            let plugin_id = plugin_id.to_string();
+            let pending = pending.clone();
+            let alive = alive.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    dispatch_line(&plugin_id, &line, &pending);
+                }
+                // 进程退出：标记失效，丢弃等待者（对应请求立即返回错误）
+                alive.store(false, Ordering::SeqCst);
+                pending.lock().clear();
+                tracing::warn!("🧩 External plugin process exited: {}", plugin_id);
+            });
+        }
+
+        // 转发 stderr 到日志
+        {
+            let plugin_id = plugin_id.to_string();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::warn!("[plugin:{}] {}", plugin_id, line);
+                }
+            });
+        }
+
+        tracing::info!("🧩 External plugin process started: {} ({:?})", plugin_id, spec.program);
+
+        Ok(Self {
+            _child: child,
+            stdin,
+            pending,
+            alive,
+        })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+}
+
+/// 处理插件 stdout 的一行输出
+fn dispatch_line(plugin_id: &str, line: &str, pending: &Mutex<HashMap<u64, oneshot::Sender<RpcMessage>>>) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+
+    let message: RpcMessage = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(_) => {
+            tracing::debug!("[plugin:{}] {}", plugin_id, line);
+            return;
+        }
+    };
+
+    match (&message.method, message.id) {
+        (Some(method), _) => handle_notification(plugin_id, method, &message.params),
+        (None, Some(id)) => {
+            if let Some(sender) = pending.lock().remove(&id) {
+                let _ = sender.send(message);
+            } else {
+                tracing::debug!("[plugin:{}] Response for unknown request {}", plugin_id, id);
+            }
+        }
+        (None, None) => tracing::debug!("[plugin:{}] Ignored message without id", plugin_id),
+    }
+}
+
+/// 插件主动发来的通知
+fn handle_notification(plugin_id: &str, method: &str, params: &serde_json::Value) {
+    match method {
+        "log" => {
+            let message = params.get("message").and_then(|v| v.as_str()).unwrap_or_default();
+            match params.get("level").and_then(|v| v.as_str()).unwrap_or("info") {
+                "error" => tracing::error!("[plugin:{}] {}", plugin_id, message),
+                "warn" => tracing::warn!("[plugin:{}] {}", plugin_id, message),
+                "debug" => tracing::debug!("[plugin:{}] {}", plugin_id, message),
+                _ => tracing::info!("[plugin:{}] {}", plugin_id, message),
+            }
+        }
+        other => tracing::debug!("[plugin:{}] Unsupported notification: {}", plugin_id, other),
+    }
+}
+
+/// 外部进程插件（实现 Plugin trait，进程崩溃后在下一次调用时自动重启）
+pub struct ExternalPlugin {
+    metadata: PluginMetadata,
+    launch: LaunchSpec,
+    settings: HashMap<String, serde_json::Value>,
+    timeout: Duration,
+    next_id: AtomicU64,
+    process: tokio::sync::Mutex<Option<HostProcess>>,
+}
+
+impl ExternalPlugin {
+    /// 启动已安装插件并完成 metadata 握手
+    pub async fn spawn(installed: &InstalledPlugin) -> Result<Self> {
+        let plugin = Self {
+            metadata: manifest_metadata(installed),
+            launch: LaunchSpec::from_installed(installed)?,
+            settings: installed.settings.clone(),
+            timeout: Duration::from_millis(installed.manifest.sandbox.timeout_ms.max(100)),
+            next_id: AtomicU64::new(1),
+            process: tokio::sync::Mutex::new(None),
+        };
+        plugin.handshake().await
+    }
+
+    /// 调用 metadata，用插件返回的信息覆盖 manifest 中的默认值
+    async fn handshake(mut self) -> Result<Self> {
+        let params = serde_json::json!({
+            "protocol_version": PROTOCOL_VERSION,
+            "plugin_id": self.metadata.id,
+            "settings": self.settings,
+        });
+        let value = self.call_with_timeout("metadata", params, HANDSHAKE_TIMEOUT).await?;
+        apply_remote_metadata(&mut self.metadata, value);
+
+        tracing::info!("🧩 External plugin ready: {} v{}", self.metadata.id, self.metadata.version);
+        Ok(self)
+    }
+
+    /// 发送 JSON-RPC 请求并等待响应（使用 manifest 中的超时）
+    async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.call_with_timeout(method, params, self.timeout).await
+    }
+
+    async fn call_with_timeout(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<serde_json::Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+
+        let pending = {
+            let mut process = self.process.lock().await;
+            if !process.as_ref().map_or(false, |p| p.is_alive()) {
+                *process = Some(HostProcess::spawn(&self.metadata.id, &self.launch)?);
+            }
+            let Some(process) = process.as_mut() else {
+                return Err(anyhow!("Plugin process unavailable: {}", self.metadata.id));
+            };
+
+            process.pending.lock().insert(id, sender);
+            let mut line = serde_json::to_string(&RpcRequest {
+                jsonrpc: "2.0",
+                id,
+                method,
+                params,
+            })?;
+            line.push('\n');
+
+            let written = async {
+                process.stdin.write_all(line.as_bytes()).await?;
+                process.stdin.flush().await
+            }
+            .await;
+            if let Err(e) = written {
+                process.pending.lock().remove(&id);
+                process.alive.store(false, Ordering::SeqCst);
+                return Err(anyhow!("Failed to write to plugin {}: {}", self.metadata.id, e));
+            }
+            process.pending.clone()
+        };
+
+        let message = match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(message)) => message,
+            Ok(Err(_)) => return Err(anyhow!("Plugin process exited: {}", self.metadata.id)),
+            Err(_) => {
+                pending.lock().remove(&id);
+                return Err(anyhow!(
+                    "Plugin {} timed out after {:?} ({})",
+                    self.metadata.id,
+                    timeout,
+                    method
+                ));
+            }
+        };
+
+        if let Some(error) = message.error {
+            return Err(anyhow!(
+                "Plugin {} returned error {}: {}",
+                self.metadata.id,
+                error.code,
+                error.message
+            ));
+        }
+        Ok(message.result.unwrap_or(serde_json::Value::Null))
+    }
+}
+
+/// 用插件 metadata 响应覆盖 manifest 中的默认值（字段均可选）
+pub(super) fn apply_remote_metadata(metadata: &mut PluginMetadata, value: serde_json::Value) {
+    let remote: ExternalMetadata = serde_json::from_value(value).unwrap_or_default();
+
+    if let Some(name) = remote.name {
+        metadata.name = name;
+    }
+    if let Some(description) = remote.description {
+        metadata.description = description;
+    }
+    if let Some(version) = remote.version {
+        metadata.version = version;
+    }
+    if let Some(trigger_keywords) = remote.trigger_keywords {
+        metadata.trigger_keywords = trigger_keywords;
+    }
+    if !remote.commands.is_empty() {
+        metadata.commands = remote.commands;
+    }
+}
+
+/// 解析插件返回的查询结果
+pub(super) fn parse_results(metadata: &PluginMetadata, value: serde_json::Value) -> Result<Vec<QueryResult>> {
+    let results: Vec<ExternalResult> = serde_json::from_value(value)
+        .with_context(|| format!("Invalid query response from plugin {}", metadata.id))?;
+
+    Ok(results.into_iter().map(|r| convert_result(metadata, r)).collect())
+}
+
+/// 解析 execute 返回值（null 或无法识别时按默认行为处理）
+/// push_results / follow_up 中的结果与 query 返回值格式相同
+pub(super) fn parse_outcome(metadata: &PluginMetadata, value: serde_json::Value) -> ExecuteOutcome {
+    if value.is_null() {
+        return ExecuteOutcome::Hide;
+    }
+
+    let parsed = match value.get("type").and_then(|t| t.as_str()) {
+        Some("push_results") => parse_results(metadata, value.get("results").cloned().unwrap_or_default())
+            .map(|results| ExecuteOutcome::PushResults { results }),
+        Some("follow_up") => parse_results(metadata, value.get("results").cloned().unwrap_or_default())
+            .map(|results| ExecuteOutcome::FollowUp(FollowUp {
+                title: value.get("title").and_then(|t| t.as_str()).unwrap_or_default().to_string(),
+                placeholder: value.get("placeholder").and_then(|p| p.as_str()).map(str::to_string),
+                context: value.get("context").cloned().unwrap_or_default(),
+                results,
+                breadcrumbs: Vec::new(),
+            })),
+        _ => serde_json::from_value(value).map_err(anyhow::Error::from),
+    };
+    parsed.unwrap_or_else(|e| {
+        tracing::warn!("Invalid execute response from plugin {}: {}", metadata.id, e);
+        ExecuteOutcome::Hide
+    })
+}
+
+/// 将插件返回的结果转换为 QueryResult
+fn convert_result(metadata: &PluginMetadata, result: ExternalResult) -> QueryResult {
+    let mut actions: Vec<Action> = result
+        .actions
+        .into_iter()
+        .map(|a| Action {
+            id: a.id,
+            name: a.name,
+            icon: None,
+            is_default: a.is_default,
+            hotkey: a.hotkey,
+            prevent_hide: a.prevent_hide,
+        })
+        .collect();
+    if actions.is_empty() {
+        actions.push(Action {
+            id: DEFAULT_ACTION_ID.to_string(),
+            name: "执行".to_string(),
+            icon: None,
+            is_default: true,
+            hotkey: None,
+            prevent_hide: false,
+        });
+    }
+
+    QueryResult {
+        id: result.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        title: result.title,
+        subtitle: result.subtitle,
+        icon: result.icon.unwrap_or_else(|| metadata.icon.clone()),
+        score: result.score,
+        plugin_id: metadata.id.clone(),
+        context_data: result.context_data,
+        actions,
+        preview: result.preview,
+        refreshable: false,
+        group: result.group,
+        accessibility: None,
+        highlights: None,
+        badges: Vec::new(),
+    }
+}
+
+/// 由 manifest 生成默认元数据
+pub(super) fn manifest_metadata(installed: &InstalledPlugin) -> PluginMetadata {
+    let manifest = &installed.manifest;
+    let icon_path = installed.install_path.join(&manifest.icon);
+    let icon = if !manifest.icon.is_empty() && icon_path.is_file() {
+        WoxImage::File(icon_path.to_string_lossy().to_string())
+    } else if !manifest.icon.is_empty() && manifest.icon.chars().count() <= 2 {
+        WoxImage::Emoji(manifest.icon.clone())
+    } else {
+        WoxImage::Emoji("🧩".to_string())
+    };
+
+    let plugin_type = match manifest.engine.r#type.as_str() {
+        "python" => PluginType::Python,
+        "node" | "nodejs" | "javascript" => PluginType::NodeJS,
+        "native" => PluginType::Native,
+        "wasm" => PluginType::Wasm,
+        _ => PluginType::Script,
+    };
+
+    PluginMetadata {
+        id: manifest.id.clone(),
+        name: manifest.name.clone(),
+        author: manifest.author.name.clone(),
+        version: manifest.version.clone(),
+        description: manifest.description.clone(),
+        icon,
+        trigger_keywords: manifest.triggers.clone(),
+        commands: vec![],
+        settings: vec![],
+        supported_os: vec!["windows".to_string(), "linux".to_string(), "macos".to_string()],
+        plugin_type,
+        query_policy: QueryPolicy::default(),
+        branding: PluginBranding {
+            category: manifest.category.as_deref().map(PluginCategory::from_name).unwrap_or_default(),
+            tags: manifest.keywords.clone(),
+            homepage: manifest.homepage.clone(),
+            icon_asset: None,
+        },
+    }
+}
+
+#[async_trait]
+impl Plugin for ExternalPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let value = self.call("query", serde_json::to_value(ctx)?).await?;
+        parse_results(&self.metadata, value)
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        let value = self
+            .call(
+                "execute",
+                serde_json::json!({
+                    "result_id": result_id,
+                    "action_id": action_id,
+                }),
+            )
+            .await?;
+        Ok(parse_outcome(&self.metadata, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_outcome() {
+        let metadata = PluginMetadata {
+            id: "demo".to_string(),
+            name: "Demo".to_string(),
+            author: String::new(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            icon: WoxImage::emoji("🔌"),
+            trigger_keywords: vec![],
+            commands: vec![],
+            settings: vec![],
+            supported_os: vec![],
+            plugin_type: PluginType::Native,
+            query_policy: QueryPolicy::default(),
+            branding: PluginBranding::default(),
+        };
+
+        assert!(matches!(parse_outcome(&metadata, serde_json::Value::Null), ExecuteOutcome::Hide));
+        assert!(matches!(
+            parse_outcome(&metadata, serde_json::json!({ "type": "show_message", "message": "copied" })),
+            ExecuteOutcome::ShowMessage { message } if message == "copied"
+        ));
+        match parse_outcome(&metadata, serde_json::json!({ "type": "push_results", "results": [{ "title": "next" }] })) {
+            ExecuteOutcome::PushResults { results } => {
+                assert_eq!(results[0].title, "next");
+                assert_eq!(results[0].plugin_id, "demo");
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        match parse_outcome(&metadata, serde_json::json!({ "type": "follow_up", "title": "Pick", "results": [{ "title": "a" }] })) {
+            ExecuteOutcome::FollowUp(follow_up) => {
+                assert_eq!(follow_up.title, "Pick");
+                assert_eq!(follow_up.results[0].plugin_id, "demo");
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        assert!(matches!(parse_outcome(&metadata, serde_json::json!({ "type": "explode" })), ExecuteOutcome::Hide));
+    }
+
+    #[test]
+    fn test_manifest_branding() {
+        let manifest: crate::plugin::plugin_installer::PluginManifest = serde_json::from_value(serde_json::json!({
+            "id": "com.example.weather",
+            "name": "Weather",
+            "version": "1.0.0",
+            "description": "",
+            "author": { "name": "Example" },
+            "homepage": "https://example.com/weather",
+            "license": "MIT",
+            "keywords": ["weather", "forecast"],
+            "category": "Tools",
+            "icon": "⛅",
+            "engine": { "type": "python", "entry": "main.py", "runtime_version": "3" },
+            "triggers": ["wt"],
+        }))
+        .unwrap();
+        let installed = InstalledPlugin {
+            manifest,
+            install_path: PathBuf::from("/nonexistent"),
+            installed_at: chrono::Utc::now(),
+            enabled: true,
+            settings: HashMap::new(),
+        };
+
+        let metadata = manifest_metadata(&installed);
+        assert_eq!(metadata.branding.category, PluginCategory::Utilities);
+        assert_eq!(metadata.branding.tags, vec!["weather", "forecast"]);
+        assert_eq!(metadata.branding.homepage.as_deref(), Some("https://example.com/weather"));
+
+        // 品牌字段展开到元数据顶层
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["category"], "utilities");
+        assert!(json.get("icon_asset").is_none());
+    }
+
+    #[test]
+    fn test_request_framing() {
+        let request = RpcRequest {
+            jsonrpc: "2.0",
+            id: 7,
+            method: "query",
+            params: serde_json::json!({ "search": "hi" }),
+        };
+        let line = serde_json::to_string(&request).unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(
+            line,
+            r#"{"jsonrpc":"2.0","id":7,"method":"query","params":{"search":"hi"}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_response_by_id() {
+        let pending = Mutex::new(HashMap::new());
+        let (sender, receiver) = oneshot::channel();
+        pending.lock().insert(3, sender);
+
+        dispatch_line("demo", "not json output", &pending);
+        dispatch_line("demo", r#"{"jsonrpc":"2.0","method":"log","params":{"message":"hi"}}"#, &pending);
+        assert_eq!(pending.lock().len(), 1);
+
+        dispatch_line("demo", r#"{"jsonrpc":"2.0","id":3,"result":[{"title":"ok"}]}"#, &pending);
+        let message = receiver.await.unwrap();
+        assert!(pending.lock().is_empty());
+
+        let results: Vec<ExternalResult> = serde_json::from_value(message.result.unwrap()).unwrap();
+        assert_eq!(results[0].title, "ok");
+        assert!(results[0].actions.is_empty());
+    }
+
+    #[test]
+    fn test_error_response() {
+        let message: RpcMessage =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"nope"}}"#).unwrap();
+        let error = message.error.unwrap();
+        assert_eq!(error.code, -32601);
+        assert_eq!(error.message, "nope");
+    }
+}
@@ -0,0 +1,371 @@
+// 文件搜索过滤语法（类似 Everything）
+// ext:pdf;docx  size:>10mb  modified:<7d / date:>2024-01-01  path:src
+// 过滤词从查询中剥离，剩余部分作为普通关键词；MFT 查询和 BFS 内存索引共用同一套匹配逻辑
+
+use chrono::{NaiveDate, TimeZone, Utc};
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Cmp {
+    /// 拆出运算符前缀，无运算符时为 Eq
+    fn split(value: &str) -> (Self, &str) {
+        for (prefix, cmp) in [(">=", Cmp::Ge), ("<=", Cmp::Le), (">", Cmp::Gt), ("<", Cmp::Lt), ("=", Cmp::Eq)] {
+            if let Some(rest) = value.strip_prefix(prefix) {
+                return (cmp, rest);
+            }
+        }
+        (Cmp::Eq, value)
+    }
+
+    /// 反向运算符（“小于 7 天前” 等价于 “修改时间晚于 7 天前”）
+    fn flip(self) -> Self {
+        match self {
+            Cmp::Lt => Cmp::Gt,
+            Cmp::Le => Cmp::Ge,
+            Cmp::Eq => Cmp::Eq,
+            Cmp::Ge => Cmp::Le,
+            Cmp::Gt => Cmp::Lt,
+        }
+    }
+
+    fn test(self, actual: i64, expected: i64) -> bool {
+        match self {
+            Cmp::Lt => actual < expected,
+            Cmp::Le => actual <= expected,
+            Cmp::Eq => actual == expected,
+            Cmp::Ge => actual >= expected,
+            Cmp::Gt => actual > expected,
+        }
+    }
+}
+
+/// 修改时间条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBound {
+    /// 与时间戳比较（秒）
+    Timestamp(Cmp, i64),
+    /// 落在某一天内（date:2024-01-01）
+    Day(i64),
+}
+
+/// 解析后的过滤条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileFilters {
+    /// 扩展名（小写，不带点），任一匹配即可
+    pub extensions: Vec<String>,
+    /// 文件大小（字节）
+    pub size: Option<(Cmp, u64)>,
+    /// 修改时间
+    pub modified: Option<TimeBound>,
+    /// 路径片段（小写），全部匹配
+    pub path_contains: Vec<String>,
+}
+
+/// 查询拆分结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileQuery {
+    /// 去掉过滤词后的关键词
+    pub text: String,
+    pub filters: FileFilters,
+}
+
+impl FileQuery {
+    /// 解析查询（now 为当前 Unix 时间戳，用于相对时间）
+    pub fn parse(search: &str, now: i64) -> Self {
+        let mut filters = FileFilters::default();
+        let mut words = Vec::new();
+
+        for token in search.split_whitespace() {
+            if !filters.apply_token(token, now) {
+                words.push(token);
+            }
+        }
+
+        Self {
+            text: words.join(" "),
+            filters,
+        }
+    }
+
+    /// 用于索引查询的关键词：只有扩展名过滤时用 “.ext” 作为关键词
+    pub fn index_keyword(&self) -> Option<String> {
+        if !self.text.is_empty() {
+            return Some(self.text.clone());
+        }
+        match self.filters.extensions.as_slice() {
+            [ext] => Some(format!(".{}", ext)),
+            _ => None,
+        }
+    }
+}
+
+impl FileFilters {
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty() && self.size.is_none() && self.modified.is_none() && self.path_contains.is_empty()
+    }
+
+    /// 是否需要读取文件元数据（大小/修改时间）
+    pub fn needs_metadata(&self) -> bool {
+        self.size.is_some() || self.modified.is_some()
+    }
+
+    /// 检查文件是否满足条件；metadata 在需要时才调用，返回 (大小, 修改时间戳)
+    pub fn matches(&self, path: &str, name: &str, is_dir: bool, metadata: impl FnOnce() -> Option<(u64, i64)>) -> bool {
+        // 扩展名和大小条件只对文件生效
+        if (!self.extensions.is_empty() || self.size.is_some()) && is_dir {
+            return false;
+        }
+
+        if !self.extensions.is_empty() {
+            let ext = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+            if !self.extensions.iter().any(|e| *e == ext) {
+                return false;
+            }
+        }
+
+        if !self.path_contains.is_empty() {
+            let path_lower = path.to_lowercase();
+            if !self.path_contains.iter().all(|p| path_lower.contains(p.as_str())) {
+                return false;
+            }
+        }
+
+        if !self.needs_metadata() {
+            return true;
+        }
+        let Some((size, modified)) = metadata() else {
+            return false;
+        };
+
+        if let Some((cmp, expected)) = self.size {
+            if !cmp.test(size as i64, expected as i64) {
+                return false;
+            }
+        }
+
+        match self.modified {
+            Some(TimeBound::Timestamp(cmp, ts)) => cmp.test(modified, ts),
+            Some(TimeBound::Day(start)) => (start..start + 86_400).contains(&modified),
+            None => true,
+        }
+    }
+
+    /// 解析单个过滤词，返回 false 表示不是（合法的）过滤词
+    fn apply_token(&mut self, token: &str, now: i64) -> bool {
+        let Some((key, value)) = token.split_once(':') else {
+            return false;
+        };
+        if value.is_empty() {
+            return false;
+        }
+
+        match key.to_lowercase().as_str() {
+            "ext" => {
+                let exts: Vec<String> = value
+                    .split([';', ','])
+                    .map(|e| e.trim_start_matches('.').to_lowercase())
+                    .filter(|e| !e.is_empty())
+                    .collect();
+                if exts.is_empty() {
+                    return false;
+                }
+                self.extensions.extend(exts);
+                true
+            }
+            "size" => match parse_size(value) {
+                Some(size) => {
+                    self.size = Some(size);
+                    true
+                }
+                None => false,
+            },
+            "modified" | "date" | "dm" => match parse_time(value, now) {
+                Some(bound) => {
+                    self.modified = Some(bound);
+                    true
+                }
+                None => false,
+            },
+            "path" => {
+                self.path_contains.push(value.to_lowercase());
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 解析大小条件：>10mb、<=512k、1gb
+fn parse_size(value: &str) -> Option<(Cmp, u64)> {
+    let (cmp, rest) = Cmp::split(value);
+    let rest = rest.to_lowercase();
+    let split = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+    let (number, unit) = rest.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier: u64 = match unit {
+        "" | "b" => 1,
+        "k" | "kb" => 1 << 10,
+        "m" | "mb" => 1 << 20,
+        "g" | "gb" => 1 << 30,
+        "t" | "tb" => 1 << 40,
+        _ => return None,
+    };
+    Some((cmp, (number * multiplier as f64) as u64))
+}
+
+/// 解析时间条件：<7d（7 天内）、>1w（一周以前）、>2024-01-01、2024-01-01（当天）
+fn parse_time(value: &str, now: i64) -> Option<TimeBound> {
+    let (cmp, rest) = Cmp::split(value);
+
+    if let Ok(date) = NaiveDate::parse_from_str(rest, "%Y-%m-%d") {
+        let start = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?).timestamp();
+        return Some(match cmp {
+            Cmp::Eq => TimeBound::Day(start),
+            // “晚于某天” 从次日零点算起
+            Cmp::Gt => TimeBound::Timestamp(Cmp::Ge, start + 86_400),
+            Cmp::Le => TimeBound::Timestamp(Cmp::Lt, start + 86_400),
+            _ => TimeBound::Timestamp(cmp, start),
+        });
+    }
+
+    // 相对时间比较的是“距今时长”，换算成时间戳时方向相反
+    let rest = rest.to_lowercase();
+    let split = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (number, unit) = rest.split_at(split);
+    let number: i64 = number.parse().ok()?;
+    let seconds: i64 = match unit {
+        "h" => 3_600,
+        "d" | "" => 86_400,
+        "w" => 7 * 86_400,
+        "mo" => 30 * 86_400,
+        "y" => 365 * 86_400,
+        _ => return None,
+    };
+    let cmp = if cmp == Cmp::Eq { Cmp::Lt } else { cmp };
+    // 超出范围的时长视为非法过滤词（保留在关键词中）
+    let ago = now.checked_sub(number.checked_mul(seconds)?)?;
+    Some(TimeBound::Timestamp(cmp.flip(), ago))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// 解析语料：每行一个查询
+    const CORPUS: &str = include_str!("testdata/file_query_corpus.txt");
+
+    /// 剩余关键词再次解析应保持不变，且不再产生过滤条件
+    fn assert_stable(input: &str, now: i64) {
+        let query = FileQuery::parse(input, now);
+        let reparsed = FileQuery::parse(&query.text, now);
+        assert_eq!(reparsed.text, query.text, "input: {:?}", input);
+        assert!(reparsed.filters.is_empty(), "input: {:?}", input);
+
+        // 关键词只来自输入中的词，且保持顺序
+        let mut tokens = input.split_whitespace();
+        for word in query.text.split_whitespace() {
+            assert!(tokens.any(|t| t == word), "input: {:?}", input);
+        }
+
+        for ext in &query.filters.extensions {
+            assert!(!ext.is_empty() && !ext.starts_with('.'));
+            assert_eq!(*ext, ext.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        let query = FileQuery::parse("report ext:pdf;DOCX size:>10mb path:Src modified:<7d", NOW);
+        assert_eq!(query.text, "report");
+        assert_eq!(query.filters.extensions, vec!["pdf", "docx"]);
+        assert_eq!(query.filters.size, Some((Cmp::Gt, 10 * 1024 * 1024)));
+        assert_eq!(query.filters.path_contains, vec!["src"]);
+        assert_eq!(query.filters.modified, Some(TimeBound::Timestamp(Cmp::Gt, NOW - 7 * 86_400)));
+    }
+
+    #[test]
+    fn test_invalid_filters_stay_in_text() {
+        let query = FileQuery::parse("size:huge http://example.com ext:", NOW);
+        assert_eq!(query.text, "size:huge http://example.com ext:");
+        assert!(query.filters.is_empty());
+    }
+
+    #[test]
+    fn test_index_keyword() {
+        assert_eq!(FileQuery::parse("ext:pdf", NOW).index_keyword(), Some(".pdf".to_string()));
+        assert_eq!(FileQuery::parse("ext:pdf;doc", NOW).index_keyword(), None);
+        assert_eq!(FileQuery::parse("foo ext:pdf", NOW).index_keyword(), Some("foo".to_string()));
+    }
+
+    #[test]
+    fn test_matches() {
+        let filters = FileQuery::parse("ext:pdf size:>1kb date:>2023-01-01 path:docs", NOW).filters;
+        let recent = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+
+        assert!(filters.matches("C:\\docs\\a.PDF", "a.PDF", false, || Some((4096, recent))));
+        assert!(!filters.matches("C:\\docs\\a.pdf", "a.pdf", false, || Some((100, recent))));
+        assert!(!filters.matches("C:\\other\\a.pdf", "a.pdf", false, || Some((4096, recent))));
+        assert!(!filters.matches("C:\\docs\\a.txt", "a.txt", false, || Some((4096, recent))));
+        assert!(!filters.matches("C:\\docs\\a.pdf", "a.pdf", true, || Some((4096, recent))));
+        // 元数据不可读时不匹配
+        assert!(!filters.matches("C:\\docs\\a.pdf", "a.pdf", false, || None));
+
+        // 同一天
+        let day = FileQuery::parse("date:2023-06-01", NOW).filters;
+        assert!(day.matches("a", "a", false, || Some((0, recent + 3_600))));
+        assert!(!day.matches("a", "a", false, || Some((0, recent - 1))));
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let older = FileQuery::parse("modified:>30d", NOW).filters;
+        assert!(older.matches("a", "a", false, || Some((0, NOW - 31 * 86_400))));
+        assert!(!older.matches("a", "a", false, || Some((0, NOW - 86_400))));
+    }
+
+    #[test]
+    fn test_out_of_range_time_stays_in_text() {
+        let query = FileQuery::parse("modified:<99999999999999999y", NOW);
+        assert_eq!(query.text, "modified:<99999999999999999y");
+        assert!(query.filters.modified.is_none());
+    }
+
+    #[test]
+    fn test_query_corpus() {
+        for line in CORPUS.lines().filter(|l| !l.starts_with('#')) {
+            assert_stable(line, NOW);
+            assert_stable(line, i64::MIN);
+            assert_stable(line, i64::MAX);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(input in "\\PC{0,64}", now in any::<i64>()) {
+            assert_stable(&input, now);
+        }
+
+        #[test]
+        fn parse_filter_like_tokens(
+            tokens in proptest::collection::vec(
+                "(ext|size|modified|date|dm|path|EXT|foo)?:?[<>=]{0,2}[0-9.]{0,20}(b|kb|mb|gb|h|d|w|mo|y|;|,|-)*",
+                0..6,
+            ),
+            now in any::<i64>(),
+        ) {
+            assert_stable(&tokens.join(" "), now);
+        }
+    }
+}
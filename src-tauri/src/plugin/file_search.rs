@@ -1,7 +1,13 @@
 // 文件搜索插件 - 超快速全盘扫描（类似 Everything）
 
+use crate::core::highlight::{self, HighlightSpan, MatchHighlights};
 use crate::core::types::*;
+use crate::plugin::content_search::{self, ContentSearchConfig};
+use crate::plugin::drive_index::{self, WatchedDriveIndex};
+use crate::plugin::file_filter::FileQuery;
 use crate::plugin::Plugin;
+use crate::session_context::{IndexingStatus, SESSION_CONTEXT};
+use crate::utils::index_exclusions::{IndexExclusions, DEFAULT_EXCLUSIONS, EXCLUSIONS_KEY};
 use anyhow::Result;
 use async_trait::async_trait;
 use fuzzy_matcher::skim::SkimMatcherV2;
@@ -19,58 +25,89 @@ use crate::mft_scanner::MftFileEntry;
 #[cfg(target_os = "windows")]
 use crate::mft_scanner::{IndexQuery, PathReader};
 
-/// 检查 Windows 进程是否存在
 #[cfg(target_os = "windows")]
-fn is_process_running(pid: u32) -> bool {
-    use windows::Win32::Foundation::CloseHandle;
-    use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
-    
-    unsafe {
-        match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
-            Ok(handle) => {
-                if handle.is_invalid() {
-                    return false;
-                }
-                let _ = CloseHandle(handle);
-                true
-            }
-            Err(_) => false,
-        }
+use crate::mft_scanner::ipc as mft_ipc;
+
+#[cfg(target_os = "windows")]
+use crate::mft_scanner::path_match::{self, PathMatch};
+
+/// MFT 服务启动 + 首次扫描的最长等待时间，超时仍未就绪的驱动器退回 BFS
+#[cfg(target_os = "windows")]
+const MFT_READY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// 删除预演时最多统计的条目数（超出时大小显示为下限）
+const DELETE_PREVIEW_LIMIT: u64 = 100_000;
+
+/// 驱动器 MFT 索引不可用的原因（None 表示可用或仍在等待首次扫描）
+#[cfg(target_os = "windows")]
+fn mft_fallback_reason(
+    state: Option<&mft_ipc::DriveState>,
+    index_error: Option<String>,
+    waited: std::time::Duration,
+) -> Option<String> {
+    match state {
+        Some(mft_ipc::DriveState::Failed { message }) => Some(format!("MFT scan failed: {}", message)),
+        Some(mft_ipc::DriveState::Ready) => index_error.map(|e| format!("MFT index is unreadable: {}", e)),
+        _ if waited >= MFT_READY_TIMEOUT => Some(format!(
+            "MFT index not ready after {} minutes",
+            MFT_READY_TIMEOUT.as_secs() / 60
+        )),
+        _ => None,
     }
 }
 
-/// 验证 .ready 文件是否有效（文件存在 + PID 进程运行中）
-#[cfg(target_os = "windows")]
-fn is_ready_file_valid(ready_file_path: &str) -> bool {
-    let path = std::path::Path::new(ready_file_path);
-    
-    // 1. 检查文件是否存在
-    if !path.exists() {
-        return false;
+/// 路径直达（即时答案）：输入为已存在的绝对路径或 ~ 开头的路径时直接给出打开结果
+pub fn path_answer(input: &str) -> Option<QueryResult> {
+    let input = input.trim().trim_matches('"');
+    let path = if let Some(rest) = input.strip_prefix('~') {
+        let home = std::env::var("USERPROFILE").or_else(|_| std::env::var("HOME")).ok()?;
+        PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+    } else {
+        PathBuf::from(input)
+    };
+    if !path.is_absolute() || !path.exists() {
+        return None;
     }
     
-    // 2. 读取文件内容（PID）
-    let pid_str = match std::fs::read_to_string(path) {
-        Ok(content) => content.trim().to_string(),
-        Err(_) => return false,
-    };
+    let path_str = path.to_string_lossy().to_string();
+    let is_dir = path.is_dir();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_str.clone());
     
-    // 3. 解析 PID
-    let pid: u32 = match pid_str.parse() {
-        Ok(p) => p,
-        Err(_) => {
-            tracing::warn!("⚠️  Invalid PID in ready file: {}", ready_file_path);
-            return false;
-        }
+    let action = |id: &str, name: &str, emoji: &str, is_default: bool| Action {
+        id: id.to_string(),
+        name: name.to_string(),
+        icon: Some(WoxImage::emoji(emoji)),
+        is_default,
+        prevent_hide: false,
+        hotkey: None,
     };
     
-    // 4. 检查进程是否运行
-    if !is_process_running(pid) {
-        tracing::warn!("⚠️  Ready file exists but MFT Service (PID {}) is not running: {}", pid, ready_file_path);
-        return false;
-    }
-    
-    true
+    Some(QueryResult {
+        id: path_str.clone(),
+        title: name,
+        subtitle: path_str.clone(),
+        icon: FileSearchPlugin::get_file_icon(&path_str, is_dir),
+        preview: crate::preview::assets::file_preview(&path_str, is_dir),
+        score: 900,
+        context_data: serde_json::json!({
+            "path": path_str,
+            "is_dir": is_dir,
+        }),
+        group: None,
+        plugin_id: "file_search".to_string(),
+        refreshable: false,
+        actions: vec![
+            action("open", if is_dir { "打开文件夹" } else { "打开文件" }, "📂", true),
+            action("open_folder", "打开所在位置", "📁", false),
+            action("copy_path", "复制路径", "📋", false),
+        ],
+        accessibility: None,
+        highlights: None,
+        badges: Vec::new(),
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +131,17 @@ struct FileItem {
     modified: i64,
 }
 
+/// 过滤用的文件元数据：(大小, 修改时间戳)
+fn metadata_stat(metadata: &std::fs::Metadata) -> (u64, i64) {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    (metadata.len(), modified)
+}
+
 /// MFT 索引缓存（避免重复打开）
 #[cfg(target_os = "windows")]
 struct MftIndexCache {
@@ -120,6 +168,7 @@ struct FileCache {
     created_at: DateTime<Utc>,
     files: Vec<FileItem>,
     name_index: HashMap<char, Vec<usize>>,
+    exclusions: u64, // 构建时排除规则的指纹（规则变化后缓存失效）
 }
 
 pub struct FileSearchPlugin {
@@ -130,6 +179,8 @@ pub struct FileSearchPlugin {
     matcher: SkimMatcherV2,
     search_paths: Vec<PathBuf>,
     config: Arc<RwLock<FileSearchConfig>>,
+    // 🔥 非 NTFS / 可移动驱动器的监听索引（随插拔自动加入/移出）
+    watched_drives: Arc<WatchedDriveIndex>,
     // 🔥 新增: MFT 索引缓存（按驱动器字母）
     #[cfg(target_os = "windows")]
     mft_cache: Arc<RwLock<HashMap<char, MftIndexCache>>>,
@@ -140,18 +191,14 @@ impl FileSearchPlugin {
         Self::new_with_config(true) // 默认启用 MFT
     }
     
-    /// 获取所有固定磁盘驱动器
+    /// 获取由 MFT 索引的驱动器（NTFS 固定磁盘）
     #[cfg(target_os = "windows")]
     fn get_fixed_drives() -> Vec<char> {
-        let mut drives = Vec::new();
-        for drive in b'A'..=b'Z' {
-            let drive_char = drive as char;
-            let path = format!("{}:\\", drive_char);
-            if std::path::Path::new(&path).exists() {
-                drives.push(drive_char);
-            }
-        }
-        drives
+        crate::utils::drives::list_drives()
+            .into_iter()
+            .filter(|drive| drive_index::select_strategy(drive, true) == drive_index::DriveStrategy::Mft)
+            .map(|drive| drive.letter)
+            .collect()
     }
     
     pub fn new_with_config(use_mft: bool) -> Self {
@@ -160,11 +207,10 @@ impl FileSearchPlugin {
         
         #[cfg(target_os = "windows")]
         {
-            // Windows: 扫描所有盘符
-            for drive in b'A'..=b'Z' {
-                let path = PathBuf::from(format!("{}:\\", drive as char));
-                if path.exists() {
-                    search_paths.push(path);
+            // Windows: BFS 全量扫描只覆盖 NTFS 固定磁盘，其余驱动器由监听索引负责
+            for drive in crate::utils::drives::list_drives() {
+                if drive_index::select_strategy(&drive, false) == drive_index::DriveStrategy::Bfs {
+                    search_paths.push(drive.root());
                 }
             }
         }
@@ -190,11 +236,11 @@ impl FileSearchPlugin {
             metadata: PluginMetadata {
                 id: "file_search".to_string(),
                 name: "File Search".to_string(),
-                description: "Search files and folders (Ultra-fast full disk scan)".to_string(),
+                description: "Search files and folders (Ultra-fast full disk scan, supports ext:/size:/modified:/path: filters)".to_string(),
                 author: "iLauncher".to_string(),
                 version: "1.0.0".to_string(),
                 icon: WoxImage::emoji("📁"),
-                trigger_keywords: vec![],
+                trigger_keywords: vec![content_search::CONTENT_KEYWORD.to_string()],
                 commands: vec![],
                 settings: vec![
                     SettingDefinition {
@@ -203,9 +249,17 @@ impl FileSearchPlugin {
                         label: Some("启用 MFT 快速扫描 (需要管理员权限)".to_string()),
                         value: Some(serde_json::json!(true)),
                     },
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(EXCLUSIONS_KEY.to_string()),
+                        label: Some("索引排除规则（逗号分隔：目录名、路径前缀、*.扩展名、通配符，重建索引后生效）".to_string()),
+                        value: Some(serde_json::json!(DEFAULT_EXCLUSIONS.join(", "))),
+                    },
                 ],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::new(2, 150),
+                branding: PluginBranding::new(PluginCategory::Search).with_tags(&["files", "mft"]),
             },
             files: Arc::new(RwLock::new(Vec::new())),
             name_index: Arc::new(RwLock::new(HashMap::new())),
@@ -214,6 +268,7 @@ impl FileSearchPlugin {
             config: Arc::new(RwLock::new(FileSearchConfig {
                 use_mft,
             })),
+            watched_drives: Arc::new(WatchedDriveIndex::new(IndexExclusions::load())),
             #[cfg(target_os = "windows")]
             mft_cache: Arc::new(RwLock::new(HashMap::new())),
         }
@@ -235,11 +290,20 @@ impl FileSearchPlugin {
         let paths = self.search_paths.clone();
         let config = self.config.clone();
         
+        let search_mode = if config.read().await.use_mft { "mft" } else { "bfs" };
+        SESSION_CONTEXT.set_search_mode(search_mode);
+        
+        // 🔥 按驱动器选择策略：exFAT/FAT32/可移动驱动器使用监听索引，插拔自动更新
+        self.watched_drives.spawn_monitor(config.read().await.use_mft);
+        
         // 🔥 如果是 MFT 模式，提前初始化缓存
         #[cfg(target_os = "windows")]
         {
             let use_mft = config.read().await.use_mft;
             if use_mft {
+                // 🩺 MFT 索引不可用的驱动器退回 BFS 监听索引
+                Self::spawn_mft_health_check(self.mft_cache.clone());
+                
                 tracing::info!("🚀 MFT mode - pre-loading index cache...");
                 let mft_cache = self.mft_cache.clone();
                 
@@ -257,16 +321,14 @@ impl FileSearchPlugin {
                     let drives = Self::get_fixed_drives();
                     let mut cache = mft_cache.write().await;
                     
+                    // 🔥 通过 IPC 查询 MFT Service 中已就绪的驱动器
+                    let ready_drives = mft_ipc::fetch_status(&output_dir)
+                        .map(|status| status.ready_drives())
+                        .unwrap_or_default();
+                    
                     for drive in drives {
-                        let fst_file = format!("{}\\{}_index.fst", output_dir, drive);
-                        if !std::path::Path::new(&fst_file).exists() {
-                            continue;
-                        }
-                        
-                        // 🔥 检查 .ready 标记文件是否有效（存在 + PID 进程运行）
-                        let ready_file = format!("{}\\{}.ready", output_dir, drive);
-                        if !is_ready_file_valid(&ready_file) {
-                            tracing::warn!("⏳ Drive {} index found but not ready yet (MFT Service not running or old ready file)", drive);
+                        if !ready_drives.contains(&drive) {
+                            tracing::warn!("⏳ Drive {} is not ready yet (MFT Service not running or still indexing)", drive);
                             continue;
                         }
                         
@@ -283,6 +345,11 @@ impl FileSearchPlugin {
                     }
                     
                     tracing::info!("✅ MFT index cache pre-loading completed ({} drives)", cache.len());
+                    SESSION_CONTEXT.set_indexing(IndexingStatus {
+                        is_indexing: cache.is_empty(),
+                        indexed_files: 0,
+                        message: format!("MFT: {} drives ready", cache.len()),
+                    });
                     
                     // 🔥 如果没有任何驱动器就绪，启动定时重试任务
                     if cache.is_empty() {
@@ -308,14 +375,13 @@ impl FileSearchPlugin {
                                 let mut cache = mft_cache_retry.write().await;
                                 let mut loaded_any = false;
                                 
+                                let ready_drives = mft_ipc::fetch_status(&output_dir_retry)
+                                    .map(|status| status.ready_drives())
+                                    .unwrap_or_default();
+                                
                                 for drive in drives {
-                                    // 跳过已加载的驱动器
-                                    if cache.contains_key(&drive) {
-                                        continue;
-                                    }
-                                    
-                                    let ready_file = format!("{}\\{}.ready", output_dir_retry, drive);
-                                    if !is_ready_file_valid(&ready_file) {
+                                    // 跳过已加载或未就绪的驱动器
+                                    if cache.contains_key(&drive) || !ready_drives.contains(&drive) {
                                         continue;
                                     }
                                     
@@ -334,6 +400,11 @@ impl FileSearchPlugin {
                                 
                                 if loaded_any {
                                     tracing::info!("✅ Successfully loaded new drives (total: {} drives ready)", cache.len());
+                                    SESSION_CONTEXT.set_indexing(IndexingStatus {
+                                        is_indexing: false,
+                                        indexed_files: 0,
+                                        message: format!("MFT: {} drives ready", cache.len()),
+                                    });
                                 }
                                 
                                 // 如果所有驱动器都已加载，停止重试
@@ -372,6 +443,9 @@ impl FileSearchPlugin {
                     let start = std::time::Instant::now();
                     
                     match Self::load_cache(&cache_path).await {
+                        Ok(cache) if cache.exclusions != IndexExclusions::load().fingerprint() => {
+                            tracing::info!("Index exclusions changed since cache was built, will rebuild");
+                        }
                         Ok(cache) => {
                             let file_count = cache.files.len();
                             
@@ -420,6 +494,65 @@ impl FileSearchPlugin {
     }
     
     /// 重建文件索引
+    /// 定期检查各驱动器的 MFT 索引：扫描失败、索引无法打开或超时未就绪的驱动器交给 BFS 监听索引，
+    /// 恢复后自动切回 MFT（就绪但未加载的索引顺便载入缓存）
+    #[cfg(target_os = "windows")]
+    fn spawn_mft_health_check(mft_cache: Arc<RwLock<HashMap<char, MftIndexCache>>>) {
+        const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+        
+        let output_dir = match crate::utils::paths::get_mft_database_dir() {
+            Ok(dir) => dir.to_string_lossy().to_string(),
+            Err(e) => {
+                tracing::error!("Failed to get MFT database dir: {}", e);
+                return;
+            }
+        };
+        
+        tokio::spawn(async move {
+            let started = std::time::Instant::now();
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+                
+                let status = mft_ipc::fetch_status(&output_dir);
+                let mut fallbacks = std::collections::BTreeMap::new();
+                for drive in Self::get_fixed_drives() {
+                    let state = status
+                        .as_ref()
+                        .and_then(|status| status.drives.iter().find(|d| d.drive == drive))
+                        .map(|d| &d.state);
+                    let index_error = match state {
+                        Some(mft_ipc::DriveState::Ready) => Self::ensure_mft_cached(&mft_cache, drive, &output_dir).await.err(),
+                        _ => None,
+                    };
+                    if let Some(reason) = mft_fallback_reason(state, index_error, started.elapsed()) {
+                        fallbacks.insert(drive, reason);
+                    }
+                }
+                drive_index::set_mft_fallbacks(fallbacks);
+            }
+        });
+    }
+    
+    /// 确保驱动器索引已载入缓存（打开失败说明索引损坏）
+    #[cfg(target_os = "windows")]
+    async fn ensure_mft_cached(
+        mft_cache: &RwLock<HashMap<char, MftIndexCache>>,
+        drive: char,
+        output_dir: &str,
+    ) -> std::result::Result<(), String> {
+        if mft_cache.read().await.contains_key(&drive) {
+            return Ok(());
+        }
+        match (IndexQuery::open(drive, output_dir), PathReader::open(drive, output_dir)) {
+            (Ok(query), Ok(path_reader)) => {
+                tracing::info!("✓ Loaded MFT index cache for drive {} (health check)", drive);
+                mft_cache.write().await.entry(drive).or_insert(MftIndexCache { query, path_reader });
+                Ok(())
+            }
+            (Err(e), _) | (_, Err(e)) => Err(format!("{:#}", e)),
+        }
+    }
+    
     async fn rebuild_index(
         files: Arc<RwLock<Vec<FileItem>>>,
         name_index: Arc<RwLock<HashMap<char, Vec<usize>>>>,
@@ -430,17 +563,23 @@ impl FileSearchPlugin {
         
         let use_mft = config.read().await.use_mft;
         
+        SESSION_CONTEXT.set_indexing(IndexingStatus {
+            is_indexing: true,
+            indexed_files: 0,
+            message: "Indexing files...".to_string(),
+        });
+        
         if let Ok(scanned_files) = Self::scan_files(&paths, use_mft).await {
             let file_count = scanned_files.len();
             
+            SESSION_CONTEXT.set_indexing(IndexingStatus {
+                is_indexing: false,
+                indexed_files: file_count as u64,
+                message: format!("{} files indexed", file_count),
+            });
+            
             // 构建索引
-            let mut index: HashMap<char, Vec<usize>> = HashMap::new();
-            for (idx, file) in scanned_files.iter().enumerate() {
-                if let Some(first_char) = file.name.chars().next() {
-                    let key = first_char.to_lowercase().next().unwrap_or(first_char);
-                    index.entry(key).or_insert_with(Vec::new).push(idx);
-                }
-            }
+            let index = Self::build_name_index(&scanned_files);
             
             // 保存到内存
             let mut files_guard = files.write().await;
@@ -474,6 +613,7 @@ impl FileSearchPlugin {
                         created_at: Utc::now(),
                         files: scanned_files,
                         name_index: index,
+                        exclusions: IndexExclusions::load().fingerprint(),
                     };
                     
                     if let Err(e) = Self::save_cache(&cache_path, &cache).await {
@@ -485,6 +625,80 @@ impl FileSearchPlugin {
             });
         } else {
             tracing::error!("File scan failed");
+            SESSION_CONTEXT.set_indexing(IndexingStatus {
+                is_indexing: false,
+                indexed_files: 0,
+                message: "File scan failed".to_string(),
+            });
+        }
+    }
+    
+    /// 按文件名首字母（小写）建立索引
+    fn build_name_index(files: &[FileItem]) -> HashMap<char, Vec<usize>> {
+        let mut index: HashMap<char, Vec<usize>> = HashMap::new();
+        for (idx, file) in files.iter().enumerate() {
+            if let Some(first_char) = file.name.chars().next() {
+                let key = first_char.to_lowercase().next().unwrap_or(first_char);
+                index.entry(key).or_insert_with(Vec::new).push(idx);
+            }
+        }
+        index
+    }
+    
+    /// 🔥 文件已不存在：从内存索引、BFS 缓存、监听索引和 MFT 墓碑中移除
+    async fn forget_missing(&self, path: &str) {
+        // 先处理监听索引和 MFT（不依赖内存列表）
+        self.watched_drives.forget(std::path::Path::new(path));
+        #[cfg(target_os = "windows")]
+        self.forget_mft_path(path).await;
+        
+        let snapshot = {
+            let mut files = self.files.write().await;
+            let before = files.len();
+            files.retain(|file| file.path != path);
+            if files.len() == before {
+                return;
+            }
+            let index = Self::build_name_index(&files);
+            *self.name_index.write().await = index.clone();
+            (files.clone(), index)
+        };
+        tracing::info!("🗑️ Removed missing file from index: {}", path);
+        
+        // MFT 模式不保存缓存（下次启动从数据库重建）
+        if self.config.read().await.use_mft && cfg!(target_os = "windows") {
+            return;
+        }
+        
+        // 异步重写 BFS 缓存，避免下次启动再次加载失效条目
+        let (files, name_index) = snapshot;
+        tokio::spawn(async move {
+            if let Ok(cache_path) = Self::get_cache_path() {
+                let cache = FileCache {
+                    version: 1,
+                    created_at: Utc::now(),
+                    files,
+                    name_index,
+                    exclusions: IndexExclusions::load().fingerprint(),
+                };
+                if let Err(e) = Self::save_cache(&cache_path, &cache).await {
+                    tracing::error!("Failed to save cache: {}", e);
+                }
+            }
+        });
+    }
+    
+    /// 将丢失的路径写入 MFT 墓碑（USN 事件尚未处理时查询也不再返回）
+    #[cfg(target_os = "windows")]
+    async fn forget_mft_path(&self, path: &str) {
+        let Some(drive) = path.chars().next().map(|c| c.to_ascii_uppercase()) else {
+            return;
+        };
+        let mut cache = self.mft_cache.write().await;
+        if let Some(cached) = cache.get_mut(&drive) {
+            if let Err(e) = cached.query.forget_path(&cached.path_reader, path) {
+                tracing::warn!("Failed to tombstone missing path {}: {:#}", path, e);
+            }
         }
     }
     
@@ -570,6 +784,7 @@ impl FileSearchPlugin {
         tokio::task::spawn_blocking(move || {
             let mut files = Vec::with_capacity(1000000); // 预分配 100 万容量
             let start = std::time::Instant::now();
+            let exclusions = IndexExclusions::load();
             
             for base_path in &paths {
                 if !base_path.exists() {
@@ -580,7 +795,7 @@ impl FileSearchPlugin {
                 tracing::info!("⚡ BFS scanning {}:\\ ...", drive_letter);
                 
                 let count_before = files.len();
-                Self::ultra_fast_walk(base_path, &mut files);
+                Self::ultra_fast_walk(base_path, &mut files, &exclusions);
                 let count_after = files.len();
                 
                 let elapsed = start.elapsed().as_secs_f32();
@@ -599,60 +814,17 @@ impl FileSearchPlugin {
     }
     
     /// 超快速遍历（优化版本）
-    fn ultra_fast_walk(base_path: &PathBuf, files: &mut Vec<FileItem>) {
-        // 使用 VecDeque 作为 BFS 队列，比递归更快
-        let mut queue = std::collections::VecDeque::with_capacity(1000);
-        queue.push_back(base_path.clone());
-        
-        // 跳过列表（最小化）
-        let skip_names = [
-            "$Recycle.Bin",
-            "System Volume Information", 
-            "Config.Msi",
-            "Recovery",
-            "$RECYCLE.BIN",
-        ];
-        
-        while let Some(current_dir) = queue.pop_front() {
-            // 快速读取目录，忽略错误
-            if let Ok(entries) = std::fs::read_dir(&current_dir) {
-                for entry in entries.flatten() {
-                    // 快速获取路径和名称
-                    let Ok(file_name) = entry.file_name().into_string() else {
-                        continue;
-                    };
-                    
-                    // 快速跳过检查
-                    if skip_names.contains(&file_name.as_str()) {
-                        continue;
-                    }
-                    
-                    let path = entry.path();
-                    let path_str = path.to_string_lossy().into_owned();
-                    
-                    // 快速判断是否是目录（避免元数据查询）
-                    let is_dir = if let Ok(file_type) = entry.file_type() {
-                        file_type.is_dir()
-                    } else {
-                        false
-                    };
-                    
-                    // 直接添加，不做其他检查
-                    files.push(FileItem {
-                        path: path_str,
-                        name: file_name,
-                        is_dir,
-                        size: 0,  // BFS 模式不获取大小（性能优化）
-                        modified: 0,
-                    });
-                    
-                    // 如果是目录，加入队列
-                    if is_dir {
-                        queue.push_back(path);
-                    }
-                }
-            }
-        }
+    fn ultra_fast_walk(base_path: &PathBuf, files: &mut Vec<FileItem>, exclusions: &IndexExclusions) {
+        drive_index::walk(base_path, exclusions, |path, name, is_dir| {
+            // 直接添加，不做其他检查
+            files.push(FileItem {
+                path,
+                name,
+                is_dir,
+                size: 0,  // BFS 模式不获取大小（性能优化）
+                modified: 0,
+            });
+        });
     }
     
     /// 打开文件或文件夹
@@ -701,6 +873,12 @@ impl FileSearchPlugin {
         Self::open_file(&folder).await
     }
     
+    /// 结果路径是否已不存在（仅 NotFound 视为丢失，权限等错误照常执行）
+    async fn is_missing(path: &str) -> bool {
+        std::path::Path::new(path).is_absolute()
+            && matches!(tokio::fs::symlink_metadata(path).await, Err(e) if e.kind() == std::io::ErrorKind::NotFound)
+    }
+    
     /// 获取文件图标
     #[cfg(target_os = "windows")]
     fn get_file_icon(path: &str, is_dir: bool) -> WoxImage {
@@ -735,12 +913,72 @@ impl FileSearchPlugin {
         }
     }
     
+    /// 首次扫描期间的等待提示（可刷新，扫描完成后自动替换为搜索结果）
+    #[cfg(target_os = "windows")]
+    fn indexing_placeholder(&self, id: &str, title: &str, subtitle: &str) -> QueryResult {
+        QueryResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            subtitle: subtitle.to_string(),
+            icon: WoxImage::emoji("⏳"),
+            preview: None,
+            score: 100,
+            context_data: serde_json::Value::Null,
+            group: None,
+            plugin_id: self.metadata.id.clone(),
+            refreshable: true,
+            actions: vec![],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        }
+    }
+    
+    /// 首次扫描进度：每个未就绪的驱动器一行（百分比、已扫描记录数、剩余时间）
+    #[cfg(target_os = "windows")]
+    fn scan_progress_results(&self, status: &mft_ipc::ServiceStatus) -> Vec<QueryResult> {
+        status
+            .drives
+            .iter()
+            .filter(|drive| !drive_index::is_mft_fallback(drive.drive))
+            .filter_map(|drive| {
+                let (title, subtitle) = match &drive.state {
+                    mft_ipc::DriveState::Scanning { progress: Some(progress) } => (
+                        format!("⚡ Indexing {}: {:.0}%", drive.drive, progress.percent),
+                        progress.detail(),
+                    ),
+                    mft_ipc::DriveState::Scanning { progress: None } => (
+                        format!("⚡ Indexing {}:", drive.drive),
+                        "Starting scan...".to_string(),
+                    ),
+                    mft_ipc::DriveState::Pending => (
+                        format!("⏸ {}: waiting to be indexed", drive.drive),
+                        "Queued for scanning".to_string(),
+                    ),
+                    mft_ipc::DriveState::Ready | mft_ipc::DriveState::Failed { .. } => return None,
+                };
+                Some(self.indexing_placeholder(
+                    &format!("mft_indexing_{}", drive.drive),
+                    &title,
+                    &subtitle,
+                ))
+            })
+            .collect()
+    }
+    
         /// 从 MFT 索引查询文件（基于 FST+RoaringBitmap）
     #[cfg(target_os = "windows")]
-    async fn query_from_mft_database(&self, search: &str, _ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+    async fn query_from_mft_database(&self, file_query: &FileQuery, _ctx: &QueryContext) -> Result<Vec<QueryResult>> {
         let query_start = std::time::Instant::now();
         use crate::utils::paths;
         
+        // 3-gram 索引需要关键词；只有过滤条件时用扩展名作为关键词
+        let Some(keyword) = file_query.index_keyword() else {
+            return Ok(Vec::new());
+        };
+        let search = keyword.as_str();
+        let filters = &file_query.filters;
+        
         // 使用统一的数据目录
         let output_dir = paths::get_mft_database_dir()?
             .to_string_lossy()
@@ -752,51 +990,45 @@ impl FileSearchPlugin {
         let db_dir = std::path::Path::new(&output_dir);
         if !db_dir.exists() {
             tracing::warn!("MFT database directory not found: {}", output_dir);
-            return Ok(vec![QueryResult {
-                id: "mft_scanning".to_string(),
-                title: "⚡ MFT Scanner is indexing...".to_string(),
-                subtitle: "Please wait for initial scan to complete".to_string(),
-                icon: WoxImage::emoji("⏳"),
-                preview: None,
-                score: 100,
-                context_data: serde_json::Value::Null,
-                group: None,
-                plugin_id: self.metadata.id.clone(),
-                refreshable: false,
-                actions: vec![],
-            }]);
+            return Ok(vec![self.indexing_placeholder(
+                "mft_scanning",
+                "⚡ MFT Scanner is indexing...",
+                "Please wait for initial scan to complete",
+            )]);
         }
         
-        // 🔥 获取所有驱动器并查询
-        let drives = Self::get_fixed_drives();
+        // 🔥 获取所有驱动器并查询（已退回 BFS 的驱动器由监听索引提供结果）
+        let drives: Vec<char> = Self::get_fixed_drives()
+            .into_iter()
+            .filter(|&drive| !drive_index::is_mft_fallback(drive))
+            .collect();
+        if drives.is_empty() {
+            return Ok(Vec::new());
+        }
         let mut all_results = Vec::new();
         
-        // 🔥 检查是否有任何驱动器就绪（验证 PID）
-        let mut any_drive_ready = false;
-        for drive in &drives {
-            let ready_file = format!("{}\\{}.ready", output_dir, drive);
-            if is_ready_file_valid(&ready_file) {
-                any_drive_ready = true;
-                break;
-            }
-        }
+        // 🔥 通过 IPC 查询服务状态（连接失败说明服务未运行）
+        let status = mft_ipc::fetch_status(&output_dir);
+        let ready_drives = status
+            .as_ref()
+            .map(|status| status.ready_drives())
+            .unwrap_or_default();
         
-        // 🔥 如果没有任何驱动器就绪，返回等待提示
-        if !any_drive_ready {
+        // 🔥 如果没有任何驱动器就绪，返回各驱动器的扫描进度（可刷新，前端定时重新查询）
+        if !drives.iter().any(|drive| ready_drives.contains(drive)) {
             tracing::info!("⏳ No drives ready yet, MFT Service is still indexing");
-            return Ok(vec![QueryResult {
-                id: "mft_indexing".to_string(),
-                title: "⚡ MFT Service is indexing...".to_string(),
-                subtitle: "Please wait a moment for the initial scan to complete".to_string(),
-                icon: WoxImage::emoji("⏳"),
-                preview: None,
-                score: 100,
-                context_data: serde_json::Value::Null,
-                group: None,
-                plugin_id: self.metadata.id.clone(),
-                refreshable: false,
-                actions: vec![],
-            }]);
+            let progress_results = status
+                .as_ref()
+                .map(|status| self.scan_progress_results(status))
+                .unwrap_or_default();
+            if !progress_results.is_empty() {
+                return Ok(progress_results);
+            }
+            return Ok(vec![self.indexing_placeholder(
+                "mft_indexing",
+                "⚡ MFT Service is indexing...",
+                "Please wait a moment for the initial scan to complete",
+            )]);
         }
         
         // 🔥 使用缓存的索引查询（缓存已在 init 时预加载）
@@ -805,13 +1037,23 @@ impl FileSearchPlugin {
         // 🔥 限制总结果数，避免评分耗时过长
         const MAX_TOTAL_RESULTS: usize = 50;
         const MAX_PER_DRIVE: usize = 20;
+        // 带过滤条件时多取候选，过滤后再截断
+        const MAX_FILTER_CANDIDATES: usize = 5000;
+        // 未能分类匹配时的默认分数
+        const MFT_DEFAULT_SCORE: i32 = 70;
         
+        let mut broken_drives = Vec::new();
         for drive in drives {
             if all_results.len() >= MAX_TOTAL_RESULTS {
                 break; // 已经收集足够的结果
             }
             
-            if let Some(cached) = cache.get_mut(&drive) {
+            // 执行查询（每个驱动器限制 20 条，总共最多 50 条）
+            let remaining = MAX_TOTAL_RESULTS - all_results.len();
+            let limit = remaining.min(MAX_PER_DRIVE);
+            let fetch_limit = if filters.is_empty() { limit } else { MAX_FILTER_CANDIDATES };
+            
+            let paths: Box<dyn Iterator<Item = (String, Option<PathMatch>)> + '_> = if let Some(cached) = cache.get_mut(&drive) {
                 // 🔥 检查索引版本是否需要重新加载
                 if cached.query.needs_reload() {
                     tracing::info!("🔄 Detected index version change for drive {}, will reload after this query...", drive);
@@ -844,110 +1086,160 @@ impl FileSearchPlugin {
                     // 继续使用旧索引完成本次查询
                 }
                 
-                // 执行查询（每个驱动器限制 20 条，总共最多 50 条）
-                let remaining = MAX_TOTAL_RESULTS - all_results.len();
-                let limit = remaining.min(MAX_PER_DRIVE);
-                
-                let file_ids = match cached.query.search(search, limit) {
-                    Ok(ids) => ids,
+                // 🔥 锚点词 3-gram 检索 + 完整路径校验（支持目录名和 "src\main" 形式）
+                match cached.query.search_paths(&cached.path_reader, search, fetch_limit) {
+                    Ok(hits) => Box::new(hits.into_iter().map(|hit| (hit.path, Some(hit.matched)))),
                     Err(e) => {
                         tracing::error!("FST search failed for drive {}: {:#}", drive, e);
+                        // 移出缓存，由健康检查重新打开（仍失败则退回 BFS）
+                        broken_drives.push(drive);
+                        continue;
+                    }
+                }
+            } else if ready_drives.contains(&drive) {
+                // 🔥 本地索引尚未加载时由 MFT Service 通过 IPC 代为查询
+                let result = mft_ipc::IpcClient::connect(&output_dir)
+                    .and_then(|mut client| client.query(drive, search, fetch_limit));
+                match result {
+                    Ok(paths) => Box::new(paths.into_iter().map(|path| (path, None))),
+                    Err(e) => {
+                        tracing::warn!("IPC query failed for drive {}: {:#}", drive, e);
                         continue;
                     }
+                }
+            } else {
+                continue;
+            };
+            
+            let mut drive_matches = 0;
+            for (path, matched) in paths {
+                if drive_matches >= limit {
+                    break;
+                }
+                // 需要大小/时间过滤时一次读取元数据，否则只做目录检查
+                let metadata = if filters.needs_metadata() {
+                    std::fs::metadata(&path).ok()
+                } else {
+                    None
+                };
+                let is_dir = match &metadata {
+                    Some(m) => m.is_dir(),
+                    None => std::path::Path::new(&path).is_dir(),
                 };
+                let name = std::path::Path::new(&path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&path)
+                    .to_string();
                 
-                // 🔥 优化3: 批量读取路径（如果实现了批量接口）
-                // 当前使用单个读取
-                for file_id in file_ids {
-                    if let Ok(path) = cached.path_reader.get_path(file_id) {
-                        // 判断是否为目录（简单检查）
-                        let is_dir = std::path::Path::new(&path).is_dir();
-                        let name = std::path::Path::new(&path)
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or(&path)
-                            .to_string();
-                        
-                        // 🔥 获取真实文件图标
-                        let icon = Self::get_file_icon(&path, is_dir);
-                        
-                        all_results.push(QueryResult {
-                            id: path.clone(),
-                            title: name.clone(),
-                            subtitle: path.clone(),
-                            icon,
-                            preview: Some(Preview::Text(format!(
-                                "Path: {}\nType: {}",
-                                path,
-                                if is_dir { "Directory" } else { "File" }
-                            ))),
-                            score: 70,  // 默认分数
-                            context_data: serde_json::json!({
-                                "path": path,
-                                "is_dir": is_dir,
-                            }),
-                            group: None,
-                            plugin_id: self.metadata.id.clone(),
-                            refreshable: false,
-                            actions: vec![
-                                Action {
-                                    id: "open".to_string(),
-                                    name: if is_dir {
-                                        "打开文件夹".to_string()
-                                    } else {
-                                        "打开文件".to_string()
-                                    },
-                                    icon: Some(WoxImage::emoji("📂")),
-                                    is_default: true,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "open_folder".to_string(),
-                                    name: "打开所在文件夹".to_string(),
-                                    icon: Some(WoxImage::emoji("📁")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "copy_path".to_string(),
-                                    name: "复制路径".to_string(),
-                                    icon: Some(WoxImage::emoji("📋")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "copy_file".to_string(),
-                                    name: "复制文件".to_string(),
-                                    icon: Some(WoxImage::emoji("📄")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "delete".to_string(),
-                                    name: "删除".to_string(),
-                                    icon: Some(WoxImage::emoji("🗑️")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "properties".to_string(),
-                                    name: "属性".to_string(),
-                                    icon: Some(WoxImage::emoji("ℹ️")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                            ],
-                        });
-                    }
+                if !filters.matches(&path, &name, is_dir, || metadata.as_ref().map(metadata_stat)) {
+                    continue;
                 }
+                drive_matches += 1;
+                
+                // 🔥 获取真实文件图标
+                let icon = Self::get_file_icon(&path, is_dir);
+                
+                // 🔥 按匹配类型（完全/前缀/子串/模糊）打分，命中位置用于高亮
+                // IPC 返回的路径在本地重新匹配，仍未匹配时退回 3-gram 高亮和默认分数
+                let matched = matched.or_else(|| path_match::match_path(&path, search));
+                let match_kind = matched.as_ref().map(|m| m.kind);
+                let (score, highlights) = match matched {
+                    Some(m) => (m.score(), MatchHighlights { title: m.name_spans, subtitle: m.path_spans }),
+                    None => (
+                        MFT_DEFAULT_SCORE,
+                        MatchHighlights {
+                            title: highlight::gram_spans(&name, search, 3),
+                            subtitle: highlight::gram_spans(&path, search, 3),
+                        },
+                    ),
+                };
+                
+                all_results.push(QueryResult {
+                    id: path.clone(),
+                    title: name.clone(),
+                    subtitle: path.clone(),
+                    icon,
+                    // 🔥 图片/PDF 通过 preview:// 协议预览
+                    preview: crate::preview::assets::file_preview(&path, is_dir).or_else(|| {
+                        Some(Preview::Text(format!(
+                            "Path: {}\nType: {}",
+                            path,
+                            if is_dir { "Directory" } else { "File" }
+                        )))
+                    }),
+                    score,
+                    context_data: serde_json::json!({
+                        "path": path,
+                        "is_dir": is_dir,
+                        "match_kind": match_kind,
+                    }),
+                    group: None,
+                    plugin_id: self.metadata.id.clone(),
+                    refreshable: false,
+                    actions: vec![
+                        Action {
+                            id: "open".to_string(),
+                            name: if is_dir {
+                                "打开文件夹".to_string()
+                            } else {
+                                "打开文件".to_string()
+                            },
+                            icon: Some(WoxImage::emoji("📂")),
+                            is_default: true,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "open_folder".to_string(),
+                            name: "打开所在文件夹".to_string(),
+                            icon: Some(WoxImage::emoji("📁")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "copy_path".to_string(),
+                            name: "复制路径".to_string(),
+                            icon: Some(WoxImage::emoji("📋")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "copy_file".to_string(),
+                            name: "复制文件".to_string(),
+                            icon: Some(WoxImage::emoji("📄")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "delete".to_string(),
+                            name: "删除".to_string(),
+                            icon: Some(WoxImage::emoji("🗑️")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "properties".to_string(),
+                            name: "属性".to_string(),
+                            icon: Some(WoxImage::emoji("ℹ️")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                    ],
+                    accessibility: None,
+                    highlights: highlights.non_empty(),
+                    badges: Vec::new(),
+                });
             }
         }
+        for drive in broken_drives {
+            cache.remove(&drive);
+        }
         
         // 如果没有结果
         if all_results.is_empty() {
@@ -963,6 +1255,9 @@ impl FileSearchPlugin {
                 plugin_id: self.metadata.id.clone(),
                 refreshable: false,
                 actions: vec![],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
         
@@ -977,6 +1272,239 @@ impl FileSearchPlugin {
         Ok(all_results)
     }
     
+    /// 模糊匹配文件名并检查过滤条件，返回 (分数, 命中字符)
+    fn match_file(&self, query: &FileQuery, path: &str, name: &str, is_dir: bool) -> Option<(i64, Vec<usize>)> {
+        let matched = if query.text.is_empty() {
+            Some((0, Vec::new()))
+        } else {
+            self.matcher.fuzzy_indices(name, &query.text)
+        };
+        // BFS/监听索引不含大小/时间，需要时再读取元数据
+        matched.filter(|_| {
+            query.filters.matches(path, name, is_dir, || {
+                std::fs::metadata(path).ok().map(|m| metadata_stat(&m))
+            })
+        })
+    }
+    
+    /// 构建文件搜索结果（BFS 索引和监听索引共用）
+    fn file_result(&self, path: &str, name: &str, is_dir: bool, score: i64, indices: &[usize]) -> QueryResult {
+        let icon = if is_dir {
+            WoxImage::emoji("📁")
+        } else {
+            // 根据扩展名显示不同图标
+            let icon_str = if let Some(ext_pos) = name.rfind('.') {
+                match &name[ext_pos + 1..].to_lowercase().as_str() {
+                    &"txt" | &"md" | &"log" => "📄",
+                    &"pdf" => "📕",
+                    &"doc" | &"docx" => "📘",
+                    &"xls" | &"xlsx" => "📊",
+                    &"ppt" | &"pptx" => "📊",
+                    &"zip" | &"rar" | &"7z" => "📦",
+                    &"jpg" | &"jpeg" | &"png" | &"gif" | &"bmp" => "🖼️",
+                    &"mp3" | &"wav" | &"flac" => "🎵",
+                    &"mp4" | &"avi" | &"mkv" => "🎬",
+                    &"exe" | &"msi" => "⚙️",
+                    &"js" | &"ts" | &"py" | &"rs" | &"go" | &"java" => "💻",
+                    _ => "📄",
+                }
+            } else {
+                "📄"
+            };
+            WoxImage::emoji(icon_str)
+        };
+        
+        // 🔥 模糊匹配命中的字符（副标题为完整路径，文件名位于末尾）
+        let title_spans = highlight::spans_from_indices(indices);
+        let name_offset = path.chars().count().saturating_sub(name.chars().count());
+        let highlights = MatchHighlights {
+            subtitle: title_spans
+                .iter()
+                .map(|s| HighlightSpan { start: s.start + name_offset, end: s.end + name_offset })
+                .collect(),
+            title: title_spans,
+        };
+        
+        QueryResult {
+            id: path.to_string(),
+            title: name.to_string(),
+            subtitle: path.to_string(),
+            icon,
+            preview: crate::preview::assets::file_preview(path, is_dir),
+            score: score as i32,
+            context_data: serde_json::Value::Null,
+            group: None,
+            plugin_id: self.metadata.id.clone(),
+            refreshable: false,
+            actions: vec![
+                Action {
+                    id: "open".to_string(),
+                    name: if is_dir { "打开文件夹" } else { "打开文件" }.to_string(),
+                    icon: Some(WoxImage::emoji("📂")),
+                    is_default: true,
+                    prevent_hide: false,
+                    hotkey: None,
+                },
+                Action {
+                    id: "open_folder".to_string(),
+                    name: "打开所在位置".to_string(),
+                    icon: Some(WoxImage::emoji("📁")),
+                    is_default: false,
+                    prevent_hide: false,
+                    hotkey: Some("Ctrl+O".to_string()),
+                },
+                Action {
+                    id: "copy_path".to_string(),
+                    name: "复制路径".to_string(),
+                    icon: Some(WoxImage::emoji("📋")),
+                    is_default: false,
+                    prevent_hide: true,
+                    hotkey: Some("Ctrl+C".to_string()),
+                },
+                Action {
+                    id: "copy_name".to_string(),
+                    name: "复制文件名".to_string(),
+                    icon: Some(WoxImage::emoji("📝")),
+                    is_default: false,
+                    prevent_hide: true,
+                    hotkey: None,
+                },
+                Action {
+                    id: "delete".to_string(),
+                    name: "删除".to_string(),
+                    icon: Some(WoxImage::emoji("🗑️")),
+                    is_default: false,
+                    prevent_hide: false,
+                    hotkey: Some("Del".to_string()),
+                },
+            ],
+            accessibility: None,
+            highlights: highlights.non_empty(),
+            badges: Vec::new(),
+        }
+    }
+    
+    /// 查询监听索引中的文件（非 NTFS / 可移动驱动器）
+    fn query_watched_drives(&self, query: &FileQuery, limit: usize) -> Vec<QueryResult> {
+        let mut results = Vec::new();
+        self.watched_drives.for_each(|path, entry| {
+            if let Some((score, indices)) = self.match_file(query, path, &entry.name, entry.is_dir) {
+                results.push(self.file_result(path, &entry.name, entry.is_dir, score, &indices));
+            }
+            results.len() < limit
+        });
+        results
+    }
+    
+    /// 文件内容搜索（匹配行片段放在预览中）
+    async fn query_content(&self, pattern: &str) -> Result<Vec<QueryResult>> {
+        const MAX_CONTENT_RESULTS: usize = 30;
+        
+        let query = FileQuery::parse(pattern, Utc::now().timestamp());
+        if query.text.chars().count() < 2 {
+            return Ok(Vec::new());
+        }
+        
+        // 每次读取配置，设置修改后立即生效
+        let config = match crate::storage::StorageManager::new() {
+            Ok(storage) => storage
+                .get_plugin_config("file_search")
+                .await
+                .map(|cfg| ContentSearchConfig::from_plugin_config(&cfg))
+                .unwrap_or_default(),
+            Err(_) => ContentSearchConfig::default(),
+        };
+        
+        // 候选文件：优先使用 BFS 内存索引，未加载（如 MFT 模式）时遍历根目录
+        let indexed: Vec<PathBuf> = self
+            .files
+            .read()
+            .await
+            .iter()
+            .filter(|f| !f.is_dir && config.accepts(std::path::Path::new(&f.path)))
+            .map(|f| PathBuf::from(&f.path))
+            .collect();
+        
+        let search_start = std::time::Instant::now();
+        let text = query.text.clone();
+        let matches = tokio::task::spawn_blocking(move || {
+            let candidates = if indexed.is_empty() {
+                content_search::walk_candidates(&config)
+            } else {
+                indexed
+            };
+            content_search::search(&query.text, &candidates, &query.filters, &config, MAX_CONTENT_RESULTS)
+        })
+        .await?;
+        
+        tracing::info!(
+            "📄 Content search '{}' → {} files in {:.2}ms",
+            text,
+            matches.len(),
+            search_start.elapsed().as_secs_f64() * 1000.0
+        );
+        
+        let results = matches
+            .into_iter()
+            .map(|m| {
+                let path = m.path.to_string_lossy().to_string();
+                let name = m
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.clone());
+                let first_line = m.lines.first().map(|(_, line)| line.clone()).unwrap_or_default();
+                
+                QueryResult {
+                    id: path.clone(),
+                    title: name,
+                    subtitle: format!("{} matches · {}", m.total, first_line),
+                    icon: Self::get_file_icon(&path, false),
+                    preview: Some(Preview::Text(m.preview())),
+                    score: 60 + m.total.min(30) as i32,
+                    context_data: serde_json::json!({
+                        "path": path,
+                        "is_dir": false,
+                    }),
+                    group: Some("Content".to_string()),
+                    plugin_id: self.metadata.id.clone(),
+                    refreshable: false,
+                    actions: vec![
+                        Action {
+                            id: "open".to_string(),
+                            name: "打开文件".to_string(),
+                            icon: Some(WoxImage::emoji("📂")),
+                            is_default: true,
+                            prevent_hide: false,
+                            hotkey: None,
+                        },
+                        Action {
+                            id: "open_folder".to_string(),
+                            name: "打开所在位置".to_string(),
+                            icon: Some(WoxImage::emoji("📁")),
+                            is_default: false,
+                            prevent_hide: false,
+                            hotkey: Some("Ctrl+O".to_string()),
+                        },
+                        Action {
+                            id: "copy_path".to_string(),
+                            name: "复制路径".to_string(),
+                            icon: Some(WoxImage::emoji("📋")),
+                            is_default: false,
+                            prevent_hide: true,
+                            hotkey: Some("Ctrl+C".to_string()),
+                        },
+                    ],
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
+                }
+            })
+            .collect();
+        
+        Ok(results)
+    }
+    
     /// 复制文本到剪贴板
     async fn copy_to_clipboard(text: &str) -> Result<()> {
         let text = text.to_string();
@@ -1071,6 +1599,52 @@ impl FileSearchPlugin {
         .await?
     }
 
+    /// 删除预演：文件大小，文件夹内的文件/子文件夹数量与总大小（路径不存在时返回 None，由 execute 报告文件缺失）
+    fn delete_preview(path: &std::path::Path) -> Result<Option<DryRun>> {
+        use crate::core::confirmation::format_bytes;
+        
+        let metadata = match std::fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        
+        let mut preview = if metadata.is_dir() {
+            let (mut files, mut dirs, mut bytes) = (0u64, 0u64, 0u64);
+            let mut truncated = false;
+            for entry in walkdir::WalkDir::new(path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+                if files + dirs >= DELETE_PREVIEW_LIMIT {
+                    truncated = true;
+                    break;
+                }
+                if entry.file_type().is_dir() {
+                    dirs += 1;
+                } else {
+                    files += 1;
+                    bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+            }
+            let at_least = if truncated { "≥ " } else { "" };
+            DryRun::new(format!("Permanently delete folder \"{}\" and everything in it", name))
+                .with_detail("Size", format!("{}{}", at_least, format_bytes(bytes)))
+                .with_detail("Contents", format!("{}{} files, {} folders", at_least, files, dirs))
+        } else {
+            DryRun::new(format!("Permanently delete file \"{}\"", name))
+                .with_detail("Size", format_bytes(metadata.len()))
+        };
+        
+        preview = preview.with_detail("Path", path.display().to_string());
+        if let Ok(modified) = metadata.modified() {
+            let modified: DateTime<Utc> = modified.into();
+            preview = preview.with_detail("Modified", modified.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string());
+        }
+        Ok(Some(preview))
+    }
+    
     /// 复制文件到剪贴板
     async fn copy_file_to_clipboard(result_id: &str) -> Result<()> {
         let path = result_id.to_string();
@@ -1193,10 +1767,17 @@ impl Plugin for FileSearchPlugin {
     }
     
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let search = ctx.search.trim();
+        // 🔥 内容搜索：content:关键词 或 ff 关键词
+        if let Some(pattern) = content_search::parse_content_query(ctx.search.trim(), &ctx.trigger_keyword) {
+            return self.query_content(pattern).await;
+        }
         
-        // 至少输入2个字符才开始搜索
-        if search.len() < 2 {
+        // 🔥 拆出过滤词（ext:/size:/modified:/path:），剩余部分为关键词
+        let query = FileQuery::parse(ctx.search.trim(), Utc::now().timestamp());
+        let search = query.text.as_str();
+        
+        // 最短查询长度由插件管理器按 query_policy 统一过滤
+        if search.is_empty() && query.filters.is_empty() {
             return Ok(Vec::new());
         }
         
@@ -1205,15 +1786,27 @@ impl Plugin for FileSearchPlugin {
         {
             let use_mft = self.config.read().await.use_mft;
             if use_mft {
-                return self.query_from_mft_database(search, ctx).await;
+                let mut results = self.query_from_mft_database(&query, ctx).await?;
+                
+                // 🔥 合并非 NTFS / 可移动驱动器（监听索引）的结果
+                let watched = self.query_watched_drives(&query, 50);
+                if !watched.is_empty() {
+                    results.retain(|r| r.id != "no_results");
+                    results.extend(watched);
+                    results.sort_by(|a, b| b.score.cmp(&a.score));
+                }
+                return Ok(results);
             }
         }
         
+        // 🔥 非 NTFS / 可移动驱动器（监听索引）
+        let watched = self.query_watched_drives(&query, 50);
+        
         // 标准 BFS 模式：使用内存索引
         let files = self.files.read().await;
         
         // 如果还没扫描完成
-        if files.is_empty() {
+        if files.is_empty() && watched.is_empty() {
             return Ok(vec![QueryResult {
                 id: "scanning".to_string(),
                 title: "⚡ Indexing files...".to_string(),
@@ -1226,6 +1819,9 @@ impl Plugin for FileSearchPlugin {
                 plugin_id: self.metadata.id.clone(),
                 refreshable: false,
                 actions: vec![],
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             }]);
         }
         
@@ -1234,117 +1830,50 @@ impl Plugin for FileSearchPlugin {
         let search_lower = search.to_lowercase();
         let first_char = search_lower.chars().next().unwrap_or(' ');
         
-        // 使用索引加速搜索
-        let indices_to_search = if let Some(indices) = name_index.get(&first_char) {
-            indices.as_slice()
+        // 使用索引加速搜索；只有过滤条件时遍历全部文件
+        let indices_to_search: Box<dyn Iterator<Item = usize> + '_> = if search.is_empty() {
+            Box::new(0..files.len())
+        } else if let Some(indices) = name_index.get(&first_char) {
+            Box::new(indices.iter().copied())
         } else {
-            // 如果索引中没有，搜索全部（兜底）
-            &[]
+            // 如果索引中没有，说明没有匹配首字母的，快速返回
+            Box::new(std::iter::empty())
         };
         
-        // 如果索引为空，说明没有匹配首字母的，快速返回
-        if !indices_to_search.is_empty() {
-            for &idx in indices_to_search {
-                if let Some(file) = files.get(idx) {
-                    if let Some(score) = self.matcher.fuzzy_match(&file.name, search) {
-                        let icon = if file.is_dir {
-                            WoxImage::emoji("📁")
-                        } else {
-                            // 根据扩展名显示不同图标
-                            let icon_str = if let Some(ext_pos) = file.name.rfind('.') {
-                                match &file.name[ext_pos + 1..].to_lowercase().as_str() {
-                                    &"txt" | &"md" | &"log" => "📄",
-                                    &"pdf" => "📕",
-                                    &"doc" | &"docx" => "📘",
-                                    &"xls" | &"xlsx" => "📊",
-                                    &"ppt" | &"pptx" => "📊",
-                                    &"zip" | &"rar" | &"7z" => "📦",
-                                    &"jpg" | &"jpeg" | &"png" | &"gif" | &"bmp" => "🖼️",
-                                    &"mp3" | &"wav" | &"flac" => "🎵",
-                                    &"mp4" | &"avi" | &"mkv" => "🎬",
-                                    &"exe" | &"msi" => "⚙️",
-                                    &"js" | &"ts" | &"py" | &"rs" | &"go" | &"java" => "💻",
-                                    _ => "📄",
-                                }
-                            } else {
-                                "📄"
-                            };
-                            WoxImage::emoji(icon_str)
-                        };
-                        
-                        results.push(QueryResult {
-                            id: file.path.clone(),
-                            title: file.name.clone(),
-                            subtitle: file.path.clone(),
-                            icon,
-                            preview: None,
-                            score: score as i32,
-                            context_data: serde_json::Value::Null,
-                            group: None,
-                            plugin_id: self.metadata.id.clone(),
-                            refreshable: false,
-                            actions: vec![
-                                Action {
-                                    id: "open".to_string(),
-                                    name: if file.is_dir { "打开文件夹" } else { "打开文件" }.to_string(),
-                                    icon: Some(WoxImage::emoji("📂")),
-                                    is_default: true,
-                                    prevent_hide: false,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "open_folder".to_string(),
-                                    name: "打开所在位置".to_string(),
-                                    icon: Some(WoxImage::emoji("📁")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: Some("Ctrl+O".to_string()),
-                                },
-                                Action {
-                                    id: "copy_path".to_string(),
-                                    name: "复制路径".to_string(),
-                                    icon: Some(WoxImage::emoji("📋")),
-                                    is_default: false,
-                                    prevent_hide: true,
-                                    hotkey: Some("Ctrl+C".to_string()),
-                                },
-                                Action {
-                                    id: "copy_name".to_string(),
-                                    name: "复制文件名".to_string(),
-                                    icon: Some(WoxImage::emoji("📝")),
-                                    is_default: false,
-                                    prevent_hide: true,
-                                    hotkey: None,
-                                },
-                                Action {
-                                    id: "delete".to_string(),
-                                    name: "删除".to_string(),
-                                    icon: Some(WoxImage::emoji("🗑️")),
-                                    is_default: false,
-                                    prevent_hide: false,
-                                    hotkey: Some("Del".to_string()),
-                                },
-                            ],
-                        });
-                        
-                        // 限制返回结果数量，避免 UI 卡顿
-                        if results.len() >= 50 {
-                            break;
-                        }
+        for idx in indices_to_search {
+            if let Some(file) = files.get(idx) {
+                if let Some((score, indices)) = self.match_file(&query, &file.path, &file.name, file.is_dir) {
+                    results.push(self.file_result(&file.path, &file.name, file.is_dir, score, &indices));
+                    
+                    // 限制返回结果数量，避免 UI 卡顿
+                    if results.len() >= 50 {
+                        break;
                     }
                 }
             }
         }
         
+        results.extend(watched);
+        
         // 按分数排序
         results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
         
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         tracing::info!("FileSearch::execute - result_id: {}, action_id: {}", result_id, action_id);
         
+        // 🔥 目标文件已不存在：从索引和使用记录中移除，并告知前端
+        if matches!(action_id, "open" | "open_folder" | "copy_file" | "delete" | "properties" | "copy_name")
+            && Self::is_missing(result_id).await
+        {
+            tracing::info!("File no longer exists: {}", result_id);
+            self.forget_missing(result_id).await;
+            return Ok(ExecuteOutcome::file_missing(result_id));
+        }
+        
+        let mut outcome = ExecuteOutcome::Hide;
         match action_id {
             "open" => {
                 tracing::info!("Executing 'open' action");
@@ -1357,10 +1886,12 @@ impl Plugin for FileSearchPlugin {
             "copy_path" => {
                 tracing::info!("Executing 'copy_path' action");
                 Self::copy_to_clipboard(result_id).await?;
+                outcome = ExecuteOutcome::message("Path copied to clipboard");
             }
             "copy_file" => {
                 tracing::info!("Executing 'copy_file' action");
                 Self::copy_file_to_clipboard(result_id).await?;
+                outcome = ExecuteOutcome::message("File copied to clipboard");
             }
             "delete" => {
                 tracing::info!("Executing 'delete' action");
@@ -1375,6 +1906,7 @@ impl Plugin for FileSearchPlugin {
                 let path_buf = PathBuf::from(result_id);
                 if let Some(file_name) = path_buf.file_name() {
                     Self::copy_to_clipboard(&file_name.to_string_lossy()).await?;
+                    outcome = ExecuteOutcome::message("Name copied to clipboard");
                 }
             }
             _ => {
@@ -1382,7 +1914,15 @@ impl Plugin for FileSearchPlugin {
             }
         }
         
-        Ok(())
+        Ok(outcome)
+    }
+    
+    async fn dry_run(&self, result_id: &str, action_id: &str) -> Result<Option<DryRun>> {
+        if action_id != "delete" {
+            return Ok(None);
+        }
+        let path = PathBuf::from(result_id);
+        tokio::task::spawn_blocking(move || Self::delete_preview(&path)).await?
     }
 }
 
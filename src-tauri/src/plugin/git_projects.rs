@@ -1,11 +1,18 @@
 // Git 项目快速访问插件
 
 use crate::core::types::*;
+use crate::plugin::workspace_members::{self, MemberCache};
 use anyhow::Result;
 use async_trait::async_trait;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// 搜索时展开工作区成员的项目数（按分数前几个）
+const MAX_EXPANDED_PROJECTS: usize = 3;
+
+/// 每个项目最多显示的工作区成员数
+const MAX_MEMBERS_PER_PROJECT: usize = 10;
 
 #[derive(Debug, Clone)]
 struct GitProject {
@@ -15,6 +22,7 @@ struct GitProject {
 
 pub struct GitProjectsPlugin {
     metadata: PluginMetadata,
+    members: MemberCache,
 }
 
 impl GitProjectsPlugin {
@@ -32,7 +40,10 @@ impl GitProjectsPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "linux".to_string(), "macos".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Developer).with_tags(&["git", "projects"]),
             },
+            members: MemberCache::default(),
         }
     }
 
@@ -161,6 +172,118 @@ impl GitProjectsPlugin {
         dirs
     }
 
+    /// 项目的工作区成员结果（filter 为 "项目/子包" 语法中的子包筛选词）
+    fn member_results(&self, project: &QueryResult, filter: Option<&str>) -> Vec<QueryResult> {
+        let root = PathBuf::from(&project.id);
+        self.members
+            .get(&root)
+            .into_iter()
+            .filter(|member| filter.map_or(true, |f| member.name.to_lowercase().contains(f)))
+            .take(MAX_MEMBERS_PER_PROJECT)
+            .enumerate()
+            .map(|(idx, member)| {
+                let relative = member
+                    .path
+                    .strip_prefix(&root)
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                let mut actions = vec![
+                    Action {
+                        id: "open_vscode".to_string(),
+                        name: "在 VSCode 中打开".to_string(),
+                        icon: None,
+                        is_default: true,
+                        hotkey: None,
+                        prevent_hide: false,
+                    },
+                    Action {
+                        id: "open_explorer".to_string(),
+                        name: "在文件管理器中打开".to_string(),
+                        icon: None,
+                        is_default: false,
+                        hotkey: None,
+                        prevent_hide: false,
+                    },
+                    Action {
+                        id: "open_terminal".to_string(),
+                        name: "在终端中打开".to_string(),
+                        icon: None,
+                        is_default: false,
+                        hotkey: None,
+                        prevent_hide: false,
+                    },
+                ];
+                for (id, command) in [("run_dev", &member.commands.dev), ("run_test", &member.commands.test)] {
+                    if let Some(command) = command {
+                        actions.push(Action {
+                            id: id.to_string(),
+                            name: format!("在终端中运行 {}", command),
+                            icon: None,
+                            is_default: false,
+                            hotkey: None,
+                            prevent_hide: false,
+                        });
+                    }
+                }
+
+                QueryResult {
+                    id: member.path.display().to_string(),
+                    plugin_id: self.metadata.id.clone(),
+                    title: member.name.clone(),
+                    subtitle: format!("{} 工作区成员 · {} / {}", member.kind.label(), project.title, relative),
+                    icon: WoxImage::emoji(member.kind.emoji()),
+                    score: (project.score - 1 - idx as i32).max(1),
+                    context_data: serde_json::json!({
+                        "name": member.name,
+                        "path": member.path.display().to_string(),
+                        "workspace": project.id,
+                    }),
+                    actions,
+                    preview: None,
+                    refreshable: false,
+                    group: None,
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// 在终端中运行命令（工作目录为 dir，命令结束后保留终端）
+    fn run_in_terminal(dir: &Path, command: &str) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        {
+            std::process::Command::new("cmd")
+                .args(["/C", "start", "", "/D"])
+                .arg(dir)
+                .args(["cmd", "/K", command])
+                .spawn()?;
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let dir = dir.to_string_lossy().replace('\'', "'\\''");
+            let script = format!("cd '{}' && {}", dir, command)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            std::process::Command::new("osascript")
+                .args(["-e", &format!("tell application \"Terminal\" to do script \"{}\"", script)])
+                .spawn()?;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            std::process::Command::new("gnome-terminal")
+                .arg("--working-directory")
+                .arg(dir)
+                .args(["--", "sh", "-c", &format!("{}; exec \"${{SHELL:-sh}}\"", command)])
+                .spawn()?;
+        }
+
+        Ok(())
+    }
+
     fn find_vscode_path(&self) -> Option<PathBuf> {
         // 尝试找到 VSCode 可执行文件
         #[cfg(target_os = "windows")]
@@ -191,19 +314,16 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.trim();
-        
-        if query.is_empty() {
+        // 仅响应触发词路由（git / project）
+        if !matches!(ctx.trigger_keyword.as_str(), "git" | "project") {
             return Ok(Vec::new());
         }
+        let search_term = ctx.search.trim();
 
-        // 检查触发词
-        let search_term = if query.starts_with("git ") {
-            &query[4..]
-        } else if query.starts_with("project ") {
-            &query[8..]
-        } else {
-            return Ok(Vec::new());
+        // "项目/子包" 语法：先按项目匹配，再按名称筛选其工作区成员
+        let (search_term, member_filter) = match search_term.split_once('/') {
+            Some((project, member)) if !project.trim().is_empty() => (project.trim(), Some(member.trim().to_lowercase())),
+            _ => (search_term, None),
         };
 
         tracing::debug!("Git projects plugin queried with search_term: '{}'", search_term);
@@ -267,6 +387,9 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
                         preview: None,
                         refreshable: false,
                         group: None,
+                        accessibility: None,
+                        highlights: None,
+                        badges: Vec::new(),
                     }
                 })
                 .collect();
@@ -361,14 +484,29 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
                     preview: None,
                     refreshable: false,
                     group: None,
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
                 }
             })
             .collect();
 
-        Ok(query_results)
+        // 📚 前几个项目下列出 Monorepo 工作区成员（Cargo / pnpm / yarn / npm / go.work）
+        let mut expanded = Vec::with_capacity(query_results.len());
+        for (idx, project) in query_results.into_iter().enumerate() {
+            let members = if idx < MAX_EXPANDED_PROJECTS {
+                self.member_results(&project, member_filter.as_deref())
+            } else {
+                Vec::new()
+            };
+            expanded.push(project);
+            expanded.extend(members);
+        }
+
+        Ok(expanded)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         let project_path = PathBuf::from(result_id);
 
         match action_id {
@@ -396,7 +534,7 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
                 }
                 
                 tracing::info!("Opened project in VSCode: {}", result_id);
-                Ok(())
+                Ok(ExecuteOutcome::Hide)
             }
             "open_explorer" => {
                 // 在文件管理器中打开
@@ -422,7 +560,7 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
                 }
                 
                 tracing::info!("Opened project in file manager: {}", result_id);
-                Ok(())
+                Ok(ExecuteOutcome::Hide)
             }
             "open_terminal" => {
                 // 在终端中打开
@@ -448,7 +586,20 @@ impl crate::plugin::Plugin for GitProjectsPlugin {
                 }
                 
                 tracing::info!("Opened project in terminal: {}", result_id);
-                Ok(())
+                Ok(ExecuteOutcome::Hide)
+            }
+            "run_dev" | "run_test" => {
+                // 工作区成员的开发/测试脚本（执行时重新读取清单）
+                let commands = workspace_members::commands(&project_path);
+                let command = match action_id {
+                    "run_dev" => commands.dev,
+                    _ => commands.test,
+                }
+                .ok_or_else(|| anyhow::anyhow!("No runnable script found in {}", result_id))?;
+                Self::run_in_terminal(&project_path, &command)?;
+
+                tracing::info!("Running '{}' in {}", command, result_id);
+                Ok(ExecuteOutcome::Hide)
             }
             _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
         }
@@ -0,0 +1,348 @@
+// 跳转列表最近文档 - 解析 %APPDATA%\Microsoft\Windows\Recent\AutomaticDestinations 下的
+// *.automaticDestinations-ms（OLE 复合文档：DestList 流记录访问时间和固定状态，十六进制编号的流是对应的 .lnk），
+// 文件名即应用的 AppID（应用路径的 CRC-64），供应用搜索结果展开“最近文档”二级列表
+
+use super::app_search::lnk_target;
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// 自动跳转列表文件扩展名
+const EXTENSION: &str = "automaticDestinations-ms";
+
+/// 记录访问顺序的流
+const DEST_LIST_STREAM: &str = "/DestList";
+
+/// DestList 头部大小（版本、条目数、固定条目数等）
+const DEST_LIST_HEADER_SIZE: usize = 32;
+
+/// 条目内字段偏移（Win7 与 Win10 相同的部分）
+const ENTRY_NUMBER_OFFSET: usize = 0x58;
+const LAST_ACCESS_OFFSET: usize = 0x64;
+const PIN_STATUS_OFFSET: usize = 0x6C;
+
+/// 路径长度字段偏移：Win7（版本 1）为 0x70，Win10 起条目更长且路径后多 4 字节
+const V1_PATH_OFFSET: usize = 0x70;
+const V3_PATH_OFFSET: usize = 0x80;
+const V3_TRAILER_SIZE: usize = 4;
+
+/// FILETIME（1601 年起的 100ns）与 Unix 毫秒的差值
+const FILETIME_UNIX_EPOCH_MS: i64 = 11_644_473_600_000;
+
+/// 二级列表最多显示的文档数
+pub const MAX_DOCUMENTS: usize = 20;
+
+/// 计算 AppID 时，路径中的已知文件夹前缀替换为 KNOWNFOLDERID
+const KNOWN_FOLDERS: &[(&str, &str)] = &[
+    ("ProgramFiles", "{6D809377-6AF0-444B-8957-A3773F02200E}"),
+    ("ProgramFiles(x86)", "{7C5A40EF-A0FB-4BFC-874A-C0F2E0B9FA8E}"),
+    ("SystemRoot\\System32", "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}"),
+    ("SystemRoot", "{F38BF404-1D43-42F2-9305-67DE0B28FC23}"),
+];
+
+/// 跳转列表中的最近文档
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentDocument {
+    pub path: String,
+    /// 最后访问时间（Unix 毫秒）
+    pub last_access: i64,
+    /// 用户在跳转列表中固定的项目
+    pub pinned: bool,
+}
+
+/// DestList 条目（路径为空时从编号对应的 .lnk 流读取）
+#[derive(Debug)]
+struct DestEntry {
+    number: u32,
+    last_access: i64,
+    pinned: bool,
+    path: String,
+}
+
+/// 应用是否有跳转列表（只检查文件是否存在，用于决定是否提供 Tab 展开）
+pub fn has_recent_documents(target: &Path) -> bool {
+    destination_file(target).is_some()
+}
+
+/// 应用的最近文档：固定项在前，其余按访问时间倒序，跳过已不存在的文件
+pub fn recent_documents(target: &Path, limit: usize) -> Result<Vec<RecentDocument>> {
+    let Some(file) = destination_file(target) else {
+        return Ok(Vec::new());
+    };
+    let data = std::fs::read(&file)?;
+    Ok(parse(&data)?
+        .into_iter()
+        .filter(|doc| Path::new(&doc.path).exists())
+        .take(limit)
+        .collect())
+}
+
+/// 应用对应的跳转列表文件
+fn destination_file(target: &Path) -> Option<PathBuf> {
+    let dir = directories::BaseDirs::new()?
+        .data_dir()
+        .join(r"Microsoft\Windows\Recent\AutomaticDestinations");
+    let folders = known_folders();
+    app_ids(&target.to_string_lossy(), &folders)
+        .into_iter()
+        .map(|id| dir.join(format!("{}.{}", id, EXTENSION)))
+        .find(|path| path.is_file())
+}
+
+/// 当前系统的已知文件夹路径（来自环境变量）
+fn known_folders() -> Vec<(String, &'static str)> {
+    KNOWN_FOLDERS
+        .iter()
+        .filter_map(|(name, guid)| {
+            let path = match name.split_once('\\') {
+                Some((var, rest)) => format!("{}\\{}", std::env::var(var).ok()?, rest),
+                None => std::env::var(name).ok()?,
+            };
+            Some((path, *guid))
+        })
+        .collect()
+}
+
+/// 应用路径可能对应的 AppID：已知文件夹替换为 GUID 的形式优先，其次是原始路径
+fn app_ids(target: &str, folders: &[(String, &str)]) -> Vec<String> {
+    let mut candidates = Vec::new();
+    // 按前缀长度匹配（System32 优先于 Windows）
+    let mut sorted: Vec<&(String, &str)> = folders.iter().collect();
+    sorted.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+    if let Some((folder, guid)) = sorted.into_iter().find(|(folder, _)| {
+        target.len() > folder.len()
+            && target.as_bytes()[folder.len()] == b'\\'
+            && target[..folder.len()].eq_ignore_ascii_case(folder)
+    }) {
+        candidates.push(format!("{}{}", guid, &target[folder.len()..]));
+    }
+    candidates.push(target.to_string());
+
+    candidates
+        .into_iter()
+        .map(|path| {
+            let bytes: Vec<u8> = path.to_uppercase().encode_utf16().flat_map(u16::to_le_bytes).collect();
+            format!("{:016x}", crc64(&bytes))
+        })
+        .collect()
+}
+
+/// CRC-64（ECMA-182 反射形式）
+fn crc64(data: &[u8]) -> u64 {
+    const POLY: u64 = 0xC96C_5795_D787_0F42;
+    let mut crc = !0u64;
+    for &byte in data {
+        crc ^= byte as u64;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ POLY } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// 解析跳转列表文件（固定项在前，其余按访问时间倒序，按路径去重）
+fn parse(data: &[u8]) -> Result<Vec<RecentDocument>> {
+    let mut file = cfb::CompoundFile::open(Cursor::new(data))?;
+    let mut dest_list = Vec::new();
+    file.open_stream(DEST_LIST_STREAM)?.read_to_end(&mut dest_list)?;
+
+    let mut documents = Vec::new();
+    for entry in parse_dest_list(&dest_list)? {
+        let path = if is_local_path(&entry.path) {
+            Some(entry.path)
+        } else {
+            // 条目路径为空或为 shell 路径时，以编号对应的快捷方式为准
+            let mut lnk = Vec::new();
+            file.open_stream(format!("/{:x}", entry.number))
+                .and_then(|mut stream| stream.read_to_end(&mut lnk))
+                .ok()
+                .and_then(|_| lnk_target(&lnk))
+        };
+        if let Some(path) = path {
+            documents.push(RecentDocument {
+                path,
+                last_access: entry.last_access,
+                pinned: entry.pinned,
+            });
+        }
+    }
+
+    documents.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.last_access.cmp(&a.last_access)));
+    let mut seen = HashSet::new();
+    documents.retain(|doc| seen.insert(doc.path.to_lowercase()));
+    Ok(documents)
+}
+
+/// 解析 DestList 流
+fn parse_dest_list(data: &[u8]) -> Result<Vec<DestEntry>> {
+    let u16_at = |offset: usize| data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let u32_at = |offset: usize| data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let u64_at = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    };
+
+    let version = u32_at(0).ok_or_else(|| anyhow!("DestList stream is too short"))?;
+    let count = u32_at(4).unwrap_or(0) as usize;
+    let (path_offset, trailer) = if version == 1 {
+        (V1_PATH_OFFSET, 0)
+    } else {
+        (V3_PATH_OFFSET, V3_TRAILER_SIZE)
+    };
+
+    let mut entries = Vec::new();
+    let mut offset = DEST_LIST_HEADER_SIZE;
+    while entries.len() < count {
+        let (Some(number), Some(filetime), Some(pin), Some(len)) = (
+            u32_at(offset + ENTRY_NUMBER_OFFSET),
+            u64_at(offset + LAST_ACCESS_OFFSET),
+            u32_at(offset + PIN_STATUS_OFFSET),
+            u16_at(offset + path_offset),
+        ) else {
+            break;
+        };
+        let start = offset + path_offset + 2;
+        let Some(raw) = data.get(start..start + len * 2) else {
+            break;
+        };
+        let units: Vec<u16> = raw.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        entries.push(DestEntry {
+            number,
+            last_access: (filetime / 10_000) as i64 - FILETIME_UNIX_EPOCH_MS,
+            // 未固定为 -1，否则为固定顺序
+            pinned: pin as i32 >= 0,
+            path: String::from_utf16_lossy(&units),
+        });
+        offset = start + len * 2 + trailer;
+    }
+    Ok(entries)
+}
+
+/// 本地或网络文件路径（排除 ::{GUID} 等 shell 路径）
+fn is_local_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with("\\\\") || (bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Win10 格式的 DestList 条目
+    fn dest_entry(number: u32, unix_ms: i64, pinned: bool, path: &str) -> Vec<u8> {
+        let mut entry = vec![0u8; V3_PATH_OFFSET];
+        entry[ENTRY_NUMBER_OFFSET..ENTRY_NUMBER_OFFSET + 4].copy_from_slice(&number.to_le_bytes());
+        let filetime = ((unix_ms + FILETIME_UNIX_EPOCH_MS) * 10_000) as u64;
+        entry[LAST_ACCESS_OFFSET..LAST_ACCESS_OFFSET + 8].copy_from_slice(&filetime.to_le_bytes());
+        let pin: i32 = if pinned { 0 } else { -1 };
+        entry[PIN_STATUS_OFFSET..PIN_STATUS_OFFSET + 4].copy_from_slice(&pin.to_le_bytes());
+        let units: Vec<u16> = path.encode_utf16().collect();
+        entry.extend_from_slice(&(units.len() as u16).to_le_bytes());
+        entry.extend(units.iter().flat_map(|u| u.to_le_bytes()));
+        entry.extend_from_slice(&[0; V3_TRAILER_SIZE]);
+        entry
+    }
+
+    /// 只包含 LinkInfo.LocalBasePath 的快捷方式
+    fn lnk(path: &str) -> Vec<u8> {
+        let mut data = vec![0u8; 0x4C];
+        data[0] = 0x4C;
+        data[0x14] = 0x2; // HasLinkInfo
+        let mut link_info = vec![0u8; 0x1C];
+        link_info[4..8].copy_from_slice(&0x1Cu32.to_le_bytes());
+        link_info[8..12].copy_from_slice(&1u32.to_le_bytes());
+        link_info[0x10..0x14].copy_from_slice(&0x1Cu32.to_le_bytes());
+        link_info.extend_from_slice(path.as_bytes());
+        link_info.push(0);
+        data.extend_from_slice(&link_info);
+        data
+    }
+
+    fn jump_list(entries: &[Vec<u8>], streams: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut dest_list = vec![0u8; DEST_LIST_HEADER_SIZE];
+        dest_list[0..4].copy_from_slice(&4u32.to_le_bytes());
+        dest_list[4..8].copy_from_slice(&(entries.len() as u32).to_le_bytes());
+        for entry in entries {
+            dest_list.extend_from_slice(entry);
+        }
+
+        let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        file.create_stream(DEST_LIST_STREAM).unwrap().write_all(&dest_list).unwrap();
+        for (number, data) in streams {
+            file.create_stream(format!("/{:x}", number)).unwrap().write_all(data).unwrap();
+        }
+        file.flush().unwrap();
+        file.into_inner().into_inner()
+    }
+
+    #[test]
+    fn test_parse_orders_pinned_then_recent() {
+        let data = jump_list(
+            &[
+                dest_entry(1, 1_000, false, r"C:\docs\old.docx"),
+                dest_entry(2, 3_000, false, r"C:\docs\new.docx"),
+                dest_entry(3, 500, true, r"C:\docs\pinned.docx"),
+                dest_entry(4, 2_000, false, r"c:\DOCS\NEW.docx"),
+            ],
+            &[],
+        );
+
+        let docs = parse(&data).unwrap();
+        let paths: Vec<&str> = docs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec![r"C:\docs\pinned.docx", r"C:\docs\new.docx", r"C:\docs\old.docx"]);
+        assert!(docs[0].pinned && !docs[1].pinned);
+        assert_eq!(docs[1].last_access, 3_000);
+    }
+
+    #[test]
+    fn test_parse_falls_back_to_lnk_stream() {
+        let data = jump_list(
+            &[
+                dest_entry(0xa, 1_000, false, "knownfolder:{FDD39AD0-238F-46AF-ADB4-6C85480369C7}"),
+                dest_entry(0xb, 2_000, false, ""),
+            ],
+            &[(0xa, lnk(r"C:\Users\me\Documents\report.docx"))],
+        );
+
+        let docs = parse(&data).unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, r"C:\Users\me\Documents\report.docx");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_files() {
+        assert!(parse(b"not a compound file").is_err());
+        // 截断的 DestList 只返回完整条目
+        let mut entry = dest_entry(1, 1_000, false, r"C:\a.txt");
+        entry.truncate(entry.len() - 8);
+        assert!(parse(&jump_list(&[entry], &[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_crc64() {
+        assert_eq!(crc64(b"123456789"), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn test_app_ids_substitute_known_folders() {
+        let folders = vec![
+            (r"C:\Program Files".to_string(), KNOWN_FOLDERS[0].1),
+            (r"C:\Windows".to_string(), KNOWN_FOLDERS[3].1),
+            (r"C:\Windows\System32".to_string(), KNOWN_FOLDERS[2].1),
+        ];
+        let word = r"C:\Program Files\Microsoft Office\root\Office16\WINWORD.EXE";
+        let ids = app_ids(word, &folders);
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], app_ids(r"{6D809377-6AF0-444B-8957-A3773F02200E}\Microsoft Office\root\Office16\winword.exe", &[])[0]);
+        assert_eq!(ids[1], app_ids(&word.to_lowercase(), &[])[0]);
+        assert!(ids.iter().all(|id| id.len() == 16));
+
+        // System32 优先于 Windows
+        let notepad = app_ids(r"c:\windows\system32\notepad.exe", &folders);
+        assert_eq!(notepad[0], app_ids(r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\notepad.exe", &[])[0]);
+        // 前缀必须在路径分隔处结束
+        assert_eq!(app_ids(r"C:\Program Files2\a.exe", &folders).len(), 1);
+    }
+}
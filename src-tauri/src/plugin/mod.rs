@@ -1,17 +1,28 @@
 // 插件系统
 
 pub mod calculator;
+pub mod calc_expr;        // 计算器表达式求值（百分比、位运算、进制字面量、ans）
 pub mod app_search;
+pub mod jump_lists;       // 跳转列表最近文档（应用结果 Tab 展开）
 pub mod file_search;
+pub mod file_filter;      // 文件搜索过滤语法（ext:/size:/modified:/path:）
+pub mod content_search;   // 文件内容搜索（content: / ff）
+pub mod drive_index;      // 按驱动器选择索引策略（MFT / 监听索引）
 pub mod web_search;
+pub mod opensearch;       // OpenSearch 描述文件解析（自定义搜索引擎导入）
 pub mod clipboard;
 pub mod unit_converter;
+pub mod exchange_rates;   // 汇率获取与缓存（货币换算）
 pub mod settings;
 pub mod browser;
+pub mod browser_profiles; // 浏览器配置文件发现与书签/历史读取
+pub mod browser_tabs;     // 浏览器已打开标签页（DevTools 协议）
 pub mod process;
 pub mod translator;
 pub mod devtools;
+pub mod color;            // 颜色解析与转换（开发工具）
 pub mod git_projects;
+pub mod workspace_members; // Monorepo 工作区成员识别（Git 项目子包）
 pub mod system_commands;
 pub mod execution_history;
 pub mod window_manager;
@@ -22,15 +33,139 @@ pub mod plugin_installer; // 插件安装器
 pub mod plugin_store;     // 插件商店
 pub mod workflow_engine;  // 工作流引擎
 pub mod smart_suggestion; // 智能建议
+pub mod plugin_suggestions; // 插件推荐（根据无结果查询）
+pub mod context_switcher; // 工作区上下文切换
+pub mod selection;        // 划词搜索
+pub mod plugin_storage;   // 插件数据存储
+pub mod external_host;    // 外部进程插件（JSON-RPC）
+pub mod wasm_runtime;     // WASM 插件运行时
+pub mod query_metrics;    // 插件查询耗时统计
+pub mod snippets;         // 文本片段
+pub mod windows_settings; // Windows 设置页
 
+use crate::core::query_cancel::QUERY_CANCELLED;
 use crate::core::types::*;
+use crate::workspace_context::WORKSPACE_CONTEXTS;
 use anyhow::Result;
 use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+
+/// 类型擦除辅助（为所有插件自动实现，用于安全的向下转型）
+pub trait AsAny: std::any::Any + Send + Sync {
+    fn as_any(&self) -> &dyn std::any::Any;
+    fn into_any_arc(self: Arc<Self>) -> Arc<dyn std::any::Any + Send + Sync>;
+}
+
+impl<T: std::any::Any + Send + Sync> AsAny for T {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any_arc(self: Arc<Self>) -> Arc<dyn std::any::Any + Send + Sync> {
+        self
+    }
+}
+
+/// 插件 ID
+pub type PluginId = String;
+
+/// 内置插件 ID（按注册顺序）
+pub const BUILTIN_PLUGIN_IDS: &[&str] = &[
+    "calculator",
+    "web_search",
+    "unit_converter",
+    "ilauncher.plugin.settings",
+    "ilauncher.plugin.plugin_manager",
+    "system-commands",
+    "windows_settings",
+    "window_manager",
+    "context_switcher",
+    "selection",
+    "snippets",
+    "execution-history",
+    "clipboard",
+    "app_search",
+    "browser",
+    "process",
+    "translator",
+    "devtools",
+    "git",
+    "file_search",
+    "ai_assistant",
+];
+
+/// 初始化时启动后台任务（文件监控、索引、健康检查）的内置插件，没有停止钩子，重新加载会让旧任务继续运行，需重启应用
+const RESTART_REQUIRED_PLUGIN_IDS: &[&str] = &["browser", "file_search"];
+
+/// 即时答案插件（纯内存计算，可在几毫秒内完成）
+pub const INSTANT_PLUGIN_IDS: &[&str] = &["calculator", "unit_converter", "clipboard"];
+
+/// 即时答案的总时间预算
+const INSTANT_ANSWER_BUDGET: std::time::Duration = std::time::Duration::from_millis(15);
+
+/// 触发词路由：输入以插件触发词加空白开头时（如 `git foo`），仅查询声明该触发词的插件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerRoute {
+    /// 命中的触发词（插件声明的原始写法）
+    pub keyword: String,
+    /// 触发词后紧跟的插件命令（需在插件元数据 commands 中声明）
+    pub command: Option<String>,
+    /// 去掉触发词和命令后的搜索内容
+    pub search: String,
+    /// 声明该触发词的插件
+    pub plugin_ids: HashSet<PluginId>,
+}
+
+/// 解析触发词路由（未命中任何插件时返回 None，查询广播给全部插件）
+pub fn route_trigger<'a, I>(input: &str, plugins: I) -> Option<TriggerRoute>
+where
+    I: IntoIterator<Item = &'a PluginMetadata>,
+{
+    let input = input.trim_start();
+    let (token, rest) = input.split_once(char::is_whitespace)?;
+    
+    let mut keyword = None;
+    let mut plugin_ids = HashSet::new();
+    let mut commands: Vec<&str> = Vec::new();
+    for metadata in plugins {
+        let Some(matched) = metadata.trigger_keywords.iter().find(|kw| kw.eq_ignore_ascii_case(token)) else {
+            continue;
+        };
+        keyword.get_or_insert_with(|| matched.clone());
+        plugin_ids.insert(metadata.id.clone());
+        commands.extend(metadata.commands.iter().map(|c| c.command.as_str()));
+    }
+    let keyword = keyword?;
+    
+    // 触发词后的第一个词为已声明命令时单独拆出
+    let rest = rest.trim_start();
+    let (first, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (command, search) = match commands.iter().find(|c| !first.is_empty() && c.eq_ignore_ascii_case(first)) {
+        Some(command) => (Some(command.to_string()), after.trim_start().to_string()),
+        None => (None, rest.to_string()),
+    };
+    
+    Some(TriggerRoute { keyword, command, search, plugin_ids })
+}
+
+/// 流式查询事件
+pub enum QueryEvent<'a> {
+    /// 即时答案（在其他插件之前一次性返回）
+    Instant(&'a [QueryResult]),
+    /// 单个插件的部分结果
+    Partial {
+        plugin_id: &'a str,
+        results: &'a [QueryResult],
+    },
+}
 
 /// 插件特征
 #[async_trait]
-pub trait Plugin: Send + Sync {
+pub trait Plugin: AsAny + Send + Sync {
     /// 获取插件元数据
     fn metadata(&self) -> &PluginMetadata;
     
@@ -38,13 +173,34 @@ pub trait Plugin: Send + Sync {
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>>;
     
     /// 执行动作
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()>;
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome>;
+    
+    /// 破坏性动作的预演（删除、结束进程等），返回 Some 时先由用户确认再执行
+    async fn dry_run(&self, _result_id: &str, _action_id: &str) -> Result<Option<DryRun>> {
+        Ok(None)
+    }
+}
+
+/// 注册表条目
+struct RegisteredPlugin {
+    /// 注册序号（替换同 ID 插件时沿用，快照按此排序）
+    order: u64,
+    plugin: Arc<dyn Plugin>,
 }
 
 /// 插件管理器
 pub struct PluginManager {
-    plugins: Vec<Box<dyn Plugin>>,
+    /// 插件注册表（运行时可增删，查询/执行时先取快照再释放锁）
+    plugins: Arc<RwLock<HashMap<PluginId, RegisteredPlugin>>>,
     sandbox_manager: Arc<sandbox::SandboxManager>,
+    /// 文件搜索是否使用 MFT（重新加载 file_search 时沿用）
+    use_mft: bool,
+    /// 用户禁用的插件（来自 AppConfig.plugins.disabled_plugins，查询/执行时跳过）
+    disabled_plugins: RwLock<HashSet<PluginId>>,
+    /// 插件查询耗时统计
+    query_metrics: query_metrics::QueryMetrics,
+    /// 插件配置中的查询节流覆盖项（min_query_length / debounce_ms）
+    query_overrides: RwLock<HashMap<PluginId, serde_json::Value>>,
 }
 
 impl PluginManager {
@@ -65,11 +221,15 @@ impl PluginManager {
             Ok(s) => s,
             Err(_) => {
                 tracing::warn!("Failed to create storage manager for plugin config");
-                let mut manager = Self { 
-                    plugins: Vec::new(),
+                let manager = Self { 
+                    plugins: Arc::new(RwLock::new(HashMap::new())),
                     sandbox_manager,
+                    use_mft: true,
+                    disabled_plugins: RwLock::new(HashSet::new()),
+                    query_metrics: query_metrics::QueryMetrics::new(),
+                    query_overrides: RwLock::new(HashMap::new()),
                 };
-                Self::register_default_plugins(&mut manager).await;
+                Self::register_builtin_plugins(&manager).await;
                 return manager;
             }
         };
@@ -91,150 +251,507 @@ impl PluginManager {
             }
         }
         
-        let mut manager = Self {
-            plugins: Vec::new(),
-            sandbox_manager,
+        let disabled_plugins = match storage.load_config().await {
+            Ok(config) => config.plugins.disabled_plugins.into_iter().collect(),
+            Err(e) => {
+                tracing::warn!("Failed to load disabled plugins: {}", e);
+                HashSet::new()
+            }
         };
         
-        // 注册插件
-        manager.register(Box::new(calculator::CalculatorPlugin::new()));
-        manager.register(Box::new(web_search::WebSearchPlugin::new()));
-        manager.register(Box::new(unit_converter::UnitConverterPlugin::new()));
-        manager.register(Box::new(settings::SettingsPlugin::new()));
-        manager.register(Box::new(settings::PluginManagerPlugin::new()));
-        manager.register(Box::new(system_commands::SystemCommandPlugin::new()));
-        manager.register(Box::new(window_manager::WindowManagerPlugin::new()));
-        
-        // 创建运行历史插件
-        let data_dir = crate::utils::paths::get_data_dir()
-            .unwrap_or_else(|_| std::path::PathBuf::from("."));
-        let exec_history_path = data_dir.join("execution_history.json");
-        manager.register(Box::new(execution_history::ExecutionHistoryPlugin::new(
-            exec_history_path.to_string_lossy().to_string()
-        )));
-        
-        let clipboard = clipboard::ClipboardPlugin::new();
-        clipboard.init().await;
-        manager.register(Box::new(clipboard));
-        
-        let app_search = app_search::AppSearchPlugin::new();
-        app_search.init().await;
-        manager.register(Box::new(app_search));
-        
-        let browser = browser::BrowserPlugin::new();
-        browser.init().await;
-        manager.register(Box::new(browser));
-        
-        manager.register(Box::new(process::ProcessPlugin::new()));
-        manager.register(Box::new(translator::TranslatorPlugin::new()));
-        manager.register(Box::new(devtools::DevToolsPlugin::new()));
-        
-        let git_projects = git_projects::GitProjectsPlugin::new();
-        git_projects.init().await;
-        manager.register(Box::new(git_projects));
-        
-        // 使用插件配置初始化文件搜索插件
-        let file_search = file_search::FileSearchPlugin::new_with_config(use_mft);
-        file_search.init().await;
-        manager.register(Box::new(file_search));
+        let manager = Self {
+            plugins: Arc::new(RwLock::new(HashMap::new())),
+            sandbox_manager,
+            use_mft,
+            disabled_plugins: RwLock::new(disabled_plugins),
+            query_metrics: query_metrics::QueryMetrics::new(),
+            query_overrides: RwLock::new(HashMap::new()),
+        };
         
-        // AI 助手插件
-        manager.register(Box::new(ai_assistant::AIAssistantPlugin::new()));
+        Self::register_builtin_plugins(&manager).await;
+        manager.load_query_overrides(&storage, BUILTIN_PLUGIN_IDS.iter().copied()).await;
         
         manager
     }
     
-    async fn register_default_plugins(manager: &mut Self) {
-        manager.register(Box::new(calculator::CalculatorPlugin::new()));
-        manager.register(Box::new(web_search::WebSearchPlugin::new()));
-        manager.register(Box::new(unit_converter::UnitConverterPlugin::new()));
-        manager.register(Box::new(settings::SettingsPlugin::new()));
-        manager.register(Box::new(settings::PluginManagerPlugin::new()));
-        manager.register(Box::new(system_commands::SystemCommandPlugin::new()));
-        manager.register(Box::new(window_manager::WindowManagerPlugin::new()));
-        
-        // 创建运行历史插件
-        let data_dir = crate::utils::paths::get_data_dir()
-            .unwrap_or_else(|_| std::path::PathBuf::from("."));
-        let exec_history_path = data_dir.join("execution_history.json");
-        manager.register(Box::new(execution_history::ExecutionHistoryPlugin::new(
-            exec_history_path.to_string_lossy().to_string()
-        )));
-        
-        let clipboard = clipboard::ClipboardPlugin::new();
-        clipboard.init().await;
-        manager.register(Box::new(clipboard));
-        
-        let app_search = app_search::AppSearchPlugin::new();
-        app_search.init().await;
-        manager.register(Box::new(app_search));
-        
-        let browser = browser::BrowserPlugin::new();
-        browser.init().await;
-        manager.register(Box::new(browser));
-        
-        manager.register(Box::new(process::ProcessPlugin::new()));
-        manager.register(Box::new(translator::TranslatorPlugin::new()));
-        manager.register(Box::new(devtools::DevToolsPlugin::new()));
-        
-        let git_projects = git_projects::GitProjectsPlugin::new();
-        git_projects.init().await;
-        manager.register(Box::new(git_projects));
-        
-        let file_search = file_search::FileSearchPlugin::new();
-        file_search.init().await;
-        manager.register(Box::new(file_search));
-        
-        // AI 助手插件
-        manager.register(Box::new(ai_assistant::AIAssistantPlugin::new()));
+    /// 注册全部内置插件
+    async fn register_builtin_plugins(manager: &Self) {
+        for plugin_id in BUILTIN_PLUGIN_IDS {
+            // 🛟 安全模式只加载必要插件（跳过初始化，避免启动索引等后台任务）
+            if !crate::safe_mode::allows_plugin(plugin_id) {
+                tracing::info!("🛟 Safe mode: skipping plugin {}", plugin_id);
+                continue;
+            }
+            if let Some(plugin) = manager.create_builtin_plugin(plugin_id).await {
+                manager.register_plugin(plugin);
+            }
+        }
+    }
+    
+    /// 创建内置插件（包含异步初始化），未知 ID 返回 None
+    async fn create_builtin_plugin(&self, plugin_id: &str) -> Option<Arc<dyn Plugin>> {
+        let plugin: Arc<dyn Plugin> = match plugin_id {
+            "calculator" => {
+                let calculator = calculator::CalculatorPlugin::new();
+                calculator.init().await;
+                Arc::new(calculator)
+            }
+            "web_search" => {
+                let web_search = web_search::WebSearchPlugin::new();
+                web_search.init().await;
+                Arc::new(web_search)
+            }
+            "unit_converter" => {
+                let unit_converter = unit_converter::UnitConverterPlugin::new(self.sandbox_manager.clone());
+                unit_converter.init().await;
+                Arc::new(unit_converter)
+            }
+            "ilauncher.plugin.settings" => Arc::new(settings::SettingsPlugin::new()),
+            "ilauncher.plugin.plugin_manager" => Arc::new(settings::PluginManagerPlugin::new()),
+            "system-commands" => Arc::new(system_commands::SystemCommandPlugin::new()),
+            "windows_settings" => Arc::new(windows_settings::WindowsSettingsPlugin::new()),
+            "window_manager" => Arc::new(window_manager::WindowManagerPlugin::new()),
+            "context_switcher" => Arc::new(context_switcher::ContextSwitcherPlugin::new()),
+            "selection" => {
+                let selection = selection::SelectionPlugin::new();
+                selection.init().await;
+                Arc::new(selection)
+            }
+            "snippets" => {
+                let data_dir = crate::utils::paths::get_data_dir()
+                    .unwrap_or_else(|_| std::path::PathBuf::from("."));
+                let snippets = snippets::SnippetsPlugin::new(data_dir.join("snippets.json"));
+                snippets.init().await;
+                Arc::new(snippets)
+            }
+            "execution-history" => {
+                // 创建运行历史插件
+                let data_dir = crate::utils::paths::get_data_dir()
+                    .unwrap_or_else(|_| std::path::PathBuf::from("."));
+                let exec_history_path = data_dir.join("execution_history.json");
+                Arc::new(execution_history::ExecutionHistoryPlugin::new(
+                    exec_history_path.to_string_lossy().to_string()
+                ))
+            }
+            "clipboard" => {
+                let clipboard = clipboard::ClipboardPlugin::new();
+                clipboard.init().await;
+                Arc::new(clipboard)
+            }
+            "app_search" => {
+                let app_search = app_search::AppSearchPlugin::new();
+                app_search.init().await;
+                Arc::new(app_search)
+            }
+            "browser" => {
+                let browser = browser::BrowserPlugin::new();
+                browser.init().await;
+                Arc::new(browser)
+            }
+            "process" => Arc::new(process::ProcessPlugin::new(self.sandbox_manager.clone())),
+            "translator" => Arc::new(translator::TranslatorPlugin::new()),
+            "devtools" => Arc::new(devtools::DevToolsPlugin::new()),
+            "git" => {
+                let git_projects = git_projects::GitProjectsPlugin::new();
+                git_projects.init().await;
+                Arc::new(git_projects)
+            }
+            "file_search" => {
+                // 使用插件配置初始化文件搜索插件
+                let file_search = file_search::FileSearchPlugin::new_with_config(self.use_mft);
+                file_search.init().await;
+                Arc::new(file_search)
+            }
+            // AI 助手插件
+            "ai_assistant" => Arc::new(ai_assistant::AIAssistantPlugin::new()),
+            _ => return None,
+        };
+        Some(plugin)
     }
     
     /// 注册插件
-    pub fn register(&mut self, plugin: Box<dyn Plugin>) {
-        self.plugins.push(plugin);
+    pub fn register(&self, plugin: Box<dyn Plugin>) {
+        self.register_plugin(Arc::from(plugin));
     }
     
-    /// 查询所有插件
-    pub async fn query(&self, input: &str) -> Result<Vec<QueryResult>> {
-        // 加载配置以获取禁用的插件列表
-        let disabled_plugins = match crate::storage::StorageManager::new() {
-            Ok(storage) => {
-                match storage.load_config().await {
-                    Ok(config) => config.plugins.disabled_plugins,
-                    Err(_) => Vec::new(),
+    /// 注册（或替换同 ID 的）插件，返回被替换的旧插件
+    pub fn register_plugin(&self, plugin: Arc<dyn Plugin>) -> Option<Arc<dyn Plugin>> {
+        let plugin_id = plugin.metadata().id.clone();
+        let previous = {
+            let mut plugins = self.plugins.write();
+            let order = match plugins.get(&plugin_id) {
+                Some(existing) => existing.order,
+                None => plugins.values().map(|p| p.order + 1).max().unwrap_or(0),
+            };
+            plugins.insert(plugin_id.clone(), RegisteredPlugin { order, plugin }).map(|p| p.plugin)
+        };
+        if previous.is_some() {
+            tracing::info!("🔌 Plugin replaced: {}", plugin_id);
+        } else {
+            tracing::debug!("🔌 Plugin registered: {}", plugin_id);
+        }
+        previous
+    }
+    
+    /// 注销插件，返回被移除的插件
+    pub fn unregister_plugin(&self, plugin_id: &str) -> Option<Arc<dyn Plugin>> {
+        let removed = self.plugins.write().remove(plugin_id).map(|p| p.plugin);
+        if removed.is_some() {
+            tracing::info!("🔌 Plugin unregistered: {}", plugin_id);
+        }
+        removed
+    }
+    
+    /// 加载插件：内置插件按 ID 重新创建，否则从插件目录启动外部插件进程
+    pub async fn load_plugin(&self, plugin_id: &str) -> Result<()> {
+        if RESTART_REQUIRED_PLUGIN_IDS.contains(&plugin_id) && self.is_registered(plugin_id) {
+            return Err(anyhow::anyhow!("Plugin '{}' runs background tasks, restart iLauncher to reload it", plugin_id));
+        }
+        let plugin = match self.create_builtin_plugin(plugin_id).await {
+            Some(plugin) => plugin,
+            None => self.create_external_plugin(plugin_id).await?,
+        };
+        self.register_plugin(plugin);
+        Ok(())
+    }
+    
+    /// 从插件目录加载已安装的第三方插件
+    async fn create_external_plugin(&self, plugin_id: &str) -> Result<Arc<dyn Plugin>> {
+        // ID 来自前端，拼接路径前拒绝 .. 和路径分隔符，防止加载插件目录以外的程序
+        if !is_valid_plugin_id(plugin_id) {
+            return Err(anyhow::anyhow!("Invalid plugin id '{}'", plugin_id));
+        }
+        let install_path = crate::storage::get_plugins_dir()?.join(plugin_id);
+        if !install_path.join("manifest.json").exists() {
+            return Err(anyhow::anyhow!("No loader available for plugin '{}'", plugin_id));
+        }
+        let installed = plugin_installer::PluginRegistry::read_installed(&install_path)?;
+        self.create_installed_plugin(&installed).await
+    }
+    
+    /// 按 engine 类型创建第三方插件：wasm 使用 WASM 运行时，其余启动外部进程
+    async fn create_installed_plugin(&self, installed: &plugin_installer::InstalledPlugin) -> Result<Arc<dyn Plugin>> {
+        if installed.manifest.engine.r#type == "wasm" {
+            let plugin = wasm_runtime::WasmPlugin::load(installed, self.sandbox_manager.clone()).await?;
+            Ok(Arc::new(plugin))
+        } else {
+            let plugin = external_host::ExternalPlugin::spawn(installed).await?;
+            Ok(Arc::new(plugin))
+        }
+    }
+    
+    /// 注册已安装（且启用）的外部插件，单个插件启动失败不影响其他插件
+    pub async fn register_external_plugins(&self, installed: &[plugin_installer::InstalledPlugin]) {
+        if let Ok(storage) = crate::storage::StorageManager::new() {
+            self.load_query_overrides(&storage, installed.iter().map(|p| p.manifest.id.as_str())).await;
+        }
+
+        for plugin in installed.iter().filter(|p| p.enabled) {
+            if BUILTIN_PLUGIN_IDS.contains(&plugin.manifest.id.as_str()) {
+                tracing::warn!("External plugin id conflicts with builtin plugin: {}", plugin.manifest.id);
+                continue;
+            }
+            match self.create_installed_plugin(plugin).await {
+                Ok(external) => {
+                    self.register_plugin(external);
                 }
+                Err(e) => tracing::warn!("Failed to start external plugin {}: {}", plugin.manifest.id, e),
             }
-            Err(_) => Vec::new(),
-        };
+        }
+    }
+    
+    /// 重新加载插件（重新创建实例并替换，进行中的查询继续使用旧实例）
+    pub async fn reload_plugin(&self, plugin_id: &str) -> Result<()> {
+        if !self.is_registered(plugin_id) {
+            return Err(anyhow::anyhow!("Plugin '{}' is not registered", plugin_id));
+        }
+        self.load_plugin(plugin_id).await?;
+        tracing::info!("🔄 Plugin reloaded: {}", plugin_id);
+        Ok(())
+    }
+    
+    /// 插件是否已注册
+    pub fn is_registered(&self, plugin_id: &str) -> bool {
+        self.plugins.read().contains_key(plugin_id)
+    }
+    
+    /// 获取单个插件
+    pub fn get(&self, plugin_id: &str) -> Option<Arc<dyn Plugin>> {
+        self.plugins.read().get(plugin_id).map(|p| p.plugin.clone())
+    }
+    
+    /// 插件是否启用
+    pub fn is_plugin_enabled(&self, plugin_id: &str) -> bool {
+        !self.disabled_plugins.read().contains(plugin_id)
+    }
+    
+    /// 启用/禁用插件（仅更新内存状态，持久化由调用方负责）
+    pub fn set_plugin_enabled(&self, plugin_id: &str, enabled: bool) {
+        let mut disabled = self.disabled_plugins.write();
+        if enabled {
+            disabled.remove(plugin_id);
+        } else {
+            disabled.insert(plugin_id.to_string());
+        }
+        tracing::info!("🔌 Plugin {}: {}", if enabled { "enabled" } else { "disabled" }, plugin_id);
+    }
+    
+    /// 用配置中的禁用列表替换当前状态（保存完整配置后调用）
+    pub fn set_disabled_plugins(&self, plugin_ids: &[String]) {
+        *self.disabled_plugins.write() = plugin_ids.iter().cloned().collect();
+    }
+    
+    /// 从插件配置加载查询节流覆盖项
+    async fn load_query_overrides<'a>(&self, storage: &crate::storage::StorageManager, plugin_ids: impl Iterator<Item = &'a str>) {
+        for plugin_id in plugin_ids {
+            if let Ok(config) = storage.get_plugin_config(plugin_id).await {
+                self.set_query_overrides(plugin_id, &config);
+            }
+        }
+    }
+    
+    /// 更新插件的查询节流覆盖项（保存插件配置后调用，立即生效）
+    pub fn set_query_overrides(&self, plugin_id: &str, config: &serde_json::Value) {
+        let overrides: serde_json::Map<String, serde_json::Value> = ["min_query_length", "debounce_ms"]
+            .into_iter()
+            .filter_map(|key| config.get(key).map(|value| (key.to_string(), value.clone())))
+            .collect();
         
-        let ctx = QueryContext {
+        let mut all = self.query_overrides.write();
+        if overrides.is_empty() {
+            all.remove(plugin_id);
+        } else {
+            all.insert(plugin_id.to_string(), serde_json::Value::Object(overrides));
+        }
+    }
+    
+    /// 插件生效的查询节流策略（元数据默认值 + 配置覆盖）
+    pub fn query_policy(&self, plugin: &dyn Plugin) -> QueryPolicy {
+        let metadata = plugin.metadata();
+        match self.query_overrides.read().get(&metadata.id) {
+            Some(config) => metadata.query_policy.with_config(config),
+            None => metadata.query_policy,
+        }
+    }
+    
+    /// 注册表快照（按注册顺序，保证结果顺序稳定；不阻塞注册/注销）
+    fn snapshot(&self) -> Vec<Arc<dyn Plugin>> {
+        let mut plugins: Vec<(u64, Arc<dyn Plugin>)> = self
+            .plugins
+            .read()
+            .values()
+            .map(|p| (p.order, p.plugin.clone()))
+            .collect();
+        plugins.sort_by_key(|(order, _)| *order);
+        plugins.into_iter().map(|(_, plugin)| plugin).collect()
+    }
+    
+    /// 查询所有插件
+    pub async fn query(&self, input: &str) -> Result<Vec<QueryResult>> {
+        self.query_with_cancel(input, CancellationToken::new()).await
+    }
+    
+    /// 查询所有插件（cancel 被取消时中止进行中的插件查询并返回 QUERY_CANCELLED 错误）
+    pub async fn query_with_cancel(&self, input: &str, cancel: CancellationToken) -> Result<Vec<QueryResult>> {
+        self.query_streaming(input, cancel, |_| {}).await
+    }
+    
+    /// 流式查询：先在预算时间内计算即时答案（计算器、单位换算、剪贴板、路径直达）并回调 Instant，
+    /// 再并发查询其余插件，每个插件完成时回调 Partial
+    /// 返回值为全部插件的合并结果，顺序与插件完成先后无关
+    pub async fn query_streaming<F>(&self, input: &str, cancel: CancellationToken, on_event: F) -> Result<Vec<QueryResult>>
+    where
+        F: Fn(QueryEvent<'_>) + Send + Sync,
+    {
+        let mut ctx = QueryContext {
             query_type: QueryType::Input,
             trigger_keyword: String::new(),
             command: None,
             search: input.to_string(),
             raw_query: input.to_string(),
+            cancel: cancel.clone(),
         };
         
-        let mut file_search_results = Vec::new();
-        let mut other_results = Vec::new();
+        if cancel.is_cancelled() {
+            return Err(anyhow::anyhow!(QUERY_CANCELLED));
+        }
         
-        for plugin in &self.plugins {
+        let mut eligible = Vec::new();
+        for (index, plugin) in self.snapshot().into_iter().enumerate() {
             let plugin_id = &plugin.metadata().id;
             
             // 跳过禁用的插件
-            if disabled_plugins.contains(plugin_id) {
+            if !self.is_plugin_enabled(plugin_id) {
                 tracing::debug!("Skipping disabled plugin: {}", plugin_id);
                 continue;
             }
             
-            match plugin.query(&ctx).await {
+            // 跳过当前工作区上下文禁用的插件
+            if !WORKSPACE_CONTEXTS.is_plugin_enabled(plugin_id) {
+                tracing::debug!("Skipping plugin disabled by context: {}", plugin_id);
+                continue;
+            }
+            
+            eligible.push((index, plugin));
+        }
+        
+        // 🎯 触发词路由：只查询声明该触发词的插件，并填充 trigger_keyword/command/search
+        let route = route_trigger(input, eligible.iter().map(|(_, plugin)| plugin.metadata()));
+        if let Some(route) = &route {
+            tracing::debug!("Routing query to {:?} via trigger '{}'", route.plugin_ids, route.keyword);
+            eligible.retain(|(_, plugin)| route.plugin_ids.contains(&plugin.metadata().id));
+            ctx.trigger_keyword = route.keyword.clone();
+            ctx.command = route.command.clone();
+            ctx.search = route.search.clone();
+        }
+        
+        // ⏳ 最短查询长度：昂贵的插件等输入足够长再查询；触发词路由是用户显式选择，不受限制
+        if route.is_none() {
+            eligible.retain(|(_, plugin)| {
+                let accepted = self.query_policy(plugin.as_ref()).accepts(&ctx.search);
+                if !accepted {
+                    tracing::debug!("Skipping plugin {}: query shorter than minimum length", plugin.metadata().id);
+                }
+                accepted
+            });
+        }
+        
+        // 按注册表顺序保存各插件结果，保证合并顺序稳定
+        let mut collected: std::collections::BTreeMap<usize, (Arc<dyn Plugin>, Vec<QueryResult>)> =
+            std::collections::BTreeMap::new();
+        
+        // ⚡ 即时答案：内存型插件在预算内完成，先于其他插件返回；超出预算的插件转入并发查询
+        let instant_start = std::time::Instant::now();
+        let mut instant_results = Vec::new();
+        let path_answer = if route.is_none() && self.is_plugin_enabled("file_search") && self.is_registered("file_search") {
+            file_search::path_answer(input)
+        } else {
+            None
+        };
+        instant_results.extend(path_answer.clone());
+        
+        let mut deferred = Vec::new();
+        for (index, plugin) in eligible {
+            let plugin_id = plugin.metadata().id.clone();
+            // 配置了防抖的插件不作为即时答案
+            if !INSTANT_PLUGIN_IDS.contains(&plugin_id.as_str()) || self.query_policy(plugin.as_ref()).debounce_ms > 0 {
+                deferred.push((index, plugin));
+                continue;
+            }
+            
+            let budget = INSTANT_ANSWER_BUDGET.saturating_sub(instant_start.elapsed());
+            let span = tracing::info_span!("plugin", plugin_id = %plugin_id, instant = true);
+            let started = std::time::Instant::now();
+            match tokio::time::timeout(budget, plugin.query(&ctx).instrument(span)).await {
+                Ok(Ok(mut results)) => {
+                    self.query_metrics.record(&plugin_id, started.elapsed(), query_metrics::QueryOutcome::Ok);
+                    crate::core::preview_cap::cap_previews(&mut results);
+                    instant_results.extend(results.iter().cloned());
+                    collected.insert(index, (plugin, results));
+                }
+                Ok(Err(e)) => {
+                    self.query_metrics.record(&plugin_id, started.elapsed(), query_metrics::QueryOutcome::Failed);
+                    tracing::warn!("Plugin {} query failed: {}", plugin.metadata().name, e);
+                }
+                Err(_) => {
+                    tracing::debug!("Plugin {} exceeded instant answer budget, deferring", plugin_id);
+                    deferred.push((index, plugin));
+                }
+            }
+        }
+        
+        if !instant_results.is_empty() && !cancel.is_cancelled() {
+            on_event(QueryEvent::Instant(&instant_results));
+        }
+        
+        let mut pending = tokio::task::JoinSet::new();
+        for (index, plugin) in deferred {
+            let plugin_id = plugin.metadata().id.clone();
+            let ctx = ctx.clone();
+            let sandbox_manager = self.sandbox_manager.clone();
+            let timeout = sandbox_manager.query_timeout(&plugin_id);
+            let debounce = self.query_policy(plugin.as_ref()).debounce();
+            let span = tracing::info_span!("plugin", plugin_id = %plugin_id);
+            pending.spawn(
+                async move {
+                    // ⏱️ 防抖：新输入会取消本次查询，JoinSet 丢弃时插件查询不会开始
+                    if !debounce.is_zero() {
+                        tokio::time::sleep(debounce).await;
+                    }
+                    
+                    let started = std::time::Instant::now();
+                    let mut timed_out = false;
+                    
+                    // ⏱️ 按沙盒配置限制查询时间，超时只丢弃该插件的结果
+                    let outcome = match timeout {
+                        Some(timeout) => match tokio::time::timeout(timeout, plugin.query(&ctx)).await {
+                            Ok(outcome) => outcome,
+                            Err(_) => {
+                                timed_out = true;
+                                sandbox_manager.record_query_timeout(&plugin_id, timeout);
+                                Err(anyhow::anyhow!("query timed out after {}ms", timeout.as_millis()))
+                            }
+                        },
+                        None => plugin.query(&ctx).await,
+                    };
+                    (index, plugin, outcome, started.elapsed(), timed_out)
+                }
+                .instrument(span),
+            );
+        }
+        
+        loop {
+            // 取消时 JoinSet 被丢弃，进行中的插件查询随之中止
+            let joined = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => return Err(anyhow::anyhow!(QUERY_CANCELLED)),
+                joined = pending.join_next() => joined,
+            };
+            let Some(joined) = joined else {
+                break;
+            };
+            
+            let (index, plugin, outcome, elapsed, timed_out) = match joined {
+                Ok(completed) => completed,
+                Err(e) => {
+                    tracing::warn!("Plugin query task failed: {}", e);
+                    continue;
+                }
+            };
+            
+            let recorded = match (&outcome, timed_out) {
+                (Ok(_), _) => query_metrics::QueryOutcome::Ok,
+                (Err(_), true) => query_metrics::QueryOutcome::TimedOut,
+                (Err(_), false) => query_metrics::QueryOutcome::Failed,
+            };
+            self.query_metrics.record(&plugin.metadata().id, elapsed, recorded);
+            tracing::debug!(
+                "Plugin {} query finished in {:.2}ms",
+                plugin.metadata().id,
+                elapsed.as_secs_f64() * 1000.0
+            );
+            
+            match outcome {
                 Ok(mut results) => {
-                    // 🔹 将文件搜索和应用搜索结果分开存放
-                    if plugin.metadata().id == "file_search" || plugin.metadata().id == "app_search" {
-                        file_search_results.append(&mut results);
-                    } else {
-                        other_results.append(&mut results);
+                    // 🔹 文件搜索结果限制在当前上下文的搜索根目录内
+                    if plugin.metadata().id == "file_search" {
+                        results.retain(|r| {
+                            r.context_data.get("path")
+                                .and_then(|p| p.as_str())
+                                .map_or(true, |p| WORKSPACE_CONTEXTS.is_path_in_roots(p))
+                        });
                     }
+                    
+                    // ✂️ 超大预览在发送前截断（完整内容按需获取）
+                    crate::core::preview_cap::cap_previews(&mut results);
+                    
+                    if !results.is_empty() {
+                        on_event(QueryEvent::Partial {
+                            plugin_id: &plugin.metadata().id,
+                            results: &results,
+                        });
+                    }
+                    collected.insert(index, (plugin, results));
                 }
                 Err(e) => {
                     tracing::warn!("Plugin {} query failed: {}", plugin.metadata().name, e);
@@ -242,6 +759,25 @@ impl PluginManager {
             }
         }
         
+        let mut file_search_results = Vec::new();
+        let mut other_results = Vec::new();
+        
+        for (plugin, mut results) in collected.into_values() {
+            // 🔹 将文件搜索和应用搜索结果分开存放
+            if plugin.metadata().id == "file_search" || plugin.metadata().id == "app_search" {
+                file_search_results.append(&mut results);
+            } else {
+                other_results.append(&mut results);
+            }
+        }
+        
+        // 路径直达结果与文件搜索结果同 ID 时不重复添加
+        if let Some(answer) = path_answer {
+            if !file_search_results.iter().any(|r| r.id == answer.id) {
+                file_search_results.push(answer);
+            }
+        }
+        
         // 分别按分数排序
         file_search_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
         other_results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
@@ -253,47 +789,61 @@ impl PluginManager {
         Ok(all_results)
     }
     
+    /// 插件查询耗时统计（按平均耗时降序）
+    pub fn query_metrics(&self) -> Vec<query_metrics::PluginQueryStats> {
+        self.query_metrics.snapshot()
+    }
+    
+    /// 清空插件查询耗时统计
+    pub fn reset_query_metrics(&self) {
+        self.query_metrics.reset();
+    }
+    
     /// 执行动作
-    pub async fn execute(&self, result_id: &str, action_id: &str, plugin_id: &str) -> Result<()> {
+    /// 动作预演（插件未提供预演时返回 None，直接执行）
+    pub async fn dry_run(&self, result_id: &str, action_id: &str, plugin_id: &str) -> Result<Option<DryRun>> {
+        let plugin = self.get(plugin_id)
+            .ok_or_else(|| anyhow::anyhow!("Plugin '{}' not found", plugin_id))?;
+        if !self.is_plugin_enabled(plugin_id) {
+            return Err(anyhow::anyhow!("Plugin '{}' is disabled", plugin_id));
+        }
+        plugin.dry_run(result_id, action_id).await
+    }
+    
+    pub async fn execute(&self, result_id: &str, action_id: &str, plugin_id: &str) -> Result<ExecuteOutcome> {
         tracing::info!("PluginManager::execute - plugin_id: {}, action_id: {}, result_id: {}", plugin_id, action_id, result_id);
         
-        // 根据 plugin_id 查找对应的插件
-        for plugin in &self.plugins {
-            if plugin.metadata().id == plugin_id {
-                tracing::info!("Found matching plugin: {}", plugin.metadata().name);
-                return plugin.execute(result_id, action_id).await;
-            }
+        // 根据 plugin_id 查找对应的插件（锁在 await 前释放）
+        let plugin = self.get(plugin_id)
+            .ok_or_else(|| anyhow::anyhow!("Plugin '{}' not found", plugin_id))?;
+        if !self.is_plugin_enabled(plugin_id) {
+            return Err(anyhow::anyhow!("Plugin '{}' is disabled", plugin_id));
         }
-        
-        Err(anyhow::anyhow!("Plugin '{}' not found", plugin_id))
+        tracing::info!("Found matching plugin: {}", plugin.metadata().name);
+        plugin.execute(result_id, action_id).await
     }
     
     /// 获取所有插件元数据
     pub fn get_plugins(&self) -> Vec<PluginMetadata> {
-        self.plugins.iter().map(|p| p.metadata().clone()).collect()
+        self.snapshot().iter().map(|p| p.metadata().clone()).collect()
+    }
+    
+    /// 按具体类型获取插件（安全向下转型，供命令访问插件的类型化接口）
+    pub fn get_plugin<T: Plugin>(&self) -> Option<Arc<T>> {
+        self.snapshot()
+            .into_iter()
+            .find(|plugin| plugin.as_ref().as_any().is::<T>())
+            .and_then(|plugin| AsAny::into_any_arc(plugin).downcast::<T>().ok())
     }
     
     /// 获取运行历史插件
-    pub fn get_execution_history_plugin(&self) -> Option<&execution_history::ExecutionHistoryPlugin> {
-        for plugin in &self.plugins {
-            if plugin.metadata().id == "execution-history" {
-                // 使用unsafe downcast - 我们知道这是ExecutionHistoryPlugin
-                let ptr = plugin.as_ref() as *const dyn Plugin as *const execution_history::ExecutionHistoryPlugin;
-                return unsafe { Some(&*ptr) };
-            }
-        }
-        None
+    pub fn get_execution_history_plugin(&self) -> Option<Arc<execution_history::ExecutionHistoryPlugin>> {
+        self.get_plugin::<execution_history::ExecutionHistoryPlugin>()
     }
     
     /// 获取 AI 助手插件
-    pub fn get_ai_plugin(&self) -> Option<&ai_assistant::AIAssistantPlugin> {
-        for plugin in &self.plugins {
-            if plugin.metadata().id == "ai_assistant" {
-                let ptr = plugin.as_ref() as *const dyn Plugin as *const ai_assistant::AIAssistantPlugin;
-                return unsafe { Some(&*ptr) };
-            }
-        }
-        None
+    pub fn get_ai_plugin(&self) -> Option<Arc<ai_assistant::AIAssistantPlugin>> {
+        self.get_plugin::<ai_assistant::AIAssistantPlugin>()
     }
     
     /// 获取沙盒管理器
@@ -360,6 +910,26 @@ impl PluginManager {
             SandboxConfig::system("execution-history")
         );
         
+        // 上下文切换 - 需要打开快捷链接
+        sandbox_manager.register(
+            SandboxConfig::system("context_switcher")
+        );
+        
+        // 划词搜索 - 需要打开浏览器
+        sandbox_manager.register(
+            SandboxConfig::system("selection")
+        );
+        
+        // 文本片段 - 需要读写片段文件和剪贴板
+        sandbox_manager.register(
+            SandboxConfig::system("snippets")
+        );
+
+        // Windows 设置页 - 需要启动 ms-settings 和控制面板程序
+        sandbox_manager.register(
+            SandboxConfig::system("windows_settings")
+        );
+
         // ===== 受信任级插件 =====
         
         // 10. 浏览器数据搜索 - 需要读取浏览器配置目录
@@ -379,6 +949,7 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(10000), // 10秒超时（数据库查询可能较慢）
                 max_memory_mb: Some(200),
+                max_storage_mb: Some(20),
             }
         );
         
@@ -396,6 +967,7 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(5000),
                 max_memory_mb: Some(150),
+                max_storage_mb: Some(10),
             }
         );
         
@@ -415,6 +987,7 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(8000), // 网络请求可能较慢
                 max_memory_mb: Some(100),
+                max_storage_mb: Some(5),
             }
         );
         
@@ -431,6 +1004,7 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(3000),
                 max_memory_mb: Some(50),
+                max_storage_mb: Some(5),
             }
         );
         
@@ -448,21 +1022,26 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(1000),
                 max_memory_mb: Some(50),
+                max_storage_mb: Some(1),
             }
         );
         
-        // 15. 单位转换 - 纯本地计算
+        // 15. 单位转换 - 本地计算 + 汇率接口（仅允许已知汇率服务域名）
         sandbox_manager.register(
             SandboxConfig {
                 plugin_id: "unit_converter".to_string(),
-                security_level: sandbox::SecurityLevel::Sandboxed,
-                custom_permissions: Some(vec![
-                    PluginPermission::ClipboardAccess,
-                    PluginPermission::SystemInfoRead,
-                ].into_iter().collect()),
+                security_level: sandbox::SecurityLevel::Restricted,
+                custom_permissions: Some(
+                    exchange_rates::PROVIDER_DOMAINS
+                        .iter()
+                        .map(|domain| PluginPermission::NetworkAccess(NetworkScope::Domain(domain.to_string())))
+                        .chain([PluginPermission::ClipboardAccess, PluginPermission::SystemInfoRead])
+                        .collect(),
+                ),
                 enabled: true,
-                timeout_ms: Some(1000),
+                timeout_ms: Some(5000), // 首次获取汇率需要联网
                 max_memory_mb: Some(50),
+                max_storage_mb: Some(1),
             }
         );
         
@@ -478,6 +1057,7 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(2000),
                 max_memory_mb: Some(50),
+                max_storage_mb: Some(5),
             }
         );
         
@@ -495,9 +1075,328 @@ impl PluginManager {
                 enabled: true,
                 timeout_ms: Some(60000), // AI 响应可能需要更长时间
                 max_memory_mb: Some(200),
+                max_storage_mb: Some(50),
             }
         );
         
         tracing::info!("✅ Configured sandbox permissions for {} plugins", 17);
     }
 }
+
+/// 插件 ID 是否可以作为插件目录名（单层目录，不含路径分隔符和 ..）
+fn is_valid_plugin_id(plugin_id: &str) -> bool {
+    !plugin_id.is_empty()
+        && plugin_id != "."
+        && !plugin_id.contains("..")
+        && !plugin_id.contains(['/', '\\', ':'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(id: &str, keywords: &[&str], commands: &[&str]) -> PluginMetadata {
+        PluginMetadata {
+            id: id.to_string(),
+            name: id.to_string(),
+            author: String::new(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            icon: WoxImage::emoji("🔌"),
+            trigger_keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            commands: commands
+                .iter()
+                .map(|c| Command { command: c.to_string(), description: String::new() })
+                .collect(),
+            settings: vec![],
+            supported_os: vec![],
+            plugin_type: PluginType::Native,
+            query_policy: QueryPolicy::default(),
+            branding: PluginBranding::default(),
+        }
+    }
+
+    #[test]
+    fn test_route_trigger() {
+        let plugins = [
+            metadata("git", &["git", "project"], &[]),
+            metadata("process", &["ps", "kill"], &[]),
+            metadata("clipboard", &["cb", "clip"], &["clear"]),
+            metadata("settings", &["clip", "history"], &[]),
+        ];
+
+        let route = route_trigger("GIT  ilauncher src", &plugins).unwrap();
+        assert_eq!(route.keyword, "git");
+        assert_eq!(route.search, "ilauncher src");
+        assert_eq!(route.command, None);
+        assert_eq!(route.plugin_ids, HashSet::from(["git".to_string()]));
+
+        let route = route_trigger("clip clear all", &plugins).unwrap();
+        assert_eq!(route.command.as_deref(), Some("clear"));
+        assert_eq!(route.search, "all");
+        assert_eq!(route.plugin_ids.len(), 2);
+
+        // 需要触发词后有空白，且必须是已注册的触发词
+        assert!(route_trigger("git", &plugins).is_none());
+        assert!(route_trigger("gitk foo", &plugins).is_none());
+        assert_eq!(route_trigger("ps ", &plugins).unwrap().search, "");
+    }
+
+    fn empty_manager() -> PluginManager {
+        PluginManager {
+            plugins: Arc::new(RwLock::new(HashMap::new())),
+            sandbox_manager: Arc::new(sandbox::SandboxManager::new()),
+            use_mft: false,
+            disabled_plugins: RwLock::new(HashSet::new()),
+            query_metrics: query_metrics::QueryMetrics::new(),
+            query_overrides: RwLock::new(HashMap::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_and_unregister_at_runtime() {
+        let manager = empty_manager();
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+        assert!(manager.is_registered("calculator"));
+        assert!(manager.get_plugin::<calculator::CalculatorPlugin>().is_some());
+        assert!(manager.get_plugin::<devtools::DevToolsPlugin>().is_none());
+
+        assert!(manager.unregister_plugin("calculator").is_some());
+        assert!(!manager.is_registered("calculator"));
+        assert!(manager.execute("x", "copy", "calculator").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_and_reload_builtin_plugin() {
+        let manager = empty_manager();
+        assert!(manager.reload_plugin("devtools").await.is_err());
+        manager.load_plugin("devtools").await.unwrap();
+        manager.reload_plugin("devtools").await.unwrap();
+        assert!(manager.is_registered("devtools"));
+        assert!(manager.load_plugin("not-a-plugin").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_keeps_registration_order() {
+        let manager = empty_manager();
+        manager.register(Box::new(web_search::WebSearchPlugin::new()));
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+        manager.register(Box::new(devtools::DevToolsPlugin::new()));
+        // 替换同 ID 插件时保留原位置
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+
+        let ids: Vec<String> = manager.get_plugins().into_iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec!["web_search", "calculator", "devtools"]);
+    }
+
+    #[tokio::test]
+    async fn test_background_plugin_reload_refused() {
+        let manager = empty_manager();
+        manager.register(Box::new(browser::BrowserPlugin::new()));
+        assert!(manager.reload_plugin("browser").await.is_err());
+        assert!(manager.is_registered("browser"));
+    }
+
+    #[tokio::test]
+    async fn test_external_plugin_id_validated() {
+        let manager = empty_manager();
+        for plugin_id in ["../evil", "..", "a/b", r"a\b", r"C:\tools\evil", ""] {
+            let err = manager.load_plugin(plugin_id).await.unwrap_err();
+            assert!(err.to_string().contains("Invalid plugin id"), "{}", plugin_id);
+        }
+        assert!(is_valid_plugin_id("com.example.todo"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_plugin_skipped() {
+        let manager = empty_manager();
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+        assert!(!manager.query("1+1").await.unwrap().is_empty());
+
+        manager.set_plugin_enabled("calculator", false);
+        assert!(manager.query("1+1").await.unwrap().is_empty());
+        assert!(manager.execute("x", "copy", "calculator").await.is_err());
+
+        manager.set_disabled_plugins(&[]);
+        assert!(manager.is_plugin_enabled("calculator"));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_query_returns_error() {
+        let manager = empty_manager();
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = manager.query_with_cancel("1+1", cancel).await.unwrap_err();
+        assert_eq!(err.to_string(), QUERY_CANCELLED);
+    }
+
+    /// 永不返回的测试插件
+    struct SlowPlugin {
+        metadata: PluginMetadata,
+    }
+
+    #[async_trait]
+    impl Plugin for SlowPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+
+        async fn query(&self, _ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+            std::future::pending().await
+        }
+
+        async fn execute(&self, _result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
+            Ok(ExecuteOutcome::Hide)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout_keeps_other_results() {
+        let manager = empty_manager();
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+        manager.register(Box::new(SlowPlugin {
+            metadata: PluginMetadata {
+                id: "slow".to_string(),
+                name: "Slow".to_string(),
+                author: String::new(),
+                version: "1.0.0".to_string(),
+                description: String::new(),
+                icon: WoxImage::Emoji("🐢".to_string()),
+                trigger_keywords: vec![],
+                commands: vec![],
+                settings: vec![],
+                supported_os: vec![],
+                plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::default(),
+            },
+        }));
+        manager.sandbox_manager.register(sandbox::SandboxConfig {
+            timeout_ms: Some(50),
+            ..sandbox::SandboxConfig::restricted("slow")
+        });
+
+        let results = manager.query("1+1").await.unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(manager.sandbox_manager.get_audit_statistics().timeouts, 1);
+        
+        let metrics = manager.query_metrics();
+        let slow = metrics.iter().find(|m| m.plugin_id == "slow").unwrap();
+        assert_eq!(slow.timeouts, 1);
+        assert!(metrics.iter().any(|m| m.plugin_id == "calculator" && m.queries == 1));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_instant_answers_first() {
+        let manager = empty_manager();
+        manager.register(Box::new(calculator::CalculatorPlugin::new()));
+        manager.register(Box::new(devtools::DevToolsPlugin::new()));
+
+        let instant = parking_lot::Mutex::new(0);
+        let partials = parking_lot::Mutex::new(Vec::new());
+        let results = manager
+            .query_streaming("1+1", CancellationToken::new(), |event| match event {
+                QueryEvent::Instant(results) => *instant.lock() += results.len(),
+                QueryEvent::Partial { plugin_id, .. } => partials.lock().push(plugin_id.to_string()),
+            })
+            .await
+            .unwrap();
+
+        // 计算器属于即时答案，不再作为部分结果重复发送
+        let instant = instant.into_inner();
+        assert!(instant > 0);
+        assert!(results.len() >= instant);
+        assert!(!partials.into_inner().contains(&"calculator".to_string()));
+    }
+
+    /// 统计查询次数的测试插件
+    struct CountingPlugin {
+        metadata: PluginMetadata,
+        queries: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingPlugin {
+        fn new(id: &str, policy: QueryPolicy) -> Arc<Self> {
+            let mut metadata = metadata(id, &["cnt"], &[]);
+            metadata.query_policy = policy;
+            Arc::new(Self { metadata, queries: Default::default() })
+        }
+
+        fn queries(&self) -> usize {
+            self.queries.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl Plugin for CountingPlugin {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+
+        async fn query(&self, _ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+            self.queries.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![])
+        }
+
+        async fn execute(&self, _result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
+            Ok(ExecuteOutcome::Hide)
+        }
+    }
+
+    #[test]
+    fn test_query_policy_config_overrides() {
+        let policy = QueryPolicy::new(2, 150);
+        assert_eq!(policy.with_config(&serde_json::json!({})), policy);
+        assert_eq!(
+            policy.with_config(&serde_json::json!({ "min_query_length": 1, "debounce_ms": 999_999 })),
+            QueryPolicy::new(1, QueryPolicy::MAX_DEBOUNCE_MS)
+        );
+        assert!(policy.accepts(" 中文 "));
+        assert!(!policy.accepts(" a "));
+    }
+
+    #[tokio::test]
+    async fn test_min_query_length_enforced_by_router() {
+        let manager = empty_manager();
+        let plugin = CountingPlugin::new("counting", QueryPolicy::new(3, 0));
+        manager.register_plugin(plugin.clone());
+
+        manager.query("ab").await.unwrap();
+        assert_eq!(plugin.queries(), 0);
+        manager.query("abc").await.unwrap();
+        assert_eq!(plugin.queries(), 1);
+
+        // 触发词路由不受最短长度限制
+        manager.query("cnt a").await.unwrap();
+        assert_eq!(plugin.queries(), 2);
+
+        // 插件配置覆盖默认值
+        manager.set_query_overrides("counting", &serde_json::json!({ "min_query_length": 1, "other": true }));
+        manager.query("a").await.unwrap();
+        assert_eq!(plugin.queries(), 3);
+        manager.set_query_overrides("counting", &serde_json::json!({}));
+        assert_eq!(manager.query_policy(plugin.as_ref()), QueryPolicy::new(3, 0));
+    }
+
+    #[tokio::test]
+    async fn test_debounced_plugin_skipped_when_cancelled() {
+        let manager = empty_manager();
+        let plugin = CountingPlugin::new("counting", QueryPolicy::new(0, 200));
+        manager.register_plugin(plugin.clone());
+
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+        assert!(manager.query_with_cancel("abc", cancel).await.is_err());
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        assert_eq!(plugin.queries(), 0);
+
+        manager.query("abc").await.unwrap();
+        assert_eq!(plugin.queries(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+// OpenSearch 描述文件解析 - 从 <ShortName>、<Url type="text/html" template="..."> 和 <Image> 提取搜索引擎，
+// 模板参数 {searchTerms} 转换为网页搜索使用的 {query}；也支持从网页的 <link rel="search"> 找到描述文件
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 描述文件的 MIME 类型（网页通过 <link rel="search" type="..."> 声明）
+pub const OPENSEARCH_MIME: &str = "application/opensearchdescription+xml";
+
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<(?:[\w-]+:)?Url\b([^>]*?)(?:/>|>(.*?)</(?:[\w-]+:)?Url\s*>)").unwrap());
+static PARAM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<(?:[\w-]+:)?Param\b([^>]*?)/?>").unwrap());
+static ATTR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+static TEMPLATE_PARAM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{([^{}]+)\}").unwrap());
+static LINK_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<link\b([^>]*)>").unwrap());
+
+/// 描述文件中的搜索引擎信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenSearchDescription {
+    pub name: String,
+    /// 已转换的 URL 模板（包含 {query}）
+    pub url_template: String,
+    /// 图标（http(s) 或 data URL）
+    pub icon: Option<String>,
+}
+
+/// 解析描述文件（只接受 GET 方式的 text/html 模板）
+pub fn parse(xml: &str) -> Result<OpenSearchDescription> {
+    let name = element_text(xml, "ShortName")
+        .or_else(|| element_text(xml, "LongName"))
+        .ok_or_else(|| anyhow!("Not an OpenSearch description: missing <ShortName>"))?;
+
+    let url_template = URL_RE
+        .captures_iter(xml)
+        .find_map(|caps| {
+            let attrs = &caps[1];
+            let is_html = attr(attrs, "type").is_some_and(|t| t.eq_ignore_ascii_case("text/html"));
+            let method = attr(attrs, "method").unwrap_or_default();
+            let is_get = method.is_empty() || method.eq_ignore_ascii_case("get");
+            if !is_html || !is_get {
+                return None;
+            }
+            let params = caps.get(2).map_or_else(Vec::new, |body| {
+                PARAM_RE
+                    .captures_iter(body.as_str())
+                    .filter_map(|p| Some((attr(&p[1], "name")?, attr(&p[1], "value")?)))
+                    .collect()
+            });
+            Some(build_template(&attr(attrs, "template")?, &params))
+        })
+        .ok_or_else(|| anyhow!("OpenSearch description has no text/html search URL"))??;
+
+    let icon = element_text(xml, "Image")
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://") || url.starts_with("data:image/"));
+
+    Ok(OpenSearchDescription { name, url_template, icon })
+}
+
+/// 在网页中查找声明的描述文件地址（相对地址按页面地址解析）
+pub fn discover(html: &str, page_url: &str) -> Option<String> {
+    let href = LINK_RE.captures_iter(html).find_map(|caps| {
+        let attrs = &caps[1];
+        let is_search = attr(attrs, "rel")?.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("search"));
+        let is_opensearch = attr(attrs, "type")?.eq_ignore_ascii_case(OPENSEARCH_MIME);
+        (is_search && is_opensearch).then(|| attr(attrs, "href")).flatten()
+    })?;
+    reqwest::Url::parse(page_url).ok()?.join(&href).ok().map(String::from)
+}
+
+/// 转换模板参数并附加 <Param> 查询参数
+fn build_template(template: &str, params: &[(String, String)]) -> Result<String> {
+    if !template.starts_with("https://") && !template.starts_with("http://") {
+        return Err(anyhow!("Unsupported search URL: {}", template));
+    }
+
+    let mut url = template.to_string();
+    for (name, value) in params {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&urlencoding::encode(name));
+        url.push('=');
+        url.push_str(value);
+    }
+
+    let converted = TEMPLATE_PARAM_RE
+        .replace_all(&url, |caps: &regex::Captures| {
+            let param = &caps[1];
+            match param.trim_end_matches('?').rsplit(':').next().unwrap_or(param) {
+                "searchTerms" => "{query}",
+                "inputEncoding" | "outputEncoding" => "UTF-8",
+                "language" => "*",
+                "count" => "20",
+                "startIndex" | "startPage" => "1",
+                // 其余可选参数留空
+                _ => "",
+            }
+            .to_string()
+        })
+        .into_owned();
+
+    if !converted.contains("{query}") {
+        return Err(anyhow!("Search URL has no {{searchTerms}} parameter"));
+    }
+    Ok(converted)
+}
+
+/// 第一个同名元素的文本内容（忽略命名空间前缀）
+fn element_text(xml: &str, tag: &str) -> Option<String> {
+    let re = Regex::new(&format!(r"(?is)<(?:[\w-]+:)?{tag}\b[^>]*>(.*?)</(?:[\w-]+:)?{tag}\s*>")).ok()?;
+    let raw = re.captures(xml)?.get(1)?.as_str().trim();
+    let raw = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|s| s.strip_suffix("]]>"))
+        .map(str::to_string)
+        .unwrap_or_else(|| decode_entities(raw));
+    let text = raw.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// 属性值（已解码实体）
+fn attr(attrs: &str, name: &str) -> Option<String> {
+    ATTR_RE
+        .captures_iter(attrs)
+        .find(|caps| caps[1].eq_ignore_ascii_case(name))
+        .map(|caps| decode_entities(caps.get(2).or(caps.get(3)).map_or("", |m| m.as_str())))
+}
+
+/// 解码 XML 预定义实体和数字字符引用
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end))
+        });
+        match decoded {
+            Some((ch, end)) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MDN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OpenSearchDescription xmlns="http://a9.com/-/spec/opensearch/1.1/">
+  <ShortName>MDN Web Docs</ShortName>
+  <Description>Search MDN</Description>
+  <InputEncoding>UTF-8</InputEncoding>
+  <Image width="16" height="16" type="image/x-icon">https://developer.mozilla.org/favicon.ico</Image>
+  <Url type="application/x-suggestions+json" template="https://developer.mozilla.org/api/suggest?q={searchTerms}"/>
+  <Url type="text/html" method="get" template="https://developer.mozilla.org/search?q={searchTerms}&amp;locale={language}&amp;page={startPage?}"/>
+</OpenSearchDescription>"#;
+
+    #[test]
+    fn test_parse_description() {
+        let description = parse(MDN).unwrap();
+        assert_eq!(description.name, "MDN Web Docs");
+        assert_eq!(description.url_template, "https://developer.mozilla.org/search?q={query}&locale=*&page=1");
+        assert_eq!(description.icon.as_deref(), Some("https://developer.mozilla.org/favicon.ico"));
+    }
+
+    #[test]
+    fn test_parse_params_and_namespaces() {
+        let xml = r#"<os:OpenSearchDescription xmlns:os="http://a9.com/-/spec/opensearch/1.1/">
+  <os:ShortName><![CDATA[Docs & More]]></os:ShortName>
+  <os:Url type="text/html" method="POST" template="https://example.com/post"/>
+  <os:Url type="text/html" template="https://example.com/search">
+    <os:Param name="q" value="{searchTerms}"/>
+    <os:Param name="src" value="opensearch"/>
+  </os:Url>
+</os:OpenSearchDescription>"#;
+        let description = parse(xml).unwrap();
+        assert_eq!(description.name, "Docs & More");
+        assert_eq!(description.url_template, "https://example.com/search?q={query}&src=opensearch");
+        assert_eq!(description.icon, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_descriptions() {
+        assert!(parse("<html><title>Not XML</title></html>").is_err());
+        assert!(parse("<OpenSearchDescription><ShortName>X</ShortName></OpenSearchDescription>").is_err());
+        assert!(parse(r#"<ShortName>X</ShortName><Url type="text/html" template="https://x.com/"/>"#).is_err());
+        assert!(parse(r#"<ShortName>X</ShortName><Url type="text/html" template="javascript:{searchTerms}"/>"#).is_err());
+    }
+
+    #[test]
+    fn test_discover_link() {
+        let html = r#"<head><link rel="icon" href="/favicon.ico">
+<link rel="search" type="application/opensearchdescription+xml" title="Docs" href="/opensearch.xml"></head>"#;
+        assert_eq!(
+            discover(html, "https://example.com/docs/page").as_deref(),
+            Some("https://example.com/opensearch.xml")
+        );
+        assert_eq!(discover("<link rel=\"stylesheet\" href=\"a.css\">", "https://example.com"), None);
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &amp; b &lt;c&gt; &#65;&#x42; &unknown; &"), "a & b <c> AB &unknown; &");
+    }
+}
@@ -23,6 +23,9 @@ pub struct PluginManifest {
     pub license: String,
     #[serde(default)]
     pub keywords: Vec<String>,
+    /// 分类（search / productivity / developer / system / utilities）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
     pub icon: String,
     pub engine: PluginEngine,
     pub triggers: Vec<String>,
@@ -55,7 +58,7 @@ pub struct PluginRepository {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginEngine {
-    pub r#type: String, // "wasm", "javascript", "native"
+    pub r#type: String, // "wasm", "javascript", "native", "python", "node", "executable"
     pub entry: String,
     pub runtime_version: String,
 }
@@ -124,6 +127,65 @@ pub struct InstalledPlugin {
     pub settings: HashMap<String, serde_json::Value>,
 }
 
+impl InstalledPlugin {
+    /// 由 manifest 的 sandbox/permissions 生成运行时沙盒配置
+    /// 第三方插件不会获得系统级权限；安装目录始终可读
+    pub fn sandbox_config(&self) -> super::sandbox::SandboxConfig {
+        use super::sandbox::{NetworkScope, PluginPermission, SandboxConfig, SecurityLevel, DEFAULT_STORAGE_QUOTA_MB};
+        
+        let manifest = &self.manifest;
+        let security_level = match manifest.sandbox.level.as_str() {
+            "none" | "basic" => SecurityLevel::Trusted,
+            "strict" => SecurityLevel::Sandboxed,
+            _ => SecurityLevel::Restricted,
+        };
+        
+        let mut permissions = security_level.default_permissions();
+        permissions.insert(PluginPermission::FileSystemRead(self.install_path.clone()));
+        for permission in &manifest.permissions {
+            let parsed = if let Some(domain) = permission.strip_prefix("network:") {
+                Some(PluginPermission::NetworkAccess(match domain {
+                    "*" | "all" => NetworkScope::All,
+                    domain => NetworkScope::Domain(domain.to_string()),
+                }))
+            } else if let Some(path) = permission.strip_prefix("filesystem:read:") {
+                Some(PluginPermission::FileSystemRead(expand_home(path)))
+            } else if let Some(path) = permission.strip_prefix("filesystem:write:") {
+                Some(PluginPermission::FileSystemWrite(expand_home(path)))
+            } else if permission.starts_with("clipboard:") {
+                Some(PluginPermission::ClipboardAccess)
+            } else if permission == "system:info" {
+                Some(PluginPermission::SystemInfoRead)
+            } else if permission == "system:execute" {
+                Some(PluginPermission::ExecuteProgram)
+            } else {
+                None
+            };
+            permissions.extend(parsed);
+        }
+        
+        SandboxConfig {
+            plugin_id: manifest.id.clone(),
+            security_level,
+            custom_permissions: Some(permissions),
+            enabled: true,
+            timeout_ms: Some(manifest.sandbox.timeout_ms),
+            max_memory_mb: Some(manifest.sandbox.max_memory_mb),
+            max_storage_mb: Some(DEFAULT_STORAGE_QUOTA_MB),
+        }
+    }
+}
+
+/// 展开权限路径中的 ~
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest.trim_start_matches(['/', '\\'])))
+            .unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
 /// 插件注册表
 pub struct PluginRegistry {
     plugins: Arc<RwLock<HashMap<String, InstalledPlugin>>>,
@@ -152,27 +214,9 @@ impl PluginRegistry {
             
             if path.is_dir() {
                 // 读取插件 manifest
-                let manifest_path = path.join("manifest.json");
-                if manifest_path.exists() {
-                    let manifest_str = fs::read_to_string(&manifest_path)?;
-                    let manifest: PluginManifest = serde_json::from_str(&manifest_str)?;
-                    
-                    // 读取安装信息
-                    let info_path = path.join(".install_info.json");
-                    let installed_plugin = if info_path.exists() {
-                        let info_str = fs::read_to_string(&info_path)?;
-                        serde_json::from_str(&info_str)?
-                    } else {
-                        InstalledPlugin {
-                            manifest: manifest.clone(),
-                            install_path: path.clone(),
-                            installed_at: chrono::Utc::now(),
-                            enabled: true,
-                            settings: HashMap::new(),
-                        }
-                    };
-                    
-                    plugins.insert(manifest.id.clone(), installed_plugin);
+                if path.join("manifest.json").exists() {
+                    let installed_plugin = Self::read_installed(&path)?;
+                    plugins.insert(installed_plugin.manifest.id.clone(), installed_plugin);
                 }
             }
         }
@@ -180,6 +224,27 @@ impl PluginRegistry {
         Ok(())
     }
     
+    /// 读取插件目录中的安装信息（没有 .install_info.json 时由 manifest 生成）
+    pub fn read_installed(path: &Path) -> Result<InstalledPlugin> {
+        let manifest_str = fs::read_to_string(path.join("manifest.json"))?;
+        let manifest: PluginManifest = serde_json::from_str(&manifest_str)?;
+        
+        // 读取安装信息
+        let info_path = path.join(".install_info.json");
+        if info_path.exists() {
+            let info_str = fs::read_to_string(&info_path)?;
+            Ok(serde_json::from_str(&info_str)?)
+        } else {
+            Ok(InstalledPlugin {
+                manifest,
+                install_path: path.to_path_buf(),
+                installed_at: chrono::Utc::now(),
+                enabled: true,
+                settings: HashMap::new(),
+            })
+        }
+    }
+    
     /// 获取已安装插件列表
     pub async fn list_plugins(&self) -> Vec<InstalledPlugin> {
         self.plugins.read().await.values().cloned().collect()
@@ -263,7 +328,7 @@ impl PluginInstaller {
         let mut archive = ZipArchive::new(file)?;
         
         // 3. 读取并解析 manifest.json
-        let manifest: PluginManifest = {
+        let mut manifest: PluginManifest = {
             let mut manifest_file = archive.by_name("manifest.json")
                 .map_err(|_| anyhow!("manifest.json not found in plugin package"))?;
             
@@ -273,6 +338,12 @@ impl PluginInstaller {
             // manifest_file 在这里 drop
         };
         
+        // 规范化分类名（设置页按分类分组）
+        manifest.category = manifest
+            .category
+            .as_deref()
+            .map(|c| crate::core::types::PluginCategory::from_name(c).as_str().to_string());
+        
         // 4. 验证插件 ID 格式
         if !Self::validate_plugin_id(&manifest.id) {
             return Err(anyhow!("Invalid plugin ID format: {}", manifest.id));
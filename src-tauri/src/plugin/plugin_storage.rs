@@ -0,0 +1,459 @@
+// 插件数据存储 - 每个插件独立的键值存储 + 二进制文件存储（data/plugins/<id>）
+// 写入前通过沙盒检查存储配额，并纳入设置导出/备份
+
+use super::sandbox::SandboxManager;
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// 键值存储文件名
+const KV_FILE: &str = "kv.json";
+/// 二进制文件目录
+const BLOBS_DIR: &str = "blobs";
+/// 数据结构版本键（迁移使用）
+const SCHEMA_VERSION_KEY: &str = "__schema_version";
+
+/// 插件存储用量
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginStorageUsage {
+    pub plugin_id: String,
+    pub used_bytes: u64,
+    pub quota_mb: Option<u64>,
+    pub keys: usize,
+    pub blobs: usize,
+}
+
+/// 插件存储导出（备份用，二进制内容使用 Base64）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginStorageExport {
+    #[serde(default)]
+    pub kv: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub blobs: BTreeMap<String, String>,
+}
+
+/// 插件存储管理器
+pub struct PluginStorageManager {
+    root: PathBuf,
+    sandbox: Arc<SandboxManager>,
+    /// 每个插件一把写锁，避免并发写入覆盖
+    locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl PluginStorageManager {
+    pub fn new(root: PathBuf, sandbox: Arc<SandboxManager>) -> Self {
+        Self {
+            root,
+            sandbox,
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取指定插件的存储句柄
+    pub fn for_plugin(&self, plugin_id: &str) -> Result<PluginStorage> {
+        validate_name(plugin_id)?;
+
+        let lock = self
+            .locks
+            .lock()
+            .entry(plugin_id.to_string())
+            .or_default()
+            .clone();
+
+        Ok(PluginStorage {
+            plugin_id: plugin_id.to_string(),
+            dir: self.root.join(plugin_id),
+            sandbox: self.sandbox.clone(),
+            lock,
+        })
+    }
+
+    /// 列出已有存储的插件
+    pub fn list_plugins(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut plugins: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+            .collect();
+        plugins.sort();
+        plugins
+    }
+
+    /// 导出全部插件数据（设置导出/备份）
+    pub fn export_all(&self) -> Result<BTreeMap<String, PluginStorageExport>> {
+        let mut exports = BTreeMap::new();
+        for plugin_id in self.list_plugins() {
+            exports.insert(plugin_id.clone(), self.for_plugin(&plugin_id)?.export()?);
+        }
+        Ok(exports)
+    }
+
+    /// 从备份恢复插件数据（覆盖同名键和文件）
+    pub fn import_all(&self, exports: BTreeMap<String, PluginStorageExport>) -> Result<()> {
+        for (plugin_id, export) in exports {
+            self.for_plugin(&plugin_id)?.import(export)?;
+        }
+        Ok(())
+    }
+}
+
+/// 单个插件的存储句柄
+pub struct PluginStorage {
+    plugin_id: String,
+    dir: PathBuf,
+    sandbox: Arc<SandboxManager>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl PluginStorage {
+    /// 插件数据目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // ==================== 键值存储 ====================
+
+    /// 读取键值
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let kv = self.load_kv()?;
+        kv.get(key)
+            .map(|value| serde_json::from_value(value.clone()).map_err(Into::into))
+            .transpose()
+    }
+
+    /// 写入键值
+    pub fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        if key.is_empty() {
+            return Err(anyhow!("Storage key must not be empty"));
+        }
+
+        let _guard = self.lock.lock();
+        let mut kv = self.load_kv()?;
+        kv.insert(key.to_string(), serde_json::to_value(value)?);
+        self.save_kv(&kv)
+    }
+
+    /// 删除键值
+    pub fn delete(&self, key: &str) -> Result<bool> {
+        let _guard = self.lock.lock();
+        let mut kv = self.load_kv()?;
+        let removed = kv.remove(key).is_some();
+        if removed {
+            self.save_kv(&kv)?;
+        }
+        Ok(removed)
+    }
+
+    /// 列出所有键
+    pub fn keys(&self) -> Result<Vec<String>> {
+        Ok(self
+            .load_kv()?
+            .into_keys()
+            .filter(|k| k != SCHEMA_VERSION_KEY)
+            .collect())
+    }
+
+    // ==================== 二进制文件存储 ====================
+
+    /// 读取二进制文件
+    pub fn read_blob(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.blob_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read(path)?))
+    }
+
+    /// 写入二进制文件
+    pub fn write_blob(&self, name: &str, data: &[u8]) -> Result<()> {
+        let path = self.blob_path(name)?;
+
+        let _guard = self.lock.lock();
+        let existing = file_size(&path);
+        self.ensure_quota(data.len() as u64, existing)?;
+
+        std::fs::create_dir_all(self.dir.join(BLOBS_DIR))?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// 删除二进制文件
+    pub fn delete_blob(&self, name: &str) -> Result<bool> {
+        let path = self.blob_path(name)?;
+
+        let _guard = self.lock.lock();
+        if path.exists() {
+            std::fs::remove_file(path)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// 列出所有二进制文件
+    pub fn blobs(&self) -> Result<Vec<String>> {
+        let Ok(entries) = std::fs::read_dir(self.dir.join(BLOBS_DIR)) else {
+            return Ok(Vec::new());
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    // ==================== 用量 / 清理 ====================
+
+    /// 当前占用字节数
+    pub fn used_bytes(&self) -> u64 {
+        dir_size(&self.dir)
+    }
+
+    /// 用量信息
+    pub fn usage(&self) -> Result<PluginStorageUsage> {
+        Ok(PluginStorageUsage {
+            plugin_id: self.plugin_id.clone(),
+            used_bytes: self.used_bytes(),
+            quota_mb: self
+                .sandbox
+                .get_config(&self.plugin_id)
+                .map_or(Some(super::sandbox::DEFAULT_STORAGE_QUOTA_MB), |c| {
+                    c.max_storage_mb.filter(|_| c.enabled)
+                }),
+            keys: self.keys()?.len(),
+            blobs: self.blobs()?.len(),
+        })
+    }
+
+    /// 清空插件数据
+    pub fn clear(&self) -> Result<()> {
+        let _guard = self.lock.lock();
+        if self.dir.exists() {
+            std::fs::remove_dir_all(&self.dir)?;
+        }
+        tracing::info!("🗑️ Cleared storage for plugin: {}", self.plugin_id);
+        Ok(())
+    }
+
+    // ==================== 迁移 ====================
+
+    /// 当前数据结构版本（未迁移过为 0）
+    pub fn schema_version(&self) -> Result<u32> {
+        Ok(self.get::<u32>(SCHEMA_VERSION_KEY)?.unwrap_or(0))
+    }
+
+    /// 按版本执行迁移：当前版本低于 target_version 时调用 migrate(当前版本)，成功后记录新版本
+    pub fn migrate<F>(&self, target_version: u32, migrate: F) -> Result<bool>
+    where
+        F: FnOnce(&Self, u32) -> Result<()>,
+    {
+        let current = self.schema_version()?;
+        if current >= target_version {
+            return Ok(false);
+        }
+
+        migrate(self, current)?;
+        self.set(SCHEMA_VERSION_KEY, &target_version)?;
+        tracing::info!(
+            "📦 Migrated storage for plugin {}: v{} -> v{}",
+            self.plugin_id,
+            current,
+            target_version
+        );
+        Ok(true)
+    }
+
+    /// 将旧的 JSON 文件导入为键值（成功后删除旧文件）
+    pub fn import_legacy_json(&self, legacy_path: &Path, key: &str) -> Result<bool> {
+        if !legacy_path.exists() {
+            return Ok(false);
+        }
+
+        let content = std::fs::read_to_string(legacy_path)?;
+        let value: serde_json::Value = serde_json::from_str(&content)?;
+        self.set(key, &value)?;
+        std::fs::remove_file(legacy_path)?;
+        Ok(true)
+    }
+
+    /// 将旧的任意文件导入为二进制文件（成功后删除旧文件）
+    pub fn import_legacy_file(&self, legacy_path: &Path, name: &str) -> Result<bool> {
+        if !legacy_path.exists() {
+            return Ok(false);
+        }
+
+        let data = std::fs::read(legacy_path)?;
+        self.write_blob(name, &data)?;
+        std::fs::remove_file(legacy_path)?;
+        Ok(true)
+    }
+
+    // ==================== 备份 ====================
+
+    /// 导出全部数据
+    pub fn export(&self) -> Result<PluginStorageExport> {
+        let mut blobs = BTreeMap::new();
+        for name in self.blobs()? {
+            if let Some(data) = self.read_blob(&name)? {
+                blobs.insert(name, BASE64.encode(data));
+            }
+        }
+
+        Ok(PluginStorageExport {
+            kv: self.load_kv()?,
+            blobs,
+        })
+    }
+
+    /// 导入数据（覆盖同名键和文件）
+    pub fn import(&self, export: PluginStorageExport) -> Result<()> {
+        {
+            let _guard = self.lock.lock();
+            let mut kv = self.load_kv()?;
+            kv.extend(export.kv);
+            self.save_kv(&kv)?;
+        }
+
+        for (name, data) in export.blobs {
+            self.write_blob(&name, &BASE64.decode(data)?)?;
+        }
+        Ok(())
+    }
+
+    // ==================== 内部 ====================
+
+    fn kv_path(&self) -> PathBuf {
+        self.dir.join(KV_FILE)
+    }
+
+    fn blob_path(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+        Ok(self.dir.join(BLOBS_DIR).join(name))
+    }
+
+    fn load_kv(&self) -> Result<BTreeMap<String, serde_json::Value>> {
+        let path = self.kv_path();
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 调用方需持有写锁
+    fn save_kv(&self, kv: &BTreeMap<String, serde_json::Value>) -> Result<()> {
+        let content = serde_json::to_vec_pretty(kv)?;
+        let path = self.kv_path();
+        self.ensure_quota(content.len() as u64, file_size(&path))?;
+
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// 检查写入后（替换 replaced_bytes 为 new_bytes）是否超出沙盒配额
+    fn ensure_quota(&self, new_bytes: u64, replaced_bytes: u64) -> Result<()> {
+        let usage = self.used_bytes().saturating_sub(replaced_bytes) + new_bytes;
+        self.sandbox.check_storage_quota(&self.plugin_id, usage)
+    }
+}
+
+/// 插件 ID / 文件名只允许单层名称，防止路径穿越
+fn validate_name(name: &str) -> Result<()> {
+    let invalid = name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(['/', '\\', ':', '\0']);
+    if invalid {
+        return Err(anyhow!("Invalid storage name: {:?}", name));
+    }
+    Ok(())
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::sandbox::SandboxConfig;
+
+    fn temp_manager() -> (PluginStorageManager, PathBuf) {
+        let root = std::env::temp_dir().join(format!("ilauncher_plugin_storage_{}", uuid::Uuid::new_v4().simple()));
+        let sandbox = Arc::new(SandboxManager::new());
+        sandbox.register(SandboxConfig {
+            max_storage_mb: Some(1),
+            ..SandboxConfig::restricted("tiny")
+        });
+        (PluginStorageManager::new(root.clone(), sandbox), root)
+    }
+
+    #[test]
+    fn test_kv_and_blob_roundtrip() {
+        let (manager, root) = temp_manager();
+        let storage = manager.for_plugin("tiny").unwrap();
+
+        storage.set("count", &3u32).unwrap();
+        assert_eq!(storage.get::<u32>("count").unwrap(), Some(3));
+        storage.write_blob("icon.png", b"png").unwrap();
+        assert_eq!(storage.read_blob("icon.png").unwrap().as_deref(), Some(&b"png"[..]));
+
+        let export = manager.export_all().unwrap();
+        assert_eq!(export["tiny"].kv["count"], serde_json::json!(3));
+        assert!(storage.delete("count").unwrap());
+        assert_eq!(storage.keys().unwrap().len(), 0);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_quota_and_path_validation() {
+        let (manager, root) = temp_manager();
+        let storage = manager.for_plugin("tiny").unwrap();
+
+        assert!(storage.write_blob("big.bin", &vec![0u8; 2 * 1024 * 1024]).is_err());
+        assert!(storage.write_blob("../escape", b"x").is_err());
+        assert!(manager.for_plugin("..").is_err());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_migrate_runs_once() {
+        let (manager, root) = temp_manager();
+        let storage = manager.for_plugin("tiny").unwrap();
+
+        assert!(storage.migrate(1, |s, from| s.set("from", &from)).unwrap());
+        assert!(!storage.migrate(1, |_, _| Err(anyhow!("should not run"))).unwrap());
+        assert_eq!(storage.schema_version().unwrap(), 1);
+        assert_eq!(storage.keys().unwrap(), vec!["from".to_string()]);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
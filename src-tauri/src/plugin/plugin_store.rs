@@ -1,6 +1,6 @@
 // 插件商店 API 客户端
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use crate::http::RequestBuilderExt;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs;
@@ -33,6 +33,8 @@ pub struct PluginListItem {
     #[serde(default)]
     pub keywords: Vec<String>,
     #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
     pub screenshots: Vec<String>,
 }
 
@@ -88,7 +90,6 @@ pub struct SearchResult {
 /// 插件商店客户端
 pub struct PluginStore {
     config: PluginStoreConfig,
-    client: Client,
     cache_dir: PathBuf,
 }
 
@@ -96,7 +97,6 @@ impl PluginStore {
     pub fn new(cache_dir: PathBuf) -> Self {
         Self {
             config: PluginStoreConfig::default(),
-            client: Client::new(),
             cache_dir,
         }
     }
@@ -104,11 +104,15 @@ impl PluginStore {
     pub fn with_config(config: PluginStoreConfig, cache_dir: PathBuf) -> Self {
         Self {
             config,
-            client: Client::new(),
             cache_dir,
         }
     }
     
+    /// 共享 HTTP 客户端（遵循代理与证书设置）
+    fn client() -> Result<reqwest::Client> {
+        crate::http::client("plugin_store")
+    }
+    
     /// 搜索插件
     pub async fn search(&self, params: SearchParams) -> Result<SearchResult> {
         let mut url = format!("{}/plugins", self.config.base_url);
@@ -131,7 +135,7 @@ impl PluginStore {
             url.push_str(&query_params.join("&"));
         }
         
-        let response = self.client.get(&url).send().await?;
+        let response = Self::client()?.get(&url).send_with_retry().await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to search plugins: {}", response.status()));
@@ -144,7 +148,7 @@ impl PluginStore {
     /// 获取插件详情
     pub async fn get_plugin_details(&self, plugin_id: &str) -> Result<PluginDetails> {
         let url = format!("{}/plugins/{}", self.config.base_url, plugin_id);
-        let response = self.client.get(&url).send().await?;
+        let response = Self::client()?.get(&url).send_with_retry().await?;
         
         if !response.status().is_success() {
             return Err(anyhow!("Failed to get plugin details: {}", response.status()));
@@ -162,40 +166,32 @@ impl PluginStore {
             url.push_str(&format!("?version={}", v));
         }
         
-        // 2. 发起下载请求
-        let response = self.client.get(&url).send().await?;
-        
-        if !response.status().is_success() {
-            return Err(anyhow!("Failed to download plugin: {}", response.status()));
-        }
+        // 2. 通过下载管理器下载（断点续传 + 进度事件）
+        fs::create_dir_all(&self.cache_dir).await?;
+        let download_path = self.cache_dir.join(format!("{}.ilp.download", plugin_id));
+        let task = crate::download::DOWNLOADS
+            .download(crate::download::DownloadRequest {
+                url,
+                dest: download_path.clone(),
+                sha256: None,
+                owner: "plugin_store".to_string(),
+            })
+            .await?;
         
         // 3. 获取文件名
-        let filename = Self::extract_filename_from_response(&response, plugin_id)?;
+        let filename = task
+            .content_disposition
+            .as_deref()
+            .and_then(Self::parse_filename_from_content_disposition)
+            .unwrap_or_else(|| format!("{}.ilp", plugin_id));
         
-        // 4. 保存到缓存目录
-        fs::create_dir_all(&self.cache_dir).await?;
+        // 4. 移动到缓存目录
         let file_path = self.cache_dir.join(&filename);
-        
-        // 5. 写入文件
-        let bytes = response.bytes().await?;
-        fs::write(&file_path, &bytes).await?;
+        fs::rename(&download_path, &file_path).await?;
         
         Ok(file_path)
     }
     
-    /// 从响应头提取文件名
-    fn extract_filename_from_response(response: &reqwest::Response, plugin_id: &str) -> Result<String> {
-        if let Some(content_disposition) = response.headers().get("content-disposition") {
-            let cd = content_disposition.to_str()?;
-            if let Some(filename) = Self::parse_filename_from_content_disposition(cd) {
-                return Ok(filename);
-            }
-        }
-        
-        // 降级方案: 使用 plugin_id + .ilp
-        Ok(format!("{}.ilp", plugin_id))
-    }
-    
     /// 解析 Content-Disposition 头获取文件名
     fn parse_filename_from_content_disposition(cd: &str) -> Option<String> {
         // Content-Disposition: attachment; filename="plugin.ilp"
@@ -293,6 +289,7 @@ impl MockPluginStore {
                 icon_url: "https://example.com/icon.png".to_string(),
                 download_url: "https://example.com/weather.ilp".to_string(),
                 keywords: vec!["weather".to_string(), "forecast".to_string()],
+                category: Some("utilities".to_string()),
                 screenshots: vec![],
             },
             PluginListItem {
@@ -306,6 +303,7 @@ impl MockPluginStore {
                 icon_url: "https://example.com/currency-icon.png".to_string(),
                 download_url: "https://example.com/currency.ilp".to_string(),
                 keywords: vec!["currency".to_string(), "money".to_string()],
+                category: Some("utilities".to_string()),
                 screenshots: vec![],
             },
             PluginListItem {
@@ -319,6 +317,7 @@ impl MockPluginStore {
                 icon_url: "https://example.com/ocr-icon.png".to_string(),
                 download_url: "https://example.com/screenshot.ilp".to_string(),
                 keywords: vec!["screenshot".to_string(), "ocr".to_string()],
+                category: Some("productivity".to_string()),
                 screenshots: vec![],
             },
         ]
@@ -334,6 +333,8 @@ impl MockPluginStore {
                 "id": plugin.id,
                 "name": plugin.name,
                 "version": plugin.version,
+                "keywords": plugin.keywords,
+                "category": plugin.category,
             }),
             readme: format!("# {}\n\n{}\n\n## Installation\n\nInstall from plugin marketplace.", plugin.name, plugin.description),
             versions: vec![plugin.version.clone(), "0.9.0".to_string()],
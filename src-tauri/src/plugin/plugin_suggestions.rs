@@ -0,0 +1,292 @@
+// 插件推荐（“你可能会喜欢”）- 根据无结果查询中的词（translate、emoji 等）推荐启用已禁用的内置插件
+// 或安装插件市场中的插件；用户忽略过的推荐记录在数据目录，之后不再提示
+
+use super::plugin_store::PluginListItem;
+use crate::core::types::PluginMetadata;
+use crate::statistics::QueryStat;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 忽略记录文件名（位于数据目录）
+const DISMISSED_FILE: &str = "plugin_suggestions.json";
+
+/// 无结果查询累计命中次数达到该值才推荐
+pub const MIN_MISSES: i32 = 3;
+
+/// 参与匹配的关键词最短长度（过短的触发词如 tr / ps 容易误匹配）
+const MIN_KEYWORD_CHARS: usize = 3;
+
+/// 常见说法 → 插件关键词（补充插件元数据中没有的同义词）
+const TOPIC_HINTS: &[(&str, &str)] = &[
+    ("translation", "translate"),
+    ("translator", "translate"),
+    ("翻译", "translate"),
+    ("kill", "process"),
+    ("task", "process"),
+    ("paste", "clipboard"),
+    ("copied", "clipboard"),
+    ("bookmark", "bookmarks"),
+    ("repo", "git"),
+    ("convert", "units"),
+    ("exchange", "currency"),
+    ("shutdown", "power"),
+    ("restart", "power"),
+    ("ask", "ai"),
+    ("gpt", "ai"),
+];
+
+/// 全局忽略记录
+pub static DISMISSED_SUGGESTIONS: Lazy<SuggestionDismissals> = Lazy::new(|| match crate::utils::paths::get_data_dir() {
+    Ok(dir) => SuggestionDismissals::load(dir.join(DISMISSED_FILE)),
+    Err(e) => {
+        tracing::warn!("Failed to locate plugin suggestion file: {}", e);
+        SuggestionDismissals::default()
+    }
+});
+
+/// 推荐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    /// 已安装但被禁用
+    Enable,
+    /// 插件市场中尚未安装
+    Install,
+}
+
+/// 可推荐的插件
+#[derive(Debug, Clone)]
+pub struct PluginCandidate {
+    pub plugin_id: String,
+    pub name: String,
+    pub description: String,
+    pub keywords: Vec<String>,
+    pub kind: SuggestionKind,
+}
+
+impl PluginCandidate {
+    /// 已禁用的插件（关键词取触发词、标签与 ID）
+    pub fn disabled(metadata: &PluginMetadata) -> Self {
+        let keywords = metadata
+            .trigger_keywords
+            .iter()
+            .chain(metadata.branding.tags.iter())
+            .cloned()
+            .chain(std::iter::once(metadata.id.clone()))
+            .collect();
+        Self {
+            plugin_id: metadata.id.clone(),
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            keywords,
+            kind: SuggestionKind::Enable,
+        }
+    }
+
+    /// 插件市场中的插件
+    pub fn store(item: &PluginListItem) -> Self {
+        Self {
+            plugin_id: item.id.clone(),
+            name: item.name.clone(),
+            description: item.description.clone(),
+            keywords: item.keywords.clone(),
+            kind: SuggestionKind::Install,
+        }
+    }
+
+    fn matches(&self, term: &str) -> bool {
+        self.keywords.iter().any(|keyword| {
+            let keyword = keyword.to_lowercase();
+            keyword.chars().count() >= MIN_KEYWORD_CHARS
+                && (term == keyword || (keyword.len() >= 4 && term.starts_with(&keyword)))
+        })
+    }
+}
+
+/// 推荐项（返回给插件管理界面）
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginSuggestion {
+    pub plugin_id: String,
+    pub name: String,
+    pub description: String,
+    pub kind: SuggestionKind,
+    /// 触发推荐的无结果查询
+    pub queries: Vec<String>,
+    /// 这些查询累计出现的次数
+    pub misses: i32,
+}
+
+/// 查询拆分为小写词，并补充同义词对应的插件关键词
+fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| c.is_whitespace() || (c.is_ascii_punctuation() && c != '-'))
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let hints: Vec<String> = terms
+        .iter()
+        .filter_map(|term| TOPIC_HINTS.iter().find(|(word, _)| word == term).map(|(_, hint)| hint.to_string()))
+        .collect();
+    terms.extend(hints);
+    terms
+}
+
+/// 按无结果查询为候选插件打分，返回累计命中次数最多的推荐
+pub fn suggest(
+    zero_results: &[QueryStat],
+    candidates: &[PluginCandidate],
+    dismissed: &HashSet<String>,
+    limit: usize,
+) -> Vec<PluginSuggestion> {
+    let mut seen = HashSet::new();
+    let mut suggestions: Vec<PluginSuggestion> = candidates
+        .iter()
+        .filter(|candidate| !dismissed.contains(&candidate.plugin_id) && seen.insert(candidate.plugin_id.clone()))
+        .filter_map(|candidate| {
+            let matched: Vec<&QueryStat> = zero_results
+                .iter()
+                .filter(|stat| query_terms(&stat.query).iter().any(|term| candidate.matches(term)))
+                .collect();
+            let misses: i32 = matched.iter().map(|stat| stat.count).sum();
+            (misses >= MIN_MISSES).then(|| PluginSuggestion {
+                plugin_id: candidate.plugin_id.clone(),
+                name: candidate.name.clone(),
+                description: candidate.description.clone(),
+                kind: candidate.kind,
+                queries: matched.iter().take(3).map(|stat| stat.query.clone()).collect(),
+                misses,
+            })
+        })
+        .collect();
+
+    // 已安装的插件（只需启用）优先
+    suggestions.sort_by(|a, b| {
+        (a.kind == SuggestionKind::Install)
+            .cmp(&(b.kind == SuggestionKind::Install))
+            .then(b.misses.cmp(&a.misses))
+    });
+    suggestions.truncate(limit);
+    suggestions
+}
+
+/// 用户忽略过的推荐（JSON 文件）
+#[derive(Debug, Default)]
+pub struct SuggestionDismissals {
+    path: Option<PathBuf>,
+    dismissed: Mutex<BTreeSet<String>>,
+}
+
+impl SuggestionDismissals {
+    /// 读取记录，文件不存在或损坏时视为没有忽略过
+    pub fn load(path: PathBuf) -> Self {
+        let dismissed = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            dismissed: Mutex::new(dismissed),
+        }
+    }
+
+    pub fn ids(&self) -> HashSet<String> {
+        self.dismissed.lock().iter().cloned().collect()
+    }
+
+    /// 忽略推荐并写回文件
+    pub fn dismiss(&self, plugin_id: &str) -> Result<()> {
+        let mut dismissed = self.dismissed.lock();
+        if !dismissed.insert(plugin_id.to_string()) {
+            return Ok(());
+        }
+        match &self.path {
+            Some(path) => save(path, &dismissed),
+            None => Ok(()),
+        }
+    }
+}
+
+fn save(path: &Path, dismissed: &BTreeSet<String>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(dismissed)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn miss(query: &str, count: i32) -> QueryStat {
+        QueryStat {
+            query: query.to_string(),
+            count,
+            last_used: Utc::now(),
+        }
+    }
+
+    fn candidate(plugin_id: &str, keywords: &[&str], kind: SuggestionKind) -> PluginCandidate {
+        PluginCandidate {
+            plugin_id: plugin_id.to_string(),
+            name: plugin_id.to_string(),
+            description: String::new(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_suggests_plugins_matching_zero_result_queries() {
+        let zero_results = vec![
+            miss("translate hello", 2),
+            miss("translation of bonjour", 1),
+            miss("emoji smile", 4),
+            miss("tr x", 9),
+        ];
+        let candidates = vec![
+            candidate("translator", &["trans", "tr", "translate"], SuggestionKind::Enable),
+            candidate("com.example.emoji", &["emoji"], SuggestionKind::Install),
+            candidate("process", &["ps", "kill", "process"], SuggestionKind::Enable),
+        ];
+
+        let suggestions = suggest(&zero_results, &candidates, &HashSet::new(), 5);
+        let ids: Vec<&str> = suggestions.iter().map(|s| s.plugin_id.as_str()).collect();
+        // 已安装插件优先；“tr” 过短不参与匹配
+        assert_eq!(ids, vec!["translator", "com.example.emoji"]);
+        assert_eq!(suggestions[0].misses, 3);
+        assert_eq!(suggestions[0].queries, vec!["translate hello", "translation of bonjour"]);
+        assert_eq!(suggestions[1].kind, SuggestionKind::Install);
+    }
+
+    #[test]
+    fn test_dismissed_and_rare_suggestions_are_skipped() {
+        let zero_results = vec![miss("emoji smile", 5), miss("kill chrome", 2)];
+        let candidates = vec![
+            candidate("com.example.emoji", &["emoji"], SuggestionKind::Install),
+            candidate("process", &["kill"], SuggestionKind::Enable),
+        ];
+        let dismissed: HashSet<String> = ["com.example.emoji".to_string()].into();
+        assert!(suggest(&zero_results, &candidates, &dismissed, 5).is_empty());
+    }
+
+    #[test]
+    fn test_dismissals_persist() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_suggestions_{}", uuid::Uuid::new_v4()));
+        let path = dir.join(DISMISSED_FILE);
+
+        let dismissals = SuggestionDismissals::load(path.clone());
+        dismissals.dismiss("translator").unwrap();
+        dismissals.dismiss("translator").unwrap();
+
+        let reloaded = SuggestionDismissals::load(path);
+        assert_eq!(reloaded.ids(), ["translator".to_string()].into());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
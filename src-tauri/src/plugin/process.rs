@@ -5,14 +5,70 @@ use anyhow::Result;
 use async_trait::async_trait;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use sysinfo::System;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+use super::sandbox::SandboxManager;
+
+/// 重启时等待原进程退出的最长时间（提权时需要等待用户确认 UAC）
+const EXIT_TIMEOUT: Duration = Duration::from_secs(3);
+const ELEVATED_EXIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 结束方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KillMode {
+    /// 仅结束该进程
+    Single,
+    /// 结束进程及其全部子进程
+    Tree,
+}
+
+impl KillMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            KillMode::Single => "kill",
+            KillMode::Tree => "kill_tree",
+        }
+    }
+}
+
+/// 预演中最多列出的子进程数
+const PREVIEW_CHILDREN_LIMIT: usize = 8;
+
+/// 进程快照（结束进程预演使用）
+#[derive(Debug, Clone)]
+struct ProcessSnapshot {
+    pid: u32,
+    parent: Option<u32>,
+    name: String,
+    path: String,
+    memory: u64,
+}
+
+/// 结束进程失败原因
+#[derive(Debug)]
+enum TerminateError {
+    /// 权限不足（受保护/管理员进程）
+    AccessDenied,
+    Other(anyhow::Error),
+}
+
+/// 重启进程所需的启动信息
+struct LaunchSpec {
+    exe: PathBuf,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+}
 
 pub struct ProcessPlugin {
     metadata: PluginMetadata,
+    sandbox: Arc<SandboxManager>,
 }
 
 impl ProcessPlugin {
-    pub fn new() -> Self {
+    pub fn new(sandbox: Arc<SandboxManager>) -> Self {
         Self {
             metadata: PluginMetadata {
                 id: "process".to_string(),
@@ -26,11 +82,196 @@ impl ProcessPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System).with_tags(&["process"]),
             },
+            sandbox,
+        }
+    }
+
+    /// 结束进程，权限不足时通过管理员权限重试，返回是否提权
+    async fn terminate(&self, pid: u32, name: &str, mode: KillMode) -> Result<bool> {
+        // 提权重试会等待 UAC 确认并同步与助手通信，放到阻塞线程池执行
+        let (elevated, result) = tokio::task::spawn_blocking(move || kill_blocking(pid, mode)).await?;
+
+        self.sandbox.record_process_termination(&self.metadata.id, pid, name, mode.as_str(), elevated, result.is_ok());
+        result.map(|_| elevated)
+    }
+
+    /// 重启进程：记录启动参数，结束后按原参数和工作目录重新启动
+    async fn restart(&self, pid: u32, name: &str) -> Result<()> {
+        let spec = {
+            let mut sys = System::new();
+            sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+            let process = sys
+                .process(Pid::from_u32(pid))
+                .ok_or_else(|| anyhow::anyhow!("Process {} not found", pid))?;
+            LaunchSpec {
+                exe: process
+                    .exe()
+                    .map(|p| p.to_path_buf())
+                    .ok_or_else(|| anyhow::anyhow!("Cannot find process executable path"))?,
+                args: process
+                    .cmd()
+                    .iter()
+                    .skip(1)
+                    .map(|arg| arg.to_string_lossy().to_string())
+                    .collect(),
+                cwd: process.cwd().map(|p| p.to_path_buf()),
+            }
+        };
+
+        let elevated = self.terminate(pid, name, KillMode::Single).await?;
+        let timeout = if elevated { ELEVATED_EXIT_TIMEOUT } else { EXIT_TIMEOUT };
+        if !wait_for_exit(pid, timeout).await {
+            return Err(anyhow::anyhow!("Process {} did not exit, restart aborted", pid));
+        }
+
+        // 原进程需要管理员权限结束时，同样以管理员权限重新启动
+        if elevated {
+            crate::elevation::shell_execute_runas(&spec.exe.to_string_lossy(), &join_args(&spec.args), spec.cwd.as_deref())?;
+        } else {
+            let mut command = std::process::Command::new(&spec.exe);
+            command.args(&spec.args);
+            if let Some(cwd) = &spec.cwd {
+                command.current_dir(cwd);
+            }
+            command.spawn()?;
+        }
+
+        tracing::info!("Restarted process {} ({})", name, spec.exe.display());
+        Ok(())
+    }
+}
+
+/// 结束进程（阻塞），权限不足时通过提权助手重试，返回是否提权及结果
+fn kill_blocking(pid: u32, mode: KillMode) -> (bool, Result<()>) {
+    let targets = match mode {
+        KillMode::Single => vec![pid],
+        KillMode::Tree => {
+            let mut sys = System::new();
+            sys.refresh_processes(ProcessesToUpdate::All);
+            let parents: Vec<(u32, Option<u32>)> = sys
+                .processes()
+                .iter()
+                .map(|(pid, process)| (pid.as_u32(), process.parent().map(|p| p.as_u32())))
+                .collect();
+            collect_tree(pid, &parents)
+        }
+    };
+
+    match terminate_all(&targets) {
+        Ok(()) => (false, Ok(())),
+        Err(TerminateError::AccessDenied) => {
+            tracing::warn!("Access denied killing process {}, retrying elevated", pid);
+            (true, elevated_kill(pid, mode))
+        }
+        Err(TerminateError::Other(e)) => (false, Err(e)),
+    }
+}
+
+/// 收集进程树（子进程在前、根进程在后，保证先结束子进程）
+fn collect_tree(root: u32, parents: &[(u32, Option<u32>)]) -> Vec<u32> {
+    let mut ordered = Vec::new();
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if ordered.contains(&pid) {
+            continue;
+        }
+        ordered.push(pid);
+        stack.extend(parents.iter().filter(|(_, parent)| *parent == Some(pid)).map(|(child, _)| *child));
+    }
+    ordered.reverse();
+    ordered
+}
+
+/// 依次结束进程；子进程已退出等错误忽略，权限不足立即返回
+fn terminate_all(targets: &[u32]) -> Result<(), TerminateError> {
+    let Some((&root, children)) = targets.split_last() else {
+        return Ok(());
+    };
+
+    for &pid in children {
+        if let Err(TerminateError::AccessDenied) = terminate_pid(pid) {
+            return Err(TerminateError::AccessDenied);
+        }
+    }
+    terminate_pid(root)
+}
+
+#[cfg(target_os = "windows")]
+fn terminate_pid(pid: u32) -> Result<(), TerminateError> {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_ACCESS_DENIED};
+    use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    let classify = |e: windows::core::Error| {
+        if e.code() == ERROR_ACCESS_DENIED.to_hresult() {
+            TerminateError::AccessDenied
+        } else {
+            TerminateError::Other(e.into())
+        }
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid).map_err(classify)?;
+        let result = TerminateProcess(handle, 1);
+        let _ = CloseHandle(handle);
+        result.map_err(classify)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn terminate_pid(pid: u32) -> Result<(), TerminateError> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+    match sys.process(Pid::from_u32(pid)) {
+        Some(process) if process.kill() => Ok(()),
+        Some(_) => Err(TerminateError::Other(anyhow::anyhow!("Failed to kill process {}", pid))),
+        None => Err(TerminateError::Other(anyhow::anyhow!("Process {} not found", pid))),
+    }
+}
+
+/// 通过提权助手结束进程（本次运行首次使用时弹出 UAC，用户拒绝过则直接失败）
+fn elevated_kill(pid: u32, mode: KillMode) -> Result<()> {
+    use crate::elevation::{ElevatedFeature, HelperRequest, ELEVATION};
+
+    let request = HelperRequest::KillProcess {
+        pid,
+        tree: mode == KillMode::Tree,
+    };
+    ELEVATION.run(ElevatedFeature::ProcessKill, request).map(|_| ())
+}
+
+/// 等待进程退出，超时返回 false
+async fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+        if sys.process(Pid::from_u32(pid)).is_none() {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
         }
+        tokio::time::sleep(Duration::from_millis(100)).await;
     }
 }
 
+/// 拼接命令行参数（含空格或引号的参数加引号）
+fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains([' ', '\t', '"']) {
+                format!("\"{}\"", arg.replace('"', "\\\""))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[async_trait]
 impl crate::plugin::Plugin for ProcessPlugin {
     fn metadata(&self) -> &PluginMetadata {
@@ -38,21 +279,14 @@ impl crate::plugin::Plugin for ProcessPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.trim();
-        
-        if query.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // 检查触发词（必须有前缀才激活）
-        let (search_term, is_kill_mode) = if query.starts_with("kill ") {
-            (&query[5..], true)
-        } else if query.starts_with("ps ") {
-            (&query[3..], false)
-        } else {
+        // 检查触发词（必须经触发词路由才激活）
+        let is_kill_mode = match ctx.trigger_keyword.as_str() {
+            "kill" => true,
+            "ps" => false,
             // 没有触发词，不激活插件
-            return Ok(Vec::new());
+            _ => return Ok(Vec::new()),
         };
+        let search_term = ctx.search.trim();
 
         if search_term.is_empty() {
             return Ok(Vec::new());
@@ -87,12 +321,28 @@ impl crate::plugin::Plugin for ProcessPlugin {
                 let mut actions = vec![
                     Action {
                         id: "kill".to_string(),
-                        name: "结束进程".to_string(),
+                        name: "强制结束进程".to_string(),
                         icon: None,
                         is_default: is_kill_mode,
                         hotkey: None,
                         prevent_hide: false,
                     },
+                    Action {
+                        id: "kill_tree".to_string(),
+                        name: "结束进程树".to_string(),
+                        icon: None,
+                        is_default: false,
+                        hotkey: None,
+                        prevent_hide: false,
+                    },
+                    Action {
+                        id: "restart".to_string(),
+                        name: "重启进程".to_string(),
+                        icon: None,
+                        is_default: false,
+                        hotkey: None,
+                        prevent_hide: false,
+                    },
                     Action {
                         id: "open_location".to_string(),
                         name: "打开文件位置".to_string(),
@@ -132,6 +382,9 @@ impl crate::plugin::Plugin for ProcessPlugin {
                     preview: None,
                     refreshable: false,
                     group: None,
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
                 });
             }
         }
@@ -143,31 +396,21 @@ impl crate::plugin::Plugin for ProcessPlugin {
         Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         let pid: u32 = result_id.parse()?;
         
         match action_id {
-            "kill" => {
-                #[cfg(target_os = "windows")]
-                {
-                    use std::process::Command;
-                    let output = Command::new("taskkill")
-                        .args(&["/F", "/PID", &pid.to_string()])
-                        .output()?;
-                    
-                    if output.status.success() {
-                        tracing::info!("Successfully killed process {}", pid);
-                        Ok(())
-                    } else {
-                        let error = String::from_utf8_lossy(&output.stderr);
-                        Err(anyhow::anyhow!("Failed to kill process: {}", error))
-                    }
-                }
-                
-                #[cfg(not(target_os = "windows"))]
-                {
-                    Err(anyhow::anyhow!("Process killing not supported on this OS"))
-                }
+            "kill" | "kill_tree" => {
+                let mode = if action_id == "kill_tree" { KillMode::Tree } else { KillMode::Single };
+                let name = process_name(pid);
+                let elevated = self.terminate(pid, &name, mode).await?;
+                tracing::info!("Killed process {} ({}), mode={:?}, elevated={}", pid, name, mode, elevated);
+                Ok(ExecuteOutcome::Hide)
+            }
+            "restart" => {
+                let name = process_name(pid);
+                self.restart(pid, &name).await?;
+                Ok(ExecuteOutcome::Hide)
             }
             "open_location" => {
                 // 获取进程路径并打开所在目录
@@ -184,7 +427,7 @@ impl crate::plugin::Plugin for ProcessPlugin {
                                     .spawn()?;
                             }
                             
-                            return Ok(());
+                            return Ok(ExecuteOutcome::Hide);
                         }
                     }
                 }
@@ -193,9 +436,137 @@ impl crate::plugin::Plugin for ProcessPlugin {
             }
             "info" => {
                 // 详细信息会在界面上展示，这里不需要执行操作
-                Ok(())
+                Ok(ExecuteOutcome::Hide)
             }
             _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
         }
     }
+
+    async fn dry_run(&self, result_id: &str, action_id: &str) -> Result<Option<DryRun>> {
+        let mode = match action_id {
+            "kill" => KillMode::Single,
+            "kill_tree" => KillMode::Tree,
+            _ => return Ok(None),
+        };
+        let pid: u32 = result_id.parse()?;
+
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::All);
+        let processes: Vec<ProcessSnapshot> = sys
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessSnapshot {
+                pid: pid.as_u32(),
+                parent: process.parent().map(|p| p.as_u32()),
+                name: process.name().to_string_lossy().to_string(),
+                path: process.exe().map(|p| p.display().to_string()).unwrap_or_default(),
+                memory: process.memory(),
+            })
+            .collect();
+        Ok(kill_preview(pid, mode, &processes))
+    }
+}
+
+/// 结束进程预演：进程信息与受影响的子进程（进程已退出时返回 None）
+fn kill_preview(pid: u32, mode: KillMode, processes: &[ProcessSnapshot]) -> Option<DryRun> {
+    use crate::core::confirmation::format_bytes;
+
+    let target = processes.iter().find(|p| p.pid == pid)?;
+    let parents: Vec<(u32, Option<u32>)> = processes.iter().map(|p| (p.pid, p.parent)).collect();
+    let descendants: Vec<&ProcessSnapshot> = collect_tree(pid, &parents)
+        .into_iter()
+        .filter(|&child| child != pid)
+        .filter_map(|child| processes.iter().find(|p| p.pid == child))
+        .collect();
+
+    let mut preview = match mode {
+        KillMode::Single => DryRun::new(format!("Force kill {} (PID {})", target.name, pid)),
+        KillMode::Tree => DryRun::new(format!(
+            "Force kill {} (PID {}) and {} child process(es)",
+            target.name,
+            pid,
+            descendants.len()
+        )),
+    };
+    if !target.path.is_empty() {
+        preview = preview.with_detail("Path", target.path.clone());
+    }
+    preview = preview.with_detail("Memory", format_bytes(target.memory));
+
+    if !descendants.is_empty() {
+        let mut names: Vec<String> = descendants
+            .iter()
+            .take(PREVIEW_CHILDREN_LIMIT)
+            .map(|p| format!("{} ({})", p.name, p.pid))
+            .collect();
+        if descendants.len() > PREVIEW_CHILDREN_LIMIT {
+            names.push(format!("… {} more", descendants.len() - PREVIEW_CHILDREN_LIMIT));
+        }
+        let label = match mode {
+            KillMode::Single => "Child processes (keep running)",
+            KillMode::Tree => "Child processes (also killed)",
+        };
+        preview = preview.with_detail(label, names.join(", "));
+    }
+    Some(preview)
+}
+
+/// 获取进程名（用于审计日志）
+fn process_name(pid: u32) -> String {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]));
+    sys.process(Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_tree_kills_children_first() {
+        let parents = vec![(1, None), (10, Some(1)), (11, Some(10)), (12, Some(1)), (20, Some(2))];
+        let tree = collect_tree(1, &parents);
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(*tree.last().unwrap(), 1);
+        let pos = |pid| tree.iter().position(|p| *p == pid).unwrap();
+        assert!(pos(11) < pos(10));
+        assert!(!tree.contains(&20));
+    }
+
+    #[test]
+    fn test_kill_preview_lists_children() {
+        let process = |pid, parent, name: &str| ProcessSnapshot {
+            pid,
+            parent,
+            name: name.to_string(),
+            path: String::new(),
+            memory: 2048,
+        };
+        let processes = vec![
+            process(1, None, "chrome.exe"),
+            process(10, Some(1), "chrome.exe"),
+            process(11, Some(10), "crashpad.exe"),
+            process(20, Some(2), "other.exe"),
+        ];
+
+        let tree = kill_preview(1, KillMode::Tree, &processes).unwrap();
+        assert_eq!(tree.summary, "Force kill chrome.exe (PID 1) and 2 child process(es)");
+        let children = tree.details.iter().find(|d| d.label.starts_with("Child processes")).unwrap();
+        assert!(children.value.contains("crashpad.exe (11)"));
+        assert!(!children.value.contains("other.exe"));
+        assert!(tree.details.iter().any(|d| d.label == "Memory" && d.value == "2.0 KB"));
+
+        let single = kill_preview(20, KillMode::Single, &processes).unwrap();
+        assert!(single.details.iter().all(|d| !d.label.starts_with("Child processes")));
+        assert!(kill_preview(99, KillMode::Single, &processes).is_none());
+    }
+
+    #[test]
+    fn test_join_args() {
+        let args = vec!["--flag".to_string(), "C:\\Program Files\\app".to_string(), "say \"hi\"".to_string()];
+        assert_eq!(join_args(&args), "--flag \"C:\\Program Files\\app\" \"say \\\"hi\\\"\"");
+    }
 }
@@ -0,0 +1,98 @@
+// 插件查询耗时统计 - 记录每个插件的查询次数、平均/最大/最近耗时、失败与超时次数
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 单次插件查询的结果状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryOutcome {
+    Ok,
+    Failed,
+    TimedOut,
+}
+
+/// 单个插件的查询统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PluginQueryStats {
+    pub plugin_id: String,
+    pub queries: u64,
+    pub failures: u64,
+    pub timeouts: u64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+    #[serde(skip)]
+    total_ms: f64,
+}
+
+/// 插件查询耗时统计
+#[derive(Default)]
+pub struct QueryMetrics {
+    stats: RwLock<HashMap<String, PluginQueryStats>>,
+}
+
+impl QueryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次插件查询
+    pub fn record(&self, plugin_id: &str, elapsed: Duration, outcome: QueryOutcome) {
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        let mut stats = self.stats.write();
+        let entry = stats
+            .entry(plugin_id.to_string())
+            .or_insert_with(|| PluginQueryStats {
+                plugin_id: plugin_id.to_string(),
+                ..Default::default()
+            });
+
+        entry.queries += 1;
+        entry.total_ms += elapsed_ms;
+        entry.avg_ms = entry.total_ms / entry.queries as f64;
+        entry.max_ms = entry.max_ms.max(elapsed_ms);
+        entry.last_ms = elapsed_ms;
+        match outcome {
+            QueryOutcome::Ok => {}
+            QueryOutcome::Failed => entry.failures += 1,
+            QueryOutcome::TimedOut => entry.timeouts += 1,
+        }
+    }
+
+    /// 所有插件的统计（按平均耗时降序）
+    pub fn snapshot(&self) -> Vec<PluginQueryStats> {
+        let mut stats: Vec<PluginQueryStats> = self.stats.read().values().cloned().collect();
+        stats.sort_by(|a, b| b.avg_ms.partial_cmp(&a.avg_ms).unwrap_or(std::cmp::Ordering::Equal));
+        stats
+    }
+
+    /// 清空统计
+    pub fn reset(&self) {
+        self.stats.write().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_snapshot() {
+        let metrics = QueryMetrics::new();
+        metrics.record("fast", Duration::from_millis(2), QueryOutcome::Ok);
+        metrics.record("slow", Duration::from_millis(100), QueryOutcome::Ok);
+        metrics.record("slow", Duration::from_millis(300), QueryOutcome::TimedOut);
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats[0].plugin_id, "slow");
+        assert_eq!(stats[0].queries, 2);
+        assert_eq!(stats[0].timeouts, 1);
+        assert!((stats[0].avg_ms - 200.0).abs() < 0.01);
+        assert!((stats[0].max_ms - 300.0).abs() < 0.01);
+
+        metrics.reset();
+        assert!(metrics.snapshot().is_empty());
+    }
+}
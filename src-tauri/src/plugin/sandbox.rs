@@ -109,6 +109,9 @@ impl SecurityLevel {
     }
 }
 
+/// 未注册插件的默认存储配额（MB）
+pub const DEFAULT_STORAGE_QUOTA_MB: u64 = 10;
+
 /// 插件沙盒配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SandboxConfig {
@@ -124,6 +127,9 @@ pub struct SandboxConfig {
     pub timeout_ms: Option<u64>,
     /// 最大内存使用（MB）
     pub max_memory_mb: Option<u64>,
+    /// 插件数据存储配额（MB，None 表示不限制）
+    #[serde(default)]
+    pub max_storage_mb: Option<u64>,
 }
 
 impl SandboxConfig {
@@ -136,6 +142,7 @@ impl SandboxConfig {
             enabled: false, // 系统插件不需要沙盒
             timeout_ms: None,
             max_memory_mb: None,
+            max_storage_mb: None,
         }
     }
 
@@ -148,6 +155,7 @@ impl SandboxConfig {
             enabled: true,
             timeout_ms: Some(5000), // 5秒超时
             max_memory_mb: Some(100), // 100MB 内存限制
+            max_storage_mb: Some(DEFAULT_STORAGE_QUOTA_MB),
         }
     }
 
@@ -330,12 +338,97 @@ impl SandboxManager {
         self.check_permission(plugin_id, &PluginPermission::ExecuteProgram)
     }
 
+    /// 检查插件数据存储配额（写入后总用量不得超过配额）
+    pub fn check_storage_quota(&self, plugin_id: &str, new_usage_bytes: u64) -> Result<()> {
+        let quota_mb = match self.get_config(plugin_id) {
+            Some(config) if !config.enabled => None,
+            Some(config) => config.max_storage_mb,
+            None => Some(DEFAULT_STORAGE_QUOTA_MB),
+        };
+
+        let Some(quota_mb) = quota_mb else {
+            return Ok(());
+        };
+
+        let quota_bytes = quota_mb * 1024 * 1024;
+        if new_usage_bytes > quota_bytes {
+            self.audit_logger.log(
+                AuditEventType::ViolationAttempt {
+                    plugin_id: plugin_id.to_string(),
+                    violation_type: "StorageQuota".to_string(),
+                    details: format!("{} bytes exceeds quota of {} MB", new_usage_bytes, quota_mb),
+                },
+                AuditSeverity::Warning,
+            );
+            return Err(anyhow!(
+                "Storage quota exceeded for plugin '{}' ({} MB)",
+                plugin_id,
+                quota_mb
+            ));
+        }
+
+        Ok(())
+    }
+
     /// 获取插件配置
     pub fn get_config(&self, plugin_id: &str) -> Option<SandboxConfig> {
         let configs = self.configs.read().unwrap();
         configs.get(plugin_id).cloned()
     }
 
+    /// 插件查询超时（未注册或未配置 timeout_ms 时不限制）
+    pub fn query_timeout(&self, plugin_id: &str) -> Option<std::time::Duration> {
+        self.get_config(plugin_id)
+            .and_then(|config| config.timeout_ms)
+            .map(std::time::Duration::from_millis)
+    }
+    
+    /// 记录查询超时审计
+    pub fn record_query_timeout(&self, plugin_id: &str, timeout: std::time::Duration) {
+        self.audit_logger.log(
+            AuditEventType::QueryTimeout {
+                plugin_id: plugin_id.to_string(),
+                timeout_ms: timeout.as_millis() as u64,
+            },
+            AuditSeverity::Warning,
+        );
+    }
+
+    /// 记录结束进程审计
+    pub fn record_process_termination(
+        &self,
+        plugin_id: &str,
+        pid: u32,
+        process_name: &str,
+        mode: &str,
+        elevated: bool,
+        success: bool,
+    ) {
+        self.audit_logger.log(
+            AuditEventType::ProcessTermination {
+                plugin_id: plugin_id.to_string(),
+                pid,
+                process_name: process_name.to_string(),
+                mode: mode.to_string(),
+                elevated,
+                success,
+            },
+            if success { AuditSeverity::Info } else { AuditSeverity::Warning },
+        );
+    }
+
+    /// 记录因忽略列表跳过剪贴板记录的审计（不记录内容本身）
+    pub fn record_clipboard_skip(&self, process_name: &str, rule: &str) {
+        self.audit_logger.log(
+            AuditEventType::ClipboardCaptureSkipped {
+                plugin_id: "clipboard".to_string(),
+                process_name: process_name.to_string(),
+                rule: rule.to_string(),
+            },
+            AuditSeverity::Info,
+        );
+    }
+
     /// 更新插件配置
     pub fn update_config(&self, config: SandboxConfig) {
         let old_config = self.configs.read().unwrap().get(&config.plugin_id).cloned();
@@ -499,6 +592,7 @@ mod tests {
             enabled: true,
             timeout_ms: Some(100),
             max_memory_mb: None,
+            max_storage_mb: None,
         };
         manager.register(config);
 
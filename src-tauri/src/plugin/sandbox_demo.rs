@@ -3,7 +3,7 @@
 
 use crate::plugin::Plugin;
 use crate::plugin::sandbox::{PluginPermission, NetworkScope};
-use crate::core::types::{PluginMetadata, QueryContext, QueryResult, Action, WoxImage};
+use crate::core::types::{PluginMetadata, QueryContext, QueryResult, Action, WoxImage, ExecuteOutcome};
 use anyhow::Result;
 use std::sync::Arc;
 
@@ -27,6 +27,8 @@ impl SandboxDemoPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: crate::core::types::PluginType::Native,
+                query_policy: crate::core::types::QueryPolicy::default(),
+                branding: crate::core::types::PluginBranding::new(crate::core::types::PluginCategory::Developer).with_tags(&["demo"]),
             },
             sandbox_manager,
         }
@@ -73,7 +75,8 @@ impl Plugin for SandboxDemoPlugin {
         let query_lower = ctx.search.to_lowercase();
         let mut results = Vec::new();
 
-        if !query_lower.starts_with("sandbox") && !query_lower.starts_with("沙盒") {
+        let triggered = matches!(ctx.trigger_keyword.as_str(), "sandbox" | "沙盒");
+        if !triggered && !query_lower.starts_with("sandbox") && !query_lower.starts_with("沙盒") {
             return Ok(results);
         }
 
@@ -97,7 +100,7 @@ impl Plugin for SandboxDemoPlugin {
         Ok(results)
     }
 
-    async fn execute(&self, _result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, _result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         let result_msg = match action_id {
             "file_read" => {
                 match self.try_read_file("config.json").await {
@@ -130,6 +133,6 @@ impl Plugin for SandboxDemoPlugin {
         };
 
         tracing::info!("🔒 Sandbox Demo: {}", result_msg);
-        Ok(())
+        Ok(ExecuteOutcome::message(result_msg))
     }
 }
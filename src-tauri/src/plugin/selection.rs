@@ -0,0 +1,193 @@
+// 划词搜索插件 - 查询内容为划词捕获的文本时，提供翻译、释义、网页搜索、AI 解释等上下文结果
+
+use crate::core::types::*;
+use crate::hotkey::selection::LAST_SELECTION;
+use crate::plugin::web_search::WebSearchPlugin;
+use crate::plugin::Plugin;
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+
+pub struct SelectionPlugin {
+    metadata: PluginMetadata,
+    /// 翻译目标语言（跟随界面语言）
+    language: RwLock<String>,
+}
+
+impl SelectionPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata {
+                id: "selection".to_string(),
+                name: "Search Selection".to_string(),
+                description: "Context actions for text captured from other apps".to_string(),
+                author: "iLauncher".to_string(),
+                version: "1.0.0".to_string(),
+                icon: WoxImage::emoji("✂️"),
+                trigger_keywords: vec![],
+                commands: vec![],
+                settings: vec![],
+                supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
+                plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Productivity).with_tags(&["selection"]),
+            },
+            language: RwLock::new("zh-CN".to_string()),
+        }
+    }
+
+    /// 初始化：读取界面语言
+    pub async fn init(&self) {
+        let Ok(storage) = crate::storage::StorageManager::new() else {
+            return;
+        };
+        if let Ok(config) = storage.load_config().await {
+            self.set_language(&config.appearance.language);
+        }
+    }
+
+    /// 更新翻译目标语言（保存设置后即时生效）
+    pub fn set_language(&self, language: &str) {
+        if !language.trim().is_empty() {
+            *self.language.write() = language.trim().to_string();
+        }
+    }
+
+    fn translate_url(&self, encoded: &str) -> String {
+        format!(
+            "https://translate.google.com/?sl=auto&tl={}&text={}",
+            urlencoding::encode(&self.language.read()),
+            encoded
+        )
+    }
+
+    fn url_result(&self, url: String, title: String, subtitle: &str, icon: &str, score: i32) -> QueryResult {
+        QueryResult {
+            id: url,
+            title,
+            subtitle: subtitle.to_string(),
+            icon: WoxImage::emoji(icon),
+            preview: None,
+            score,
+            context_data: serde_json::Value::Null,
+            group: Some("Selection".to_string()),
+            plugin_id: self.metadata.id.clone(),
+            refreshable: false,
+            actions: vec![Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                icon: None,
+                is_default: true,
+                prevent_hide: false,
+                hotkey: None,
+            }],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Plugin for SelectionPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let search = ctx.search.trim();
+        if search.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 只对划词捕获的文本生效
+        let is_selection = LAST_SELECTION
+            .read()
+            .as_deref()
+            .map_or(false, |selection| selection == search);
+        if !is_selection {
+            return Ok(Vec::new());
+        }
+
+        let encoded = urlencoding::encode(search);
+        let short: String = search.chars().take(40).collect();
+
+        let mut results = vec![
+            self.url_result(
+                self.translate_url(&encoded),
+                format!("Translate: {}", short),
+                "Google Translate",
+                "🌍",
+                300,
+            ),
+            self.url_result(
+                format!("https://www.google.com/search?q=define+{}", encoded),
+                format!("Define: {}", short),
+                "Dictionary definition",
+                "📖",
+                290,
+            ),
+            self.url_result(
+                format!("https://www.google.com/search?q={}", encoded),
+                format!("Search the web: {}", short),
+                "Google",
+                "🔍",
+                280,
+            ),
+        ];
+
+        // AI 解释：复用 AI 助手的发送流程（由前端处理）
+        results.push(QueryResult {
+            id: "ask".to_string(),
+            title: format!("AI Explain: {}", short),
+            subtitle: "Ask the AI assistant to explain the selection".to_string(),
+            icon: WoxImage::emoji("🤖"),
+            preview: Some(Preview::Text(search.to_string())),
+            score: 270,
+            context_data: serde_json::to_value(format!("Explain the following:\n\n{}", search))?,
+            group: Some("Selection".to_string()),
+            plugin_id: "ai_assistant".to_string(),
+            refreshable: false,
+            actions: vec![Action {
+                id: "send".to_string(),
+                name: "Send Message".to_string(),
+                icon: None,
+                is_default: true,
+                prevent_hide: true,
+                hotkey: None,
+            }],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        });
+
+        Ok(results)
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        match action_id {
+            "open" => {
+                WebSearchPlugin::open_url(result_id).await?;
+                Ok(ExecuteOutcome::Hide)
+            }
+            _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_url_follows_ui_language() {
+        let plugin = SelectionPlugin::new();
+        assert!(plugin.translate_url("hello").contains("tl=zh-CN&text=hello"));
+
+        plugin.set_language("en");
+        assert!(plugin.translate_url("hello").contains("tl=en&text=hello"));
+        // 空值不覆盖
+        plugin.set_language(" ");
+        assert!(plugin.translate_url("hello").contains("tl=en&"));
+    }
+}
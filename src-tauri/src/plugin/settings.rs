@@ -1,10 +1,286 @@
 // Settings 插件 - 快速打开设置界面
+// 🔥 同时作为 iLauncher 自身的命令面板：设置开关、维护任务、重新扫描、窗口均可搜索并直接执行
 
 use super::Plugin;
 use crate::core::types::*;
+use crate::storage::AppConfig;
 use anyhow::Result;
 use async_trait::async_trait;
 
+/// Settings 插件 ID
+pub const SETTINGS_PLUGIN_ID: &str = "ilauncher.plugin.settings";
+
+/// 命令面板结果 ID 前缀
+const PALETTE_PREFIX: &str = "palette:";
+
+/// 命令面板执行动作 ID
+pub const ACTION_RUN_PALETTE: &str = "run";
+
+/// 维护任务
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceTask {
+    ClearCache,
+    ClearStatistics,
+    ResetQueryMetrics,
+    /// 重新加载插件（触发重新扫描/重建索引）
+    ReloadPlugin(&'static str),
+}
+
+/// 命令面板条目的执行方式
+#[derive(Clone, Copy)]
+pub enum PaletteAction {
+    /// 打开前端视图（通过 open-view 事件通知前端）
+    OpenView(&'static str),
+    /// 切换布尔设置项
+    Toggle {
+        get: fn(&AppConfig) -> bool,
+        set: fn(&mut AppConfig, bool),
+    },
+    /// 执行维护任务
+    Maintenance(MaintenanceTask),
+}
+
+/// 命令面板条目（声明式注册，新增条目即可被搜索到）
+pub struct PaletteEntry {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub subtitle: &'static str,
+    pub icon: &'static str,
+    pub keywords: &'static [&'static str],
+    pub action: PaletteAction,
+}
+
+impl PaletteEntry {
+    /// 查询结果 ID
+    pub fn result_id(&self) -> String {
+        format!("{}{}", PALETTE_PREFIX, self.id)
+    }
+
+    /// 匹配分数（0 表示不匹配）
+    fn score(&self, query: &str) -> i32 {
+        let title = self.title.to_lowercase();
+        if title.starts_with(query) {
+            90
+        } else if self.keywords.iter().any(|kw| kw.starts_with(query)) {
+            85
+        } else if title.contains(query) || self.keywords.iter().any(|kw| kw.contains(query)) {
+            70
+        } else {
+            0
+        }
+    }
+}
+
+/// 命令面板注册表
+pub const PALETTE_ENTRIES: &[PaletteEntry] = &[
+    // 窗口
+    PaletteEntry {
+        id: "view.plugins",
+        title: "Open Plugin Manager",
+        subtitle: "Manage installed plugins",
+        icon: "🧩",
+        keywords: &["plugins", "extensions", "插件"],
+        action: PaletteAction::OpenView("plugins"),
+    },
+    PaletteEntry {
+        id: "view.clipboard",
+        title: "Open Clipboard History",
+        subtitle: "View and manage clipboard history",
+        icon: "📋",
+        keywords: &["clipboard", "剪贴板"],
+        action: PaletteAction::OpenView("clipboard"),
+    },
+    PaletteEntry {
+        id: "view.ai_chat",
+        title: "Open AI Chat",
+        subtitle: "Chat with the AI assistant",
+        icon: "🤖",
+        keywords: &["ai", "chat", "对话"],
+        action: PaletteAction::OpenView("ai-chat"),
+    },
+    PaletteEntry {
+        id: "view.hotkey_guide",
+        title: "Show Hotkey Guide",
+        subtitle: "List keyboard shortcuts",
+        icon: "⌨️",
+        keywords: &["hotkeys", "shortcuts", "快捷键"],
+        action: PaletteAction::OpenView("hotkey-guide"),
+    },
+    // 设置开关
+    PaletteEntry {
+        id: "toggle.clear_on_hide",
+        title: "Toggle Clear Query on Hide",
+        subtitle: "Clear the search box when the window hides",
+        icon: "🧹",
+        keywords: &["clear on hide", "隐藏时清空"],
+        action: PaletteAction::Toggle {
+            get: |c| c.general.clear_on_hide,
+            set: |c, v| c.general.clear_on_hide = v,
+        },
+    },
+    PaletteEntry {
+        id: "toggle.show_preview",
+        title: "Toggle Preview Panel",
+        subtitle: "Show file previews next to results",
+        icon: "👁️",
+        keywords: &["preview", "预览"],
+        action: PaletteAction::Toggle {
+            get: |c| c.appearance.show_preview,
+            set: |c, v| c.appearance.show_preview = v,
+        },
+    },
+    PaletteEntry {
+        id: "toggle.show_tray_icon",
+        title: "Toggle Tray Icon",
+        subtitle: "Show the iLauncher icon in the system tray",
+        icon: "📌",
+        keywords: &["tray", "托盘"],
+        action: PaletteAction::Toggle {
+            get: |c| c.advanced.show_tray_icon,
+            set: |c, v| c.advanced.show_tray_icon = v,
+        },
+    },
+    PaletteEntry {
+        id: "toggle.analytics",
+        title: "Toggle Usage Analytics",
+        subtitle: "Record local usage statistics for ranking",
+        icon: "📊",
+        keywords: &["analytics", "statistics", "统计"],
+        action: PaletteAction::Toggle {
+            get: |c| c.advanced.enable_analytics,
+            set: |c, v| c.advanced.enable_analytics = v,
+        },
+    },
+    PaletteEntry {
+        id: "toggle.cache",
+        title: "Toggle Result Cache",
+        subtitle: "Cache query results between searches",
+        icon: "💾",
+        keywords: &["cache", "缓存"],
+        action: PaletteAction::Toggle {
+            get: |c| c.advanced.cache_enabled,
+            set: |c, v| c.advanced.cache_enabled = v,
+        },
+    },
+    // 维护任务
+    PaletteEntry {
+        id: "maintenance.clear_cache",
+        title: "Clear Cache",
+        subtitle: "Delete cached data",
+        icon: "🗑️",
+        keywords: &["cache", "clear", "清除缓存"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ClearCache),
+    },
+    PaletteEntry {
+        id: "maintenance.clear_statistics",
+        title: "Clear Usage Statistics",
+        subtitle: "Reset click and usage statistics",
+        icon: "📉",
+        keywords: &["statistics", "reset", "清除统计"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ClearStatistics),
+    },
+    PaletteEntry {
+        id: "maintenance.reset_query_metrics",
+        title: "Reset Plugin Query Metrics",
+        subtitle: "Clear per-plugin query timing statistics",
+        icon: "⏱️",
+        keywords: &["metrics", "timing", "性能"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ResetQueryMetrics),
+    },
+    // 重新扫描
+    PaletteEntry {
+        id: "rescan.apps",
+        title: "Rescan Applications",
+        subtitle: "Rebuild the installed application list",
+        icon: "🔄",
+        keywords: &["rescan", "apps", "refresh", "重新扫描"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ReloadPlugin("app_search")),
+    },
+    PaletteEntry {
+        id: "rescan.files",
+        title: "Rebuild File Index",
+        subtitle: "Reload the file search index",
+        icon: "🔄",
+        keywords: &["rescan", "files", "index", "reindex", "重建索引"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ReloadPlugin("file_search")),
+    },
+    PaletteEntry {
+        id: "rescan.bookmarks",
+        title: "Reload Browser Bookmarks",
+        subtitle: "Re-read bookmarks from installed browsers",
+        icon: "🔄",
+        keywords: &["rescan", "bookmarks", "browser", "书签"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ReloadPlugin("browser")),
+    },
+    PaletteEntry {
+        id: "rescan.git",
+        title: "Rescan Git Projects",
+        subtitle: "Search for local git repositories again",
+        icon: "🔄",
+        keywords: &["rescan", "git", "projects", "项目"],
+        action: PaletteAction::Maintenance(MaintenanceTask::ReloadPlugin("git")),
+    },
+];
+
+/// 根据结果 ID 查找命令面板条目
+pub fn palette_entry(result_id: &str) -> Option<&'static PaletteEntry> {
+    let id = result_id.strip_prefix(PALETTE_PREFIX)?;
+    PALETTE_ENTRIES.iter().find(|entry| entry.id == id)
+}
+
+/// 搜索命令面板（config 用于显示开关的当前状态）
+pub fn search_palette(query: &str, config: Option<&AppConfig>, plugin_id: &str) -> Vec<QueryResult> {
+    let query = query.trim().to_lowercase();
+    if query.len() < 2 {
+        return vec![];
+    }
+
+    PALETTE_ENTRIES
+        .iter()
+        .filter_map(|entry| {
+            let score = entry.score(&query);
+            if score == 0 {
+                return None;
+            }
+
+            let subtitle = match (entry.action, config) {
+                (PaletteAction::Toggle { get, .. }, Some(config)) => format!(
+                    "{} · {}",
+                    entry.subtitle,
+                    if get(config) { "On" } else { "Off" }
+                ),
+                _ => entry.subtitle.to_string(),
+            };
+            // 打开窗口时不隐藏启动器
+            let prevent_hide = matches!(entry.action, PaletteAction::OpenView(_));
+
+            Some(QueryResult {
+                id: entry.result_id(),
+                title: entry.title.to_string(),
+                subtitle,
+                icon: WoxImage::Emoji(entry.icon.to_string()),
+                score,
+                plugin_id: plugin_id.to_string(),
+                context_data: serde_json::json!({ "palette": entry.id }),
+                actions: vec![Action {
+                    id: ACTION_RUN_PALETTE.to_string(),
+                    name: "Run".to_string(),
+                    icon: None,
+                    is_default: true,
+                    hotkey: None,
+                    prevent_hide,
+                }],
+                preview: None,
+                refreshable: false,
+                group: Some("Commands".to_string()),
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
+            })
+        })
+        .collect()
+}
+
 pub struct SettingsPlugin {
     metadata: PluginMetadata,
 }
@@ -13,7 +289,7 @@ impl SettingsPlugin {
     pub fn new() -> Self {
         Self {
             metadata: PluginMetadata {
-                id: "ilauncher.plugin.settings".to_string(),
+                id: SETTINGS_PLUGIN_ID.to_string(),
                 name: "Settings".to_string(),
                 author: "iLauncher".to_string(),
                 version: "1.0.0".to_string(),
@@ -30,6 +306,8 @@ impl SettingsPlugin {
                 settings: vec![],
                 supported_os: vec!["Windows".to_string(), "macOS".to_string(), "Linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System),
             },
         }
     }
@@ -42,14 +320,36 @@ impl Plugin for SettingsPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.to_lowercase();
+        // 经触发词路由时（如 `settings ...`）以触发词匹配
+        let query = if ctx.trigger_keyword.is_empty() {
+            ctx.search.to_lowercase()
+        } else {
+            ctx.trigger_keyword.to_lowercase()
+        };
+        
+        // 命令面板条目（仅在包含开关时读取配置以显示当前状态）
+        let mut results = {
+            let palette_query = ctx.search.to_lowercase();
+            let needs_config = PALETTE_ENTRIES
+                .iter()
+                .any(|e| matches!(e.action, PaletteAction::Toggle { .. }) && e.score(palette_query.trim()) > 0);
+            let config = if needs_config {
+                match crate::storage::StorageManager::new() {
+                    Ok(storage) => storage.load_config().await.ok(),
+                    Err(_) => None,
+                }
+            } else {
+                None
+            };
+            search_palette(&palette_query, config.as_ref(), &self.metadata.id)
+        };
         
         // 匹配关键词
         let keywords = ["settings", "setting", "config", "preferences", "设置", "配置"];
         let matched = keywords.iter().any(|kw| kw.contains(&query) || query.contains(kw));
         
         if !matched && query.len() < 2 {
-            return Ok(vec![]);
+            return Ok(results);
         }
         
         let score = if query.is_empty() {
@@ -63,10 +363,10 @@ impl Plugin for SettingsPlugin {
         };
         
         if score == 0 {
-            return Ok(vec![]);
+            return Ok(results);
         }
         
-        Ok(vec![QueryResult {
+        results.insert(0, QueryResult {
             id: "settings".to_string(),
             title: "Settings".to_string(),
             subtitle: "Open iLauncher settings".to_string(),
@@ -85,17 +385,22 @@ impl Plugin for SettingsPlugin {
             preview: None,
             refreshable: false,
             group: None,
-        }])
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        });
+        Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
-        if result_id != "settings" {
+    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
+        // 命令面板条目由 execute_action 命令统一处理（需要访问 PluginManager 和窗口）
+        if result_id != "settings" && palette_entry(result_id).is_none() {
             return Err(anyhow::anyhow!("Unknown result_id"));
         }
         
         // 通过 emit 事件通知前端打开设置界面
         // 前端需要监听 'open-settings' 事件
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
     }
 }
 
@@ -125,6 +430,8 @@ impl PluginManagerPlugin {
                 settings: vec![],
                 supported_os: vec!["Windows".to_string(), "macOS".to_string(), "Linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System),
             },
         }
     }
@@ -137,7 +444,12 @@ impl Plugin for PluginManagerPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.to_lowercase();
+        // 经触发词路由时（如 `settings ...`）以触发词匹配
+        let query = if ctx.trigger_keyword.is_empty() {
+            ctx.search.to_lowercase()
+        } else {
+            ctx.trigger_keyword.to_lowercase()
+        };
         
         // 匹配关键词
         let keywords = ["plugins", "plugin", "extensions", "插件", "扩展"];
@@ -180,17 +492,20 @@ impl Plugin for PluginManagerPlugin {
             preview: None,
             refreshable: false,
             group: None,
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         }])
     }
 
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
         if result_id != "plugin_manager" {
             return Err(anyhow::anyhow!("Unknown result_id"));
         }
         
         // 通过 emit 事件通知前端打开插件管理器
         // 前端需要监听 'open-plugin-manager' 事件
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
     }
 }
 
@@ -220,6 +535,8 @@ impl ClipboardHistoryPlugin {
                 settings: vec![],
                 supported_os: vec!["Windows".to_string(), "macOS".to_string(), "Linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System),
             },
         }
     }
@@ -232,7 +549,12 @@ impl Plugin for ClipboardHistoryPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.to_lowercase();
+        // 经触发词路由时（如 `settings ...`）以触发词匹配
+        let query = if ctx.trigger_keyword.is_empty() {
+            ctx.search.to_lowercase()
+        } else {
+            ctx.trigger_keyword.to_lowercase()
+        };
         
         // 匹配关键词
         let keywords = ["clipboard", "clip", "history", "剪贴板", "历史"];
@@ -275,14 +597,45 @@ impl Plugin for ClipboardHistoryPlugin {
             preview: None,
             refreshable: false,
             group: None,
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         }])
     }
 
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
         if result_id != "clipboard_history" {
             return Err(anyhow::anyhow!("Unknown result_id"));
         }
         
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_entries_unique() {
+        let mut ids: Vec<_> = PALETTE_ENTRIES.iter().map(|e| e.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), PALETTE_ENTRIES.len());
+    }
+
+    #[test]
+    fn test_search_palette() {
+        let config = AppConfig::default();
+        let results = search_palette("rescan", Some(&config), SETTINGS_PLUGIN_ID);
+        assert!(results.len() >= 4);
+        assert!(results.iter().all(|r| palette_entry(&r.id).is_some()));
+
+        let results = search_palette("preview", Some(&config), SETTINGS_PLUGIN_ID);
+        assert_eq!(results[0].id, "palette:toggle.show_preview");
+        assert!(results[0].subtitle.ends_with("On"));
+
+        assert!(search_palette("r", None, SETTINGS_PLUGIN_ID).is_empty());
+        assert!(palette_entry("settings").is_none());
     }
 }
@@ -0,0 +1,413 @@
+// 文本片段插件 - 保存常用文本，按关键词搜索后粘贴/复制
+// 支持占位符：{date} {time} {datetime} {clipboard} {uuid}
+
+use crate::core::types::*;
+use crate::plugin::Plugin;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Local, Utc};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// 粘贴前等待启动器窗口隐藏、焦点回到目标窗口
+const PASTE_DELAY_MS: u64 = 150;
+
+/// 文本片段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub id: String,
+    pub name: String,
+    /// 快速匹配关键词（如 `addr`），输入完全一致时排在最前
+    #[serde(default)]
+    pub keyword: String,
+    pub content: String,
+    /// 敏感片段（密码、令牌等）：不显示内容，复制后按剪贴板设置自动清除
+    #[serde(default)]
+    pub secret: bool,
+    #[serde(default)]
+    pub use_count: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 展开占位符（未知占位符原样保留）
+pub fn expand_placeholders(content: &str, clipboard: Option<&str>, now: DateTime<Local>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let name = &after[..end];
+        match name {
+            "date" => output.push_str(&now.format("%Y-%m-%d").to_string()),
+            "time" => output.push_str(&now.format("%H:%M:%S").to_string()),
+            "datetime" => output.push_str(&now.format("%Y-%m-%d %H:%M:%S").to_string()),
+            "clipboard" => output.push_str(clipboard.unwrap_or_default()),
+            "uuid" => output.push_str(&uuid::Uuid::new_v4().to_string()),
+            _ => {
+                output.push('{');
+                output.push_str(name);
+                output.push('}');
+            }
+        }
+        rest = &after[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+pub struct SnippetsPlugin {
+    metadata: PluginMetadata,
+    snippets: Arc<RwLock<Vec<Snippet>>>,
+    storage_path: PathBuf,
+    matcher: SkimMatcherV2,
+}
+
+impl SnippetsPlugin {
+    pub fn new(storage_path: PathBuf) -> Self {
+        Self {
+            metadata: PluginMetadata {
+                id: "snippets".to_string(),
+                name: "Snippets".to_string(),
+                author: "iLauncher".to_string(),
+                version: "1.0.0".to_string(),
+                description: "Store text snippets and paste them anywhere".to_string(),
+                icon: WoxImage::emoji("📝"),
+                trigger_keywords: vec!["sn".to_string(), "snippet".to_string(), "片段".to_string()],
+                commands: vec![],
+                settings: vec![],
+                supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
+                plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Productivity).with_tags(&["snippets", "text"]),
+            },
+            snippets: Arc::new(RwLock::new(Vec::new())),
+            storage_path,
+            matcher: SkimMatcherV2::default(),
+        }
+    }
+
+    /// 从数据目录加载片段
+    pub async fn init(&self) {
+        if !self.storage_path.exists() {
+            return;
+        }
+
+        let loaded = async {
+            let content = tokio::fs::read_to_string(&self.storage_path).await?;
+            Ok::<Vec<Snippet>, anyhow::Error>(serde_json::from_str(&content)?)
+        }
+        .await;
+
+        match loaded {
+            Ok(snippets) => {
+                tracing::info!("📝 Loaded {} snippets", snippets.len());
+                *self.snippets.write().await = snippets;
+            }
+            Err(e) => tracing::warn!("Failed to load snippets: {}", e),
+        }
+    }
+
+    /// 获取全部片段
+    pub async fn list(&self) -> Vec<Snippet> {
+        self.snippets.read().await.clone()
+    }
+
+    /// 新增或更新片段（id 为空时新增），返回保存后的片段
+    pub async fn save_snippet(
+        &self,
+        id: Option<String>,
+        name: String,
+        keyword: String,
+        content: String,
+        secret: bool,
+    ) -> Result<Snippet> {
+        if name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Snippet name cannot be empty"));
+        }
+
+        let now = Utc::now();
+        let keyword = keyword.trim().to_string();
+        let mut snippets = self.snippets.write().await;
+
+        if !keyword.is_empty()
+            && snippets
+                .iter()
+                .any(|s| s.keyword.eq_ignore_ascii_case(&keyword) && Some(&s.id) != id.as_ref())
+        {
+            return Err(anyhow::anyhow!("Keyword '{}' is already used by another snippet", keyword));
+        }
+
+        let saved = match id.and_then(|id| snippets.iter_mut().find(|s| s.id == id)) {
+            Some(existing) => {
+                existing.name = name;
+                existing.keyword = keyword;
+                existing.content = content;
+                existing.secret = secret;
+                existing.updated_at = now;
+                existing.clone()
+            }
+            None => {
+                let snippet = Snippet {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name,
+                    keyword,
+                    content,
+                    secret,
+                    use_count: 0,
+                    created_at: now,
+                    updated_at: now,
+                };
+                snippets.push(snippet.clone());
+                snippet
+            }
+        };
+        drop(snippets);
+
+        self.save().await?;
+        Ok(saved)
+    }
+
+    /// 删除片段，返回是否存在
+    pub async fn delete_snippet(&self, id: &str) -> Result<bool> {
+        let mut snippets = self.snippets.write().await;
+        let before = snippets.len();
+        snippets.retain(|s| s.id != id);
+        let removed = snippets.len() != before;
+        drop(snippets);
+
+        if removed {
+            self.save().await?;
+        }
+        Ok(removed)
+    }
+
+    /// 保存到数据目录
+    async fn save(&self) -> Result<()> {
+        let snippets = self.snippets.read().await.clone();
+        let storage_path = self.storage_path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = storage_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(&snippets)?;
+            std::fs::write(&storage_path, json)?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// 匹配分数（None 表示不匹配）
+    fn score(&self, snippet: &Snippet, search: &str) -> Option<i64> {
+        if search.is_empty() {
+            return Some(0);
+        }
+        if !snippet.keyword.is_empty() && snippet.keyword.eq_ignore_ascii_case(search) {
+            return Some(10_000);
+        }
+        // 敏感片段不按内容匹配
+        let content = if snippet.secret { "" } else { snippet.content.as_str() };
+        [snippet.keyword.as_str(), snippet.name.as_str(), content]
+            .iter()
+            .filter_map(|text| self.matcher.fuzzy_match(text, search))
+            .max()
+    }
+
+    fn to_result(&self, snippet: &Snippet, score: i32, expanded: String) -> QueryResult {
+        let expanded = if snippet.secret { "••••••••".to_string() } else { expanded };
+        let first_line = expanded.lines().next().unwrap_or_default().chars().take(80).collect::<String>();
+        let subtitle = if snippet.keyword.is_empty() {
+            first_line
+        } else {
+            format!("[{}] {}", snippet.keyword, first_line)
+        };
+
+        QueryResult {
+            id: snippet.id.clone(),
+            title: snippet.name.clone(),
+            subtitle,
+            icon: WoxImage::emoji("📝"),
+            score,
+            plugin_id: self.metadata.id.clone(),
+            context_data: serde_json::json!({ "keyword": snippet.keyword }),
+            actions: vec![
+                Action {
+                    id: "paste".to_string(),
+                    name: "Paste".to_string(),
+                    icon: None,
+                    is_default: true,
+                    hotkey: None,
+                    prevent_hide: false,
+                },
+                Action {
+                    id: "copy".to_string(),
+                    name: "Copy".to_string(),
+                    icon: None,
+                    is_default: false,
+                    hotkey: Some("Ctrl+C".to_string()),
+                    prevent_hide: true,
+                },
+            ],
+            preview: Some(Preview::Text(expanded)),
+            refreshable: false,
+            group: Some("Snippets".to_string()),
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        }
+    }
+
+    /// 记录使用次数（用于空查询时排序）
+    async fn record_use(&self, id: &str) {
+        let mut snippets = self.snippets.write().await;
+        if let Some(snippet) = snippets.iter_mut().find(|s| s.id == id) {
+            snippet.use_count += 1;
+        }
+        drop(snippets);
+        if let Err(e) = self.save().await {
+            tracing::warn!("Failed to save snippets: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl Plugin for SnippetsPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let routed = !ctx.trigger_keyword.is_empty();
+        let search = ctx.search.trim();
+
+        // 未经触发词路由时仅在输入足够长时参与全局搜索
+        if !routed && search.chars().count() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let snippets = self.snippets.read().await;
+        let mut matched: Vec<(i64, &Snippet)> = snippets
+            .iter()
+            .filter_map(|snippet| self.score(snippet, search).map(|score| (score, snippet)))
+            .collect();
+        if matched.is_empty() {
+            return Ok(Vec::new());
+        }
+        matched.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.use_count.cmp(&a.1.use_count)));
+
+        let clipboard = arboard::Clipboard::new().ok().and_then(|mut c| c.get_text().ok());
+        let now = Local::now();
+        let results = matched
+            .into_iter()
+            .take(20)
+            .map(|(score, snippet)| {
+                // 全局搜索时片段排在精确结果之后，关键词完全一致时除外
+                let score = if routed || score >= 10_000 { 900 } else { (score as i32).min(80) };
+                let expanded = expand_placeholders(&snippet.content, clipboard.as_deref(), now);
+                self.to_result(snippet, score, expanded)
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        let (content, secret) = self
+            .snippets
+            .read()
+            .await
+            .iter()
+            .find(|s| s.id == result_id)
+            .map(|s| (s.content.clone(), s.secret))
+            .ok_or_else(|| anyhow::anyhow!("Snippet not found: {}", result_id))?;
+
+        let clipboard_text = arboard::Clipboard::new()?.get_text().ok();
+        let expanded = expand_placeholders(&content, clipboard_text.as_deref(), Local::now());
+        if secret {
+            crate::clipboard::write_secret(&expanded, crate::clipboard::SecretSource::Snippet)?;
+        } else {
+            crate::clipboard::write_text(&expanded)?;
+        }
+        self.record_use(result_id).await;
+
+        match action_id {
+            "copy" => Ok(ExecuteOutcome::message("Snippet copied to clipboard")),
+            "paste" => {
+                // 启动器隐藏后把片段粘贴到之前的窗口
+                tokio::task::spawn_blocking(|| {
+                    std::thread::sleep(std::time::Duration::from_millis(PASTE_DELAY_MS));
+                    if let Err(e) = crate::hotkey::input::send_paste_shortcut() {
+                        tracing::warn!("Failed to paste snippet: {}", e);
+                    }
+                });
+                Ok(ExecuteOutcome::Hide)
+            }
+            _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_expand_placeholders() {
+        let now = Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 1).unwrap();
+        assert_eq!(
+            expand_placeholders("Date: {date} {time}", None, now),
+            "Date: 2024-03-05 09:07:01"
+        );
+        assert_eq!(expand_placeholders("> {clipboard}", Some("hello"), now), "> hello");
+        assert_eq!(expand_placeholders("{clipboard}", None, now), "");
+        assert_eq!(expand_placeholders("{unknown} {date", None, now), "{unknown} {date");
+        assert_eq!(expand_placeholders("{uuid}", None, now).len(), 36);
+    }
+
+    #[tokio::test]
+    async fn test_save_and_delete_snippet() {
+        let path = std::env::temp_dir().join(format!("ilauncher_snippets_{}.json", uuid::Uuid::new_v4()));
+        let plugin = SnippetsPlugin::new(path.clone());
+
+        let saved = plugin
+            .save_snippet(None, "Address".to_string(), "addr".to_string(), "1 Main St".to_string(), false)
+            .await
+            .unwrap();
+        assert!(plugin
+            .save_snippet(None, "Other".to_string(), "ADDR".to_string(), "x".to_string(), false)
+            .await
+            .is_err());
+
+        let reloaded = SnippetsPlugin::new(path.clone());
+        reloaded.init().await;
+        assert_eq!(reloaded.list().await.len(), 1);
+        assert_eq!(reloaded.score(&saved, "addr"), Some(10_000));
+
+        assert!(plugin.delete_snippet(&saved.id).await.unwrap());
+        assert!(plugin.list().await.is_empty());
+
+        // 敏感片段不按内容匹配
+        let secret = plugin
+            .save_snippet(None, "VPN".to_string(), String::new(), "hunter2".to_string(), true)
+            .await
+            .unwrap();
+        assert_eq!(plugin.score(&secret, "hunter"), None);
+        assert!(plugin.score(&secret, "vpn").is_some());
+        let _ = std::fs::remove_file(path);
+    }
+}
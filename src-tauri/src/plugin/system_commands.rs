@@ -106,6 +106,8 @@ impl SystemCommandPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System).with_tags(&["power"]),
             },
             commands,
         }
@@ -196,7 +198,7 @@ impl Plugin for SystemCommandPlugin {
         Ok(results)
     }
     
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         if action_id != "execute" {
             return Err(anyhow::anyhow!("Unknown action"));
         }
@@ -207,6 +209,6 @@ impl Plugin for SystemCommandPlugin {
             self.execute_system_command(&cmd.command_type)?;
         }
         
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
     }
 }
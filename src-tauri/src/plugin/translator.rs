@@ -1,6 +1,7 @@
 // 翻译插件
 
 use crate::core::types::*;
+use crate::http::RequestBuilderExt;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -51,6 +52,8 @@ impl TranslatorPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "linux".to_string(), "macos".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::new(1, 300),
+                branding: PluginBranding::new(PluginCategory::Utilities).with_tags(&["translate"]),
             },
             dictionary,
         }
@@ -86,7 +89,7 @@ impl TranslatorPlugin {
         // 这里先返回一个占位结果，你可以后续添加真实的API调用
         
         // 尝试使用 Google Translate 的非官方接口
-        let client = reqwest::Client::new();
+        let client = crate::http::client("translator")?;
         let url = format!(
             "https://translate.googleapis.com/translate_a/single?client=gtx&sl={}&tl={}&dt=t&q={}",
             source_lang,
@@ -94,7 +97,7 @@ impl TranslatorPlugin {
             urlencoding::encode(text)
         );
 
-        match client.get(&url).send().await {
+        match client.get(&url).send_with_retry().await {
             Ok(response) => {
                 if let Ok(body) = response.text().await {
                     // 简单解析返回的JSON（实际格式比较复杂）
@@ -142,22 +145,11 @@ impl crate::plugin::Plugin for TranslatorPlugin {
     }
 
     async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
-        let query = ctx.search.trim();
-        
-        if query.is_empty() {
+        // 检查触发词（trans / tr / 翻译）
+        if !matches!(ctx.trigger_keyword.as_str(), "trans" | "tr" | "翻译") {
             return Ok(Vec::new());
         }
-
-        // 检查触发词
-        let text = if query.starts_with("trans ") {
-            &query[6..]
-        } else if query.starts_with("tr ") {
-            &query[3..]
-        } else if query.starts_with("翻译 ") {
-            &query["翻译 ".len()..]
-        } else {
-            return Ok(Vec::new());
-        };
+        let text = ctx.search.trim();
 
         if text.is_empty() {
             return Ok(vec![QueryResult {
@@ -172,6 +164,9 @@ impl crate::plugin::Plugin for TranslatorPlugin {
                 preview: None,
                 refreshable: false,
                 group: None,
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             }]);
         }
 
@@ -204,6 +199,9 @@ impl crate::plugin::Plugin for TranslatorPlugin {
                 preview: None,
                 refreshable: false,
                 group: Some("本地".to_string()),
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
 
@@ -231,6 +229,9 @@ impl crate::plugin::Plugin for TranslatorPlugin {
                     preview: None,
                     refreshable: false,
                     group: Some("在线".to_string()),
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
                 });
             }
             Err(e) => {
@@ -251,20 +252,21 @@ impl crate::plugin::Plugin for TranslatorPlugin {
                 preview: None,
                 refreshable: false,
                 group: None,
+                accessibility: None,
+                highlights: None,
+                badges: Vec::new(),
             });
         }
 
         Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         match action_id {
             "copy" => {
-                use arboard::Clipboard;
-                let mut clipboard = Clipboard::new()?;
-                clipboard.set_text(result_id)?;
+                crate::clipboard::write_text(result_id)?;
                 tracing::info!("Copied translation to clipboard: {}", result_id);
-                Ok(())
+                Ok(ExecuteOutcome::message("译文已复制到剪贴板"))
             }
             _ => Err(anyhow::anyhow!("Unknown action: {}", action_id)),
         }
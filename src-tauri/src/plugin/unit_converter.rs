@@ -1,34 +1,91 @@
-// 单位转换插件
+// 单位转换插件（含货币换算，汇率见 exchange_rates）
 
 use crate::core::types::*;
+use crate::plugin::exchange_rates::{self, ExchangeRates, RateSettings};
+use crate::plugin::sandbox::SandboxManager;
 use crate::plugin::Plugin;
 use anyhow::Result;
 use async_trait::async_trait;
 use regex::Regex;
+use std::sync::Arc;
+
+/// 汇率接口配置键
+pub const RATES_PROVIDER_KEY: &str = "rates_provider";
+/// 汇率缓存有效期配置键（小时）
+pub const RATES_TTL_KEY: &str = "rates_ttl_hours";
 
 pub struct UnitConverterPlugin {
     metadata: PluginMetadata,
+    rates: Arc<ExchangeRates>,
 }
 
 impl UnitConverterPlugin {
-    pub fn new() -> Self {
+    pub fn new(sandbox: Arc<SandboxManager>) -> Self {
+        let cache_path = crate::storage::get_cache_dir()
+            .map(|dir| dir.join(exchange_rates::CACHE_FILE))
+            .map_err(|e| tracing::warn!("Failed to locate exchange rate cache: {}", e))
+            .ok();
         Self {
             metadata: PluginMetadata {
                 id: "unit_converter".to_string(),
                 name: "Unit Converter".to_string(),
-                description: "Convert between different units".to_string(),
+                description: "Convert between different units and currencies".to_string(),
                 author: "iLauncher".to_string(),
                 version: "1.0.0".to_string(),
                 icon: WoxImage::emoji("📏"),
                 trigger_keywords: vec![],
                 commands: vec![],
-                settings: vec![],
+                settings: vec![
+                    SettingDefinition {
+                        r#type: "text".to_string(),
+                        key: Some(RATES_PROVIDER_KEY.to_string()),
+                        label: Some("汇率接口地址（返回 base + rates 的 JSON，如 open.er-api.com / api.frankfurter.app）".to_string()),
+                        value: Some(serde_json::json!(exchange_rates::DEFAULT_PROVIDER)),
+                    },
+                    SettingDefinition {
+                        r#type: "number".to_string(),
+                        key: Some(RATES_TTL_KEY.to_string()),
+                        label: Some("汇率缓存有效期（小时，过期后后台刷新，离线时继续使用缓存）".to_string()),
+                        value: Some(serde_json::json!(exchange_rates::DEFAULT_TTL_HOURS)),
+                    },
+                ],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Utilities).with_tags(&["units", "currency"]),
             },
+            rates: Arc::new(ExchangeRates::new("unit_converter", crate::core::clock::system(), sandbox, cache_path)),
+        }
+    }
+
+    /// 初始化：读取汇率接口配置
+    pub async fn init(&self) {
+        let Ok(storage) = crate::storage::StorageManager::new() else {
+            return;
+        };
+        if let Ok(config) = storage.get_plugin_config(&self.metadata.id).await {
+            self.configure(&config);
         }
     }
 
+    /// 应用插件配置（保存配置后即时生效）
+    pub fn configure(&self, config: &serde_json::Value) {
+        let provider = config
+            .get(RATES_PROVIDER_KEY)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or(exchange_rates::DEFAULT_PROVIDER);
+        let ttl_hours = config
+            .get(RATES_TTL_KEY)
+            .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+            .unwrap_or(exchange_rates::DEFAULT_TTL_HOURS);
+        self.rates.configure(RateSettings {
+            provider: provider.to_string(),
+            ttl_hours,
+        });
+    }
+
     fn parse_conversion(input: &str) -> Option<(f64, String, String)> {
         // 匹配格式: "10 km to miles" 或 "100 usd to cny"
         let re = Regex::new(r"^(\d+\.?\d*)\s*([a-z]+)\s+to\s+([a-z]+)$").ok()?;
@@ -108,6 +165,74 @@ impl UnitConverterPlugin {
         }
     }
 
+    /// 货币换算（无法获取汇率时返回 None，不影响其他插件结果）
+    async fn convert_currency(&self, value: f64, from: &str, to: &str) -> Option<QueryResult> {
+        if from == to || !self.rates.recognizes(from) || !self.rates.recognizes(to) {
+            return None;
+        }
+        let rates = match self.rates.rates().await {
+            Ok(rates) => rates,
+            Err(e) => {
+                tracing::warn!("Exchange rates unavailable: {}", e);
+                return None;
+            }
+        };
+        let result = rates.table.convert(value, from, to)?;
+
+        let (from, to) = (exchange_rates::normalize_code(from), exchange_rates::normalize_code(to));
+        let formatted_result = Self::format_money(result);
+        let updated = rates.table.fetched_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        let freshness = if rates.stale {
+            format!("cached rates from {}", updated)
+        } else {
+            format!("rates updated {}", updated)
+        };
+
+        Some(self.result(
+            formatted_result.clone(),
+            format!("{} {}", formatted_result, to),
+            format!("{} {} = {} {} · {}", Self::format_money(value), from, formatted_result, to, freshness),
+            "💱",
+        ))
+    }
+
+    fn result(&self, id: String, title: String, subtitle: String, icon: &str) -> QueryResult {
+        QueryResult {
+            id,
+            title,
+            subtitle,
+            icon: WoxImage::emoji(icon),
+            preview: None,
+            score: 100,
+            context_data: serde_json::Value::Null,
+            group: Some("Unit Converter".to_string()),
+            plugin_id: self.metadata.id.clone(),
+            refreshable: false,
+            actions: vec![
+                Action {
+                    id: "copy".to_string(),
+                    name: "Copy Result".to_string(),
+                    icon: None,
+                    is_default: true,
+                    prevent_hide: false,
+                    hotkey: None,
+                },
+            ],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        }
+    }
+
+    /// 金额保留两位小数（极小金额保留有效数字）
+    fn format_money(num: f64) -> String {
+        if num != 0.0 && num.abs() < 0.01 {
+            Self::format_number(num)
+        } else {
+            format!("{:.2}", num)
+        }
+    }
+
     fn format_number(num: f64) -> String {
         if num.abs() < 0.001 || num.abs() > 1_000_000.0 {
             format!("{:.6e}", num)
@@ -138,35 +263,24 @@ impl Plugin for UnitConverterPlugin {
                 let formatted_result = Self::format_number(result);
                 let formatted_value = Self::format_number(value);
                 
-                return Ok(vec![QueryResult {
-                    id: formatted_result.clone(),
-                    title: format!("{} {}", formatted_result, to_name),
-                    subtitle: format!("{} {} = {} {}", formatted_value, from_name, formatted_result, to_name),
-                    icon: WoxImage::emoji("🔄"),
-                    preview: None,
-                    score: 100,
-                    context_data: serde_json::Value::Null,
-                    group: Some("Unit Converter".to_string()),
-                    plugin_id: self.metadata.id.clone(),
-                    refreshable: false,
-                    actions: vec![
-                        Action {
-                            id: "copy".to_string(),
-                            name: "Copy Result".to_string(),
-                            icon: None,
-                            is_default: true,
-                            prevent_hide: false,
-                            hotkey: None,
-                        },
-                    ],
-                }]);
+                return Ok(vec![self.result(
+                    formatted_result.clone(),
+                    format!("{} {}", formatted_result, to_name),
+                    format!("{} {} = {} {}", formatted_value, from_name, formatted_result, to_name),
+                    "🔄",
+                )]);
+            }
+            
+            // 💱 货币换算
+            if let Some(result) = self.convert_currency(value, &from_unit, &to_unit).await {
+                return Ok(vec![result]);
             }
         }
         
         Ok(Vec::new())
     }
     
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
         // 复制结果到剪贴板
         #[cfg(target_os = "windows")]
         {
@@ -196,6 +310,31 @@ impl Plugin for UnitConverterPlugin {
         }
         
         tracing::info!("Copied conversion result: {}", result_id);
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conversion() {
+        assert_eq!(
+            UnitConverterPlugin::parse_conversion("100 USD to cny"),
+            Some((100.0, "usd".to_string(), "cny".to_string()))
+        );
+        assert_eq!(
+            UnitConverterPlugin::parse_conversion("2.5km to miles"),
+            Some((2.5, "km".to_string(), "miles".to_string()))
+        );
+        assert_eq!(UnitConverterPlugin::parse_conversion("usd to cny"), None);
+    }
+
+    #[test]
+    fn test_format_money() {
+        assert_eq!(UnitConverterPlugin::format_money(718.234), "718.23");
+        assert_eq!(UnitConverterPlugin::format_money(0.0), "0.00");
+        assert_eq!(UnitConverterPlugin::format_money(0.0069), "0.0069");
     }
 }
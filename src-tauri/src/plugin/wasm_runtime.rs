@@ -0,0 +1,512 @@
+// WebAssembly 插件运行时 - 基于 wasmtime 加载插件目录中的 .wasm 插件
+// 运行时层面强制执行沙盒限制：timeout_ms（epoch 中断）、max_memory_mb（线性内存上限）
+//
+// ABI（所有字符串为 UTF-8 JSON；返回值 i64 = (ptr << 32) | len，0 表示空）
+//   插件导出：memory, alloc(len) -> ptr, metadata() -> i64, query(ptr, len) -> i64, execute(ptr, len) -> i64
+//   宿主导入（模块 "ilauncher"，受沙盒权限控制，失败时返回 {"error": "..."}）：
+//     log(level, ptr, len)                 level: 0=debug 1=info 2=warn 3=error
+//     clipboard_read() -> i64              {"ok": "文本"}
+//     clipboard_write(ptr, len) -> i32     0 成功，-1 失败
+//     clipboard_write_secret(ptr, len, kind) -> i32
+//                                          写入敏感内容（kind: 0=密码 1=TOTP），按剪贴板设置自动清除
+//     http_request(ptr, len) -> i64        {"method","url","headers","body"} → {"ok": {"status","body"}}
+//     fs_read(ptr, len) -> i64             路径（相对路径基于插件目录）→ {"ok": "文本"}
+
+use super::external_host::{apply_remote_metadata, manifest_metadata, parse_outcome, parse_results};
+use super::plugin_installer::InstalledPlugin;
+use super::sandbox::{PluginPermission, SandboxManager};
+use super::Plugin;
+use crate::core::types::*;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use wasmtime::{Caller, Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+/// epoch 计时粒度
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// 宿主导入模块名
+const HOST_MODULE: &str = "ilauncher";
+
+/// 全局 wasmtime 引擎（所有 WASM 插件共享，后台线程按 EPOCH_TICK 推进 epoch）
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let mut config = Config::new();
+    config.epoch_interruption(true);
+    let engine = Engine::new(&config).expect("Failed to create wasmtime engine");
+
+    let ticker = engine.clone();
+    std::thread::Builder::new()
+        .name("wasm-epoch".to_string())
+        .spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK);
+            ticker.increment_epoch();
+        })
+        .expect("Failed to start wasm epoch thread");
+
+    engine
+});
+
+/// 宿主 API 中的 HTTP 请求
+#[derive(Debug, Deserialize)]
+struct HostHttpRequest {
+    #[serde(default = "default_method")]
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Store 中的宿主状态
+struct HostState {
+    plugin_id: String,
+    install_path: PathBuf,
+    sandbox: Arc<SandboxManager>,
+    runtime: tokio::runtime::Handle,
+    limits: StoreLimits,
+}
+
+/// 已实例化的插件
+struct WasmInstance {
+    store: Store<HostState>,
+    instance: Instance,
+}
+
+/// 插件模块及其运行限制
+struct WasmModule {
+    plugin_id: String,
+    install_path: PathBuf,
+    module: Module,
+    linker: Linker<HostState>,
+    sandbox: Arc<SandboxManager>,
+    runtime: tokio::runtime::Handle,
+    timeout: Duration,
+    max_memory_bytes: usize,
+    /// 调用串行执行；陷入（trap）后丢弃实例，下次调用重新实例化
+    instance: Mutex<Option<WasmInstance>>,
+}
+
+impl WasmModule {
+    fn instantiate(&self) -> Result<WasmInstance> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory_bytes)
+            .instances(1)
+            .trap_on_grow_failure(true)
+            .build();
+
+        let mut store = Store::new(
+            &ENGINE,
+            HostState {
+                plugin_id: self.plugin_id.clone(),
+                install_path: self.install_path.clone(),
+                sandbox: self.sandbox.clone(),
+                runtime: self.runtime.clone(),
+                limits,
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_epoch_deadline(self.deadline_ticks());
+
+        let instance = self.linker.instantiate(&mut store, &self.module)?;
+        Ok(WasmInstance { store, instance })
+    }
+
+    fn deadline_ticks(&self) -> u64 {
+        (self.timeout.as_millis() / EPOCH_TICK.as_millis()).max(1) as u64
+    }
+
+    /// 调用插件导出函数（阻塞，需在 spawn_blocking 中执行）
+    fn call(&self, export: &str, input: Option<&[u8]>) -> Result<serde_json::Value> {
+        let mut guard = self.instance.lock();
+        if guard.is_none() {
+            *guard = Some(self.instantiate()?);
+        }
+        let Some(wasm) = guard.as_mut() else {
+            return Err(anyhow!("WASM plugin {} is not instantiated", self.plugin_id));
+        };
+
+        wasm.store.set_epoch_deadline(self.deadline_ticks());
+        let result = Self::invoke(wasm, export, input);
+
+        if let Err(e) = &result {
+            // 超时或内存越界后实例状态不可信，丢弃
+            if e.downcast_ref::<Trap>().is_some() {
+                *guard = None;
+            }
+            if matches!(e.downcast_ref::<Trap>(), Some(Trap::Interrupt)) {
+                return Err(anyhow!(
+                    "WASM plugin {} timed out after {:?} ({})",
+                    self.plugin_id,
+                    self.timeout,
+                    export
+                ));
+            }
+        }
+        result
+    }
+
+    fn invoke(wasm: &mut WasmInstance, export: &str, input: Option<&[u8]>) -> Result<serde_json::Value> {
+        let packed = match input {
+            Some(input) => {
+                let ptr = write_guest(&mut wasm.store, &wasm.instance, input)?;
+                let func = wasm
+                    .instance
+                    .get_typed_func::<(i32, i32), i64>(&mut wasm.store, export)?;
+                func.call(&mut wasm.store, (ptr, input.len() as i32))?
+            }
+            None => {
+                let func = wasm.instance.get_typed_func::<(), i64>(&mut wasm.store, export)?;
+                func.call(&mut wasm.store, ())?
+            }
+        };
+
+        let memory = wasm
+            .instance
+            .get_memory(&mut wasm.store, "memory")
+            .ok_or_else(|| anyhow!("WASM plugin does not export memory"))?;
+        let bytes = read_packed(memory.data(&wasm.store), packed)?;
+        if bytes.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+
+        let value: serde_json::Value = serde_json::from_slice(&bytes).context("Invalid JSON from WASM plugin")?;
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow!("{}", error));
+        }
+        Ok(value)
+    }
+}
+
+/// WASM 插件（实现 Plugin trait）
+pub struct WasmPlugin {
+    metadata: PluginMetadata,
+    module: Arc<WasmModule>,
+}
+
+impl WasmPlugin {
+    /// 编译并实例化已安装的 WASM 插件，注册沙盒配置
+    pub async fn load(installed: &InstalledPlugin, sandbox: Arc<SandboxManager>) -> Result<Self> {
+        let sandbox_config = installed.sandbox_config();
+        let timeout = Duration::from_millis(sandbox_config.timeout_ms.unwrap_or(5000).max(10));
+        let max_memory_bytes = (sandbox_config.max_memory_mb.unwrap_or(100) as usize).saturating_mul(1024 * 1024);
+        sandbox.register(sandbox_config);
+
+        let wasm_path = installed.install_path.join(&installed.manifest.engine.entry);
+        let runtime = tokio::runtime::Handle::current();
+        let mut metadata = manifest_metadata(installed);
+
+        let module = tokio::task::spawn_blocking({
+            let plugin_id = installed.manifest.id.clone();
+            let install_path = installed.install_path.clone();
+            move || -> Result<WasmModule> {
+                let module = Module::from_file(&ENGINE, &wasm_path)
+                    .with_context(|| format!("Failed to compile {:?}", wasm_path))?;
+                Ok(WasmModule {
+                    plugin_id,
+                    install_path,
+                    module,
+                    linker: host_linker()?,
+                    sandbox,
+                    runtime,
+                    timeout,
+                    max_memory_bytes,
+                    instance: Mutex::new(None),
+                })
+            }
+        })
+        .await??;
+        let module = Arc::new(module);
+
+        // metadata 导出可选
+        let has_metadata = module.module.get_export("metadata").is_some();
+        if has_metadata {
+            let value = Self::run(&module, "metadata", None).await?;
+            apply_remote_metadata(&mut metadata, value);
+        }
+
+        tracing::info!(
+            "🧩 WASM plugin loaded: {} v{} (timeout {:?}, memory {} MB)",
+            metadata.id,
+            metadata.version,
+            module.timeout,
+            module.max_memory_bytes / (1024 * 1024)
+        );
+        Ok(Self { metadata, module })
+    }
+
+    async fn run(module: &Arc<WasmModule>, export: &'static str, input: Option<Vec<u8>>) -> Result<serde_json::Value> {
+        let module = module.clone();
+        tokio::task::spawn_blocking(move || module.call(export, input.as_deref())).await?
+    }
+}
+
+#[async_trait]
+impl Plugin for WasmPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let input = serde_json::to_vec(ctx)?;
+        let value = Self::run(&self.module, "query", Some(input)).await?;
+        if value.is_null() {
+            return Ok(Vec::new());
+        }
+        parse_results(&self.metadata, value)
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        let input = serde_json::to_vec(&serde_json::json!({
+            "result_id": result_id,
+            "action_id": action_id,
+        }))?;
+        let value = Self::run(&self.module, "execute", Some(input)).await?;
+        Ok(parse_outcome(&self.metadata, value))
+    }
+}
+
+/// 拆分 (ptr << 32) | len
+fn unpack(packed: i64) -> (usize, usize) {
+    let packed = packed as u64;
+    ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize)
+}
+
+fn pack(ptr: i32, len: usize) -> i64 {
+    (((ptr as u32 as u64) << 32) | len as u64) as i64
+}
+
+/// 读取插件内存中的返回数据（越界视为错误）
+fn read_packed(memory: &[u8], packed: i64) -> Result<Vec<u8>> {
+    if packed == 0 {
+        return Ok(Vec::new());
+    }
+    let (ptr, len) = unpack(packed);
+    memory
+        .get(ptr..ptr.saturating_add(len))
+        .map(|bytes| bytes.to_vec())
+        .ok_or_else(|| anyhow!("WASM plugin returned out-of-bounds buffer"))
+}
+
+/// 通过插件的 alloc 导出分配内存并写入数据
+fn write_guest(store: &mut Store<HostState>, instance: &Instance, data: &[u8]) -> Result<i32> {
+    let alloc = instance.get_typed_func::<i32, i32>(&mut *store, "alloc")?;
+    let ptr = alloc.call(&mut *store, data.len() as i32)?;
+    let memory = instance
+        .get_memory(&mut *store, "memory")
+        .ok_or_else(|| anyhow!("WASM plugin does not export memory"))?;
+    memory.write(&mut *store, ptr as usize, data)?;
+    Ok(ptr)
+}
+
+/// 宿主函数中读取插件传入的字符串
+fn read_guest_str(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> Result<String> {
+    let memory = guest_memory(caller)?;
+    let bytes = memory
+        .data(&*caller)
+        .get(ptr as usize..(ptr as usize).saturating_add(len as usize))
+        .ok_or_else(|| anyhow!("Out-of-bounds read from WASM memory"))?;
+    Ok(String::from_utf8_lossy(bytes).to_string())
+}
+
+/// 宿主函数中把结果写回插件内存
+fn return_to_guest(caller: &mut Caller<'_, HostState>, value: serde_json::Value) -> Result<i64> {
+    let data = serde_json::to_vec(&value)?;
+    let alloc = caller
+        .get_export("alloc")
+        .and_then(|e| e.into_func())
+        .ok_or_else(|| anyhow!("WASM plugin does not export alloc"))?
+        .typed::<i32, i32>(&*caller)?;
+    let ptr = alloc.call(&mut *caller, data.len() as i32)?;
+    guest_memory(caller)?.write(&mut *caller, ptr as usize, &data)?;
+    Ok(pack(ptr, data.len()))
+}
+
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(|e| e.into_memory())
+        .ok_or_else(|| anyhow!("WASM plugin does not export memory"))
+}
+
+/// 宿主 API 结果 → {"ok": ...} / {"error": ...}
+fn host_result(result: Result<serde_json::Value>) -> serde_json::Value {
+    match result {
+        Ok(value) => serde_json::json!({ "ok": value }),
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    }
+}
+
+/// 创建宿主 API（能力受沙盒权限限制）
+fn host_linker() -> Result<Linker<HostState>> {
+    let mut linker = Linker::new(&ENGINE);
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "log",
+        |mut caller: Caller<'_, HostState>, level: i32, ptr: i32, len: i32| -> Result<()> {
+            let message = read_guest_str(&mut caller, ptr, len)?;
+            let plugin_id = &caller.data().plugin_id;
+            match level {
+                0 => tracing::debug!("[wasm:{}] {}", plugin_id, message),
+                2 => tracing::warn!("[wasm:{}] {}", plugin_id, message),
+                3 => tracing::error!("[wasm:{}] {}", plugin_id, message),
+                _ => tracing::info!("[wasm:{}] {}", plugin_id, message),
+            }
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "clipboard_read",
+        |mut caller: Caller<'_, HostState>| -> Result<i64> {
+            let state = caller.data();
+            let result = state
+                .sandbox
+                .check_permission(&state.plugin_id, &PluginPermission::ClipboardAccess)
+                .and_then(|_| Ok(arboard::Clipboard::new()?.get_text()?))
+                .map(serde_json::Value::String);
+            return_to_guest(&mut caller, host_result(result))
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "clipboard_write",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i32> {
+            let text = read_guest_str(&mut caller, ptr, len)?;
+            let state = caller.data();
+            let result = state
+                .sandbox
+                .check_permission(&state.plugin_id, &PluginPermission::ClipboardAccess)
+                .and_then(|_| crate::clipboard::write_text(&text));
+            Ok(if result.is_ok() { 0 } else { -1 })
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "clipboard_write_secret",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32, kind: i32| -> Result<i32> {
+            let text = read_guest_str(&mut caller, ptr, len)?;
+            let source = match kind {
+                1 => crate::clipboard::SecretSource::Totp,
+                _ => crate::clipboard::SecretSource::PasswordManager,
+            };
+            let state = caller.data();
+            let result = state
+                .sandbox
+                .check_permission(&state.plugin_id, &PluginPermission::ClipboardAccess)
+                .and_then(|_| crate::clipboard::write_secret(&text, source));
+            Ok(if result.is_ok() { 0 } else { -1 })
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "http_request",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i64> {
+            let request = read_guest_str(&mut caller, ptr, len)?;
+            let result = host_http_request(caller.data(), &request);
+            return_to_guest(&mut caller, host_result(result))
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "fs_read",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> Result<i64> {
+            let path = read_guest_str(&mut caller, ptr, len)?;
+            let result = host_fs_read(caller.data(), &path);
+            return_to_guest(&mut caller, host_result(result))
+        },
+    )?;
+
+    Ok(linker)
+}
+
+/// HTTP 请求（需要目标域名的网络权限，使用统一 HTTP 客户端）
+fn host_http_request(state: &HostState, request: &str) -> Result<serde_json::Value> {
+    let request: HostHttpRequest = serde_json::from_str(request)?;
+    let url = reqwest::Url::parse(&request.url)?;
+    let domain = url.host_str().ok_or_else(|| anyhow!("URL has no host"))?;
+    state.sandbox.validate_network_access(&state.plugin_id, domain)?;
+
+    let client = crate::http::client(&state.plugin_id)?;
+    let method = reqwest::Method::from_bytes(request.method.to_uppercase().as_bytes())?;
+    let mut builder = client.request(method, url);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = request.body {
+        builder = builder.body(body);
+    }
+
+    // 在阻塞线程中等待异步请求
+    state.runtime.block_on(async move {
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let body = response.text().await?;
+        Ok(serde_json::json!({ "status": status, "body": body }))
+    })
+}
+
+/// 读取文件（需要文件读取权限；相对路径基于插件目录，规范化后再检查防止 .. 越界）
+fn host_fs_read(state: &HostState, path: &str) -> Result<serde_json::Value> {
+    let path = Path::new(path);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        state.install_path.join(path)
+    };
+    let path = path.canonicalize().with_context(|| format!("File not found: {:?}", path))?;
+    sandbox_path_check(state, &path)?;
+
+    Ok(serde_json::Value::String(std::fs::read_to_string(&path)?))
+}
+
+/// 沙盒路径检查（与规范化后的授权目录比较）
+fn sandbox_path_check(state: &HostState, path: &Path) -> Result<()> {
+    // 插件目录始终可读（规范化后比较，避免符号链接或盘符大小写差异）
+    let install_path = state.install_path.canonicalize().unwrap_or_else(|_| state.install_path.clone());
+    if path.starts_with(&install_path) {
+        return Ok(());
+    }
+    state.sandbox.validate_file_access(&state.plugin_id, path, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_roundtrip() {
+        let packed = pack(0x1234, 42);
+        assert_eq!(unpack(packed), (0x1234, 42));
+    }
+
+    #[test]
+    fn test_read_packed_bounds() {
+        let memory = b"hello world";
+        assert_eq!(read_packed(memory, pack(6, 5)).unwrap(), b"world");
+        assert!(read_packed(memory, pack(6, 50)).is_err());
+        assert!(read_packed(memory, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_host_result_shape() {
+        assert_eq!(host_result(Ok(serde_json::json!(1)))["ok"], 1);
+        assert!(host_result(Err(anyhow!("denied")))["error"].is_string());
+    }
+}
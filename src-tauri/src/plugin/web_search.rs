@@ -1,80 +1,111 @@
 // Web 搜索插件
+// 内置搜索引擎 + 用户自定义引擎（保存在插件配置 custom_engines 中，同关键词覆盖内置引擎），
+// 自定义引擎可从 OpenSearch 描述文件导入
 
 use crate::core::types::*;
+use crate::plugin::opensearch;
 use crate::plugin::Plugin;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// 插件配置中保存自定义引擎的字段
+pub const CUSTOM_ENGINES_KEY: &str = "custom_engines";
+
+/// 关键词提示结果 ID 前缀（执行时把关键词填入搜索框）
+const KEYWORD_PREFIX: &str = "keyword:";
+
+/// 输入关键词前缀时最多提示的引擎数
+const MAX_KEYWORD_SUGGESTIONS: usize = 3;
+
+/// 自定义引擎未设置图标时使用的图标
+const DEFAULT_ENGINE_ICON: &str = "🔍";
 
 pub struct WebSearchPlugin {
     metadata: PluginMetadata,
     search_engines: Vec<SearchEngine>,
+    custom_engines: RwLock<Vec<SearchEngine>>,
 }
 
-#[derive(Clone)]
-struct SearchEngine {
-    name: String,
-    keyword: String,
-    url_template: String,
-    icon: String,
+/// 搜索引擎（URL 模板中的 {query} 替换为编码后的搜索词）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchEngine {
+    pub name: String,
+    pub keyword: String,
+    pub url_template: String,
+    /// Emoji，或 http(s) / data URL 图片
+    #[serde(default)]
+    pub icon: String,
+    /// 用户自定义引擎（内置引擎不可删除）
+    #[serde(default)]
+    pub custom: bool,
+}
+
+impl SearchEngine {
+    fn builtin(name: &str, keyword: &str, url_template: &str, icon: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            keyword: keyword.to_string(),
+            url_template: url_template.to_string(),
+            icon: icon.to_string(),
+            custom: false,
+        }
+    }
+
+    /// 规范化并校验自定义引擎（关键词小写、不含空白，模板为 http(s) 且包含 {query}）
+    fn normalized(mut self) -> Result<Self> {
+        self.name = self.name.trim().to_string();
+        self.keyword = self.keyword.trim().to_lowercase();
+        self.url_template = self.url_template.trim().to_string();
+        self.icon = self.icon.trim().to_string();
+        self.custom = true;
+
+        if self.name.is_empty() {
+            return Err(anyhow!("Search engine name is required"));
+        }
+        if self.keyword.is_empty() || self.keyword.contains(char::is_whitespace) || self.keyword.starts_with('?') {
+            return Err(anyhow!("Keyword must be a single word and cannot start with '?'"));
+        }
+        if !self.url_template.starts_with("https://") && !self.url_template.starts_with("http://") {
+            return Err(anyhow!("URL template must start with http:// or https://"));
+        }
+        if !self.url_template.contains("{query}") {
+            return Err(anyhow!("URL template must contain {{query}}"));
+        }
+        if self.icon.is_empty() {
+            self.icon = DEFAULT_ENGINE_ICON.to_string();
+        }
+        Ok(self)
+    }
+
+    fn url(&self, query: &str) -> String {
+        self.url_template.replace("{query}", &urlencoding::encode(query))
+    }
+
+    fn image(&self) -> WoxImage {
+        if self.icon.starts_with("https://") || self.icon.starts_with("http://") {
+            WoxImage::url(&self.icon)
+        } else if self.icon.starts_with("data:") {
+            WoxImage::Base64(self.icon.clone())
+        } else {
+            WoxImage::emoji(&self.icon)
+        }
+    }
 }
 
 impl WebSearchPlugin {
     pub fn new() -> Self {
         let search_engines = vec![
-            SearchEngine {
-                name: "Google".to_string(),
-                keyword: "g".to_string(),
-                url_template: "https://www.google.com/search?q={query}".to_string(),
-                icon: "🔍".to_string(),
-            },
-            SearchEngine {
-                name: "Bing".to_string(),
-                keyword: "b".to_string(),
-                url_template: "https://www.bing.com/search?q={query}".to_string(),
-                icon: "🔎".to_string(),
-            },
-            SearchEngine {
-                name: "Baidu".to_string(),
-                keyword: "bd".to_string(),
-                url_template: "https://www.baidu.com/s?wd={query}".to_string(),
-                icon: "🐻".to_string(),
-            },
-            SearchEngine {
-                name: "GitHub".to_string(),
-                keyword: "gh".to_string(),
-                url_template: "https://github.com/search?q={query}".to_string(),
-                icon: "😺".to_string(),
-            },
-            SearchEngine {
-                name: "Stack Overflow".to_string(),
-                keyword: "so".to_string(),
-                url_template: "https://stackoverflow.com/search?q={query}".to_string(),
-                icon: "📚".to_string(),
-            },
-            SearchEngine {
-                name: "YouTube".to_string(),
-                keyword: "yt".to_string(),
-                url_template: "https://www.youtube.com/results?search_query={query}".to_string(),
-                icon: "📺".to_string(),
-            },
-            SearchEngine {
-                name: "Wikipedia".to_string(),
-                keyword: "wiki".to_string(),
-                url_template: "https://en.wikipedia.org/wiki/Special:Search?search={query}".to_string(),
-                icon: "📖".to_string(),
-            },
-            SearchEngine {
-                name: "淘宝".to_string(),
-                keyword: "tb".to_string(),
-                url_template: "https://s.taobao.com/search?q={query}".to_string(),
-                icon: "🛒".to_string(),
-            },
-            SearchEngine {
-                name: "知乎".to_string(),
-                keyword: "zh".to_string(),
-                url_template: "https://www.zhihu.com/search?q={query}".to_string(),
-                icon: "💡".to_string(),
-            },
+            SearchEngine::builtin("Google", "g", "https://www.google.com/search?q={query}", "🔍"),
+            SearchEngine::builtin("Bing", "b", "https://www.bing.com/search?q={query}", "🔎"),
+            SearchEngine::builtin("Baidu", "bd", "https://www.baidu.com/s?wd={query}", "🐻"),
+            SearchEngine::builtin("GitHub", "gh", "https://github.com/search?q={query}", "😺"),
+            SearchEngine::builtin("Stack Overflow", "so", "https://stackoverflow.com/search?q={query}", "📚"),
+            SearchEngine::builtin("YouTube", "yt", "https://www.youtube.com/results?search_query={query}", "📺"),
+            SearchEngine::builtin("Wikipedia", "wiki", "https://en.wikipedia.org/wiki/Special:Search?search={query}", "📖"),
+            SearchEngine::builtin("淘宝", "tb", "https://s.taobao.com/search?q={query}", "🛒"),
+            SearchEngine::builtin("知乎", "zh", "https://www.zhihu.com/search?q={query}", "💡"),
         ];
 
         Self {
@@ -90,13 +121,195 @@ impl WebSearchPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::Search).with_tags(&["web"]),
             },
             search_engines,
+            custom_engines: RwLock::new(Vec::new()),
+        }
+    }
+    
+    /// 初始化：读取自定义引擎
+    pub async fn init(&self) {
+        let Ok(storage) = crate::storage::StorageManager::new() else {
+            return;
+        };
+        if let Ok(config) = storage.get_plugin_config(&self.metadata.id).await {
+            self.configure(&config);
+        }
+    }
+    
+    /// 应用插件配置（保存配置后即时生效，无效条目忽略）
+    pub fn configure(&self, config: &serde_json::Value) {
+        let engines = config
+            .get(CUSTOM_ENGINES_KEY)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| serde_json::from_value::<SearchEngine>(item.clone()).ok())
+                    .filter_map(|engine| engine.normalized().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        *self.custom_engines.write() = engines;
+    }
+    
+    /// 全部生效的引擎（内置引擎按原顺序，被同关键词的自定义引擎覆盖，其余自定义引擎在后）
+    pub fn engines(&self) -> Vec<SearchEngine> {
+        let custom = self.custom_engines.read();
+        let mut engines: Vec<SearchEngine> = self
+            .search_engines
+            .iter()
+            .map(|builtin| custom.iter().find(|c| c.keyword == builtin.keyword).unwrap_or(builtin).clone())
+            .collect();
+        engines.extend(
+            custom
+                .iter()
+                .filter(|c| !self.search_engines.iter().any(|builtin| builtin.keyword == c.keyword))
+                .cloned(),
+        );
+        engines
+    }
+    
+    /// 新增或修改自定义引擎（original_keyword 为修改前的关键词），返回需要写入配置的自定义引擎列表
+    pub fn save_engine(&self, engine: SearchEngine, original_keyword: Option<&str>) -> Result<serde_json::Value> {
+        let engine = engine.normalized()?;
+        let original = original_keyword.map(|k| k.trim().to_lowercase());
+        
+        let mut custom = self.custom_engines.write();
+        if original.as_deref() != Some(engine.keyword.as_str()) && custom.iter().any(|c| c.keyword == engine.keyword) {
+            return Err(anyhow!("Keyword '{}' is already used by another search engine", engine.keyword));
+        }
+        custom.retain(|c| Some(&c.keyword) != original.as_ref() && c.keyword != engine.keyword);
+        custom.push(engine);
+        Ok(serde_json::to_value(&*custom)?)
+    }
+    
+    /// 删除自定义引擎，返回需要写入配置的自定义引擎列表
+    pub fn delete_engine(&self, keyword: &str) -> Result<serde_json::Value> {
+        let keyword = keyword.trim().to_lowercase();
+        let mut custom = self.custom_engines.write();
+        let before = custom.len();
+        custom.retain(|c| c.keyword != keyword);
+        if custom.len() == before {
+            return Err(anyhow!("No custom search engine with keyword '{}'", keyword));
+        }
+        Ok(serde_json::to_value(&*custom)?)
+    }
+    
+    /// 从 OpenSearch 描述文件（或声明了描述文件的网页）生成引擎，关键词未指定时按名称生成
+    pub async fn fetch_opensearch(&self, url: &str, keyword: Option<String>) -> Result<SearchEngine> {
+        let client = crate::http::client(&self.metadata.id)?;
+        let fetch = |url: String| {
+            let client = client.clone();
+            async move { anyhow::Ok(client.get(&url).send().await?.error_for_status()?.text().await?) }
+        };
+        
+        let body = fetch(url.to_string()).await?;
+        let description = match opensearch::parse(&body) {
+            Ok(description) => description,
+            Err(e) => match opensearch::discover(&body, url) {
+                Some(descriptor_url) => opensearch::parse(&fetch(descriptor_url).await?)?,
+                None => return Err(e),
+            },
+        };
+        
+        let keyword = keyword
+            .filter(|k| !k.trim().is_empty())
+            .unwrap_or_else(|| self.suggest_keyword(&description.name));
+        SearchEngine {
+            name: description.name,
+            keyword,
+            url_template: description.url_template,
+            icon: description.icon.unwrap_or_default(),
+            custom: true,
+        }
+        .normalized()
+    }
+    
+    /// 按名称生成未被占用的关键词（首个单词的字母数字，最多 8 个字符，冲突时追加数字）
+    fn suggest_keyword(&self, name: &str) -> String {
+        let base: String = name
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .take(8)
+            .collect();
+        let base = if base.is_empty() { "search".to_string() } else { base };
+        
+        let engines = self.engines();
+        let taken = |keyword: &str| engines.iter().any(|e| e.keyword == keyword);
+        if !taken(&base) {
+            return base;
+        }
+        (2..).map(|n| format!("{}{}", base, n)).find(|k| !taken(k)).unwrap_or(base)
+    }
+    
+    /// 搜索结果
+    fn search_result(&self, engine: &SearchEngine, query: &str, subtitle: String, score: i32, is_default: bool) -> QueryResult {
+        let url = engine.url(query);
+        QueryResult {
+            id: url.clone(),
+            title: format!("Search '{}' on {}", query, engine.name),
+            subtitle,
+            icon: engine.image(),
+            preview: None,
+            score,
+            context_data: serde_json::Value::Null,
+            group: Some("Web Search".to_string()),
+            plugin_id: self.metadata.id.clone(),
+            refreshable: false,
+            actions: vec![
+                Action {
+                    id: "open".to_string(),
+                    name: format!("Search on {}", engine.name),
+                    icon: None,
+                    is_default,
+                    prevent_hide: false,
+                    hotkey: None,
+                },
+            ],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
+        }
+    }
+    
+    /// 关键词提示（输入是引擎关键词的前缀时显示，执行后把关键词填入搜索框）
+    fn keyword_suggestion(&self, engine: &SearchEngine, score: i32) -> QueryResult {
+        QueryResult {
+            id: format!("{}{}", KEYWORD_PREFIX, engine.keyword),
+            title: format!("{} — {}", engine.keyword, engine.name),
+            subtitle: format!("Type \"{} <query>\" to search {}", engine.keyword, engine.name),
+            icon: engine.image(),
+            preview: None,
+            score,
+            context_data: serde_json::Value::Null,
+            group: Some("Web Search".to_string()),
+            plugin_id: self.metadata.id.clone(),
+            refreshable: false,
+            actions: vec![
+                Action {
+                    id: "use_keyword".to_string(),
+                    name: "Use Keyword".to_string(),
+                    icon: None,
+                    is_default: true,
+                    prevent_hide: true,
+                    hotkey: None,
+                },
+            ],
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         }
     }
 
     /// 打开 URL
-    async fn open_url(url: &str) -> Result<()> {
+    pub(crate) async fn open_url(url: &str) -> Result<()> {
         let url = url.to_string();
         
         tokio::task::spawn_blocking(move || {
@@ -147,83 +360,148 @@ impl Plugin for WebSearchPlugin {
             return Ok(Vec::new());
         }
         
-        let mut results = Vec::new();
+        let engines = self.engines();
         
         // 检查是否是特定搜索引擎的关键词
-        for engine in &self.search_engines {
-            // 格式: "g rust" 或 "gh tauri"
-            if search.starts_with(&format!("{} ", engine.keyword)) {
-                let query = search[engine.keyword.len() + 1..].trim();
-                if !query.is_empty() {
-                    let url = engine.url_template.replace("{query}", &urlencoding::encode(query));
-                    
-                    results.push(QueryResult {
-                        id: url.clone(),
-                        title: format!("Search '{}' on {}", query, engine.name),
-                        subtitle: url.clone(),
-                        icon: WoxImage::emoji(&engine.icon),
-                        preview: None,
-                        score: 100,
-                        context_data: serde_json::Value::Null,
-                        group: Some("Web Search".to_string()),
-                        plugin_id: self.metadata.id.clone(),
-                        refreshable: false,
-                        actions: vec![
-                            Action {
-                                id: "open".to_string(),
-                                name: format!("Search on {}", engine.name),
-                                icon: None,
-                                is_default: true,
-                                prevent_hide: false,
-                                hotkey: None,
-                            },
-                        ],
-                    });
+        // 格式: "g rust" 或 "gh tauri"
+        if let Some((keyword, query)) = search.split_once(char::is_whitespace) {
+            if let Some(engine) = engines.iter().find(|e| e.keyword.eq_ignore_ascii_case(keyword)) {
+                let query = query.trim();
+                if query.is_empty() {
+                    return Ok(Vec::new());
                 }
-                
                 // 只返回匹配的搜索引擎结果
-                return Ok(results);
+                return Ok(vec![self.search_result(engine, query, engine.url(query), 100, true)]);
             }
         }
         
         // 🔥 优化：只在输入 "?" 前缀时显示网页搜索选项，避免干扰文件搜索
         // 用户可以输入 "? keyword" 来触发网页搜索
-        if search.starts_with("? ") && search.len() > 2 {
-            let query = search[2..].trim();
-            if !query.is_empty() {
-                for (idx, engine) in self.search_engines.iter().enumerate() {
-                    let url = engine.url_template.replace("{query}", &urlencoding::encode(query));
-                    
-                    results.push(QueryResult {
-                        id: url.clone(),
-                        title: format!("Search '{}' on {}", query, engine.name),
-                        subtitle: format!("Keyword: {} | {}", engine.keyword, url),
-                        icon: WoxImage::emoji(&engine.icon),
-                        preview: None,
-                        score: 90 - idx as i32,
-                        context_data: serde_json::Value::Null,
-                        group: Some("Web Search".to_string()),
-                        plugin_id: self.metadata.id.clone(),
-                        refreshable: false,
-                        actions: vec![
-                            Action {
-                                id: "open".to_string(),
-                                name: format!("Search on {}", engine.name),
-                                icon: None,
-                                is_default: idx == 0,
-                                prevent_hide: false,
-                                hotkey: None,
-                            },
-                        ],
-                    });
-                }
-            }
+        if let Some(query) = search.strip_prefix("? ").map(str::trim).filter(|q| !q.is_empty()) {
+            return Ok(engines
+                .iter()
+                .enumerate()
+                .map(|(idx, engine)| {
+                    let subtitle = format!("Keyword: {} | {}", engine.keyword, engine.url(query));
+                    self.search_result(engine, query, subtitle, 90 - idx as i32, idx == 0)
+                })
+                .collect());
         }
         
-        Ok(results)
+        // 💡 输入的是引擎关键词前缀时提示关键词（低分，不干扰其他结果）
+        let typed = search.to_lowercase();
+        Ok(engines
+            .iter()
+            .filter(|e| e.keyword.starts_with(&typed))
+            .take(MAX_KEYWORD_SUGGESTIONS)
+            .enumerate()
+            .map(|(idx, engine)| self.keyword_suggestion(engine, 20 - idx as i32))
+            .collect())
     }
     
-    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<()> {
-        Self::open_url(result_id).await
+    async fn execute(&self, result_id: &str, _action_id: &str) -> Result<ExecuteOutcome> {
+        if let Some(keyword) = result_id.strip_prefix(KEYWORD_PREFIX) {
+            return Ok(ExecuteOutcome::ReplaceQuery { query: format!("{} ", keyword) });
+        }
+        Self::open_url(result_id).await?;
+        Ok(ExecuteOutcome::Hide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(search: &str) -> QueryContext {
+        QueryContext {
+            query_type: QueryType::Input,
+            trigger_keyword: String::new(),
+            command: None,
+            search: search.to_string(),
+            raw_query: search.to_string(),
+            cancel: Default::default(),
+        }
+    }
+
+    fn custom(name: &str, keyword: &str, url_template: &str) -> SearchEngine {
+        SearchEngine {
+            name: name.to_string(),
+            keyword: keyword.to_string(),
+            url_template: url_template.to_string(),
+            icon: String::new(),
+            custom: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_engines_override_and_extend_builtin() {
+        let plugin = WebSearchPlugin::new();
+        plugin.configure(&serde_json::json!({
+            CUSTOM_ENGINES_KEY: [
+                { "name": "MDN", "keyword": "MDN", "url_template": "https://developer.mozilla.org/search?q={query}" },
+                { "name": "Google (HK)", "keyword": "g", "url_template": "https://www.google.com.hk/search?q={query}" },
+                { "name": "Broken", "keyword": "bad", "url_template": "https://example.com/" }
+            ]
+        }));
+
+        let engines = plugin.engines();
+        assert_eq!(engines[0].name, "Google (HK)");
+        assert_eq!(engines.last().unwrap().keyword, "mdn");
+        assert!(engines.last().unwrap().custom);
+        assert!(!engines.iter().any(|e| e.keyword == "bad"));
+
+        let results = plugin.query(&ctx("mdn flexbox gap")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "https://developer.mozilla.org/search?q=flexbox%20gap");
+        assert!(matches!(&results[0].icon, WoxImage::Emoji(icon) if icon == DEFAULT_ENGINE_ICON));
+
+        let results = plugin.query(&ctx("g rust")).await.unwrap();
+        assert_eq!(results[0].id, "https://www.google.com.hk/search?q=rust");
+    }
+
+    #[test]
+    fn test_save_and_delete_engines() {
+        let plugin = WebSearchPlugin::new();
+        let saved = plugin.save_engine(custom(" Docs ", "docs", "https://docs.rs/releases/search?query={query}"), None).unwrap();
+        assert_eq!(saved[0]["name"], "Docs");
+
+        assert!(plugin.save_engine(custom("Docs 2", "docs", "https://example.com/?q={query}"), None).is_err());
+        assert!(plugin.save_engine(custom("Bad", "two words", "https://example.com/?q={query}"), None).is_err());
+        assert!(plugin.save_engine(custom("Bad", "x", "https://example.com/"), None).is_err());
+        assert!(plugin.save_engine(custom("Bad", "x", "file:///{query}"), None).is_err());
+
+        // 修改关键词
+        let saved = plugin
+            .save_engine(custom("Docs", "rs", "https://docs.rs/releases/search?query={query}"), Some("docs"))
+            .unwrap();
+        assert_eq!(saved.as_array().unwrap().len(), 1);
+        assert_eq!(saved[0]["keyword"], "rs");
+
+        assert!(plugin.delete_engine("g").is_err());
+        assert_eq!(plugin.delete_engine("RS").unwrap(), serde_json::json!([]));
+        assert!(plugin.engines().iter().all(|e| !e.custom));
+    }
+
+    #[tokio::test]
+    async fn test_keyword_suggestions() {
+        let plugin = WebSearchPlugin::new();
+        let results = plugin.query(&ctx("g")).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["keyword:g", "keyword:gh"]);
+
+        let outcome = plugin.execute("keyword:gh", "use_keyword").await.unwrap();
+        assert!(matches!(outcome, ExecuteOutcome::ReplaceQuery { query } if query == "gh "));
+
+        assert!(plugin.query(&ctx("notepad")).await.unwrap().is_empty());
+        assert!(plugin.query(&ctx("? ")).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_keyword_avoids_conflicts() {
+        let plugin = WebSearchPlugin::new();
+        assert_eq!(plugin.suggest_keyword("MDN Web Docs"), "mdn");
+        assert_eq!(plugin.suggest_keyword("GitHub Code"), "github");
+        assert_eq!(plugin.suggest_keyword("G"), "g2");
+        assert_eq!(plugin.suggest_keyword("***"), "search");
     }
 }
@@ -2,7 +2,7 @@
 // 功能：切换窗口、最小化全部、显示桌面、窗口置顶等
 
 use crate::plugin::Plugin;
-use crate::core::types::{PluginMetadata, QueryContext, QueryResult, Action, WoxImage};
+use crate::core::types::{PluginMetadata, QueryContext, QueryResult, Action, WoxImage, ExecuteOutcome};
 use anyhow::Result;
 use sysinfo::System;
 
@@ -51,6 +51,8 @@ impl WindowManagerPlugin {
                 settings: vec![],
                 supported_os: vec!["windows".to_string(), "macos".to_string(), "linux".to_string()],
                 plugin_type: crate::core::types::PluginType::Native,
+                query_policy: crate::core::types::QueryPolicy::default(),
+                branding: crate::core::types::PluginBranding::new(crate::core::types::PluginCategory::System).with_tags(&["windows"]),
             },
         }
     }
@@ -338,7 +340,7 @@ impl Plugin for WindowManagerPlugin {
         Ok(results)
     }
 
-    async fn execute(&self, result_id: &str, action_id: &str) -> Result<()> {
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
         let hwnd = if result_id.starts_with("window_") {
             // 从 result_id 中提取 hwnd
             result_id
@@ -349,6 +351,6 @@ impl Plugin for WindowManagerPlugin {
         };
 
         self.execute_window_action(action_id, hwnd).await?;
-        Ok(())
+        Ok(ExecuteOutcome::Hide)
     }
 }
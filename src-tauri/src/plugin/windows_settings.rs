@@ -0,0 +1,271 @@
+// Windows 设置页插件 - 按名称打开 ms-settings: 设置页和经典控制面板项
+// 静态目录 + 模糊匹配（名称、中文名、拼音、关键词）
+
+use crate::core::types::*;
+use crate::plugin::Plugin;
+use anyhow::Result;
+use async_trait::async_trait;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+/// 打开方式
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// ms-settings: URI
+    Settings(&'static str),
+    /// 控制面板项 / 系统工具（程序 + 参数）
+    Command(&'static str, &'static [&'static str]),
+}
+
+/// 设置页条目
+struct SettingsPage {
+    id: &'static str,
+    name: &'static str,
+    name_zh: &'static str,
+    /// 英文别名、拼音等额外关键词
+    keywords: &'static [&'static str],
+    icon: &'static str,
+    target: Target,
+}
+
+const fn page(
+    id: &'static str,
+    name: &'static str,
+    name_zh: &'static str,
+    keywords: &'static [&'static str],
+    icon: &'static str,
+    target: Target,
+) -> SettingsPage {
+    SettingsPage { id, name, name_zh, keywords, icon, target }
+}
+
+use Target::{Command, Settings};
+
+/// 设置页目录
+const CATALOG: &[SettingsPage] = &[
+    // ===== ms-settings: 设置页 =====
+    page("display", "Display", "显示", &["screen", "resolution", "分辨率", "xianshi"], "🖥️", Settings("ms-settings:display")),
+    page("night_light", "Night light", "夜间模式", &["blue light", "护眼", "yejian"], "🌙", Settings("ms-settings:nightlight")),
+    page("sound", "Sound", "声音", &["audio", "volume", "speaker", "音量", "shengyin"], "🔊", Settings("ms-settings:sound")),
+    page("notifications", "Notifications", "通知", &["focus", "tongzhi"], "🔔", Settings("ms-settings:notifications")),
+    page("power", "Power & battery", "电源和电池", &["battery", "sleep", "电池", "dianyuan"], "🔋", Settings("ms-settings:powersleep")),
+    page("storage", "Storage", "存储", &["disk", "cleanup", "磁盘", "cunchu"], "💽", Settings("ms-settings:storagesense")),
+    page("multitasking", "Multitasking", "多任务处理", &["snap", "virtual desktop", "duorenwu"], "🗂️", Settings("ms-settings:multitasking")),
+    page("clipboard", "Clipboard settings", "剪贴板设置", &["clipboard history", "jiantieban"], "📋", Settings("ms-settings:clipboard")),
+    page("about", "About", "关于", &["system info", "pc name", "系统信息", "guanyu"], "ℹ️", Settings("ms-settings:about")),
+    page("bluetooth", "Bluetooth & devices", "蓝牙和其他设备", &["bluetooth", "devices", "蓝牙", "lanya"], "🔵", Settings("ms-settings:bluetooth")),
+    page("printers", "Printers & scanners", "打印机和扫描仪", &["printer", "打印机", "dayinji"], "🖨️", Settings("ms-settings:printers")),
+    page("mouse", "Mouse", "鼠标", &["cursor", "pointer", "shubiao"], "🖱️", Settings("ms-settings:mousetouchpad")),
+    page("touchpad", "Touchpad", "触摸板", &["trackpad", "chumoban"], "👆", Settings("ms-settings:devices-touchpad")),
+    page("autoplay", "AutoPlay", "自动播放", &["usb", "zidongbofang"], "▶️", Settings("ms-settings:autoplay")),
+    page("wifi", "Wi-Fi", "WLAN", &["wireless", "wlan", "无线", "wuxian"], "📶", Settings("ms-settings:network-wifi")),
+    page("network", "Network & internet", "网络和 Internet", &["ethernet", "网络", "wangluo"], "🌐", Settings("ms-settings:network-status")),
+    page("vpn", "VPN", "VPN", &["vpn"], "🔐", Settings("ms-settings:network-vpn")),
+    page("proxy", "Proxy", "代理", &["proxy", "daili"], "🧭", Settings("ms-settings:network-proxy")),
+    page("personalization", "Personalization", "个性化", &["theme", "主题", "gexinghua"], "🎨", Settings("ms-settings:personalization")),
+    page("background", "Background", "背景", &["wallpaper", "壁纸", "bizhi"], "🖼️", Settings("ms-settings:personalization-background")),
+    page("colors", "Colors", "颜色", &["dark mode", "accent", "深色模式", "yanse"], "🌈", Settings("ms-settings:colors")),
+    page("taskbar", "Taskbar", "任务栏", &["renwulan"], "📌", Settings("ms-settings:taskbar")),
+    page("fonts", "Fonts", "字体", &["font", "ziti"], "🔤", Settings("ms-settings:fonts")),
+    page("apps", "Installed apps", "已安装的应用", &["uninstall", "programs", "卸载", "yingyong"], "📦", Settings("ms-settings:appsfeatures")),
+    page("default_apps", "Default apps", "默认应用", &["default browser", "默认浏览器", "moren"], "⭐", Settings("ms-settings:defaultapps")),
+    page("startup_apps", "Startup apps", "启动应用", &["autostart", "开机启动", "qidong"], "🚀", Settings("ms-settings:startupapps")),
+    page("accounts", "Your info", "账户信息", &["account", "账户", "zhanghu"], "👤", Settings("ms-settings:yourinfo")),
+    page("sign_in", "Sign-in options", "登录选项", &["password", "pin", "windows hello", "密码", "denglu"], "🔑", Settings("ms-settings:signinoptions")),
+    page("date_time", "Date & time", "日期和时间", &["clock", "timezone", "时区", "riqi"], "🕐", Settings("ms-settings:dateandtime")),
+    page("language", "Language & region", "语言和区域", &["region", "input", "输入法", "yuyan"], "🌏", Settings("ms-settings:regionlanguage")),
+    page("gaming", "Game Mode", "游戏模式", &["game", "youxi"], "🎮", Settings("ms-settings:gaming-gamemode")),
+    page("accessibility", "Accessibility", "辅助功能", &["ease of access", "fuzhu"], "♿", Settings("ms-settings:easeofaccess")),
+    page("privacy", "Privacy & security", "隐私和安全性", &["permissions", "隐私", "yinsi"], "🛡️", Settings("ms-settings:privacy")),
+    page("windows_update", "Windows Update", "Windows 更新", &["update", "更新", "gengxin"], "🔄", Settings("ms-settings:windowsupdate")),
+    page("windows_security", "Windows Security", "Windows 安全中心", &["defender", "antivirus", "杀毒", "anquan"], "🛡️", Settings("ms-settings:windowsdefender")),
+    page("recovery", "Recovery", "恢复", &["reset", "重置", "huifu"], "♻️", Settings("ms-settings:recovery")),
+    page("developers", "For developers", "开发者选项", &["developer mode", "开发者模式", "kaifazhe"], "🧑‍💻", Settings("ms-settings:developers")),
+    // ===== 经典控制面板 / 系统工具 =====
+    page("control_panel", "Control Panel", "控制面板", &["control", "kongzhimianban"], "🗃️", Command("control.exe", &[])),
+    page("environment_variables", "Environment Variables", "环境变量", &["env", "path", "huanjingbianliang"], "🧬", Command("rundll32.exe", &["sysdm.cpl,EditEnvironmentVariables"])),
+    page("system_properties", "System Properties", "系统属性", &["advanced system settings", "高级系统设置", "xitongshuxing"], "💻", Command("SystemPropertiesAdvanced.exe", &[])),
+    page("programs_features", "Programs and Features", "程序和功能", &["appwiz", "uninstall", "chengxu"], "📦", Command("control.exe", &["appwiz.cpl"])),
+    page("network_connections", "Network Connections", "网络连接", &["adapter", "ncpa", "网卡", "wangka"], "🔌", Command("control.exe", &["ncpa.cpl"])),
+    page("sound_panel", "Sound Control Panel", "声音控制面板", &["playback devices", "mmsys", "播放设备"], "🎚️", Command("control.exe", &["mmsys.cpl"])),
+    page("power_options", "Power Options", "电源选项", &["power plan", "powercfg", "电源计划"], "⚡", Command("control.exe", &["powercfg.cpl"])),
+    page("device_manager", "Device Manager", "设备管理器", &["drivers", "devmgmt", "驱动", "shebeiguanliqi"], "🧰", Command("mmc.exe", &["devmgmt.msc"])),
+    page("disk_management", "Disk Management", "磁盘管理", &["partition", "diskmgmt", "分区", "cipanguanli"], "💾", Command("mmc.exe", &["diskmgmt.msc"])),
+    page("services", "Services", "服务", &["services.msc", "fuwu"], "⚙️", Command("mmc.exe", &["services.msc"])),
+    page("event_viewer", "Event Viewer", "事件查看器", &["logs", "eventvwr", "日志", "shijian"], "📜", Command("mmc.exe", &["eventvwr.msc"])),
+    page("task_scheduler", "Task Scheduler", "任务计划程序", &["taskschd", "计划任务", "jihua"], "🗓️", Command("mmc.exe", &["taskschd.msc"])),
+    page("computer_management", "Computer Management", "计算机管理", &["compmgmt", "jisuanjiguanli"], "🖥️", Command("mmc.exe", &["compmgmt.msc"])),
+    page("firewall", "Windows Firewall", "防火墙", &["firewall.cpl", "fanghuoqiang"], "🧱", Command("control.exe", &["firewall.cpl"])),
+    page("user_accounts", "User Accounts", "用户账户", &["netplwiz", "yonghu"], "👥", Command("netplwiz.exe", &[])),
+    page("registry_editor", "Registry Editor", "注册表编辑器", &["regedit", "注册表", "zhucebiao"], "🗝️", Command("regedit.exe", &[])),
+    page("task_manager", "Task Manager", "任务管理器", &["taskmgr", "renwuguanliqi"], "📊", Command("taskmgr.exe", &[])),
+    page("internet_options", "Internet Options", "Internet 选项", &["inetcpl", "ie"], "🌐", Command("control.exe", &["inetcpl.cpl"])),
+    page("mouse_panel", "Mouse Properties", "鼠标属性", &["main.cpl", "pointer speed"], "🖱️", Command("control.exe", &["main.cpl"])),
+    page("credential_manager", "Credential Manager", "凭据管理器", &["passwords", "凭据", "pingju"], "🔏", Command("control.exe", &["/name", "Microsoft.CredentialManager"])),
+];
+
+/// 全局搜索时的最高分（低于应用搜索等精确结果）
+const MAX_GLOBAL_SCORE: i64 = 700;
+/// 名称或关键词完全一致时的分数
+const EXACT_SCORE: i64 = 850;
+
+pub struct WindowsSettingsPlugin {
+    metadata: PluginMetadata,
+    matcher: SkimMatcherV2,
+}
+
+impl WindowsSettingsPlugin {
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata {
+                id: "windows_settings".to_string(),
+                name: "Windows 设置".to_string(),
+                description: "打开 Windows 设置页和控制面板项".to_string(),
+                author: "iLauncher".to_string(),
+                version: "1.0.0".to_string(),
+                icon: WoxImage::emoji("🪟"),
+                trigger_keywords: vec!["ws".to_string()],
+                commands: vec![],
+                settings: vec![],
+                supported_os: vec!["windows".to_string()],
+                plugin_type: PluginType::Native,
+                query_policy: QueryPolicy::default(),
+                branding: PluginBranding::new(PluginCategory::System).with_tags(&["settings"]),
+            },
+            matcher: SkimMatcherV2::default(),
+        }
+    }
+
+    /// 匹配分数（None 表示不匹配）
+    fn score(&self, page: &SettingsPage, query: &str) -> Option<i64> {
+        let candidates = [page.name, page.name_zh].into_iter().chain(page.keywords.iter().copied());
+
+        let mut best: Option<i64> = None;
+        for candidate in candidates {
+            if candidate.eq_ignore_ascii_case(query) {
+                return Some(EXACT_SCORE);
+            }
+            if let Some(score) = self.matcher.fuzzy_match(candidate, query) {
+                best = Some(best.map_or(score, |b| b.max(score)));
+            }
+        }
+        best.map(|score| score.min(MAX_GLOBAL_SCORE))
+    }
+
+    fn open(page: &SettingsPage) -> Result<()> {
+        tracing::info!("Opening Windows settings page: {}", page.id);
+        let mut command = match page.target {
+            // ms-settings: URI 交给 explorer 打开
+            Settings(uri) => {
+                let mut command = std::process::Command::new("explorer");
+                command.arg(uri);
+                command
+            }
+            Command(program, args) => {
+                let mut command = std::process::Command::new(program);
+                command.args(args);
+                command
+            }
+        };
+        command.spawn()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Plugin for WindowsSettingsPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn query(&self, ctx: &QueryContext) -> Result<Vec<QueryResult>> {
+        let routed = !ctx.trigger_keyword.is_empty();
+        let query = ctx.search.trim();
+
+        // 全局搜索时至少 2 个字符，避免单字母匹配大量设置页
+        if !routed && query.chars().count() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let mut results: Vec<QueryResult> = CATALOG
+            .iter()
+            .filter_map(|page| {
+                let score = if query.is_empty() { 0 } else { self.score(page, query)? };
+                Some(QueryResult {
+                    id: page.id.to_string(),
+                    title: format!("{} / {}", page.name_zh, page.name),
+                    subtitle: match page.target {
+                        Settings(uri) => uri.to_string(),
+                        Command(program, args) => format!("{} {}", program, args.join(" ")).trim().to_string(),
+                    },
+                    icon: WoxImage::emoji(page.icon),
+                    score: score as i32,
+                    plugin_id: self.metadata.id.clone(),
+                    context_data: serde_json::Value::Null,
+                    actions: vec![Action {
+                        id: "open".to_string(),
+                        name: "打开".to_string(),
+                        icon: None,
+                        is_default: true,
+                        hotkey: None,
+                        prevent_hide: false,
+                    }],
+                    preview: None,
+                    refreshable: false,
+                    group: Some("Windows 设置".to_string()),
+                    accessibility: None,
+                    highlights: None,
+                    badges: Vec::new(),
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.cmp(&a.score));
+        results.truncate(10);
+        Ok(results)
+    }
+
+    async fn execute(&self, result_id: &str, action_id: &str) -> Result<ExecuteOutcome> {
+        if action_id != "open" {
+            return Err(anyhow::anyhow!("Unknown action: {}", action_id));
+        }
+
+        let page = CATALOG
+            .iter()
+            .find(|page| page.id == result_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown settings page: {}", result_id))?;
+        Self::open(page)?;
+        Ok(ExecuteOutcome::Hide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_match(plugin: &WindowsSettingsPlugin, query: &str) -> Option<&'static str> {
+        CATALOG
+            .iter()
+            .filter_map(|page| plugin.score(page, query).map(|score| (score, page.id)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, id)| id)
+    }
+
+    #[test]
+    fn test_catalog_ids_unique() {
+        let mut ids: Vec<_> = CATALOG.iter().map(|page| page.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), CATALOG.len());
+    }
+
+    #[test]
+    fn test_match_by_name_keyword_and_pinyin() {
+        let plugin = WindowsSettingsPlugin::new();
+        assert_eq!(top_match(&plugin, "bluetooth"), Some("bluetooth"));
+        assert_eq!(top_match(&plugin, "环境变量"), Some("environment_variables"));
+        assert_eq!(top_match(&plugin, "sound"), Some("sound"));
+        assert_eq!(top_match(&plugin, "lanya"), Some("bluetooth"));
+        assert!(top_match(&plugin, "zzzzqqq").is_none());
+    }
+}
@@ -1,4 +1,6 @@
 // 工作流引擎 - 自动化任务编排系统
+use crate::http::RequestBuilderExt;
+use crate::session_context::{ActiveTimer, SESSION_CONTEXT};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -365,9 +367,7 @@ impl WorkflowEngine {
             }
             WorkflowAction::CopyToClipboard { content } => {
                 let resolved_content = context.resolve_string(content);
-                use arboard::Clipboard;
-                let mut clipboard = Clipboard::new()?;
-                clipboard.set_text(&resolved_content)?;
+                crate::clipboard::write_text(&resolved_content)?;
             }
             WorkflowAction::ShowNotification { title, message } => {
                 let resolved_title = context.resolve_string(title);
@@ -376,7 +376,7 @@ impl WorkflowEngine {
                 // TODO: 实现系统通知
             }
             WorkflowAction::HttpRequest { method, url, headers, body } => {
-                let client = reqwest::Client::new();
+                let client = crate::http::client("workflow")?;
                 let resolved_url = context.resolve_string(url);
                 let mut request = match method.as_str() {
                     "GET" => client.get(&resolved_url),
@@ -394,7 +394,11 @@ impl WorkflowEngine {
                     request = request.body(context.resolve_string(body_content));
                 }
 
-                let response = request.send().await?;
+                // 只对幂等请求重试，避免重复提交
+                let response = match method.as_str() {
+                    "GET" | "PUT" | "DELETE" => request.send_with_retry().await?,
+                    _ => request.send().await?,
+                };
                 let status = response.status().as_u16();
                 let body = response.text().await?;
 
@@ -408,7 +412,18 @@ impl WorkflowEngine {
                 context.set_variable(name.clone(), serde_json::json!(resolved_value));
             }
             WorkflowAction::Delay { milliseconds } => {
+                // 等待期间显示在底部状态栏的计时器中（被取消时由快照按到期时间清理）
+                let started_at = chrono::Utc::now();
+                let timer = ActiveTimer {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    label: step.name.clone(),
+                    started_at,
+                    ends_at: started_at + chrono::Duration::milliseconds(*milliseconds as i64),
+                };
+                let timer_id = timer.id.clone();
+                SESSION_CONTEXT.add_timer(timer);
                 tokio::time::sleep(tokio::time::Duration::from_millis(*milliseconds)).await;
+                SESSION_CONTEXT.remove_timer(&timer_id);
             }
             WorkflowAction::If { condition, then_steps, else_steps } => {
                 if self.evaluate_condition(condition, context).await? {
@@ -0,0 +1,492 @@
+// Monorepo 工作区成员识别 - Cargo workspace、pnpm / yarn / npm workspaces、go.work
+// 展开成员通配符（每段支持 *，** 匹配任意层级），读取成员名称和可在终端运行的开发/测试命令
+
+use crate::utils::wildcard;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 工作区根目录的清单文件（修改时间作为缓存校验）
+const ROOT_MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "pnpm-workspace.yaml", "go.work"];
+
+/// ** 通配符最多展开的目录层级
+const MAX_GLOB_DEPTH: usize = 4;
+
+/// 展开通配符时跳过的目录
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+/// 查找包管理器锁文件时最多向上的层级
+const MAX_LOCKFILE_DEPTH: usize = 6;
+
+/// 工作区类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    Cargo,
+    Npm,
+    Go,
+}
+
+impl WorkspaceKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo",
+            Self::Npm => "npm",
+            Self::Go => "Go",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Cargo => "🦀",
+            Self::Npm => "📦",
+            Self::Go => "🐹",
+        }
+    }
+
+    fn manifest(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm => "package.json",
+            Self::Go => "go.mod",
+        }
+    }
+}
+
+/// 工作区成员（子包）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: WorkspaceKind,
+    pub commands: MemberCommands,
+}
+
+/// 成员可在终端运行的命令（在成员目录下执行）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemberCommands {
+    pub dev: Option<String>,
+    pub test: Option<String>,
+}
+
+/// 识别项目根目录下的全部工作区成员（不包含根目录自身）
+pub fn detect(root: &Path) -> Vec<WorkspaceMember> {
+    let mut members = Vec::new();
+    for (kind, paths) in [
+        (WorkspaceKind::Cargo, cargo_member_paths(root)),
+        (WorkspaceKind::Npm, npm_member_paths(root)),
+        (WorkspaceKind::Go, go_member_paths(root)),
+    ] {
+        for path in paths {
+            if path == root || members.iter().any(|m: &WorkspaceMember| m.path == path && m.kind == kind) {
+                continue;
+            }
+            let name = member_name(&path, kind).unwrap_or_else(|| dir_name(&path));
+            let commands = member_commands(&path, kind);
+            members.push(WorkspaceMember { name, path, kind, commands });
+        }
+    }
+    members
+}
+
+/// 成员目录的开发/测试命令（按目录中的清单文件判断类型，执行动作时重新读取）
+pub fn commands(path: &Path) -> MemberCommands {
+    [WorkspaceKind::Npm, WorkspaceKind::Cargo, WorkspaceKind::Go]
+        .into_iter()
+        .find(|kind| path.join(kind.manifest()).is_file())
+        .map(|kind| member_commands(path, kind))
+        .unwrap_or_default()
+}
+
+/// 按项目缓存的成员列表（根目录清单文件修改后重新识别）
+#[derive(Default)]
+pub struct MemberCache {
+    entries: Mutex<HashMap<PathBuf, (Vec<Option<SystemTime>>, Vec<WorkspaceMember>)>>,
+}
+
+impl MemberCache {
+    pub fn get(&self, root: &Path) -> Vec<WorkspaceMember> {
+        let stamp: Vec<Option<SystemTime>> = ROOT_MANIFESTS
+            .iter()
+            .map(|name| std::fs::metadata(root.join(name)).and_then(|m| m.modified()).ok())
+            .collect();
+
+        if let Some((cached_stamp, members)) = self.entries.lock().get(root) {
+            if *cached_stamp == stamp {
+                return members.clone();
+            }
+        }
+
+        let members = if stamp.iter().any(Option::is_some) { detect(root) } else { Vec::new() };
+        self.entries.lock().insert(root.to_path_buf(), (stamp, members.clone()));
+        members
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn toml_strings(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+fn json_strings(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Cargo.toml [workspace] members / exclude
+fn cargo_member_paths(root: &Path) -> Vec<PathBuf> {
+    let Some(workspace) = read_toml(&root.join("Cargo.toml")).and_then(|t| t.get("workspace").cloned()) else {
+        return Vec::new();
+    };
+    let patterns = toml_strings(workspace.get("members"));
+    let exclude = toml_strings(workspace.get("exclude"));
+    expand_patterns(root, &patterns, &exclude, WorkspaceKind::Cargo)
+}
+
+/// package.json workspaces（数组或 { packages: [...] }）与 pnpm-workspace.yaml packages
+fn npm_member_paths(root: &Path) -> Vec<PathBuf> {
+    let mut patterns = Vec::new();
+    if let Some(package) = read_json(&root.join("package.json")) {
+        let workspaces = package.get("workspaces");
+        patterns.extend(json_strings(workspaces));
+        patterns.extend(json_strings(workspaces.and_then(|w| w.get("packages"))));
+    }
+    if let Ok(yaml) = std::fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+        patterns.extend(pnpm_packages(&yaml));
+    }
+    expand_patterns(root, &patterns, &[], WorkspaceKind::Npm)
+}
+
+/// go.work 中的 use 指令（单行或 use ( ... ) 块）
+fn go_member_paths(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = std::fs::read_to_string(root.join("go.work")) else {
+        return Vec::new();
+    };
+    let patterns = go_work_uses(&content);
+    expand_patterns(root, &patterns, &[], WorkspaceKind::Go)
+}
+
+/// 解析 pnpm-workspace.yaml 的 packages 列表（只需要这一个顶层键，不引入完整 YAML 解析）
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let content = line.split(" #").next().unwrap_or_default().trim_end();
+        if content.trim().is_empty() || content.trim_start().starts_with('#') {
+            continue;
+        }
+        if !content.starts_with(char::is_whitespace) && !content.starts_with('-') {
+            in_packages = content.trim() == "packages:";
+            continue;
+        }
+        if let Some(item) = content.trim().strip_prefix('-').filter(|_| in_packages) {
+            packages.push(unquote(item.trim()).to_string());
+        }
+    }
+    packages
+}
+
+fn go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                uses.push(unquote(line).to_string());
+            }
+        } else if let Some(rest) = line
+            .strip_prefix("use")
+            .filter(|rest| rest.starts_with(|c: char| c.is_whitespace() || c == '('))
+        {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                uses.push(unquote(rest).to_string());
+            }
+        }
+    }
+    uses
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'' || c == '`')
+}
+
+/// 展开成员模式（! 开头为排除模式），只保留包含对应清单文件的目录
+fn expand_patterns(root: &Path, patterns: &[String], exclude: &[String], kind: WorkspaceKind) -> Vec<PathBuf> {
+    let mut excluded: Vec<String> = exclude.iter().map(|p| normalize_pattern(p)).collect();
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) => excluded.push(normalize_pattern(negated)),
+            None => {
+                let pattern = normalize_pattern(pattern);
+                let segments: Vec<&str> = pattern.split('/').collect();
+                expand(root, &segments, 0, &mut paths);
+            }
+        }
+    }
+
+    paths.retain(|path| {
+        let relative = path
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        path.join(kind.manifest()).is_file() && !excluded.iter().any(|pattern| glob_matches(pattern, &relative))
+    });
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn normalize_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim().replace('\\', "/");
+    let pattern = pattern.strip_prefix("./").unwrap_or(&pattern);
+    pattern.trim_end_matches('/').to_string()
+}
+
+fn expand(dir: &Path, segments: &[&str], depth: usize, out: &mut Vec<PathBuf>) {
+    let Some((first, rest)) = segments.split_first() else {
+        out.push(dir.to_path_buf());
+        return;
+    };
+    match *first {
+        "" | "." => expand(dir, rest, depth, out),
+        "**" => {
+            expand(dir, rest, depth, out);
+            if depth < MAX_GLOB_DEPTH {
+                for child in child_dirs(dir) {
+                    expand(&child, segments, depth + 1, out);
+                }
+            }
+        }
+        segment if segment.contains('*') => {
+            for child in child_dirs(dir) {
+                if wildcard::matches(segment, &dir_name(&child)) {
+                    expand(&child, rest, depth, out);
+                }
+            }
+        }
+        segment => {
+            let child = dir.join(segment);
+            if child.is_dir() {
+                expand(&child, rest, depth, out);
+            }
+        }
+    }
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = dir_name(path);
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .collect()
+}
+
+/// 按路径段匹配（每段支持 *，** 匹配任意层级）
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+            Some((segment, rest)) => path
+                .split_first()
+                .is_some_and(|(name, path_rest)| wildcard::matches(segment, name) && match_segments(rest, path_rest)),
+        }
+    }
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match_segments(&pattern, &path)
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// 成员名称：Cargo 包名 / package.json name / go.mod module
+fn member_name(path: &Path, kind: WorkspaceKind) -> Option<String> {
+    let manifest = path.join(kind.manifest());
+    let name = match kind {
+        WorkspaceKind::Cargo => read_toml(&manifest)?
+            .get("package")?
+            .get("name")?
+            .as_str()
+            .map(str::to_string),
+        WorkspaceKind::Npm => read_json(&manifest)?.get("name")?.as_str().map(str::to_string),
+        WorkspaceKind::Go => std::fs::read_to_string(&manifest)
+            .ok()?
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("module "))
+            .map(|module| unquote(module.trim()).to_string()),
+    };
+    name.filter(|name| !name.is_empty())
+}
+
+fn member_commands(path: &Path, kind: WorkspaceKind) -> MemberCommands {
+    match kind {
+        WorkspaceKind::Cargo => {
+            let runnable = path.join("src").join("main.rs").is_file() || path.join("src").join("bin").is_dir();
+            MemberCommands {
+                dev: runnable.then(|| "cargo run".to_string()),
+                test: Some("cargo test".to_string()),
+            }
+        }
+        WorkspaceKind::Npm => {
+            let scripts = read_json(&path.join("package.json"))
+                .and_then(|p| p.get("scripts").cloned())
+                .unwrap_or_default();
+            let manager = package_manager(path);
+            let script = |name: &str| scripts.get(name).map(|_| format!("{} run {}", manager, name));
+            MemberCommands {
+                dev: script("dev").or_else(|| script("start")),
+                test: script("test"),
+            }
+        }
+        WorkspaceKind::Go => MemberCommands {
+            dev: path.join("main.go").is_file().then(|| "go run .".to_string()),
+            test: Some("go test ./...".to_string()),
+        },
+    }
+}
+
+/// 按锁文件判断包管理器（从成员目录向上查找）
+fn package_manager(path: &Path) -> &'static str {
+    for dir in path.ancestors().take(MAX_LOCKFILE_DEPTH) {
+        if dir.join("pnpm-lock.yaml").is_file() || dir.join("pnpm-workspace.yaml").is_file() {
+            return "pnpm";
+        }
+        if dir.join("yarn.lock").is_file() {
+            return "yarn";
+        }
+        if dir.join("bun.lockb").is_file() || dir.join("bun.lock").is_file() {
+            return "bun";
+        }
+        if dir.join("package-lock.json").is_file() || dir.join(".git").exists() {
+            break;
+        }
+    }
+    "npm"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ilauncher_workspace_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_cargo_workspace() {
+        let root = temp_root();
+        write(&root, "Cargo.toml", "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n");
+        write(&root, "crates/core/Cargo.toml", "[package]\nname = \"acme-core\"\n");
+        write(&root, "crates/old/Cargo.toml", "[package]\nname = \"acme-old\"\n");
+        write(&root, "crates/notes/README.md", "not a crate");
+        write(&root, "tools/cli/Cargo.toml", "[package]\nname = \"acme-cli\"\n");
+        write(&root, "tools/cli/src/main.rs", "fn main() {}");
+
+        let members = detect(&root);
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["acme-core", "acme-cli"]);
+        assert_eq!(members[0].commands.dev, None);
+        assert_eq!(members[1].commands.dev.as_deref(), Some("cargo run"));
+        assert_eq!(members[1].commands.test.as_deref(), Some("cargo test"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_pnpm_and_npm_workspaces() {
+        let root = temp_root();
+        write(&root, "package.json", r#"{ "name": "acme", "workspaces": { "packages": ["apps/*"] } }"#);
+        write(&root, "pnpm-workspace.yaml", "packages:\n  - 'packages/**'\n  - \"!packages/**/test\" # fixtures\ncatalog:\n  - ignored\n");
+        write(&root, "apps/web/package.json", r#"{ "name": "@acme/web", "scripts": { "dev": "vite", "test": "vitest" } }"#);
+        write(&root, "packages/ui/package.json", r#"{ "name": "@acme/ui", "scripts": { "start": "storybook" } }"#);
+        write(&root, "packages/ui/test/package.json", r#"{ "name": "fixture" }"#);
+        write(&root, "packages/ui/node_modules/dep/package.json", r#"{ "name": "dep" }"#);
+
+        let members = detect(&root);
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["@acme/web", "@acme/ui"]);
+        assert_eq!(members[0].commands.dev.as_deref(), Some("pnpm run dev"));
+        assert_eq!(members[0].commands.test.as_deref(), Some("pnpm run test"));
+        assert_eq!(members[1].commands.dev.as_deref(), Some("pnpm run start"));
+        assert_eq!(members[1].commands.test, None);
+        assert_eq!(commands(&root.join("apps/web")), members[0].commands);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_go_work() {
+        assert_eq!(go_work_uses("go 1.22\n\nuse ./api\nuse (\n\t./cmd/cli // tool\n\t\"./lib\"\n)\n"), vec!["./api", "./cmd/cli", "./lib"]);
+
+        let root = temp_root();
+        write(&root, "go.work", "go 1.22\nuse (\n  ./api\n  ./missing\n)\n");
+        write(&root, "api/go.mod", "module github.com/acme/api\n\ngo 1.22\n");
+        write(&root, "api/main.go", "package main");
+
+        let members = detect(&root);
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name, "github.com/acme/api");
+        assert_eq!(members[0].kind, WorkspaceKind::Go);
+        assert_eq!(members[0].commands.dev.as_deref(), Some("go run ."));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("crates/*", "crates/core"));
+        assert!(!glob_matches("crates/*", "crates/core/sub"));
+        assert!(glob_matches("packages/**/test", "packages/ui/test"));
+        assert!(glob_matches("packages/**/test", "packages/test"));
+        assert!(!glob_matches("packages/**/test", "apps/test"));
+    }
+
+    #[test]
+    fn test_cache_refreshes_when_manifest_changes() {
+        let root = temp_root();
+        let cache = MemberCache::default();
+        assert!(cache.get(&root).is_empty());
+
+        write(&root, "package.json", r#"{ "workspaces": ["apps/*"] }"#);
+        write(&root, "apps/web/package.json", r#"{ "name": "web" }"#);
+        assert_eq!(cache.get(&root).len(), 1);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
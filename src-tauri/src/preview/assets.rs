@@ -0,0 +1,273 @@
+// 预览资源通道 - 图片/PDF 等二进制预览通过自定义协议 preview:// 提供，结果中只携带临时 URL
+// 资源按路径登记，请求时才读取文件（支持 Range 和 ETag 协商缓存）；
+// 大图片在首次请求时生成缩略图并写入缓存目录
+
+use crate::core::types::{Preview, PreviewAsset};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tauri::http::{header, Request, Response, StatusCode};
+
+/// 自定义协议名
+pub const PREVIEW_SCHEME: &str = "preview";
+
+/// 最多登记的资源数（超出后淘汰最早登记的）
+const MAX_ASSETS: usize = 1024;
+
+/// 超过该大小的图片生成缩略图
+#[cfg(target_os = "windows")]
+const THUMBNAIL_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+/// 缩略图最大边长
+#[cfg(target_os = "windows")]
+const THUMBNAIL_SIZE: u32 = 1024;
+
+/// 已登记的资源：ID → (文件路径, MIME)
+struct AssetRegistry {
+    entries: HashMap<String, (PathBuf, &'static str)>,
+    order: VecDeque<String>,
+}
+
+static ASSETS: Lazy<Mutex<AssetRegistry>> = Lazy::new(|| {
+    Mutex::new(AssetRegistry {
+        entries: HashMap::new(),
+        order: VecDeque::new(),
+    })
+});
+
+/// 支持二进制预览的文件类型
+pub fn mime_for(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        _ => return None,
+    })
+}
+
+/// 资源 URL（Windows WebView2 使用 http://<scheme>.localhost 形式）
+fn asset_url(id: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("http://{}.localhost/{}", PREVIEW_SCHEME, id)
+    } else {
+        format!("{}://localhost/{}", PREVIEW_SCHEME, id)
+    }
+}
+
+/// 登记文件预览资源（ID 由路径决定，重复登记返回同一 URL）
+pub fn register_file(path: &Path) -> Option<PreviewAsset> {
+    let mime = mime_for(path)?;
+    let id = format!("{:x}", md5::compute(path.to_string_lossy().as_bytes()));
+
+    let mut assets = ASSETS.lock();
+    if assets.entries.insert(id.clone(), (path.to_path_buf(), mime)).is_none() {
+        assets.order.push_back(id.clone());
+        while assets.order.len() > MAX_ASSETS {
+            if let Some(oldest) = assets.order.pop_front() {
+                assets.entries.remove(&oldest);
+            }
+        }
+    }
+
+    Some(PreviewAsset { url: asset_url(&id), mime: mime.to_string() })
+}
+
+/// 文件结果的二进制预览（目录和不支持的类型返回 None）
+pub fn file_preview(path: &str, is_dir: bool) -> Option<Preview> {
+    if is_dir {
+        return None;
+    }
+    register_file(Path::new(path)).map(Preview::Asset)
+}
+
+/// 处理 preview:// 协议请求
+pub fn handle(request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let id = request.uri().path().trim_start_matches('/').split('/').next().unwrap_or_default();
+    let Some((path, mime)) = ASSETS.lock().entries.get(id).cloned() else {
+        return status(StatusCode::NOT_FOUND);
+    };
+
+    match serve_file(request, &path, mime) {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!("Failed to serve preview asset {:?}: {}", path, e);
+            status(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+fn status(code: StatusCode) -> Response<Vec<u8>> {
+    Response::builder().status(code).body(Vec::new()).unwrap_or_default()
+}
+
+fn serve_file(request: &Request<Vec<u8>>, path: &Path, mime: &str) -> anyhow::Result<Response<Vec<u8>>> {
+    let metadata = std::fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    // 文件变化后 ETag 随之变化，前端重新加载
+    let etag = format!("\"{:x}-{:x}\"", metadata.len(), modified);
+
+    let builder = Response::builder()
+        .header(header::CACHE_CONTROL, "private, no-cache")
+        .header(header::ETAG, &etag)
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok());
+    if if_none_match == Some(etag.as_str()) {
+        return Ok(builder.status(StatusCode::NOT_MODIFIED).body(Vec::new())?);
+    }
+
+    #[cfg(target_os = "windows")]
+    if mime.starts_with("image/") && mime != "image/svg+xml" && metadata.len() > THUMBNAIL_THRESHOLD {
+        match thumbnail(path, &etag) {
+            Ok(data) => {
+                return Ok(builder
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "image/png")
+                    .body(data)?);
+            }
+            Err(e) => tracing::debug!("Thumbnail generation failed for {:?}: {}", path, e),
+        }
+    }
+
+    let len = metadata.len();
+    let builder = builder
+        .header(header::CONTENT_TYPE, mime)
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    // 🔥 Range 请求（PDF 查看器按需读取页面）
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, len));
+    match range {
+        Some(Some((start, end))) => {
+            let mut file = std::fs::File::open(path)?;
+            file.seek(SeekFrom::Start(start))?;
+            let mut data = vec![0; (end - start + 1) as usize];
+            file.read_exact(&mut data)?;
+            Ok(builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, len))
+                .body(data)?)
+        }
+        Some(None) => Ok(builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", len))
+            .body(Vec::new())?),
+        None => Ok(builder.status(StatusCode::OK).body(std::fs::read(path)?)?),
+    }
+}
+
+/// 解析单段 Range 头（bytes=start-end / bytes=start- / bytes=-suffix），返回闭区间
+fn parse_range(value: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') || len == 0 {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().ok()?;
+            (len.saturating_sub(suffix), len - 1)
+        }
+        (start, "") => (start.parse().ok()?, len - 1),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(len - 1)),
+    };
+    (start <= end && start < len).then_some((start, end))
+}
+
+/// 生成（或读取已缓存的）PNG 缩略图
+#[cfg(target_os = "windows")]
+fn thumbnail(path: &Path, etag: &str) -> anyhow::Result<Vec<u8>> {
+    let key = format!("{:x}", md5::compute(format!("{}{}", path.to_string_lossy(), etag)));
+    let cache_dir = crate::utils::paths::get_cache_dir()?.join("preview_assets");
+    let cache_path = cache_dir.join(format!("{}.png", key));
+    if let Ok(data) = std::fs::read(&cache_path) {
+        return Ok(data);
+    }
+
+    let image = image::open(path)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    let mut data = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut data, image::ImageFormat::Png)?;
+    let data = data.into_inner();
+
+    std::fs::create_dir_all(&cache_dir)?;
+    if let Err(e) = std::fs::write(&cache_path, &data) {
+        tracing::debug!("Failed to cache thumbnail {:?}: {}", cache_path, e);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, headers: &[(header::HeaderName, &str)]) -> Request<Vec<u8>> {
+        let mut builder = Request::builder().uri(url);
+        for (name, value) in headers {
+            builder = builder.header(name, *value);
+        }
+        builder.body(Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), Some((0, 9)));
+        assert_eq!(parse_range("bytes=90-", 100), Some((90, 99)));
+        assert_eq!(parse_range("bytes=-10", 100), Some((90, 99)));
+        assert_eq!(parse_range("bytes=50-500", 100), Some((50, 99)));
+        assert_eq!(parse_range("bytes=100-", 100), None);
+        assert_eq!(parse_range("bytes=9-0", 100), None);
+        assert_eq!(parse_range("bytes=0-1,5-6", 100), None);
+        assert_eq!(parse_range("items=0-1", 100), None);
+        assert_eq!(parse_range("bytes=0-0", 0), None);
+    }
+
+    #[test]
+    fn test_serve_registered_file() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_preview_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("doc.pdf");
+        std::fs::write(&path, b"%PDF-1.4 test").unwrap();
+
+        assert!(file_preview(&dir.to_string_lossy(), true).is_none());
+        assert!(register_file(&dir.join("notes.txt")).is_none());
+        let asset = register_file(&path).unwrap();
+        assert_eq!(asset.mime, "application/pdf");
+        assert_eq!(register_file(&path).unwrap(), asset);
+
+        let response = handle(&request(&asset.url, &[]));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), b"%PDF-1.4 test");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let response = handle(&request(&asset.url, &[(header::RANGE, "bytes=0-3")]));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.body(), b"%PDF");
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 0-3/13");
+
+        let response = handle(&request(&asset.url, &[(header::IF_NONE_MATCH, etag.as_str())]));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(response.body().is_empty());
+
+        // 文件删除后返回 404；未登记的 ID 同样返回 404
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(handle(&request(&asset.url, &[])).status(), StatusCode::NOT_FOUND);
+        assert_eq!(handle(&request("preview://localhost/unknown", &[])).status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,6 +1,8 @@
 // 文件预览模块
 
+pub mod assets;
 mod code_highlight;
+pub mod properties;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
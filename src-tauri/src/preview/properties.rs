@@ -0,0 +1,438 @@
+// 文件属性 - 预览面板“详细信息”页使用的元数据：时间戳、属性、所有者、
+// 可执行文件版本信息、数字签名状态和可选的 SHA-256；结果按路径缓存（文件大小或修改时间变化时重新计算）
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// 缓存条目上限（超出时清空）
+const CACHE_CAPACITY: usize = 256;
+
+/// 计算哈希的文件大小上限
+const MAX_HASH_SIZE: u64 = 1024 * 1024 * 1024; // 1GB
+
+/// 带版本信息和数字签名的文件类型
+const SIGNABLE_EXTENSIONS: &[&str] = &["exe", "dll", "sys", "msi", "ocx", "cpl", "scr", "efi"];
+
+/// 全局属性缓存
+static PROPERTIES_CACHE: Lazy<PropertiesCache> = Lazy::new(PropertiesCache::default);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileProperties {
+    pub path: String,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub accessed: Option<String>,
+    /// 文件属性（readonly / hidden / system / archive / compressed / encrypted ...）
+    pub attributes: Vec<String>,
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<VersionInfo>,
+    pub signature: SignatureStatus,
+    /// SHA-256（仅在请求时计算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// 可执行文件版本信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct VersionInfo {
+    pub file_version: Option<String>,
+    pub product_version: Option<String>,
+    pub product_name: Option<String>,
+    pub company_name: Option<String>,
+    pub file_description: Option<String>,
+    pub copyright: Option<String>,
+}
+
+/// 数字签名状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// 签名有效且受信任
+    Valid,
+    /// 有签名但校验失败（被篡改、证书不受信任或已过期等）
+    Invalid,
+    Unsigned,
+    /// 不是可签名的文件类型，或当前平台不支持校验
+    NotApplicable,
+}
+
+/// 缓存键对应的文件状态（大小 + 修改时间）
+type Stamp = (u64, Option<SystemTime>);
+
+#[derive(Default)]
+struct PropertiesCache {
+    entries: Mutex<HashMap<String, (Stamp, FileProperties)>>,
+}
+
+impl PropertiesCache {
+    fn get(&self, path: &str, stamp: &Stamp) -> Option<FileProperties> {
+        let entries = self.entries.lock();
+        let (cached_stamp, properties) = entries.get(path)?;
+        (cached_stamp == stamp).then(|| properties.clone())
+    }
+
+    fn insert(&self, path: &str, stamp: Stamp, properties: FileProperties) {
+        let mut entries = self.entries.lock();
+        if entries.len() >= CACHE_CAPACITY && !entries.contains_key(path) {
+            entries.clear();
+        }
+        entries.insert(path.to_string(), (stamp, properties));
+    }
+}
+
+/// 获取文件属性（阻塞，调用方应在 spawn_blocking 中执行）
+pub fn file_properties(path: &str, with_hash: bool) -> Result<FileProperties> {
+    let file = Path::new(path);
+    let metadata = std::fs::metadata(file)?;
+    let stamp = (metadata.len(), metadata.modified().ok());
+
+    if let Some(cached) = PROPERTIES_CACHE.get(path, &stamp) {
+        if !with_hash || cached.sha256.is_some() || metadata.is_dir() {
+            return Ok(cached);
+        }
+        // 已缓存的属性只缺哈希
+        let mut properties = cached;
+        properties.sha256 = hash_file(file, metadata.len())?;
+        PROPERTIES_CACHE.insert(path, stamp, properties.clone());
+        return Ok(properties);
+    }
+
+    let signable = is_signable(file);
+    let mut properties = FileProperties {
+        path: path.to_string(),
+        name: file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| path.to_string()),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+        created: metadata.created().ok().map(format_time),
+        modified: metadata.modified().ok().map(format_time),
+        accessed: metadata.accessed().ok().map(format_time),
+        attributes: attributes(file, &metadata),
+        owner: platform::owner(file),
+        version: if signable { platform::version_info(file) } else { None },
+        signature: if signable { platform::signature_status(file) } else { SignatureStatus::NotApplicable },
+        sha256: None,
+    };
+    if with_hash && !metadata.is_dir() {
+        properties.sha256 = hash_file(file, metadata.len())?;
+    }
+
+    PROPERTIES_CACHE.insert(path, stamp, properties.clone());
+    Ok(properties)
+}
+
+fn is_signable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| SIGNABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339()
+}
+
+/// SHA-256（超过大小上限时返回 None）
+fn hash_file(path: &Path, size: u64) -> Result<Option<String>> {
+    use sha2::{Digest, Sha256};
+
+    if size > MAX_HASH_SIZE {
+        return Ok(None);
+    }
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(Some(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()))
+}
+
+#[cfg(target_os = "windows")]
+fn attributes(_path: &Path, metadata: &std::fs::Metadata) -> Vec<String> {
+    use std::os::windows::fs::MetadataExt;
+
+    // FILE_ATTRIBUTE_* 标志位
+    const FLAGS: &[(u32, &str)] = &[
+        (0x1, "readonly"),
+        (0x2, "hidden"),
+        (0x4, "system"),
+        (0x20, "archive"),
+        (0x100, "temporary"),
+        (0x400, "reparse_point"),
+        (0x800, "compressed"),
+        (0x1000, "offline"),
+        (0x4000, "encrypted"),
+    ];
+    let bits = metadata.file_attributes();
+    FLAGS.iter().filter(|(flag, _)| bits & flag != 0).map(|(_, name)| name.to_string()).collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attributes(path: &Path, metadata: &std::fs::Metadata) -> Vec<String> {
+    let mut attributes = Vec::new();
+    if metadata.permissions().readonly() {
+        attributes.push("readonly".to_string());
+    }
+    if path.file_name().map(|n| n.to_string_lossy().starts_with('.')).unwrap_or(false) {
+        attributes.push("hidden".to_string());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        attributes.push(format!("mode {:o}", metadata.permissions().mode() & 0o7777));
+    }
+    attributes
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{SignatureStatus, VersionInfo};
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use windows::core::{PCWSTR, PWSTR};
+
+    fn wide(text: &std::ffi::OsStr) -> Vec<u16> {
+        text.encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    /// 所有者（DOMAIN\user）
+    pub fn owner(path: &Path) -> Option<String> {
+        use windows::Win32::Foundation::{LocalFree, ERROR_SUCCESS, HLOCAL};
+        use windows::Win32::Security::Authorization::{GetNamedSecurityInfoW, SE_FILE_OBJECT};
+        use windows::Win32::Security::{
+            LookupAccountSidW, OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID, SID_NAME_USE,
+        };
+
+        let path = wide(path.as_os_str());
+        unsafe {
+            let mut owner = PSID::default();
+            let mut descriptor = PSECURITY_DESCRIPTOR::default();
+            let status = GetNamedSecurityInfoW(
+                PCWSTR(path.as_ptr()),
+                SE_FILE_OBJECT,
+                OWNER_SECURITY_INFORMATION,
+                Some(&mut owner),
+                None,
+                None,
+                None,
+                &mut descriptor,
+            );
+            if status != ERROR_SUCCESS {
+                return None;
+            }
+
+            let mut name = vec![0u16; 256];
+            let mut domain = vec![0u16; 256];
+            let mut name_len = name.len() as u32;
+            let mut domain_len = domain.len() as u32;
+            let mut sid_use = SID_NAME_USE::default();
+            let looked_up = LookupAccountSidW(
+                PCWSTR::null(),
+                owner,
+                PWSTR(name.as_mut_ptr()),
+                &mut name_len,
+                PWSTR(domain.as_mut_ptr()),
+                &mut domain_len,
+                &mut sid_use,
+            );
+            let _ = LocalFree(HLOCAL(descriptor.0));
+            looked_up.ok()?;
+
+            let name = String::from_utf16_lossy(&name[..name_len as usize]);
+            let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+            Some(if domain.is_empty() { name } else { format!("{}\\{}", domain, name) })
+        }
+    }
+
+    /// 版本资源（VS_FIXEDFILEINFO + StringFileInfo）
+    pub fn version_info(path: &Path) -> Option<VersionInfo> {
+        use windows::Win32::Storage::FileSystem::{
+            GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+        };
+
+        let path = wide(path.as_os_str());
+        unsafe {
+            let size = GetFileVersionInfoSizeW(PCWSTR(path.as_ptr()), None);
+            if size == 0 {
+                return None;
+            }
+            let mut data = vec![0u8; size as usize];
+            GetFileVersionInfoW(PCWSTR(path.as_ptr()), 0, size, data.as_mut_ptr() as *mut c_void).ok()?;
+            let block = data.as_ptr() as *const c_void;
+
+            let query = |sub_block: &str| -> Option<(*const c_void, u32)> {
+                let sub_block = wide(std::ffi::OsStr::new(sub_block));
+                let mut buffer: *mut c_void = std::ptr::null_mut();
+                let mut len = 0u32;
+                VerQueryValueW(block, PCWSTR(sub_block.as_ptr()), &mut buffer, &mut len)
+                    .as_bool()
+                    .then_some((buffer as *const c_void, len))
+                    .filter(|(buffer, len)| !buffer.is_null() && *len > 0)
+            };
+
+            let mut info = VersionInfo::default();
+            if let Some((fixed, _)) = query("\\") {
+                let fixed = &*(fixed as *const VS_FIXEDFILEINFO);
+                let version = |ms: u32, ls: u32| format!("{}.{}.{}.{}", ms >> 16, ms & 0xFFFF, ls >> 16, ls & 0xFFFF);
+                info.file_version = Some(version(fixed.dwFileVersionMS, fixed.dwFileVersionLS));
+                info.product_version = Some(version(fixed.dwProductVersionMS, fixed.dwProductVersionLS));
+            }
+
+            // 取第一个语言/代码页的字符串表
+            let (lang, codepage) = match query("\\VarFileInfo\\Translation") {
+                Some((translation, len)) if len >= 4 => {
+                    let pair = translation as *const u16;
+                    (*pair, *pair.add(1))
+                }
+                _ => (0x0409, 0x04B0),
+            };
+            let string = |key: &str| -> Option<String> {
+                let (value, len) = query(&format!("\\StringFileInfo\\{:04x}{:04x}\\{}", lang, codepage, key))?;
+                let chars = std::slice::from_raw_parts(value as *const u16, len as usize);
+                let text = String::from_utf16_lossy(chars).trim_end_matches('\0').trim().to_string();
+                (!text.is_empty()).then_some(text)
+            };
+            info.product_name = string("ProductName");
+            info.company_name = string("CompanyName");
+            info.file_description = string("FileDescription");
+            info.copyright = string("LegalCopyright");
+            if let Some(version) = string("FileVersion") {
+                info.file_version = Some(version);
+            }
+
+            (info != VersionInfo::default()).then_some(info)
+        }
+    }
+
+    /// Authenticode 签名校验（不弹出界面，不检查吊销）
+    pub fn signature_status(path: &Path) -> SignatureStatus {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::Security::WinTrust::{
+            WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_DATA, WINTRUST_DATA_0, WINTRUST_FILE_INFO,
+            WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE, WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+        };
+
+        // 未签名相关的 HRESULT
+        const TRUST_E_NOSIGNATURE: i32 = 0x800B0100_u32 as i32;
+        const TRUST_E_SUBJECT_FORM_UNKNOWN: i32 = 0x800B0003_u32 as i32;
+        const TRUST_E_PROVIDER_UNKNOWN: i32 = 0x800B0001_u32 as i32;
+
+        let path = wide(path.as_os_str());
+        unsafe {
+            let mut file_info = WINTRUST_FILE_INFO {
+                cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+                pcwszFilePath: PCWSTR(path.as_ptr()),
+                ..Default::default()
+            };
+            let mut data = WINTRUST_DATA {
+                cbStruct: std::mem::size_of::<WINTRUST_DATA>() as u32,
+                dwUIChoice: WTD_UI_NONE,
+                fdwRevocationChecks: WTD_REVOKE_NONE,
+                dwUnionChoice: WTD_CHOICE_FILE,
+                Anonymous: WINTRUST_DATA_0 { pFile: &mut file_info },
+                dwStateAction: WTD_STATEACTION_VERIFY,
+                ..Default::default()
+            };
+            let mut action = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+
+            let status = WinVerifyTrust(HWND::default(), &mut action, &mut data as *mut _ as *mut c_void);
+            data.dwStateAction = WTD_STATEACTION_CLOSE;
+            let _ = WinVerifyTrust(HWND::default(), &mut action, &mut data as *mut _ as *mut c_void);
+
+            match status {
+                0 => SignatureStatus::Valid,
+                TRUST_E_NOSIGNATURE | TRUST_E_SUBJECT_FORM_UNKNOWN | TRUST_E_PROVIDER_UNKNOWN => SignatureStatus::Unsigned,
+                _ => SignatureStatus::Invalid,
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::{SignatureStatus, VersionInfo};
+    use std::path::Path;
+
+    /// 所有者（uid）
+    pub fn owner(path: &Path) -> Option<String> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            std::fs::metadata(path).ok().map(|m| format!("uid {}", m.uid()))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            None
+        }
+    }
+
+    pub fn version_info(_path: &Path) -> Option<VersionInfo> {
+        None
+    }
+
+    pub fn signature_status(_path: &Path) -> SignatureStatus {
+        SignatureStatus::NotApplicable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_properties_are_cached_and_hashed_on_demand() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_props_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("hello.txt");
+        std::fs::write(&file, "hello").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let properties = file_properties(&path, false).unwrap();
+        assert_eq!(properties.name, "hello.txt");
+        assert_eq!(properties.size, 5);
+        assert!(properties.modified.is_some());
+        assert!(properties.sha256.is_none());
+        assert_eq!(properties.signature, SignatureStatus::NotApplicable);
+
+        let hashed = file_properties(&path, true).unwrap();
+        assert_eq!(
+            hashed.sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        // 哈希写入缓存后，不请求哈希也会返回
+        assert!(file_properties(&path, false).unwrap().sha256.is_some());
+
+        // 内容变化后重新计算
+        std::fs::write(&file, "hello world").unwrap();
+        let changed = file_properties(&path, false).unwrap();
+        assert_eq!(changed.size, 11);
+        assert!(changed.sha256.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_signable_extensions() {
+        assert!(is_signable(Path::new("setup.EXE")));
+        assert!(is_signable(Path::new("lib.dll")));
+        assert!(!is_signable(Path::new("notes.txt")));
+        assert!(!is_signable(Path::new("Makefile")));
+    }
+}
@@ -0,0 +1,317 @@
+// 查询性能追踪 - 为每次查询分配 query_id，并可将单次查询的 span 导出为 Chrome Trace（chrome://tracing / Perfetto / speedscope）
+// TraceCaptureLayer 注册在全局 tracing subscriber 中；未开启捕获时只做一次原子读，几乎没有开销
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 全局追踪捕获器
+pub static TRACE_CAPTURE: Lazy<TraceCapture> = Lazy::new(TraceCapture::new);
+
+/// 根 span 名称（commands::query 中创建）
+pub const QUERY_SPAN: &str = "query";
+
+static NEXT_QUERY_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Chrome Trace 中的线程编号
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 分配查询 ID
+pub fn next_query_id() -> u64 {
+    NEXT_QUERY_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Chrome Trace 事件（"X" = 完整事件，时间单位微秒）
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEvent {
+    pub name: String,
+    pub cat: &'static str,
+    pub ph: &'static str,
+    pub ts: u64,
+    pub dur: u64,
+    pub pid: u32,
+    pub tid: u64,
+    pub args: serde_json::Map<String, serde_json::Value>,
+}
+
+/// 导出结果摘要
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedTrace {
+    pub query_id: u64,
+    pub path: PathBuf,
+    pub event_count: usize,
+    pub total_ms: f64,
+}
+
+/// 进行中的捕获
+struct ActiveCapture {
+    query_id: u64,
+    start: Instant,
+    events: Vec<TraceEvent>,
+}
+
+/// 单次查询追踪捕获器（同一时间只捕获一个查询）
+pub struct TraceCapture {
+    armed: AtomicBool,
+    active: Mutex<Option<ActiveCapture>>,
+}
+
+impl TraceCapture {
+    fn new() -> Self {
+        Self {
+            armed: AtomicBool::new(false),
+            active: Mutex::new(None),
+        }
+    }
+
+    /// 开始捕获指定查询（覆盖未完成的捕获）
+    pub fn arm(&self, query_id: u64) {
+        *self.active.lock() = Some(ActiveCapture {
+            query_id,
+            start: Instant::now(),
+            events: Vec::new(),
+        });
+        self.armed.store(true, Ordering::SeqCst);
+    }
+
+    /// 结束捕获并取出事件（按开始时间排序）
+    pub fn finish(&self, query_id: u64) -> Option<Vec<TraceEvent>> {
+        let mut active = self.active.lock();
+        if active.as_ref().map(|c| c.query_id) != Some(query_id) {
+            return None;
+        }
+        self.armed.store(false, Ordering::SeqCst);
+        let mut events = active.take()?.events;
+        events.sort_by_key(|e| e.ts);
+        Some(events)
+    }
+
+    fn is_armed(&self) -> bool {
+        self.armed.load(Ordering::Relaxed)
+    }
+
+    fn armed_query_id(&self) -> Option<u64> {
+        self.active.lock().as_ref().map(|c| c.query_id)
+    }
+
+    fn record(&self, name: &str, args: &serde_json::Map<String, serde_json::Value>, entered: Instant) {
+        let mut active = self.active.lock();
+        let Some(capture) = active.as_mut() else {
+            return;
+        };
+        let ts = entered.saturating_duration_since(capture.start).as_micros() as u64;
+        let dur = entered.elapsed().as_micros() as u64;
+        capture.events.push(TraceEvent {
+            name: name.to_string(),
+            cat: "ilauncher",
+            ph: "X",
+            ts,
+            dur,
+            pid: std::process::id(),
+            tid: THREAD_ID.with(|id| *id),
+            args: args.clone(),
+        });
+    }
+}
+
+/// 写入 Chrome Trace JSON 文件（logs/traces/query-<id>-<时间>.json）
+pub fn write_chrome_trace(query_id: u64, events: &[TraceEvent]) -> anyhow::Result<CapturedTrace> {
+    let dir = crate::utils::paths::get_log_dir()?.join("traces");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "query-{}-{}.json",
+        query_id,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    std::fs::write(&path, serde_json::to_vec_pretty(&chrome_trace_json(events))?)?;
+
+    let total_ms = events
+        .iter()
+        .find(|e| e.name == QUERY_SPAN)
+        .map(|e| e.dur as f64 / 1000.0)
+        .unwrap_or_default();
+    tracing::info!("🔥 Query trace written: {:?} ({} events)", path, events.len());
+
+    Ok(CapturedTrace {
+        query_id,
+        path,
+        event_count: events.len(),
+        total_ms,
+    })
+}
+
+/// Chrome Trace 文件内容
+fn chrome_trace_json(events: &[TraceEvent]) -> serde_json::Value {
+    serde_json::json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+        "otherData": {
+            "app": "iLauncher",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// 被捕获 span 的扩展数据
+struct CapturedSpan {
+    args: serde_json::Map<String, serde_json::Value>,
+    /// 进入时间栈（异步 span 可能被多次进入）
+    entered: Vec<Instant>,
+}
+
+/// span 字段收集
+struct FieldVisitor<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+/// 捕获层：记录属于被捕获查询（根 span 的 query_id 匹配）的所有子 span
+pub struct TraceCaptureLayer;
+
+impl<S> Layer<S> for TraceCaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !TRACE_CAPTURE.is_armed() {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut args = serde_json::Map::new();
+        attrs.record(&mut FieldVisitor(&mut args));
+
+        let captured = if attrs.metadata().name() == QUERY_SPAN {
+            let query_id = args.get("query_id").and_then(|v| v.as_u64());
+            query_id.is_some() && query_id == TRACE_CAPTURE.armed_query_id()
+        } else {
+            span.parent()
+                .map_or(false, |parent| parent.extensions().get::<CapturedSpan>().is_some())
+        };
+
+        if captured {
+            span.extensions_mut().insert(CapturedSpan {
+                args,
+                entered: Vec::new(),
+            });
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if !TRACE_CAPTURE.is_armed() {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            if let Some(captured) = span.extensions_mut().get_mut::<CapturedSpan>() {
+                values.record(&mut FieldVisitor(&mut captured.args));
+            }
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if !TRACE_CAPTURE.is_armed() {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            if let Some(captured) = span.extensions_mut().get_mut::<CapturedSpan>() {
+                captured.entered.push(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if !TRACE_CAPTURE.is_armed() {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            if let Some(captured) = span.extensions_mut().get_mut::<CapturedSpan>() {
+                if let Some(entered) = captured.entered.pop() {
+                    TRACE_CAPTURE.record(span.name(), &captured.args, entered);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_capture_only_armed_query() {
+        let subscriber = tracing_subscriber::registry().with(TraceCaptureLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let query_id = next_query_id();
+            TRACE_CAPTURE.arm(query_id);
+
+            tracing::info_span!("query", query_id = query_id + 1000).in_scope(|| {
+                tracing::info_span!("ranking").in_scope(|| {});
+            });
+            tracing::info_span!("query", query_id).in_scope(|| {
+                tracing::info_span!("plugin", plugin_id = "calculator").in_scope(|| {});
+                tracing::info_span!("ranking").in_scope(|| {});
+            });
+
+            let events = TRACE_CAPTURE.finish(query_id).unwrap();
+            let names: Vec<&str> = events.iter().map(|e| e.name.as_str()).collect();
+            assert_eq!(names.len(), 3);
+            assert!(names.contains(&"query"));
+            assert!(names.contains(&"ranking"));
+
+            let plugin = events.iter().find(|e| e.name == "plugin").unwrap();
+            assert_eq!(plugin.args["plugin_id"], "calculator");
+            assert!(TRACE_CAPTURE.finish(query_id).is_none());
+        });
+    }
+
+    #[test]
+    fn test_chrome_trace_shape() {
+        let event = TraceEvent {
+            name: "query".to_string(),
+            cat: "ilauncher",
+            ph: "X",
+            ts: 0,
+            dur: 1500,
+            pid: 1,
+            tid: 1,
+            args: serde_json::Map::new(),
+        };
+        let json = chrome_trace_json(&[event]);
+        assert_eq!(json["traceEvents"][0]["ph"], "X");
+        assert_eq!(json["traceEvents"][0]["dur"], 1500);
+    }
+}
@@ -0,0 +1,153 @@
+// 快速记录 - 独立热键呼出极简输入窗口（启动时预创建、隐藏），不打开搜索界面，
+// 文本直接追加到 Markdown 收件箱（带时间戳）或保存为文本片段
+
+use crate::storage::CaptureConfig;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 快速记录窗口标签（tauri.conf.json 中预创建）
+pub const WINDOW_LABEL: &str = "capture";
+
+/// 窗口显示后通知前端聚焦并清空输入框
+pub const SHOWN_EVENT: &str = "quick-capture-shown";
+
+/// 保存到 Markdown 收件箱
+pub const TARGET_INBOX: &str = "inbox";
+
+/// 保存为文本片段
+pub const TARGET_SNIPPET: &str = "snippet";
+
+/// 默认收件箱文件名（位于数据目录）
+const DEFAULT_INBOX_FILE: &str = "inbox.md";
+
+/// 片段名称最大长度（取首行）
+const SNIPPET_NAME_MAX_CHARS: usize = 40;
+
+/// 切换快速记录窗口（已显示时隐藏）
+pub fn toggle(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(WINDOW_LABEL) else {
+        tracing::warn!("Quick capture window not found");
+        return;
+    };
+
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+        return;
+    }
+
+    let _ = window.center();
+    if let Err(e) = window.show() {
+        tracing::error!("Failed to show quick capture window: {}", e);
+        return;
+    }
+    let _ = window.set_focus();
+    let _ = window.emit(SHOWN_EVENT, ());
+}
+
+/// 隐藏快速记录窗口
+pub fn hide(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// 收件箱文件路径（未配置时使用数据目录下的 inbox.md）
+pub fn inbox_path(config: &CaptureConfig) -> Result<PathBuf> {
+    if !config.inbox_path.trim().is_empty() {
+        return Ok(PathBuf::from(config.inbox_path.trim()));
+    }
+    Ok(crate::utils::paths::get_data_dir()?.join(DEFAULT_INBOX_FILE))
+}
+
+/// 追加一条记录到收件箱（文件不存在时创建）
+pub fn append_to_inbox(path: &Path, text: &str, now: DateTime<Local>) -> Result<()> {
+    append(path, &format_entry(text, now))
+}
+
+/// 追加一条待办（Markdown 任务列表项）到收件箱
+pub fn append_todo(path: &Path, text: &str, now: DateTime<Local>) -> Result<()> {
+    append(path, &format_todo(text, now))
+}
+
+fn append(path: &Path, entry: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(entry.as_bytes())?;
+    Ok(())
+}
+
+/// 格式化为 Markdown 列表项，多行内容缩进到同一列表项下
+fn format_entry(text: &str, now: DateTime<Local>) -> String {
+    let mut lines = text.trim().lines();
+    let mut entry = format!("- [{}] {}\n", now.format("%Y-%m-%d %H:%M"), lines.next().unwrap_or_default());
+    for line in lines {
+        entry.push_str("  ");
+        entry.push_str(line);
+        entry.push('\n');
+    }
+    entry
+}
+
+/// 格式化为未完成的任务列表项（多行内容合并为一行）
+fn format_todo(text: &str, now: DateTime<Local>) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("- [ ] {} ({})\n", text, now.format("%Y-%m-%d %H:%M"))
+}
+
+/// 以首行作为片段名称
+pub fn snippet_name(text: &str) -> String {
+    let first_line = text.trim().lines().next().unwrap_or_default().trim();
+    let mut name: String = first_line.chars().take(SNIPPET_NAME_MAX_CHARS).collect();
+    if first_line.chars().count() > SNIPPET_NAME_MAX_CHARS {
+        name.push('…');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_entry() {
+        let now = Local.with_ymd_and_hms(2026, 3, 5, 9, 7, 0).unwrap();
+        assert_eq!(format_entry("  buy milk \n", now), "- [2026-03-05 09:07] buy milk\n");
+        assert_eq!(
+            format_entry("meeting notes\nask about budget", now),
+            "- [2026-03-05 09:07] meeting notes\n  ask about budget\n"
+        );
+    }
+
+    #[test]
+    fn test_format_todo() {
+        let now = Local.with_ymd_and_hms(2026, 3, 5, 9, 7, 0).unwrap();
+        assert_eq!(format_todo(" call bob\nabout invoice ", now), "- [ ] call bob about invoice (2026-03-05 09:07)\n");
+    }
+
+    #[test]
+    fn test_append_to_inbox() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_capture_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("notes").join("inbox.md");
+        let now = Local.with_ymd_and_hms(2026, 3, 5, 9, 7, 0).unwrap();
+
+        append_to_inbox(&path, "first", now).unwrap();
+        append_to_inbox(&path, "second", now).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.ends_with("second\n"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_snippet_name() {
+        assert_eq!(snippet_name("\n  Standup template  \nYesterday: ..."), "Standup template");
+        assert_eq!(snippet_name(&"x".repeat(50)).chars().count(), SNIPPET_NAME_MAX_CHARS + 1);
+    }
+}
@@ -1,11 +1,25 @@
 // 智能排序算法模块
 // 基于多维度因素计算结果相关性分数
 
+pub mod pins;
+pub mod rules;
+
+use crate::core::clock::{self, SharedClock};
+use crate::core::highlight::{self, MatchHighlights};
 use crate::core::types::{QueryResult, WoxImage};
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc, Duration};
+use pins::ResultPin;
+use rules::CompiledRules;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 单个插件分数倍率的上限
+pub const MAX_PLUGIN_BOOST: f64 = 10.0;
 
-/// 排序因素权重配置
-#[derive(Debug, Clone)]
+/// 排序因素权重配置（持久化在 AppConfig.ranking.weights，缺失的字段使用默认值）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RankingWeights {
     /// 文本匹配分数权重 (默认: 1.0)
     pub text_match: f64,
@@ -17,6 +31,8 @@ pub struct RankingWeights {
     pub result_type: f64,
     /// MRU 提升权重 (默认: 3.0)
     pub mru_boost: f64,
+    /// 查询 → 选择亲和度权重 (默认: 3.0)
+    pub query_affinity: f64,
 }
 
 impl Default for RankingWeights {
@@ -27,24 +43,116 @@ impl Default for RankingWeights {
             recency: 1.5,
             result_type: 0.5,
             mru_boost: 3.0,
+            query_affinity: 3.0,
         }
     }
 }
 
+impl RankingWeights {
+    /// 校验权重：必须是非负有限数
+    pub fn validate(&self) -> Result<()> {
+        let fields = [
+            ("text_match", self.text_match),
+            ("usage_frequency", self.usage_frequency),
+            ("recency", self.recency),
+            ("result_type", self.result_type),
+            ("mru_boost", self.mru_boost),
+            ("query_affinity", self.query_affinity),
+        ];
+        for (name, value) in fields {
+            if !value.is_finite() || value < 0.0 {
+                return Err(anyhow!("Ranking weight '{}' must be a non-negative number", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 校验插件分数倍率（0 会把该插件的结果排到最后）
+pub fn validate_plugin_boosts(boosts: &HashMap<String, f64>) -> Result<()> {
+    for (plugin_id, boost) in boosts {
+        if !boost.is_finite() || *boost < 0.0 || *boost > MAX_PLUGIN_BOOST {
+            return Err(anyhow!(
+                "Boost for plugin '{}' must be between 0 and {}",
+                plugin_id, MAX_PLUGIN_BOOST
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// 智能排序器
 pub struct IntelligentRanker {
     weights: RankingWeights,
+    /// 插件分数倍率（plugin_id → 倍率，未配置为 1.0）
+    plugin_boosts: HashMap<String, f64>,
+    /// 当前查询下各结果的历史选择亲和度（result_id → 0-100 分）
+    query_affinity: HashMap<String, f64>,
+    /// 各结果的 frecency 值（result_id → 值），存在时代替原始点击次数计算频率分数
+    frecency: HashMap<String, f64>,
+    /// 当前查询下用户置顶的结果（始终排在最前，按置顶先后排列）
+    pins: Vec<ResultPin>,
+    rules: CompiledRules,
+    clock: SharedClock,
 }
 
 impl IntelligentRanker {
     pub fn new() -> Self {
+        Self::with_weights(RankingWeights::default())
+    }
+    
+    pub fn with_weights(weights: RankingWeights) -> Self {
         Self {
-            weights: RankingWeights::default(),
+            weights,
+            plugin_boosts: HashMap::new(),
+            query_affinity: HashMap::new(),
+            frecency: HashMap::new(),
+            pins: Vec::new(),
+            rules: CompiledRules::default(),
+            clock: clock::system(),
         }
     }
     
-    pub fn with_weights(weights: RankingWeights) -> Self {
-        Self { weights }
+    /// 指定计算时间衰减使用的时钟（测试用）
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+    
+    /// 设置插件分数倍率
+    pub fn with_plugin_boosts(mut self, plugin_boosts: HashMap<String, f64>) -> Self {
+        self.plugin_boosts = plugin_boosts;
+        self
+    }
+    
+    /// 设置当前查询的历史选择亲和度
+    pub fn with_query_affinity(mut self, query_affinity: HashMap<String, f64>) -> Self {
+        self.query_affinity = query_affinity;
+        self
+    }
+    
+    /// 设置各结果的 frecency 值
+    pub fn with_frecency(mut self, frecency: HashMap<String, f64>) -> Self {
+        self.frecency = frecency;
+        self
+    }
+    
+    /// 设置当前查询的置顶结果
+    pub fn with_pins(mut self, pins: Vec<ResultPin>) -> Self {
+        self.pins = pins;
+        self
+    }
+    
+    /// 附加用户自定义排序规则
+    pub fn with_rules(mut self, rules: CompiledRules) -> Self {
+        self.rules = rules;
+        self
+    }
+    
+    /// 计算用户规则的调整分数，返回 (调整值, 命中的规则名)
+    pub fn rule_adjustment(&self, result: &QueryResult, query: &str) -> (i32, Vec<String>) {
+        self.rules.evaluate(result, query)
     }
     
     /// 计算综合排序分数
@@ -62,8 +170,11 @@ impl IntelligentRanker {
         let text_score = self.calculate_text_match_score(result, query);
         total_score += text_score * self.weights.text_match;
         
-        // 2. 使用频率分数
-        let frequency_score = self.calculate_frequency_score(usage_count);
+        // 2. 使用频率分数（有 frecency 时按新近度加权，避免陈旧的大计数主导排序）
+        let frequency_score = match self.frecency.get(&result.id) {
+            Some(frecency) => crate::statistics::frecency::frecency_score(*frecency),
+            None => self.calculate_frequency_score(usage_count),
+        };
         total_score += frequency_score * self.weights.usage_frequency;
         
         // 3. 最近使用时间分数
@@ -81,6 +192,22 @@ impl IntelligentRanker {
             total_score += 100.0 * self.weights.mru_boost;
         }
         
+        // 6. 相似查询下曾被选择
+        if let Some(affinity) = self.query_affinity.get(&result.id) {
+            total_score += affinity * self.weights.query_affinity;
+        }
+        
+        // 7. 插件分数倍率
+        if let Some(boost) = self.plugin_boosts.get(&result.plugin_id) {
+            total_score *= boost;
+        }
+        
+        // 8. 用户自定义规则
+        if !self.rules.is_empty() {
+            let (adjustment, _) = self.rules.evaluate(result, query);
+            total_score += adjustment as f64;
+        }
+        
         total_score
     }
     
@@ -140,7 +267,7 @@ impl IntelligentRanker {
     
     /// 计算最近使用时间分数 (0-100)
     fn calculate_recency_score(&self, last_used: DateTime<Utc>) -> f64 {
-        let now = Utc::now();
+        let now = self.clock.now();
         let duration = now.signed_duration_since(last_used);
         
         // 时间衰减曲线
@@ -223,6 +350,24 @@ impl IntelligentRanker {
         (max_continuous as f64) / (query.len() as f64)
     }
     
+    /// 计算标题/副标题的匹配高亮（子串匹配，标题无子串命中时尝试首字母缩写）
+    fn match_highlights(&self, result: &QueryResult, query: &str) -> Option<MatchHighlights> {
+        if query.trim().is_empty() {
+            return None;
+        }
+        
+        let mut title = highlight::term_spans(&result.title, query);
+        if title.is_empty() {
+            title = highlight::initials_spans(&result.title, query.trim());
+        }
+        
+        MatchHighlights {
+            title,
+            subtitle: highlight::term_spans(&result.subtitle, query),
+        }
+        .non_empty()
+    }
+    
     /// 对结果列表进行智能排序
     pub fn rank_results(
         &self,
@@ -258,11 +403,19 @@ impl IntelligentRanker {
             
             // 更新结果分数
             result.score = final_score as i32;
+            
+            // 插件未给出高亮时，按文本匹配补充
+            if result.highlights.is_none() {
+                result.highlights = self.match_highlights(result, query);
+            }
         }
         
-        // 按分数降序排序
+        // 置顶结果优先，其余按分数降序排序
         results.sort_by(|a, b| {
-            b.score.cmp(&a.score)
+            let pin_a = pins::pin_rank(&self.pins, a).unwrap_or(usize::MAX);
+            let pin_b = pins::pin_rank(&self.pins, b).unwrap_or(usize::MAX);
+            pin_a.cmp(&pin_b)
+                .then_with(|| b.score.cmp(&a.score))
                 .then_with(|| a.title.cmp(&b.title))  // 分数相同时按标题排序
         });
     }
@@ -277,6 +430,7 @@ impl Default for IntelligentRanker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::clock::{Clock, FakeClock};
     
     #[test]
     fn test_text_match_score() {
@@ -293,6 +447,9 @@ mod tests {
             preview: None,
             refreshable: false,
             group: None,
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         };
         
         // 精确匹配
@@ -308,6 +465,25 @@ mod tests {
         assert!(score > 35.0);
     }
     
+    #[test]
+    fn test_rank_results_fills_highlights() {
+        let ranker = IntelligentRanker::new();
+        let mut results = vec![
+            QueryResult::new("Visual Studio Code").with_subtitle("Code Editor"),
+            QueryResult::new("Git Client"),
+        ];
+        
+        ranker.rank_results(&mut results, "code", &[], &[]);
+        let highlights = results[0].highlights.as_ref().unwrap();
+        assert_eq!(highlights.title, vec![highlight::HighlightSpan { start: 14, end: 18 }]);
+        assert_eq!(highlights.subtitle, vec![highlight::HighlightSpan { start: 0, end: 4 }]);
+        assert!(results[1].highlights.is_none());
+        
+        let mut results = vec![QueryResult::new("Git Client")];
+        ranker.rank_results(&mut results, "gc", &[], &[]);
+        assert_eq!(results[0].highlights.as_ref().unwrap().title.len(), 2);
+    }
+    
     #[test]
     fn test_frequency_score() {
         let ranker = IntelligentRanker::new();
@@ -336,4 +512,139 @@ mod tests {
         let score = ranker.calculate_recency_score(one_week_ago);
         assert!(score >= 20.0 && score <= 40.0);
     }
+    
+    #[test]
+    fn test_recency_decay_with_fake_clock() {
+        let clock = FakeClock::fixed();
+        let ranker = IntelligentRanker::new().with_clock(clock.clone());
+        let last_used = clock.now();
+        
+        let steps = [
+            (Duration::minutes(4), 100.0),
+            (Duration::minutes(30), 80.0),
+            (Duration::hours(12), 60.0),
+            (Duration::days(5), 40.0),
+            (Duration::days(20), 20.0),
+            (Duration::days(60), 10.0),
+        ];
+        for (elapsed, expected) in steps {
+            clock.set(last_used + elapsed);
+            assert_eq!(ranker.calculate_recency_score(last_used), expected, "after {}", elapsed);
+        }
+    }
+    
+    #[test]
+    fn test_weights_and_plugin_boosts() {
+        let mut bookmark = QueryResult::new("Rust Docs");
+        bookmark.id = "bookmark".to_string();
+        bookmark.plugin_id = "browser-bookmarks".to_string();
+        let mut file = QueryResult::new("Rust Docs");
+        file.id = "file".to_string();
+        file.plugin_id = "file-search".to_string();
+        
+        let mut results = vec![bookmark.clone(), file.clone()];
+        IntelligentRanker::new().rank_results(&mut results, "rust", &[], &[]);
+        assert_eq!(results[0].id, "bookmark");
+        
+        let boosts = HashMap::from([("file-search".to_string(), 2.0)]);
+        let mut results = vec![bookmark, file];
+        IntelligentRanker::new()
+            .with_plugin_boosts(boosts)
+            .rank_results(&mut results, "rust", &[], &[]);
+        assert_eq!(results[0].id, "file");
+        
+        // 未配置的字段使用默认权重
+        let weights: RankingWeights = serde_json::from_str(r#"{"recency": 0.5}"#).unwrap();
+        assert_eq!(weights.recency, 0.5);
+        assert_eq!(weights.mru_boost, RankingWeights::default().mru_boost);
+        
+        assert!(RankingWeights { text_match: -1.0, ..Default::default() }.validate().is_err());
+        assert!(validate_plugin_boosts(&HashMap::from([("x".to_string(), f64::NAN)])).is_err());
+        assert!(validate_plugin_boosts(&HashMap::from([("x".to_string(), 0.0)])).is_ok());
+    }
+    
+    #[test]
+    fn test_query_affinity_promotes_previous_selection() {
+        let mut chromium = QueryResult::new("Chromium");
+        chromium.id = "chromium".to_string();
+        let mut chrome = QueryResult::new("Google Chrome");
+        chrome.id = "chrome".to_string();
+        
+        let mut results = vec![chromium.clone(), chrome.clone()];
+        IntelligentRanker::new().rank_results(&mut results, "chr", &[], &[]);
+        assert_eq!(results[0].id, "chromium");
+        
+        let affinity = HashMap::from([("chrome".to_string(), 75.0)]);
+        let mut results = vec![chromium, chrome];
+        IntelligentRanker::new()
+            .with_query_affinity(affinity)
+            .rank_results(&mut results, "chr", &[], &[]);
+        assert_eq!(results[0].id, "chrome");
+    }
+    
+    #[test]
+    fn test_frecency_replaces_raw_count() {
+        let mut old = QueryResult::new("Notes Old");
+        old.id = "old".to_string();
+        let mut new = QueryResult::new("Notes New");
+        new.id = "new".to_string();
+        let usage = vec![("old".to_string(), 200, None), ("new".to_string(), 5, None)];
+        
+        let mut results = vec![old.clone(), new.clone()];
+        IntelligentRanker::new().rank_results(&mut results, "notes", &usage, &[]);
+        assert_eq!(results[0].id, "old");
+        
+        // 200 次点击都在半年前，5 次点击在本周
+        let frecency = HashMap::from([("old".to_string(), 200.0 * 10.0), ("new".to_string(), 5.0 * 100.0)]);
+        let frecency_ranker = IntelligentRanker::new().with_frecency(frecency);
+        assert!(
+            frecency_ranker.calculate_score(&old, "notes", 200, None, false)
+                < IntelligentRanker::new().calculate_score(&old, "notes", 200, None, false)
+        );
+    }
+    
+    #[test]
+    fn test_pins_override_scores() {
+        let results: Vec<QueryResult> = ["API Gateway", "api", "Rapid API"]
+            .iter()
+            .map(|title| {
+                let mut result = QueryResult::new(*title);
+                result.id = title.to_lowercase();
+                result
+            })
+            .collect();
+        let pin = |id: &str| ResultPin { result_id: id.to_string(), plugin_id: String::new() };
+        
+        let mut ranked = results.clone();
+        IntelligentRanker::new().rank_results(&mut ranked, "api", &[], &[]);
+        assert_eq!(ranked[0].id, "api");
+        
+        let mut ranked = results;
+        IntelligentRanker::new()
+            .with_pins(vec![pin("rapid api"), pin("api gateway"), pin("missing")])
+            .rank_results(&mut ranked, "api", &[], &[]);
+        let ids: Vec<&str> = ranked.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["rapid api", "api gateway", "api"]);
+    }
+    
+    #[test]
+    fn test_rank_results_recency_tiebreak() {
+        let clock = FakeClock::fixed();
+        let ranker = IntelligentRanker::new().with_clock(clock.clone());
+        let now = clock.now();
+        
+        let mut result_a = QueryResult::new("Notes A");
+        result_a.id = "a".to_string();
+        let mut result_b = QueryResult::new("Notes B");
+        result_b.id = "b".to_string();
+        let mut results = vec![result_a, result_b];
+        
+        // 使用次数相同，最近使用的排前面
+        let usage = vec![
+            ("a".to_string(), 3, Some(now - Duration::days(10))),
+            ("b".to_string(), 3, Some(now - Duration::minutes(1))),
+        ];
+        ranker.rank_results(&mut results, "notes", &usage, &[]);
+        assert_eq!(results[0].id, "b");
+    }
 }
@@ -0,0 +1,55 @@
+// 结果置顶 - 用户可以把某个结果固定在指定查询的最前面（如输入 "api" 总是先显示某个项目）
+// 置顶记录保存在 statistics.db 的 ranking_overrides 表中，排序时优先于智能排序分数
+
+use crate::core::types::{Action, QueryResult, WoxImage};
+use crate::statistics::query_selection::normalize_query;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// 为当前查询置顶（后端直接处理）
+pub const ACTION_PIN: &str = "pin_for_query";
+
+/// 取消当前查询的置顶
+pub const ACTION_UNPIN: &str = "unpin_for_query";
+
+/// 某个查询下置顶的结果（按置顶先后排列）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultPin {
+    pub result_id: String,
+    pub plugin_id: String,
+}
+
+/// 最近一次注入置顶操作的查询（规范化后），执行置顶操作时使用
+static LAST_QUERY: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// 为结果注入"为此查询置顶"/"取消置顶"操作
+pub fn attach_actions(results: &mut [QueryResult], query: &str, pins: &[ResultPin]) {
+    let query = normalize_query(query);
+    if query.is_empty() {
+        return;
+    }
+    *LAST_QUERY.lock() = Some(query);
+
+    for result in results.iter_mut() {
+        let pinned = pin_rank(pins, result).is_some();
+        result.actions.push(Action {
+            id: if pinned { ACTION_UNPIN } else { ACTION_PIN }.to_string(),
+            name: if pinned { "Unpin for This Query" } else { "Pin for This Query" }.to_string(),
+            icon: Some(WoxImage::emoji("📌")),
+            is_default: false,
+            hotkey: None,
+            prevent_hide: true,
+        });
+    }
+}
+
+/// 最近一次查询（规范化后）
+pub fn last_query() -> Option<String> {
+    LAST_QUERY.lock().clone()
+}
+
+/// 结果在置顶列表中的位置（未置顶为 None）
+pub fn pin_rank(pins: &[ResultPin], result: &QueryResult) -> Option<usize> {
+    pins.iter()
+        .position(|pin| pin.result_id == result.id && pin.plugin_id == result.plugin_id)
+}
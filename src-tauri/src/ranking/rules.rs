@@ -0,0 +1,258 @@
+// 用户自定义排序规则
+// 规则示例: "path contains node_modules → -50"、"extension = .exe 且 query 包含 run → +30"
+
+use crate::core::types::QueryResult;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 规则匹配字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleField {
+    Title,
+    Subtitle,
+    /// 文件路径（优先取 context_data.path，否则使用结果 ID）
+    Path,
+    /// 扩展名（带点，例如 ".exe"）
+    Extension,
+    PluginId,
+    /// 当前查询文本
+    Query,
+}
+
+/// 规则比较方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOperator {
+    Contains,
+    Equals,
+    StartsWith,
+    EndsWith,
+    Regex,
+}
+
+/// 单个匹配条件（默认忽略大小写）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleCondition {
+    pub field: RuleField,
+    pub operator: RuleOperator,
+    pub value: String,
+}
+
+/// 排序规则：所有条件同时满足时，分数加上 adjustment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringRule {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub conditions: Vec<RuleCondition>,
+    pub adjustment: i32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 编译后的条件
+enum CompiledMatcher {
+    Text { operator: RuleOperator, value: String },
+    Regex(Regex),
+}
+
+struct CompiledCondition {
+    field: RuleField,
+    matcher: CompiledMatcher,
+}
+
+struct CompiledRule {
+    name: String,
+    conditions: Vec<CompiledCondition>,
+    adjustment: i32,
+}
+
+/// 编译后的规则集（在排序器中对每个结果求值）
+#[derive(Default)]
+pub struct CompiledRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl CompiledRules {
+    /// 编译规则，任意规则无效时返回错误（用于保存前校验）
+    pub fn compile(rules: &[ScoringRule]) -> Result<Self> {
+        let mut compiled = Vec::new();
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            if rule.conditions.is_empty() {
+                return Err(anyhow!("Rule '{}' has no conditions", rule.name));
+            }
+
+            let mut conditions = Vec::with_capacity(rule.conditions.len());
+            for condition in &rule.conditions {
+                let matcher = match condition.operator {
+                    RuleOperator::Regex => {
+                        let regex = Regex::new(&format!("(?i){}", condition.value))
+                            .map_err(|e| anyhow!("Rule '{}': invalid regex: {}", rule.name, e))?;
+                        CompiledMatcher::Regex(regex)
+                    }
+                    operator => CompiledMatcher::Text {
+                        operator,
+                        value: condition.value.to_lowercase(),
+                    },
+                };
+                conditions.push(CompiledCondition { field: condition.field, matcher });
+            }
+
+            compiled.push(CompiledRule {
+                name: rule.name.clone(),
+                conditions,
+                adjustment: rule.adjustment,
+            });
+        }
+
+        Ok(Self { rules: compiled })
+    }
+
+    /// 编译规则，跳过无效规则（查询时使用，避免一条坏规则影响搜索）
+    pub fn compile_lenient(rules: &[ScoringRule]) -> Self {
+        let mut compiled = Self::default();
+        for rule in rules {
+            match Self::compile(std::slice::from_ref(rule)) {
+                Ok(mut single) => compiled.rules.append(&mut single.rules),
+                Err(e) => tracing::warn!("Skipping invalid ranking rule: {}", e),
+            }
+        }
+        compiled
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 计算规则调整分数，返回 (总调整值, 命中的规则名)
+    pub fn evaluate(&self, result: &QueryResult, query: &str) -> (i32, Vec<String>) {
+        let mut total = 0;
+        let mut matched = Vec::new();
+
+        for rule in &self.rules {
+            if rule.conditions.iter().all(|c| c.matches(result, query)) {
+                total += rule.adjustment;
+                matched.push(rule.name.clone());
+            }
+        }
+
+        (total, matched)
+    }
+}
+
+impl CompiledCondition {
+    fn matches(&self, result: &QueryResult, query: &str) -> bool {
+        let value = field_value(self.field, result, query);
+
+        match &self.matcher {
+            CompiledMatcher::Regex(regex) => regex.is_match(&value),
+            CompiledMatcher::Text { operator, value: expected } => {
+                let value = value.to_lowercase();
+                match operator {
+                    RuleOperator::Contains => value.contains(expected.as_str()),
+                    RuleOperator::Equals => value == *expected,
+                    RuleOperator::StartsWith => value.starts_with(expected.as_str()),
+                    RuleOperator::EndsWith => value.ends_with(expected.as_str()),
+                    RuleOperator::Regex => false,
+                }
+            }
+        }
+    }
+}
+
+/// 取出结果中对应字段的值
+fn field_value(field: RuleField, result: &QueryResult, query: &str) -> String {
+    match field {
+        RuleField::Title => result.title.clone(),
+        RuleField::Subtitle => result.subtitle.clone(),
+        RuleField::Path => result_path(result).to_string(),
+        RuleField::Extension => std::path::Path::new(result_path(result))
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+            .unwrap_or_default(),
+        RuleField::PluginId => result.plugin_id.clone(),
+        RuleField::Query => query.to_string(),
+    }
+}
+
+fn result_path(result: &QueryResult) -> &str {
+    result
+        .context_data
+        .get("path")
+        .and_then(|p| p.as_str())
+        .unwrap_or(&result.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_result(path: &str) -> QueryResult {
+        let mut result = QueryResult::new(path.rsplit('\\').next().unwrap_or(path));
+        result.id = path.to_string();
+        result.context_data = serde_json::json!({ "path": path });
+        result
+    }
+
+    #[test]
+    fn test_rules_apply_adjustments() {
+        let rules = vec![
+            ScoringRule {
+                name: "demote node_modules".to_string(),
+                enabled: true,
+                conditions: vec![RuleCondition {
+                    field: RuleField::Path,
+                    operator: RuleOperator::Contains,
+                    value: "node_modules".to_string(),
+                }],
+                adjustment: -50,
+            },
+            ScoringRule {
+                name: "boost exe for run".to_string(),
+                enabled: true,
+                conditions: vec![
+                    RuleCondition {
+                        field: RuleField::Extension,
+                        operator: RuleOperator::Equals,
+                        value: ".EXE".to_string(),
+                    },
+                    RuleCondition {
+                        field: RuleField::Query,
+                        operator: RuleOperator::Contains,
+                        value: "run".to_string(),
+                    },
+                ],
+                adjustment: 30,
+            },
+        ];
+        let compiled = CompiledRules::compile(&rules).unwrap();
+
+        let (adj, matched) = compiled.evaluate(&file_result("C:\\proj\\node_modules\\a.js"), "a");
+        assert_eq!(adj, -50);
+        assert_eq!(matched, vec!["demote node_modules".to_string()]);
+
+        assert_eq!(compiled.evaluate(&file_result("C:\\tools\\app.exe"), "run app").0, 30);
+        assert_eq!(compiled.evaluate(&file_result("C:\\tools\\app.exe"), "app").0, 0);
+    }
+
+    #[test]
+    fn test_invalid_regex_rejected() {
+        let rules = vec![ScoringRule {
+            name: "bad".to_string(),
+            enabled: true,
+            conditions: vec![RuleCondition {
+                field: RuleField::Title,
+                operator: RuleOperator::Regex,
+                value: "(".to_string(),
+            }],
+            adjustment: 10,
+        }];
+        assert!(CompiledRules::compile(&rules).is_err());
+        assert!(CompiledRules::compile_lenient(&rules).is_empty());
+    }
+}
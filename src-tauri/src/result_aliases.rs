@@ -0,0 +1,286 @@
+// 结果别名 - 为任意结果指定自定义关键词（如 "ps" → Photoshop.exe）
+// 查询时在插件之前查找，精确命中关键词时直接置顶返回保存的结果
+
+use crate::core::types::{Action, QueryResult, WoxImage};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// 全局结果别名管理器
+pub static RESULT_ALIASES: Lazy<ResultAliasesManager> = Lazy::new(ResultAliasesManager::new);
+
+/// 指定别名操作（前端弹出输入框后调用 set_result_alias）
+pub const ACTION_ASSIGN_ALIAS: &str = "assign_alias";
+/// 删除别名操作（删除指向该结果的所有别名，后端直接处理）
+pub const ACTION_REMOVE_ALIAS: &str = "remove_alias";
+
+/// 别名命中结果的分数（高于任何插件结果）
+const ALIAS_SCORE: i32 = 1000;
+
+/// 关键词最大长度（字符）
+const MAX_KEYWORD_LENGTH: usize = 32;
+
+/// 别名条目（保存结果快照，执行时仍交给原插件处理）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultAlias {
+    pub keyword: String,
+    pub result_id: String,
+    pub plugin_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: String,
+    pub icon: WoxImage,
+    #[serde(default)]
+    pub context_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 结果别名管理器
+pub struct ResultAliasesManager {
+    aliases: RwLock<HashMap<String, ResultAlias>>,
+    storage_path: Option<PathBuf>,
+}
+
+impl ResultAliasesManager {
+    fn new() -> Self {
+        let storage_path = crate::utils::paths::get_data_dir()
+            .ok()
+            .map(|dir| dir.join("result_aliases.json"));
+        Self::with_storage(storage_path)
+    }
+
+    fn with_storage(storage_path: Option<PathBuf>) -> Self {
+        let aliases = storage_path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match Self::load(path) {
+                Ok(aliases) => Some(aliases),
+                Err(e) => {
+                    tracing::warn!("Failed to load result aliases: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            aliases: RwLock::new(aliases),
+            storage_path,
+        }
+    }
+
+    /// 规范化关键词（去除首尾空白、转小写）；不允许为空或包含空白
+    fn normalize(keyword: &str) -> Result<String> {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() {
+            return Err(anyhow!("Alias keyword cannot be empty"));
+        }
+        if keyword.chars().any(char::is_whitespace) {
+            return Err(anyhow!("Alias keyword cannot contain spaces"));
+        }
+        if keyword.chars().count() > MAX_KEYWORD_LENGTH {
+            return Err(anyhow!("Alias keyword is longer than {} characters", MAX_KEYWORD_LENGTH));
+        }
+        Ok(keyword)
+    }
+
+    /// 添加或替换别名（同一关键词只能指向一个结果）
+    pub fn set(&self, keyword: &str, result: &QueryResult) -> Result<ResultAlias> {
+        let keyword = Self::normalize(keyword)?;
+        let alias = ResultAlias {
+            keyword: keyword.clone(),
+            result_id: result.id.clone(),
+            plugin_id: result.plugin_id.clone(),
+            title: result.title.clone(),
+            subtitle: result.subtitle.clone(),
+            icon: result.icon.clone(),
+            context_data: result.context_data.clone(),
+            created_at: Utc::now(),
+        };
+
+        self.aliases.write().insert(keyword.clone(), alias.clone());
+        tracing::info!("🏷️ Alias '{}' → {}", keyword, alias.title);
+        self.save()?;
+        Ok(alias)
+    }
+
+    /// 删除别名
+    pub fn remove(&self, keyword: &str) -> Result<()> {
+        let removed = self.aliases.write().remove(&keyword.trim().to_lowercase()).is_some();
+        if removed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// 删除指向某个结果的所有别名
+    pub fn remove_for_result(&self, result_id: &str) -> Result<()> {
+        let removed = {
+            let mut aliases = self.aliases.write();
+            let before = aliases.len();
+            aliases.retain(|_, alias| alias.result_id != result_id);
+            before != aliases.len()
+        };
+        if removed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// 获取全部别名（按关键词排序）
+    pub fn list(&self) -> Vec<ResultAlias> {
+        let mut aliases: Vec<ResultAlias> = self.aliases.read().values().cloned().collect();
+        aliases.sort_by(|a, b| a.keyword.cmp(&b.keyword));
+        aliases
+    }
+
+    /// 查询是否精确命中某个别名，命中时返回置顶结果
+    pub fn lookup(&self, query: &str) -> Option<QueryResult> {
+        let keyword = query.trim().to_lowercase();
+        if keyword.is_empty() {
+            return None;
+        }
+        let alias = self.aliases.read().get(&keyword).cloned()?;
+
+        let mut result = QueryResult::new(alias.title)
+            .with_subtitle(alias.subtitle)
+            .with_icon(alias.icon)
+            .with_score(ALIAS_SCORE);
+        result.id = alias.result_id;
+        result.plugin_id = alias.plugin_id;
+        result.context_data = alias.context_data;
+        result.group = Some(format!("🏷️ {}", alias.keyword));
+        result.actions.push(Action {
+            id: "open".to_string(),
+            name: "Open".to_string(),
+            icon: None,
+            is_default: true,
+            hotkey: None,
+            prevent_hide: false,
+        });
+        Some(result)
+    }
+
+    /// 把别名结果放到首位（移除插件返回的同一结果）
+    pub fn pin_to_top(results: &mut Vec<QueryResult>, mut alias: QueryResult) {
+        if let Some(index) = results.iter().position(|r| r.id == alias.id && r.plugin_id == alias.plugin_id) {
+            // 保留插件给出的最新动作和预览
+            let original = results.remove(index);
+            alias.actions = original.actions;
+            alias.preview = original.preview;
+        }
+        results.insert(0, alias);
+    }
+
+    /// 为结果注入指定/删除别名操作
+    pub fn attach_actions(&self, results: &mut [QueryResult]) {
+        let aliases = self.aliases.read();
+        for result in results.iter_mut() {
+            if result.actions.iter().any(|a| a.id == ACTION_ASSIGN_ALIAS) {
+                continue;
+            }
+
+            result.actions.push(Action {
+                id: ACTION_ASSIGN_ALIAS.to_string(),
+                name: "Assign Alias".to_string(),
+                icon: Some(WoxImage::emoji("🏷️")),
+                is_default: false,
+                hotkey: None,
+                prevent_hide: true,
+            });
+
+            if aliases.values().any(|alias| alias.result_id == result.id) {
+                result.actions.push(Action {
+                    id: ACTION_REMOVE_ALIAS.to_string(),
+                    name: "Remove Alias".to_string(),
+                    icon: Some(WoxImage::emoji("🗑️")),
+                    is_default: false,
+                    hotkey: None,
+                    prevent_hide: true,
+                });
+            }
+        }
+    }
+
+    fn load(path: &PathBuf) -> Result<HashMap<String, ResultAlias>> {
+        let content = std::fs::read_to_string(path)?;
+        let aliases: Vec<ResultAlias> = serde_json::from_str(&content)?;
+        Ok(aliases.into_iter().map(|a| (a.keyword.clone(), a)).collect())
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.storage_path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.list())?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn photoshop() -> QueryResult {
+        let mut result = QueryResult::new("Adobe Photoshop").with_subtitle("C:\\Program Files\\Adobe\\Photoshop.exe");
+        result.id = "C:\\Program Files\\Adobe\\Photoshop.exe".to_string();
+        result.plugin_id = "app_search".to_string();
+        result
+    }
+
+    #[test]
+    fn test_alias_lookup_and_pin() {
+        let manager = ResultAliasesManager::with_storage(None);
+        manager.set(" PS ", &photoshop()).unwrap();
+        assert!(manager.set("p s", &photoshop()).is_err());
+        assert!(manager.lookup("p").is_none());
+
+        let alias = manager.lookup("ps").unwrap();
+        assert_eq!(alias.plugin_id, "app_search");
+        assert_eq!(alias.score, ALIAS_SCORE);
+
+        let mut results = vec![QueryResult::new("PowerShell"), photoshop()];
+        results[1].actions.push(Action {
+            id: "run_as_admin".to_string(),
+            name: "Run as Administrator".to_string(),
+            icon: None,
+            is_default: false,
+            hotkey: None,
+            prevent_hide: false,
+        });
+        ResultAliasesManager::pin_to_top(&mut results, alias);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Adobe Photoshop");
+        assert!(results[0].actions.iter().any(|a| a.id == "run_as_admin"));
+    }
+
+    #[test]
+    fn test_alias_persistence_and_removal() {
+        let path = std::env::temp_dir().join(format!("ilauncher_aliases_{}.json", uuid::Uuid::new_v4()));
+        let manager = ResultAliasesManager::with_storage(Some(path.clone()));
+        manager.set("ps", &photoshop()).unwrap();
+        manager.set("photo", &photoshop()).unwrap();
+
+        let reloaded = ResultAliasesManager::with_storage(Some(path.clone()));
+        assert_eq!(reloaded.list().iter().map(|a| a.keyword.as_str()).collect::<Vec<_>>(), vec!["photo", "ps"]);
+
+        let mut results = vec![photoshop()];
+        reloaded.attach_actions(&mut results);
+        assert!(results[0].actions.iter().any(|a| a.id == ACTION_REMOVE_ALIAS));
+
+        reloaded.remove("PS").unwrap();
+        assert!(reloaded.lookup("ps").is_none());
+        reloaded.remove_for_result(&photoshop().id).unwrap();
+        assert!(reloaded.list().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
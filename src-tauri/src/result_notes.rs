@@ -0,0 +1,311 @@
+// 结果备注管理器 - 为任意结果（文件、应用、书签）附加简短备注
+
+use crate::core::types::{Action, Preview, QueryResult, WoxImage};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// 备注最大长度（字符）
+const MAX_NOTE_LENGTH: usize = 500;
+
+/// 编辑备注操作（前端弹出输入框后调用 set_result_note）
+pub const ACTION_EDIT_NOTE: &str = "edit_note";
+/// 删除备注操作（后端直接处理）
+pub const ACTION_REMOVE_NOTE: &str = "remove_note";
+
+/// 结果 ID 即本地路径的插件：备注匹配查询时可直接生成打开该路径的结果
+const PATH_PLUGIN_IDS: &[&str] = &["file_search", "app_search"];
+
+/// 备注键（同一 ID 在不同插件中代表不同结果）
+type NoteKey = (String, String);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultNote {
+    pub result_id: String,
+    pub plugin_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: String,
+    pub note: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct ResultNotesManager {
+    notes: Arc<RwLock<HashMap<NoteKey, ResultNote>>>,
+    storage_path: String,
+}
+
+impl ResultNotesManager {
+    pub fn new(storage_path: String) -> Self {
+        let notes = match Self::load_from_disk(&storage_path) {
+            Ok(notes) => notes,
+            Err(e) => {
+                tracing::warn!("Failed to load result notes: {}", e);
+                HashMap::new()
+            }
+        };
+
+        Self {
+            notes: Arc::new(RwLock::new(notes)),
+            storage_path,
+        }
+    }
+
+    /// 添加或更新备注（空备注等同于删除）
+    pub async fn set_note(
+        &self,
+        result_id: String,
+        plugin_id: String,
+        title: String,
+        subtitle: String,
+        note: String,
+    ) -> Result<()> {
+        let note = note.trim().to_string();
+        if note.is_empty() {
+            return self.remove_note(&plugin_id, &result_id).await;
+        }
+        if !has_stable_id(&result_id) {
+            anyhow::bail!("Result {} has no stable id, notes are not supported", result_id);
+        }
+
+        let note: String = note.chars().take(MAX_NOTE_LENGTH).collect();
+
+        self.notes.write().await.insert((plugin_id.clone(), result_id.clone()), ResultNote {
+            result_id,
+            plugin_id,
+            title,
+            subtitle,
+            note,
+            updated_at: Utc::now(),
+        });
+
+        self.save().await
+    }
+
+    /// 删除备注
+    pub async fn remove_note(&self, plugin_id: &str, result_id: &str) -> Result<()> {
+        let removed = self.notes.write().await.remove(&note_key(plugin_id, result_id)).is_some();
+        if removed {
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    /// 获取单个备注
+    pub async fn get_note(&self, plugin_id: &str, result_id: &str) -> Option<ResultNote> {
+        self.notes.read().await.get(&note_key(plugin_id, result_id)).cloned()
+    }
+
+    /// 获取全部备注（按更新时间倒序）
+    pub async fn list_notes(&self) -> Vec<ResultNote> {
+        let mut notes: Vec<ResultNote> = self.notes.read().await.values().cloned().collect();
+        notes.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        notes
+    }
+
+    /// 用导入的数据替换全部备注
+    pub async fn replace_all(&self, notes: Vec<ResultNote>) -> Result<()> {
+        *self.notes.write().await = notes
+            .into_iter()
+            .map(|n| (note_key(&n.plugin_id, &n.result_id), n))
+            .collect();
+        self.save().await
+    }
+
+    /// 为查询结果附加备注：显示在副标题/预览中，并注入备注相关操作（仅限 ID 稳定的结果）；
+    /// 路径类插件中备注内容匹配查询但不在结果中的条目会作为额外结果返回
+    pub async fn apply_to_results(&self, results: &mut Vec<QueryResult>, query: &str) {
+        let notes = self.notes.read().await;
+
+        for result in results.iter_mut().filter(|r| has_stable_id(&r.id)) {
+            if let Some(note) = notes.get(&note_key(&result.plugin_id, &result.id)) {
+                Self::decorate(result, note);
+                Self::push_note_actions(result, true);
+            } else {
+                Self::push_note_actions(result, false);
+            }
+        }
+
+        let query_lower = query.trim().to_lowercase();
+        if query_lower.len() < 2 {
+            return;
+        }
+
+        for note in notes.values() {
+            if !PATH_PLUGIN_IDS.contains(&note.plugin_id.as_str()) || !note.note.to_lowercase().contains(&query_lower) {
+                continue;
+            }
+            if results.iter().any(|r| r.plugin_id == note.plugin_id && r.id == note.result_id) {
+                continue;
+            }
+
+            let mut result = QueryResult::new(note.title.clone())
+                .with_subtitle(note.subtitle.clone())
+                .with_icon(WoxImage::emoji("📝"))
+                .with_score(60);
+            result.id = note.result_id.clone();
+            result.plugin_id = note.plugin_id.clone();
+            result.context_data = serde_json::json!({
+                "path": note.result_id,
+                "from_note": true,
+            });
+            result.actions.push(Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                icon: None,
+                is_default: true,
+                hotkey: None,
+                prevent_hide: false,
+            });
+            Self::decorate(&mut result, note);
+            Self::push_note_actions(&mut result, true);
+            results.push(result);
+        }
+    }
+
+    /// 把备注写入副标题和预览
+    fn decorate(result: &mut QueryResult, note: &ResultNote) {
+        result.subtitle = if result.subtitle.is_empty() {
+            format!("📝 {}", note.note)
+        } else {
+            format!("📝 {} | {}", note.note, result.subtitle)
+        };
+
+        let note_block = format!("📝 Note ({})\n{}", note.updated_at.format("%Y-%m-%d %H:%M"), note.note);
+        result.preview = Some(match result.preview.take() {
+            Some(Preview::Text(text)) => Preview::Text(format!("{}\n\n{}", note_block, text)),
+            Some(Preview::Markdown(md)) => Preview::Markdown(format!("> {}\n\n{}", note.note, md)),
+            Some(other) => other,
+            None => Preview::Text(note_block),
+        });
+    }
+
+    /// 注入添加/编辑/删除备注操作
+    fn push_note_actions(result: &mut QueryResult, has_note: bool) {
+        if result.actions.iter().any(|a| a.id == ACTION_EDIT_NOTE) {
+            return;
+        }
+
+        result.actions.push(Action {
+            id: ACTION_EDIT_NOTE.to_string(),
+            name: if has_note { "Edit Note" } else { "Add Note" }.to_string(),
+            icon: Some(WoxImage::emoji("📝")),
+            is_default: false,
+            hotkey: None,
+            prevent_hide: true,
+        });
+
+        if has_note {
+            result.actions.push(Action {
+                id: ACTION_REMOVE_NOTE.to_string(),
+                name: "Remove Note".to_string(),
+                icon: Some(WoxImage::emoji("🗑️")),
+                is_default: false,
+                hotkey: None,
+                prevent_hide: true,
+            });
+        }
+    }
+
+    /// 保存备注
+    async fn save(&self) -> Result<()> {
+        let notes: Vec<ResultNote> = self.notes.read().await.values().cloned().collect();
+        let storage_path = self.storage_path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = std::path::Path::new(&storage_path).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(&notes)?;
+            std::fs::write(&storage_path, json)?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// 从磁盘加载备注
+    fn load_from_disk(storage_path: &str) -> Result<HashMap<NoteKey, ResultNote>> {
+        if !std::path::Path::new(storage_path).exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content = std::fs::read_to_string(storage_path)?;
+        let notes: Vec<ResultNote> = serde_json::from_str(&content)?;
+
+        Ok(notes.into_iter().map(|n| (note_key(&n.plugin_id, &n.result_id), n)).collect())
+    }
+}
+
+fn note_key(plugin_id: &str, result_id: &str) -> NoteKey {
+    (plugin_id.to_string(), result_id.to_string())
+}
+
+/// QueryResult::new 生成的随机 UUID 每次查询都会变化，无法关联备注
+fn has_stable_id(result_id: &str) -> bool {
+    !result_id.is_empty() && uuid::Uuid::parse_str(result_id).is_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_manager(name: &str) -> ResultNotesManager {
+        let path = std::env::temp_dir().join(format!("ilauncher_notes_{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        ResultNotesManager::new(path.to_string_lossy().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_note_decorates_result_and_is_searchable() {
+        let manager = temp_manager("decorate");
+        manager
+            .set_note("C:\\docs\\report.docx".into(), "file_search".into(), "report.docx".into(), "C:\\docs".into(), "Q3 budget draft".into())
+            .await
+            .unwrap();
+
+        let mut results = vec![QueryResult::new("report.docx").with_subtitle("C:\\docs")];
+        results[0].id = "C:\\docs\\report.docx".to_string();
+        results[0].plugin_id = "file_search".to_string();
+        manager.apply_to_results(&mut results, "report").await;
+        assert!(results[0].subtitle.starts_with("📝 Q3 budget draft"));
+        assert!(results[0].actions.iter().any(|a| a.id == ACTION_REMOVE_NOTE));
+
+        let mut results = Vec::new();
+        manager.apply_to_results(&mut results, "budget").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].plugin_id, "file_search");
+    }
+
+    #[tokio::test]
+    async fn test_empty_note_removes_entry() {
+        let manager = temp_manager("remove");
+        manager.set_note("id".into(), "app".into(), "t".into(), String::new(), "hi".into()).await.unwrap();
+        manager.set_note("id".into(), "app".into(), "t".into(), String::new(), "  ".into()).await.unwrap();
+        assert!(manager.get_note("app", "id").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notes_scoped_to_plugin_and_stable_ids() {
+        let manager = temp_manager("scoped");
+        manager.set_note("google".into(), "web_search".into(), "Google".into(), String::new(), "search engine".into()).await.unwrap();
+        assert!(manager.get_note("bookmark", "google").await.is_none());
+
+        let mut results = vec![QueryResult::new("Google"), QueryResult::new("Google")];
+        results[0].id = "google".to_string();
+        results[0].plugin_id = "bookmark".to_string();
+        results[1].plugin_id = "web_search".to_string();
+        manager.apply_to_results(&mut results, "engine").await;
+        assert!(!results[0].subtitle.starts_with("📝"));
+        // 随机 ID 的结果不提供备注操作，非路径插件的备注不生成额外结果
+        assert!(!results[1].actions.iter().any(|a| a.id == ACTION_EDIT_NOTE));
+        assert_eq!(results.len(), 2);
+
+        let uuid = QueryResult::new("x").id;
+        assert!(manager.set_note(uuid, "calculator".into(), "x".into(), String::new(), "n".into()).await.is_err());
+    }
+}
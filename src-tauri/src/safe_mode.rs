@@ -0,0 +1,206 @@
+// 安全模式与恢复 - 通过 --safe-mode 参数或连续 3 次启动失败进入安全模式：
+// 只加载必要插件、使用默认配置、暂停文件索引；并提供按存储单独重置（配置/统计/剪贴板/索引）的恢复命令
+
+use crate::utils::paths;
+use anyhow::Result;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// 命令行参数
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+/// 连续启动失败达到该次数后自动进入安全模式
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// 启动后稳定运行这么久才算启动成功
+pub const STABLE_AFTER: Duration = Duration::from_secs(20);
+
+/// 安全模式下仍然加载的内置插件
+pub const ESSENTIAL_PLUGINS: &[&str] = &[
+    "calculator",
+    "app_search",
+    "system-commands",
+    "ilauncher.plugin.settings",
+    "ilauncher.plugin.plugin_manager",
+];
+
+/// 启动状态文件（位于默认数据根目录，数据目录重定向失效时也能记录）
+const STARTUP_STATE_FILE: &str = "startup_state.json";
+
+/// 本次运行的安全模式状态（启动时确定）
+static STATUS: OnceCell<SafeModeStatus> = OnceCell::new();
+
+/// 进入安全模式的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafeModeReason {
+    /// 命令行参数 --safe-mode
+    CliFlag,
+    /// 连续启动失败
+    RepeatedFailures,
+}
+
+/// 安全模式状态（返回给前端）
+#[derive(Debug, Clone, Serialize)]
+pub struct SafeModeStatus {
+    pub active: bool,
+    pub reason: Option<SafeModeReason>,
+    /// 本次启动前连续失败的次数
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StartupState {
+    #[serde(default)]
+    consecutive_failures: u32,
+}
+
+/// 可单独重置的存储
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryStore {
+    Config,
+    Stats,
+    Clipboard,
+    Indexes,
+}
+
+/// 启动时调用：记录本次启动尝试并决定是否进入安全模式
+pub fn begin_startup(args: &[String]) -> &'static SafeModeStatus {
+    STATUS.get_or_init(|| {
+        let state_path = paths::get_default_app_data_dir().ok().map(|dir| dir.join(STARTUP_STATE_FILE));
+        let mut state = state_path.as_deref().map(read_state).unwrap_or_default();
+
+        let status = decide(args.iter().any(|a| a == SAFE_MODE_FLAG), state.consecutive_failures);
+
+        // 先记为失败，稳定运行后再清零（崩溃或卡死时计数保留）
+        state.consecutive_failures += 1;
+        if let Some(path) = &state_path {
+            if let Err(e) = write_state(path, &state) {
+                tracing::warn!("Failed to write startup state: {}", e);
+            }
+        }
+
+        if let Some(reason) = status.reason {
+            tracing::warn!(
+                "🛟 Starting in safe mode ({:?}, {} previous failed starts)",
+                reason,
+                status.consecutive_failures
+            );
+        }
+        status
+    })
+}
+
+fn decide(cli_flag: bool, consecutive_failures: u32) -> SafeModeStatus {
+    let reason = if cli_flag {
+        Some(SafeModeReason::CliFlag)
+    } else if consecutive_failures >= FAILURE_THRESHOLD {
+        Some(SafeModeReason::RepeatedFailures)
+    } else {
+        None
+    };
+    SafeModeStatus {
+        active: reason.is_some(),
+        reason,
+        consecutive_failures,
+    }
+}
+
+/// 本次启动成功（稳定运行或正常退出），清零失败计数
+pub fn mark_startup_succeeded() {
+    let Ok(dir) = paths::get_default_app_data_dir() else {
+        return;
+    };
+    let path = dir.join(STARTUP_STATE_FILE);
+    if read_state(&path).consecutive_failures == 0 {
+        return;
+    }
+    if let Err(e) = write_state(&path, &StartupState::default()) {
+        tracing::warn!("Failed to reset startup state: {}", e);
+    }
+}
+
+/// 当前是否处于安全模式
+pub fn is_active() -> bool {
+    STATUS.get().is_some_and(|status| status.active)
+}
+
+/// 当前安全模式状态
+pub fn status() -> SafeModeStatus {
+    STATUS.get().cloned().unwrap_or_else(|| decide(false, 0))
+}
+
+/// 插件在安全模式下是否加载
+pub fn allows_plugin(plugin_id: &str) -> bool {
+    !is_active() || ESSENTIAL_PLUGINS.contains(&plugin_id)
+}
+
+fn read_state(path: &Path) -> StartupState {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(path: &Path, state: &StartupState) -> Result<()> {
+    std::fs::write(path, serde_json::to_string(state)?)?;
+    Ok(())
+}
+
+/// 删除文件索引（MFT 数据库和 BFS 文件缓存），返回删除的文件数
+pub fn reset_indexes() -> Result<usize> {
+    let mut removed = 0;
+    let mft_dir = paths::get_mft_database_dir()?;
+    for entry in std::fs::read_dir(&mft_dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            std::fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+
+    let bfs_cache = paths::get_cache_dir()?.join("file_index.bin");
+    if bfs_cache.exists() {
+        std::fs::remove_file(&bfs_cache)?;
+        removed += 1;
+    }
+
+    tracing::info!("🛟 Removed {} index files", removed);
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        assert!(!decide(false, 0).active);
+        assert!(!decide(false, FAILURE_THRESHOLD - 1).active);
+        assert_eq!(decide(false, FAILURE_THRESHOLD).reason, Some(SafeModeReason::RepeatedFailures));
+        assert_eq!(decide(true, FAILURE_THRESHOLD).reason, Some(SafeModeReason::CliFlag));
+    }
+
+    #[test]
+    fn test_startup_state_round_trip() {
+        let path = std::env::temp_dir().join(format!("ilauncher_startup_{}.json", uuid::Uuid::new_v4()));
+        assert_eq!(read_state(&path).consecutive_failures, 0);
+
+        write_state(&path, &StartupState { consecutive_failures: 2 }).unwrap();
+        assert_eq!(read_state(&path).consecutive_failures, 2);
+
+        std::fs::write(&path, b"not json").unwrap();
+        assert_eq!(read_state(&path).consecutive_failures, 0);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_recovery_store_names() {
+        let store: RecoveryStore = serde_json::from_str("\"clipboard\"").unwrap();
+        assert_eq!(store, RecoveryStore::Clipboard);
+        assert!(serde_json::from_str::<RecoveryStore>("\"everything\"").is_err());
+    }
+}
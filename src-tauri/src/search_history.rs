@@ -1,7 +1,10 @@
 // 搜索历史管理器
 
+use crate::core::clock::{self, SharedClock};
+use crate::storage::backend::{FileStorage, Storage};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
@@ -21,22 +24,39 @@ pub struct SearchHistoryItem {
 
 pub struct SearchHistoryManager {
     history: Arc<RwLock<Vec<SearchHistoryItem>>>,
-    storage_path: String,
+    storage: Arc<dyn Storage>,
+    key: String,
+    clock: SharedClock,
 }
 
 impl SearchHistoryManager {
     pub fn new(storage_path: String) -> Self {
-        let manager = Self {
-            history: Arc::new(RwLock::new(Vec::new())),
-            storage_path,
-        };
+        let path = Path::new(&storage_path);
+        let root = path.parent().unwrap_or_else(|| Path::new("."));
+        let key = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "search_history.json".to_string());
+        
+        Self::with_storage(Arc::new(FileStorage::new(root)), key, clock::system())
+    }
+    
+    /// 使用指定的存储后端和时钟创建
+    pub fn with_storage(storage: Arc<dyn Storage>, key: impl Into<String>, clock: SharedClock) -> Self {
+        let key = key.into();
         
         // 尝试加载历史记录
-        if let Err(e) = manager.load_blocking() {
+        let history = Self::load(storage.as_ref(), &key).unwrap_or_else(|e| {
             tracing::warn!("Failed to load search history: {}", e);
-        }
+            Vec::new()
+        });
         
-        manager
+        Self {
+            history: Arc::new(RwLock::new(history)),
+            storage,
+            key,
+            clock,
+        }
     }
     
     /// 添加搜索记录
@@ -51,13 +71,13 @@ impl SearchHistoryManager {
         if let Some(existing) = history.iter_mut().find(|item| item.query == query) {
             // 更新频率和时间戳
             existing.frequency += 1;
-            existing.timestamp = Utc::now();
+            existing.timestamp = self.clock.now();
             existing.result_count = result_count;
         } else {
             // 添加新记录
             history.insert(0, SearchHistoryItem {
                 query,
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
                 result_count,
                 frequency: 1,
                 last_executed: None,
@@ -82,7 +102,7 @@ impl SearchHistoryManager {
         let mut history = self.history.write().await;
         
         if let Some(item) = history.iter_mut().find(|item| item.query == query) {
-            item.last_executed = Some(Utc::now());
+            item.last_executed = Some(self.clock.now());
             item.frequency += 1;
         }
         
@@ -97,7 +117,7 @@ impl SearchHistoryManager {
         let mut history = self.history.read().await.clone();
         
         // 智能排序算法：综合考虑频率、时效性、执行情况
-        let now = Utc::now();
+        let now = self.clock.now();
         history.sort_by(|a, b| {
             let score_a = calculate_relevance_score(a, &now);
             let score_b = calculate_relevance_score(b, &now);
@@ -141,40 +161,21 @@ impl SearchHistoryManager {
     
     /// 保存历史记录
     async fn save(&self) -> Result<()> {
-        let history = self.history.read().await.clone();
-        let storage_path = self.storage_path.clone();
-        
-        tokio::task::spawn_blocking(move || {
-            if let Some(parent) = std::path::Path::new(&storage_path).parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            let json = serde_json::to_string_pretty(&history)?;
-            std::fs::write(&storage_path, json)?;
-            Ok::<(), anyhow::Error>(())
-        })
-        .await??;
+        let json = serde_json::to_vec_pretty(&*self.history.read().await)?;
+        let storage = self.storage.clone();
+        let key = self.key.clone();
+        
+        tokio::task::spawn_blocking(move || storage.write(&key, &json)).await??;
         
         Ok(())
     }
     
-    /// 加载历史记录（同步版本，用于初始化）
-    fn load_blocking(&self) -> Result<()> {
-        if !std::path::Path::new(&self.storage_path).exists() {
-            return Ok(());
-        }
-        
-        let content = std::fs::read_to_string(&self.storage_path)?;
-        let history: Vec<SearchHistoryItem> = serde_json::from_str(&content)?;
-        
-        // 使用 blocking API 写入
-        let rt = tokio::runtime::Handle::try_current();
-        if let Ok(handle) = rt {
-            handle.block_on(async {
-                *self.history.write().await = history;
-            });
+    /// 加载历史记录（同步，用于初始化）
+    fn load(storage: &dyn Storage, key: &str) -> Result<Vec<SearchHistoryItem>> {
+        match storage.read(key)? {
+            Some(content) => Ok(serde_json::from_slice(&content)?),
+            None => Ok(Vec::new()),
         }
-        
-        Ok(())
     }
 }
 
@@ -216,3 +217,69 @@ fn calculate_relevance_score(item: &SearchHistoryItem, now: &DateTime<Utc>) -> f
     // 综合得分
     frequency_score + recency_score + execution_bonus
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::{Clock, FakeClock};
+    use crate::storage::backend::MemoryStorage;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_history_persists_to_storage() {
+        let storage = MemoryStorage::new();
+        let clock = FakeClock::fixed();
+        let manager = SearchHistoryManager::with_storage(storage.clone(), "history.json", clock.clone());
+
+        manager.add("rust".to_string(), 3).await.unwrap();
+        manager.add("rust".to_string(), 5).await.unwrap();
+        manager.add("  ".to_string(), 1).await.unwrap();
+
+        let reloaded = SearchHistoryManager::with_storage(storage.clone(), "history.json", clock.clone());
+        let history = reloaded.get_history().await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].frequency, 2);
+        assert_eq!(history[0].result_count, 5);
+        assert_eq!(history[0].timestamp, clock.now());
+        assert_eq!(storage.keys(), vec!["history.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_recency_decay_ordering() {
+        let clock = FakeClock::fixed();
+        let manager = SearchHistoryManager::with_storage(MemoryStorage::new(), "history.json", clock.clone());
+
+        // "old" 使用频率更高，但一周后 "new" 凭时效性排在前面
+        for _ in 0..3 {
+            manager.add("old".to_string(), 1).await.unwrap();
+        }
+        clock.advance(Duration::days(7));
+        manager.add("new".to_string(), 1).await.unwrap();
+
+        let history = manager.get_history().await;
+        assert_eq!(history[0].query, "new");
+
+        // 再过一个月，频率重新占优
+        clock.advance(Duration::days(30));
+        let history = manager.get_history().await;
+        assert_eq!(history[0].query, "old");
+    }
+
+    #[test]
+    fn test_relevance_score_decay() {
+        let clock = FakeClock::fixed();
+        let now = clock.now();
+        let item = SearchHistoryItem {
+            query: "q".to_string(),
+            timestamp: now,
+            result_count: 0,
+            frequency: 1,
+            last_executed: None,
+        };
+
+        assert_eq!(calculate_relevance_score(&item, &now), 110.0);
+        assert_eq!(calculate_relevance_score(&item, &(now + Duration::hours(2))), 35.0);
+        assert_eq!(calculate_relevance_score(&item, &(now + Duration::days(2))), 20.0);
+        assert_eq!(calculate_relevance_score(&item, &(now + Duration::days(14))), 12.5);
+    }
+}
@@ -0,0 +1,161 @@
+// 会话上下文 - 供前端底部状态栏渲染的轻量状态（ans、计时器、剪贴板数量、索引状态、搜索模式）
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// 推送给前端的事件名
+pub const SESSION_CONTEXT_EVENT: &str = "session-context:updated";
+
+/// 全局会话上下文（插件与命令共享）
+pub static SESSION_CONTEXT: Lazy<SessionContextManager> = Lazy::new(SessionContextManager::new);
+
+/// 活动计时器
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveTimer {
+    pub id: String,
+    pub label: String,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// 索引状态
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IndexingStatus {
+    pub is_indexing: bool,
+    pub indexed_files: u64,
+    pub message: String,
+}
+
+/// 会话上下文快照
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SessionContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_answer: Option<String>,
+    pub active_timers: Vec<ActiveTimer>,
+    pub clipboard_count: usize,
+    pub indexing: IndexingStatus,
+    pub search_mode: String,
+}
+
+/// 会话上下文管理器
+pub struct SessionContextManager {
+    state: RwLock<SessionContext>,
+    app_handle: OnceCell<AppHandle>,
+}
+
+impl SessionContextManager {
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(SessionContext {
+                search_mode: "bfs".to_string(),
+                ..Default::default()
+            }),
+            app_handle: OnceCell::new(),
+        }
+    }
+
+    /// 绑定 AppHandle（setup 阶段调用一次），之后的变更会推送到前端
+    pub fn attach(&self, app_handle: AppHandle) {
+        if self.app_handle.set(app_handle).is_err() {
+            tracing::warn!("Session context already attached to app handle");
+        }
+    }
+
+    /// 获取当前快照（顺便清理已到期的计时器）
+    pub fn snapshot(&self) -> SessionContext {
+        let now = Utc::now();
+        let mut state = self.state.write();
+        state.active_timers.retain(|t| t.ends_at > now);
+        state.clone()
+    }
+
+    /// 记录计算器最近一次结果
+    pub fn set_last_answer(&self, answer: impl Into<String>) {
+        let answer = answer.into();
+        let changed = {
+            let mut state = self.state.write();
+            if state.last_answer.as_deref() == Some(answer.as_str()) {
+                false
+            } else {
+                state.last_answer = Some(answer);
+                true
+            }
+        };
+        if changed {
+            self.push();
+        }
+    }
+
+    /// 获取计算器最近一次结果
+    pub fn last_answer(&self) -> Option<String> {
+        self.state.read().last_answer.clone()
+    }
+
+    /// 添加计时器
+    pub fn add_timer(&self, timer: ActiveTimer) {
+        self.state.write().active_timers.push(timer);
+        self.push();
+    }
+
+    /// 移除计时器
+    pub fn remove_timer(&self, id: &str) {
+        let removed = {
+            let mut state = self.state.write();
+            let before = state.active_timers.len();
+            state.active_timers.retain(|t| t.id != id);
+            state.active_timers.len() != before
+        };
+        if removed {
+            self.push();
+        }
+    }
+
+    /// 更新剪贴板条目数量
+    pub fn set_clipboard_count(&self, count: usize) {
+        let changed = {
+            let mut state = self.state.write();
+            let changed = state.clipboard_count != count;
+            state.clipboard_count = count;
+            changed
+        };
+        if changed {
+            self.push();
+        }
+    }
+
+    /// 更新索引状态
+    pub fn set_indexing(&self, indexing: IndexingStatus) {
+        self.state.write().indexing = indexing;
+        self.push();
+    }
+
+    /// 更新搜索模式（mft / bfs）
+    pub fn set_search_mode(&self, mode: impl Into<String>) {
+        let mode = mode.into();
+        let changed = {
+            let mut state = self.state.write();
+            let changed = state.search_mode != mode;
+            state.search_mode = mode;
+            changed
+        };
+        if changed {
+            self.push();
+        }
+    }
+
+    /// 推送快照到前端
+    fn push(&self) {
+        if let Some(app_handle) = self.app_handle.get() {
+            let _ = app_handle.emit(SESSION_CONTEXT_EVENT, self.snapshot());
+        }
+    }
+}
+
+impl Default for SessionContextManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
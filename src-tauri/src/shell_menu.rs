@@ -0,0 +1,305 @@
+// 系统右键菜单 - 枚举资源管理器为文件提供的真实菜单项（IContextMenu），作为动态操作暴露
+// 菜单每次重新构建：枚举时记录命令偏移和规范动词，执行时按动词（优先）或偏移定位
+
+use crate::core::types::Action;
+use anyhow::Result;
+use std::path::Path;
+
+/// 右键菜单操作 ID 前缀（execute_action 中由后端直接处理）
+pub const SHELL_ACTION_PREFIX: &str = "shell:";
+
+/// 菜单项 ID 范围起点（QueryContextMenu 的 idCmdFirst）
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const ID_FIRST: u32 = 1;
+
+/// 单个右键菜单项
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShellMenuItem {
+    /// 相对 ID_FIRST 的命令偏移
+    offset: u32,
+    /// 规范动词（如 open、properties），部分扩展不提供
+    verb: Option<String>,
+    name: String,
+}
+
+impl ShellMenuItem {
+    fn action_id(&self) -> String {
+        match &self.verb {
+            Some(verb) => format!("{}{}:{}", SHELL_ACTION_PREFIX, self.offset, verb),
+            None => format!("{}{}", SHELL_ACTION_PREFIX, self.offset),
+        }
+    }
+}
+
+/// 解析操作 ID，返回 (偏移, 动词)
+fn parse_action_id(action_id: &str) -> Option<(u32, Option<&str>)> {
+    let rest = action_id.strip_prefix(SHELL_ACTION_PREFIX)?;
+    match rest.split_once(':') {
+        Some((offset, verb)) => Some((offset.parse().ok()?, Some(verb).filter(|v| !v.is_empty()))),
+        None => Some((rest.parse().ok()?, None)),
+    }
+}
+
+/// 清理菜单文本：去掉加速键标记（&）、快捷键说明（\t 之后）和末尾省略号
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn clean_menu_text(raw: &str) -> String {
+    let text = raw.split('\t').next().unwrap_or_default();
+    let mut cleaned = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            // "&&" 表示字面量 &
+            if chars.peek() == Some(&'&') {
+                cleaned.push('&');
+                chars.next();
+            }
+            continue;
+        }
+        cleaned.push(c);
+    }
+    cleaned.trim().trim_end_matches("...").trim_end_matches('…').trim().to_string()
+}
+
+/// 获取文件的系统右键菜单操作
+pub fn list_actions(path: &Path) -> Result<Vec<Action>> {
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", path.display()));
+    }
+
+    let items = platform::enumerate(path)?;
+    Ok(items
+        .into_iter()
+        .map(|item| Action {
+            id: item.action_id(),
+            name: item.name,
+            icon: None,
+            is_default: false,
+            hotkey: None,
+            prevent_hide: false,
+        })
+        .collect())
+}
+
+/// 执行系统右键菜单项
+pub fn invoke(path: &Path, action_id: &str) -> Result<()> {
+    let (offset, verb) =
+        parse_action_id(action_id).ok_or_else(|| anyhow::anyhow!("Invalid shell action: {}", action_id))?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", path.display()));
+    }
+
+    tracing::info!("🖱️ Invoking shell verb {:?} (offset {}) on {}", verb, offset, path.display());
+    platform::invoke(path, offset, verb.map(str::to_string))
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{clean_menu_text, ShellMenuItem, ID_FIRST};
+    use anyhow::Result;
+    use std::path::{Path, PathBuf};
+    use windows::core::{HSTRING, PCSTR, PSTR, PWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{CoInitializeEx, CoTaskMemFree, CoUninitialize, COINIT_APARTMENTTHREADED};
+    use windows::Win32::UI::Shell::Common::ITEMIDLIST;
+    use windows::Win32::UI::Shell::{
+        IContextMenu, IShellFolder, SHBindToParent, SHParseDisplayName, CMF_NORMAL, CMINVOKECOMMANDINFO, GCS_VERBW,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreatePopupMenu, DestroyMenu, GetMenuItemCount, GetMenuItemInfoW, HMENU, MENUITEMINFOW, MFS_DISABLED,
+        MFT_OWNERDRAW, MFT_SEPARATOR, MIIM_FTYPE, MIIM_ID, MIIM_STATE, MIIM_STRING, MIIM_SUBMENU, SW_SHOWNORMAL,
+    };
+
+    /// 菜单项 ID 范围终点
+    const ID_LAST: u32 = 0x7FFF;
+
+    /// 右键菜单（销毁时释放菜单和 PIDL）
+    struct ContextMenu {
+        menu: IContextMenu,
+        hmenu: HMENU,
+        pidl: *mut ITEMIDLIST,
+    }
+
+    impl ContextMenu {
+        unsafe fn build(path: &Path) -> Result<Self> {
+            let mut pidl: *mut ITEMIDLIST = std::ptr::null_mut();
+            SHParseDisplayName(&HSTRING::from(path.as_os_str()), None, &mut pidl, 0, None)?;
+
+            let built = (|| {
+                let mut child: *mut ITEMIDLIST = std::ptr::null_mut();
+                let folder: IShellFolder = SHBindToParent(pidl, Some(&mut child))?;
+                let menu: IContextMenu = folder.GetUIObjectOf(HWND::default(), &[child as *const _], None)?;
+                let hmenu = CreatePopupMenu()?;
+                if let Err(e) = menu.QueryContextMenu(hmenu, 0, ID_FIRST, ID_LAST, CMF_NORMAL) {
+                    let _ = DestroyMenu(hmenu);
+                    return Err(e.into());
+                }
+                Ok::<_, anyhow::Error>((menu, hmenu))
+            })();
+
+            match built {
+                Ok((menu, hmenu)) => Ok(Self { menu, hmenu, pidl }),
+                Err(e) => {
+                    CoTaskMemFree(Some(pidl as *const _));
+                    Err(e)
+                }
+            }
+        }
+
+        /// 递归收集可用菜单项（子菜单项名称带上父级前缀）
+        unsafe fn collect(&self, hmenu: HMENU, prefix: &str, items: &mut Vec<ShellMenuItem>) {
+            for position in 0..GetMenuItemCount(hmenu).max(0) as u32 {
+                let mut info = MENUITEMINFOW {
+                    cbSize: std::mem::size_of::<MENUITEMINFOW>() as u32,
+                    fMask: MIIM_FTYPE | MIIM_ID | MIIM_STATE | MIIM_STRING | MIIM_SUBMENU,
+                    ..Default::default()
+                };
+                if GetMenuItemInfoW(hmenu, position, true, &mut info).is_err() {
+                    continue;
+                }
+                if (info.fType & (MFT_SEPARATOR | MFT_OWNERDRAW)).0 != 0 || (info.fState & MFS_DISABLED).0 != 0 {
+                    continue;
+                }
+
+                // 第二次调用取文本
+                let mut buffer = vec![0u16; info.cch as usize + 1];
+                info.dwTypeData = PWSTR(buffer.as_mut_ptr());
+                info.cch += 1;
+                if GetMenuItemInfoW(hmenu, position, true, &mut info).is_err() {
+                    continue;
+                }
+                let name = clean_menu_text(&String::from_utf16_lossy(&buffer[..info.cch as usize]));
+                if name.is_empty() {
+                    continue;
+                }
+                let name = if prefix.is_empty() { name } else { format!("{} › {}", prefix, name) };
+
+                if !info.hSubMenu.is_invalid() {
+                    self.collect(info.hSubMenu, &name, items);
+                } else if info.wID >= ID_FIRST && info.wID <= ID_LAST {
+                    let offset = info.wID - ID_FIRST;
+                    items.push(ShellMenuItem {
+                        offset,
+                        verb: self.verb(offset),
+                        name,
+                    });
+                }
+            }
+        }
+
+        unsafe fn verb(&self, offset: u32) -> Option<String> {
+            let mut buffer = [0u16; 256];
+            self.menu
+                .GetCommandString(offset as usize, GCS_VERBW, None, PSTR(buffer.as_mut_ptr() as *mut u8), buffer.len() as u32)
+                .ok()?;
+            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+            Some(String::from_utf16_lossy(&buffer[..len])).filter(|verb| !verb.is_empty())
+        }
+
+        unsafe fn invoke(&self, offset: u32) -> Result<()> {
+            let info = CMINVOKECOMMANDINFO {
+                cbSize: std::mem::size_of::<CMINVOKECOMMANDINFO>() as u32,
+                // MAKEINTRESOURCE(offset)
+                lpVerb: PCSTR(offset as usize as *const u8),
+                nShow: SW_SHOWNORMAL.0,
+                ..Default::default()
+            };
+            self.menu.InvokeCommand(&info)?;
+            Ok(())
+        }
+    }
+
+    impl Drop for ContextMenu {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = DestroyMenu(self.hmenu);
+                CoTaskMemFree(Some(self.pidl as *const _));
+            }
+        }
+    }
+
+    /// 在独立 STA 线程中执行（Shell 扩展要求单线程单元）
+    fn with_sta<T: Send + 'static>(task: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+        std::thread::spawn(move || unsafe {
+            CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok()?;
+            let result = task();
+            CoUninitialize();
+            result
+        })
+        .join()
+        .map_err(|_| anyhow::anyhow!("Shell context menu thread panicked"))?
+    }
+
+    pub fn enumerate(path: &Path) -> Result<Vec<ShellMenuItem>> {
+        let path = path.to_path_buf();
+        with_sta(move || unsafe {
+            let menu = ContextMenu::build(&path)?;
+            let mut items = Vec::new();
+            menu.collect(menu.hmenu, "", &mut items);
+            Ok(items)
+        })
+    }
+
+    pub fn invoke(path: &Path, offset: u32, verb: Option<String>) -> Result<()> {
+        let path: PathBuf = path.to_path_buf();
+        with_sta(move || unsafe {
+            let menu = ContextMenu::build(&path)?;
+            // 菜单重建后偏移可能变化，有动词时按动词重新定位
+            let offset = match verb {
+                Some(verb) => {
+                    let mut items = Vec::new();
+                    menu.collect(menu.hmenu, "", &mut items);
+                    items
+                        .iter()
+                        .find(|item| item.verb.as_deref() == Some(verb.as_str()))
+                        .map(|item| item.offset)
+                        .ok_or_else(|| anyhow::anyhow!("Shell verb '{}' is no longer available", verb))?
+                }
+                None => offset,
+            };
+            menu.invoke(offset)
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use super::ShellMenuItem;
+    use anyhow::Result;
+    use std::path::Path;
+
+    /// 非 Windows 平台没有 Shell 扩展菜单
+    pub fn enumerate(_path: &Path) -> Result<Vec<ShellMenuItem>> {
+        Ok(Vec::new())
+    }
+
+    pub fn invoke(_path: &Path, _offset: u32, _verb: Option<String>) -> Result<()> {
+        Err(anyhow::anyhow!("Shell context menu is only supported on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_menu_text() {
+        assert_eq!(clean_menu_text("&Open"), "Open");
+        assert_eq!(clean_menu_text("Open &with..."), "Open with");
+        assert_eq!(clean_menu_text("Save && Close\tCtrl+S"), "Save & Close");
+        assert_eq!(clean_menu_text("打开方式(&H)…"), "打开方式(H)");
+    }
+
+    #[test]
+    fn test_action_id_round_trip() {
+        let item = ShellMenuItem {
+            offset: 12,
+            verb: Some("extract".to_string()),
+            name: "Extract here".to_string(),
+        };
+        assert_eq!(item.action_id(), "shell:12:extract");
+        assert_eq!(parse_action_id(&item.action_id()), Some((12, Some("extract"))));
+        assert_eq!(parse_action_id("shell:3"), Some((3, None)));
+        assert_eq!(parse_action_id("open"), None);
+        assert_eq!(parse_action_id("shell:x"), None);
+    }
+}
@@ -0,0 +1,63 @@
+// 频率 + 新近度（frecency）模型 - 点击次数按最近使用时间加权，并由后台任务定期衰减长期未用的计数，
+// 避免早年累积的大量点击一直压在新常用结果之上
+
+use chrono::{DateTime, Duration, Utc};
+
+/// 超过这么多天未使用的计数会被减半（每经过一个周期衰减一次）
+pub const DECAY_AFTER_DAYS: i64 = 14;
+
+/// 后台衰减任务的执行间隔
+pub const DECAY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// 最近使用时间 → 权重（天数上限, 权重），参考浏览器地址栏的 frecency 分桶
+const RECENCY_BUCKETS: &[(i64, f64)] = &[(4, 100.0), (14, 70.0), (31, 50.0), (90, 30.0)];
+
+/// 超出所有分桶时的权重
+const STALE_WEIGHT: f64 = 10.0;
+
+/// 计算 frecency 值（点击次数 × 新近度权重）
+pub fn frecency(count: u32, last_used: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let age_days = now.signed_duration_since(last_used).num_days().max(0);
+    let weight = RECENCY_BUCKETS
+        .iter()
+        .find(|(max_days, _)| age_days < *max_days)
+        .map_or(STALE_WEIGHT, |(_, weight)| *weight);
+    count as f64 * weight
+}
+
+/// frecency 值 → 0-100 分（对数增长，单次近期点击约 50 分）
+pub fn frecency_score(frecency: f64) -> f64 {
+    if frecency <= 0.0 {
+        return 0.0;
+    }
+    ((frecency + 1.0).log10() * 25.0).min(100.0)
+}
+
+/// 衰减截止时间：最后使用和上次衰减都早于该时间的计数需要减半
+pub fn decay_cutoff(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::days(DECAY_AFTER_DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frecency_prefers_recent_use() {
+        let now = Utc::now();
+        let recent = frecency(3, now - Duration::hours(5), now);
+        let stale = frecency(20, now - Duration::days(200), now);
+        assert_eq!(recent, 300.0);
+        assert_eq!(stale, 200.0);
+        assert!(recent > stale);
+        // 时钟回拨时按刚使用处理
+        assert_eq!(frecency(1, now + Duration::days(1), now), 100.0);
+    }
+
+    #[test]
+    fn test_frecency_score() {
+        assert_eq!(frecency_score(0.0), 0.0);
+        assert!((frecency_score(100.0) - 50.1).abs() < 0.1);
+        assert_eq!(frecency_score(1e9), 100.0);
+    }
+}
@@ -0,0 +1,188 @@
+// statistics.db 结构迁移 - 以 PRAGMA user_version 记录当前版本，启动时按顺序执行未应用的迁移
+// 新增表或字段时在 MIGRATIONS 末尾追加，已发布的迁移不要修改
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+/// 一次结构迁移
+struct Migration {
+    description: &'static str,
+    sql: &'static str,
+}
+
+/// 迁移列表（下标 + 1 即迁移后的版本号）
+const MIGRATIONS: &[Migration] = &[
+    // v1：基础结构（引入迁移前的数据库已有这些表，均使用 IF NOT EXISTS）
+    Migration {
+        description: "baseline schema",
+        sql: "
+            CREATE TABLE IF NOT EXISTS queries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                query TEXT NOT NULL,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_queries_query ON queries(query);
+
+            CREATE TABLE IF NOT EXISTS result_clicks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                result_id TEXT NOT NULL,
+                plugin_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_result_clicks_result ON result_clicks(result_id);
+
+            CREATE TABLE IF NOT EXISTS plugin_usage (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                plugin_id TEXT NOT NULL,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_plugin_usage_plugin ON plugin_usage(plugin_id);
+
+            -- 工作区上下文独立的点击统计
+            CREATE TABLE IF NOT EXISTS context_result_clicks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                context_id TEXT NOT NULL,
+                result_id TEXT NOT NULL,
+                plugin_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_context_clicks_result ON context_result_clicks(context_id, result_id);
+
+            -- 查询 → 选择关联（规范化查询下选择了哪个结果）
+            CREATE TABLE IF NOT EXISTS query_selections (
+                query TEXT NOT NULL,
+                result_id TEXT NOT NULL,
+                plugin_id TEXT NOT NULL,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL,
+                PRIMARY KEY (query, result_id, plugin_id)
+            );
+
+            -- 用户置顶（规范化查询下固定在最前面的结果，position 越小越靠前）
+            CREATE TABLE IF NOT EXISTS ranking_overrides (
+                query TEXT NOT NULL,
+                result_id TEXT NOT NULL,
+                plugin_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (query, result_id, plugin_id)
+            );
+        ",
+    },
+    // v2：点击计数衰减时间（避免同一时间段内重复衰减）
+    Migration {
+        description: "click count decay timestamps",
+        sql: "
+            ALTER TABLE result_clicks ADD COLUMN decayed_at TEXT;
+            ALTER TABLE context_result_clicks ADD COLUMN decayed_at TEXT;
+        ",
+    },
+    // v3：没有任何结果的查询（用于推荐启用/安装相关插件）
+    Migration {
+        description: "zero-result queries",
+        sql: "
+            CREATE TABLE IF NOT EXISTS zero_result_queries (
+                query TEXT PRIMARY KEY,
+                count INTEGER DEFAULT 1,
+                last_used TEXT NOT NULL
+            );
+        ",
+    },
+];
+
+/// 最新的结构版本
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// 读取当前结构版本
+pub fn current_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// 执行所有未应用的迁移，返回迁移后的版本号
+///
+/// 每个迁移在独立事务中执行并同时更新 user_version，失败时回滚且不影响已完成的迁移
+pub fn migrate(conn: &mut Connection) -> Result<u32> {
+    let current = current_version(conn)?;
+    if current > LATEST_VERSION {
+        anyhow::bail!(
+            "statistics.db schema version {} is newer than supported version {}",
+            current,
+            LATEST_VERSION
+        );
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as u32 + 1;
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)
+            .with_context(|| format!("statistics migration v{} ({}) failed", version, migration.description))?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+        tracing::info!("📦 Migrated statistics.db to v{}: {}", version, migration.description);
+    }
+
+    Ok(LATEST_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+        let columns: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        columns.iter().any(|c| c == column)
+    }
+
+    #[test]
+    fn test_fresh_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(migrate(&mut conn).unwrap(), LATEST_VERSION);
+        assert_eq!(current_version(&conn).unwrap(), LATEST_VERSION);
+        assert!(has_column(&conn, "result_clicks", "decayed_at"));
+        assert!(has_column(&conn, "zero_result_queries", "query"));
+
+        // 再次执行不做任何事
+        assert_eq!(migrate(&mut conn).unwrap(), LATEST_VERSION);
+    }
+
+    #[test]
+    fn test_upgrade_pre_migration_database() {
+        // 引入迁移前创建的数据库：表已存在但 user_version 为 0
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0].sql).unwrap();
+        conn.execute(
+            "INSERT INTO result_clicks (result_id, plugin_id, title, count, last_used, created_at)
+             VALUES ('code', 'app', 'VS Code', 7, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+            [],
+        )
+        .unwrap();
+        assert_eq!(current_version(&conn).unwrap(), 0);
+
+        migrate(&mut conn).unwrap();
+        assert!(has_column(&conn, "context_result_clicks", "decayed_at"));
+        let count: i32 = conn.query_row("SELECT count FROM result_clicks", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", LATEST_VERSION + 1).unwrap();
+        assert!(migrate(&mut conn).is_err());
+    }
+}
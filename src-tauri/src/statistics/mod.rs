@@ -1,12 +1,28 @@
 // 使用统计系统 - 记录用户行为，智能排序结果
 
+pub mod frecency;
+mod migrations;
+mod query_buffer;
+pub mod query_selection;
+
+use crate::core::clock::{self, SharedClock};
+use crate::ranking::pins::ResultPin;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, params};
 use std::path::PathBuf;
+use query_buffer::{FinalQuery, QueryBuffer};
+use query_selection::QuerySelection;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// WAL 自动 checkpoint 的页数阈值
+const WAL_AUTOCHECKPOINT_PAGES: i64 = 1000;
+
+/// 无结果查询的最短长度（更短的多为输入中途）
+const ZERO_RESULT_MIN_CHARS: usize = 3;
+
 #[derive(Debug, Clone)]
 pub struct QueryStat {
     pub query: String,
@@ -25,69 +41,41 @@ pub struct ResultStat {
 
 pub struct StatisticsManager {
     db: Arc<Mutex<Connection>>,
+    clock: SharedClock,
+    /// 逐键输入的查询先在内存中合并，只批量写入最终查询
+    pending: parking_lot::Mutex<QueryBuffer>,
 }
 
 impl StatisticsManager {
     /// 创建统计管理器
     pub fn new() -> Result<Self> {
         let db_path = Self::get_db_path()?;
-        let conn = Connection::open(db_path)?;
-        
-        // 创建表
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS queries (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                query TEXT NOT NULL,
-                count INTEGER DEFAULT 1,
-                last_used TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_queries_query ON queries(query)",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS result_clicks (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                result_id TEXT NOT NULL,
-                plugin_id TEXT NOT NULL,
-                title TEXT NOT NULL,
-                count INTEGER DEFAULT 1,
-                last_used TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_result_clicks_result ON result_clicks(result_id)",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS plugin_usage (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                plugin_id TEXT NOT NULL,
-                count INTEGER DEFAULT 1,
-                last_used TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-        
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_plugin_usage_plugin ON plugin_usage(plugin_id)",
-            [],
-        )?;
-        
-        tracing::info!("Statistics database initialized");
+        Self::with_connection(Connection::open(db_path)?, clock::system())
+    }
+    
+    /// 使用内存数据库和指定时钟创建（测试用）
+    #[cfg(test)]
+    pub fn in_memory(clock: SharedClock) -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?, clock)
+    }
+    
+    /// 初始化表结构
+    fn with_connection(mut conn: Connection, clock: SharedClock) -> Result<Self> {
+        // WAL 模式：写入不阻塞读取，减少 fsync 次数
+        let journal_mode: String = conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.pragma_update(None, "wal_autocheckpoint", WAL_AUTOCHECKPOINT_PAGES)?;
+        tracing::debug!("Statistics database journal mode: {}", journal_mode);
+        
+        // 按 user_version 执行结构迁移
+        let version = migrations::migrate(&mut conn)?;
+        
+        tracing::info!("Statistics database initialized (schema v{})", version);
         
         Ok(Self {
             db: Arc::new(Mutex::new(conn)),
+            clock,
+            pending: parking_lot::Mutex::new(QueryBuffer::default()),
         })
     }
     
@@ -102,36 +90,49 @@ impl StatisticsManager {
         Ok(data_dir.join("statistics.db"))
     }
     
-    /// 记录查询
+    /// 记录查询（先缓存在内存中，攒够一批最终查询后才写入）
     pub async fn record_query(&self, query: &str) -> Result<()> {
-        let query = query.to_string();
+        let full = self.pending.lock().record(query, self.clock.now());
+        if full {
+            self.flush_queries().await?;
+        }
+        Ok(())
+    }
+    
+    /// 把正在输入的查询标记为最终查询（退出前调用）
+    pub fn finalize_pending_queries(&self) {
+        self.pending.lock().finalize();
+    }
+    
+    /// 把缓存的最终查询写入数据库（单个事务）
+    pub async fn flush_queries(&self) -> Result<()> {
+        let queries = self.pending.lock().drain(self.clock.now());
+        if queries.is_empty() {
+            return Ok(());
+        }
         let db = self.db.clone();
         
         tokio::task::spawn_blocking(move || {
-            let conn = db.blocking_lock();
-            let now = Utc::now().to_rfc3339();
+            let mut conn = db.blocking_lock();
+            let tx = conn.transaction()?;
             
-            // 查找是否已存在
-            let exists: bool = conn.query_row(
-                "SELECT EXISTS(SELECT 1 FROM queries WHERE query = ?1)",
-                params![&query],
-                |row| row.get(0),
-            )?;
-            
-            if exists {
-                // 更新计数和时间
-                conn.execute(
-                    "UPDATE queries SET count = count + 1, last_used = ?1 WHERE query = ?2",
-                    params![&now, &query],
-                )?;
-            } else {
-                // 插入新记录
-                conn.execute(
-                    "INSERT INTO queries (query, count, last_used, created_at) VALUES (?1, 1, ?2, ?2)",
-                    params![&query, &now],
+            for FinalQuery { query, count, last_used } in &queries {
+                let last_used = last_used.to_rfc3339();
+                let updated = tx.execute(
+                    "UPDATE queries SET count = count + ?1, last_used = MAX(last_used, ?2) WHERE query = ?3",
+                    params![count, &last_used, query],
                 )?;
+                
+                if updated == 0 {
+                    tx.execute(
+                        "INSERT INTO queries (query, count, last_used, created_at) VALUES (?1, ?2, ?3, ?3)",
+                        params![query, count, &last_used],
+                    )?;
+                }
             }
             
+            tx.commit()?;
+            tracing::debug!("Flushed {} query statistics", queries.len());
             Ok::<(), anyhow::Error>(())
         })
         .await??;
@@ -141,14 +142,22 @@ impl StatisticsManager {
     
     /// 记录结果点击
     pub async fn record_result_click(&self, result_id: &str, plugin_id: &str, title: &str) -> Result<()> {
+        // 执行结果说明当前输入的查询是最终查询，同时记录该查询下选择了这个结果
+        let query = {
+            let mut pending = self.pending.lock();
+            let query = pending.typing().map(query_selection::normalize_query);
+            pending.finalize();
+            query.filter(|q| !q.is_empty())
+        };
+        
         let result_id = result_id.to_string();
         let plugin_id = plugin_id.to_string();
         let title = title.to_string();
         let db = self.db.clone();
+        let now = self.clock.now().to_rfc3339();
         
         tokio::task::spawn_blocking(move || {
             let conn = db.blocking_lock();
-            let now = Utc::now().to_rfc3339();
             
             // 查找是否已存在
             let exists: bool = conn.query_row(
@@ -171,6 +180,14 @@ impl StatisticsManager {
                 )?;
             }
             
+            if let Some(query) = &query {
+                conn.execute(
+                    "INSERT INTO query_selections (query, result_id, plugin_id, count, last_used) VALUES (?1, ?2, ?3, 1, ?4)
+                     ON CONFLICT(query, result_id, plugin_id) DO UPDATE SET count = count + 1, last_used = ?4",
+                    params![query, &result_id, &plugin_id, &now],
+                )?;
+            }
+            
             Ok::<(), anyhow::Error>(())
         })
         .await??;
@@ -182,10 +199,10 @@ impl StatisticsManager {
     pub async fn record_plugin_usage(&self, plugin_id: &str) -> Result<()> {
         let plugin_id = plugin_id.to_string();
         let db = self.db.clone();
+        let now = self.clock.now().to_rfc3339();
         
         tokio::task::spawn_blocking(move || {
             let conn = db.blocking_lock();
-            let now = Utc::now().to_rfc3339();
             
             // 查找是否已存在
             let exists: bool = conn.query_row(
@@ -259,8 +276,221 @@ impl StatisticsManager {
         Ok(count)
     }
     
+    /// 当前查询与历史查询 → 选择记录的亲和度（result_id → 0-100 分）
+    pub async fn get_query_affinity(&self, query: &str) -> Result<HashMap<String, f64>> {
+        let normalized = query_selection::normalize_query(query);
+        let Some(first) = normalized.chars().next() else {
+            return Ok(HashMap::new());
+        };
+        let db = self.db.clone();
+        
+        let selections = tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            // 只比较首字符相同的历史查询（前缀和常见拼写错误都满足）
+            let mut stmt = conn.prepare(
+                "SELECT query, result_id, count FROM query_selections WHERE substr(query, 1, 1) = ?1"
+            )?;
+            let selections = stmt
+                .query_map(params![first.to_string()], |row| {
+                    Ok(QuerySelection {
+                        query: row.get(0)?,
+                        result_id: row.get(1)?,
+                        count: row.get(2)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, anyhow::Error>(selections)
+        })
+        .await??;
+        
+        Ok(query_selection::affinity_scores(&selections, &normalized))
+    }
+    
+    /// 结果的 frecency 值（点击次数按最近使用时间加权，未点击过为 0）
+    pub async fn get_frecency(&self, result_id: &str, plugin_id: &str) -> Result<f64> {
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let db = self.db.clone();
+        let now = self.clock.now();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let row: Option<(u32, String)> = conn.query_row(
+                "SELECT count, last_used FROM result_clicks WHERE result_id = ?1 AND plugin_id = ?2",
+                params![&result_id, &plugin_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).ok();
+            
+            let frecency = row
+                .and_then(|(count, last_used)| {
+                    let last_used = DateTime::parse_from_rfc3339(&last_used).ok()?.with_timezone(&Utc);
+                    Some(frecency::frecency(count, last_used, now))
+                })
+                .unwrap_or(0.0);
+            Ok::<f64, anyhow::Error>(frecency)
+        })
+        .await?
+    }
+    
+    /// 衰减长期未用的点击计数：最后使用和上次衰减都早于截止时间的计数减半，减到 0 的记录删除
+    /// 返回衰减的记录数
+    pub async fn decay_counts(&self) -> Result<usize> {
+        let db = self.db.clone();
+        let now = self.clock.now();
+        let cutoff = frecency::decay_cutoff(now).to_rfc3339();
+        let now = now.to_rfc3339();
+        
+        let decayed = tokio::task::spawn_blocking(move || {
+            let mut conn = db.blocking_lock();
+            let tx = conn.transaction()?;
+            let mut decayed = 0;
+            for table in ["result_clicks", "context_result_clicks"] {
+                decayed += tx.execute(
+                    &format!(
+                        "UPDATE {} SET count = count / 2, decayed_at = ?1
+                         WHERE last_used < ?2 AND (decayed_at IS NULL OR decayed_at < ?2)",
+                        table
+                    ),
+                    params![&now, &cutoff],
+                )?;
+                tx.execute(&format!("DELETE FROM {} WHERE count <= 0", table), [])?;
+            }
+            tx.commit()?;
+            Ok::<usize, anyhow::Error>(decayed)
+        })
+        .await??;
+        
+        if decayed > 0 {
+            tracing::info!("📉 Decayed {} stale click counts", decayed);
+        }
+        Ok(decayed)
+    }
+    
+    /// 把结果置顶到某个查询的最前面（排在已有置顶之后）
+    pub async fn pin_result(&self, query: &str, result_id: &str, plugin_id: &str) -> Result<()> {
+        let query = query_selection::normalize_query(query);
+        if query.is_empty() {
+            anyhow::bail!("Cannot pin a result for an empty query");
+        }
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let db = self.db.clone();
+        let now = self.clock.now().to_rfc3339();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            conn.execute(
+                "INSERT OR IGNORE INTO ranking_overrides (query, result_id, plugin_id, position, created_at)
+                 VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), -1) + 1 FROM ranking_overrides WHERE query = ?1), ?4)",
+                params![&query, &result_id, &plugin_id, &now],
+            )?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+        
+        Ok(())
+    }
+    
+    /// 取消置顶，返回是否存在该置顶
+    pub async fn unpin_result(&self, query: &str, result_id: &str, plugin_id: &str) -> Result<bool> {
+        let query = query_selection::normalize_query(query);
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let db = self.db.clone();
+        
+        let removed = tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let removed = conn.execute(
+                "DELETE FROM ranking_overrides WHERE query = ?1 AND result_id = ?2 AND plugin_id = ?3",
+                params![&query, &result_id, &plugin_id],
+            )?;
+            Ok::<bool, anyhow::Error>(removed > 0)
+        })
+        .await??;
+        
+        Ok(removed)
+    }
+    
+    /// 获取某个查询下的置顶结果（按置顶先后排列）
+    pub async fn get_pins(&self, query: &str) -> Result<Vec<ResultPin>> {
+        let query = query_selection::normalize_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let db = self.db.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let mut stmt = conn.prepare(
+                "SELECT result_id, plugin_id FROM ranking_overrides WHERE query = ?1 ORDER BY position"
+            )?;
+            let pins = stmt
+                .query_map(params![&query], |row| {
+                    Ok(ResultPin {
+                        result_id: row.get(0)?,
+                        plugin_id: row.get(1)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, anyhow::Error>(pins)
+        })
+        .await?
+    }
+    
+    /// 记录没有任何结果的查询（规范化为小写，过短的输入不记录）
+    pub async fn record_zero_result(&self, query: &str) -> Result<()> {
+        let query = query.trim().to_lowercase();
+        if query.chars().count() < ZERO_RESULT_MIN_CHARS {
+            return Ok(());
+        }
+        let last_used = self.clock.now().to_rfc3339();
+        let db = self.db.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            conn.execute(
+                "INSERT INTO zero_result_queries (query, count, last_used) VALUES (?1, 1, ?2)
+                 ON CONFLICT(query) DO UPDATE SET count = count + 1, last_used = ?2",
+                params![&query, &last_used],
+            )?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+        
+        Ok(())
+    }
+    
+    /// 获取最常出现的无结果查询
+    pub async fn get_zero_result_queries(&self, limit: usize) -> Result<Vec<QueryStat>> {
+        let db = self.db.clone();
+        
+        let queries = tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let mut stmt = conn.prepare(
+                "SELECT query, count, last_used FROM zero_result_queries ORDER BY count DESC, last_used DESC LIMIT ?1"
+            )?;
+            
+            let rows = stmt.query_map(params![limit as i32], |row| {
+                Ok(QueryStat {
+                    query: row.get(0)?,
+                    count: row.get(1)?,
+                    last_used: DateTime::parse_from_rfc3339(&row.get::<_, String>(2)?)
+                        .unwrap()
+                        .with_timezone(&Utc),
+                })
+            })?;
+            
+            let queries = rows.collect::<rusqlite::Result<Vec<QueryStat>>>()?;
+            Ok::<Vec<QueryStat>, anyhow::Error>(queries)
+        })
+        .await??;
+        
+        Ok(queries)
+    }
+    
     /// 获取热门查询
     pub async fn get_top_queries(&self, limit: usize) -> Result<Vec<QueryStat>> {
+        self.flush_queries().await?;
         let db = self.db.clone();
         
         let queries = tokio::task::spawn_blocking(move || {
@@ -325,17 +555,112 @@ impl StatisticsManager {
         Ok(results)
     }
     
+    /// 记录工作区上下文内的结果点击
+    pub async fn record_context_click(&self, context_id: &str, result_id: &str, plugin_id: &str, title: &str) -> Result<()> {
+        let context_id = context_id.to_string();
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let title = title.to_string();
+        let db = self.db.clone();
+        let now = self.clock.now().to_rfc3339();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            
+            let updated = conn.execute(
+                "UPDATE context_result_clicks SET count = count + 1, last_used = ?1, title = ?2
+                 WHERE context_id = ?3 AND result_id = ?4 AND plugin_id = ?5",
+                params![&now, &title, &context_id, &result_id, &plugin_id],
+            )?;
+            
+            if updated == 0 {
+                conn.execute(
+                    "INSERT INTO context_result_clicks (context_id, result_id, plugin_id, title, count, last_used, created_at)
+                     VALUES (?1, ?2, ?3, ?4, 1, ?5, ?5)",
+                    params![&context_id, &result_id, &plugin_id, &title, &now],
+                )?;
+            }
+            
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+        
+        Ok(())
+    }
+    
+    /// 获取工作区上下文内结果的使用次数
+    pub async fn get_context_result_score(&self, context_id: &str, result_id: &str, plugin_id: &str) -> Result<i32> {
+        let context_id = context_id.to_string();
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let db = self.db.clone();
+        
+        let count = tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let count: Option<i32> = conn.query_row(
+                "SELECT count FROM context_result_clicks WHERE context_id = ?1 AND result_id = ?2 AND plugin_id = ?3",
+                params![&context_id, &result_id, &plugin_id],
+                |row| row.get(0),
+            ).ok();
+            
+            Ok::<i32, anyhow::Error>(count.unwrap_or(0))
+        })
+        .await??;
+        
+        Ok(count)
+    }
+    
+    /// 获取工作区上下文内的热门结果
+    pub async fn get_context_top_results(&self, context_id: &str, limit: usize) -> Result<Vec<ResultStat>> {
+        let context_id = context_id.to_string();
+        let db = self.db.clone();
+        
+        let results = tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            let mut stmt = conn.prepare(
+                "SELECT result_id, plugin_id, title, count, last_used FROM context_result_clicks
+                 WHERE context_id = ?1 ORDER BY count DESC, last_used DESC LIMIT ?2"
+            )?;
+            
+            let rows = stmt.query_map(params![&context_id, limit as i32], |row| {
+                Ok(ResultStat {
+                    result_id: row.get(0)?,
+                    plugin_id: row.get(1)?,
+                    title: row.get(2)?,
+                    count: row.get(3)?,
+                    last_used: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                        .unwrap()
+                        .with_timezone(&Utc),
+                })
+            })?;
+            
+            let mut results = Vec::new();
+            for row in rows {
+                results.push(row?);
+            }
+            
+            Ok::<Vec<ResultStat>, anyhow::Error>(results)
+        })
+        .await??;
+        
+        Ok(results)
+    }
+    
     /// 清除旧数据（保留最近90天）
     pub async fn cleanup_old_data(&self) -> Result<()> {
+        self.flush_queries().await?;
         let db = self.db.clone();
+        let cutoff = (self.clock.now() - chrono::Duration::days(90)).to_rfc3339();
         
         tokio::task::spawn_blocking(move || {
             let conn = db.blocking_lock();
-            let cutoff = (Utc::now() - chrono::Duration::days(90)).to_rfc3339();
             
             conn.execute("DELETE FROM queries WHERE last_used < ?1", params![&cutoff])?;
             conn.execute("DELETE FROM result_clicks WHERE last_used < ?1", params![&cutoff])?;
             conn.execute("DELETE FROM plugin_usage WHERE last_used < ?1", params![&cutoff])?;
+            conn.execute("DELETE FROM context_result_clicks WHERE last_used < ?1", params![&cutoff])?;
+            conn.execute("DELETE FROM query_selections WHERE last_used < ?1", params![&cutoff])?;
+            conn.execute("DELETE FROM zero_result_queries WHERE last_used < ?1", params![&cutoff])?;
             
             // 压缩数据库
             conn.execute("VACUUM", [])?;
@@ -348,6 +673,52 @@ impl StatisticsManager {
         Ok(())
     }
     
+    /// 删除全部统计数据（恢复命令使用）
+    pub async fn reset_all(&self) -> Result<()> {
+        *self.pending.lock() = QueryBuffer::default();
+        let db = self.db.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            conn.execute_batch(
+                "DELETE FROM queries;
+                 DELETE FROM result_clicks;
+                 DELETE FROM plugin_usage;
+                 DELETE FROM context_result_clicks;
+                 DELETE FROM query_selections;
+                 DELETE FROM ranking_overrides;
+                 DELETE FROM zero_result_queries;
+                 VACUUM;",
+            )?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+        
+        tracing::info!("Reset all statistics data");
+        Ok(())
+    }
+    
+    /// 删除某个结果的全部点击与查询关联记录（结果已失效，如文件已被删除）
+    pub async fn forget_result(&self, result_id: &str, plugin_id: &str) -> Result<()> {
+        let result_id = result_id.to_string();
+        let plugin_id = plugin_id.to_string();
+        let db = self.db.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = db.blocking_lock();
+            for table in ["result_clicks", "context_result_clicks", "query_selections", "ranking_overrides"] {
+                conn.execute(
+                    &format!("DELETE FROM {} WHERE result_id = ?1 AND plugin_id = ?2", table),
+                    params![&result_id, &plugin_id],
+                )?;
+            }
+            Ok::<(), anyhow::Error>(())
+        })
+        .await??;
+        
+        Ok(())
+    }
+    
     /// 🔥 从 MRU 记录创建 QueryResult（用于注入）
     pub async fn create_result_from_mru(&self, mru: &ResultStat) -> Result<crate::core::types::QueryResult> {
         use crate::core::types::{QueryResult, Action, WoxImage, Preview};
@@ -461,6 +832,176 @@ impl StatisticsManager {
             plugin_id: mru.plugin_id.clone(),
             refreshable: false,
             actions,
+            accessibility: None,
+            highlights: None,
+            badges: Vec::new(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::{Clock, FakeClock};
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_records_use_clock() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        stats.record_query("code").await.unwrap();
+        clock.advance(Duration::minutes(5));
+        stats.record_query("code").await.unwrap();
+        stats.record_query("git").await.unwrap();
+
+        let top = stats.get_top_queries(10).await.unwrap();
+        assert_eq!(top[0].query, "code");
+        assert_eq!(top[0].count, 2);
+        assert_eq!(top[0].last_used, clock.now());
+
+        stats.record_result_click("vscode", "app-search", "VS Code").await.unwrap();
+        assert_eq!(stats.get_result_score("vscode", "app-search").await.unwrap(), 1);
+        assert_eq!(stats.get_result_score("unknown", "other").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_data() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        stats.record_query("stale").await.unwrap();
+        clock.advance(Duration::days(60));
+        stats.record_query("recent").await.unwrap();
+
+        // 距 stale 91 天、距 recent 31 天
+        clock.advance(Duration::days(31));
+        stats.cleanup_old_data().await.unwrap();
+
+        let top = stats.get_top_queries(10).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].query, "recent");
+    }
+
+    #[tokio::test]
+    async fn test_keystrokes_persist_final_query_only() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        for query in ["f", "fi", "fir", "fire", "firef", "firefox"] {
+            stats.record_query(query).await.unwrap();
+            clock.advance(Duration::milliseconds(120));
+        }
+        stats.record_result_click("firefox", "app-search", "Firefox").await.unwrap();
+
+        let top = stats.get_top_queries(10).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].query.as_str(), top[0].count), ("firefox", 1));
+    }
+
+    #[tokio::test]
+    async fn test_query_selection_affinity() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        for _ in 0..3 {
+            stats.record_query("Chr").await.unwrap();
+            stats.record_result_click("chrome", "app-search", "Google Chrome").await.unwrap();
+            clock.advance(Duration::seconds(30));
+        }
+        stats.record_query("chr").await.unwrap();
+        stats.record_result_click("chrome-remote", "app-search", "Chrome Remote Desktop").await.unwrap();
+
+        let affinity = stats.get_query_affinity("chr").await.unwrap();
+        assert!(affinity["chrome"] > affinity["chrome-remote"]);
+        // 前缀相似的查询也能命中
+        assert!(stats.get_query_affinity("chro").await.unwrap().contains_key("chrome"));
+        assert!(stats.get_query_affinity("firefox").await.unwrap().is_empty());
+        assert!(stats.get_query_affinity("  ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_decay_counts() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        for _ in 0..8 {
+            stats.record_result_click("old-tool", "app-search", "Old Tool").await.unwrap();
+        }
+        stats.record_result_click("once", "app-search", "Once").await.unwrap();
+        clock.advance(Duration::days(frecency::DECAY_AFTER_DAYS + 1));
+        stats.record_result_click("new-tool", "app-search", "New Tool").await.unwrap();
+        let stale = stats.get_frecency("old-tool", "app-search").await.unwrap();
+
+        // 长期未用的计数减半，计数为 1 的记录被删除，最近使用的不受影响
+        assert_eq!(stats.decay_counts().await.unwrap(), 2);
+        assert_eq!(stats.get_result_score("old-tool", "app-search").await.unwrap(), 4);
+        assert_eq!(stats.get_frecency("once", "app-search").await.unwrap(), 0.0);
+        assert_eq!(stats.get_result_score("new-tool", "app-search").await.unwrap(), 1);
+        assert!(stats.get_frecency("old-tool", "app-search").await.unwrap() < stale);
+
+        // 同一周期内不重复衰减
+        assert_eq!(stats.decay_counts().await.unwrap(), 0);
+        clock.advance(Duration::days(frecency::DECAY_AFTER_DAYS + 1));
+        stats.decay_counts().await.unwrap();
+        assert_eq!(stats.get_result_score("old-tool", "app-search").await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pins() {
+        let stats = StatisticsManager::in_memory(FakeClock::fixed()).unwrap();
+
+        stats.pin_result("API", "proj-api", "git_projects").await.unwrap();
+        stats.pin_result(" api ", "api-docs", "browser").await.unwrap();
+        // 重复置顶不改变顺序
+        stats.pin_result("api", "proj-api", "git_projects").await.unwrap();
+        assert!(stats.pin_result("  ", "x", "y").await.is_err());
+
+        let pins = stats.get_pins("api").await.unwrap();
+        let ids: Vec<&str> = pins.iter().map(|p| p.result_id.as_str()).collect();
+        assert_eq!(ids, vec!["proj-api", "api-docs"]);
+        assert!(stats.get_pins("ap").await.unwrap().is_empty());
+
+        assert!(stats.unpin_result("api", "proj-api", "git_projects").await.unwrap());
+        assert!(!stats.unpin_result("api", "proj-api", "git_projects").await.unwrap());
+        assert_eq!(stats.get_pins("api").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_forget_result() {
+        let stats = StatisticsManager::in_memory(FakeClock::fixed()).unwrap();
+
+        stats.record_query("report").await.unwrap();
+        stats.record_result_click(r"C:\docs\report.docx", "file_search", "report.docx").await.unwrap();
+        stats.record_context_click("work", r"C:\docs\report.docx", "file_search", "report.docx").await.unwrap();
+        stats.record_result_click("vscode", "app-search", "VS Code").await.unwrap();
+
+        stats.forget_result(r"C:\docs\report.docx", "file_search").await.unwrap();
+
+        assert_eq!(stats.get_result_score(r"C:\docs\report.docx", "file_search").await.unwrap(), 0);
+        assert_eq!(stats.get_context_result_score("work", r"C:\docs\report.docx", "file_search").await.unwrap(), 0);
+        assert!(stats.get_query_affinity("report").await.unwrap().is_empty());
+        assert_eq!(stats.get_result_score("vscode", "app-search").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_zero_result_queries() {
+        let clock = FakeClock::fixed();
+        let stats = StatisticsManager::in_memory(clock.clone()).unwrap();
+
+        stats.record_zero_result("Translate hello").await.unwrap();
+        clock.advance(Duration::minutes(1));
+        stats.record_zero_result("translate hello ").await.unwrap();
+        stats.record_zero_result("emoji smile").await.unwrap();
+        stats.record_zero_result("tr").await.unwrap();
+
+        let queries = stats.get_zero_result_queries(10).await.unwrap();
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].query, "translate hello");
+        assert_eq!(queries[0].count, 2);
+        assert_eq!(queries[0].last_used, clock.now());
+
+        stats.reset_all().await.unwrap();
+        assert!(stats.get_zero_result_queries(10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+// 查询统计缓冲 - 合并逐键输入产生的查询，只把“最终”查询（被执行或停顿超过阈值）批量写入数据库
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// 输入停顿超过该时长视为最终查询
+pub const PAUSE_THRESHOLD_MS: i64 = 2000;
+
+/// 缓冲的最终查询达到该数量时批量写入
+pub const BATCH_SIZE: usize = 20;
+
+/// 待写入的最终查询
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinalQuery {
+    pub query: String,
+    pub count: i32,
+    pub last_used: DateTime<Utc>,
+}
+
+/// 查询缓冲
+#[derive(Debug, Default)]
+pub struct QueryBuffer {
+    /// 正在输入的查询及其时间
+    typing: Option<(String, DateTime<Utc>)>,
+    finals: HashMap<String, FinalQuery>,
+}
+
+impl QueryBuffer {
+    /// 记录一次输入，返回是否已攒够一批需要写入
+    ///
+    /// 与正在输入的查询互为前缀（继续输入或删除字符）且未停顿时视为同一次输入，只保留最新值
+    pub fn record(&mut self, query: &str, now: DateTime<Utc>) -> bool {
+        if let Some((typing, typed_at)) = self.typing.take() {
+            let paused = now - typed_at >= Duration::milliseconds(PAUSE_THRESHOLD_MS);
+            let editing = query.starts_with(typing.as_str()) || typing.starts_with(query);
+            if paused || !editing {
+                self.push_final(typing, typed_at);
+            }
+        }
+        self.typing = Some((query.to_string(), now));
+        self.finals.len() >= BATCH_SIZE
+    }
+
+    /// 正在输入的查询
+    pub fn typing(&self) -> Option<&str> {
+        self.typing.as_ref().map(|(query, _)| query.as_str())
+    }
+
+    /// 当前输入的查询被执行：立即视为最终查询
+    pub fn finalize(&mut self) {
+        if let Some((query, typed_at)) = self.typing.take() {
+            self.push_final(query, typed_at);
+        }
+    }
+
+    /// 取出全部待写入的最终查询（停顿超过阈值的当前输入一并取出）
+    pub fn drain(&mut self, now: DateTime<Utc>) -> Vec<FinalQuery> {
+        let paused = self
+            .typing
+            .as_ref()
+            .is_some_and(|(_, typed_at)| now - *typed_at >= Duration::milliseconds(PAUSE_THRESHOLD_MS));
+        if paused {
+            self.finalize();
+        }
+        self.finals.drain().map(|(_, query)| query).collect()
+    }
+
+    fn push_final(&mut self, query: String, at: DateTime<Utc>) {
+        self.finals
+            .entry(query.clone())
+            .and_modify(|q| {
+                q.count += 1;
+                q.last_used = q.last_used.max(at);
+            })
+            .or_insert(FinalQuery { query, count: 1, last_used: at });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::milliseconds(ms)
+    }
+
+    #[test]
+    fn test_keystrokes_collapse_into_final_query() {
+        let mut buffer = QueryBuffer::default();
+        for (i, query) in ["c", "co", "cod", "code"].iter().enumerate() {
+            buffer.record(query, at(i as i64 * 100));
+        }
+        // 仍在输入
+        assert!(buffer.drain(at(1000)).is_empty());
+
+        let finals = buffer.drain(at(2400));
+        assert_eq!(finals, vec![FinalQuery { query: "code".into(), count: 1, last_used: at(300) }]);
+    }
+
+    #[test]
+    fn test_unrelated_query_and_execution_finalize() {
+        let mut buffer = QueryBuffer::default();
+        buffer.record("git", at(0));
+        buffer.record("npm", at(100));
+        buffer.finalize();
+        buffer.record("npm", at(200));
+        buffer.finalize();
+
+        let mut finals = buffer.drain(at(300));
+        finals.sort_by(|a, b| a.query.cmp(&b.query));
+        assert_eq!(finals.len(), 2);
+        assert_eq!((finals[0].query.as_str(), finals[0].count), ("git", 1));
+        assert_eq!((finals[1].query.as_str(), finals[1].count, finals[1].last_used), ("npm", 2, at(200)));
+    }
+
+    #[test]
+    fn test_batch_threshold() {
+        let mut buffer = QueryBuffer::default();
+        let full = (0..=BATCH_SIZE).map(|i| buffer.record(&format!("q{}", i), at(i as i64 * 3000))).last();
+        assert_eq!(full, Some(true));
+    }
+}
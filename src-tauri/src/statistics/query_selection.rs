@@ -0,0 +1,124 @@
+// 查询 → 选择关联 - 记录用户在某个查询下最终选择了哪个结果，
+// 对相似查询（相同、前缀、编辑距离很小）再次出现时给这些结果加分，例如输入 "chr" 几次后 Chrome 会稳定排在第一
+
+use std::collections::HashMap;
+
+/// 每次相同查询下的选择贡献的亲和度分数（4 次即达到满分）
+const SCORE_PER_SELECTION: f64 = 25.0;
+
+/// 亲和度分数上限
+pub const MAX_AFFINITY: f64 = 100.0;
+
+/// 一条历史选择记录
+#[derive(Debug, Clone)]
+pub struct QuerySelection {
+    pub query: String,
+    pub result_id: String,
+    pub count: u32,
+}
+
+/// 规范化查询：去掉首尾空白、合并连续空白、转小写
+pub fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// 历史查询与当前查询的相似度 (0.0-1.0)，两者均为规范化后的查询
+pub fn similarity(stored: &str, current: &str) -> f64 {
+    if stored.is_empty() || current.is_empty() {
+        return 0.0;
+    }
+    if stored == current {
+        return 1.0;
+    }
+    // 当前输入是历史查询的前缀（继续输入前就已经能命中）或反之
+    if stored.starts_with(current) || current.starts_with(stored) {
+        let (short, long) = if stored.len() < current.len() { (stored, current) } else { (current, stored) };
+        return 0.5 + 0.3 * (short.chars().count() as f64 / long.chars().count() as f64);
+    }
+    // 拼写错误：短查询允许 1 处差异，长查询允许 2 处
+    let max_distance = match current.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    };
+    if max_distance > 0 && edit_distance(stored, current) <= max_distance {
+        return 0.5;
+    }
+    0.0
+}
+
+/// 计算各结果对当前查询的亲和度分数 (0-100)
+pub fn affinity_scores(selections: &[QuerySelection], query: &str) -> HashMap<String, f64> {
+    let query = normalize_query(query);
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for selection in selections {
+        let weight = similarity(&selection.query, &query);
+        if weight > 0.0 {
+            *scores.entry(selection.result_id.clone()).or_default() +=
+                weight * selection.count as f64 * SCORE_PER_SELECTION;
+        }
+    }
+    for score in scores.values_mut() {
+        *score = score.min(MAX_AFFINITY);
+    }
+    scores
+}
+
+/// Levenshtein 编辑距离（按字符）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection(query: &str, result_id: &str, count: u32) -> QuerySelection {
+        QuerySelection {
+            query: query.to_string(),
+            result_id: result_id.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(normalize_query("  Visual   Studio "), "visual studio");
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("chr", "chr"), 1.0);
+        assert!(similarity("chrome", "chr") > 0.5);
+        assert!(similarity("chr", "chrome") > 0.5);
+        assert_eq!(similarity("chxyzw", "chrome"), 0.0);
+        assert_eq!(similarity("chorme", "chrome"), 0.5);
+        assert_eq!(similarity("ab", "ac"), 0.0);
+        assert_eq!(similarity("firefox", "chrome"), 0.0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_affinity_scores() {
+        let selections = vec![
+            selection("chr", "chrome", 3),
+            selection("chrome", "chrome", 1),
+            selection("chr", "chrome-devtools", 1),
+            selection("code", "vscode", 10),
+        ];
+        let scores = affinity_scores(&selections, " CHR ");
+        assert!(scores["chrome"] > scores["chrome-devtools"]);
+        assert!(!scores.contains_key("vscode"));
+        assert_eq!(affinity_scores(&selections, "code")["vscode"], MAX_AFFINITY);
+    }
+}
@@ -0,0 +1,130 @@
+// 存储后端 - 按相对路径（如 config/config.json）读写字节
+// 生产环境使用应用数据目录下的文件，测试使用内存实现，避免触碰真实文件系统
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// 键值形式的存储后端（键为以 / 分隔的相对路径）
+pub trait Storage: Send + Sync {
+    /// 读取内容（不存在时返回 None）
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// 写入内容（自动创建上级目录）
+    fn write(&self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// 删除指定前缀（目录）下的所有内容
+    fn remove_prefix(&self, prefix: &str) -> Result<()>;
+}
+
+/// 基于文件系统的存储（键映射为根目录下的文件）
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        key.split('/').filter(|part| !part.is_empty()).fold(self.root.clone(), |path, part| path.join(part))
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> Result<()> {
+        let path = self.path(prefix);
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+            std::fs::create_dir_all(&path)?;
+        }
+        Ok(())
+    }
+}
+
+/// 内存存储（测试用）
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStorage {
+    files: parking_lot::RwLock<std::collections::BTreeMap<String, Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl MemoryStorage {
+    pub fn new() -> std::sync::Arc<Self> {
+        std::sync::Arc::new(Self::default())
+    }
+
+    /// 当前保存的所有键
+    pub fn keys(&self) -> Vec<String> {
+        self.files.read().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.files.read().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.files.write().insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> Result<()> {
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+        self.files.write().retain(|key, _| !key.starts_with(&prefix));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise(storage: &dyn Storage) {
+        assert_eq!(storage.read("data/a.json").unwrap(), None);
+
+        storage.write("data/a.json", b"{}").unwrap();
+        storage.write("cache/x/b.cache", b"1").unwrap();
+        assert_eq!(storage.read("data/a.json").unwrap().as_deref(), Some(&b"{}"[..]));
+
+        storage.remove_prefix("cache").unwrap();
+        assert_eq!(storage.read("cache/x/b.cache").unwrap(), None);
+        assert!(storage.read("data/a.json").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_memory_storage() {
+        let storage = MemoryStorage::new();
+        exercise(storage.as_ref());
+        assert_eq!(storage.keys(), vec!["data/a.json"]);
+    }
+
+    #[test]
+    fn test_file_storage() {
+        let root = std::env::temp_dir().join(format!("ilauncher_storage_test_{}", uuid::Uuid::new_v4()));
+        let storage = FileStorage::new(&root);
+        exercise(&storage);
+        assert!(root.join("cache").is_dir());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -18,6 +18,35 @@ pub struct ClipboardRecord {
     pub category: Option<String>,
     pub tags: Vec<String>,
     pub file_path: Option<String>, // 图片文件路径（如果保存为文件）
+    pub ocr_text: Option<String>,  // 图片 OCR 文字（None 表示尚未识别）
+    pub pinned_slot: Option<u8>,   // 快速粘贴槽位（1-9）
+}
+
+/// 快速粘贴槽位数量（Ctrl+Shift+1..9）
+pub const PINNED_SLOT_COUNT: u8 = 9;
+
+/// 查询记录时选取的列（顺序与 record_from_row 对应）
+const RECORD_COLUMNS: &str = "id, content_type, content, plain_text, preview, timestamp, 
+                    favorite, category, tags, file_path, ocr_text, pinned_slot";
+
+fn record_from_row(row: &rusqlite::Row) -> rusqlite::Result<ClipboardRecord> {
+    Ok(ClipboardRecord {
+        id: row.get(0)?,
+        content_type: row.get(1)?,
+        content: row.get(2)?,
+        plain_text: row.get(3)?,
+        preview: row.get(4)?,
+        timestamp: Local.timestamp_opt(row.get(5)?, 0).unwrap(),
+        favorite: row.get::<_, i32>(6)? == 1,
+        category: row.get(7)?,
+        tags: row
+            .get::<_, Option<String>>(8)?
+            .map(|s| s.split(',').map(|t| t.to_string()).collect())
+            .unwrap_or_default(),
+        file_path: row.get(9)?,
+        ocr_text: row.get(10)?,
+        pinned_slot: row.get(11)?,
+    })
 }
 
 pub struct ClipboardDatabase {
@@ -72,6 +101,26 @@ impl ClipboardDatabase {
             [],
         )?;
 
+        // 旧数据库补充 OCR 文字列
+        let has_ocr_column = conn
+            .prepare("SELECT 1 FROM pragma_table_info('clipboard_history') WHERE name = 'ocr_text'")?
+            .exists([])?;
+        if !has_ocr_column {
+            conn.execute("ALTER TABLE clipboard_history ADD COLUMN ocr_text TEXT", [])?;
+        }
+
+        // 旧数据库补充快速粘贴槽位列（每个槽位最多一条记录）
+        let has_slot_column = conn
+            .prepare("SELECT 1 FROM pragma_table_info('clipboard_history') WHERE name = 'pinned_slot'")?
+            .exists([])?;
+        if !has_slot_column {
+            conn.execute("ALTER TABLE clipboard_history ADD COLUMN pinned_slot INTEGER", [])?;
+        }
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_pinned_slot ON clipboard_history(pinned_slot) WHERE pinned_slot IS NOT NULL",
+            [],
+        )?;
+
         tracing::info!("Clipboard database tables initialized");
         Ok(())
     }
@@ -132,10 +181,9 @@ impl ClipboardDatabase {
     ) -> Result<Vec<ClipboardRecord>> {
         let conn = self.conn.lock().unwrap();
 
-        let mut query = String::from(
-            "SELECT id, content_type, content, plain_text, preview, timestamp, 
-                    favorite, category, tags, file_path 
-             FROM clipboard_history WHERE 1=1",
+        let mut query = format!(
+            "SELECT {} FROM clipboard_history WHERE 1=1",
+            RECORD_COLUMNS
         );
 
         if let Some(ct) = content_type_filter {
@@ -150,23 +198,7 @@ impl ClipboardDatabase {
 
         let mut stmt = conn.prepare(&query)?;
         let records = stmt
-            .query_map(params![limit, offset], |row| {
-                Ok(ClipboardRecord {
-                    id: row.get(0)?,
-                    content_type: row.get(1)?,
-                    content: row.get(2)?,
-                    plain_text: row.get(3)?,
-                    preview: row.get(4)?,
-                    timestamp: Local.timestamp_opt(row.get(5)?, 0).unwrap(),
-                    favorite: row.get::<_, i32>(6)? == 1,
-                    category: row.get(7)?,
-                    tags: row
-                        .get::<_, Option<String>>(8)?
-                        .map(|s| s.split(',').map(|t| t.to_string()).collect())
-                        .unwrap_or_default(),
-                    file_path: row.get(9)?,
-                })
-            })?
+            .query_map(params![limit, offset], record_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(records)
@@ -178,37 +210,44 @@ impl ClipboardDatabase {
 
         let search_pattern = format!("%{}%", query);
 
-        let mut stmt = conn.prepare(
-            "SELECT id, content_type, content, plain_text, preview, timestamp, 
-                    favorite, category, tags, file_path 
-             FROM clipboard_history 
-             WHERE content LIKE ?1 OR plain_text LIKE ?1 OR tags LIKE ?1
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM clipboard_history 
+             WHERE content LIKE ?1 OR plain_text LIKE ?1 OR tags LIKE ?1 OR ocr_text LIKE ?1
              ORDER BY timestamp DESC LIMIT ?2",
-        )?;
+            RECORD_COLUMNS
+        ))?;
 
         let records = stmt
-            .query_map(params![search_pattern, limit], |row| {
-                Ok(ClipboardRecord {
-                    id: row.get(0)?,
-                    content_type: row.get(1)?,
-                    content: row.get(2)?,
-                    plain_text: row.get(3)?,
-                    preview: row.get(4)?,
-                    timestamp: Local.timestamp_opt(row.get(5)?, 0).unwrap(),
-                    favorite: row.get::<_, i32>(6)? == 1,
-                    category: row.get(7)?,
-                    tags: row
-                        .get::<_, Option<String>>(8)?
-                        .map(|s| s.split(',').map(|t| t.to_string()).collect())
-                        .unwrap_or_default(),
-                    file_path: row.get(9)?,
-                })
-            })?
+            .query_map(params![search_pattern, limit], record_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(records)
     }
 
+    /// 写入图片 OCR 文字（空字符串表示已识别但没有文字）
+    pub fn set_ocr_text(&self, id: i64, text: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE clipboard_history SET ocr_text = ?1 WHERE id = ?2",
+            params![text, id],
+        )?;
+        Ok(())
+    }
+
+    /// 尚未 OCR 的图片记录 (id, 文件路径)，最新的在前
+    pub fn pending_ocr_images(&self, limit: usize) -> Result<Vec<(i64, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, file_path FROM clipboard_history 
+             WHERE content_type = 'image' AND ocr_text IS NULL AND file_path IS NOT NULL
+             ORDER BY timestamp DESC LIMIT ?1",
+        )?;
+        let pending = stmt
+            .query_map(params![limit], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pending)
+    }
+
     /// 切换收藏状态
     pub fn toggle_favorite(&self, id: i64) -> Result<bool> {
         let conn = self.conn.lock().unwrap();
@@ -268,6 +307,99 @@ impl ClipboardDatabase {
         Ok(())
     }
 
+    /// 把记录固定到快速粘贴槽位（替换该槽位原有记录），slot 为 None 时取消固定
+    pub fn set_pinned_slot(&self, id: i64, slot: Option<u8>) -> Result<()> {
+        if let Some(slot) = slot {
+            if !(1..=PINNED_SLOT_COUNT).contains(&slot) {
+                return Err(anyhow::anyhow!("Pinned slot must be between 1 and {}", PINNED_SLOT_COUNT));
+            }
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        if let Some(slot) = slot {
+            tx.execute(
+                "UPDATE clipboard_history SET pinned_slot = NULL WHERE pinned_slot = ?1",
+                params![slot],
+            )?;
+        }
+        let updated = tx.execute(
+            "UPDATE clipboard_history SET pinned_slot = ?1 WHERE id = ?2",
+            params![slot, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Clipboard record not found: {}", id));
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 获取固定在某槽位的记录
+    pub fn get_pinned_slot(&self, slot: u8) -> Result<Option<ClipboardRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let record = conn
+            .prepare(&format!("SELECT {} FROM clipboard_history WHERE pinned_slot = ?1", RECORD_COLUMNS))?
+            .query_map(params![slot], record_from_row)?
+            .next()
+            .transpose()?;
+        Ok(record)
+    }
+
+    /// 获取所有固定记录（按槽位排序）
+    pub fn get_pinned(&self) -> Result<Vec<ClipboardRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let records = conn
+            .prepare(&format!(
+                "SELECT {} FROM clipboard_history WHERE pinned_slot IS NOT NULL ORDER BY pinned_slot",
+                RECORD_COLUMNS
+            ))?
+            .query_map([], record_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+
+    /// 获取全部收藏记录（配置导出使用，不分页）
+    pub fn get_all_favorites(&self) -> Result<Vec<ClipboardRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let records = conn
+            .prepare(&format!(
+                "SELECT {} FROM clipboard_history WHERE favorite = 1 ORDER BY timestamp DESC",
+                RECORD_COLUMNS
+            ))?
+            .query_map([], record_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+
+    /// 导入收藏记录：已有相同内容时只标记为收藏，返回是否新增了记录
+    pub fn import_favorite(
+        &self,
+        content_type: &str,
+        content: &str,
+        preview: Option<&str>,
+        timestamp: i64,
+        category: Option<&str>,
+        tags: &[String],
+    ) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE clipboard_history SET favorite = 1 WHERE content = ?1",
+            params![content],
+        )?;
+        if updated > 0 {
+            return Ok(false);
+        }
+
+        let tags = (!tags.is_empty()).then(|| tags.join(","));
+        conn.execute(
+            "INSERT INTO clipboard_history 
+             (content_type, content, preview, timestamp, favorite, category, tags) 
+             VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6)",
+            params![content_type, content, preview, timestamp, category, tags],
+        )?;
+        Ok(true)
+    }
+
     /// 删除记录
     pub fn delete_record(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
@@ -276,12 +408,34 @@ impl ClipboardDatabase {
         Ok(())
     }
 
-    /// 清理旧记录（保留最近N条）
+    /// 删除某分类（小写，None 为未分类）中早于 cutoff 的非收藏、未固定记录，返回删除数量与图片文件路径
+    pub fn expire_category(&self, category: Option<&str>, cutoff: i64) -> Result<(usize, Vec<String>)> {
+        let conn = self.conn.lock().unwrap();
+
+        let file_paths: Vec<String> = conn
+            .prepare(
+                "SELECT file_path FROM clipboard_history 
+                 WHERE lower(category) IS ?1 AND favorite = 0 AND pinned_slot IS NULL AND timestamp < ?2 AND file_path IS NOT NULL",
+            )?
+            .query_map(params![category, cutoff], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let deleted = conn.execute(
+            "DELETE FROM clipboard_history WHERE lower(category) IS ?1 AND favorite = 0 AND pinned_slot IS NULL AND timestamp < ?2",
+            params![category, cutoff],
+        )?;
+        if deleted > 0 {
+            tracing::info!("Expired {} clipboard records in category {:?}", deleted, category);
+        }
+        Ok((deleted, file_paths))
+    }
+
+    /// 清理旧记录（保留最近N条，固定到槽位的记录不清理）
     pub fn cleanup_old_records(&self, keep_count: usize) -> Result<usize> {
         let conn = self.conn.lock().unwrap();
 
         conn.execute(
-            "DELETE FROM clipboard_history WHERE id NOT IN (
+            "DELETE FROM clipboard_history WHERE pinned_slot IS NULL AND id NOT IN (
                 SELECT id FROM clipboard_history 
                 ORDER BY timestamp DESC LIMIT ?1
             )",
@@ -295,6 +449,14 @@ impl ClipboardDatabase {
         Ok(deleted as usize)
     }
 
+    /// 删除全部记录（包括收藏和固定记录，恢复命令使用）
+    pub fn clear_all(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn.execute("DELETE FROM clipboard_history", [])?;
+        conn.execute("VACUUM", [])?;
+        Ok(deleted)
+    }
+
     /// 获取统计信息
     pub fn get_stats(&self) -> Result<(usize, usize, usize, usize)> {
         let conn = self.conn.lock().unwrap();
@@ -376,4 +538,79 @@ mod tests {
         fs::remove_file(&db_path)?;
         Ok(())
     }
+
+    #[test]
+    fn test_expire_category() -> Result<()> {
+        let db_path = std::env::temp_dir().join(format!("test_clipboard_expire_{}.db", uuid::Uuid::new_v4()));
+        let db = ClipboardDatabase::new(db_path.clone())?;
+
+        let id1 = db.add_record("text", "Hello World", None, None, None)?;
+        db.toggle_favorite(id1)?;
+        db.set_category(id1, Some("work"))?;
+        let id3 = db.add_record("text", "temporary", None, None, None)?;
+        db.set_category(id3, Some("temp"))?;
+
+        assert_eq!(db.expire_category(Some("temp"), Local::now().timestamp() + 1)?, (1, Vec::new()));
+        assert!(db.search("temporary", 10)?.is_empty());
+        // 收藏不过期
+        assert_eq!(db.expire_category(Some("work"), Local::now().timestamp() + 1)?.0, 0);
+        assert_eq!(db.search("Hello", 10)?.len(), 1);
+
+        drop(db);
+        fs::remove_file(&db_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_ocr_text() -> Result<()> {
+        let db_path = std::env::temp_dir().join(format!("test_clipboard_ocr_{}.db", uuid::Uuid::new_v4()));
+        let db = ClipboardDatabase::new(db_path.clone())?;
+
+        db.add_record("text", "Hello World", None, None, None)?;
+        let image = db.add_record("image", "base64data", None, Some("Image"), Some("/path/to/img.png"))?;
+        assert_eq!(db.pending_ocr_images(10)?, vec![(image, "/path/to/img.png".to_string())]);
+
+        db.set_ocr_text(image, "Quarterly Invoice")?;
+        assert!(db.pending_ocr_images(10)?.is_empty());
+        let results = db.search("invoice", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, image);
+
+        drop(db);
+        fs::remove_file(&db_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_pinned_slots() -> Result<()> {
+        let db_path = std::env::temp_dir().join(format!("test_clipboard_pins_{}.db", uuid::Uuid::new_v4()));
+        let db = ClipboardDatabase::new(db_path.clone())?;
+
+        let email = db.add_record("text", "me@example.com", None, None, None)?;
+        let address = db.add_record("text", "1 Infinite Loop", None, None, None)?;
+        assert!(db.set_pinned_slot(email, Some(0)).is_err());
+        assert!(db.set_pinned_slot(email, Some(PINNED_SLOT_COUNT + 1)).is_err());
+
+        db.set_pinned_slot(email, Some(1))?;
+        assert_eq!(db.get_pinned_slot(1)?.map(|r| r.content), Some("me@example.com".to_string()));
+
+        // 同一槽位只保留最新固定的记录
+        db.set_pinned_slot(address, Some(1))?;
+        db.set_pinned_slot(email, Some(2))?;
+        let pinned = db.get_pinned()?;
+        assert_eq!(pinned.iter().map(|r| (r.id, r.pinned_slot)).collect::<Vec<_>>(), vec![(address, Some(1)), (email, Some(2))]);
+
+        // 清理历史不影响固定记录
+        db.add_record("text", "scratch", None, None, None)?;
+        db.cleanup_old_records(0)?;
+        assert_eq!(db.get_stats()?.0, 2);
+
+        db.set_pinned_slot(email, None)?;
+        assert!(db.get_pinned_slot(2)?.is_none());
+        assert!(db.set_pinned_slot(9999, Some(3)).is_err());
+
+        drop(db);
+        fs::remove_file(&db_path)?;
+        Ok(())
+    }
 }
@@ -1,12 +1,15 @@
 // 持久化存储模块
 
+pub mod backend;
 pub mod clipboard_db;
 
+use crate::ranking::rules::ScoringRule;
+use crate::ranking::RankingWeights;
 use anyhow::Result;
+use backend::{FileStorage, Storage};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::fs;
 use tokio::sync::RwLock;
 
 /// 应用配置
@@ -20,6 +23,22 @@ pub struct AppConfig {
     pub ui: UIConfig,
     #[serde(default)]
     pub font: FontConfig,
+    #[serde(default)]
+    pub ranking: RankingConfig,
+    #[serde(default)]
+    pub local_api: LocalApiConfig,
+    #[serde(default)]
+    pub voice: VoiceConfig,
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    #[serde(default)]
+    pub fallback: FallbackConfig,
+    #[serde(default)]
+    pub tts: TtsConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default = "default_hotkey_bindings")]
+    pub hotkey_bindings: Vec<HotkeyBinding>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,12 +50,32 @@ pub struct GeneralConfig {
     pub language: String,
     #[serde(default = "default_true")]
     pub clear_on_hide: bool,
+    // 划词搜索热键（空字符串表示禁用）
+    #[serde(default = "default_selection_hotkey")]
+    pub selection_hotkey: String,
+    // 快速粘贴热键：第 N 个粘贴第 N 条剪贴板历史（空列表表示禁用）
+    #[serde(default = "default_quick_paste_hotkeys")]
+    pub quick_paste_hotkeys: Vec<String>,
+    // 启动时预热主窗口（屏幕外显示一次再隐藏），缩短首次呼出延迟
+    #[serde(default)]
+    pub prewarm_window: bool,
+    // 会话恢复：隐藏后多少秒内重新呼出时恢复查询、选中项和滚动位置（0 表示禁用）
+    #[serde(default)]
+    pub restore_session_secs: u64,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_selection_hotkey() -> String {
+    "Alt+Shift+Space".to_string()
+}
+
+fn default_quick_paste_hotkeys() -> Vec<String> {
+    (1..=9).map(|n| format!("Ctrl+Alt+{}", n)).collect()
+}
+
 // 窗口位置配置（每个视图单独记忆）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WindowPositions {
@@ -70,6 +109,23 @@ pub struct AppearanceConfig {
     // 窗口位置记忆（仅用于非搜索视图）
     #[serde(default)]
     pub window_positions: WindowPositions,
+    // 图标集：default / high-contrast（低视力用户）
+    #[serde(default = "default_icon_set")]
+    pub icon_set: String,
+    // 按显示器名称覆盖窗口尺寸（逻辑像素）
+    #[serde(default)]
+    pub monitor_sizes: std::collections::HashMap<String, WindowSize>,
+}
+
+// 窗口尺寸（100% 缩放下的逻辑像素，显示时按显示器 DPI 缩放）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+fn default_icon_set() -> String {
+    "default".to_string()
 }
 
 // UI外观微调配置
@@ -172,6 +228,259 @@ pub struct PluginsConfig {
     pub disabled_plugins: Vec<String>,
 }
 
+impl PluginsConfig {
+    /// 插件是否启用（以 disabled_plugins 为准）
+    pub fn is_enabled(&self, plugin_id: &str) -> bool {
+        !self.disabled_plugins.iter().any(|id| id == plugin_id)
+    }
+    
+    /// 启用/禁用插件，同步维护两个列表
+    pub fn set_enabled(&mut self, plugin_id: &str, enabled: bool) {
+        let (add_to, remove_from) = if enabled {
+            (&mut self.enabled_plugins, &mut self.disabled_plugins)
+        } else {
+            (&mut self.disabled_plugins, &mut self.enabled_plugins)
+        };
+        remove_from.retain(|id| id != plugin_id);
+        if !add_to.iter().any(|id| id == plugin_id) {
+            add_to.push(plugin_id.to_string());
+        }
+    }
+}
+
+// 排序配置（因素权重、插件分数倍率、用户自定义评分规则）
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RankingConfig {
+    #[serde(default)]
+    pub weights: RankingWeights,
+    // 插件分数倍率（plugin_id → 倍率）
+    #[serde(default)]
+    pub plugin_boosts: std::collections::HashMap<String, f64>,
+    #[serde(default)]
+    pub rules: Vec<ScoringRule>,
+}
+
+// 本地 API 配置（外部集成）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_local_api_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub token: String,
+}
+
+impl Default for LocalApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_local_api_port(),
+            token: String::new(),
+        }
+    }
+}
+
+fn default_local_api_port() -> u16 { 27121 }
+
+// 语音输入配置（默认关闭，录音仅在本地处理）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_voice_hotkey")]
+    pub hotkey: String,           // 按住说话
+    #[serde(default = "default_voice_language")]
+    pub language: String,         // whisper 语言代码，auto 为自动检测
+    #[serde(default)]
+    pub whisper_path: String,     // whisper.cpp 可执行文件
+    #[serde(default)]
+    pub model_path: String,       // ggml 模型文件，空为使用模型管理器下载的模型
+    #[serde(default = "default_voice_target")]
+    pub target: String,           // query / ai
+    #[serde(default)]
+    pub keep_recordings: bool,    // 隐私：默认转写后删除录音
+}
+
+impl Default for VoiceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hotkey: default_voice_hotkey(),
+            language: default_voice_language(),
+            whisper_path: String::new(),
+            model_path: String::new(),
+            target: default_voice_target(),
+            keep_recordings: false,
+        }
+    }
+}
+
+fn default_voice_hotkey() -> String { "Alt+Shift+V".to_string() }
+fn default_voice_language() -> String { "auto".to_string() }
+fn default_voice_target() -> String { "query".to_string() }
+
+// 快速记录配置（默认关闭）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_capture_hotkey")]
+    pub hotkey: String,
+    #[serde(default = "default_capture_target")]
+    pub target: String,           // inbox / snippet
+    #[serde(default)]
+    pub inbox_path: String,       // Markdown 收件箱文件，空为数据目录下的 inbox.md
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hotkey: default_capture_hotkey(),
+            target: default_capture_target(),
+            inbox_path: String::new(),
+        }
+    }
+}
+
+fn default_capture_hotkey() -> String { "Alt+Shift+N".to_string() }
+fn default_capture_target() -> String { "inbox".to_string() }
+
+// 无结果时的备选操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackKind {
+    WebSearch,                    // 用默认搜索引擎搜索
+    AiAsk,                        // 询问 AI
+    CreateTodo,                   // 作为待办追加到收件箱
+    CreateSnippet,                // 保存为文本片段
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FallbackEntry {
+    pub kind: FallbackKind,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+// 无结果备选链：没有任何插件返回结果时按顺序显示为合成结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackConfig {
+    #[serde(default = "default_fallback_chain")]
+    pub chain: Vec<FallbackEntry>,
+    #[serde(default = "default_fallback_search_url")]
+    pub web_search_url: String,   // {query} 替换为搜索内容
+}
+
+impl Default for FallbackConfig {
+    fn default() -> Self {
+        Self {
+            chain: default_fallback_chain(),
+            web_search_url: default_fallback_search_url(),
+        }
+    }
+}
+
+fn default_fallback_chain() -> Vec<FallbackEntry> {
+    [FallbackKind::WebSearch, FallbackKind::AiAsk, FallbackKind::CreateTodo, FallbackKind::CreateSnippet]
+        .into_iter()
+        .map(|kind| FallbackEntry { kind, enabled: true })
+        .collect()
+}
+
+fn default_fallback_search_url() -> String { "https://www.google.com/search?q={query}".to_string() }
+
+// 命名热键绑定：按下后执行指定动作（打开带预填查询的启动器 / 运行工作流）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub id: String,
+    pub hotkey: String,
+    pub action: String,           // query / workflow
+    #[serde(default)]
+    pub argument: String,         // query：预填的查询文本；workflow：工作流 ID
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+// 内置示例绑定（默认关闭）：剪贴板历史、计算器模式
+fn default_hotkey_bindings() -> Vec<HotkeyBinding> {
+    vec![
+        HotkeyBinding {
+            id: "clipboard".to_string(),
+            hotkey: "Ctrl+Alt+V".to_string(),
+            action: "query".to_string(),
+            argument: "cb ".to_string(),
+            enabled: false,
+        },
+        HotkeyBinding {
+            id: "calculator".to_string(),
+            hotkey: "Ctrl+Alt+C".to_string(),
+            action: "query".to_string(),
+            argument: "= ".to_string(),
+            enabled: false,
+        },
+    ]
+}
+
+// 文字转语音配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsConfig {
+    #[serde(default = "default_tts_rate")]
+    pub rate: f32,                // 语速倍率 0.5-2.0
+    #[serde(default)]
+    pub voice: String,            // 系统语音名称，空为默认
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            rate: default_tts_rate(),
+            voice: String::new(),
+        }
+    }
+}
+
+fn default_tts_rate() -> f32 { 1.0 }
+
+// 网络配置（所有插件共用的 HTTP 客户端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    #[serde(default = "default_proxy_mode")]
+    pub proxy_mode: String,       // system / none / custom
+    #[serde(default)]
+    pub proxy_url: String,        // custom 模式使用，如 http://proxy:8080 或 socks5://...
+    #[serde(default = "default_no_proxy")]
+    pub no_proxy: String,         // 逗号分隔的直连地址
+    #[serde(default)]
+    pub ca_bundle_path: String,   // 企业自签 CA（PEM），空为不追加
+    #[serde(default = "default_http_timeout")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_http_retries")]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub download_limit_kbps: u64, // 下载限速（KB/s），0 为不限速
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            proxy_mode: default_proxy_mode(),
+            proxy_url: String::new(),
+            no_proxy: default_no_proxy(),
+            ca_bundle_path: String::new(),
+            timeout_secs: default_http_timeout(),
+            max_retries: default_http_retries(),
+            download_limit_kbps: 0,
+        }
+    }
+}
+
+fn default_proxy_mode() -> String { "system".to_string() }
+fn default_no_proxy() -> String { "localhost,127.0.0.1".to_string() }
+fn default_http_timeout() -> u64 { 30 }
+fn default_http_retries() -> u32 { 2 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedConfig {
     pub start_on_boot: bool,
@@ -189,6 +498,10 @@ impl Default for AppConfig {
                 max_results: 10,
                 language: "en".to_string(),
                 clear_on_hide: true,
+                selection_hotkey: default_selection_hotkey(),
+                quick_paste_hotkeys: default_quick_paste_hotkeys(),
+                prewarm_window: false,
+                restore_session_secs: 0,
             },
             appearance: AppearanceConfig {
                 theme: "dark".to_string(),
@@ -199,6 +512,8 @@ impl Default for AppConfig {
                 transparency: 95,
                 show_preview: true,
                 window_positions: WindowPositions::default(),
+                icon_set: default_icon_set(),
+                monitor_sizes: Default::default(),
             },
             plugins: PluginsConfig {
                 enabled_plugins: vec![
@@ -219,13 +534,33 @@ impl Default for AppConfig {
             },
             ui: UIConfig::default(),
             font: FontConfig::default(),
+            ranking: RankingConfig::default(),
+            local_api: LocalApiConfig::default(),
+            voice: VoiceConfig::default(),
+            capture: CaptureConfig::default(),
+            fallback: FallbackConfig::default(),
+            tts: TtsConfig::default(),
+            http: HttpConfig::default(),
+            hotkey_bindings: default_hotkey_bindings(),
         }
     }
 }
 
+impl AppConfig {
+    /// 去除敏感信息的副本（设置导出、配置备份使用），导入后本地 API 令牌为空，保存时重新生成
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        config.local_api.token.clear();
+        config
+    }
+}
+
+/// 配置文件在存储中的键
+const CONFIG_KEY: &str = "config/config.json";
+
 /// 存储管理器
 pub struct StorageManager {
-    config_path: PathBuf,
+    backend: Arc<dyn Storage>,
     cache_dir: PathBuf,
     data_dir: PathBuf,
     // 配置缓存，避免重复读取文件
@@ -248,14 +583,35 @@ impl StorageManager {
         std::fs::create_dir_all(&cache_dir)?;
         std::fs::create_dir_all(&data_dir)?;
 
-        let config_path = config_dir.join("config.json");
+        Ok(Self::with_backend(Arc::new(FileStorage::new(app_data_dir)), cache_dir, data_dir))
+    }
 
-        Ok(Self {
-            config_path,
+    /// 使用指定存储后端创建（目录仅用于向前端展示）
+    pub fn with_backend(backend: Arc<dyn Storage>, cache_dir: PathBuf, data_dir: PathBuf) -> Self {
+        Self {
+            backend,
             cache_dir,
             data_dir,
             config_cache: Arc::new(RwLock::new(None)),
-        })
+        }
+    }
+
+    /// 内存存储（测试用）
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        Self::with_backend(backend::MemoryStorage::new(), PathBuf::from("cache"), PathBuf::from("data"))
+    }
+
+    /// 在阻塞线程中读取
+    async fn read(&self, key: String) -> Result<Option<Vec<u8>>> {
+        let backend = self.backend.clone();
+        tokio::task::spawn_blocking(move || backend.read(&key)).await?
+    }
+
+    /// 在阻塞线程中写入
+    async fn write(&self, key: String, data: Vec<u8>) -> Result<()> {
+        let backend = self.backend.clone();
+        tokio::task::spawn_blocking(move || backend.write(&key, &data)).await?
     }
 
     /// 加载配置
@@ -269,14 +625,16 @@ impl StorageManager {
         }
         
         // 从文件加载
-        let config = if self.config_path.exists() {
-            let content = fs::read_to_string(&self.config_path).await?;
-            let config: AppConfig = serde_json::from_str(&content)?;
-            tracing::info!("Loaded config from {:?}", self.config_path);
-            config
-        } else {
-            tracing::info!("No config file found, using defaults");
-            AppConfig::default()
+        let config = match self.read(CONFIG_KEY.to_string()).await? {
+            Some(content) => {
+                let config: AppConfig = serde_json::from_slice(&content)?;
+                tracing::info!("Loaded config from {}", CONFIG_KEY);
+                config
+            }
+            None => {
+                tracing::info!("No config file found, using defaults");
+                AppConfig::default()
+            }
         };
         
         // 更新缓存
@@ -291,8 +649,8 @@ impl StorageManager {
     /// 保存配置
     pub async fn save_config(&self, config: &AppConfig) -> Result<()> {
         let content = serde_json::to_string_pretty(config)?;
-        fs::write(&self.config_path, content).await?;
-        tracing::info!("Saved config to {:?}", self.config_path);
+        self.write(CONFIG_KEY.to_string(), content.into_bytes()).await?;
+        tracing::info!("Saved config to {}", CONFIG_KEY);
         
         // 更新缓存
         {
@@ -303,47 +661,39 @@ impl StorageManager {
         Ok(())
     }
 
+    /// 仅在本次运行中使用指定配置（不写入配置文件，安全模式使用）
+    pub async fn set_session_config(&self, config: AppConfig) {
+        *self.config_cache.write().await = Some(config);
+    }
+
     /// 保存缓存数据
     pub async fn save_cache(&self, key: &str, data: &[u8]) -> Result<()> {
-        let cache_file = self.cache_dir.join(format!("{}.cache", key));
-        fs::write(&cache_file, data).await?;
-        Ok(())
+        self.write(format!("cache/{}.cache", key), data.to_vec()).await
     }
 
     /// 加载缓存数据
     pub async fn load_cache(&self, key: &str) -> Result<Vec<u8>> {
-        let cache_file = self.cache_dir.join(format!("{}.cache", key));
-        if cache_file.exists() {
-            Ok(fs::read(&cache_file).await?)
-        } else {
-            Ok(Vec::new())
-        }
+        Ok(self.read(format!("cache/{}.cache", key)).await?.unwrap_or_default())
     }
 
     /// 保存数据文件
     pub async fn save_data(&self, filename: &str, data: &str) -> Result<()> {
-        let data_file = self.data_dir.join(filename);
-        fs::write(&data_file, data).await?;
-        Ok(())
+        self.write(format!("data/{}", filename), data.as_bytes().to_vec()).await
     }
 
     /// 加载数据文件
     pub async fn load_data(&self, filename: &str) -> Result<String> {
-        let data_file = self.data_dir.join(filename);
-        if data_file.exists() {
-            Ok(fs::read_to_string(&data_file).await?)
-        } else {
-            Ok(String::new())
+        match self.read(format!("data/{}", filename)).await? {
+            Some(content) => Ok(String::from_utf8(content)?),
+            None => Ok(String::new()),
         }
     }
 
     /// 清除所有缓存
     pub async fn clear_cache(&self) -> Result<()> {
-        if self.cache_dir.exists() {
-            fs::remove_dir_all(&self.cache_dir).await?;
-            fs::create_dir_all(&self.cache_dir).await?;
-            tracing::info!("Cleared all cache");
-        }
+        let backend = self.backend.clone();
+        tokio::task::spawn_blocking(move || backend.remove_prefix("cache")).await??;
+        tracing::info!("Cleared all cache");
         Ok(())
     }
 
@@ -357,34 +707,24 @@ impl StorageManager {
         &self.cache_dir
     }
 
-    /// 获取插件配置目录
-    fn get_plugin_config_dir(&self) -> PathBuf {
-        self.data_dir.join("plugins_config")
+    /// 插件配置在存储中的键
+    pub(crate) fn plugin_config_key(plugin_id: &str) -> String {
+        format!("data/plugins_config/{}.json", plugin_id)
     }
 
     /// 获取插件配置
     pub async fn get_plugin_config(&self, plugin_id: &str) -> Result<serde_json::Value> {
-        let config_dir = self.get_plugin_config_dir();
-        let config_file = config_dir.join(format!("{}.json", plugin_id));
-        
-        if config_file.exists() {
-            let content = fs::read_to_string(&config_file).await?;
-            let config: serde_json::Value = serde_json::from_str(&content)?;
-            Ok(config)
-        } else {
+        match self.read(Self::plugin_config_key(plugin_id)).await? {
+            Some(content) => Ok(serde_json::from_slice(&content)?),
             // 返回空对象
-            Ok(serde_json::json!({}))
+            None => Ok(serde_json::json!({})),
         }
     }
 
     /// 保存插件配置
     pub async fn save_plugin_config(&self, plugin_id: &str, config: serde_json::Value) -> Result<()> {
-        let config_dir = self.get_plugin_config_dir();
-        std::fs::create_dir_all(&config_dir)?;
-        
-        let config_file = config_dir.join(format!("{}.json", plugin_id));
         let content = serde_json::to_string_pretty(&config)?;
-        fs::write(&config_file, content).await?;
+        self.write(Self::plugin_config_key(plugin_id), content.into_bytes()).await?;
         
         tracing::info!("Saved config for plugin: {}", plugin_id);
         Ok(())
@@ -410,3 +750,43 @@ pub fn get_cache_dir() -> Result<PathBuf> {
     std::fs::create_dir_all(&cache_dir)?;
     Ok(cache_dir)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_storage_manager() {
+        let storage = StorageManager::in_memory();
+
+        let mut config = storage.load_config().await.unwrap();
+        config.general.max_results = 42;
+        storage.save_config(&config).await.unwrap();
+        assert_eq!(storage.load_config().await.unwrap().general.max_results, 42);
+
+        assert_eq!(storage.get_plugin_config("calc").await.unwrap(), serde_json::json!({}));
+        storage.save_plugin_config("calc", serde_json::json!({ "precision": 4 })).await.unwrap();
+        assert_eq!(storage.get_plugin_config("calc").await.unwrap()["precision"], 4);
+
+        storage.save_cache("icons", b"abc").await.unwrap();
+        assert_eq!(storage.load_cache("icons").await.unwrap(), b"abc");
+        storage.clear_cache().await.unwrap();
+        assert!(storage.load_cache("icons").await.unwrap().is_empty());
+
+        storage.save_data("notes.txt", "hello").await.unwrap();
+        assert_eq!(storage.load_data("notes.txt").await.unwrap(), "hello");
+        assert_eq!(storage.load_data("missing.txt").await.unwrap(), "");
+    }
+
+    #[test]
+    fn test_redacted_config_drops_local_api_token() {
+        let mut config = AppConfig::default();
+        config.local_api.token = "secret".to_string();
+        config.local_api.port = 30000;
+
+        let redacted = config.redacted();
+        assert!(redacted.local_api.token.is_empty());
+        assert_eq!(redacted.local_api.port, 30000);
+        assert!(!serde_json::to_string(&redacted).unwrap().contains("secret"));
+    }
+}
@@ -0,0 +1,176 @@
+// 文字转语音 - 使用系统语音合成朗读 AI 回答、释义和预览内容
+// Windows: System.Speech (SAPI) / macOS: say / Linux: espeak
+
+use crate::core::types::{Action, Preview, QueryResult, WoxImage};
+use crate::storage::TtsConfig;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::process::{Child, Command, Stdio};
+
+/// 全局语音合成服务
+pub static TTS_SERVICE: Lazy<TtsService> = Lazy::new(TtsService::new);
+
+/// 朗读操作（后端直接处理）
+pub const ACTION_READ_ALOUD: &str = "read_aloud";
+
+/// 朗读文本最大长度（字符）
+const MAX_SPEAK_LENGTH: usize = 5000;
+
+/// 支持朗读的插件（其余插件仅在有文本预览时提供）
+const SPEAKABLE_PLUGINS: &[&str] = &["ai_assistant", "translator"];
+
+pub struct TtsService {
+    current: Mutex<Option<Child>>,
+    /// 最近一次查询中可朗读结果的文本（result_id → text）
+    speakable: Mutex<HashMap<String, String>>,
+}
+
+impl TtsService {
+    fn new() -> Self {
+        Self {
+            current: Mutex::new(None),
+            speakable: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 为可朗读的结果注入"朗读"操作，并记录待朗读文本
+    pub fn attach_actions(&self, results: &mut [QueryResult]) {
+        let mut speakable = self.speakable.lock();
+        speakable.clear();
+
+        for result in results.iter_mut() {
+            let text = match &result.preview {
+                Some(Preview::Text(text)) | Some(Preview::Markdown(text)) => text.clone(),
+                _ if SPEAKABLE_PLUGINS.contains(&result.plugin_id.as_str()) => {
+                    format!("{}. {}", result.title, result.subtitle)
+                }
+                _ => continue,
+            };
+
+            speakable.insert(result.id.clone(), text);
+            result.actions.push(Action {
+                id: ACTION_READ_ALOUD.to_string(),
+                name: "Read Aloud".to_string(),
+                icon: Some(WoxImage::emoji("🔊")),
+                is_default: false,
+                hotkey: None,
+                prevent_hide: true,
+            });
+        }
+    }
+
+    /// 朗读某个结果（由 execute_action 调用）
+    pub fn speak_result(&self, result_id: &str, config: &TtsConfig) -> Result<()> {
+        let text = self
+            .speakable
+            .lock()
+            .get(result_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Nothing to read for this result"))?;
+        self.speak(&text, config)
+    }
+
+    /// 朗读文本（会打断正在进行的朗读）
+    pub fn speak(&self, text: &str, config: &TtsConfig) -> Result<()> {
+        self.stop();
+
+        let text: String = strip_markdown(text).chars().take(MAX_SPEAK_LENGTH).collect();
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+
+        let mut command = build_command(&text, config);
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        *self.current.lock() = Some(child);
+        tracing::info!("🔊 Speaking {} chars", text.chars().count());
+        Ok(())
+    }
+
+    /// 停止朗读
+    pub fn stop(&self) {
+        if let Some(mut child) = self.current.lock().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    /// 是否正在朗读
+    pub fn is_speaking(&self) -> bool {
+        let mut current = self.current.lock();
+        match current.as_mut().map(|c| c.try_wait()) {
+            Some(Ok(None)) => true,
+            Some(_) => {
+                *current = None;
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// 去掉常见 Markdown 标记，避免读出符号
+fn strip_markdown(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .map(|line| line.trim_start_matches(['#', '>', '-', '*', ' ']))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace("**", "")
+        .replace('`', "")
+}
+
+#[cfg(target_os = "windows")]
+fn build_command(text: &str, config: &TtsConfig) -> Command {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    // SAPI 语速范围 -10..10
+    let rate = ((config.rate - 1.0) * 10.0).round().clamp(-10.0, 10.0) as i32;
+    let escape = |s: &str| s.replace('\'', "''");
+    let select_voice = if config.voice.is_empty() {
+        String::new()
+    } else {
+        format!("$s.SelectVoice('{}');", escape(&config.voice))
+    };
+    let script = format!(
+        "Add-Type -AssemblyName System.Speech; $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; {} $s.Rate = {}; $s.Speak('{}')",
+        select_voice,
+        rate,
+        escape(text)
+    );
+
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn build_command(text: &str, config: &TtsConfig) -> Command {
+    let mut command = Command::new("say");
+    command.args(["-r", &((175.0 * config.rate) as i32).to_string()]);
+    if !config.voice.is_empty() {
+        command.args(["-v", &config.voice]);
+    }
+    command.arg(text);
+    command
+}
+
+#[cfg(target_os = "linux")]
+fn build_command(text: &str, config: &TtsConfig) -> Command {
+    let mut command = Command::new("espeak");
+    command.args(["-s", &((175.0 * config.rate) as i32).to_string()]);
+    if !config.voice.is_empty() {
+        command.args(["-v", &config.voice]);
+    }
+    command.arg(text);
+    command
+}
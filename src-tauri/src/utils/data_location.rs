@@ -0,0 +1,289 @@
+// 数据目录迁移 - 将配置/缓存/数据/MFT 索引等迁移到用户指定目录（如其他磁盘）
+// 流程：校验目标目录并登记迁移 → 重启 → 启动时在打开任何数据文件之前移动条目并切换重定向
+// （运行中复制 WAL 模式的数据库会丢失未合并的数据，之后写入旧目录的数据也不会被带走）
+
+use super::paths::{self, DataLocation, DATA_LOCATION_FILE};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// 不迁移的条目（日志文件正在写入，新位置会重新生成）
+const SKIPPED_ENTRIES: &[&str] = &[DATA_LOCATION_FILE, "logs"];
+
+/// 数据目录信息（返回给设置界面）
+#[derive(Debug, Clone, Serialize)]
+pub struct DataLocationInfo {
+    /// 本次运行使用的目录
+    pub current: PathBuf,
+    /// 默认目录
+    pub default: PathBuf,
+    /// 重定向文件中配置的目录（含待迁移的目录，重启后生效）
+    pub configured: PathBuf,
+    pub is_custom: bool,
+    /// 已登记迁移但尚未重启
+    pub restart_required: bool,
+}
+
+/// 迁移结果
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub entries: Vec<String>,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// 获取数据目录信息
+pub fn info() -> Result<DataLocationInfo> {
+    let current = paths::get_app_data_dir()?;
+    let default = paths::get_default_app_data_dir()?;
+    let location = paths::read_data_location();
+    let configured = location.pending.or(location.path).unwrap_or_else(|| default.clone());
+
+    Ok(DataLocationInfo {
+        restart_required: configured != current,
+        is_custom: configured != default,
+        current,
+        default,
+        configured,
+    })
+}
+
+/// 登记把当前数据迁移到 target（None 表示恢复默认目录），重启后在启动时执行
+pub fn migrate(target: Option<PathBuf>) -> Result<MigrationReport> {
+    let current = paths::get_app_data_dir()?;
+    let default = paths::get_default_app_data_dir()?;
+    let target = target.unwrap_or_else(|| default.clone());
+
+    let mut location = paths::read_data_location();
+    if location.pending.is_some() {
+        return Err(anyhow!("A data directory migration is pending, restart iLauncher first"));
+    }
+
+    let entries = migratable_entries(&current)?;
+    validate_target(&current, &target, &entries)?;
+
+    let mut report = MigrationReport {
+        from: current,
+        to: target.clone(),
+        entries,
+        files: 0,
+        bytes: 0,
+    };
+    for entry in &report.entries {
+        let (files, bytes) = measure_entry(&report.from.join(entry));
+        report.files += files;
+        report.bytes += bytes;
+    }
+
+    location.pending = Some(target);
+    paths::write_data_location(&location)?;
+
+    tracing::info!(
+        "📦 Data directory migration scheduled: {:?} → {:?} ({} files, {} bytes), restart required",
+        report.from,
+        report.to,
+        report.files,
+        report.bytes
+    );
+    Ok(report)
+}
+
+/// 启动时执行登记的迁移（须在解析数据目录、打开任何数据文件之前调用，此时日志尚未初始化，由调用方记录结果）
+pub fn apply_pending_migration() -> Option<Result<MigrationReport>> {
+    let mut location = paths::read_data_location();
+    let target = location.pending.take()?;
+
+    let result = paths::get_default_app_data_dir().and_then(|default| {
+        let current = location.path.clone().unwrap_or_else(|| default.clone());
+        let report = move_data(&current, &target)?;
+        location.path = (target != default).then(|| target.clone());
+        Ok(report)
+    });
+
+    // 失败时保留原目录和重定向，只清除登记，避免每次启动重复尝试
+    if let Err(e) = paths::write_data_location(&location) {
+        return Some(Err(e.context("Failed to update data location file")));
+    }
+    Some(result)
+}
+
+/// 把 current 中的条目移动到 target：全部复制成功后才删除旧条目，失败时回滚已复制的条目
+fn move_data(current: &Path, target: &Path) -> Result<MigrationReport> {
+    let entries = migratable_entries(current)?;
+    validate_target(current, target, &entries)?;
+
+    let mut report = MigrationReport {
+        from: current.to_path_buf(),
+        to: target.to_path_buf(),
+        entries: entries.clone(),
+        files: 0,
+        bytes: 0,
+    };
+
+    for entry in &entries {
+        if let Err(e) = copy_entry(&current.join(entry), &target.join(entry), &mut report) {
+            // 回滚：删除已复制的条目，保持旧目录不变
+            for copied in &entries {
+                remove_entry(&target.join(copied));
+            }
+            return Err(e.context(format!("Failed to copy '{}'", entry)));
+        }
+    }
+
+    for entry in &entries {
+        remove_entry(&current.join(entry));
+    }
+    Ok(report)
+}
+
+/// 统计条目的文件数和字节数
+fn measure_entry(path: &Path) -> (u64, u64) {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .fold((0, 0), |(files, bytes), e| {
+            (files + 1, bytes + e.metadata().map(|m| m.len()).unwrap_or(0))
+        })
+}
+
+/// 当前目录中需要迁移的条目
+fn migratable_entries(dir: &Path) -> Result<Vec<String>> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if !SKIPPED_ENTRIES.contains(&name.as_str()) {
+            entries.push(name);
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// 校验目标目录
+fn validate_target(current: &Path, target: &Path, entries: &[String]) -> Result<()> {
+    if !target.is_absolute() {
+        return Err(anyhow!("Data directory must be an absolute path"));
+    }
+    if target.parent().is_none() {
+        return Err(anyhow!("Please choose a folder instead of a drive root"));
+    }
+    if target == current {
+        return Err(anyhow!("Data is already stored in {:?}", target));
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return Err(anyhow!("Data directory cannot be nested inside the current one"));
+    }
+
+    std::fs::create_dir_all(target).with_context(|| format!("Cannot create {:?}", target))?;
+
+    // 不覆盖目标目录中已有的同名数据
+    if let Some(conflict) = entries.iter().find(|e| target.join(e).exists()) {
+        return Err(anyhow!("{:?} already contains '{}'", target, conflict));
+    }
+
+    // 写入权限检查
+    let probe = target.join(".ilauncher_write_test");
+    std::fs::write(&probe, b"ok").with_context(|| format!("{:?} is not writable", target))?;
+    let _ = std::fs::remove_file(probe);
+    Ok(())
+}
+
+/// 递归复制文件或目录
+fn copy_entry(from: &Path, to: &Path, report: &mut MigrationReport) -> Result<()> {
+    if from.is_file() {
+        report.bytes += std::fs::copy(from, to)?;
+        report.files += 1;
+        return Ok(());
+    }
+
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(from)?;
+        let dest = to.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest)?;
+        } else {
+            report.bytes += std::fs::copy(entry.path(), &dest)?;
+            report.files += 1;
+        }
+    }
+    Ok(())
+}
+
+fn remove_entry(path: &Path) {
+    let result = if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        std::fs::remove_file(path)
+    } else {
+        Ok(())
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to remove {:?}: {}", path, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_target_rejects_nested_and_relative() {
+        let current = std::env::temp_dir().join("ilauncher_location_test");
+        assert!(validate_target(&current, Path::new("relative/dir"), &[]).is_err());
+        assert!(validate_target(&current, &current, &[]).is_err());
+        assert!(validate_target(&current, &current.join("nested"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_move_data_moves_entries() {
+        let root = std::env::temp_dir().join(format!("ilauncher_move_test_{}", uuid::Uuid::new_v4()));
+        let current = root.join("current");
+        std::fs::create_dir_all(current.join("data")).unwrap();
+        std::fs::create_dir_all(current.join("logs")).unwrap();
+        std::fs::write(current.join("data").join("statistics.db"), b"db").unwrap();
+        std::fs::write(current.join("config.json"), b"{}").unwrap();
+
+        let report = move_data(&current, &root.join("target")).unwrap();
+        assert_eq!(report.entries, vec!["config.json", "data"]);
+        assert_eq!(report.files, 2);
+        assert!(root.join("target").join("data").join("statistics.db").is_file());
+        assert!(!current.join("data").exists());
+        assert!(!current.join("config.json").exists());
+        // 日志目录不迁移
+        assert!(current.join("logs").is_dir());
+
+        // 目标目录已有同名条目时不移动，旧目录保持不变
+        std::fs::write(current.join("config.json"), b"{}").unwrap();
+        assert!(move_data(&current, &root.join("target")).is_err());
+        assert!(current.join("config.json").is_file());
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_copy_entry_counts_files() {
+        let root = std::env::temp_dir().join(format!("ilauncher_copy_test_{}", uuid::Uuid::new_v4()));
+        let from = root.join("from");
+        std::fs::create_dir_all(from.join("sub")).unwrap();
+        std::fs::write(from.join("a.json"), b"{}").unwrap();
+        std::fs::write(from.join("sub").join("b.dat"), b"1234").unwrap();
+
+        let mut report = MigrationReport {
+            from: from.clone(),
+            to: root.join("to"),
+            entries: vec![],
+            files: 0,
+            bytes: 0,
+        };
+        copy_entry(&from, &root.join("to"), &mut report).unwrap();
+
+        assert_eq!(report.files, 2);
+        assert_eq!(report.bytes, 6);
+        assert!(root.join("to").join("sub").join("b.dat").is_file());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
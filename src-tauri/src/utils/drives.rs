@@ -0,0 +1,100 @@
+// 驱动器检测 - 盘符、驱动器类型和文件系统
+// MFT 扫描只支持 NTFS 固定磁盘，其余驱动器（exFAT/FAT32/U 盘等）由文件搜索的监听索引兜底
+
+/// 驱动器类型（对应 GetDriveTypeW）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveKind {
+    Fixed,
+    Removable,
+    Network,
+    CdRom,
+    Other,
+}
+
+/// 驱动器信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriveInfo {
+    pub letter: char,
+    pub kind: DriveKind,
+    /// 文件系统名称（NTFS / exFAT / FAT32 ...），读取失败时为空
+    pub file_system: String,
+}
+
+impl DriveInfo {
+    /// 根目录路径，如 "E:\"
+    pub fn root(&self) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!("{}:\\", self.letter))
+    }
+
+    pub fn is_ntfs(&self) -> bool {
+        self.file_system.eq_ignore_ascii_case("NTFS")
+    }
+
+    /// 是否可以使用 MFT 扫描（NTFS 固定磁盘）
+    pub fn supports_mft(&self) -> bool {
+        self.kind == DriveKind::Fixed && self.is_ntfs()
+    }
+}
+
+/// 列出所有逻辑驱动器
+#[cfg(target_os = "windows")]
+pub fn list_drives() -> Vec<DriveInfo> {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW};
+
+    let mut drives = Vec::new();
+
+    unsafe {
+        // 获取所有逻辑驱动器的位掩码
+        let drive_mask = GetLogicalDrives();
+
+        for i in 0..26u8 {
+            if (drive_mask & (1 << i)) == 0 {
+                continue;
+            }
+
+            let letter = (b'A' + i) as char;
+            let root_path: Vec<u16> = format!("{}:\\", letter)
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect();
+
+            // 2 = DRIVE_REMOVABLE, 3 = DRIVE_FIXED, 4 = DRIVE_REMOTE, 5 = DRIVE_CDROM
+            let kind = match GetDriveTypeW(PCWSTR(root_path.as_ptr())) {
+                2 => DriveKind::Removable,
+                3 => DriveKind::Fixed,
+                4 => DriveKind::Network,
+                5 => DriveKind::CdRom,
+                _ => DriveKind::Other,
+            };
+
+            // 读卡器等未插入介质时读取失败，文件系统留空
+            let mut fs_name = vec![0u16; 32];
+            let file_system = match GetVolumeInformationW(
+                PCWSTR(root_path.as_ptr()),
+                None,
+                None,
+                None,
+                None,
+                Some(&mut fs_name),
+            ) {
+                Ok(()) => OsString::from_wide(&fs_name)
+                    .to_string_lossy()
+                    .trim_end_matches('\0')
+                    .to_string(),
+                Err(_) => String::new(),
+            };
+
+            drives.push(DriveInfo { letter, kind, file_system });
+        }
+    }
+
+    drives
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn list_drives() -> Vec<DriveInfo> {
+    Vec::new()
+}
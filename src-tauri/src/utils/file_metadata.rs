@@ -0,0 +1,157 @@
+// 文件元数据 - Windows 下载来源标记（Mark-of-the-Web）
+// 浏览器/邮件客户端下载的文件带有 Zone.Identifier 备用数据流，记录安全区域和来源 URL；
+// 移除该数据流即“解除锁定”（等同于文件属性中的“解除锁定”复选框）
+
+use std::path::Path;
+
+/// 下载来源标记所在的备用数据流名称
+pub const ZONE_STREAM: &str = "Zone.Identifier";
+
+/// URL 安全区域（URLZONE_*）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityZone {
+    LocalMachine,
+    Intranet,
+    Trusted,
+    Internet,
+    Restricted,
+}
+
+impl SecurityZone {
+    fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::LocalMachine),
+            1 => Some(Self::Intranet),
+            2 => Some(Self::Trusted),
+            3 => Some(Self::Internet),
+            4 => Some(Self::Restricted),
+            _ => None,
+        }
+    }
+}
+
+/// Zone.Identifier 内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneIdentifier {
+    pub zone: SecurityZone,
+    /// 下载页面
+    pub referrer_url: Option<String>,
+    /// 文件本身的下载地址
+    pub host_url: Option<String>,
+}
+
+impl ZoneIdentifier {
+    /// 解析 [ZoneTransfer] 段（ZoneId / ReferrerUrl / HostUrl），没有 ZoneId 时返回 None
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut zone = None;
+        let mut referrer_url = None;
+        let mut host_url = None;
+
+        for line in content.lines() {
+            let Some((key, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "ZoneId" => zone = value.parse().ok().and_then(SecurityZone::from_id),
+                "ReferrerUrl" if !value.is_empty() => referrer_url = Some(value.to_string()),
+                "HostUrl" if !value.is_empty() => host_url = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            zone: zone?,
+            referrer_url,
+            host_url,
+        })
+    }
+
+    /// 是否来自互联网或受限站点（系统会对这类文件弹出安全警告）
+    pub fn is_untrusted(&self) -> bool {
+        matches!(self.zone, SecurityZone::Internet | SecurityZone::Restricted)
+    }
+
+    /// 来源 URL（优先使用文件下载地址）
+    pub fn origin(&self) -> Option<&str> {
+        self.host_url.as_deref().or(self.referrer_url.as_deref())
+    }
+}
+
+/// 数据流内容按 UTF-16（带 BOM）或 UTF-8 解码
+fn decode_stream(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn stream_path(path: &Path) -> std::path::PathBuf {
+    let mut stream = path.as_os_str().to_owned();
+    stream.push(":");
+    stream.push(ZONE_STREAM);
+    stream.into()
+}
+
+/// 读取文件的下载来源标记（没有标记或无法读取时返回 None）
+#[cfg(target_os = "windows")]
+pub fn read_zone_identifier(path: &Path) -> Option<ZoneIdentifier> {
+    let bytes = std::fs::read(stream_path(path)).ok()?;
+    ZoneIdentifier::parse(&decode_stream(&bytes))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_zone_identifier(_path: &Path) -> Option<ZoneIdentifier> {
+    None
+}
+
+/// 移除下载来源标记（解除锁定），标记不存在时视为成功
+#[cfg(target_os = "windows")]
+pub fn remove_zone_identifier(path: &Path) -> anyhow::Result<()> {
+    match std::fs::remove_file(stream_path(path)) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(anyhow::anyhow!("Failed to unblock {}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn remove_zone_identifier(_path: &Path) -> anyhow::Result<()> {
+    anyhow::bail!("Unblocking files is only supported on Windows")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zone_identifier() {
+        let content = "[ZoneTransfer]\r\nZoneId=3\r\nReferrerUrl=https://example.com/downloads\r\nHostUrl=https://cdn.example.com/setup.exe\r\n";
+        let zone = ZoneIdentifier::parse(content).unwrap();
+        assert_eq!(zone.zone, SecurityZone::Internet);
+        assert!(zone.is_untrusted());
+        assert_eq!(zone.origin(), Some("https://cdn.example.com/setup.exe"));
+
+        let local = ZoneIdentifier::parse("[ZoneTransfer]\nZoneId=0\nHostUrl=\n").unwrap();
+        assert!(!local.is_untrusted());
+        assert_eq!(local.origin(), None);
+
+        assert!(ZoneIdentifier::parse("[ZoneTransfer]\nReferrerUrl=x").is_none());
+        assert!(ZoneIdentifier::parse("ZoneId=9").is_none());
+    }
+
+    #[test]
+    fn test_decode_utf16_stream() {
+        let text = "[ZoneTransfer]\r\nZoneId=4\r\n";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+
+        let zone = ZoneIdentifier::parse(&decode_stream(&bytes)).unwrap();
+        assert_eq!(zone.zone, SecurityZone::Restricted);
+        assert_eq!(decode_stream(text.as_bytes()), text);
+    }
+}
@@ -0,0 +1,209 @@
+// 索引排除规则 - MFT 全量扫描、USN 增量更新和 BFS 遍历共用
+// 规则保存在 file_search 插件配置中，支持目录名、路径前缀、扩展名和通配符：
+//   node_modules      任意一级目录/文件名
+//   build*            名称通配符
+//   *.tmp             扩展名
+//   C:\Temp           绝对路径前缀（含子项）
+//   AppData\Local\Temp 相对路径片段（任意位置）
+//   */cache/*.bin     含路径分隔符的通配符匹配完整路径
+
+use crate::storage::backend::{FileStorage, Storage};
+use crate::storage::StorageManager;
+use crate::utils::wildcard;
+
+/// 插件配置中的字段名
+pub const EXCLUSIONS_KEY: &str = "index_exclusions";
+
+/// 默认排除规则（依赖目录、构建产物、临时目录）
+pub const DEFAULT_EXCLUSIONS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".git",
+    "__pycache__",
+    ".gradle",
+    "*.tmp",
+    "AppData\\Local\\Temp",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Rule {
+    /// 路径中任意一段等于该名称
+    Name(String),
+    /// 路径中任意一段匹配通配符
+    NameGlob(String),
+    /// 文件扩展名（不含点）
+    Extension(String),
+    /// 绝对路径前缀
+    Prefix(String),
+    /// 相对路径片段（如 appdata/local/temp）
+    Segment(String),
+    /// 完整路径通配符
+    PathGlob(String),
+}
+
+impl Rule {
+    fn parse(pattern: &str) -> Option<Self> {
+        let pattern = normalize(pattern.trim());
+        let pattern = pattern.trim_end_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+
+        let has_wildcard = pattern.contains('*');
+        if let Some(ext) = pattern.strip_prefix("*.") {
+            if !ext.is_empty() && !ext.contains('*') && !ext.contains('/') {
+                return Some(Rule::Extension(ext.to_string()));
+            }
+        }
+
+        let rule = if !pattern.contains('/') {
+            if has_wildcard {
+                Rule::NameGlob(pattern.to_string())
+            } else {
+                Rule::Name(pattern.to_string())
+            }
+        } else if has_wildcard {
+            Rule::PathGlob(pattern.to_string())
+        } else if pattern.starts_with('/') || pattern.as_bytes().get(1) == Some(&b':') {
+            Rule::Prefix(pattern.to_string())
+        } else {
+            Rule::Segment(format!("/{}/", pattern.trim_start_matches('/')))
+        };
+        Some(rule)
+    }
+
+    /// path 已统一为小写、`/` 分隔
+    fn matches(&self, path: &str) -> bool {
+        let mut components = path.split('/').filter(|c| !c.is_empty());
+        match self {
+            Rule::Name(name) => components.any(|c| c == name),
+            Rule::NameGlob(pattern) => components.any(|c| wildcard::matches(pattern, c)),
+            Rule::Extension(ext) => components
+                .last()
+                .and_then(|name| name.rsplit_once('.'))
+                .is_some_and(|(_, e)| e == ext),
+            Rule::Prefix(prefix) => {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            }
+            Rule::Segment(segment) => format!("/{}/", path.trim_matches('/')).contains(segment.as_str()),
+            Rule::PathGlob(pattern) => wildcard::matches(pattern, path),
+        }
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.to_lowercase().replace('\\', "/")
+}
+
+/// 索引排除规则集合
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexExclusions {
+    rules: Vec<Rule>,
+}
+
+impl Default for IndexExclusions {
+    fn default() -> Self {
+        Self::new(DEFAULT_EXCLUSIONS.iter().map(|s| s.to_string()))
+    }
+}
+
+impl IndexExclusions {
+    pub fn new(patterns: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            rules: patterns.into_iter().filter_map(|p| Rule::parse(&p)).collect(),
+        }
+    }
+
+    /// 从插件配置读取（逗号/分号/换行分隔的字符串或字符串数组，缺失时使用默认规则）
+    pub fn from_plugin_config(config: &serde_json::Value) -> Self {
+        match config.get(EXCLUSIONS_KEY) {
+            Some(serde_json::Value::String(text)) => Self::new(text.split([',', ';', '\n']).map(str::to_string)),
+            Some(serde_json::Value::Array(items)) => {
+                Self::new(items.iter().filter_map(|v| v.as_str()).map(str::to_string))
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// 同步读取 file_search 插件配置中的规则（MFT Service 进程和扫描线程使用）
+    pub fn load() -> Self {
+        let config = crate::utils::paths::get_app_data_dir()
+            .and_then(|dir| FileStorage::new(dir).read(&StorageManager::plugin_config_key("file_search")))
+            .ok()
+            .flatten()
+            .and_then(|content| serde_json::from_slice::<serde_json::Value>(&content).ok())
+            .unwrap_or_else(|| serde_json::json!({}));
+
+        let exclusions = Self::from_plugin_config(&config);
+        tracing::info!("📋 Index exclusions loaded: {} rules", exclusions.rules.len());
+        exclusions
+    }
+
+    /// 规则指纹（用于判断缓存的索引是否按当前规则构建）
+    pub fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 路径（文件或目录）是否应排除在索引之外
+    pub fn is_excluded(&self, path: &str) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let path = normalize(path);
+        self.rules.iter().any(|rule| rule.matches(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_kinds() {
+        let exclusions = IndexExclusions::new(
+            ["node_modules", "build*", "*.tmp", "C:\\Temp\\", "AppData\\Local\\Temp", "*/cache/*.bin"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+
+        // 目录名（含子项）
+        assert!(exclusions.is_excluded("D:\\web\\node_modules"));
+        assert!(exclusions.is_excluded("D:\\web\\node_modules\\react\\index.js"));
+        assert!(!exclusions.is_excluded("D:\\web\\node_modules_backup.txt"));
+        // 名称通配符
+        assert!(exclusions.is_excluded("D:\\proj\\build-release\\app.exe"));
+        // 扩展名
+        assert!(exclusions.is_excluded("D:\\docs\\~report.TMP"));
+        assert!(!exclusions.is_excluded("D:\\docs\\report.tmpl"));
+        // 绝对路径前缀
+        assert!(exclusions.is_excluded("c:\\temp"));
+        assert!(exclusions.is_excluded("C:\\Temp\\a.txt"));
+        assert!(!exclusions.is_excluded("C:\\Temporary\\a.txt"));
+        // 相对路径片段
+        assert!(exclusions.is_excluded("C:\\Users\\me\\AppData\\Local\\Temp\\x.log"));
+        assert!(!exclusions.is_excluded("C:\\Users\\me\\AppData\\Local\\Programs\\x.exe"));
+        // 完整路径通配符
+        assert!(exclusions.is_excluded("/home/me/cache/blob.bin"));
+        assert!(!exclusions.is_excluded("/home/me/cache/blob.txt"));
+    }
+
+    #[test]
+    fn test_from_plugin_config() {
+        let exclusions = IndexExclusions::from_plugin_config(&serde_json::json!({ "index_exclusions": "target, ;\n*.log" }));
+        assert_eq!(exclusions.rules, vec![Rule::Name("target".to_string()), Rule::Extension("log".to_string())]);
+
+        let exclusions = IndexExclusions::from_plugin_config(&serde_json::json!({ "index_exclusions": [] }));
+        assert!(exclusions.is_empty());
+        assert!(!exclusions.is_excluded("C:\\node_modules"));
+
+        assert_eq!(IndexExclusions::from_plugin_config(&serde_json::json!({})), IndexExclusions::default());
+    }
+}
@@ -1,4 +1,10 @@
 pub mod paths;
+pub mod data_location;
+pub mod profile_backup;
+pub mod drives;
 pub mod autostart;
+pub mod index_exclusions;
+pub mod wildcard;
+pub mod file_metadata;
 #[cfg(target_os = "windows")]
 pub mod icon_cache;
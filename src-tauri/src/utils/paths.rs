@@ -1,10 +1,29 @@
 // 统一的路径管理模块
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 
-/// 获取应用数据根目录 (AppData\Local\iLauncher)
-pub fn get_app_data_dir() -> Result<PathBuf> {
+/// 数据目录重定向文件（始终位于默认目录）
+pub const DATA_LOCATION_FILE: &str = "data_location.json";
+
+/// 本次运行使用的数据根目录（首次解析后固定，迁移在重启后生效）
+static APP_DATA_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// 数据目录重定向配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataLocation {
+    /// 自定义数据目录（None 表示使用默认目录）
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// 待迁移的目标目录（下次启动时在打开任何数据文件之前执行）
+    #[serde(default)]
+    pub pending: Option<PathBuf>,
+}
+
+/// 获取默认应用数据根目录 (AppData\Local\iLauncher)
+pub fn get_default_app_data_dir() -> Result<PathBuf> {
     let local_appdata = std::env::var("LOCALAPPDATA")
         .context("Failed to get LOCALAPPDATA environment variable")?;
     
@@ -19,6 +38,62 @@ pub fn get_app_data_dir() -> Result<PathBuf> {
     Ok(app_dir)
 }
 
+/// 读取数据目录重定向配置
+pub fn read_data_location() -> DataLocation {
+    get_default_app_data_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(DATA_LOCATION_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 写入数据目录重定向配置
+pub fn write_data_location(location: &DataLocation) -> Result<()> {
+    let path = get_default_app_data_dir()?.join(DATA_LOCATION_FILE);
+    if location.path.is_none() && location.pending.is_none() {
+        if path.exists() {
+            std::fs::remove_file(&path).context("Failed to remove data location file")?;
+        }
+        return Ok(());
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(location)?)
+        .context("Failed to write data location file")
+}
+
+/// 指定数据根目录（MFT 服务以 --data-dir 参数启动时使用，须在首次获取路径前调用）
+pub fn set_app_data_dir_override(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).context("Failed to create app data directory")?;
+    APP_DATA_DIR
+        .set(dir.to_path_buf())
+        .map_err(|_| anyhow::anyhow!("App data directory already resolved"))
+}
+
+/// 获取应用数据根目录（默认 AppData\Local\iLauncher，可通过重定向文件指向其他目录）
+pub fn get_app_data_dir() -> Result<PathBuf> {
+    if let Some(dir) = APP_DATA_DIR.get() {
+        return Ok(dir.clone());
+    }
+    
+    let app_dir = resolve_app_data_dir()?;
+    Ok(APP_DATA_DIR.get_or_init(|| app_dir).clone())
+}
+
+/// 解析数据根目录：重定向目录不可用（如移动硬盘未连接）时回退到默认目录
+fn resolve_app_data_dir() -> Result<PathBuf> {
+    let default_dir = get_default_app_data_dir()?;
+    
+    if let Some(custom) = read_data_location().path {
+        match std::fs::create_dir_all(&custom) {
+            Ok(()) => return Ok(custom),
+            Err(e) => {
+                tracing::warn!("Custom data directory {:?} unavailable ({}), using default", custom, e);
+            }
+        }
+    }
+    
+    Ok(default_dir)
+}
+
 /// 获取 MFT 数据库目录 (AppData\Local\iLauncher\mft_databases)
 pub fn get_mft_database_dir() -> Result<PathBuf> {
     let app_dir = get_app_data_dir()?;
@@ -0,0 +1,571 @@
+// 配置档案备份 - 把设置、插件配置、搜索历史、统计数据库、剪贴板收藏和工作流打包为带版本号的 zip，
+// 用于换机迁移和备份。导入时先解压到暂存目录，重启后在各管理器打开文件之前替换到数据目录
+
+use super::paths;
+use crate::storage::clipboard_db::ClipboardDatabase;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// 档案格式版本（结构不兼容时递增）
+pub const PROFILE_FORMAT_VERSION: u32 = 1;
+
+/// 档案内的清单文件
+const MANIFEST_ENTRY: &str = "profile.json";
+
+/// 剪贴板收藏（以 JSON 导出，导入时合并到剪贴板数据库）
+const FAVORITES_ENTRY: &str = "clipboard_favorites.json";
+
+/// 统计数据库（相对数据目录）
+const STATISTICS_DB: &str = "data/statistics.db";
+
+/// 单个文件条目（相对数据目录）
+const FILE_ENTRIES: &[&str] = &["config/config.json", "data/search_history.json", STATISTICS_DB];
+
+/// 插件配置目录（相对数据目录）
+const PLUGINS_CONFIG_DIR: &str = "data/plugins_config";
+
+/// 整个目录的 JSON 文件（相对数据目录）
+const DIR_ENTRIES: &[&str] = &[PLUGINS_CONFIG_DIR, "cache/workflows"];
+
+/// 插件配置中视为凭据的键名片段（小写匹配），导出时移除
+const SECRET_KEY_MARKERS: &[&str] = &["api_key", "apikey", "token", "password", "secret"];
+
+/// 导入暂存目录（相对数据目录）
+const PENDING_DIR: &str = "profile_import";
+
+/// 档案清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: i64,
+    pub entries: Vec<String>,
+}
+
+/// 导出的剪贴板收藏
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteEntry {
+    pub content_type: String,
+    pub content: String,
+    #[serde(default)]
+    pub preview: Option<String>,
+    pub timestamp: i64,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// 导出 / 导入结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub path: PathBuf,
+    pub manifest: ProfileManifest,
+    /// 导入后需要重启才能生效
+    pub restart_required: bool,
+}
+
+/// 导出当前档案到 dest
+pub fn export(dest: &Path) -> Result<ProfileReport> {
+    let root = paths::get_app_data_dir()?;
+    let favorites = match ClipboardDatabase::new(root.join("clipboard.db")) {
+        Ok(db) => db
+            .get_all_favorites()?
+            .into_iter()
+            // 图片内容保存在单独的文件中，不随档案导出
+            .filter(|record| record.file_path.is_none())
+            .map(|record| FavoriteEntry {
+                content_type: record.content_type,
+                content: record.content,
+                preview: record.preview,
+                timestamp: record.timestamp.timestamp(),
+                category: record.category,
+                tags: record.tags,
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to read clipboard favorites: {}", e);
+            Vec::new()
+        }
+    };
+    export_from(&root, dest, &favorites)
+}
+
+/// 校验档案并解压到暂存目录，重启后生效
+pub fn import(archive: &Path) -> Result<ProfileReport> {
+    stage_import(&paths::get_app_data_dir()?, archive)
+}
+
+/// 启动时应用暂存的导入（须在存储、统计、剪贴板等管理器打开文件之前调用）
+pub fn apply_pending_import() {
+    let root = match paths::get_app_data_dir() {
+        Ok(dir) => dir,
+        Err(_) => return,
+    };
+    if !root.join(PENDING_DIR).is_dir() {
+        return;
+    }
+
+    match apply_staged(&root) {
+        Ok(files) => {
+            tracing::info!("📦 Restored profile ({} files)", files);
+            if let Err(e) = std::fs::remove_dir_all(root.join(PENDING_DIR)) {
+                tracing::warn!("Failed to remove staged profile: {}", e);
+            }
+        }
+        // 保留暂存文件，下次启动重试，避免半途失败后导入的数据丢失
+        Err(e) => tracing::error!("Failed to restore profile, staged files kept for retry: {}", e),
+    }
+}
+
+fn export_from(root: &Path, dest: &Path, favorites: &[FavoriteEntry]) -> Result<ProfileReport> {
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+    for entry in FILE_ENTRIES {
+        let path = root.join(entry);
+        if !path.is_file() {
+            continue;
+        }
+        let bytes = if *entry == STATISTICS_DB {
+            snapshot_database(&path)?
+        } else {
+            let bytes = std::fs::read(&path).with_context(|| format!("Failed to read {:?}", path))?;
+            if *entry == "config/config.json" {
+                redact_app_config(bytes)?
+            } else {
+                bytes
+            }
+        };
+        files.push((entry.to_string(), bytes));
+    }
+
+    for dir in DIR_ENTRIES {
+        let Ok(read_dir) = std::fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+        let mut names: Vec<String> = read_dir
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".json"))
+            .collect();
+        names.sort();
+        for name in names {
+            let entry = format!("{}/{}", dir, name);
+            let bytes = std::fs::read(root.join(dir).join(&name))?;
+            let bytes = if *dir == PLUGINS_CONFIG_DIR { strip_plugin_secrets(bytes) } else { bytes };
+            files.push((entry, bytes));
+        }
+    }
+
+    if !favorites.is_empty() {
+        files.push((FAVORITES_ENTRY.to_string(), serde_json::to_vec_pretty(favorites)?));
+    }
+
+    let manifest = ProfileManifest {
+        format_version: PROFILE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        entries: files.iter().map(|(name, _)| name.clone()).collect(),
+    };
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(dest).with_context(|| format!("Failed to create {:?}", dest))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(MANIFEST_ENTRY, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    for (name, bytes) in &files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish()?;
+
+    tracing::info!("📦 Exported profile to {:?} ({} entries)", dest, manifest.entries.len());
+    Ok(ProfileReport {
+        path: dest.to_path_buf(),
+        manifest,
+        restart_required: false,
+    })
+}
+
+/// 档案可能被分享或同步到云盘：移除本地 API 令牌，导入后启动时重新生成
+fn redact_app_config(bytes: Vec<u8>) -> Result<Vec<u8>> {
+    let mut config: serde_json::Value = serde_json::from_slice(&bytes).context("Invalid config/config.json")?;
+    match config.pointer_mut("/local_api/token") {
+        Some(token) if token.as_str().is_some_and(|t| !t.is_empty()) => {
+            *token = serde_json::Value::String(String::new());
+            Ok(serde_json::to_vec_pretty(&config)?)
+        }
+        _ => Ok(bytes),
+    }
+}
+
+/// 移除插件配置中的 API Key、密码等凭据（无法解析的文件原样保留，导入时仍按 JSON 处理）
+fn strip_plugin_secrets(bytes: Vec<u8>) -> Vec<u8> {
+    let Ok(mut config) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return bytes;
+    };
+    if !remove_secret_keys(&mut config) {
+        return bytes;
+    }
+    serde_json::to_vec_pretty(&config).unwrap_or(bytes)
+}
+
+/// 递归移除凭据键，返回是否有改动
+fn remove_secret_keys(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => {
+            let before = map.len();
+            map.retain(|key, _| {
+                let key = key.to_lowercase();
+                !SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+            });
+            let mut changed = map.len() != before;
+            for child in map.values_mut() {
+                changed |= remove_secret_keys(child);
+            }
+            changed
+        }
+        serde_json::Value::Array(items) => items.iter_mut().fold(false, |changed, item| remove_secret_keys(item) | changed),
+        _ => false,
+    }
+}
+
+/// 数据库以 WAL 模式运行，直接复制文件可能丢失未合并的数据，用 VACUUM INTO 生成一致的快照
+fn snapshot_database(path: &Path) -> Result<Vec<u8>> {
+    let snapshot = std::env::temp_dir().join(format!("ilauncher_profile_{}.db", uuid::Uuid::new_v4().simple()));
+    let conn = rusqlite::Connection::open(path)?;
+    conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy().to_string()])?;
+    drop(conn);
+
+    let bytes = std::fs::read(&snapshot);
+    let _ = std::fs::remove_file(&snapshot);
+    Ok(bytes?)
+}
+
+fn stage_import(root: &Path, archive_path: &Path) -> Result<ProfileReport> {
+    let file = std::fs::File::open(archive_path).with_context(|| format!("Failed to open {:?}", archive_path))?;
+    let mut archive = zip::ZipArchive::new(file).context("Not a valid profile archive")?;
+
+    let manifest: ProfileManifest = {
+        let mut entry = archive
+            .by_name(MANIFEST_ENTRY)
+            .map_err(|_| anyhow!("Not an iLauncher profile (missing {})", MANIFEST_ENTRY))?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        serde_json::from_str(&content)?
+    };
+    if manifest.format_version > PROFILE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "Profile was created by a newer iLauncher ({}), please upgrade first",
+            manifest.app_version
+        ));
+    }
+
+    let pending = root.join(PENDING_DIR);
+    if pending.exists() {
+        std::fs::remove_dir_all(&pending)?;
+    }
+
+    let mut restored = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+        // 只接受已知条目，防止档案写入数据目录以外的位置
+        if !is_restorable(&name) {
+            if name != MANIFEST_ENTRY {
+                tracing::warn!("Skipping unknown profile entry: {}", name);
+            }
+            continue;
+        }
+
+        let target = pending.join(&name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        std::fs::write(&target, bytes)?;
+        restored.push(name);
+    }
+
+    if restored.is_empty() {
+        let _ = std::fs::remove_dir_all(&pending);
+        return Err(anyhow!("Profile archive contains no restorable data"));
+    }
+
+    tracing::info!("📦 Staged profile import from {:?} ({} entries), restart required", archive_path, restored.len());
+    Ok(ProfileReport {
+        path: archive_path.to_path_buf(),
+        manifest: ProfileManifest { entries: restored, ..manifest },
+        restart_required: true,
+    })
+}
+
+/// 档案条目是否允许恢复
+fn is_restorable(name: &str) -> bool {
+    if name == FAVORITES_ENTRY || FILE_ENTRIES.contains(&name) {
+        return true;
+    }
+    DIR_ENTRIES.iter().any(|dir| {
+        name.strip_prefix(dir)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .is_some_and(|file| {
+                file.ends_with(".json") && !file.contains(['/', '\\']) && !file.starts_with('.')
+            })
+    })
+}
+
+/// 把暂存目录中的文件替换到数据目录，返回恢复的文件数
+fn apply_staged(root: &Path) -> Result<usize> {
+    let pending = root.join(PENDING_DIR);
+    let mut files = 0;
+
+    for entry in FILE_ENTRIES {
+        let staged = pending.join(entry);
+        if !staged.is_file() {
+            continue;
+        }
+        let target = root.join(entry);
+        if *entry == STATISTICS_DB {
+            // 旧数据库的 WAL 文件与新数据库不匹配
+            for suffix in ["-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{}", target.display(), suffix));
+            }
+        }
+        replace_file(&staged, &target)?;
+        files += 1;
+    }
+
+    for dir in DIR_ENTRIES {
+        let Ok(read_dir) = std::fs::read_dir(pending.join(dir)) else {
+            continue;
+        };
+        for entry in read_dir.filter_map(|e| e.ok()) {
+            let target = root.join(dir).join(entry.file_name());
+            if *dir == PLUGINS_CONFIG_DIR {
+                restore_plugin_config(&entry.path(), &target)?;
+            } else {
+                replace_file(&entry.path(), &target)?;
+            }
+            files += 1;
+        }
+    }
+
+    let favorites_path = pending.join(FAVORITES_ENTRY);
+    if favorites_path.is_file() {
+        let favorites: Vec<FavoriteEntry> = serde_json::from_slice(&std::fs::read(&favorites_path)?)?;
+        let db = ClipboardDatabase::new(root.join("clipboard.db"))?;
+        for favorite in &favorites {
+            db.import_favorite(
+                &favorite.content_type,
+                &favorite.content,
+                favorite.preview.as_deref(),
+                favorite.timestamp,
+                favorite.category.as_deref(),
+                &favorite.tags,
+            )?;
+        }
+        files += 1;
+    }
+
+    Ok(files)
+}
+
+/// 导出时已移除凭据：把导入的插件配置合并到现有文件上，保留本机的 API Key 等凭据
+fn restore_plugin_config(from: &Path, to: &Path) -> Result<()> {
+    let existing = std::fs::read(to).ok().and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok());
+    let imported = serde_json::from_slice::<serde_json::Value>(&std::fs::read(from)?).ok();
+    let (Some(mut merged), Some(imported)) = (existing, imported) else {
+        return replace_file(from, to);
+    };
+    merge_json(&mut merged, imported);
+    std::fs::write(to, serde_json::to_vec_pretty(&merged)?).with_context(|| format!("Failed to restore {:?}", to))?;
+    Ok(())
+}
+
+/// 递归合并对象，overlay 中的值覆盖 base，overlay 缺少的键保留
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn replace_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(from, to).with_context(|| format!("Failed to restore {:?}", to))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restorable_entries() {
+        assert!(is_restorable("config/config.json"));
+        assert!(is_restorable("data/statistics.db"));
+        assert!(is_restorable("data/plugins_config/calculator.json"));
+        assert!(is_restorable("cache/workflows/abc.json"));
+        assert!(is_restorable(FAVORITES_ENTRY));
+
+        assert!(!is_restorable(MANIFEST_ENTRY));
+        assert!(!is_restorable("data/plugins_config/../../evil.json"));
+        assert!(!is_restorable("data/plugins_config/sub/x.json"));
+        assert!(!is_restorable("data/plugins_config/x.exe"));
+        assert!(!is_restorable("data_location.json"));
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_profile_test_{}", uuid::Uuid::new_v4()));
+        let source = dir.join("source");
+        std::fs::create_dir_all(source.join("config")).unwrap();
+        std::fs::create_dir_all(source.join("data/plugins_config")).unwrap();
+        std::fs::write(source.join("config/config.json"), br#"{"general":{}}"#).unwrap();
+        std::fs::write(source.join("data/plugins_config/calculator.json"), br#"{"precision":4}"#).unwrap();
+        std::fs::write(source.join("data/plugins_config/notes.txt"), b"ignored").unwrap();
+        rusqlite::Connection::open(source.join(STATISTICS_DB))
+            .unwrap()
+            .execute_batch("CREATE TABLE t (v INTEGER); INSERT INTO t VALUES (7);")
+            .unwrap();
+
+        let favorites = vec![FavoriteEntry {
+            content_type: "text".to_string(),
+            content: "ssh deploy@example.com".to_string(),
+            preview: None,
+            timestamp: 1_700_000_000,
+            category: Some("work".to_string()),
+            tags: vec!["ssh".to_string()],
+        }];
+        let archive = dir.join("profile.zip");
+        let exported = export_from(&source, &archive, &favorites).unwrap();
+        assert_eq!(exported.manifest.format_version, PROFILE_FORMAT_VERSION);
+        assert_eq!(
+            exported.manifest.entries,
+            vec!["config/config.json", STATISTICS_DB, "data/plugins_config/calculator.json", FAVORITES_ENTRY]
+        );
+
+        let target = dir.join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        let imported = stage_import(&target, &archive).unwrap();
+        assert!(imported.restart_required);
+        assert_eq!(imported.manifest.entries.len(), 4);
+        assert!(!target.join("config/config.json").exists());
+
+        assert_eq!(apply_staged(&target).unwrap(), 4);
+        assert_eq!(std::fs::read(target.join("config/config.json")).unwrap(), br#"{"general":{}}"#);
+        let value: i64 = rusqlite::Connection::open(target.join(STATISTICS_DB))
+            .unwrap()
+            .query_row("SELECT v FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, 7);
+        let db = ClipboardDatabase::new(target.join("clipboard.db")).unwrap();
+        let restored = db.get_all_favorites().unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].tags, vec!["ssh".to_string()]);
+
+        // 重复导入只标记收藏，不产生重复记录
+        assert!(!db.import_favorite("text", "ssh deploy@example.com", None, 0, None, &[]).unwrap());
+        drop(db);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_export_strips_secrets() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_profile_test_{}", uuid::Uuid::new_v4()));
+        let source = dir.join("source");
+        std::fs::create_dir_all(source.join("config")).unwrap();
+        std::fs::create_dir_all(source.join("data/plugins_config")).unwrap();
+        std::fs::write(
+            source.join("config/config.json"),
+            br#"{"general":{},"local_api":{"enabled":true,"port":27121,"token":"local-secret"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            source.join("data/plugins_config/ai_assistant.json"),
+            br#"{"provider":"openai","api_key":"sk-live","nested":{"Access_Token":"t"}}"#,
+        )
+        .unwrap();
+
+        let archive = dir.join("profile.zip");
+        export_from(&source, &archive, &[]).unwrap();
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+        let mut read_entry = |name: &str| {
+            let mut content = String::new();
+            zip.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+            serde_json::from_str::<serde_json::Value>(&content).unwrap()
+        };
+
+        let config = read_entry("config/config.json");
+        assert_eq!(config["local_api"]["token"], "");
+        assert_eq!(config["local_api"]["port"], 27121);
+        let plugin = read_entry("data/plugins_config/ai_assistant.json");
+        assert_eq!(plugin, serde_json::json!({"provider": "openai", "nested": {}}));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_import_keeps_local_plugin_secrets() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_profile_test_{}", uuid::Uuid::new_v4()));
+        let source = dir.join("source");
+        std::fs::create_dir_all(source.join("data/plugins_config")).unwrap();
+        std::fs::write(
+            source.join("data/plugins_config/ai_assistant.json"),
+            br#"{"provider":"claude","api_key":"sk-exported","nested":{"model":"opus"}}"#,
+        )
+        .unwrap();
+        let archive = dir.join("profile.zip");
+        export_from(&source, &archive, &[]).unwrap();
+
+        let target = dir.join("target");
+        std::fs::create_dir_all(target.join("data/plugins_config")).unwrap();
+        std::fs::write(
+            target.join("data/plugins_config/ai_assistant.json"),
+            br#"{"provider":"openai","api_key":"sk-local","nested":{"Access_Token":"t","model":"gpt"}}"#,
+        )
+        .unwrap();
+        stage_import(&target, &archive).unwrap();
+        apply_staged(&target).unwrap();
+
+        let restored: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(target.join("data/plugins_config/ai_assistant.json")).unwrap()).unwrap();
+        assert_eq!(
+            restored,
+            serde_json::json!({"provider": "claude", "api_key": "sk-local", "nested": {"Access_Token": "t", "model": "opus"}})
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_import_rejects_newer_format() {
+        let dir = std::env::temp_dir().join(format!("ilauncher_profile_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("future.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        zip.start_file(MANIFEST_ENTRY, zip::write::SimpleFileOptions::default()).unwrap();
+        zip.write_all(br#"{"format_version":99,"app_version":"9.0.0","created_at":0,"entries":[]}"#).unwrap();
+        zip.finish().unwrap();
+
+        assert!(stage_import(&dir, &archive).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,39 @@
+// 简单通配符匹配（`*` 匹配任意字符序列），用于剪贴板忽略列表和索引排除规则
+
+/// 判断 text 是否匹配 pattern（区分大小写，调用方自行统一大小写）
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("a*c", "abc"));
+        assert!(matches("*pass*", "onepassword"));
+        assert!(matches("*", ""));
+        assert!(matches("*.log", "app.log"));
+        assert!(!matches("a*c", "ab"));
+        assert!(!matches("ab*ba", "aba"));
+        assert!(!matches("abc", "abcd"));
+    }
+}
@@ -0,0 +1,264 @@
+// 语音输入 - 按住热键录音，松开后使用本地 whisper.cpp 转写，结果送入查询（或 AI 插件）
+// 隐私：默认关闭；录音只保存在本地临时目录，转写完成后删除（除非开启 keep_recordings）
+
+use crate::storage::VoiceConfig;
+use anyhow::{anyhow, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+/// 全局语音输入
+pub static VOICE_INPUT: Lazy<VoiceInput> = Lazy::new(VoiceInput::new);
+
+/// 录音状态事件（payload: bool）
+pub const VOICE_RECORDING_EVENT: &str = "voice:recording";
+/// 转写结果事件（payload: VoiceTranscript）
+pub const VOICE_TRANSCRIPT_EVENT: &str = "voice:transcript";
+
+/// whisper.cpp 需要 16kHz 单声道
+const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// 转写结果
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceTranscript {
+    pub text: String,
+    /// 送入目标：query（搜索框）/ ai（AI 插件）
+    pub target: String,
+    pub language: String,
+}
+
+/// 正在进行的录音
+struct Recording {
+    stop_tx: mpsc::Sender<()>,
+    done_rx: mpsc::Receiver<Result<PathBuf>>,
+}
+
+/// 语音输入管理器
+pub struct VoiceInput {
+    recording: Mutex<Option<Recording>>,
+}
+
+impl VoiceInput {
+    fn new() -> Self {
+        Self {
+            recording: Mutex::new(None),
+        }
+    }
+
+    /// 是否正在录音
+    pub fn is_recording(&self) -> bool {
+        self.recording.lock().is_some()
+    }
+
+    /// 开始录音（cpal 音频流不能跨线程，在独立线程中录制）
+    pub fn start_recording(&self, app_handle: &AppHandle) -> Result<()> {
+        let mut recording = self.recording.lock();
+        if recording.is_some() {
+            return Ok(());
+        }
+
+        let output = std::env::temp_dir().join(format!("ilauncher_voice_{}.wav", uuid::Uuid::new_v4().simple()));
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let (done_tx, done_rx) = mpsc::channel::<Result<PathBuf>>();
+
+        std::thread::spawn(move || {
+            let result = record_until_stopped(stop_rx, &output).map(|_| output);
+            let _ = done_tx.send(result);
+        });
+
+        *recording = Some(Recording { stop_tx, done_rx });
+        let _ = app_handle.emit(VOICE_RECORDING_EVENT, true);
+        tracing::info!("🎙️ Voice recording started");
+        Ok(())
+    }
+
+    /// 停止录音并转写（阻塞调用，需在独立线程或 spawn_blocking 中执行）
+    pub fn stop_and_transcribe(&self, app_handle: &AppHandle, config: &VoiceConfig) -> Result<Option<VoiceTranscript>> {
+        let Some(recording) = self.recording.lock().take() else {
+            return Ok(None);
+        };
+
+        let _ = recording.stop_tx.send(());
+        let _ = app_handle.emit(VOICE_RECORDING_EVENT, false);
+
+        let audio_path = recording
+            .done_rx
+            .recv()
+            .map_err(|_| anyhow!("Recording thread exited unexpectedly"))??;
+
+        let result = transcribe(&audio_path, config);
+
+        // 🔒 隐私：默认转写后立即删除录音
+        if !config.keep_recordings {
+            let _ = std::fs::remove_file(&audio_path);
+        }
+
+        let text = result?;
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        let transcript = VoiceTranscript {
+            text,
+            target: config.target.clone(),
+            language: config.language.clone(),
+        };
+        let _ = app_handle.emit(VOICE_TRANSCRIPT_EVENT, &transcript);
+        tracing::info!("🎙️ Voice transcript: {} chars", transcript.text.chars().count());
+        Ok(Some(transcript))
+    }
+}
+
+/// 录音直到收到停止信号，写出 16kHz 单声道 WAV
+fn record_until_stopped(stop_rx: mpsc::Receiver<()>, output: &Path) -> Result<()> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or_else(|| anyhow!("No input device available"))?;
+    let config = device.default_input_config()?;
+
+    let sample_rate = config.sample_rate().0;
+    let channels = config.channels() as usize;
+    let samples: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let err_fn = |e| tracing::error!("Voice input stream error: {}", e);
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            let samples = samples.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[f32], _| push_mono(&samples, data.iter().copied(), channels),
+                err_fn,
+                None,
+            )?
+        }
+        cpal::SampleFormat::I16 => {
+            let samples = samples.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[i16], _| {
+                    push_mono(&samples, data.iter().map(|s| *s as f32 / i16::MAX as f32), channels)
+                },
+                err_fn,
+                None,
+            )?
+        }
+        format => return Err(anyhow!("Unsupported sample format: {:?}", format)),
+    };
+
+    stream.play()?;
+    let _ = stop_rx.recv();
+    drop(stream);
+
+    let samples = resample(&samples.lock(), sample_rate, TARGET_SAMPLE_RATE);
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(output, spec)?;
+    for sample in samples {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+
+    Ok(())
+}
+
+/// 多声道混合为单声道
+fn push_mono(samples: &Mutex<Vec<f32>>, data: impl Iterator<Item = f32>, channels: usize) {
+    let data: Vec<f32> = data.collect();
+    let mut samples = samples.lock();
+    for frame in data.chunks(channels.max(1)) {
+        samples.push(frame.iter().sum::<f32>() / frame.len() as f32);
+    }
+}
+
+/// 线性插值重采样
+fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio) as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = samples[idx.min(samples.len() - 1)];
+            let b = samples[(idx + 1).min(samples.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// 模型路径：优先使用手动配置，否则使用模型管理器中已下载的 whisper 模型
+pub fn resolve_model_path(config: &VoiceConfig) -> Result<PathBuf> {
+    if !config.model_path.is_empty() {
+        return Ok(PathBuf::from(&config.model_path));
+    }
+    crate::models::MODELS.require("voice_input")
+}
+
+/// 调用 whisper.cpp 命令行转写
+fn transcribe(audio_path: &Path, config: &VoiceConfig) -> Result<String> {
+    if config.whisper_path.is_empty() {
+        return Err(anyhow!("whisper.cpp path is not configured"));
+    }
+    let model_path = resolve_model_path(config)?;
+
+    let mut command = std::process::Command::new(&config.whisper_path);
+    command
+        .arg("-m")
+        .arg(&model_path)
+        .arg("-f")
+        .arg(audio_path)
+        .args(["-l", &config.language, "-nt", "-np"]);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "whisper.cpp failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_halves_length() {
+        let samples: Vec<f32> = (0..32_000).map(|i| (i as f32 / 100.0).sin()).collect();
+        let out = resample(&samples, 32_000, TARGET_SAMPLE_RATE);
+        assert_eq!(out.len(), 16_000);
+        assert_eq!(resample(&samples, 16_000, 16_000).len(), samples.len());
+    }
+}
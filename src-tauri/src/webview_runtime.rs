@@ -0,0 +1,193 @@
+// WebView 运行时检测 - 启动前探测 WebView2（Windows）/ WebKitGTK（Linux）是否可用
+// 缺失或损坏时不再 panic：Windows 下提示下载安装 WebView2 引导程序，
+// 其余情况弹出原生错误窗口并附带诊断信息（同时写入日志）
+
+/// WebView2 Evergreen 引导程序下载地址
+#[cfg(target_os = "windows")]
+const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
+
+/// WebView 运行时状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeStatus {
+    Available { version: String },
+    /// 未安装或无法加载（附带底层错误）
+    Missing { error: String },
+}
+
+/// 探测当前平台的 WebView 运行时
+pub fn probe() -> RuntimeStatus {
+    match tauri::webview_version() {
+        Ok(version) if !version.trim().is_empty() => RuntimeStatus::Available { version },
+        Ok(_) => RuntimeStatus::Missing { error: "WebView runtime reported an empty version".to_string() },
+        Err(e) => RuntimeStatus::Missing { error: e.to_string() },
+    }
+}
+
+/// 启动前确认 WebView 运行时可用；返回 false 时调用方应直接退出
+pub fn ensure_available() -> bool {
+    match probe() {
+        RuntimeStatus::Available { version } => {
+            tracing::info!("🌐 WebView runtime: {}", version);
+            true
+        }
+        RuntimeStatus::Missing { error } => {
+            tracing::error!("❌ WebView runtime unavailable: {}", error);
+            offer_install(&error)
+        }
+    }
+}
+
+/// Tauri 启动失败（运行时损坏等）时显示诊断信息，替代 expect() panic
+pub fn report_startup_failure(error: &str) {
+    tracing::error!("❌ Failed to start iLauncher: {}", error);
+    show_error(&diagnostics(error));
+}
+
+/// 诊断信息：版本、平台、运行时探测结果、日志位置
+pub fn diagnostics(error: &str) -> String {
+    let runtime = match probe() {
+        RuntimeStatus::Available { version } => version,
+        RuntimeStatus::Missing { error } => format!("unavailable ({})", error),
+    };
+    let log_file = crate::utils::paths::get_log_dir()
+        .map(|dir| dir.join("ilauncher.log").display().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    format!(
+        "iLauncher could not start.\n\n\
+         Error: {}\n\n\
+         Version: {}\n\
+         Platform: {} ({})\n\
+         WebView runtime: {}\n\
+         Log file: {}",
+        error,
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        runtime,
+        log_file,
+    )
+}
+
+/// Windows：询问是否下载并安装 WebView2，安装成功后重新探测
+#[cfg(target_os = "windows")]
+fn offer_install(error: &str) -> bool {
+    let message = format!(
+        "{}\n\nMicrosoft Edge WebView2 Runtime is required. Download and install it now?",
+        diagnostics(error)
+    );
+    if !confirm(&message) {
+        return false;
+    }
+
+    match install_webview2() {
+        Ok(()) => match probe() {
+            RuntimeStatus::Available { version } => {
+                tracing::info!("✅ WebView2 installed: {}", version);
+                true
+            }
+            RuntimeStatus::Missing { error } => {
+                show_error(&format!("WebView2 was installed but still cannot be loaded.\n\n{}", diagnostics(&error)));
+                false
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to install WebView2: {}", e);
+            // 下载失败时打开下载页面让用户手动安装
+            let _ = std::process::Command::new("cmd")
+                .args(["/C", "start", "", WEBVIEW2_BOOTSTRAPPER_URL])
+                .spawn();
+            show_error(&format!(
+                "Failed to install WebView2 automatically: {}\n\nPlease install it manually from:\n{}",
+                e, WEBVIEW2_BOOTSTRAPPER_URL
+            ));
+            false
+        }
+    }
+}
+
+/// 其他平台无法自动安装（WebKitGTK 由系统包管理器提供）
+#[cfg(not(target_os = "windows"))]
+fn offer_install(error: &str) -> bool {
+    show_error(&format!(
+        "{}\n\nPlease install the WebKitGTK runtime (e.g. libwebkit2gtk-4.1) with your package manager.",
+        diagnostics(error)
+    ));
+    false
+}
+
+/// 下载 WebView2 引导程序并静默安装（阻塞调用）
+#[cfg(target_os = "windows")]
+fn install_webview2() -> anyhow::Result<()> {
+    let installer = std::env::temp_dir().join("MicrosoftEdgeWebview2Setup.exe");
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    runtime.block_on(async {
+        let bytes = reqwest::get(WEBVIEW2_BOOTSTRAPPER_URL)
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        tokio::fs::write(&installer, &bytes).await?;
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    tracing::info!("📥 Running WebView2 bootstrapper: {:?}", installer);
+    let status = std::process::Command::new(&installer)
+        .args(["/silent", "/install"])
+        .status()?;
+    let _ = std::fs::remove_file(&installer);
+    if !status.success() {
+        return Err(anyhow::anyhow!("WebView2 installer exited with {}", status));
+    }
+    Ok(())
+}
+
+/// 原生确认对话框（是 / 否）
+#[cfg(target_os = "windows")]
+fn confirm(message: &str) -> bool {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONWARNING, MB_YESNO};
+
+    let result = unsafe {
+        MessageBoxW(HWND::default(), &HSTRING::from(message), &HSTRING::from("iLauncher"), MB_YESNO | MB_ICONWARNING)
+    };
+    result == IDYES
+}
+
+/// 原生错误窗口（不依赖 WebView）
+#[cfg(target_os = "windows")]
+fn show_error(message: &str) {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONERROR, MB_OK};
+
+    unsafe {
+        MessageBoxW(HWND::default(), &HSTRING::from(message), &HSTRING::from("iLauncher"), MB_OK | MB_ICONERROR);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn show_error(message: &str) {
+    eprintln!("{}", message);
+    let script = format!(
+        "display alert \"iLauncher\" message \"{}\" as critical",
+        message.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let _ = std::process::Command::new("osascript").args(["-e", &script]).status();
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn show_error(message: &str) {
+    eprintln!("{}", message);
+    // WebKitGTK 不可用时 GTK 本身通常仍可用，尝试 zenity / kdialog 弹窗
+    let shown = std::process::Command::new("zenity")
+        .args(["--error", "--title=iLauncher", "--no-markup", &format!("--text={}", message)])
+        .status()
+        .is_ok();
+    if !shown {
+        let _ = std::process::Command::new("kdialog")
+            .args(["--title", "iLauncher", "--error", message])
+            .status();
+    }
+}
@@ -0,0 +1,99 @@
+// 窗口尺寸 - 按目标显示器 DPI 缩放配置中的窗口大小
+// AppearanceConfig.window_width/height 视为 100% 缩放下的逻辑像素，
+// 显示时乘以显示器缩放比例；monitor_sizes 可按显示器名称单独覆盖
+
+use crate::storage::{AppearanceConfig, WindowSize};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use tauri::{Monitor, PhysicalSize};
+
+/// 全局窗口尺寸配置
+pub static WINDOW_GEOMETRY: Lazy<WindowGeometry> = Lazy::new(WindowGeometry::new);
+
+/// 默认窗口尺寸
+pub const DEFAULT_WINDOW_SIZE: WindowSize = WindowSize { width: 800, height: 600 };
+
+/// 窗口最多占显示器的比例（避免低分辨率屏幕上超出边界）
+const MAX_MONITOR_FRACTION: f64 = 0.95;
+
+/// 窗口尺寸配置
+pub struct WindowGeometry {
+    default_size: RwLock<WindowSize>,
+    monitor_sizes: RwLock<HashMap<String, WindowSize>>,
+}
+
+impl WindowGeometry {
+    fn new() -> Self {
+        Self {
+            default_size: RwLock::new(DEFAULT_WINDOW_SIZE),
+            monitor_sizes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 应用外观配置（启动时及保存设置后调用）
+    pub fn configure(&self, appearance: &AppearanceConfig) {
+        *self.default_size.write() = WindowSize {
+            width: appearance.window_width,
+            height: appearance.window_height,
+        };
+        *self.monitor_sizes.write() = appearance.monitor_sizes.clone();
+    }
+
+    /// 显示器上的逻辑尺寸（优先使用该显示器的覆盖配置）
+    pub fn logical_size(&self, monitor_name: Option<&str>) -> WindowSize {
+        monitor_name
+            .and_then(|name| self.monitor_sizes.read().get(name).copied())
+            .unwrap_or_else(|| *self.default_size.read())
+    }
+
+    /// 目标显示器上的物理尺寸；无法获取显示器时返回 None（保持窗口当前尺寸）
+    pub fn size_for_monitor(&self, monitor: Option<&Monitor>) -> Option<PhysicalSize<u32>> {
+        let monitor = monitor?;
+        let logical = self.logical_size(monitor.name().map(String::as_str));
+        let bounds = monitor.size();
+        let (width, height) = scaled_size(logical, monitor.scale_factor(), (bounds.width, bounds.height));
+        Some(PhysicalSize::new(width, height))
+    }
+}
+
+/// 逻辑尺寸 × 缩放比例，并限制在显示器范围内
+fn scaled_size(logical: WindowSize, scale_factor: f64, monitor: (u32, u32)) -> (u32, u32) {
+    let scale_factor = if scale_factor.is_finite() && scale_factor > 0.0 { scale_factor } else { 1.0 };
+    let fit = |value: u32, bound: u32| -> u32 {
+        let scaled = (value as f64 * scale_factor).round();
+        let max = (bound as f64 * MAX_MONITOR_FRACTION).floor();
+        let clamped = if max >= 1.0 { scaled.min(max) } else { scaled };
+        clamped.max(1.0) as u32
+    };
+    (fit(logical.width, monitor.0), fit(logical.height, monitor.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_size() {
+        let size = WindowSize { width: 800, height: 600 };
+        assert_eq!(scaled_size(size, 1.0, (1920, 1080)), (800, 600));
+        assert_eq!(scaled_size(size, 1.5, (2560, 1440)), (1200, 900));
+        // 超出显示器时按比例上限裁剪
+        assert_eq!(scaled_size(size, 2.0, (1280, 720)), (1216, 684));
+        // 无效缩放比例按 1.0 处理
+        assert_eq!(scaled_size(size, 0.0, (1920, 1080)), (800, 600));
+    }
+
+    #[test]
+    fn test_monitor_override() {
+        let geometry = WindowGeometry::new();
+        let mut appearance = crate::storage::AppConfig::default().appearance;
+        appearance.window_width = 700;
+        appearance.monitor_sizes.insert("DELL U2720Q".to_string(), WindowSize { width: 1000, height: 640 });
+        geometry.configure(&appearance);
+
+        assert_eq!(geometry.logical_size(Some("DELL U2720Q")).width, 1000);
+        assert_eq!(geometry.logical_size(Some("Other")).width, 700);
+        assert_eq!(geometry.logical_size(None).height, 600);
+    }
+}
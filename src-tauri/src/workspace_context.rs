@@ -0,0 +1,233 @@
+// 工作区上下文 - 在 工作/个人/游戏 等场景之间切换启用的插件、快捷链接和搜索根目录
+// 只影响查询时的过滤，不修改底层数据
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 全局工作区上下文管理器
+pub static WORKSPACE_CONTEXTS: Lazy<WorkspaceContextManager> = Lazy::new(WorkspaceContextManager::new);
+
+/// 快捷链接（URL 或本地路径）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quicklink {
+    pub name: String,
+    pub target: String,
+}
+
+/// 单个上下文
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceContext {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_icon")]
+    pub icon: String,
+    /// 该上下文中额外禁用的插件
+    #[serde(default)]
+    pub disabled_plugins: Vec<String>,
+    #[serde(default)]
+    pub quicklinks: Vec<Quicklink>,
+    /// 文件搜索根目录（为空表示不限制）
+    #[serde(default)]
+    pub search_roots: Vec<String>,
+}
+
+fn default_icon() -> String {
+    "🗂️".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ContextStore {
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(default)]
+    contexts: Vec<WorkspaceContext>,
+}
+
+impl ContextStore {
+    /// 首次运行时的默认上下文（均为空配置，由用户自行调整）
+    fn with_defaults() -> Self {
+        let preset = |id: &str, name: &str, icon: &str| WorkspaceContext {
+            id: id.to_string(),
+            name: name.to_string(),
+            icon: icon.to_string(),
+            disabled_plugins: Vec::new(),
+            quicklinks: Vec::new(),
+            search_roots: Vec::new(),
+        };
+
+        Self {
+            active: None,
+            contexts: vec![
+                preset("work", "Work", "💼"),
+                preset("personal", "Personal", "🏠"),
+                preset("gaming", "Gaming", "🎮"),
+            ],
+        }
+    }
+}
+
+/// 工作区上下文管理器
+pub struct WorkspaceContextManager {
+    store: RwLock<ContextStore>,
+    storage_path: Option<PathBuf>,
+}
+
+impl WorkspaceContextManager {
+    fn new() -> Self {
+        let storage_path = crate::utils::paths::get_data_dir()
+            .ok()
+            .map(|dir| dir.join("workspace_contexts.json"));
+
+        let store = storage_path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match Self::load(path) {
+                Ok(store) => Some(store),
+                Err(e) => {
+                    tracing::warn!("Failed to load workspace contexts: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_else(ContextStore::with_defaults);
+
+        Self {
+            store: RwLock::new(store),
+            storage_path,
+        }
+    }
+
+    /// 获取所有上下文
+    pub fn list(&self) -> Vec<WorkspaceContext> {
+        self.store.read().contexts.clone()
+    }
+
+    /// 获取当前激活的上下文（None 表示默认，不做任何过滤）
+    pub fn active(&self) -> Option<WorkspaceContext> {
+        let store = self.store.read();
+        let active_id = store.active.as_ref()?;
+        store.contexts.iter().find(|c| &c.id == active_id).cloned()
+    }
+
+    /// 当前上下文 ID（用于隔离排序统计）
+    pub fn active_id(&self) -> Option<String> {
+        self.active().map(|c| c.id)
+    }
+
+    /// 切换上下文（None 切回默认）
+    pub fn switch(&self, id: Option<&str>) -> Result<()> {
+        {
+            let mut store = self.store.write();
+            if let Some(id) = id {
+                if !store.contexts.iter().any(|c| c.id == id) {
+                    return Err(anyhow!("Context '{}' not found", id));
+                }
+            }
+            store.active = id.map(|s| s.to_string());
+        }
+
+        tracing::info!("🗂️ Switched workspace context to {}", id.unwrap_or("default"));
+        self.save()
+    }
+
+    /// 添加或更新上下文
+    pub fn upsert(&self, context: WorkspaceContext) -> Result<()> {
+        if context.id.trim().is_empty() {
+            return Err(anyhow!("Context id cannot be empty"));
+        }
+
+        {
+            let mut store = self.store.write();
+            match store.contexts.iter_mut().find(|c| c.id == context.id) {
+                Some(existing) => *existing = context,
+                None => store.contexts.push(context),
+            }
+        }
+        self.save()
+    }
+
+    /// 删除上下文（若为当前上下文则切回默认）
+    pub fn remove(&self, id: &str) -> Result<()> {
+        {
+            let mut store = self.store.write();
+            store.contexts.retain(|c| c.id != id);
+            if store.active.as_deref() == Some(id) {
+                store.active = None;
+            }
+        }
+        self.save()
+    }
+
+    /// 插件在当前上下文中是否启用
+    pub fn is_plugin_enabled(&self, plugin_id: &str) -> bool {
+        match self.active() {
+            Some(context) => !context.disabled_plugins.iter().any(|p| p == plugin_id),
+            None => true,
+        }
+    }
+
+    /// 路径是否位于当前上下文的搜索根目录下
+    pub fn is_path_in_roots(&self, path: &str) -> bool {
+        let Some(context) = self.active() else {
+            return true;
+        };
+        if context.search_roots.is_empty() {
+            return true;
+        }
+
+        let path = path.to_lowercase().replace('\\', "/");
+        context.search_roots.iter().any(|root| {
+            let root = root.to_lowercase().replace('\\', "/");
+            is_under_root(&path, root.trim_end_matches('/'))
+        })
+    }
+
+    fn load(path: &PathBuf) -> Result<ContextStore> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.storage_path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&*self.store.read())?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// 路径等于根目录或位于其下（按路径分隔符边界匹配，C:/work 不匹配 C:/workshop）
+fn is_under_root(path: &str, root: &str) -> bool {
+    path.strip_prefix(root)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_in_roots_respects_separator_boundary() {
+        let manager = WorkspaceContextManager {
+            store: RwLock::new(ContextStore::with_defaults()),
+            storage_path: None,
+        };
+        manager.store.write().contexts[0].search_roots = vec![r"C:\Work\".to_string()];
+        // 未激活上下文时不限制
+        assert!(manager.is_path_in_roots(r"C:\workshop\a.txt"));
+
+        manager.switch(Some("work")).unwrap();
+        assert!(manager.is_path_in_roots(r"C:\work"));
+        assert!(manager.is_path_in_roots(r"c:\work\notes\a.txt"));
+        assert!(manager.is_path_in_roots("C:/Work/a.txt"));
+        assert!(!manager.is_path_in_roots(r"C:\workshop\a.txt"));
+        assert!(!manager.is_path_in_roots("C:/work2"));
+    }
+}
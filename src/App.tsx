@@ -30,9 +30,11 @@ function App() {
   const [currentView, setCurrentView] = useState<View>('search');
   const [previewPath, setPreviewPath] = useState<string | null>(null);
   const [showHotkeyGuide, setShowHotkeyGuide] = useState(false);
+  const [aiPrompt, setAiPrompt] = useState<string | undefined>(undefined);
   const [showWelcomeGuide, setShowWelcomeGuide] = useState(false);
   const results = useAppStore((state) => state.results);
   const selectedIndex = useAppStore((state) => state.selectedIndex);
+  const pinnedPreview = useAppStore((state) => state.pinnedPreview);
   const { config, loadConfig, saveConfig } = useConfigStore();
   const { message, type, visible, hideToast } = useToast();
   const showPreview = config?.appearance.show_preview ?? true;
@@ -46,6 +48,11 @@ function App() {
 
   // 当选中项变化时更新预览
   useEffect(() => {
+    // 插件执行后要求打开的文件预览优先
+    if (showPreview && pinnedPreview && pinnedPreview.type.toLowerCase() === 'file') {
+      setPreviewPath(pinnedPreview.data);
+      return;
+    }
     if (showPreview && results.length > 0 && selectedIndex >= 0 && selectedIndex < results.length) {
       const result = results[selectedIndex];
       // 只预览文件插件的结果
@@ -57,7 +64,7 @@ function App() {
     } else {
       setPreviewPath(null);
     }
-  }, [selectedIndex, results, showPreview]);
+  }, [selectedIndex, results, showPreview, pinnedPreview]);
 
   // 加载配置（仅在应用启动时加载一次）
   useEffect(() => {
@@ -203,6 +210,27 @@ function App() {
       return unlisten;
     };
     
+    // 监听打开视图事件（从命令面板触发）
+    const setupOpenViewListener = async () => {
+      const unlisten = await appWindow.listen<string>('open-view', (event) => {
+        if (event.payload === 'hotkey-guide') {
+          setShowHotkeyGuide(true);
+          return;
+        }
+        setCurrentView(event.payload as View);
+      });
+      return unlisten;
+    };
+    
+    // 监听询问 AI 事件（无结果备选），打开对话界面并预填问题
+    const setupAiAskListener = async () => {
+      const unlisten = await appWindow.listen<string>('ai-ask', (event) => {
+        setAiPrompt(event.payload);
+        setCurrentView('ai-chat');
+      });
+      return unlisten;
+    };
+    
     // 监听窗口失焦事件，自动隐藏并切换回搜索视图（但设置界面除外）
     const setupBlurListener = async () => {
       const unlisten = await appWindow.onFocusChanged(({ payload: focused }) => {
@@ -222,11 +250,15 @@ function App() {
     
     const showListenerPromise = setupShowListener();
     const openSettingsListenerPromise = setupOpenSettingsListener();
+    const openViewListenerPromise = setupOpenViewListener();
+    const aiAskListenerPromise = setupAiAskListener();
     const blurListenerPromise = setupBlurListener();
     
     return () => {
       showListenerPromise.then(fn => fn());
       openSettingsListenerPromise.then(fn => fn());
+      openViewListenerPromise.then(fn => fn());
+      aiAskListenerPromise.then(fn => fn());
       blurListenerPromise.then(fn => fn());
     };
   }, []);
@@ -287,7 +319,7 @@ function App() {
           {currentView === 'settings' && <Settings onClose={() => { setCurrentView('search'); }} />}
           {currentView === 'plugins' && <PluginManager onClose={() => { invoke("hide_app"); setCurrentView('search'); }} />}
           {currentView === 'clipboard' && <ClipboardHistory onClose={() => { invoke("hide_app"); setCurrentView('search'); }} />}
-          {currentView === 'ai-chat' && <AIChat onClose={() => { invoke("hide_app"); setCurrentView('search'); }} />}
+          {currentView === 'ai-chat' && <AIChat initialPrompt={aiPrompt} onClose={() => { invoke("hide_app"); setAiPrompt(undefined); setCurrentView('search'); }} />}
         </div>
       )}
     </div>
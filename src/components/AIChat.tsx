@@ -26,9 +26,11 @@ interface AIConfig {
 
 interface AIChatProps {
   onClose?: () => void;
+  // 预填的问题（搜索无结果时的“询问 AI”）
+  initialPrompt?: string;
 }
 
-const AIChat: React.FC<AIChatProps> = ({ onClose }) => {
+const AIChat: React.FC<AIChatProps> = ({ onClose, initialPrompt }) => {
   const { t: _t } = useTranslation();
   const [conversations, setConversations] = useState<Conversation[]>([]);
   const [currentConvId, setCurrentConvId] = useState<string | null>(null);
@@ -43,6 +45,12 @@ const AIChat: React.FC<AIChatProps> = ({ onClose }) => {
     loadConversations();
   }, []);
 
+  useEffect(() => {
+    if (initialPrompt) {
+      setInput(initialPrompt);
+    }
+  }, [initialPrompt]);
+
   // ESC 键关闭窗口
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
@@ -1,6 +1,6 @@
 import React, { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Clipboard, Search, Trash2, Copy, Image, File, Type, Star } from 'lucide-react';
+import { Clipboard, Search, Trash2, Copy, Image, File, Type, Star, Pin, ClipboardPaste, RemoveFormatting, CaseUpper, CaseLower, Braces } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 
@@ -14,8 +14,19 @@ interface ClipboardItem {
   file_path?: string;  // 图片文件路径
   category?: string;
   tags?: string[];
+  pinned_slot?: number;  // 快速粘贴槽位（Ctrl+Shift+1..9）
 }
 
+const PINNED_SLOT_COUNT = 9;
+
+// 变换粘贴（对应后端 paste_clipboard_content 的 transform）
+const PASTE_TRANSFORMS = [
+  { id: 'paste_plain', icon: RemoveFormatting, label: 'clipboard.pastePlain' },
+  { id: 'paste_upper', icon: CaseUpper, label: 'clipboard.pasteUpper' },
+  { id: 'paste_lower', icon: CaseLower, label: 'clipboard.pasteLower' },
+  { id: 'paste_json', icon: Braces, label: 'clipboard.pasteJson' },
+] as const;
+
 interface ClipboardHistoryProps {
   onClose?: () => void;
 }
@@ -49,6 +60,13 @@ const ClipboardHistory: React.FC<ClipboardHistoryProps> = ({ onClose }) => {
       if (e.key === 'Escape' && onClose) {
         onClose();
       }
+      const pinnedSlot = e.ctrlKey && e.shiftKey ? /^Digit([1-9])$/.exec(e.code) : null;
+      if (pinnedSlot) {
+        e.preventDefault();
+        invoke('paste_pinned_slot', { slot: Number(pinnedSlot[1]) })
+          .then(() => onClose?.())
+          .catch(error => console.error('Failed to paste pinned slot:', error));
+      }
     };
     
     window.addEventListener('keydown', handleEsc);
@@ -98,6 +116,40 @@ const ClipboardHistory: React.FC<ClipboardHistoryProps> = ({ onClose }) => {
     }
   };
 
+  // 粘贴到打开启动器前的窗口（后端隐藏窗口、恢复焦点并模拟 Ctrl+V）
+  const pasteItem = async (item: ClipboardItem, transform?: string) => {
+    try {
+      await invoke('paste_clipboard_content', {
+        content: item.content,
+        contentType: item.type,
+        transform
+      });
+      if (onClose) onClose();
+    } catch (error) {
+      console.error('Failed to paste clipboard item:', error);
+    }
+  };
+
+  // 固定到第一个空闲槽位；已固定时取消固定
+  const togglePin = async (item: ClipboardItem) => {
+    try {
+      let slot: number | null = null;
+      if (!item.pinned_slot) {
+        const pinned = await invoke<ClipboardItem[]>('get_pinned_clipboard_items');
+        const used = new Set(pinned.map(p => p.pinned_slot));
+        slot = Array.from({ length: PINNED_SLOT_COUNT }, (_, i) => i + 1).find(n => !used.has(n)) ?? null;
+        if (slot === null) {
+          window.alert(t('clipboard.slotsFull'));
+          return;
+        }
+      }
+      await invoke('pin_clipboard_item', { id: item.id, slot });
+      await loadHistory();
+    } catch (error) {
+      console.error('Failed to pin item:', error);
+    }
+  };
+
   const deleteItem = async (id: string) => {
     try {
       await invoke('delete_clipboard_item', { id });
@@ -349,6 +401,27 @@ const ClipboardHistory: React.FC<ClipboardHistoryProps> = ({ onClose }) => {
 
                     {/* 操作按钮 */}
                     <div className="flex-shrink-0 flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity">
+                      <button
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          togglePin(item);
+                        }}
+                        className="p-1.5 rounded transition-colors flex items-center gap-0.5"
+                        style={{ 
+                          color: item.pinned_slot ? 'var(--color-primary, #3182ce)' : 'var(--color-text-secondary)',
+                          backgroundColor: 'transparent'
+                        }}
+                        onMouseEnter={(e) => {
+                          e.currentTarget.style.backgroundColor = 'var(--color-hover)';
+                        }}
+                        onMouseLeave={(e) => {
+                          e.currentTarget.style.backgroundColor = 'transparent';
+                        }}
+                        title={item.pinned_slot ? t('clipboard.unpin', { slot: item.pinned_slot }) : t('clipboard.pin')}
+                      >
+                        <Pin className={`w-4 h-4 ${item.pinned_slot ? 'fill-current' : ''}`} />
+                        {item.pinned_slot && <span className="text-xs">{item.pinned_slot}</span>}
+                      </button>
                       <button
                         onClick={(e) => {
                           e.stopPropagation();
@@ -369,6 +442,49 @@ const ClipboardHistory: React.FC<ClipboardHistoryProps> = ({ onClose }) => {
                       >
                         <Star className={`w-4 h-4 ${item.favorite ? 'fill-current' : ''}`} />
                       </button>
+                      {item.type !== 'image' && PASTE_TRANSFORMS.map(({ id, icon: Icon, label }) => (
+                        <button
+                          key={id}
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            pasteItem(item, id);
+                          }}
+                          className="p-1.5 rounded transition-colors"
+                          style={{ 
+                            color: 'var(--color-text-secondary)',
+                            backgroundColor: 'transparent'
+                          }}
+                          onMouseEnter={(e) => {
+                            e.currentTarget.style.backgroundColor = 'var(--color-hover)';
+                          }}
+                          onMouseLeave={(e) => {
+                            e.currentTarget.style.backgroundColor = 'transparent';
+                          }}
+                          title={t(label)}
+                        >
+                          <Icon className="w-4 h-4" />
+                        </button>
+                      ))}
+                      <button
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          pasteItem(item);
+                        }}
+                        className="p-1.5 rounded transition-colors"
+                        style={{ 
+                          color: 'var(--color-text-secondary)',
+                          backgroundColor: 'transparent'
+                        }}
+                        onMouseEnter={(e) => {
+                          e.currentTarget.style.backgroundColor = 'var(--color-hover)';
+                        }}
+                        onMouseLeave={(e) => {
+                          e.currentTarget.style.backgroundColor = 'transparent';
+                        }}
+                        title={t('clipboard.paste')}
+                      >
+                        <ClipboardPaste className="w-4 h-4" />
+                      </button>
                       <button
                         onClick={(e) => {
                           e.stopPropagation();
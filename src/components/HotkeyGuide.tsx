@@ -16,6 +16,7 @@ export const HotkeyGuide: React.FC<HotkeyGuideProps> = ({ onClose }) => {
     // 基础导航
     { keys: ['↑', '↓'], description: '上下选择结果', category: '导航' },
     { keys: ['Enter'], description: '执行默认操作', category: '导航' },
+    { keys: ['Alt', '1-9'], description: '执行第 N 个结果', category: '导航' },
     { keys: ['Tab'], description: '切换操作面板', category: '导航' },
     { keys: ['Esc'], description: '隐藏窗口', category: '导航' },
     
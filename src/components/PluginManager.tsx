@@ -1,6 +1,6 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { Settings, Power, PowerOff, RefreshCw, Download, X, Save, AlertCircle, Shield } from 'lucide-react';
+import { Settings, Power, PowerOff, RefreshCw, Download, X, Save, AlertCircle, Shield, Sparkles } from 'lucide-react';
 import { useConfigStore } from '../store/useConfigStore';
 import { SandboxSettings } from './SandboxSettings';
 
@@ -11,6 +11,10 @@ interface PluginMetadata {
   author: string;
   version: string;
   icon: { Emoji: string } | { Url: string } | { File: string };
+  category?: PluginCategory;
+  tags?: string[];
+  homepage?: string;
+  icon_asset?: { url: string; mime: string };
   trigger_keywords: string[];
   supported_os: string[];
   plugin_type: string;
@@ -22,6 +26,28 @@ interface PluginMetadata {
   }>;
 }
 
+type PluginCategory = 'search' | 'productivity' | 'developer' | 'system' | 'utilities' | 'other';
+
+// 插件列表分组顺序
+const CATEGORY_ORDER: Array<[PluginCategory, string]> = [
+  ['search', 'Search'],
+  ['productivity', 'Productivity'],
+  ['developer', 'Developer'],
+  ['utilities', 'Utilities'],
+  ['system', 'System'],
+  ['other', 'Other'],
+];
+
+// 根据无结果查询推荐的插件
+interface PluginSuggestion {
+  plugin_id: string;
+  name: string;
+  description: string;
+  kind: 'enable' | 'install';
+  queries: string[];
+  misses: number;
+}
+
 interface PluginManagerProps {
   onClose: () => void;
 }
@@ -36,10 +62,13 @@ export const PluginManager: React.FC<PluginManagerProps> = ({ onClose }) => {
   const [loading, setLoading] = useState(true);
   const [refreshing, setRefreshing] = useState(false);
   const [configPlugin, setConfigPlugin] = useState<PluginMetadata | null>(null);
+  const [suggestions, setSuggestions] = useState<PluginSuggestion[]>([]);
+  const [installing, setInstalling] = useState<string | null>(null);
   const { config, saveConfig } = useConfigStore();
 
   useEffect(() => {
     loadPlugins();
+    loadSuggestions();
   }, []);
   
   // 监听 config 变化，同步 pluginStatuses
@@ -92,6 +121,42 @@ export const PluginManager: React.FC<PluginManagerProps> = ({ onClose }) => {
     }
   };
 
+  const loadSuggestions = async () => {
+    try {
+      setSuggestions(await invoke<PluginSuggestion[]>('get_plugin_suggestions', { limit: 3 }));
+    } catch (error) {
+      console.error('Failed to load plugin suggestions:', error);
+    }
+  };
+
+  const dismissSuggestion = async (pluginId: string) => {
+    setSuggestions((current) => current.filter((s) => s.plugin_id !== pluginId));
+    try {
+      await invoke('dismiss_plugin_suggestion', { pluginId });
+    } catch (error) {
+      console.error('Failed to dismiss plugin suggestion:', error);
+    }
+  };
+
+  const acceptSuggestion = async (suggestion: PluginSuggestion) => {
+    if (suggestion.kind === 'enable') {
+      await togglePlugin(suggestion.plugin_id);
+      setSuggestions((current) => current.filter((s) => s.plugin_id !== suggestion.plugin_id));
+      return;
+    }
+
+    setInstalling(suggestion.plugin_id);
+    try {
+      await invoke('install_plugin', { pluginId: suggestion.plugin_id });
+      setSuggestions((current) => current.filter((s) => s.plugin_id !== suggestion.plugin_id));
+      await loadPlugins();
+    } catch (error) {
+      alert('Failed to install plugin: ' + error);
+    } finally {
+      setInstalling(null);
+    }
+  };
+
   const togglePlugin = async (pluginId: string) => {
     if (!config) return;
     
@@ -207,100 +272,176 @@ export const PluginManager: React.FC<PluginManagerProps> = ({ onClose }) => {
             </div>
           </div>
 
+          {/* 插件推荐（根据没有结果的搜索） */}
+          {suggestions.length > 0 && (
+            <div className="bg-[#252526] border border-blue-600/30 rounded-lg p-4 space-y-3">
+              <h2 className="text-sm font-semibold text-gray-100 flex items-center gap-2">
+                <Sparkles className="w-4 h-4 text-blue-400" />
+                You might like
+              </h2>
+              {suggestions.map((suggestion) => (
+                <div key={suggestion.plugin_id} className="flex items-start gap-4">
+                  <div className="flex-1 min-w-0">
+                    <div className="text-sm text-gray-100">{suggestion.name}</div>
+                    {suggestion.description && (
+                      <div className="text-xs text-gray-400 line-clamp-1">{suggestion.description}</div>
+                    )}
+                    <div className="text-xs text-gray-500 mt-1 truncate">
+                      {suggestion.misses} searches without results, e.g. {suggestion.queries.map((q) => `"${q}"`).join(', ')}
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => acceptSuggestion(suggestion)}
+                    disabled={installing === suggestion.plugin_id}
+                    className="px-3 py-1.5 rounded text-xs font-medium bg-blue-600/20 text-blue-400 hover:bg-blue-600/30 border border-blue-600/30 flex items-center gap-1.5 disabled:opacity-50"
+                  >
+                    {suggestion.kind === 'enable' ? (
+                      <>
+                        <Power className="w-3 h-3" />
+                        Enable
+                      </>
+                    ) : (
+                      <>
+                        <Download className="w-3 h-3" />
+                        {installing === suggestion.plugin_id ? 'Installing...' : 'Install'}
+                      </>
+                    )}
+                  </button>
+                  <button
+                    onClick={() => dismissSuggestion(suggestion.plugin_id)}
+                    className="p-1.5 hover:bg-[#3e3e42] rounded transition-colors"
+                    title="Don't suggest again"
+                  >
+                    <X className="w-3 h-3 text-gray-400" />
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+
           {/* 插件列表 */}
-          <div className="space-y-3">
-            {plugins.map((plugin) => {
-              const isEnabled = pluginStatuses[plugin.id] ?? true;
+          <div className="space-y-6">
+            {CATEGORY_ORDER.map(([category, label]) => {
+              const group = plugins.filter((plugin) => (plugin.category ?? 'other') === category);
+              if (group.length === 0) return null;
               
               return (
-                <div
-                  key={plugin.id}
-                  className={`bg-[#252526] rounded-lg p-4 border transition-all ${
-                    isEnabled
-                      ? 'border-[#3e3e42] hover:border-[#555]'
-                      : 'border-[#3e3e42] opacity-60'
-                  }`}
-                >
-                  <div className="flex items-start gap-4">
-                    <div className={`text-3xl ${!isEnabled && 'grayscale opacity-50'}`}>
-                      {getIconEmoji(plugin.icon)}
-                    </div>
+                <div key={category} className="space-y-3">
+                  <h2 className="text-xs font-semibold uppercase tracking-wide text-gray-500">
+                    {label} ({group.length})
+                  </h2>
+                  {group.map((plugin) => {
+                    const isEnabled = pluginStatuses[plugin.id] ?? true;
+              
+                    return (
+                      <div
+                        key={plugin.id}
+                        className={`bg-[#252526] rounded-lg p-4 border transition-all ${
+                          isEnabled
+                            ? 'border-[#3e3e42] hover:border-[#555]'
+                            : 'border-[#3e3e42] opacity-60'
+                        }`}
+                      >
+                        <div className="flex items-start gap-4">
+                          <div className={`text-3xl ${!isEnabled && 'grayscale opacity-50'}`}>
+                            {plugin.icon_asset ? (
+                              <img src={plugin.icon_asset.url} alt="" className="w-8 h-8 object-contain" />
+                            ) : (
+                              getIconEmoji(plugin.icon)
+                            )}
+                          </div>
                     
-                    <div className="flex-1 min-w-0">
-                      <div className="flex items-center gap-2 mb-2 flex-wrap">
-                        <h3 className="text-base font-semibold text-gray-100">{plugin.name}</h3>
-                        <span className="text-xs bg-[#3e3e42] px-2 py-0.5 rounded text-gray-400">
-                          v{plugin.version}
-                        </span>
-                        <span className="text-xs bg-blue-600/20 text-blue-400 px-2 py-0.5 rounded">
-                          {plugin.plugin_type}
-                        </span>
-                        {!isEnabled && (
-                          <span className="text-xs bg-red-600/20 text-red-400 px-2 py-0.5 rounded">
-                            Disabled
-                          </span>
-                        )}
-                      </div>
+                          <div className="flex-1 min-w-0">
+                            <div className="flex items-center gap-2 mb-2 flex-wrap">
+                              <h3 className="text-base font-semibold text-gray-100">{plugin.name}</h3>
+                              <span className="text-xs bg-[#3e3e42] px-2 py-0.5 rounded text-gray-400">
+                                v{plugin.version}
+                              </span>
+                              <span className="text-xs bg-blue-600/20 text-blue-400 px-2 py-0.5 rounded">
+                                {plugin.plugin_type}
+                              </span>
+                              {!isEnabled && (
+                                <span className="text-xs bg-red-600/20 text-red-400 px-2 py-0.5 rounded">
+                                  Disabled
+                                </span>
+                              )}
+                            </div>
                       
-                      <p className="text-gray-400 text-sm mb-2 line-clamp-2">{plugin.description}</p>
+                            <p className="text-gray-400 text-sm mb-2 line-clamp-2">{plugin.description}</p>
                       
-                      <div className="flex items-center gap-3 text-xs text-gray-500">
-                        <span>👤 {plugin.author}</span>
-                        <span className="truncate">🆔 {plugin.id}</span>
-                      </div>
+                            <div className="flex items-center gap-3 text-xs text-gray-500">
+                              <span>👤 {plugin.author}</span>
+                              <span className="truncate">🆔 {plugin.id}</span>
+                              {plugin.homepage && (
+                                <a href={plugin.homepage} target="_blank" rel="noreferrer" className="hover:text-gray-300 truncate">
+                                  🔗 {plugin.homepage}
+                                </a>
+                              )}
+                            </div>
+                            
+                            {plugin.tags && plugin.tags.length > 0 && (
+                              <div className="mt-2 flex gap-1 flex-wrap">
+                                {plugin.tags.map((tag) => (
+                                  <span key={tag} className="text-xs text-gray-500">#{tag}</span>
+                                ))}
+                              </div>
+                            )}
                       
-                      {plugin.trigger_keywords.length > 0 && (
-                        <div className="mt-2 flex gap-1 flex-wrap">
-                          {plugin.trigger_keywords.slice(0, 5).map((keyword) => (
-                            <span
-                              key={keyword}
-                              className="text-xs bg-[#3e3e42] px-2 py-0.5 rounded text-gray-400"
-                            >
-                              {keyword}
-                            </span>
-                          ))}
-                          {plugin.trigger_keywords.length > 5 && (
-                            <span className="text-xs text-gray-500">
-                              +{plugin.trigger_keywords.length - 5} more
-                            </span>
-                          )}
-                        </div>
-                      )}
-                    </div>
+                            {plugin.trigger_keywords.length > 0 && (
+                              <div className="mt-2 flex gap-1 flex-wrap">
+                                {plugin.trigger_keywords.slice(0, 5).map((keyword) => (
+                                  <span
+                                    key={keyword}
+                                    className="text-xs bg-[#3e3e42] px-2 py-0.5 rounded text-gray-400"
+                                  >
+                                    {keyword}
+                                  </span>
+                                ))}
+                                {plugin.trigger_keywords.length > 5 && (
+                                  <span className="text-xs text-gray-500">
+                                    +{plugin.trigger_keywords.length - 5} more
+                                  </span>
+                                )}
+                              </div>
+                            )}
+                          </div>
                     
-                    {/* 操作按钮 */}
-                    <div className="flex flex-col gap-2">
-                      <button
-                        onClick={() => togglePlugin(plugin.id)}
-                        className={`px-3 py-1.5 rounded text-xs font-medium transition-colors flex items-center gap-1.5 ${
-                          isEnabled
-                            ? 'bg-green-600/20 text-green-400 hover:bg-green-600/30 border border-green-600/30'
-                            : 'bg-gray-700 text-gray-400 hover:bg-gray-600 border border-gray-600'
-                        }`}
-                      >
-                        {isEnabled ? (
-                          <>
-                            <Power className="w-3 h-3" />
-                            Enabled
-                          </>
-                        ) : (
-                          <>
-                            <PowerOff className="w-3 h-3" />
-                            Disabled
-                          </>
-                        )}
-                      </button>
+                          {/* 操作按钮 */}
+                          <div className="flex flex-col gap-2">
+                            <button
+                              onClick={() => togglePlugin(plugin.id)}
+                              className={`px-3 py-1.5 rounded text-xs font-medium transition-colors flex items-center gap-1.5 ${
+                                isEnabled
+                                  ? 'bg-green-600/20 text-green-400 hover:bg-green-600/30 border border-green-600/30'
+                                  : 'bg-gray-700 text-gray-400 hover:bg-gray-600 border border-gray-600'
+                              }`}
+                            >
+                              {isEnabled ? (
+                                <>
+                                  <Power className="w-3 h-3" />
+                                  Enabled
+                                </>
+                              ) : (
+                                <>
+                                  <PowerOff className="w-3 h-3" />
+                                  Disabled
+                                </>
+                              )}
+                            </button>
                       
-                      <button
-                        onClick={() => setConfigPlugin(plugin)}
-                        className="px-3 py-1.5 bg-[#3e3e42] hover:bg-[#555] text-gray-300 rounded text-xs font-medium transition-colors flex items-center gap-1.5"
-                        title="Configure plugin"
-                      >
-                        <Settings className="w-3 h-3" />
-                        Config
-                      </button>
-                    </div>
-                  </div>
+                            <button
+                              onClick={() => setConfigPlugin(plugin)}
+                              className="px-3 py-1.5 bg-[#3e3e42] hover:bg-[#555] text-gray-300 rounded text-xs font-medium transition-colors flex items-center gap-1.5"
+                              title="Configure plugin"
+                            >
+                              <Settings className="w-3 h-3" />
+                              Config
+                            </button>
+                          </div>
+                        </div>
+                      </div>
+                    );
+                  })}
                 </div>
               );
             })}
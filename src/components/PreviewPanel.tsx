@@ -18,12 +18,45 @@ interface FilePreview {
   extension: string;
 }
 
+interface FileProperties {
+  path: string;
+  name: string;
+  is_dir: boolean;
+  size: number;
+  created?: string;
+  modified?: string;
+  accessed?: string;
+  attributes: string[];
+  owner?: string;
+  version?: {
+    file_version?: string;
+    product_version?: string;
+    product_name?: string;
+    company_name?: string;
+    file_description?: string;
+    copyright?: string;
+  };
+  signature: 'valid' | 'invalid' | 'unsigned' | 'not_applicable';
+  sha256?: string;
+}
+
+const SIGNATURE_LABELS: Record<FileProperties['signature'], string> = {
+  valid: '✅ Signed (trusted)',
+  invalid: '⚠️ Signature invalid or untrusted',
+  unsigned: 'Not signed',
+  not_applicable: '—',
+};
+
 export const PreviewPanel: React.FC<PreviewPanelProps> = ({ filePath }) => {
   const [preview, setPreview] = useState<FilePreview | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [tab, setTab] = useState<'preview' | 'details'>('preview');
+  const [properties, setProperties] = useState<FileProperties | null>(null);
+  const [hashing, setHashing] = useState(false);
 
   useEffect(() => {
+    setProperties(null);
     if (!filePath) {
       setPreview(null);
       setError(null);
@@ -33,6 +66,26 @@ export const PreviewPanel: React.FC<PreviewPanelProps> = ({ filePath }) => {
     loadPreview(filePath);
   }, [filePath]);
 
+  // 详细信息页：切换到该页时异步获取（后端按文件修改时间缓存）
+  useEffect(() => {
+    if (tab !== 'details' || !filePath || properties?.path === filePath) return;
+    invoke<FileProperties>('get_file_properties', { path: filePath })
+      .then(setProperties)
+      .catch((err) => console.error('Failed to load file properties:', err));
+  }, [tab, filePath, properties]);
+
+  const computeHash = async () => {
+    if (!filePath) return;
+    setHashing(true);
+    try {
+      setProperties(await invoke<FileProperties>('get_file_properties', { path: filePath, hash: true }));
+    } catch (err) {
+      console.error('Failed to hash file:', err);
+    } finally {
+      setHashing(false);
+    }
+  };
+
   const loadPreview = async (path: string) => {
     setLoading(true);
     setError(null);
@@ -150,11 +203,72 @@ export const PreviewPanel: React.FC<PreviewPanelProps> = ({ filePath }) => {
               <span className="uppercase">{preview.extension}</span>
             </div>
           </div>
+          <div className="flex-shrink-0 flex gap-1 text-xs">
+            {(['preview', 'details'] as const).map((name) => (
+              <button
+                key={name}
+                onClick={() => setTab(name)}
+                className="px-2 py-1 rounded capitalize"
+                style={{
+                  color: tab === name ? 'var(--color-text-primary)' : 'var(--color-text-muted)',
+                  backgroundColor: tab === name ? 'rgba(255, 255, 255, 0.08)' : 'transparent',
+                }}
+              >
+                {name}
+              </button>
+            ))}
+          </div>
         </div>
       </div>
 
+      {/* 详细信息 */}
+      {tab === 'details' && (
+        <div className="flex-1 overflow-auto p-4 text-xs">
+          {!properties ? (
+            <p style={{ color: 'var(--color-text-muted)' }}>Loading details...</p>
+          ) : (
+            <table className="w-full">
+              <tbody>
+                {([
+                  ['Size', formatFileSize(properties.size)],
+                  ['Created', properties.created && formatDate(properties.created)],
+                  ['Modified', properties.modified && formatDate(properties.modified)],
+                  ['Accessed', properties.accessed && formatDate(properties.accessed)],
+                  ['Attributes', properties.attributes.join(', ') || '—'],
+                  ['Owner', properties.owner],
+                  ['Description', properties.version?.file_description],
+                  ['Product', properties.version?.product_name],
+                  ['Company', properties.version?.company_name],
+                  ['File version', properties.version?.file_version],
+                  ['Product version', properties.version?.product_version],
+                  ['Copyright', properties.version?.copyright],
+                  ['Signature', properties.signature === 'not_applicable' ? undefined : SIGNATURE_LABELS[properties.signature]],
+                ] as [string, string | undefined][])
+                  .filter(([, value]) => value)
+                  .map(([label, value]) => (
+                    <tr key={label}>
+                      <td className="py-1 pr-4 align-top whitespace-nowrap" style={{ color: 'var(--color-text-muted)' }}>{label}</td>
+                      <td className="py-1 break-all" style={{ color: 'var(--color-text-primary)' }}>{value}</td>
+                    </tr>
+                  ))}
+                <tr>
+                  <td className="py-1 pr-4 align-top whitespace-nowrap" style={{ color: 'var(--color-text-muted)' }}>SHA-256</td>
+                  <td className="py-1 break-all font-mono" style={{ color: 'var(--color-text-primary)' }}>
+                    {properties.sha256 ?? (
+                      <button onClick={computeHash} disabled={hashing} className="underline" style={{ color: 'var(--color-primary)' }}>
+                        {hashing ? 'Computing...' : 'Compute'}
+                      </button>
+                    )}
+                  </td>
+                </tr>
+              </tbody>
+            </table>
+          )}
+        </div>
+      )}
+
       {/* 预览内容区 */}
-      <div className="flex-1 overflow-auto">
+      <div className="flex-1 overflow-auto" style={{ display: tab === 'preview' ? undefined : 'none' }}>
         {/* 图片预览 */}
         {preview.file_type === 'image' && (
           <div className="p-4 flex items-center justify-center min-h-full">
@@ -0,0 +1,86 @@
+import { useEffect, useRef, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+
+// 快速记录窗口：Enter 保存（Shift+Enter 换行），Esc 或失去焦点时隐藏
+export function QuickCapture() {
+  const { t } = useTranslation();
+  const [text, setText] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+  const inputRef = useRef<HTMLTextAreaElement>(null);
+
+  useEffect(() => {
+    const appWindow = getCurrentWindow();
+
+    const setupListeners = async () => {
+      // 窗口显示后清空并聚焦输入框
+      const unlistenShown = await appWindow.listen('quick-capture-shown', () => {
+        setText('');
+        setError(null);
+        inputRef.current?.focus();
+      });
+
+      const unlistenFocus = await appWindow.onFocusChanged(({ payload: focused }) => {
+        if (!focused) {
+          invoke('hide_quick_capture').catch(() => {});
+        }
+      });
+
+      return () => {
+        unlistenShown();
+        unlistenFocus();
+      };
+    };
+
+    const cleanup = setupListeners();
+    return () => {
+      cleanup.then(fn => fn());
+    };
+  }, []);
+
+  const save = async () => {
+    if (!text.trim() || saving) return;
+    setSaving(true);
+    try {
+      await invoke<string>('save_quick_capture', { text });
+      setText('');
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent<HTMLTextAreaElement>) => {
+    if (e.key === 'Enter' && !e.shiftKey) {
+      e.preventDefault();
+      save();
+    } else if (e.key === 'Escape') {
+      e.preventDefault();
+      invoke('hide_quick_capture').catch(() => {});
+    }
+  };
+
+  return (
+    <div className="h-screen p-2">
+      <div className="h-full flex flex-col rounded-xl border border-gray-200 bg-white/95 shadow-xl backdrop-blur-sm">
+        <textarea
+          ref={inputRef}
+          autoFocus
+          value={text}
+          onChange={(e) => setText(e.target.value)}
+          onKeyDown={handleKeyDown}
+          placeholder={t('capture.placeholder')}
+          className="flex-1 resize-none bg-transparent px-4 py-3 text-base text-gray-800 outline-none"
+        />
+        <div className="flex items-center justify-between border-t border-gray-100 px-4 py-1.5 text-xs text-gray-400">
+          <span className={error ? 'text-red-500' : undefined}>{error ?? t('capture.hint')}</span>
+          {saving && <span>{t('common.loading')}</span>}
+        </div>
+      </div>
+    </div>
+  );
+}
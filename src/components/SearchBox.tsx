@@ -7,6 +7,8 @@ import { convertFileSrc } from '@tauri-apps/api/core';
 import { useAppStore } from '../store/useAppStore';
 import { useConfigStore } from '../store/useConfigStore';
 import { useQuery, useExecuteAction } from '../hooks/useQuery';
+import { useToast } from '../hooks/useToast';
+import type { Action, ConfirmationRequest, ExecuteOutcome, FollowUp, HotkeyBindingEvent, QueryResult, ResultBadge, SessionState } from '../types';
 import { ContextMenu } from './ContextMenu';
 import { highlightMatch } from '../utils/pinyinSearch';
 import '../animations.css';
@@ -32,6 +34,19 @@ function formatTimestamp(timestamp: string): string {
   }
 }
 
+// 由前端打开内置面板的结果（设置、插件管理、剪贴板、AI 助手），包括执行历史中的这些条目
+const BUILTIN_VIEW_IDS = ['settings', 'plugin_manager', 'clipboard_history'];
+
+function opensBuiltinView(result: QueryResult): boolean {
+  const isBuiltin = (id?: string, pluginId?: string) =>
+    (!!id && BUILTIN_VIEW_IDS.includes(id)) || (id === 'config' && pluginId === 'ai_assistant');
+  if (result.plugin_id === 'ai_assistant' || isBuiltin(result.id, result.plugin_id)) {
+    return true;
+  }
+  const original = result.context_data as { original_id?: string; plugin_id?: string } | null;
+  return result.plugin_id === 'execution-history' && isBuiltin(original?.original_id, original?.plugin_id);
+}
+
 interface SearchBoxProps {
   onOpenSettings: () => void;
   onOpenPlugins: () => void;
@@ -57,16 +72,27 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
   } | null>(null);
   const [selectedActionIndex, setSelectedActionIndex] = useState(0);
   const [suggestions, setSuggestions] = useState<any[]>([]);
+  // 插件执行后推送的结果（输入变化时清除）
+  const [pushedResults, setPushedResults] = useState<QueryResult[] | null>(null);
+  // 二级选择（候选由后端维护，输入在候选中过滤）
+  const [followUp, setFollowUp] = useState<FollowUp | null>(null);
+  // 执行时发现已不存在的文件（后端已移出索引，输入变化前先在本地隐藏）
+  const [missingIds, setMissingIds] = useState<string[]>([]);
+  const [confirmation, setConfirmation] = useState<ConfirmationRequest | null>(null);
+  const followUpActive = useRef(false);
+  const { showToast } = useToast();
   
   const {
     query,
     setQuery,
     setResults: _setResults,
+    setPinnedPreview,
     reset,
   } = useAppStore();
   
   const { config } = useConfigStore();
   const clearOnHide = config?.general.clear_on_hide ?? true;
+  const restoreSessionSecs = config?.general.restore_session_secs ?? 0;
   
   const { results, loading, debouncedQuery } = useQuery();
   const executeAction = useExecuteAction();
@@ -106,7 +132,13 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
   }, [results]);
   
   // 合并搜索结果和建议
-  const displayResults = useMemo(() => {
+  const mergedResults = useMemo(() => {
+    if (followUp) {
+      return followUp.results;
+    }
+    if (pushedResults) {
+      return pushedResults;
+    }
     if (hasValidResults) {
       return results;
     } else if (suggestions.length > 0) {
@@ -137,7 +169,12 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       return suggestionResults;
     }
     return results;
-  }, [results, suggestions, hasValidResults]);
+  }, [results, suggestions, hasValidResults, pushedResults, followUp]);
+  
+  const displayResults = useMemo(
+    () => (missingIds.length > 0 ? mergedResults.filter(r => !missingIds.includes(r.id)) : mergedResults),
+    [mergedResults, missingIds]
+  );
   
   // 本地导航函数（定义在displayResults之后）
   const selectNext = () => {
@@ -153,10 +190,118 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
     setSelectedIndex(0);
   }, [displayResults.length]);
   
+  // 会话恢复：隐藏时上报的快照，以及等待结果加载后恢复的选中项/滚动位置
+  const sessionSnapshot = useRef({ query, selected_index: selectedIndex });
+  sessionSnapshot.current = { query, selected_index: selectedIndex };
+  const pendingRestore = useRef<SessionState | null>(null);
+  
+  useEffect(() => {
+    const pending = pendingRestore.current;
+    if (!pending || loading || displayResults.length === 0) return;
+    pendingRestore.current = null;
+    setSelectedIndex(Math.min(pending.selected_index, displayResults.length - 1));
+    requestAnimationFrame(() => {
+      if (resultsContainerRef.current) {
+        resultsContainerRef.current.scrollTop = pending.scroll_top;
+      }
+    });
+  }, [displayResults, loading]);
+  
   useEffect(() => {
+    setPushedResults(null);
+    setMissingIds([]);
+    if (followUpActive.current) {
+      invoke<FollowUp | null>('filter_follow_up', { search: query })
+        .then(view => {
+          if (view) {
+            setFollowUp(view);
+          } else {
+            // 选择链已在后端结束
+            exitFollowUp();
+            debouncedQuery(query);
+          }
+        })
+        .catch(error => console.error('Failed to filter follow-up:', error));
+      return;
+    }
     debouncedQuery(query);
   }, [query, debouncedQuery]);
   
+  // 进入/返回二级选择层（view 为 null 时回到普通搜索）
+  const showFollowUp = (view: FollowUp | null) => {
+    followUpActive.current = view !== null;
+    setFollowUp(view);
+    setSelectedIndex(0);
+    if (query) {
+      setQuery('');
+    } else if (!view) {
+      debouncedQuery('');
+    }
+    inputRef.current?.focus();
+  };
+  
+  const exitFollowUp = () => {
+    followUpActive.current = false;
+    setFollowUp(null);
+  };
+  
+  // 处理插件执行反馈，返回 true 表示保持窗口显示
+  const applyExecuteOutcome = (outcome: ExecuteOutcome | null): boolean => {
+    if (!outcome) return false;
+    switch (outcome.type) {
+      case 'show_message':
+        showToast(outcome.message, 'success');
+        return true;
+      case 'replace_query':
+        setQuery(outcome.query);
+        inputRef.current?.focus();
+        return true;
+      case 'push_results':
+        setPushedResults(outcome.results);
+        setSelectedIndex(0);
+        return true;
+      case 'open_preview':
+        setPinnedPreview(outcome.preview);
+        return true;
+      case 'follow_up':
+        showFollowUp(outcome);
+        return true;
+      case 'file_missing':
+        showToast(outcome.message, 'warning');
+        setMissingIds(prev => [...prev, outcome.path]);
+        return true;
+      case 'confirm':
+        setConfirmation(outcome);
+        return true;
+      default:
+        return false;
+    }
+  };
+  
+  // 破坏性动作确认：凭令牌继续执行
+  const handleConfirm = async () => {
+    if (!confirmation) return;
+    const { token } = confirmation;
+    setConfirmation(null);
+    let outcome: ExecuteOutcome;
+    try {
+      outcome = await invoke<ExecuteOutcome>('confirm_action', { token });
+    } catch (error) {
+      showToast(String(error), 'error');
+      return;
+    }
+    if (!applyExecuteOutcome(outcome)) {
+      await handleHide();
+    }
+  };
+  
+  const handleCancelConfirm = () => {
+    if (!confirmation) return;
+    invoke('cancel_confirmation', { token: confirmation.token }).catch(() => {});
+    setConfirmation(null);
+    inputRef.current?.focus();
+  };
+  
   // 自动滚动到选中项
   useEffect(() => {
     if (selectedItemRef.current && resultsContainerRef.current) {
@@ -174,15 +319,39 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
     const setupListeners = async () => {
       // 监听 focus-input 事件
       const unlistenFocusInput = await appWindow.listen('focus-input', () => {
+        exitFollowUp();
         reset();
         if (inputRef.current) {
           inputRef.current.focus();
           inputRef.current.select();
         }
+        // 回报输入框就绪，完成窗口显示延迟计时
+        invoke('report_show_input_ready').catch(() => {});
+        
+        if (restoreSessionSecs > 0) {
+          invoke<SessionState | null>('get_session_state')
+            .then(state => {
+              if (!state) return;
+              pendingRestore.current = state;
+              setQuery(state.query);
+            })
+            .catch(error => console.error('Failed to restore session:', error));
+        }
       });
       
       // 监听 app-hiding 事件，根据配置清空搜索结果
       const unlistenAppHiding = await appWindow.listen('app-hiding', () => {
+        // 后端隐藏窗口时已清空选择链
+        exitFollowUp();
+        if (restoreSessionSecs > 0) {
+          const state: SessionState = {
+            ...sessionSnapshot.current,
+            scroll_top: resultsContainerRef.current?.scrollTop ?? 0,
+          };
+          invoke('save_session_state', { state }).catch(error =>
+            console.error('Failed to save session:', error)
+          );
+        }
         if (clearOnHide) {
           console.log('Clearing search results on hide (clear_on_hide enabled)');
           reset();
@@ -191,9 +360,23 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
         }
       });
       
+      // 命名热键绑定：预填查询或运行工作流
+      const unlistenBinding = await appWindow.listen<HotkeyBindingEvent>('hotkey:binding', ({ payload }) => {
+        if (payload.action === 'query') {
+          exitFollowUp();
+          setQuery(payload.argument);
+          inputRef.current?.focus();
+        } else if (payload.action === 'workflow') {
+          invoke('execute_workflow', { id: payload.argument, variables: {} })
+            .then(() => showToast(t('hotkeyBinding.workflowDone', { id: payload.id }), 'success'))
+            .catch(error => showToast(String(error), 'error'));
+        }
+      });
+      
       return () => {
         unlistenFocusInput();
         unlistenAppHiding();
+        unlistenBinding();
       };
     };
     
@@ -202,7 +385,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
     return () => {
       cleanup.then(fn => fn());
     };
-  }, [reset, clearOnHide]);
+  }, [reset, clearOnHide, restoreSessionSecs]);
   
   // 当选中的结果改变时，关闭右键菜单
   useEffect(() => {
@@ -218,6 +401,18 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
   }, []);
   
   const handleKeyDown = async (e: React.KeyboardEvent) => {
+    // 确认框显示中：Enter 确认，Esc 取消
+    if (confirmation) {
+      if (e.key === 'Enter') {
+        e.preventDefault();
+        await handleConfirm();
+      } else if (e.key === 'Escape') {
+        e.preventDefault();
+        handleCancelConfirm();
+      }
+      return;
+    }
+    
     // 快捷键指南
     if ((e.key === '?' && !e.shiftKey) || e.key === 'F1') {
       e.preventDefault();
@@ -225,6 +420,26 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       return;
     }
     
+    // Ctrl+Shift+1..9：粘贴固定在对应槽位的剪贴板条目
+    const pinnedSlot = e.ctrlKey && e.shiftKey ? /^Digit([1-9])$/.exec(e.code) : null;
+    if (pinnedSlot) {
+      e.preventDefault();
+      try {
+        await invoke('paste_pinned_slot', { slot: Number(pinnedSlot[1]) });
+      } catch (error) {
+        console.error('Failed to paste pinned slot:', error);
+      }
+      return;
+    }
+    
+    // Alt+1..9：直接执行第 N 个结果
+    const quickSelect = e.altKey && !e.ctrlKey && !e.shiftKey ? /^Digit([1-9])$/.exec(e.code) : null;
+    if (quickSelect && !contextMenu) {
+      e.preventDefault();
+      await handleQuickSelect(Number(quickSelect[1]) - 1);
+      return;
+    }
+    
     // 如果右键菜单显示中，处理上下键选择操作
     if (contextMenu && contextMenu.actions.length > 0) {
       switch (e.key) {
@@ -280,17 +495,69 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
         }
         break;
         
+      case 'Tab': {
+        // 结果声明了 Tab 快捷动作时进入下一级（如应用的最近文档）
+        const tabAction = displayResults[selectedIndex]?.actions.find(a => a.hotkey === 'Tab');
+        if (!tabAction || e.shiftKey) break;
+        e.preventDefault();
+        await handleExecuteAction(tabAction.id);
+        break;
+      }
+        
       case 'Escape':
         e.preventDefault();
+        if (followUp) {
+          // 返回上一级选择
+          try {
+            showFollowUp(await invoke<FollowUp | null>('follow_up_back'));
+          } catch (error) {
+            console.error('Failed to go back:', error);
+            exitFollowUp();
+          }
+          break;
+        }
         await handleHide();
         break;
     }
   };
   
-  const handleExecute = async () => {
+  // 快速选择：后端结果集中的结果只传位置，由后端查找并执行默认动作；
+  // 搜索建议、推送结果等前端列表及打开内置面板的结果走回车流程
+  const handleQuickSelect = async (index: number) => {
+    const result = displayResults[index];
+    if (!result) return;
+    setSelectedIndex(index);
+    
+    const position = results.indexOf(result) + 1;
+    if (position === 0 || pushedResults || followUp || opensBuiltinView(result)) {
+      await handleExecute(index);
+      return;
+    }
+    
+    if (query.trim()) {
+      invoke('record_search_execution', { query: query.trim() }).catch(error =>
+        console.error('Failed to record search execution:', error)
+      );
+    }
+    
+    let outcome: ExecuteOutcome;
+    try {
+      outcome = await invoke<ExecuteOutcome>('execute_result_index', { position, resultId: result.id });
+    } catch (error) {
+      console.error('Quick select failed:', error);
+      showToast(String(error), 'error');
+      return;
+    }
+    const defaultAction = result.actions.find(a => a.is_default) || result.actions[0];
+    if (!applyExecuteOutcome(outcome) && !defaultAction?.prevent_hide) {
+      await handleHide();
+    }
+  };
+  
+  const handleExecute = async (index: number = selectedIndex) => {
     if (displayResults.length === 0) return;
     
-    const result = displayResults[selectedIndex];
+    const result = displayResults[index];
     
     // 处理搜索建议
     if (result.plugin_id === 'search_history') {
@@ -335,14 +602,14 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
     const defaultAction = result.actions.find(a => a.is_default) || result.actions[0];
     
     if (defaultAction) {
-      await handleExecuteAction(defaultAction.id);
+      await handleExecuteAction(defaultAction.id, index);
     }
   };
   
-  const handleExecuteAction = async (actionId: string) => {
+  const handleExecuteAction = async (actionId: string, index: number = selectedIndex) => {
     if (displayResults.length === 0) return;
     
-    const result = displayResults[selectedIndex];
+    const result = displayResults[index];
     const action = result.actions.find(a => a.id === actionId);
     
     if (!action) return;
@@ -375,7 +642,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
           return;
         }
         
-        await executeAction(
+        const outcome = await executeAction(
           contextData.original_id,
           contextData.action_id,
           contextData.plugin_id,
@@ -384,7 +651,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
           result.icon
         );
         
-        if (!action.prevent_hide) {
+        if (!applyExecuteOutcome(outcome) && !action.prevent_hide) {
           await handleHide();
         }
         
@@ -393,9 +660,9 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       }
     }
     
-    await executeAction(result.id, actionId, result.plugin_id, result.title, result.subtitle, result.icon);
+    const outcome = await executeAction(result.id, actionId, result.plugin_id, result.title, result.subtitle, result.icon);
     
-    if (!action.prevent_hide) {
+    if (!applyExecuteOutcome(outcome) && !action.prevent_hide) {
       await handleHide();
     }
     
@@ -445,7 +712,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
           return;
         }
         
-        await executeAction(
+        const outcome = await executeAction(
           contextData.original_id,
           contextData.action_id,
           contextData.plugin_id,
@@ -454,7 +721,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
           result?.icon || { type: 'emoji', data: '📋' }
         );
         
-        if (!action.prevent_hide) {
+        if (!applyExecuteOutcome(outcome) && !action.prevent_hide) {
           await handleHide();
         }
         
@@ -463,7 +730,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       }
     }
     
-    await executeAction(
+    const outcome = await executeAction(
       contextMenu.resultId, 
       actionId, 
       contextMenu.pluginId, 
@@ -472,7 +739,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       result?.icon || { type: 'emoji', data: '📋' }
     );
     
-    if (!action.prevent_hide) {
+    if (!applyExecuteOutcome(outcome) && !action.prevent_hide) {
       await handleHide();
     }
     
@@ -480,7 +747,7 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
     setContextMenu(null);
   };
   
-  const handleContextMenu = (e: React.MouseEvent, result: any) => {
+  const handleContextMenu = async (e: React.MouseEvent, result: any) => {
     e.preventDefault();
     e.stopPropagation();
     
@@ -495,6 +762,21 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
       pluginId: result.plugin_id,
     });
     setSelectedActionIndex(0);
+    
+    // 文件结果追加系统右键菜单项（打开方式、解压等）
+    const path = result.context_data?.path;
+    if (typeof path === 'string' && path === result.id) {
+      try {
+        const shellActions = await invoke<Action[]>('get_shell_context_actions', { path });
+        if (shellActions.length > 0) {
+          setContextMenu(prev => prev && prev.resultId === result.id
+            ? { ...prev, actions: [...prev.actions, ...shellActions] }
+            : prev);
+        }
+      } catch (error) {
+        console.error('Failed to load shell context menu:', error);
+      }
+    }
   };
   
   const handleHide = async () => {
@@ -516,13 +798,18 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
         borderBottomColor: 'rgba(255, 255, 255, 0.08)'
       }}>
         <Search className="w-5 h-5" style={{ color: 'var(--color-text-muted)' }} />
+        {followUp && (
+          <span className="text-xs whitespace-nowrap" style={{ color: 'var(--color-text-muted)' }}>
+            {followUp.breadcrumbs.join(' › ')} ›
+          </span>
+        )}
         <input
           ref={inputRef}
           type="text"
           value={query}
           onChange={(e) => setQuery(e.target.value)}
           onKeyDown={handleKeyDown}
-          placeholder={t('search.placeholder') || 'Type to search...'}
+          placeholder={followUp?.placeholder || t('search.placeholder') || 'Type to search...'}
           autoFocus
           className="search-input flex-1 text-base bg-transparent border-0 focus:outline-none focus:ring-0 focus:border-0 placeholder:text-gray-500"
           style={{ 
@@ -624,6 +911,48 @@ export function SearchBox({ onOpenSettings, onOpenPlugins, onOpenClipboard, onOp
         </>
       )}
       
+      {/* 破坏性动作确认 */}
+      {confirmation && (
+        <div className="mx-6 my-3 p-4 rounded-lg border" style={{
+          backgroundColor: 'var(--color-background)',
+          borderColor: 'var(--color-error, #f44336)'
+        }}>
+          <div className="text-sm font-semibold mb-1" style={{ color: 'var(--color-text-primary)' }}>
+            ⚠️ {confirmation.dry_run.summary}
+          </div>
+          <dl className="text-xs grid grid-cols-[auto_1fr] gap-x-3 gap-y-1 my-2">
+            {confirmation.dry_run.details.map(detail => (
+              <React.Fragment key={detail.label}>
+                <dt style={{ color: 'var(--color-text-muted)' }}>{detail.label}</dt>
+                <dd className="break-all" style={{ color: 'var(--color-text-secondary)' }}>{detail.value}</dd>
+              </React.Fragment>
+            ))}
+          </dl>
+          {!confirmation.dry_run.reversible && (
+            <p className="text-xs mb-2" style={{ color: 'var(--color-error, #f44336)' }}>{t('confirm.irreversible')}</p>
+          )}
+          <div className="flex items-center justify-between">
+            <span className="text-xs" style={{ color: 'var(--color-text-muted)' }}>{t('confirm.hint')}</span>
+            <div className="flex gap-2">
+              <button
+                onClick={handleCancelConfirm}
+                className="px-3 py-1 text-xs rounded border"
+                style={{ borderColor: 'var(--color-border)', color: 'var(--color-text-secondary)' }}
+              >
+                {t('confirm.cancel')}
+              </button>
+              <button
+                onClick={handleConfirm}
+                className="px-3 py-1 text-xs rounded text-white"
+                style={{ backgroundColor: 'var(--color-error, #f44336)' }}
+              >
+                {t('confirm.confirm')}
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+      
       {/* 右键上下文菜单 */}
       {contextMenu && (
         <ContextMenu
@@ -727,8 +1056,21 @@ const ResultItem = React.forwardRef<HTMLDivElement, ResultItemProps>(
         
         {/* 文本内容 - 使用动画类 */}
         <div className="flex-1 min-w-0">
-          <div className="result-title text-sm font-medium truncate mb-0.5" style={{ color: 'var(--color-text-primary)' }}>
-            {highlightMatch(result.title, query)}
+          <div className="result-title text-sm font-medium truncate mb-0.5 flex items-center gap-1.5" style={{ color: 'var(--color-text-primary)' }}>
+            <span className="truncate">{highlightMatch(result.title, query)}</span>
+            {result.badges?.map((badge: ResultBadge) => (
+              <span
+                key={badge.id}
+                title={badge.tooltip}
+                className="flex-shrink-0 px-1.5 py-px rounded text-[10px] font-semibold"
+                style={{
+                  color: badge.tone === 'danger' ? '#f87171' : badge.tone === 'warning' ? '#fbbf24' : 'var(--color-primary)',
+                  backgroundColor: badge.tone === 'danger' ? 'rgba(248, 113, 113, 0.15)' : badge.tone === 'warning' ? 'rgba(251, 191, 36, 0.15)' : 'rgba(255, 255, 255, 0.06)',
+                }}
+              >
+                {badge.label}
+              </span>
+            ))}
           </div>
           {result.subtitle && (
             <div className="result-subtitle text-xs truncate" style={{ color: 'var(--color-text-secondary)' }}>
@@ -2,7 +2,7 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useTranslation } from 'react-i18next';
 import { useThemeStore } from '../stores/themeStore';
-import { useConfigStore } from '../store/useConfigStore';
+import { useConfigStore, FallbackKind } from '../store/useConfigStore';
 import { useToast } from '../hooks/useToast';
 import { applyTheme, Theme, themes } from '../theme';
 import { ThemeEditor } from './ThemeEditor';
@@ -16,6 +16,8 @@ interface AppConfig {
     max_results: number;
     language: string;
     clear_on_hide: boolean;
+    prewarm_window?: boolean;
+    restore_session_secs?: number;
   };
   appearance: {
     theme: string;
@@ -30,6 +32,10 @@ interface AppConfig {
     enabled_plugins: string[];
     disabled_plugins: string[];
   };
+  fallback?: {
+    chain: Array<{ kind: FallbackKind; enabled: boolean }>;
+    web_search_url: string;
+  };
   advanced: {
     start_on_boot: boolean;
     show_tray_icon: boolean;
@@ -58,6 +64,13 @@ interface PluginConfig {
   [key: string]: any;
 }
 
+// 需要管理员权限的功能及用户的选择
+interface ElevationConsent {
+  feature: string;
+  label: string;
+  decision: 'ask' | 'allowed' | 'denied';
+}
+
 interface SettingsProps {
   onClose: () => void;
 }
@@ -77,6 +90,7 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
   const [plugins, setPlugins] = useState<PluginMetadata[]>([]);
   const [pluginConfigs, setPluginConfigs] = useState<Record<string, PluginConfig>>({});
   const [searchQuery, setSearchQuery] = useState('');
+  const [elevationConsents, setElevationConsents] = useState<ElevationConsent[]>([]);
 
   // 从全局配置初始化本地编辑状态
   useEffect(() => {
@@ -114,6 +128,22 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
     loadPlugins();
   }, []);
 
+  // 加载提权同意记录
+  useEffect(() => {
+    invoke<ElevationConsent[]>('get_elevation_consents')
+      .then(setElevationConsents)
+      .catch(error => console.error('Failed to load elevation consents:', error));
+  }, []);
+
+  const updateElevationConsent = async (feature: string, decision: ElevationConsent['decision']) => {
+    try {
+      await invoke('set_elevation_consent', { feature, decision });
+      setElevationConsents(prev => prev.map(c => (c.feature === feature ? { ...c, decision } : c)));
+    } catch (error) {
+      showToast(String(error), 'error');
+    }
+  };
+
   useEffect(() => {
     const handleEsc = async (e: KeyboardEvent) => {
       if (e.key === 'Escape') {
@@ -132,6 +162,29 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
     
     setSaving(true);
     try {
+      // 热键变化时先在后端重新注册，冲突或无效时不保存
+      if (config.general.hotkey !== globalConfig?.general.hotkey) {
+        try {
+          await invoke('update_hotkey', { hotkey: config.general.hotkey });
+        } catch (error) {
+          setHotkeyError(String(error));
+          showToast(String(error), 'error');
+          return;
+        }
+      }
+      
+      // 命名热键绑定变化时重新注册（个别热键被占用时仍保存，只提示）
+      const bindings = (config as any).hotkey_bindings ?? [];
+      if (JSON.stringify(bindings) !== JSON.stringify((globalConfig as any)?.hotkey_bindings ?? [])) {
+        try {
+          const failed = await invoke<[string, string][]>('update_hotkey_bindings', { bindings });
+          failed.forEach(([id, reason]) => showToast(`${id}: ${reason}`, 'warning'));
+        } catch (error) {
+          showToast(String(error), 'error');
+          return;
+        }
+      }
+      
       // 保存到全局store（会同时调用后端保存）
       await saveGlobalConfig(config as any);
       setTheme(config.appearance.theme);
@@ -471,6 +524,148 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
                       </label>
                     </div>
 
+                    {/* 会话恢复 */}
+                    <div 
+                      className="p-5 rounded-xl border transition-all hover:shadow-lg"
+                      style={{
+                        backgroundColor: 'var(--color-surface)',
+                        borderColor: 'var(--color-border)'
+                      }}
+                    >
+                      <label className="block">
+                        <div className="flex items-center justify-between mb-3">
+                          <div className="flex items-center gap-2">
+                            <span className="text-lg">↩️</span>
+                            <span className="text-sm font-medium" style={{ color: 'var(--color-text-primary)' }}>
+                              {t('settings.restoreSession')}
+                            </span>
+                          </div>
+                          <span className="text-sm font-mono px-2 py-1 rounded" style={{ 
+                            backgroundColor: 'var(--color-primary-alpha)',
+                            color: 'var(--color-primary)' 
+                          }}>
+                            {config.general.restore_session_secs ? `${config.general.restore_session_secs}s` : t('settings.restoreSessionOff')}
+                          </span>
+                        </div>
+                        <input
+                          type="number"
+                          value={config.general.restore_session_secs ?? 0}
+                          onChange={(e) => setConfig({
+                            ...config,
+                            general: { ...config.general, restore_session_secs: Math.max(0, parseInt(e.target.value) || 0) }
+                          })}
+                          className="w-full px-4 py-2.5 text-sm rounded-lg border transition-all focus:ring-2"
+                          style={{
+                            backgroundColor: 'var(--color-background)',
+                            color: 'var(--color-text-primary)',
+                            borderColor: 'var(--color-border)',
+                            outline: 'none'
+                          }}
+                          min="0"
+                          max="3600"
+                        />
+                        <p className="mt-2 text-xs" style={{ color: 'var(--color-text-muted)' }}>
+                          {t('settings.restoreSessionDesc')}
+                        </p>
+                      </label>
+                    </div>
+
+                    {/* 无结果备选链 */}
+                    {config.fallback && (
+                      <div 
+                        className="p-5 rounded-xl border transition-all hover:shadow-lg"
+                        style={{
+                          backgroundColor: 'var(--color-surface)',
+                          borderColor: 'var(--color-border)'
+                        }}
+                      >
+                        <div className="flex items-center gap-2 mb-1">
+                          <span className="text-lg">🪂</span>
+                          <span className="text-sm font-medium" style={{ color: 'var(--color-text-primary)' }}>
+                            {t('settings.fallback')}
+                          </span>
+                        </div>
+                        <p className="mb-3 text-xs" style={{ color: 'var(--color-text-muted)' }}>
+                          {t('settings.fallbackDesc')}
+                        </p>
+                        <div className="space-y-2">
+                          {config.fallback.chain.map((entry, index, chain) => {
+                            const updateChain = (next: typeof chain) => setConfig({
+                              ...config,
+                              fallback: { ...config.fallback!, chain: next }
+                            });
+                            const move = (delta: number) => {
+                              const next = [...chain];
+                              [next[index], next[index + delta]] = [next[index + delta], next[index]];
+                              updateChain(next);
+                            };
+                            return (
+                              <div
+                                key={entry.kind}
+                                className="flex items-center justify-between px-3 py-2 rounded-lg"
+                                style={{ backgroundColor: 'var(--color-background)' }}
+                              >
+                                <label className="flex items-center gap-3 cursor-pointer">
+                                  <input
+                                    type="checkbox"
+                                    checked={entry.enabled}
+                                    onChange={(e) => updateChain(chain.map((item, i) =>
+                                      i === index ? { ...item, enabled: e.target.checked } : item
+                                    ))}
+                                    className="w-4 h-4 accent-[#007acc]"
+                                  />
+                                  <span className="text-sm" style={{ color: 'var(--color-text-primary)' }}>
+                                    {t(`settings.fallbackKinds.${entry.kind}`)}
+                                  </span>
+                                </label>
+                                <div className="flex gap-1">
+                                  <button
+                                    onClick={() => move(-1)}
+                                    disabled={index === 0}
+                                    className="px-2 py-0.5 text-xs rounded disabled:opacity-30"
+                                    style={{ color: 'var(--color-text-secondary)' }}
+                                    title={t('settings.fallbackMoveUp')}
+                                  >
+                                    ↑
+                                  </button>
+                                  <button
+                                    onClick={() => move(1)}
+                                    disabled={index === chain.length - 1}
+                                    className="px-2 py-0.5 text-xs rounded disabled:opacity-30"
+                                    style={{ color: 'var(--color-text-secondary)' }}
+                                    title={t('settings.fallbackMoveDown')}
+                                  >
+                                    ↓
+                                  </button>
+                                </div>
+                              </div>
+                            );
+                          })}
+                        </div>
+                        <label className="block mt-3">
+                          <span className="text-xs" style={{ color: 'var(--color-text-secondary)' }}>
+                            {t('settings.fallbackSearchUrl')}
+                          </span>
+                          <input
+                            type="text"
+                            value={config.fallback.web_search_url}
+                            onChange={(e) => setConfig({
+                              ...config,
+                              fallback: { ...config.fallback!, web_search_url: e.target.value }
+                            })}
+                            className="mt-1 w-full px-4 py-2.5 text-sm rounded-lg border transition-all focus:ring-2"
+                            style={{
+                              backgroundColor: 'var(--color-background)',
+                              color: 'var(--color-text-primary)',
+                              borderColor: 'var(--color-border)',
+                              outline: 'none'
+                            }}
+                            placeholder="https://www.google.com/search?q={query}"
+                          />
+                        </label>
+                      </div>
+                    )}
+
                     {/* 开关选项组 */}
                     <div 
                       className="p-5 rounded-xl border space-y-3"
@@ -510,6 +705,37 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
                         </div>
                       </label>
 
+                      <label className="flex items-center justify-between p-3 rounded-lg cursor-pointer transition-all hover:shadow-md"
+                        style={{ backgroundColor: 'var(--color-background)' }}
+                      >
+                        <div className="flex items-center gap-3">
+                          <span className="text-lg">⚡</span>
+                          <div>
+                            <span className="text-sm font-medium block" style={{ color: 'var(--color-text-primary)' }}>
+                              {t('settings.prewarmWindow')}
+                            </span>
+                            <p className="text-xs mt-0.5" style={{ color: 'var(--color-text-muted)' }}>
+                              {t('settings.prewarmWindowDesc')}
+                            </p>
+                          </div>
+                        </div>
+                        <div className="relative">
+                          <input
+                            type="checkbox"
+                            checked={config.general.prewarm_window ?? false}
+                            onChange={(e) => setConfig({
+                              ...config,
+                              general: { ...config.general, prewarm_window: e.target.checked }
+                            })}
+                            className="peer sr-only"
+                          />
+                          <div className="w-11 h-6 rounded-full transition-all peer-checked:bg-[var(--color-primary)]"
+                            style={{ backgroundColor: 'var(--color-border)' }}
+                          ></div>
+                          <div className="absolute left-1 top-1 w-4 h-4 bg-white rounded-full transition-all peer-checked:translate-x-5"></div>
+                        </div>
+                      </label>
+
                       <label className="flex items-center justify-between p-3 rounded-lg cursor-pointer transition-all hover:shadow-md"
                         style={{ backgroundColor: 'var(--color-background)' }}
                       >
@@ -1035,6 +1261,30 @@ export const Settings: React.FC<SettingsProps> = ({ onClose }) => {
                     </div>
                   </div>
 
+                  {/* 管理员权限（每个功能记住 UAC 选择） */}
+                  {elevationConsents.length > 0 && (
+                    <div>
+                      <h2 className="text-base font-semibold mb-1" style={{ color: 'var(--color-text-primary)' }}>{t('settings.elevation')}</h2>
+                      <p className="text-xs mb-3" style={{ color: 'var(--color-text-muted)' }}>{t('settings.elevationDesc')}</p>
+                      <div className="space-y-3">
+                        {elevationConsents.map(consent => (
+                          <div key={consent.feature} className="flex items-center justify-between px-4 py-3 bg-[#2d2d30] rounded border border-[#3e3e42]">
+                            <span className="text-sm font-medium text-gray-300">{consent.label}</span>
+                            <select
+                              value={consent.decision}
+                              onChange={(e) => updateElevationConsent(consent.feature, e.target.value as ElevationConsent['decision'])}
+                              className="px-2 py-1 text-sm rounded bg-[#1e1e1e] border border-[#3e3e42] text-gray-300"
+                            >
+                              <option value="ask">{t('settings.elevationAsk')}</option>
+                              <option value="allowed">{t('settings.elevationAllowed')}</option>
+                              <option value="denied">{t('settings.elevationDenied')}</option>
+                            </select>
+                          </div>
+                        ))}
+                      </div>
+                    </div>
+                  )}
+
                   {/* 更新检查 */}
                   <div>
                     <h2 className="text-base font-semibold mb-3" style={{ color: 'var(--color-text-primary)' }}>{t('updates.title')}</h2>
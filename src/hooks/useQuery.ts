@@ -1,17 +1,71 @@
-import { useState, useCallback, useRef } from 'react';
+import { useState, useCallback, useRef, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { QueryResult } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import type { ExecuteOutcome, QueryResult } from '../types';
 
 let debounceTimer: ReturnType<typeof setTimeout>;
 
+// 与后端 core::query_cancel::QUERY_CANCELLED 保持一致
+const QUERY_CANCELLED = 'Query cancelled';
+
+// 存在可刷新结果（如 MFT 首次扫描进度）时，按此间隔静默重新执行当前查询
+const REFRESH_INTERVAL_MS = 1000;
+
+// 流式查询：即时答案（计算器、单位换算、剪贴板、路径直达）先于其他插件发送
+interface InstantAnswers {
+  generation: number;
+  results: QueryResult[];
+}
+
+// 流式查询：每个插件完成时后端发送的部分结果
+interface PartialQueryResults {
+  generation: number;
+  plugin_id: string;
+  results: QueryResult[];
+}
+
 export function useQuery() {
   const [results, setResults] = useState<QueryResult[]>([]);
   const [loading, setLoading] = useState(false);
   
   // 使用 ref 跟踪最新的查询序列号
   const queryIdRef = useRef(0);
+  // 本次挂载的会话 ID：重新挂载后序列号从 1 开始，后端按会话区分新旧查询
+  const sessionRef = useRef(`${Date.now().toString(36)}-${Math.random().toString(36).slice(2)}`);
+  // 已收到部分结果的查询序列号（首个部分结果替换上一次查询的结果）
+  const partialQueryIdRef = useRef(0);
+  // 最近一次执行的查询输入（刷新时复用）
+  const lastInputRef = useRef('');
   
-  const performQuery = useCallback(async (input: string) => {
+  useEffect(() => {
+    const unlisten = listen<PartialQueryResults>('query-results-partial', (event) => {
+      const { generation, plugin_id, results: partial } = event.payload;
+      if (generation !== queryIdRef.current) {
+        return;
+      }
+      const isFirst = partialQueryIdRef.current !== generation;
+      partialQueryIdRef.current = generation;
+      setResults((prev) => {
+        const kept = isFirst ? [] : prev.filter((r) => r.plugin_id !== plugin_id);
+        return [...kept, ...partial].sort((a, b) => b.score - a.score);
+      });
+    });
+    const unlistenInstant = listen<InstantAnswers>('query-instant-answers', (event) => {
+      const { generation, results: instant } = event.payload;
+      if (generation !== queryIdRef.current) {
+        return;
+      }
+      partialQueryIdRef.current = generation;
+      setResults([...instant].sort((a, b) => b.score - a.score));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+      unlistenInstant.then((fn) => fn());
+    };
+  }, []);
+  
+  const performQuery = useCallback(async (input: string, silent = false) => {
+    lastInputRef.current = input;
     if (!input.trim()) {
       setResults([]);
       return;
@@ -23,9 +77,17 @@ export function useQuery() {
     
     console.log(`[Query] Starting query #${currentQueryId}: "${input}"`);
     
-    setLoading(true);
+    if (!silent) {
+      setLoading(true);
+    }
     try {
-      const data = await invoke<QueryResult[]>('query', { input });
+      // 后端按 generation 取消旧查询，过期查询返回 "Query cancelled"
+      const data = await invoke<QueryResult[]>('query', {
+        input,
+        generation: currentQueryId,
+        session: sessionRef.current,
+        stream: true,
+      });
       const queryElapsed = performance.now() - queryStartTime;
       
       // 只有当这是最新的查询时才更新结果
@@ -40,6 +102,10 @@ export function useQuery() {
         });
       }
     } catch (error) {
+      if (error === QUERY_CANCELLED) {
+        console.log(`[Query] 🚫 Cancelled #${currentQueryId}`);
+        return;
+      }
       const queryElapsed = performance.now() - queryStartTime;
       console.error(`[Query] ❌ Failed #${currentQueryId} after ${queryElapsed.toFixed(2)}ms:`, error);
       // 只有当这是最新的查询时才清空结果
@@ -54,6 +120,15 @@ export function useQuery() {
     }
   }, []);
   
+  // 结果中包含可刷新项时定时重新查询（结果更新后重新计时，不再有可刷新项时停止）
+  useEffect(() => {
+    if (!results.some((r) => r.refreshable)) {
+      return;
+    }
+    const timer = setTimeout(() => performQuery(lastInputRef.current, true), REFRESH_INTERVAL_MS);
+    return () => clearTimeout(timer);
+  }, [results, performQuery]);
+  
   const debouncedQuery = useCallback((input: string) => {
     clearTimeout(debounceTimer);
     // 🔥 优化：增加 debounce 延迟到 100ms，减少连续输入时的查询次数
@@ -74,12 +149,13 @@ export function useExecuteAction() {
     title: string,
     subtitle: string,
     icon: any // WoxImage type
-  ) => {
+  ): Promise<ExecuteOutcome | null> => {
     console.log('[useExecuteAction] Called with:', { resultId, actionId, pluginId, title });
     try {
-      await invoke('execute_action', { resultId, actionId, pluginId, title, subtitle, icon });
+      return await invoke<ExecuteOutcome>('execute_action', { resultId, actionId, pluginId, title, subtitle, icon });
     } catch (error) {
       console.error('Execute action failed:', error);
+      return null;
     }
   }, []);
 }
@@ -1,6 +1,7 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import { QuickCapture } from "./components/QuickCapture";
 import "./index.css";
 import "./i18n";
 import { useThemeStore } from "./stores/themeStore";
@@ -10,8 +11,11 @@ import { applyTheme, getTheme } from "./theme";
 const initialTheme = getTheme(useThemeStore.getState().currentTheme);
 applyTheme(initialTheme);
 
+// 快速记录窗口与主窗口共用同一页面，通过 URL 参数区分
+const isCaptureWindow = new URLSearchParams(window.location.search).get("window") === "capture";
+
 ReactDOM.createRoot(document.getElementById("root")!).render(
   <React.StrictMode>
-    <App />
+    {isCaptureWindow ? <QuickCapture /> : <App />}
   </React.StrictMode>,
 );
@@ -1,5 +1,5 @@
 import { create } from 'zustand';
-import type { QueryResult } from '../types';
+import type { Preview, QueryResult } from '../types';
 
 interface AppState {
   query: string;
@@ -7,12 +7,15 @@ interface AppState {
   selectedIndex: number;
   loading: boolean;
   visible: boolean;
+  // 插件执行后要求打开的预览（查询变化时清除）
+  pinnedPreview: Preview | null;
   
   setQuery: (query: string) => void;
   setResults: (results: QueryResult[]) => void;
   setSelectedIndex: (index: number) => void;
   setLoading: (loading: boolean) => void;
   setVisible: (visible: boolean) => void;
+  setPinnedPreview: (preview: Preview | null) => void;
   
   selectNext: () => void;
   selectPrev: () => void;
@@ -25,12 +28,14 @@ export const useAppStore = create<AppState>((set, get) => ({
   selectedIndex: 0,
   loading: false,
   visible: false,
+  pinnedPreview: null,
   
-  setQuery: (query) => set({ query }),
+  setQuery: (query) => set({ query, pinnedPreview: null }),
   setResults: (results) => set({ results, selectedIndex: 0 }),
   setSelectedIndex: (index) => set({ selectedIndex: index }),
   setLoading: (loading) => set({ loading }),
   setVisible: (visible) => set({ visible }),
+  setPinnedPreview: (pinnedPreview) => set({ pinnedPreview }),
   
   selectNext: () => {
     const { selectedIndex, results } = get();
@@ -51,5 +56,6 @@ export const useAppStore = create<AppState>((set, get) => ({
     results: [],
     selectedIndex: 0,
     loading: false,
+    pinnedPreview: null,
   }),
 }));
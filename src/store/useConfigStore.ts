@@ -1,10 +1,14 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 
+export type FallbackKind = 'web_search' | 'ai_ask' | 'create_todo' | 'create_snippet';
+
 export interface AppConfig {
   general: {
     language: string;
     clear_on_hide: boolean;
+    prewarm_window?: boolean;
+    restore_session_secs?: number;
     max_results: number;
     startup_on_boot: boolean;
     hotkey: string;
@@ -64,6 +68,11 @@ export interface AppConfig {
     enabled_plugins: string[];
     disabled_plugins: string[];
   };
+  // 无结果备选链
+  fallback?: {
+    chain: Array<{ kind: FallbackKind; enabled: boolean }>;
+    web_search_url: string;
+  };
   advanced: {
     start_on_boot: boolean;
     show_tray_icon: boolean;
@@ -12,6 +12,99 @@ export interface QueryResult {
   preview?: Preview;
   refreshable: boolean;
   group?: string;
+  highlights?: MatchHighlights;
+  badges?: ResultBadge[];
+}
+
+// 结果徽章（如下载来源标记）
+export interface ResultBadge {
+  id: string;
+  label: string;
+  tone: 'info' | 'warning' | 'danger';
+  tooltip?: string;
+}
+
+// 匹配高亮区间（字符下标，左闭右开）
+export interface HighlightSpan {
+  start: number;
+  end: number;
+}
+
+export interface MatchHighlights {
+  title?: HighlightSpan[];
+  subtitle?: HighlightSpan[];
+}
+
+// 轻量结果列表项（query_items 返回，选中时用 get_result_detail 获取详情）
+export interface ResultListItem {
+  id: string;
+  title: string;
+  subtitle: string;
+  icon: WoxImage;
+  score: number;
+  plugin_id: string;
+  group?: string;
+  has_preview: boolean;
+  refreshable: boolean;
+  highlights?: MatchHighlights;
+  badges?: ResultBadge[];
+}
+
+export interface ResultDetail {
+  id: string;
+  plugin_id: string;
+  preview?: Preview;
+  actions: Action[];
+  context_data: any;
+}
+
+// 插件执行动作后的反馈
+export type ExecuteOutcome =
+  | { type: 'hide' }
+  | { type: 'show_message'; message: string }
+  | { type: 'replace_query'; query: string }
+  | { type: 'push_results'; results: QueryResult[] }
+  | { type: 'open_preview'; preview: Preview }
+  | ({ type: 'follow_up' } & FollowUp)
+  | { type: 'file_missing'; path: string; message: string }
+  | ({ type: 'confirm' } & ConfirmationRequest);
+
+// 破坏性动作预演（删除、结束进程等执行前展示）
+export interface DryRun {
+  summary: string;
+  details: { label: string; value: string }[];
+  reversible: boolean;
+}
+
+// 确认请求（确认后以 token 调用 confirm_action 继续执行）
+export interface ConfirmationRequest {
+  token: string;
+  title: string;
+  dry_run: DryRun;
+  expires_in_secs: number;
+}
+
+// 命名热键绑定触发事件（hotkey:binding）
+export interface HotkeyBindingEvent {
+  id: string;
+  action: 'query' | 'workflow';
+  argument: string;
+}
+
+// 会话恢复（隐藏时保存，在恢复时间内重新呼出时返回）
+export interface SessionState {
+  query: string;
+  selected_index: number;
+  scroll_top: number;
+}
+
+// 二级选择（面包屑由后端填充）
+export interface FollowUp {
+  title: string;
+  placeholder?: string;
+  context: any;
+  results: QueryResult[];
+  breadcrumbs: string[];
 }
 
 export interface Action {
@@ -50,6 +143,10 @@ export interface PluginMetadata {
   settings: SettingDefinition[];
   supported_os: string[];
   plugin_type: PluginType;
+  category?: 'search' | 'productivity' | 'developer' | 'system' | 'utilities' | 'other';
+  tags?: string[];
+  homepage?: string;
+  icon_asset?: { url: string; mime: string };
 }
 
 export interface Command {